@@ -0,0 +1,86 @@
+use std::hint::black_box;
+
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use tux_pdf::{
+    document::{PdfDocument, PdfResources},
+    graphics::{
+        LayerType, OperationWriter, PdfObjectType, shapes::PaintedRect, styles::GraphicStyles,
+    },
+    page::{PdfPage, page_sizes::A4},
+    units::UnitType,
+};
+use tux_pdf_low::{content::write_operations_to_vec, types::PdfType};
+
+const NUMBER_OF_RECTANGLES: usize = 5_000;
+
+fn rectangles() -> Vec<PaintedRect> {
+    (0..NUMBER_OF_RECTANGLES)
+        .map(|index| {
+            let x = (index % 50) as f32 * 10f32;
+            let y = (index / 50) as f32 * 8f32;
+            PaintedRect::new(x.pt(), y.pt(), 8f32.pt(), 6f32.pt())
+        })
+        .collect()
+}
+
+fn fill_writer(resources: &PdfResources, rectangles: Vec<PaintedRect>) -> OperationWriter {
+    let mut writer = OperationWriter::with_capacity(rectangles.len() * 4);
+    for rect in rectangles {
+        writer.save_graphics_state();
+        GraphicStyles::default()
+            .write(resources, &mut writer)
+            .unwrap();
+        rect.write(resources, &mut writer).unwrap();
+        writer.restore_graphics_state();
+    }
+    writer
+}
+
+fn operation_writer(c: &mut Criterion) {
+    let resources = PdfResources::default();
+    c.bench_function("operation_writer/build", |b| {
+        b.iter_batched(
+            rectangles,
+            |rectangles| black_box(fill_writer(&resources, rectangles)),
+            BatchSize::LargeInput,
+        )
+    });
+    c.bench_function("operation_writer/encode_presized", |b| {
+        b.iter_batched(
+            || fill_writer(&resources, rectangles()).operations(),
+            |operations| black_box(write_operations_to_vec(operations).unwrap()),
+            BatchSize::LargeInput,
+        )
+    });
+    c.bench_function("operation_writer/encode_unsized", |b| {
+        b.iter_batched(
+            || fill_writer(&resources, rectangles()).operations(),
+            |operations| black_box(operations.write_to_vec().unwrap()),
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+fn graphics_heavy_document(c: &mut Criterion) {
+    c.bench_function("document/graphics_heavy_page", |b| {
+        b.iter_batched(
+            rectangles,
+            |rectangles| {
+                let mut doc = PdfDocument::new("Benchmark");
+                let mut page = PdfPage::new_from_page_size(A4);
+                for rect in rectangles {
+                    page.add_to_layer(rect).unwrap();
+                }
+                doc.add_page(page);
+                let pdf = doc.write_into_pdf_document_writer().unwrap();
+                let mut output = Vec::new();
+                pdf.save(&mut output).unwrap();
+                black_box(output)
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+criterion_group!(benches, operation_writer, graphics_heavy_document);
+criterion_main!(benches);
@@ -1,23 +1,53 @@
+mod assembly;
 pub mod conformance;
+mod extract;
+mod finalize;
+mod hooks;
+mod imposition;
+mod merge;
 mod meta;
 mod resources;
+mod section;
+mod validate;
+mod write_report;
 
-use std::{io::Write, mem};
+use std::{borrow::Cow, io::Write, mem, sync::Arc};
 
 use crate::{
     TuxPdfError, TuxPdfResult,
     document::emoji_rasterizer,
-    graphics::{OperationWriter, PdfObject, PdfObjectType},
+    error::{ErrorContext, ErrorContextExt},
+    graphics::{
+        OperationWriter, PdfObject, PdfObjectType, PdfPosition, TextStyle, color::ColorBits,
+        size::RenderSize,
+    },
     page::PdfPage,
 };
 use ahash::{HashMap, HashMapExt};
+pub use extract::*;
+pub use finalize::FinalizeOptions;
+use hooks::DictionaryHooks;
+pub use hooks::{CatalogDictionaryHook, PageCreatedHook, PageDictionaryHook, PageNumber};
+pub use imposition::*;
+pub use merge::*;
 pub use meta::*;
 pub use resources::*;
+pub use section::*;
+use tracing::{debug_span, field, info_span};
 use tux_pdf_low::{
-    document::PdfDocumentWriter,
-    types::{Dictionary, Object, ObjectId, ReferenceOrObject},
+    document::{IncrementalSave, PdfDocumentWriter, xref::XrefType},
+    types::{
+        DEFAULT_REAL_PRECISION, Dictionary, Object, ObjectId, PdfVersion, ReferenceOrObject,
+        Stream, with_real_precision,
+    },
 };
-use types::{OptionalContentProperties, Page, PagesObject, PdfDirectoryType, Resources};
+use types::{
+    DestinationView, JavascriptAction, OptionalContentProperties, Page, PagesObject,
+    PdfDestination, PdfDirectoryType, Resources,
+};
+pub use validate::*;
+use write_report::{ReportedResource, WriteReportBuilder};
+pub use write_report::{ResourceSize, WriteReport};
 pub mod types;
 pub struct PdfDocument {
     /// Metadata about the document (author, info, XMP metadata, etc.)
@@ -28,7 +58,21 @@ pub struct PdfDocument {
     pub bookmarks: PageAnnotMap,
     /// Page contents
     pages: Vec<PdfPage>,
+    /// Sections and the index of their first page. See [PdfDocument::start_section]
+    sections: Vec<(usize, Section)>,
+    /// See [PdfDocument::on_page_dictionary] and [PdfDocument::on_catalog_dictionary]
+    dictionary_hooks: DictionaryHooks,
+    /// See [PdfDocument::on_page_created]
+    page_created_hooks: Vec<PageCreatedHook>,
+    /// See [PdfDocument::set_max_content_stream_bytes]
+    max_content_stream_bytes: Option<usize>,
+    /// See [PdfDocument::set_real_precision]
+    real_precision: Option<u8>,
+    /// See [PdfDocument::set_finalize_options]
+    finalize_options: FinalizeOptions,
 }
+/// Content streams of pages and layers are split once they are larger than this
+pub const DEFAULT_MAX_CONTENT_STREAM_BYTES: usize = 32 * 1024 * 1024;
 impl AsRef<PdfResources> for PdfDocument {
     fn as_ref(&self) -> &PdfResources {
         &self.resources
@@ -52,12 +96,235 @@ impl PdfDocument {
             resources: PdfResources::default(),
             bookmarks: PageAnnotMap::default(),
             pages: Vec::new(),
+            sections: Vec::new(),
+            dictionary_hooks: DictionaryHooks::default(),
+            page_created_hooks: Vec::new(),
+            max_content_stream_bytes: Some(DEFAULT_MAX_CONTENT_STREAM_BYTES),
+            real_precision: Some(DEFAULT_REAL_PRECISION),
+            finalize_options: FinalizeOptions::default(),
+        }
+    }
+    /// Adds a hook that is called with every page dictionary before it is written
+    ///
+    /// Entries can be added or replaced. Such as `/Group` for a transparency group, `/Tabs` or keys for other tools.
+    /// Nothing is checked. The dictionary must stay a valid page
+    ///
+    /// ```rust
+    /// use tux_pdf::document::PdfDocument;
+    /// use tux_pdf_low::types::Object;
+    /// let mut document = PdfDocument::new("Hooks");
+    /// document.on_page_dictionary(|_page_index, page| {
+    ///     page.set("Tabs", Object::name("S"));
+    /// });
+    /// ```
+    pub fn on_page_dictionary(
+        &mut self,
+        hook: impl Fn(usize, &mut Dictionary) + Send + Sync + 'static,
+    ) {
+        self.dictionary_hooks.pages.push(Arc::new(hook));
+    }
+    /// Adds a hook that is called with the catalog dictionary before it is written. See [Self::on_page_dictionary]
+    pub fn on_catalog_dictionary(
+        &mut self,
+        hook: impl Fn(&mut Dictionary) + Send + Sync + 'static,
+    ) {
+        self.dictionary_hooks.catalog.push(Arc::new(hook));
+    }
+    /// Adds a hook that is called with every page a paginating layout creates. Such as the pages a [Table](crate::layouts::table::Table) continues on
+    ///
+    /// Called before the layout draws on the page. So anything added by the hook is beneath the layout.
+    /// Useful for stamping headers, footers and watermarks without repeating it in every `new_page` function.
+    /// The first page given to a layout is not passed to the hook
+    ///
+    /// ```rust
+    /// use tux_pdf::{
+    ///     document::PdfDocument,
+    ///     graphics::{PdfPosition, TextBlock},
+    ///     units::UnitType,
+    /// };
+    /// let mut document = PdfDocument::new("Hooks");
+    /// document.on_page_created(|page, number| {
+    ///     let footer = TextBlock::from(format!("Page {number}"))
+    ///         .with_position(PdfPosition::new(20f32.pt(), 20f32.pt()));
+    ///     page.contents.push(footer.into());
+    /// });
+    /// ```
+    pub fn on_page_created(
+        &mut self,
+        hook: impl Fn(&mut PdfPage, PageNumber) + Send + Sync + 'static,
+    ) {
+        self.page_created_hooks.push(Arc::new(hook));
+    }
+    /// Calls the [page created hooks](PdfDocument::on_page_created) with a page a layout created
+    ///
+    /// `pending_pages` is the number of pages the layout has that are not added to the document yet.
+    /// It is used to work out the number of the page
+    pub fn page_created(&self, page: &mut PdfPage, pending_pages: usize) {
+        let number = PageNumber(self.pages.len() + pending_pages + 1);
+        for hook in &self.page_created_hooks {
+            hook(page, number);
         }
     }
     pub fn font_map(&mut self) -> &mut PdfFontMap {
         &mut self.resources.fonts
     }
+    /// Sets the font used by text styles that do not set a [font_ref](crate::graphics::TextStyle::font_ref)
+    ///
+    /// See [PdfFontMap::set_default_font]
+    pub fn set_default_font(&mut self, font_ref: FontRef) -> TuxPdfResult<()> {
+        self.resources.fonts.set_default_font(font_ref)
+    }
+    /// Registers a TrueType or OpenType font from its bytes. See [PdfFontMap::register_font_bytes]
+    ///
+    /// ```rust,no_run
+    /// use tux_pdf::document::PdfDocument;
+    /// let mut document = PdfDocument::new("Fonts");
+    /// let roboto = document.register_font_bytes(std::fs::read("Roboto-Regular.ttf").unwrap()).unwrap();
+    /// document.set_default_font(roboto).unwrap();
+    /// ```
+    pub fn register_font_bytes(
+        &mut self,
+        data: impl Into<Cow<'static, [u8]>>,
+    ) -> TuxPdfResult<FontRef> {
+        self.resources.fonts.register_font_bytes(data)
+    }
+    /// The reading direction from the [viewer preferences](ViewerPreferences). Left to right if not set
+    pub fn reading_direction(&self) -> ReadingDirection {
+        self.metadata
+            .catalog_info
+            .viewer_preferences
+            .as_ref()
+            .map(|preferences| preferences.direction)
+            .unwrap_or_default()
+    }
+    /// Sets the reading direction of the document
+    ///
+    /// Tables without their own [direction](crate::layouts::table::TableStyles::direction) follow it
+    pub fn set_reading_direction(&mut self, direction: ReadingDirection) {
+        self.metadata
+            .catalog_info
+            .viewer_preferences
+            .get_or_insert_default()
+            .direction = direction;
+    }
+    /// Sets the version written in the header of the file. Defaults to 1.7
+    ///
+    /// Features are adapted to the version when writing. Below 1.5 a cross reference table is written instead of a stream.
+    /// Features that can not be adapted such as layers cause writing to fail
+    pub fn set_pdf_version(&mut self, version: impl Into<PdfVersion>) -> TuxPdfResult<()> {
+        let version = version.into();
+        if !version.is_published() {
+            return Err(TuxPdfError::UnsupportedPdfVersion(version));
+        }
+        self.metadata.pdf_version = version;
+        Ok(())
+    }
+    /// Splits the content of a page or layer into multiple streams once it is larger than `max_bytes`
+    ///
+    /// Some viewers fail to open pages with very large content streams. Defaults to [DEFAULT_MAX_CONTENT_STREAM_BYTES].
+    /// `None` always writes one stream
+    pub fn set_max_content_stream_bytes(&mut self, max_bytes: Option<usize>) {
+        self.max_content_stream_bytes = max_bytes;
+    }
+    /// Digits written after the decimal point of real numbers in content streams and objects
+    ///
+    /// Defaults to [DEFAULT_REAL_PRECISION]. `None` writes every real with full precision.
+    /// Use [Pt::as_written](crate::units::Pt::as_written) to compare values with numbers read back from the file
+    pub fn set_real_precision(&mut self, precision: Option<u8>) {
+        self.real_precision = precision;
+    }
+    /// Passes that shrink the document once every object is written. Such as removing fonts that were never used
+    ///
+    /// Every pass is off by default
+    pub fn set_finalize_options(&mut self, options: FinalizeOptions) {
+        self.finalize_options = options;
+    }
+    /// Adds a destination that links and actions can target by name
+    ///
+    /// The view is positioned so `position` is at the top left of the window. See [GoToAction](types::GoToAction)
+    pub fn add_named_destination(
+        &mut self,
+        name: impl Into<String>,
+        page: usize,
+        position: PdfPosition,
+    ) {
+        let destination = PdfDestination {
+            page,
+            view: DestinationView::Xyz {
+                left: Some(position.x),
+                top: Some(position.y),
+                zoom: None,
+            },
+        };
+        self.metadata
+            .catalog_info
+            .named_destinations
+            .insert(name.into(), destination);
+    }
+    /// Adds document level JavaScript that runs when the document is opened
+    pub fn add_javascript(&mut self, name: impl Into<String>, script: impl Into<JavascriptAction>) {
+        self.metadata
+            .catalog_info
+            .javascript
+            .insert(name.into(), script.into());
+    }
+    /// Measures the text as it would be rendered with the style. Nothing is added to the document
+    ///
+    /// Text is measured as a single line. `max_width` is not applied
+    pub fn measure_text(
+        &self,
+        text: &str,
+        style: &TextStyle,
+    ) -> Result<TextMetrics, ResourceNotRegistered> {
+        let size = text.render_size(self, style)?;
+        let font_metrics = style.font_ref.metrics(&self.resources.fonts)?;
+        Ok(TextMetrics {
+            width: size.width,
+            height: size.height,
+            ascent: font_metrics.ascent(style.font_size),
+            descent: font_metrics.descent(style.font_size),
+        })
+    }
 
+    /// Registers a soft mask. Apply it to content with a [MaskedGroup](crate::graphics::MaskedGroup)
+    ///
+    /// Soft masks require PDF 1.4 or newer
+    pub fn add_soft_mask(&mut self, mask: SoftMask) -> ExtGStateId {
+        self.resources.ext_g_states.add_ext_g_state(mask.into())
+    }
+    /// Registers a blend mode so it can be set on [GraphicStyles](crate::graphics::GraphicStyles)
+    ///
+    /// Blend modes require PDF 1.4 or newer
+    pub fn register_blend_mode(&mut self, blend_mode: BlendMode) -> BlendMode {
+        self.resources.ext_g_states.register_blend_mode(blend_mode)
+    }
+    /// Registers an opacity so it can be set on [GraphicStyles](crate::graphics::GraphicStyles)
+    ///
+    /// Opacity requires PDF 1.4 or newer
+    pub fn register_opacity(&mut self, opacity: Opacity) -> Opacity {
+        self.resources.ext_g_states.register_opacity(opacity)
+    }
+    /// Reserves an id for a raw object. See [RawObjects::reserve_id]
+    pub fn reserve_object_id(&mut self) -> ObjectId {
+        self.resources.raw.reserve_id()
+    }
+    /// Adds an object that is written to the document as is. See [RawObjects]
+    pub fn add_raw_object(&mut self, object: impl Into<Object>) -> ObjectId {
+        self.resources.raw.add(object)
+    }
+    /// Sets the object of an id returned by [Self::reserve_object_id]
+    pub fn set_raw_object(&mut self, id: ObjectId, object: impl Into<Object>) -> TuxPdfResult<()> {
+        self.resources.raw.set(id, object)
+    }
+    /// Makes a raw object usable by name from [PdfObject::Raw] content. See [RawObjects::add_resource]
+    pub fn add_raw_resource(
+        &mut self,
+        category: impl Into<String>,
+        name: impl Into<String>,
+        id: ObjectId,
+    ) -> TuxPdfResult<()> {
+        self.resources.raw.add_resource(category, name, id)
+    }
     pub fn add_xobject<T>(&mut self, xobject: T) -> XObjectId
     where
         T: Into<XObject>,
@@ -78,28 +345,115 @@ impl PdfDocument {
         document.save(writer)?;
         Ok(())
     }
+    /// Saves the PDF document to an [AsyncWrite](tokio::io::AsyncWrite)
+    ///
+    /// See [PdfDocumentWriter::save_async]
+    #[cfg(feature = "tokio")]
+    pub async fn save_to_async<W>(self, writer: &mut W) -> TuxPdfResult<()>
+    where
+        W: tokio::io::AsyncWrite + Unpin,
+    {
+        let document = self.write_into_pdf_document_writer()?;
+        document.save_async(writer).await?;
+        Ok(())
+    }
     /// Saves the PDF document to a [PdfDocumentWriter]
     ///
     /// This is useful if you want to manipulate the document further before saving it to a file
-    pub fn write_into_pdf_document_writer(mut self) -> TuxPdfResult<PdfDocumentWriter> {
-        // Note to future developers: This function requires a very specific order of operations.
+    ///
+    /// Emits a `write_document` span. With `write_layer`, `write_page`, `write_fonts` and `write_xobjects` debug spans inside of it.
+    /// Enable span close events in tracing-subscriber or use tracing-flame to get the timings
+    pub fn write_into_pdf_document_writer(self) -> TuxPdfResult<PdfDocumentWriter> {
+        self.write_objects().map(|(document, _)| document)
+    }
+    /// Same as [Self::write_into_pdf_document_writer] but also returns a [WriteReport] describing the written objects
+    pub fn write_into_pdf_document_writer_with_report(
+        self,
+    ) -> TuxPdfResult<(PdfDocumentWriter, WriteReport)> {
+        let number_of_pages = self.pages.len();
+        let (document, report) = self.write_objects()?;
+        let report = report.build(&document, number_of_pages)?;
+        Ok((document, report))
+    }
+    fn write_objects(self) -> TuxPdfResult<(PdfDocumentWriter, WriteReportBuilder)> {
+        // Content streams are encoded while the pages are written
+        with_real_precision(self.real_precision, || self.write_objects_inner())
+    }
+    fn write_objects_inner(mut self) -> TuxPdfResult<(PdfDocumentWriter, WriteReportBuilder)> {
+        // Note to future developers: Writing requires a very specific order of operations.
         // When writing pages they require the XObjects and Fonts to still be in the resources map
         // Layers can be immeidately removed from resources as nothing else will access them from the resources map
-
+        let _span = info_span!("write_document", pages = self.pages.len()).entered();
+        let (mut writer, mut report) = self.start_writing()?;
+        let pages = mem::take(&mut self.pages);
+        self.write_pages(pages, 0, &mut writer, &mut report)?;
+        self.finish_writing(writer, report)
+    }
+    /// Writes everything that comes before the pages. Such as the layers
+    fn start_writing(&mut self) -> TuxPdfResult<(DocumentWriter, WriteReportBuilder)> {
+        // Decorators can add text that needs emoji glyphs so they run first
+        self.apply_sections()?;
         // Pre-rasterize emoji glyphs if RasterizeToImage mode is active.
         // Must happen before pages are written so XObjects are available.
         self.prepare_emoji_resources()?;
 
+        let version = self.metadata.pdf_version;
+        if !self.resources.layers.map.is_empty() {
+            require_pdf_version("Layers", PdfVersion::PDF_1_5, version)?;
+        }
+        if !self.resources.ext_g_states.is_empty() {
+            require_pdf_version(
+                "Soft masks, blend modes and opacity",
+                PdfVersion::PDF_1_4,
+                version,
+            )?;
+        }
+        for xobject in self.resources.xobjects.map.values() {
+            let XObject::Image(image) = xobject else {
+                continue;
+            };
+            for data in std::iter::once(&image.image).chain(&image.mask) {
+                if data.bits_per_component == ColorBits::Bit16 {
+                    require_pdf_version("16 bit images", PdfVersion::PDF_1_5, version)?;
+                }
+                if matches!(data.image_filter, Some(ImageFilter::Jbig2 { .. })) {
+                    require_pdf_version("JBIG2 images", PdfVersion::PDF_1_4, version)?;
+                }
+            }
+        }
+
         let mut writer = DocumentWriter::default();
+        writer.document.version = version;
+        writer.document.real_precision = self.real_precision;
+        writer.finalize_options = self.finalize_options;
+        writer.document.trailer.id = self.metadata.document_id.take();
+        writer.document.trailer.id_generation = self.metadata.document_id_generation;
+        writer.document.cross_reference_type = if version.supports_cross_reference_streams() {
+            XrefType::CrossReferenceStream
+        } else {
+            XrefType::CrossReferenceTable
+        };
+        // Raw objects were given their ids when they were added so they are written first
+        let raw_objects = mem::take(&mut self.resources.raw);
+        writer.raw_resources = raw_objects.write(&mut writer)?;
+        let mut report = WriteReportBuilder::default();
         {
-            let info_dict: Dictionary = self.metadata.info.into();
+            let info_dict: Dictionary = self.metadata.info.clone().into();
             let info_dict_id = writer.insert_object(info_dict.into());
             writer.info_dict = Some(info_dict_id);
         }
-        writer.catalog_extras = Some(self.metadata.catalog_info);
+        writer.catalog_extras = Some(mem::take(&mut self.metadata.catalog_info));
+        writer.dictionary_hooks = self.dictionary_hooks.clone();
         // Take the layers from resources and create the layers in the writer
         // The pages should not access the layers after this point so it should be fine to take them and leave the resources empty
         for (layer_id, layer) in std::mem::take(&mut self.resources.layers.map).into_iter() {
+            let layer_span = debug_span!(
+                "write_layer",
+                layer = layer_id.as_str(),
+                operations = layer.operations.len(),
+                content_bytes = field::Empty
+            )
+            .entered();
             let optional_content_group = layer.create_ocg_dictionary();
             let ocg_id = writer.insert_object(optional_content_group.into_dictionary().into());
 
@@ -109,40 +463,101 @@ impl PdfDocument {
                 OperationWriter::with_capacity(2 + layer.operations.len());
 
             operation_writer.start_layer(layer_id.clone());
-            operations_to_content(&self.resources, layer.operations, &mut operation_writer);
+            operations_to_content(&self.resources, layer.operations, &mut operation_writer)
+                .with_context(|| ErrorContext::Layer(layer_id.as_str().to_owned()))?;
             operation_writer.end_section();
 
-            let stream_content = operation_writer.into_stream(Dictionary::default())?;
-            let stream_id = writer.insert_object(stream_content.into());
+            let streams = operation_writer.into_content_streams(self.max_content_stream_bytes)?;
+            layer_span.record(
+                "content_bytes",
+                streams
+                    .iter()
+                    .map(|stream| stream.content.len())
+                    .sum::<usize>(),
+            );
+            let mut stream_ids = Vec::with_capacity(streams.len());
+            for stream in streams {
+                let stream_id = writer.insert_content_stream(stream);
+                report.add(stream_id, ReportedResource::ContentStream);
+                stream_ids.push(stream_id);
+            }
 
             writer
                 .layers
-                .insert(layer_id.clone(), WriterLayer { ocg_id, stream_id });
+                .insert(layer_id.clone(), WriterLayer { ocg_id, stream_ids });
         }
-
-        for page in self.pages {
+        Ok((writer, report))
+    }
+    /// Writes the pages. `first_index` is the index of the first page in the document
+    fn write_pages(
+        &self,
+        pages: impl IntoIterator<Item = PdfPage>,
+        first_index: usize,
+        writer: &mut DocumentWriter,
+        report: &mut WriteReportBuilder,
+    ) -> TuxPdfResult<()> {
+        let has_ext_g_states = !self.resources.ext_g_states.is_empty();
+        let version = self.metadata.pdf_version;
+        for (page_index, page) in (first_index..).zip(pages) {
+            if !page.viewports.is_empty() {
+                require_pdf_version("Viewports", PdfVersion::PDF_1_6, version)
+                    .context(ErrorContext::Page(page_index))?;
+            }
+            if let Some(required) = page
+                .transition
+                .as_ref()
+                .and_then(|transition| transition.style.required_pdf_version())
+            {
+                require_pdf_version("The page transition", required, version)
+                    .context(ErrorContext::Page(page_index))?;
+            }
+            let page_span = debug_span!(
+                "write_page",
+                page = page_index,
+                operations = page.contents.len(),
+                content_bytes = field::Empty
+            )
+            .entered();
             let mut layers = Vec::new();
             for layer_id in page.layers {
                 let layer = writer
                     .layers
                     .get(&layer_id)
-                    .ok_or_else(|| ResourceNotRegistered::LayerId(layer_id.clone()))?;
+                    .ok_or_else(|| ResourceNotRegistered::LayerId(layer_id.clone()))
+                    .context(ErrorContext::Page(page_index))?;
 
-                layers.push((layer_id, *layer));
+                layers.push((layer_id, layer.clone()));
             }
 
             let mut content_ids = Vec::with_capacity(layers.len() + 1);
+            let mut contents = page.contents;
+            // The page contents are written before the layers so the background is beneath everything
+            if let Some(background) = page.background {
+                contents.insert(0, background.into_object(page.media_box));
+            }
             // Check if the page has any content. If it does, write the content to the page
-            if !page.contents.is_empty() {
+            if !contents.is_empty() {
                 // Note: The amount of page ops does != the amount of pdf operations.
                 // This is because this library is abstracting pdf and each "page op" is usually multiple operations
                 let mut operation_writer: OperationWriter =
-                    OperationWriter::with_capacity(page.contents.len());
+                    OperationWriter::with_capacity(contents.len());
 
-                operations_to_content(&self.resources, page.contents, &mut operation_writer);
-                let content_stream = operation_writer.into_stream(Dictionary::default())?;
-                let content_id = writer.insert_object(content_stream.into());
-                content_ids.push(content_id);
+                operations_to_content(&self.resources, contents, &mut operation_writer)
+                    .context(ErrorContext::Page(page_index))?;
+                let streams =
+                    operation_writer.into_content_streams(self.max_content_stream_bytes)?;
+                page_span.record(
+                    "content_bytes",
+                    streams
+                        .iter()
+                        .map(|stream| stream.content.len())
+                        .sum::<usize>(),
+                );
+                for stream in streams {
+                    let content_id = writer.insert_content_stream(stream);
+                    report.add(content_id, ReportedResource::ContentStream);
+                    content_ids.push(content_id);
+                }
             }
 
             let resources_id = if layers.is_empty() {
@@ -151,13 +566,16 @@ impl PdfDocument {
             } else {
                 let mut properties = Dictionary::new();
                 for (layer_id, layer) in layers {
-                    content_ids.push(layer.stream_id);
+                    content_ids.extend(layer.stream_ids);
                     properties.set(layer_id.as_str(), layer.ocg_id);
                 }
                 let resources = Resources {
                     font: Some(ReferenceOrObject::Reference(writer.font_id())),
                     xobject: Some(ReferenceOrObject::Reference(writer.xobjects_id())),
+                    ext_g_state: has_ext_g_states
+                        .then(|| ReferenceOrObject::Reference(writer.ext_g_states_id())),
                     properties: Some(properties),
+                    raw: writer.raw_resources.clone(),
                 };
                 Some(writer.insert_object(resources.into_dictionary().into()))
             };
@@ -171,22 +589,63 @@ impl PdfDocument {
                 bleed_box: page.bleed_box.map(|bb| bb.to_array()),
                 trim_box: page.trim_box.map(|tb| tb.to_array()),
                 rotation: page.rotate.map(Object::from),
+                transition: page.transition,
+                viewports: page.viewports,
             };
 
-            writer.new_page(page.into_dictionary());
+            let mut page = page.into_dictionary();
+            self.dictionary_hooks.apply_to_page(page_index, &mut page);
+            writer.new_page(page);
+        }
+        Ok(())
+    }
+    /// Writes the resources used by the pages and the page tree
+    fn finish_writing(
+        mut self,
+        mut writer: DocumentWriter,
+        mut report: WriteReportBuilder,
+    ) -> TuxPdfResult<(PdfDocumentWriter, WriteReportBuilder)> {
+        // Soft masks contain content that uses the fonts and xobjects. So they are written before the resources are consumed
+        let ext_g_states = mem::take(&mut self.resources.ext_g_states);
+        if !ext_g_states.is_empty() {
+            let _span =
+                debug_span!("write_ext_g_states", states = ext_g_states.map.len()).entered();
+            let states = ext_g_states.dictionary(&self.resources, &mut writer)?;
+            writer.ext_g_states(states);
         }
         // We can consume the rest of the resources as the only parts of the code that needs them now has been converted into pdf operations
         let PdfResources {
             fonts, xobjects, ..
         } = mem::take(&mut self.resources);
-        let fonts = fonts.dictionary(&mut writer);
+        let before_fonts = writer.document.max_object_number();
+        let fonts = {
+            let font_bytes: usize = fonts
+                .map
+                .values()
+                .map(|font| font.font.font_bytes().len())
+                .sum();
+            let _span = debug_span!(
+                "write_fonts",
+                external = fonts.map.len(),
+                builtin = fonts.registered_builtin_fonts.len(),
+                font_bytes
+            )
+            .entered();
+            fonts.dictionary(&mut writer)?
+        };
+        report.add_created_since(before_fonts, &writer.document, ReportedResource::Font);
 
         writer.fonts(fonts);
 
-        let xobjects = xobjects.dictionary(&mut writer)?;
+        let before_xobjects = writer.document.max_object_number();
+        let xobjects = {
+            let _span = debug_span!("write_xobjects", xobjects = xobjects.map.len()).entered();
+            xobjects.dictionary(&mut writer)?
+        };
+        report.add_created_since(before_xobjects, &writer.document, ReportedResource::Image);
         writer.xobjects(xobjects);
 
-        writer.finish()
+        Ok((writer.finish()?, report))
     }
     pub fn create_layer(&mut self, name: &str) -> LayerId {
         self.resources.layers.create_layer(name)
@@ -194,6 +653,10 @@ impl PdfDocument {
     pub fn add_page(&mut self, page: PdfPage) {
         self.pages.push(page);
     }
+    /// The number of pages added with [PdfDocument::add_page]
+    pub fn number_of_pages(&self) -> usize {
+        self.pages.len()
+    }
 
     /// Pre-rasterizes emoji glyphs used in the document when `RasterizeToImage` mode is active.
     ///
@@ -306,7 +769,7 @@ impl PdfDocument {
                         })
                         .unwrap_or(default_font_ref);
 
-                    let FontRef::External(font_id) = font_ref else {
+                    let FontRef::External(font_id) = fonts.resolve(font_ref) else {
                         continue;
                     };
 
@@ -334,24 +797,116 @@ fn operations_to_content(
     resources: &PdfResources,
     operations: Vec<PdfObject>,
     writer: &mut OperationWriter,
-) {
-    for operation in operations {
-        operation.write(resources, writer).unwrap();
+) -> TuxPdfResult<()> {
+    for (index, operation) in operations.into_iter().enumerate() {
+        operation
+            .write(resources, writer)
+            .context(ErrorContext::Object(index))?;
+    }
+    Ok(())
+}
+/// Returns [TuxPdfError::NotAllowedInPdfVersion] if `version` is older than `required`
+fn require_pdf_version(
+    feature: &'static str,
+    required: PdfVersion,
+    version: PdfVersion,
+) -> TuxPdfResult<()> {
+    if version < required {
+        return Err(TuxPdfError::NotAllowedInPdfVersion {
+            feature,
+            required,
+            version,
+        });
+    }
+    Ok(())
+}
+/// Writes the pages of a document to the output as they are added. So only the added pages are in memory
+///
+/// The pages of the document are written first. The resources and the page tree are written by [Self::finish].
+/// Objects are never deduplicated or removed as flushed objects can still reference them
+pub(crate) struct PageStreamWriter<W> {
+    document: PdfDocument,
+    writer: DocumentWriter,
+    report: WriteReportBuilder,
+    save: IncrementalSave<W>,
+    pages: usize,
+}
+impl<W: Write> PageStreamWriter<W> {
+    pub(crate) fn new(mut document: PdfDocument, output: W) -> TuxPdfResult<Self> {
+        let (mut writer, report) =
+            with_real_precision(document.real_precision, || document.start_writing())?;
+        writer.finalize_options = FinalizeOptions::default();
+        let save = writer.document.start_save(output)?;
+        let pages = mem::take(&mut document.pages);
+        let mut stream = Self {
+            document,
+            writer,
+            report,
+            save,
+            pages: 0,
+        };
+        stream.write_pages(pages)?;
+        Ok(stream)
+    }
+    /// The number of pages already written to the output
+    pub(crate) fn pages_written(&self) -> usize {
+        self.pages
+    }
+    /// The document the pages belong to. Resources such as fonts can still be registered
+    pub(crate) fn document(&mut self) -> &mut PdfDocument {
+        &mut self.document
+    }
+    /// Writes the pages to the output and releases them
+    pub(crate) fn write_pages(&mut self, pages: Vec<PdfPage>) -> TuxPdfResult<()> {
+        let number_of_pages = pages.len();
+        let Self {
+            document,
+            writer,
+            report,
+            ..
+        } = self;
+        with_real_precision(document.real_precision, || {
+            document.write_pages(pages, self.pages, writer, report)
+        })?;
+        self.pages += number_of_pages;
+        // The written streams can not be shared anymore
+        self.writer.content_streams.clear();
+        self.save.flush(&mut self.writer.document)?;
+        Ok(())
+    }
+    /// Writes the resources, the page tree and the cross reference section
+    ///
+    /// Returns the number of pages written
+    pub(crate) fn finish(self) -> TuxPdfResult<usize> {
+        let Self {
+            document,
+            writer,
+            report,
+            save,
+            pages,
+        } = self;
+        let (writer, _) = with_real_precision(document.real_precision, || {
+            document.finish_writing(writer, report)
+        })?;
+        save.finish(writer)?;
+        Ok(pages)
     }
 }
 #[derive(Debug, PartialEq, Default, Clone)]
 pub struct PageAnnotMap {
     //pub map: BTreeMap<PageAnnotId, PageAnnotation>,
 }
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub(crate) struct WriterLayer {
     ocg_id: ObjectId,
-    stream_id: ObjectId,
+    /// More than one if the content was split. See [PdfDocument::set_max_content_stream_bytes]
+    stream_ids: Vec<ObjectId>,
 }
 pub(crate) struct DocumentWriter {
     layers: HashMap<LayerId, WriterLayer>,
     fonts: Option<ObjectId>,
     xobjects: Option<ObjectId>,
+    ext_g_states: Option<ObjectId>,
     pages: Vec<ObjectId>,
 
     pages_id: Option<ObjectId>,
@@ -359,6 +914,13 @@ pub(crate) struct DocumentWriter {
     resources_id: Option<ObjectId>,
     info_dict: Option<ObjectId>,
     catalog_extras: Option<CatalogInfo>,
+    dictionary_hooks: DictionaryHooks,
+    /// Entries added to every resources dictionary. See [RawObjects::add_resource]
+    raw_resources: Option<Dictionary>,
+    /// Content streams that have been written. Keyed by the hash of their content
+    content_streams: HashMap<u64, Vec<ObjectId>>,
+    content_hasher: ahash::RandomState,
+    finalize_options: FinalizeOptions,
     document: PdfDocumentWriter,
 }
 impl Default for DocumentWriter {
@@ -367,6 +929,7 @@ impl Default for DocumentWriter {
             layers: HashMap::new(),
             fonts: None,
             xobjects: None,
+            ext_g_states: None,
             document: PdfDocumentWriter::default(),
             pages: Vec::new(),
             pages_id: None,
@@ -374,6 +937,11 @@ impl Default for DocumentWriter {
             uses_shared_resources: false,
             resources_id: None,
             catalog_extras: None,
+            dictionary_hooks: DictionaryHooks::default(),
+            raw_resources: None,
+            content_streams: HashMap::new(),
+            content_hasher: ahash::RandomState::new(),
+            finalize_options: FinalizeOptions::default(),
         }
     }
 }
@@ -386,9 +954,35 @@ impl DocumentWriter {
         let xobjects_id = self.xobjects_id();
         self.document.set_object(xobjects_id, xobjects);
     }
+    pub fn ext_g_states(&mut self, states: Dictionary) {
+        let ext_g_states_id = self.ext_g_states_id();
+        self.document.set_object(ext_g_states_id, states);
+    }
     pub fn insert_object(&mut self, object: Object) -> ObjectId {
         self.document.add_object(object)
     }
+    /// Inserts a content stream
+    ///
+    /// If an identical stream was already written its id is returned instead.
+    /// This keeps documents with many identical pages small
+    pub fn insert_content_stream(&mut self, stream: Stream) -> ObjectId {
+        let hash = self.content_hasher.hash_one(&stream.content);
+        let same_hash = self.content_streams.entry(hash).or_default();
+        let existing = same_hash.iter().find(|id| {
+            self.document
+                .get_object(id)
+                .and_then(Object::as_stream)
+                .is_some_and(|existing| {
+                    existing.content == stream.content && existing.dictionary == stream.dictionary
+                })
+        });
+        if let Some(id) = existing {
+            return *id;
+        }
+        let id = self.document.add_object(stream);
+        same_hash.push(id);
+        id
+    }
     pub fn new_object_id(&mut self) -> ObjectId {
         self.document.next_object_id()
     }
@@ -425,6 +1019,15 @@ impl DocumentWriter {
             xobjects_id
         }
     }
+    pub fn ext_g_states_id(&mut self) -> ObjectId {
+        if let Some(ext_g_states_id) = self.ext_g_states {
+            ext_g_states_id
+        } else {
+            let ext_g_states_id = self.new_object_id();
+            self.ext_g_states = Some(ext_g_states_id);
+            ext_g_states_id
+        }
+    }
     pub fn uses_shared_resources(&mut self) {
         self.uses_shared_resources = true;
     }
@@ -443,6 +1046,7 @@ impl DocumentWriter {
         let Self {
             fonts,
             xobjects,
+            ext_g_states,
             pages,
             pages_id,
             uses_shared_resources,
@@ -451,12 +1055,21 @@ impl DocumentWriter {
             catalog_extras,
             mut document,
             layers,
+            raw_resources,
+            dictionary_hooks,
+            finalize_options,
+            ..
         } = self;
         let pages_id = pages_id.ok_or(TuxPdfError::NoPagesCreated)?;
+        let mut catalog_object = catalog_extras
+            .unwrap_or_default()
+            .create_catalog_object(pages_id, &pages);
         let shared_resources = if uses_shared_resources {
             let resources = Resources {
                 font: fonts.map(ReferenceOrObject::Reference),
                 xobject: xobjects.map(ReferenceOrObject::Reference),
+                ext_g_state: ext_g_states.map(ReferenceOrObject::Reference),
+                raw: raw_resources,
                 ..Default::default()
             };
             if let Some(resources_id) = resources_id {
@@ -477,9 +1090,6 @@ impl DocumentWriter {
             .into_dictionary(),
         );
 
-        let mut catalog_object = catalog_extras
-            .unwrap_or_default()
-            .create_catalog_object(pages_id);
         if !layers.is_empty() {
             let oc_properties = OptionalContentProperties {
                 ocgs: layers.into_values().map(|layer| layer.ocg_id).collect(),
@@ -488,12 +1098,259 @@ impl DocumentWriter {
             catalog_object.oc_properties = Some(oc_properties);
         }
         // Create Catalog object
-        let catalog_id = document.add_object(catalog_object.into_dictionary());
+        let mut catalog = catalog_object.into_dictionary();
+        dictionary_hooks.apply_to_catalog(&mut catalog);
+        let catalog_id = document.add_object(catalog);
         // Point the Root key to the Pages object
         document.trailer.root = Some(catalog_id);
         if let Some(info_dict) = info_dict {
             document.trailer.info = Some(info_dict);
         }
+        let resource_dictionaries: Vec<ObjectId> = [fonts, xobjects, ext_g_states]
+            .into_iter()
+            .flatten()
+            .collect();
+        finalize::finalize(&mut document, &resource_dictionaries, finalize_options)?;
         Ok(document)
     }
 }
+#[cfg(test)]
+mod tests {
+    use image::{DynamicImage, ImageBuffer};
+    use tux_pdf_low::types::{
+        PdfVersion,
+        trailer::{DocumentId, DocumentIdGeneration},
+    };
+
+    use super::PdfDocument;
+    use crate::{
+        TuxPdfError,
+        document::{BuiltinFont, PdfXObjectImage, PdfXObjectImageData},
+        graphics::{LayerType, PdfPosition, TextBlock, TextStyle, shapes::OutlineRect, size::Size},
+        page::{
+            MeasureUnit, PageTransition, PageViewport, PdfPage, RectilinearMeasure, SlideDirection,
+            TransitionStyle, page_sizes::A4,
+        },
+        units::{Px, UnitType},
+    };
+
+    #[test]
+    fn identical_pages_share_content_stream() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Shared Content");
+        let font = doc
+            .resources
+            .fonts
+            .register_builtin_font(BuiltinFont::Helvetica);
+        for content in ["Ticket", "Ticket", "Other"] {
+            let mut page = PdfPage::new_from_page_size(A4);
+            page.add_to_layer(TextBlock {
+                content: content.into(),
+                style: TextStyle {
+                    font_ref: font.clone(),
+                    ..Default::default()
+                },
+                position: PdfPosition::new(20f32.pt(), 20f32.pt()),
+                draw_as_lines: false,
+            })?;
+            doc.add_page(page);
+        }
+        let (_, report) = doc.write_into_pdf_document_writer_with_report()?;
+        assert_eq!(report.number_of_pages, 3);
+        assert_eq!(report.content_streams.objects, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn split_content_streams() -> anyhow::Result<()> {
+        fn document(max_bytes: Option<usize>) -> anyhow::Result<PdfDocument> {
+            let mut doc = PdfDocument::new("Split Content");
+            doc.set_max_content_stream_bytes(max_bytes);
+            let font = doc
+                .resources
+                .fonts
+                .register_builtin_font(BuiltinFont::Helvetica);
+            let mut page = PdfPage::new_from_page_size(A4);
+            for line in 0..10 {
+                page.add_to_layer(TextBlock {
+                    content: format!("Line {line}").into(),
+                    style: TextStyle {
+                        font_ref: font.clone(),
+                        ..Default::default()
+                    },
+                    position: PdfPosition::new(20f32.pt(), (20f32 + line as f32 * 20f32).pt()),
+                    draw_as_lines: false,
+                })?;
+            }
+            doc.add_page(page);
+            Ok(doc)
+        }
+        let (_, report) = document(None)?.write_into_pdf_document_writer_with_report()?;
+        assert_eq!(report.content_streams.objects, 1);
+
+        let (writer, report) = document(Some(64))?.write_into_pdf_document_writer_with_report()?;
+        assert!(report.content_streams.objects > 1);
+        // Content streams are the only streams without a type
+        for (_, object) in writer.objects() {
+            if let Some(stream) = object.as_stream()
+                && stream.dictionary.get("Type").is_none()
+            {
+                assert!(stream.content.len() <= 64);
+            }
+        }
+        Ok(())
+    }
+    #[test]
+    fn real_precision_per_document() -> anyhow::Result<()> {
+        fn content(precision: Option<u8>) -> anyhow::Result<Vec<u8>> {
+            let mut doc = PdfDocument::new("Real Precision");
+            doc.set_real_precision(precision);
+            let font = doc
+                .resources
+                .fonts
+                .register_builtin_font(BuiltinFont::Helvetica);
+            let mut page = PdfPage::new_from_page_size(A4);
+            page.add_to_layer(TextBlock {
+                content: "Precision".into(),
+                style: TextStyle {
+                    font_ref: font,
+                    ..Default::default()
+                },
+                position: PdfPosition::new(20.125f32.pt(), 20f32.pt()),
+                draw_as_lines: false,
+            })?;
+            doc.add_page(page);
+            let writer = doc.write_into_pdf_document_writer()?;
+            assert_eq!(writer.real_precision, precision);
+            Ok(writer.save_to_vec()?)
+        }
+        let contains = |pdf: &[u8], text: &[u8]| pdf.windows(text.len()).any(|w| w == text);
+        let rounded = content(Some(1))?;
+        assert!(contains(&rounded, b"20.1 "));
+        assert!(!contains(&rounded, b"20.125"));
+        // Another document is not affected by the first one
+        assert!(contains(&content(None)?, b"20.125 "));
+        Ok(())
+    }
+    #[test]
+    fn pdf_version() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("PDF 1.4");
+        assert!(doc.set_pdf_version((1, 9)).is_err());
+        assert_eq!(doc.metadata.pdf_version(), PdfVersion::default());
+        doc.set_pdf_version((1, 4))?;
+        assert_eq!(doc.metadata.pdf_version(), PdfVersion::from((1, 4)));
+        doc.add_page(PdfPage::new_from_page_size(A4));
+        let pdf = doc.write_into_pdf_document_writer()?.save_to_vec()?;
+        assert!(pdf.starts_with(b"%PDF-1.4"));
+        // Cross reference streams require 1.5. A table is followed by the trailer keyword
+        assert!(pdf.windows(7).any(|window| window == b"trailer"));
+
+        let mut doc = PdfDocument::new("Layers in PDF 1.4");
+        doc.set_pdf_version((1, 4))?;
+        doc.create_layer("Layer");
+        doc.add_page(PdfPage::new_from_page_size(A4));
+        assert!(matches!(
+            doc.write_into_pdf_document_writer(),
+            Err(TuxPdfError::NotAllowedInPdfVersion { .. })
+        ));
+        Ok(())
+    }
+    /// Writes a document with one page and returns the version the error says is required
+    fn required_version(
+        version: (u8, u8),
+        build: impl FnOnce(&mut PdfDocument, &mut PdfPage),
+    ) -> Option<PdfVersion> {
+        let mut doc = PdfDocument::new("Feature versions");
+        doc.set_pdf_version(version).unwrap();
+        let mut page = PdfPage::new_from_page_size(A4);
+        build(&mut doc, &mut page);
+        doc.add_page(page);
+        let error = doc.write_into_pdf_document_writer().err()?;
+        match error.root_cause() {
+            TuxPdfError::NotAllowedInPdfVersion { required, .. } => Some(*required),
+            other => panic!("Unexpected error {other}"),
+        }
+    }
+    #[test]
+    fn features_require_pdf_version() -> anyhow::Result<()> {
+        let sixteen_bit = |doc: &mut PdfDocument, _: &mut PdfPage| {
+            let luma = ImageBuffer::from_raw(1, 1, vec![0x0102u16]).unwrap();
+            let image =
+                PdfXObjectImage::load_from_dynamic_image(DynamicImage::ImageLuma16(luma)).unwrap();
+            doc.add_xobject(image);
+        };
+        assert_eq!(
+            required_version((1, 4), sixteen_bit),
+            Some(PdfVersion::PDF_1_5)
+        );
+        assert_eq!(required_version((1, 5), sixteen_bit), None);
+
+        let jbig2 = |doc: &mut PdfDocument, _: &mut PdfPage| {
+            doc.add_xobject(PdfXObjectImage {
+                image: PdfXObjectImageData::jbig2(Size::new(Px(8), Px(1)), vec![0], None),
+                mask: None,
+            });
+        };
+        assert_eq!(required_version((1, 3), jbig2), Some(PdfVersion::PDF_1_4));
+        assert_eq!(required_version((1, 4), jbig2), None);
+
+        let viewport = |_: &mut PdfDocument, page: &mut PdfPage| {
+            page.add_viewport(PageViewport::new(
+                OutlineRect {
+                    position: PdfPosition::new(10f32.pt(), 10f32.pt()),
+                    size: Size::new(100f32.pt(), 100f32.pt()),
+                },
+                RectilinearMeasure::scale(100.0, MeasureUnit::Meters),
+            ));
+        };
+        assert_eq!(
+            required_version((1, 5), viewport),
+            Some(PdfVersion::PDF_1_6)
+        );
+        assert_eq!(required_version((1, 6), viewport), None);
+
+        for style in [
+            TransitionStyle::Push(SlideDirection::LeftToRight),
+            TransitionStyle::Cover(SlideDirection::TopToBottom),
+            TransitionStyle::Fade,
+        ] {
+            let transition = |_: &mut PdfDocument, page: &mut PdfPage| {
+                page.set_transition(PageTransition::new(style));
+            };
+            assert_eq!(
+                required_version((1, 4), transition),
+                Some(PdfVersion::PDF_1_5),
+                "{style:?}"
+            );
+            assert_eq!(required_version((1, 5), transition), None, "{style:?}");
+        }
+        // Older transitions are written in any version
+        assert_eq!(
+            required_version((1, 4), |_, page| {
+                page.set_transition(PageTransition::new(TransitionStyle::Dissolve));
+            }),
+            None
+        );
+        Ok(())
+    }
+    #[test]
+    fn document_id() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Document ID");
+        doc.add_page(PdfPage::new_from_page_size(A4));
+        let pdf = doc.write_into_pdf_document_writer()?.save_to_vec()?;
+        assert!(pdf.windows(3).any(|window| window == b"/ID"));
+
+        let id = DocumentId::hash_of(b"invoice-42");
+        let mut doc = PdfDocument::new("Document ID");
+        doc.metadata.set_document_id(id.clone());
+        doc.add_page(PdfPage::new_from_page_size(A4));
+        let writer = doc.write_into_pdf_document_writer()?;
+        assert_eq!(writer.trailer.id, Some(id));
+
+        let mut doc = PdfDocument::new("No Document ID");
+        doc.metadata.document_id_generation = DocumentIdGeneration::None;
+        doc.add_page(PdfPage::new_from_page_size(A4));
+        let pdf = doc.write_into_pdf_document_writer()?.save_to_vec()?;
+        assert!(!pdf.windows(3).any(|window| window == b"/ID"));
+        Ok(())
+    }
+}
@@ -0,0 +1,408 @@
+use std::{cmp::Ordering, collections::BTreeMap, mem};
+
+use crate::{TuxPdfError, TuxPdfResult, page::PdfPage};
+
+use super::{PageLabel, PdfDocument};
+
+impl PdfDocument {
+    /// The pages in the order they will be written
+    pub fn pages(&self) -> &[PdfPage] {
+        &self.pages
+    }
+    /// Mutable access to every page. Pages can be changed but not added or removed
+    pub fn pages_mut(&mut self) -> std::slice::IterMut<'_, PdfPage> {
+        self.pages.iter_mut()
+    }
+    pub fn page(&self, index: usize) -> Option<&PdfPage> {
+        self.pages.get(index)
+    }
+    pub fn page_mut(&mut self, index: usize) -> Option<&mut PdfPage> {
+        self.pages.get_mut(index)
+    }
+    /// Inserts the page before the page at the index. An index equal to the number of pages adds it to the end
+    ///
+    /// Named destinations, page labels and sections keep pointing to the same pages when pages are inserted, removed or moved.
+    /// So a page inserted before the first page of a section is not part of it
+    pub fn insert_page_at(&mut self, index: usize, page: PdfPage) -> TuxPdfResult<()> {
+        if index > self.pages.len() {
+            return Err(self.page_out_of_bounds(index));
+        }
+        self.pages.insert(index, page);
+        self.remap_pages(self.pages.len() - 1, |page| {
+            Some(if page >= index { page + 1 } else { page })
+        });
+        Ok(())
+    }
+    /// Removes the page and returns it. The pages after it move forward
+    ///
+    /// Destinations to the page are removed. Page labels and sections starting at it start at the page after it
+    pub fn remove_page(&mut self, index: usize) -> TuxPdfResult<PdfPage> {
+        self.check_page_index(index)?;
+        let page = self.pages.remove(index);
+        self.remap_pages(self.pages.len() + 1, |page| match page.cmp(&index) {
+            Ordering::Less => Some(page),
+            Ordering::Equal => None,
+            Ordering::Greater => Some(page - 1),
+        });
+        Ok(page)
+    }
+    pub fn swap_pages(&mut self, a: usize, b: usize) -> TuxPdfResult<()> {
+        self.check_page_index(a)?;
+        self.check_page_index(b)?;
+        self.pages.swap(a, b);
+        self.remap_pages(self.pages.len(), |page| {
+            Some(if page == a {
+                b
+            } else if page == b {
+                a
+            } else {
+                page
+            })
+        });
+        Ok(())
+    }
+    /// Moves the page so it ends up at the index `to`. The pages in between shift by one
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), tux_pdf::TuxPdfError> {
+    /// use tux_pdf::{
+    ///     document::PdfDocument,
+    ///     page::{PdfPage, page_sizes::{A4, A5}},
+    /// };
+    /// let mut document = PdfDocument::new("Report");
+    /// // The body is generated first. The cover is added last once the totals are known
+    /// document.add_page(PdfPage::new_from_page_size(A4));
+    /// document.add_page(PdfPage::new_from_page_size(A4));
+    /// document.add_page(PdfPage::new_from_page_size(A5));
+    /// document.move_page(2, 0)?;
+    /// assert_eq!(document.pages()[0].size(), A5);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn move_page(&mut self, from: usize, to: usize) -> TuxPdfResult<()> {
+        self.check_page_index(from)?;
+        self.check_page_index(to)?;
+        let page = self.pages.remove(from);
+        self.pages.insert(to, page);
+        self.remap_pages(self.pages.len(), |page| {
+            Some(if page == from {
+                to
+            } else if from < to && (from + 1..=to).contains(&page) {
+                page - 1
+            } else if to < from && (to..from).contains(&page) {
+                page + 1
+            } else {
+                page
+            })
+        });
+        Ok(())
+    }
+    /// Inserts a copy of the page directly after it. Returns the index of the copy
+    ///
+    /// Layers are shared between the page and its copy
+    pub fn duplicate_page(&mut self, index: usize) -> TuxPdfResult<usize> {
+        self.check_page_index(index)?;
+        let copy = self.pages[index].clone();
+        self.pages.insert(index + 1, copy);
+        self.remap_pages(self.pages.len() - 1, |page| {
+            Some(if page > index { page + 1 } else { page })
+        });
+        Ok(index + 1)
+    }
+    /// Updates everything that refers to a page by its index. `new_index` returns `None` for a removed page
+    ///
+    /// `old_number_of_pages` is the number of pages before they were changed.
+    /// Sections of a removed page start at the page that took its place. Page labels move with their pages
+    pub(super) fn remap_pages(
+        &mut self,
+        old_number_of_pages: usize,
+        new_index: impl Fn(usize) -> Option<usize>,
+    ) {
+        let catalog_info = &mut self.metadata.catalog_info;
+        catalog_info.named_destinations.retain(|_, destination| {
+            new_index(destination.page)
+                .map(|page| destination.page = page)
+                .is_some()
+        });
+        if let Some(destination) = &mut catalog_info.open_destination {
+            match new_index(destination.page) {
+                Some(page) => destination.page = page,
+                None => catalog_info.open_destination = None,
+            }
+        }
+        catalog_info.page_labels = remap_page_labels(
+            mem::take(&mut catalog_info.page_labels),
+            old_number_of_pages,
+            self.pages.len(),
+            &new_index,
+        );
+        for (start, _) in &mut self.sections {
+            *start = new_index(*start).unwrap_or(*start);
+        }
+        self.sections.sort_by_key(|(start, _)| *start);
+        // Like start_section only the last section starting at a page is kept
+        self.sections.reverse();
+        self.sections.dedup_by_key(|(start, _)| *start);
+        self.sections.reverse();
+    }
+    fn check_page_index(&self, index: usize) -> TuxPdfResult<()> {
+        if index >= self.pages.len() {
+            return Err(self.page_out_of_bounds(index));
+        }
+        Ok(())
+    }
+    fn page_out_of_bounds(&self, index: usize) -> TuxPdfError {
+        TuxPdfError::PageIndexOutOfBounds {
+            index,
+            number_of_pages: self.pages.len(),
+        }
+    }
+}
+/// Moves the page labels with their pages
+///
+/// Every page keeps the label it had. A range is split when its pages are no longer next to each other.
+/// Pages that were added continue the range of the page before them.
+/// When more than one page ends up at the same index, such as with [imposition](PdfDocument::impose), the first one decides the label
+fn remap_page_labels(
+    mut page_labels: BTreeMap<usize, PageLabel>,
+    old_number_of_pages: usize,
+    number_of_pages: usize,
+    new_index: impl Fn(usize) -> Option<usize>,
+) -> BTreeMap<usize, PageLabel> {
+    if page_labels.is_empty() {
+        return page_labels;
+    }
+    // Labels for pages that were not added yet keep following the mapping
+    let later_labels = page_labels.split_off(&old_number_of_pages);
+    // Without a label for the first page viewers use the default numbering for the pages before the first label
+    let implicit_first_label = !page_labels.contains_key(&0);
+    page_labels.entry(0).or_default();
+
+    let mut origins = vec![None; number_of_pages];
+    for old_page in 0..old_number_of_pages {
+        if let Some(origin) = new_index(old_page).and_then(|page| origins.get_mut(page))
+            && origin.is_none()
+        {
+            *origin = Some(old_page);
+        }
+    }
+    let mut remapped = BTreeMap::new();
+    // The old page, the start of its range and the new page of the last page with an origin
+    let mut previous: Option<(usize, usize, usize)> = None;
+    for (page, origin) in origins.into_iter().enumerate() {
+        let Some(old_page) = origin else {
+            continue;
+        };
+        let Some((&start, label)) = page_labels.range(..=old_page).next_back() else {
+            continue;
+        };
+        let continues_range =
+            previous.is_some_and(|(previous_old_page, previous_start, previous_page)| {
+                previous_start == start
+                    && previous_old_page < old_page
+                    && (previous_old_page + 1..old_page).all(|between| {
+                        new_index(between).is_none_or(|between| between == previous_page)
+                    })
+            });
+        previous = Some((old_page, start, page));
+        // Pages before the first label are numbered by their position until another range comes before them
+        let before_first_label = implicit_first_label && start == 0 && remapped.is_empty();
+        if continues_range || before_first_label {
+            continue;
+        }
+        // The number of pages in the range before this one that are still in the document
+        let mut pages_before: Vec<usize> = (start..old_page).filter_map(&new_index).collect();
+        pages_before.sort_unstable();
+        pages_before.dedup();
+        let first_number = label.first_number + pages_before.len();
+        // Pages moved back next to the rest of their range do not need a range of their own
+        let continues_last_label = remapped.last_key_value().is_some_and(
+            |(last_start, last_label): (&usize, &PageLabel)| {
+                last_label.style == label.style
+                    && last_label.prefix == label.prefix
+                    && last_label.first_number + (page - last_start) == first_number
+            },
+        );
+        if !continues_last_label {
+            remapped.insert(page, label.clone().with_first_number(first_number));
+        }
+    }
+    for (start, label) in later_labels {
+        remapped.insert(new_index(start).unwrap_or(start), label);
+    }
+    remapped
+}
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::{
+        TuxPdfError,
+        document::{PageLabel, PageNumberStyle, PdfDocument, Section},
+        graphics::{LayerType, PdfPosition, TextBlock},
+        page::{
+            PdfPage,
+            page_sizes::{A3, A4, A5},
+        },
+    };
+
+    fn document() -> PdfDocument {
+        let mut doc = PdfDocument::new("Assembly");
+        for size in [A3, A4, A5] {
+            doc.add_page(PdfPage::new_from_page_size(size));
+        }
+        doc
+    }
+    fn sizes(doc: &PdfDocument) -> Vec<crate::graphics::size::Size> {
+        doc.pages().iter().map(PdfPage::size).collect()
+    }
+
+    #[test]
+    fn reorder_pages() -> anyhow::Result<()> {
+        let mut doc = document();
+        doc.move_page(2, 0)?;
+        assert_eq!(sizes(&doc), [A5, A3, A4]);
+        doc.swap_pages(0, 2)?;
+        assert_eq!(sizes(&doc), [A4, A3, A5]);
+        let removed = doc.remove_page(1)?;
+        assert_eq!(removed.size(), A3);
+        doc.insert_page_at(2, removed)?;
+        assert_eq!(sizes(&doc), [A4, A5, A3]);
+
+        assert!(matches!(
+            doc.remove_page(3),
+            Err(TuxPdfError::PageIndexOutOfBounds {
+                index: 3,
+                number_of_pages: 3
+            })
+        ));
+        assert!(doc.insert_page_at(4, PdfPage::default()).is_err());
+        Ok(())
+    }
+    #[test]
+    fn references_follow_their_pages() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Assembly");
+        doc.add_page(PdfPage::new_from_page_size(A4));
+        doc.start_section(Section::new("Body"));
+        doc.add_page(PdfPage::new_from_page_size(A4));
+        doc.add_page(PdfPage::new_from_page_size(A4));
+        doc.add_named_destination("end", 2, PdfPosition::default());
+        doc.add_named_destination("start", 0, PdfPosition::default());
+        let destination = |doc: &PdfDocument, name: &str| {
+            doc.metadata
+                .catalog_info
+                .named_destinations
+                .get(name)
+                .map(|destination| destination.page)
+        };
+        let section_starts = |doc: &PdfDocument| -> Vec<usize> {
+            doc.sections().iter().map(|(start, _)| *start).collect()
+        };
+
+        // The cover is created last and moved to the front
+        doc.add_page(PdfPage::new_from_page_size(A5));
+        doc.move_page(3, 0)?;
+        assert_eq!(destination(&doc, "end"), Some(3));
+        assert_eq!(destination(&doc, "start"), Some(1));
+        assert_eq!(section_starts(&doc), [2]);
+        assert!(doc.section_of_page(0).is_none());
+
+        doc.insert_page_at(2, PdfPage::new_from_page_size(A3))?;
+        assert_eq!(destination(&doc, "end"), Some(4));
+        assert_eq!(section_starts(&doc), [3]);
+
+        doc.swap_pages(1, 4)?;
+        assert_eq!(destination(&doc, "start"), Some(4));
+        assert_eq!(destination(&doc, "end"), Some(1));
+
+        // Destinations to a removed page are removed. The section starts at the page after it
+        doc.remove_page(3)?;
+        assert_eq!(section_starts(&doc), [3]);
+        doc.remove_page(1)?;
+        assert_eq!(destination(&doc, "end"), None);
+        assert_eq!(destination(&doc, "start"), Some(2));
+        assert_eq!(section_starts(&doc), [2]);
+        Ok(())
+    }
+    #[test]
+    fn labels_move_with_their_pages() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Labels");
+        for _ in 0..5 {
+            doc.add_page(PdfPage::new_from_page_size(A4));
+        }
+        // i ii 1 2 3
+        doc.metadata.catalog_info.page_labels = BTreeMap::from([
+            (0, PageLabel::new(PageNumberStyle::LowerRoman)),
+            (2, PageLabel::default()),
+        ]);
+        let labels = |doc: &PdfDocument| -> Vec<(usize, PageNumberStyle, usize)> {
+            doc.metadata
+                .catalog_info
+                .page_labels
+                .iter()
+                .map(|(start, label)| (*start, label.style, label.first_number))
+                .collect()
+        };
+        // 2 i ii 1 3
+        doc.move_page(3, 0)?;
+        assert_eq!(
+            labels(&doc),
+            [
+                (0, PageNumberStyle::Decimal, 2),
+                (1, PageNumberStyle::LowerRoman, 1),
+                (3, PageNumberStyle::Decimal, 1),
+                (4, PageNumberStyle::Decimal, 3),
+            ]
+        );
+        // i ii 1 2 3
+        doc.move_page(0, 3)?;
+        assert_eq!(
+            labels(&doc),
+            [
+                (0, PageNumberStyle::LowerRoman, 1),
+                (2, PageNumberStyle::Decimal, 1),
+            ]
+        );
+        // Inserted pages continue the range before them. i ii 1 2 3 4
+        doc.insert_page_at(3, PdfPage::new_from_page_size(A4))?;
+        assert_eq!(
+            labels(&doc),
+            [
+                (0, PageNumberStyle::LowerRoman, 1),
+                (2, PageNumberStyle::Decimal, 1),
+            ]
+        );
+        // The range starts at the page after a removed first page. i ii 1 2 3
+        doc.remove_page(2)?;
+        assert_eq!(
+            labels(&doc),
+            [
+                (0, PageNumberStyle::LowerRoman, 1),
+                (2, PageNumberStyle::Decimal, 1),
+            ]
+        );
+        // Pages before the first label are numbered by their position
+        doc.metadata.catalog_info.page_labels =
+            BTreeMap::from([(3, PageLabel::new(PageNumberStyle::UpperRoman))]);
+        doc.swap_pages(0, 1)?;
+        assert_eq!(labels(&doc), [(3, PageNumberStyle::UpperRoman, 1)]);
+        Ok(())
+    }
+    #[test]
+    fn duplicate_and_edit_pages() -> anyhow::Result<()> {
+        let mut doc = document();
+        doc.page_mut(0)
+            .expect("First page")
+            .add_to_layer(TextBlock::from("Original"))?;
+        let copy = doc.duplicate_page(0)?;
+        assert_eq!(copy, 1);
+        assert_eq!(doc.number_of_pages(), 4);
+        assert_eq!(doc.page(1), doc.page(0));
+
+        for page in doc.pages_mut() {
+            page.contents.clear();
+        }
+        assert!(doc.pages().iter().all(|page| page.contents.is_empty()));
+        Ok(())
+    }
+}
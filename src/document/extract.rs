@@ -0,0 +1,193 @@
+use crate::{
+    graphics::{PathText, PdfObject, PdfPosition, TextBlock, TextStyle},
+    units::Pt,
+};
+
+use super::{ObjectLocation, PdfDocument};
+
+/// The text of one page. Created by [PdfDocument::extract_text]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageText {
+    /// Zero based page index
+    pub page: usize,
+    /// Text blocks in the order they are drawn. Page contents first then the layers of the page
+    pub blocks: Vec<ExtractedText>,
+}
+impl PageText {
+    /// All text of the page. Blocks are separated by a blank line
+    pub fn text(&self) -> String {
+        self.blocks
+            .iter()
+            .map(ExtractedText::text)
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+/// The text of a [TextBlock] or a [PathText]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedText {
+    /// The lines of the block before they are wrapped to the max width
+    pub lines: Vec<String>,
+    pub position: PdfPosition,
+    pub style: TextStyle,
+    /// The object the block is or is contained in
+    pub location: ObjectLocation,
+    /// False if the block is inside of a [TransformedGroup](crate::graphics::TransformedGroup).
+    /// Then the position is not in page space
+    pub in_page_space: bool,
+}
+impl ExtractedText {
+    fn new(block: &TextBlock, location: ObjectLocation, in_page_space: bool) -> Self {
+        let lines = block
+            .content
+            .iter()
+            .map(|line| line.items.iter().map(|item| item.text.as_str()).collect())
+            .collect();
+        Self {
+            lines,
+            position: block.position,
+            style: block.style.clone(),
+            location,
+            in_page_space,
+        }
+    }
+    /// The position is the start of the path
+    fn from_path_text(text: &PathText, location: ObjectLocation, in_page_space: bool) -> Self {
+        Self {
+            lines: vec![text.text.clone()],
+            position: text.path.point_at(Pt::default()).0,
+            style: text.style.clone(),
+            location,
+            in_page_space,
+        }
+    }
+    /// The lines joined with new lines
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+}
+impl PdfDocument {
+    /// Collects the text of every page without writing the document
+    ///
+    /// Useful for indexing generated documents. Text inside of clipped and transformed groups is included
+    pub fn extract_text(&self) -> Vec<PageText> {
+        self.pages
+            .iter()
+            .enumerate()
+            .map(|(page_index, page)| {
+                let mut blocks = Vec::new();
+                for (index, object) in page.contents.iter().enumerate() {
+                    let location = ObjectLocation::Page {
+                        page: page_index,
+                        object: index,
+                    };
+                    collect_text(object, &location, true, &mut blocks);
+                }
+                for layer_id in &page.layers {
+                    let Some(layer) = self.resources.layers.map.get(layer_id) else {
+                        continue;
+                    };
+                    for (index, object) in layer.operations.iter().enumerate() {
+                        let location = ObjectLocation::Layer {
+                            layer: layer_id.clone(),
+                            object: index,
+                        };
+                        collect_text(object, &location, true, &mut blocks);
+                    }
+                }
+                PageText {
+                    page: page_index,
+                    blocks,
+                }
+            })
+            .collect()
+    }
+}
+fn collect_text(
+    object: &PdfObject,
+    location: &ObjectLocation,
+    in_page_space: bool,
+    blocks: &mut Vec<ExtractedText>,
+) {
+    match object {
+        PdfObject::TextBlock(block) => {
+            blocks.push(ExtractedText::new(block, location.clone(), in_page_space))
+        }
+        PdfObject::PathText(text) => blocks.push(ExtractedText::from_path_text(
+            text,
+            location.clone(),
+            in_page_space,
+        )),
+        PdfObject::Clipped(group) => {
+            for object in &group.contents {
+                collect_text(object, location, in_page_space, blocks);
+            }
+        }
+        PdfObject::Masked(group) => {
+            for object in &group.contents {
+                collect_text(object, location, in_page_space, blocks);
+            }
+        }
+        PdfObject::Transformed(group) => {
+            for object in &group.contents {
+                collect_text(object, location, false, blocks);
+            }
+        }
+        PdfObject::NewLine
+        | PdfObject::Graphics(_)
+        | PdfObject::Styles(_)
+        | PdfObject::Image(_)
+        | PdfObject::Raw(_) => {}
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::{
+        document::{ObjectLocation, PdfDocument},
+        graphics::{
+            LayerType, PdfPosition, TextBlock, TextBlockContent, TransformedGroup,
+            primitives::ctm::CurTransMat,
+        },
+        page::{PdfPage, page_sizes::A4},
+        units::UnitType,
+    };
+
+    #[test]
+    fn extracts_text_per_page() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Extract");
+        let mut page = PdfPage::new_from_page_size(A4);
+        page.add_to_layer(
+            TextBlock::from(
+                TextBlockContent::default()
+                    .add_line("First line")
+                    .add_line("Second line"),
+            )
+            .with_position(PdfPosition::new(10f32.pt(), 20f32.pt())),
+        )?;
+        page.add_to_layer(
+            TransformedGroup::new(vec![CurTransMat::Identity])
+                .with_contents(vec![TextBlock::from("Moved").into()]),
+        )?;
+        doc.add_page(page);
+        doc.add_page(PdfPage::new_from_page_size(A4));
+
+        let pages = doc.extract_text();
+        assert_eq!(pages.len(), 2);
+        let first = &pages[0];
+        assert_eq!(first.blocks.len(), 2);
+        assert_eq!(first.blocks[0].lines, ["First line", "Second line"]);
+        assert_eq!(
+            first.blocks[0].position,
+            PdfPosition::new(10f32.pt(), 20f32.pt())
+        );
+        assert!(first.blocks[0].in_page_space);
+        assert_eq!(
+            first.blocks[1].location,
+            ObjectLocation::Page { page: 0, object: 1 }
+        );
+        assert!(!first.blocks[1].in_page_space);
+        assert_eq!(first.text(), "First line\nSecond line\n\nMoved");
+        assert!(pages[1].blocks.is_empty());
+        Ok(())
+    }
+}
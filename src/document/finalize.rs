@@ -0,0 +1,446 @@
+use ahash::{HashMap, HashMapExt, HashSet, HashSetExt};
+use tux_pdf_low::{
+    document::PdfDocumentWriter,
+    types::{Dictionary, Object, ObjectId, PdfObjectType},
+};
+
+use crate::TuxPdfResult;
+
+/// Dictionary types that are never merged with an identical object. Their id is what identifies them
+const DISTINCT_TYPES: [&[u8]; 6] = [
+    b"Catalog",
+    b"Pages",
+    b"Page",
+    b"Annot",
+    b"OCG",
+    b"StructElem",
+];
+/// Passes that shrink the written document. Every pass is off by default
+///
+/// See [PdfDocument::set_finalize_options](crate::document::PdfDocument::set_finalize_options)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FinalizeOptions {
+    /// Removes fonts, XObjects and graphics states that no content uses.
+    /// Then removes objects that can not be reached from the catalog or the info dictionary
+    ///
+    /// Resources are kept if a content stream uses a filter other than `FlateDecode`
+    pub remove_unused: bool,
+    /// Merges identical objects into one
+    pub deduplicate: bool,
+}
+impl FinalizeOptions {
+    /// Every pass enabled
+    pub fn all() -> Self {
+        Self {
+            remove_unused: true,
+            deduplicate: true,
+        }
+    }
+}
+/// Shrinks the written document
+///
+/// 1. Entries of `resource_dictionaries` whose name is not used by any content are removed. Such as fonts that were registered but never used
+/// 2. Objects that can not be reached from the catalog or the info dictionary are removed
+/// 3. Identical objects are merged into one
+pub(crate) fn finalize(
+    document: &mut PdfDocumentWriter,
+    resource_dictionaries: &[ObjectId],
+    options: FinalizeOptions,
+) -> TuxPdfResult<()> {
+    if options.remove_unused {
+        loop {
+            remove_unreferenced(document);
+            // Removed resources can make more objects unreferenced
+            if !remove_unused_resources(document, resource_dictionaries) {
+                break;
+            }
+        }
+    }
+    if options.deduplicate {
+        deduplicate(document)?;
+    }
+    Ok(())
+}
+fn remove_unreferenced(document: &mut PdfDocumentWriter) {
+    let mut reachable = HashSet::new();
+    let mut pending: Vec<ObjectId> = [document.trailer.root, document.trailer.info]
+        .into_iter()
+        .flatten()
+        .collect();
+    while let Some(id) = pending.pop() {
+        if !reachable.insert(id) {
+            continue;
+        }
+        if let Some(object) = document.get_object(&id) {
+            collect_references(object, &mut pending);
+        }
+    }
+    let unreachable: Vec<ObjectId> = document
+        .objects()
+        .map(|(id, _)| *id)
+        .filter(|id| !reachable.contains(id))
+        .collect();
+    for id in unreachable {
+        document.remove_object(&id);
+    }
+}
+fn collect_references(object: &Object, references: &mut Vec<ObjectId>) {
+    match object {
+        Object::Reference(id) => references.push(*id),
+        Object::Array(items) => {
+            for item in items {
+                collect_references(item, references);
+            }
+        }
+        Object::Dictionary(dictionary) => collect_dictionary_references(dictionary, references),
+        Object::Stream(stream) => collect_dictionary_references(&stream.dictionary, references),
+        _ => {}
+    }
+}
+fn collect_dictionary_references(dictionary: &Dictionary, references: &mut Vec<ObjectId>) {
+    for (_, value) in dictionary.iter() {
+        collect_references(value, references);
+    }
+}
+/// Returns true if any entry was removed
+fn remove_unused_resources(
+    document: &mut PdfDocumentWriter,
+    resource_dictionaries: &[ObjectId],
+) -> bool {
+    let Some(used) = collect_used_names(document) else {
+        return false;
+    };
+    let mut removed_any = false;
+    for id in resource_dictionaries {
+        let Some(Object::Dictionary(dictionary)) = document.get_object_mut(id) else {
+            continue;
+        };
+        let unused: Vec<_> = dictionary
+            .iter()
+            .map(|(name, _)| name.clone())
+            .filter(|name| !used.contains(name.as_slice()))
+            .collect();
+        for name in unused {
+            dictionary.remove(&name);
+            removed_any = true;
+        }
+    }
+    removed_any
+}
+/// Names used by content streams and by strings such as the default appearance of form fields
+///
+/// `None` if a content stream can not be decoded
+fn collect_used_names(document: &PdfDocumentWriter) -> Option<HashSet<Vec<u8>>> {
+    let mut used = HashSet::new();
+    let mut content_streams = Vec::new();
+    for (id, object) in document.objects() {
+        collect_string_names(object, &mut used);
+        match object {
+            Object::Dictionary(dictionary) if has_name(dictionary, "Type", b"Page") => {
+                if let Some(contents) = dictionary.get("Contents") {
+                    collect_references(contents, &mut content_streams);
+                }
+            }
+            Object::Dictionary(dictionary) if has_name(dictionary, "Subtype", b"Type3") => {
+                if let Some(char_procs) = dictionary.get("CharProcs") {
+                    let char_procs = match char_procs {
+                        Object::Reference(id) => document.get_object(id),
+                        char_procs => Some(char_procs),
+                    };
+                    if let Some(Object::Dictionary(char_procs)) = char_procs {
+                        collect_dictionary_references(char_procs, &mut content_streams);
+                    }
+                }
+            }
+            // Form XObjects and tiling patterns
+            Object::Stream(stream)
+                if has_name(&stream.dictionary, "Subtype", b"Form")
+                    || stream.dictionary.get("PatternType").is_some() =>
+            {
+                content_streams.push(*id);
+            }
+            _ => {}
+        }
+    }
+    for id in content_streams {
+        if let Some(Object::Stream(stream)) = document.get_object(&id) {
+            collect_content_names(&stream.decoded_content()?, &mut used);
+        }
+    }
+    Some(used)
+}
+fn has_name(dictionary: &Dictionary, key: &str, name: &[u8]) -> bool {
+    dictionary
+        .get(key)
+        .and_then(Object::as_name)
+        .is_some_and(|value| value.as_slice() == name)
+}
+fn collect_string_names(object: &Object, used: &mut HashSet<Vec<u8>>) {
+    match object {
+        Object::String(string) => collect_content_names(string.as_slice(), used),
+        Object::Array(items) => {
+            for item in items {
+                collect_string_names(item, used);
+            }
+        }
+        Object::Dictionary(dictionary) => {
+            for (_, value) in dictionary.iter() {
+                collect_string_names(value, used);
+            }
+        }
+        Object::Stream(stream) => {
+            for (_, value) in stream.dictionary.iter() {
+                collect_string_names(value, used);
+            }
+        }
+        _ => {}
+    }
+}
+/// Finds every `/Name` token. Anything that looks like a name counts so a resource is never removed by mistake
+fn collect_content_names(content: &[u8], used: &mut HashSet<Vec<u8>>) {
+    let is_end = |byte: &u8| byte.is_ascii_whitespace() || b"()<>[]{}/%".contains(byte);
+    let mut rest = content;
+    while let Some(start) = rest.iter().position(|byte| *byte == b'/') {
+        rest = &rest[start + 1..];
+        let length = rest.iter().position(is_end).unwrap_or(rest.len());
+        used.insert(decode_name(&rest[..length]));
+        rest = &rest[length..];
+    }
+}
+/// Decodes the `#xx` escapes of a name
+fn decode_name(name: &[u8]) -> Vec<u8> {
+    let mut decoded = Vec::with_capacity(name.len());
+    let mut index = 0;
+    while index < name.len() {
+        let escaped = (name[index] == b'#')
+            .then(|| name.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(name[index]);
+                index += 1;
+            }
+        }
+    }
+    decoded
+}
+fn can_merge(object: &Object) -> bool {
+    let Some(dictionary) = object.as_dictionary_or_stream_dictionary() else {
+        return true;
+    };
+    match dictionary.dictionary_type() {
+        Ok(Some(name)) => !DISTINCT_TYPES.contains(&name.as_slice()),
+        _ => true,
+    }
+}
+/// Merges identical objects. Repeats until none are left as merging can make the objects referencing them identical
+fn deduplicate(document: &mut PdfDocumentWriter) -> TuxPdfResult<()> {
+    let hasher = ahash::RandomState::new();
+    let mut buffer = Vec::new();
+    loop {
+        let mut by_hash: HashMap<u64, Vec<ObjectId>> = HashMap::new();
+        let mut replacements: HashMap<ObjectId, ObjectId> = HashMap::new();
+        for (id, object) in document.objects() {
+            if !can_merge(object) {
+                continue;
+            }
+            buffer.clear();
+            object.encode_borrowed(&mut buffer)?;
+            let same_hash = by_hash.entry(hasher.hash_one(&buffer)).or_default();
+            let existing = same_hash
+                .iter()
+                .find(|existing| document.get_object(existing) == Some(object));
+            if let Some(existing) = existing {
+                replacements.insert(*id, *existing);
+            } else {
+                same_hash.push(*id);
+            }
+        }
+        if replacements.is_empty() {
+            return Ok(());
+        }
+        for id in replacements.keys() {
+            document.remove_object(id);
+        }
+        for (_, object) in document.objects_mut() {
+            replace_references(object, &replacements);
+        }
+        let trailer = &mut document.trailer;
+        for id in [&mut trailer.root, &mut trailer.info].into_iter().flatten() {
+            if let Some(replacement) = replacements.get(&*id) {
+                *id = *replacement;
+            }
+        }
+    }
+}
+fn replace_references(object: &mut Object, replacements: &HashMap<ObjectId, ObjectId>) {
+    match object {
+        Object::Reference(id) => {
+            if let Some(replacement) = replacements.get(&*id) {
+                *id = *replacement;
+            }
+        }
+        Object::Array(items) => {
+            for item in items {
+                replace_references(item, replacements);
+            }
+        }
+        Object::Dictionary(dictionary) => {
+            for (_, value) in dictionary.iter_mut() {
+                replace_references(value, replacements);
+            }
+        }
+        Object::Stream(stream) => {
+            for (_, value) in stream.dictionary.iter_mut() {
+                replace_references(value, replacements);
+            }
+        }
+        _ => {}
+    }
+}
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use ahash::{HashSet, HashSetExt};
+    use flate2::{Compression, write::ZlibEncoder};
+    use tux_pdf_low::{
+        dictionary,
+        document::PdfDocumentWriter,
+        types::{Dictionary, Object, Stream},
+    };
+
+    use super::{FinalizeOptions, collect_content_names, finalize};
+    use crate::{
+        document::{BuiltinFont, PdfDocument},
+        graphics::{LayerType, PdfPosition, TextBlock, TextStyle},
+        page::{PdfPage, page_sizes::A4},
+        units::UnitType,
+    };
+
+    #[test]
+    fn content_names() {
+        let mut used = HashSet::new();
+        collect_content_names(b"BT /F1 12 Tf ET /Im#200 Do/GS1 gs", &mut used);
+        assert!(used.contains(b"F1".as_slice()));
+        assert!(used.contains(b"Im 0".as_slice()));
+        assert!(used.contains(b"GS1".as_slice()));
+        assert_eq!(used.len(), 3);
+    }
+    #[test]
+    fn unused_and_duplicate_objects_are_removed() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Finalize");
+        doc.set_finalize_options(FinalizeOptions::all());
+        let helvetica = doc
+            .resources
+            .fonts
+            .register_builtin_font(BuiltinFont::Helvetica);
+        doc.resources
+            .fonts
+            .register_builtin_font(BuiltinFont::Courier);
+        let mut shared = Dictionary::new();
+        shared.set("Value", 1);
+        let first = doc.add_raw_object(shared.clone());
+        let second = doc.add_raw_object(shared);
+        let unreferenced = doc.add_raw_object(Dictionary::new());
+        doc.add_raw_resource("Pattern", "P1", first)?;
+        doc.add_raw_resource("Pattern", "P2", second)?;
+
+        let mut page = PdfPage::new_from_page_size(A4);
+        page.add_to_layer(TextBlock {
+            content: "Hello".into(),
+            style: TextStyle {
+                font_ref: helvetica,
+                ..Default::default()
+            },
+            position: PdfPosition::new(20f32.pt(), 20f32.pt()),
+            draw_as_lines: false,
+        })?;
+        doc.add_page(page);
+        let writer = doc.write_into_pdf_document_writer()?;
+
+        let base_fonts: Vec<_> = writer
+            .objects()
+            .filter_map(|(_, object)| object.as_dictionary_or_stream_dictionary())
+            .filter_map(|dictionary| dictionary.get("BaseFont"))
+            .collect();
+        assert_eq!(base_fonts, vec![&Object::name("Helvetica")]);
+
+        assert!(writer.get_object(&unreferenced).is_none());
+        assert!(writer.get_object(&first).is_some());
+        assert!(writer.get_object(&second).is_none());
+        Ok(())
+    }
+    #[test]
+    fn nothing_is_removed_by_default() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Finalize");
+        doc.resources
+            .fonts
+            .register_builtin_font(BuiltinFont::Courier);
+        let unreferenced = doc.add_raw_object(Dictionary::new());
+        doc.add_page(PdfPage::new_from_page_size(A4));
+        let writer = doc.write_into_pdf_document_writer()?;
+        assert!(writer.get_object(&unreferenced).is_some());
+        let courier = writer
+            .objects()
+            .filter_map(|(_, object)| object.as_dictionary())
+            .any(|dictionary| dictionary.get("BaseFont") == Some(&Object::name("Courier")));
+        assert!(courier);
+        Ok(())
+    }
+    #[test]
+    fn names_in_compressed_forms_are_used() -> anyhow::Result<()> {
+        fn compress(content: &[u8]) -> anyhow::Result<Vec<u8>> {
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(content)?;
+            Ok(encoder.finish()?)
+        }
+        let mut writer = PdfDocumentWriter::default();
+        let fonts = writer.add_object(dictionary! {
+            "F1" => Dictionary::new(),
+            "F2" => Dictionary::new(),
+            "F3" => Dictionary::new()
+        });
+        let form = writer.add_object(Stream::new(
+            dictionary! {
+                "Type" => Object::name("XObject"),
+                "Subtype" => Object::name("Form"),
+                "Filter" => Object::name("FlateDecode")
+            },
+            compress(b"BT /F2 12 Tf (Form) Tj ET")?,
+        ));
+        let contents = writer.add_object(Stream::new(
+            Dictionary::new(),
+            b"/Fm1 Do BT /F1 12 Tf (Page) Tj ET".to_vec(),
+        ));
+        // The font file is not a content stream. Its bytes are not names
+        let font_file = writer.add_object(Stream::new(Dictionary::new(), b"/F3".to_vec()));
+        let page = writer.add_object(dictionary! {
+            "Type" => Object::name("Page"),
+            "Contents" => contents,
+            "Resources" => dictionary! {
+                "Font" => fonts,
+                "XObject" => dictionary! { "Fm1" => form }
+            },
+            "FontFile" => font_file
+        });
+        writer.trailer.root = Some(writer.add_object(dictionary! {
+            "Type" => Object::name("Catalog"),
+            "Page" => page
+        }));
+        finalize(&mut writer, &[fonts], FinalizeOptions::all())?;
+        let Some(Object::Dictionary(fonts)) = writer.get_object(&fonts) else {
+            panic!("The fonts are kept");
+        };
+        assert!(fonts.get("F1").is_some());
+        assert!(fonts.get("F2").is_some());
+        assert!(fonts.get("F3").is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use std::{fmt::Display, sync::Arc};
+
+use tux_pdf_low::types::Dictionary;
+
+use crate::page::PdfPage;
+
+/// Called with the index of the page and its dictionary. See [PdfDocument::on_page_dictionary](super::PdfDocument::on_page_dictionary)
+pub type PageDictionaryHook = Arc<dyn Fn(usize, &mut Dictionary) + Send + Sync>;
+/// Called with the catalog dictionary. See [PdfDocument::on_catalog_dictionary](super::PdfDocument::on_catalog_dictionary)
+pub type CatalogDictionaryHook = Arc<dyn Fn(&mut Dictionary) + Send + Sync>;
+/// Called with every page a paginating layout creates. See [PdfDocument::on_page_created](super::PdfDocument::on_page_created)
+pub type PageCreatedHook = Arc<dyn Fn(&mut PdfPage, PageNumber) + Send + Sync>;
+/// The number a page has in the document. Starts at 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PageNumber(pub usize);
+impl PageNumber {
+    /// The index of the page. Starts at 0
+    pub fn index(&self) -> usize {
+        self.0 - 1
+    }
+}
+impl Display for PageNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+/// Hooks that can change dictionaries before they are written. Called in the order they were added
+#[derive(Clone, Default)]
+pub(crate) struct DictionaryHooks {
+    pub(crate) pages: Vec<PageDictionaryHook>,
+    pub(crate) catalog: Vec<CatalogDictionaryHook>,
+}
+impl DictionaryHooks {
+    pub(crate) fn apply_to_page(&self, page_index: usize, page: &mut Dictionary) {
+        for hook in &self.pages {
+            hook(page_index, page);
+        }
+    }
+    pub(crate) fn apply_to_catalog(&self, catalog: &mut Dictionary) {
+        for hook in &self.catalog {
+            hook(catalog);
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use tux_pdf_low::types::{Dictionary, Object, ObjectId};
+
+    use crate::{
+        document::PdfDocument,
+        page::{PdfPage, page_sizes::A4},
+    };
+
+    #[test]
+    fn hooks_change_dictionaries() -> anyhow::Result<()> {
+        let mut document = PdfDocument::new("Hooks");
+        document.add_page(PdfPage::new_from_page_size(A4));
+        document.add_page(PdfPage::new_from_page_size(A4));
+        document.on_page_dictionary(|page_index, page| {
+            page.set("Tabs", Object::name("S"));
+            if page_index == 1 {
+                page.set("Rotate", 90);
+            }
+        });
+        document.on_catalog_dictionary(|catalog| {
+            catalog.set("PageLayout", Object::name("TwoColumnLeft"));
+        });
+        let writer = document.write_into_pdf_document_writer()?;
+
+        let dictionary = |id: ObjectId| {
+            writer
+                .get_object(&id)
+                .and_then(Object::as_dictionary_or_stream_dictionary)
+                .cloned()
+                .unwrap_or_default()
+        };
+        let catalog = dictionary(writer.trailer.root.expect("The catalog was written"));
+        assert_eq!(
+            catalog.get("PageLayout"),
+            Some(&Object::name("TwoColumnLeft"))
+        );
+        let pages = dictionary(*catalog.get("Pages").and_then(Object::as_reference).unwrap());
+        let Some(Object::Array(kids)) = pages.get("Kids") else {
+            panic!("The pages object has no kids");
+        };
+        let pages: Vec<Dictionary> = kids
+            .iter()
+            .filter_map(Object::as_reference)
+            .copied()
+            .map(dictionary)
+            .collect();
+        assert_eq!(pages.len(), 2);
+        assert!(pages.iter().all(|page| page.get("Tabs").is_some()));
+        assert_eq!(pages[0].get("Rotate"), None);
+        assert_eq!(pages[1].get("Rotate"), Some(&Object::from(90)));
+        Ok(())
+    }
+}
@@ -0,0 +1,808 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    mem,
+};
+
+use tracing::warn;
+
+use crate::{
+    document::{IdType, Layer, LayerId, types::DestinationView},
+    graphics::{
+        GraphicStyles, GraphicsGroup, PdfPosition, TransformedGroup,
+        color::{Cmyk, Color},
+        primitives::{PaintMode, PathBuilder, StraightLine, ctm::CurTransMat},
+        shapes::OutlineRect,
+        size::Size,
+    },
+    page::{Measure, PageRotation, PdfPage},
+    units::{Pt, UnitType},
+};
+
+use super::PdfDocument;
+
+/// How many pages are placed on each sheet by [PdfDocument::impose]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NUp {
+    /// Two pages side by side
+    TwoUp,
+    /// Two columns and two rows
+    FourUp,
+    Grid {
+        columns: usize,
+        rows: usize,
+    },
+}
+impl NUp {
+    /// Number of columns and rows on each sheet
+    pub fn grid(&self) -> (usize, usize) {
+        match self {
+            NUp::TwoUp => (2, 1),
+            NUp::FourUp => (2, 2),
+            NUp::Grid { columns, rows } => ((*columns).max(1), (*rows).max(1)),
+        }
+    }
+    pub fn pages_per_sheet(&self) -> usize {
+        let (columns, rows) = self.grid();
+        columns * rows
+    }
+}
+impl PdfDocument {
+    /// Places multiple pages on each sheet. Run after all content has been added
+    ///
+    /// Each cell of the sheet is the size of the first page.
+    /// Pages with a different size are scaled down to fit their cell and centered.
+    /// Pages are placed left to right then top to bottom.
+    /// The trim box of each sheet is the area of its cells. So [Self::add_print_marks] marks where to cut.
+    ///
+    /// Layer content is shared by every page using the layer so it can not be moved into each cell.
+    /// Instead each sheet gets its own copy of the layers its pages use, with the same name and the content moved into the cells.
+    /// The original layers are removed.
+    ///
+    /// The background of each page is drawn in its cell.
+    ///
+    /// Rotated pages are drawn rotated in their cell and the size of a cell is the size of the first page as it is displayed.
+    /// Viewports move with their page. Geospatial viewports of rotated pages are dropped with a warning.
+    ///
+    /// Named destinations, page labels and sections move to the sheet their page was placed on.
+    /// Destinations that fit the page fit its cell instead. Destinations on rotated pages always fit the cell
+    pub fn impose(&mut self, layout: NUp) {
+        let Some(first_page) = self.pages.first() else {
+            return;
+        };
+        let cell = displayed_size(first_page);
+        let (columns, rows) = layout.grid();
+        let sheet_size = Size::new(cell.width * columns as f32, cell.height * rows as f32);
+
+        let mut imposed_layers = BTreeSet::new();
+        let number_of_pages = self.pages.len();
+        // The scale, offset and area of each page on its sheet
+        let mut placements = Vec::with_capacity(number_of_pages);
+        let mut pages = mem::take(&mut self.pages).into_iter().peekable();
+        while pages.peek().is_some() {
+            let mut sheet = PdfPage::new_from_page_size(sheet_size).with_trim_box(
+                OutlineRect::new_from_bottom_left(PdfPosition::<Pt>::default(), sheet_size),
+            );
+            // Original layer to the copy on this sheet
+            let mut sheet_layers: BTreeMap<LayerId, LayerId> = BTreeMap::new();
+            for (index, mut page) in pages.by_ref().take(layout.pages_per_sheet()).enumerate() {
+                let column = index % columns;
+                let row = index / columns;
+                let rotation = page.rotation();
+                let page_size = displayed_size(&page);
+                let scale = if page_size.width.0 > 0f32 && page_size.height.0 > 0f32 {
+                    (cell.width.0 / page_size.width.0)
+                        .min(cell.height.0 / page_size.height.0)
+                        .min(1f32)
+                } else {
+                    1f32
+                };
+                // Bottom left corner of the page as it is displayed
+                let x = cell.width * column as f32 + (cell.width - page_size.width * scale) / 2f32;
+                let y = sheet_size.height - cell.height * (row + 1) as f32
+                    + (cell.height - page_size.height * scale) / 2f32;
+                let (media_x, media_y) = (page.media_box.position.x.0, page.media_box.position.y.0);
+                let [a, b, c, d, e, f] = rotation_matrix(rotation, page.size());
+                let matrix = [
+                    a * scale,
+                    b * scale,
+                    c * scale,
+                    d * scale,
+                    (e - a * media_x - c * media_y) * scale + x.0,
+                    (f - b * media_x - d * media_y) * scale + y.0,
+                ];
+                let transform = vec![CurTransMat::Raw(matrix)];
+                let offset = PdfPosition::new(Pt(matrix[4]), Pt(matrix[5]));
+                placements.push((
+                    scale,
+                    offset,
+                    OutlineRect::new_from_bottom_left(
+                        PdfPosition::new(x, y),
+                        Size::new(page_size.width * scale, page_size.height * scale),
+                    ),
+                    rotation,
+                ));
+                for mut viewport in mem::take(&mut page.viewports) {
+                    if let Measure::Rectilinear(measure) = &mut viewport.measure {
+                        measure.units_per_point /= scale;
+                    } else if rotation != PageRotation::None {
+                        warn!(
+                            ?viewport.name,
+                            "Geospatial viewports of rotated pages can not be imposed and are dropped"
+                        );
+                        continue;
+                    }
+                    viewport.bbox = transform_rect(matrix, viewport.bbox);
+                    sheet.viewports.push(viewport);
+                }
+                // Drawn beneath the rest of the page in its cell. The sheet has no background of its own
+                if let Some(background) = page.background.take() {
+                    page.contents
+                        .insert(0, background.into_object(page.media_box));
+                }
+                page.transform(transform.clone());
+                sheet.contents.append(&mut page.contents);
+                for layer_id in page.layers {
+                    let layers = &mut self.resources.layers;
+                    let Some(layer) = layers.get_layer(&layer_id) else {
+                        // Left for the writer to report
+                        if !sheet.layers.contains(&layer_id) {
+                            sheet.layers.push(layer_id);
+                        }
+                        continue;
+                    };
+                    let contents = TransformedGroup::new(transform.clone())
+                        .with_contents(layer.operations.clone());
+                    let sheet_layer_id = match sheet_layers.get(&layer_id) {
+                        Some(id) => id.clone(),
+                        None => {
+                            let sheet_layer = Layer {
+                                operations: Vec::new(),
+                                ..layer.clone()
+                            };
+                            let id = LayerId::new_random();
+                            layers.map.insert(id.clone(), sheet_layer);
+                            sheet.layers.push(id.clone());
+                            sheet_layers.insert(layer_id.clone(), id.clone());
+                            id
+                        }
+                    };
+                    if let Some(sheet_layer) = layers.get_layer_mut(&sheet_layer_id) {
+                        sheet_layer.operations.push(contents.into());
+                    }
+                    imposed_layers.insert(layer_id);
+                }
+            }
+            self.pages.push(sheet);
+        }
+        for layer_id in imposed_layers {
+            self.resources.layers.map.remove(&layer_id);
+        }
+        let catalog_info = &mut self.metadata.catalog_info;
+        for destination in catalog_info
+            .named_destinations
+            .values_mut()
+            .chain(&mut catalog_info.open_destination)
+        {
+            if let Some((scale, offset, area, rotation)) = placements.get(destination.page) {
+                let view = if *rotation == PageRotation::None {
+                    destination.view
+                } else {
+                    DestinationView::Fit
+                };
+                destination.view = view.placed(*scale, *offset, *area);
+            }
+        }
+        let pages_per_sheet = layout.pages_per_sheet();
+        self.remap_pages(number_of_pages, |page| Some(page / pages_per_sheet));
+    }
+    /// Reorders the pages for a saddle stitched booklet. See [booklet_order]
+    ///
+    /// Blank pages the size of the first page are added to the end to make the page count a multiple of 4.
+    /// Follow with [Self::impose] using [NUp::TwoUp] to place the pages on sheets.
+    /// Named destinations, page labels and sections move with their pages
+    pub fn order_for_booklet(&mut self) {
+        let Some(first_page) = self.pages.first() else {
+            return;
+        };
+        let size = first_page.size();
+        let mut pages: Vec<Option<PdfPage>> =
+            mem::take(&mut self.pages).into_iter().map(Some).collect();
+        let number_of_pages = pages.len();
+        let order = booklet_order(number_of_pages);
+        for index in &order {
+            let page = pages
+                .get_mut(*index)
+                .and_then(Option::take)
+                .unwrap_or_else(|| PdfPage::new_from_page_size(size));
+            self.pages.push(page);
+        }
+        self.remap_pages(number_of_pages, |page| {
+            order.iter().position(|index| *index == page)
+        });
+    }
+    /// Reorders the pages for a booklet and places two pages on each side of a sheet
+    pub fn impose_booklet(&mut self) {
+        self.order_for_booklet();
+        self.impose(NUp::TwoUp);
+    }
+    /// Draws the print marks on every page. See [PdfPage::add_print_marks]
+    ///
+    /// Run after [Self::impose] so the marks are not scaled with the page
+    pub fn add_print_marks(&mut self, marks: PrintMarks) {
+        for page in &mut self.pages {
+            page.add_print_marks(&marks);
+        }
+    }
+}
+/// Page order for a saddle stitched booklet
+///
+/// `page_count` is rounded up to a multiple of 4. Indices past the end of the document are blank pages.
+///
+/// Every pair of indices is one side of a sheet from left to right.
+/// The front of the first sheet holds the last and first page, the back holds the second and second to last page.
+pub fn booklet_order(page_count: usize) -> Vec<usize> {
+    let padded = page_count.div_ceil(4) * 4;
+    let mut order = Vec::with_capacity(padded);
+    for sheet in 0..padded / 4 {
+        let front = sheet * 2;
+        order.extend([padded - 1 - front, front, front + 1, padded - 2 - front]);
+    }
+    order
+}
+/// Marks drawn outside of the trim box by [PdfDocument::add_print_marks]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrintMarks {
+    /// Lines at the corners of the trim box
+    pub crop_marks: bool,
+    /// Lines at the corners of the bleed box
+    pub bleed_marks: bool,
+    /// Circles with a cross at the middle of each side
+    pub registration_marks: bool,
+    /// Length of each mark
+    pub length: Pt,
+    /// Distance between the bleed box, or trim box if there is no bleed box, and the marks
+    pub offset: Pt,
+    pub line_width: Pt,
+    /// Defaults to registration black. 100% of every CMYK ink so the mark shows on every plate
+    pub color: Color,
+}
+impl Default for PrintMarks {
+    fn default() -> Self {
+        Self {
+            crop_marks: true,
+            bleed_marks: false,
+            registration_marks: false,
+            length: 18f32.pt(),
+            offset: 6f32.pt(),
+            line_width: 0.25f32.pt(),
+            color: Color::Cmyk(Cmyk {
+                c: 1f32,
+                m: 1f32,
+                y: 1f32,
+                k: 1f32,
+                icc_profile: None,
+            }),
+        }
+    }
+}
+impl PrintMarks {
+    pub fn with_crop_marks(mut self, crop_marks: bool) -> Self {
+        self.crop_marks = crop_marks;
+        self
+    }
+    pub fn with_bleed_marks(mut self, bleed_marks: bool) -> Self {
+        self.bleed_marks = bleed_marks;
+        self
+    }
+    pub fn with_registration_marks(mut self, registration_marks: bool) -> Self {
+        self.registration_marks = registration_marks;
+        self
+    }
+    pub fn with_length(mut self, length: Pt) -> Self {
+        self.length = length;
+        self
+    }
+    pub fn with_offset(mut self, offset: Pt) -> Self {
+        self.offset = offset;
+        self
+    }
+    pub fn with_line_width(mut self, line_width: Pt) -> Self {
+        self.line_width = line_width;
+        self
+    }
+    pub fn with_color(mut self, color: impl Into<Color>) -> Self {
+        self.color = color.into();
+        self
+    }
+}
+impl PdfPage {
+    /// Draws the print marks outside of the trim box
+    ///
+    /// Does nothing if the page has no trim box.
+    /// The media box is grown to fit the marks if they would be outside of it
+    pub fn add_print_marks(&mut self, marks: &PrintMarks) {
+        let Some(trim_box) = self.trim_box else {
+            return;
+        };
+        let outer = self.bleed_box.unwrap_or(trim_box);
+        let start = marks.offset;
+        let end = marks.offset + marks.length;
+        let mut group = GraphicsGroup::new().with_styles(GraphicStyles {
+            line_width: Some(marks.line_width),
+            outline_color: Some(marks.color.clone()),
+            ..Default::default()
+        });
+        let mut corner_marks = |rect: &OutlineRect| {
+            let (left, bottom) = (rect.position.x, rect.position.y);
+            let (right, top) = (left + rect.size.width, bottom + rect.size.height);
+            let (outer_left, outer_bottom) = (outer.position.x, outer.position.y);
+            let outer_right = outer_left + outer.size.width;
+            let outer_top = outer_bottom + outer.size.height;
+            for y in [bottom, top] {
+                group.add_item(line((outer_left - end, y), (outer_left - start, y)));
+                group.add_item(line((outer_right + start, y), (outer_right + end, y)));
+            }
+            for x in [left, right] {
+                group.add_item(line((x, outer_bottom - end), (x, outer_bottom - start)));
+                group.add_item(line((x, outer_top + start), (x, outer_top + end)));
+            }
+        };
+        if marks.crop_marks {
+            corner_marks(&trim_box);
+        }
+        if marks.bleed_marks
+            && let Some(bleed_box) = self.bleed_box
+        {
+            corner_marks(&bleed_box);
+        }
+        if marks.registration_marks {
+            let center = start + marks.length / 2f32;
+            let radius = marks.length / 4f32;
+            let middle_x = outer.position.x + outer.size.width / 2f32;
+            let middle_y = outer.position.y + outer.size.height / 2f32;
+            let centers = [
+                PdfPosition::new(middle_x, outer.position.y - center),
+                PdfPosition::new(middle_x, outer.position.y + outer.size.height + center),
+                PdfPosition::new(outer.position.x - center, middle_y),
+                PdfPosition::new(outer.position.x + outer.size.width + center, middle_y),
+            ];
+            let half = marks.length / 2f32;
+            for point in centers {
+                group.add_item(
+                    PathBuilder::new()
+                        .arc_to(point, radius, 0f32, 360f32)
+                        .close()
+                        .move_to((point.x - half, point.y))
+                        .line_to((point.x + half, point.y))
+                        .move_to((point.x, point.y - half))
+                        .line_to((point.x, point.y + half))
+                        .with_mode(PaintMode::Stroke)
+                        .build(),
+                );
+            }
+        }
+        if !group.items.is_empty() {
+            let marks_area = OutlineRect::new_from_bottom_left(
+                (outer.position.x - end, outer.position.y - end),
+                Size::new(
+                    outer.size.width + end * 2f32,
+                    outer.size.height + end * 2f32,
+                ),
+            );
+            self.media_box = self.media_box.union(&marks_area);
+            self.contents.push(group.into());
+        }
+    }
+}
+/// The size of the page as it is displayed
+fn displayed_size(page: &PdfPage) -> Size {
+    let size = page.size();
+    match page.rotation() {
+        PageRotation::Clockwise90 | PageRotation::Clockwise270 => {
+            Size::new(size.height, size.width)
+        }
+        PageRotation::None | PageRotation::Rotate180 => size,
+    }
+}
+/// Maps the unrotated page, with its bottom left corner at the origin, onto the page as it is displayed
+fn rotation_matrix(rotation: PageRotation, size: Size) -> [f32; 6] {
+    let (width, height) = (size.width.0, size.height.0);
+    match rotation {
+        PageRotation::None => [1f32, 0f32, 0f32, 1f32, 0f32, 0f32],
+        PageRotation::Clockwise90 => [0f32, -1f32, 1f32, 0f32, 0f32, width],
+        PageRotation::Rotate180 => [-1f32, 0f32, 0f32, -1f32, width, height],
+        PageRotation::Clockwise270 => [0f32, 1f32, -1f32, 0f32, height, 0f32],
+    }
+}
+/// The bounding box of the rectangle after it is transformed by `matrix`
+fn transform_rect(matrix: [f32; 6], rect: OutlineRect) -> OutlineRect {
+    let [a, b, c, d, e, f] = matrix;
+    let lower_left = rect.position;
+    let upper_right = rect.upper_right();
+    let corners = [
+        (lower_left.x.0, lower_left.y.0),
+        (lower_left.x.0, upper_right.y.0),
+        (upper_right.x.0, lower_left.y.0),
+        (upper_right.x.0, upper_right.y.0),
+    ]
+    .map(|(x, y)| (a * x + c * y + e, b * x + d * y + f));
+    let (mut left, mut bottom) = corners[0];
+    let (mut right, mut top) = corners[0];
+    for (x, y) in corners {
+        left = left.min(x);
+        right = right.max(x);
+        bottom = bottom.min(y);
+        top = top.max(y);
+    }
+    OutlineRect::new_from_bottom_left(
+        PdfPosition::new(Pt(left), Pt(bottom)),
+        Size::new(Pt(right - left), Pt(top - bottom)),
+    )
+}
+fn line(start: (Pt, Pt), end: (Pt, Pt)) -> StraightLine {
+    StraightLine {
+        start: start.into(),
+        points: vec![end.into()],
+        is_closed: false,
+    }
+}
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{NUp, PrintMarks, booklet_order};
+    use crate::{
+        document::{
+            PageLabel, PageNumberStyle, PdfDocument, Section,
+            types::{DestinationView, PdfDestination},
+        },
+        graphics::{
+            GraphicItems, LayerType, PdfObject, PdfPosition,
+            color::{BLUE_RGB, RED_RGB},
+            primitives::ctm::CurTransMat,
+            shapes::OutlineRect,
+            size::Size,
+        },
+        page::{
+            GeospatialMeasure, Measure, MeasureUnit, PageBackground, PageRotation, PageViewport,
+            PdfPage, RectilinearMeasure,
+        },
+        units::{Pt, UnitType},
+    };
+
+    #[test]
+    fn two_up() -> anyhow::Result<()> {
+        let page_size = Size::new(420f32.pt(), 595f32.pt());
+        let mut doc = PdfDocument::new("Imposition");
+        for _ in 0..3 {
+            let mut page = PdfPage::new_from_page_size(page_size);
+            page.add_to_layer(OutlineRect::new_from_bottom_left(
+                PdfPosition::<Pt>::default(),
+                page_size,
+            ))?;
+            doc.add_page(page);
+        }
+        doc.impose(NUp::TwoUp);
+        assert_eq!(doc.pages.len(), 2);
+        assert_eq!(doc.pages[0].size(), Size::new(840f32.pt(), 595f32.pt()));
+        assert_eq!(doc.pages[0].contents.len(), 2);
+        assert_eq!(doc.pages[1].contents.len(), 1);
+        let PdfObject::Transformed(second) = &doc.pages[0].contents[1] else {
+            panic!("Expected transformed content");
+        };
+        assert_eq!(
+            second.transform,
+            vec![CurTransMat::Raw([1f32, 0f32, 0f32, 1f32, 420f32, 0f32])]
+        );
+        Ok(())
+    }
+    #[test]
+    fn references_move_to_their_sheet() -> anyhow::Result<()> {
+        let page_size = Size::new(420f32.pt(), 595f32.pt());
+        let mut doc = PdfDocument::new("Imposition");
+        doc.metadata.catalog_info.page_labels = BTreeMap::from([
+            (0, PageLabel::new(PageNumberStyle::LowerRoman)),
+            (2, PageLabel::default()),
+        ]);
+        for index in 0..5 {
+            if index == 2 {
+                doc.start_section(Section::new("Body"));
+            }
+            doc.add_page(PdfPage::new_from_page_size(page_size));
+        }
+        doc.add_named_destination("fourth", 3, PdfPosition::new(10f32.pt(), 20f32.pt()));
+        doc.metadata
+            .catalog_info
+            .named_destinations
+            .insert("third".to_owned(), PdfDestination::fit_page(2));
+
+        doc.impose(NUp::TwoUp);
+        let destinations = &doc.metadata.catalog_info.named_destinations;
+        assert_eq!(
+            destinations["fourth"],
+            PdfDestination {
+                page: 1,
+                view: DestinationView::Xyz {
+                    left: Some(430f32.pt()),
+                    top: Some(20f32.pt()),
+                    zoom: None,
+                },
+            }
+        );
+        // The left cell of the second sheet
+        assert_eq!(
+            destinations["third"].view,
+            DestinationView::FitR {
+                left: 0f32.pt(),
+                bottom: 0f32.pt(),
+                right: 420f32.pt(),
+                top: 595f32.pt(),
+            }
+        );
+        assert_eq!(destinations["third"].page, 1);
+        let labels: Vec<(usize, PageNumberStyle, usize)> = doc
+            .metadata
+            .catalog_info
+            .page_labels
+            .iter()
+            .map(|(start, label)| (*start, label.style, label.first_number))
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                (0, PageNumberStyle::LowerRoman, 1),
+                (1, PageNumberStyle::Decimal, 1)
+            ]
+        );
+        let section_starts: Vec<usize> = doc.sections().iter().map(|(start, _)| *start).collect();
+        assert_eq!(section_starts, [1]);
+        Ok(())
+    }
+    #[test]
+    fn layers_are_moved_with_their_pages() -> anyhow::Result<()> {
+        let page_size = Size::new(420f32.pt(), 595f32.pt());
+        let mut doc = PdfDocument::new("Imposition");
+        let layer_id = doc.create_layer("Watermark");
+        doc.resources
+            .layers
+            .get_layer_mut(&layer_id)
+            .unwrap()
+            .add_to_layer(OutlineRect::new_from_bottom_left(
+                PdfPosition::<Pt>::default(),
+                page_size,
+            ))?;
+        for _ in 0..3 {
+            let mut page = PdfPage::new_from_page_size(page_size);
+            page.add_layer(layer_id.clone());
+            doc.add_page(page);
+        }
+        doc.impose(NUp::TwoUp);
+        assert!(doc.resources.layers.get_layer(&layer_id).is_none());
+        assert_eq!(doc.resources.layers.map.len(), 2);
+
+        let [sheet_layer] = doc.pages[0].layers.as_slice() else {
+            panic!("Expected one layer on the first sheet");
+        };
+        let layer = doc.resources.layers.get_layer(sheet_layer).unwrap();
+        assert_eq!(layer.name, "Watermark");
+        let transforms: Vec<_> = layer
+            .operations
+            .iter()
+            .map(|object| match object {
+                PdfObject::Transformed(group) => group.transform.clone(),
+                other => panic!("Expected transformed layer content, got {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            transforms,
+            vec![
+                vec![CurTransMat::Raw([1f32, 0f32, 0f32, 1f32, 0f32, 0f32])],
+                vec![CurTransMat::Raw([1f32, 0f32, 0f32, 1f32, 420f32, 0f32])],
+            ]
+        );
+        assert_eq!(doc.pages[1].layers.len(), 1);
+        assert_ne!(&doc.pages[1].layers[0], sheet_layer);
+
+        let mut pdf = Vec::new();
+        doc.save_to(&mut pdf)?;
+        Ok(())
+    }
+    #[test]
+    fn backgrounds_are_moved_with_their_pages() -> anyhow::Result<()> {
+        let page_size = Size::new(100f32.pt(), 150f32.pt());
+        let mut doc = PdfDocument::new("Imposed Backgrounds");
+        for color in [RED_RGB, BLUE_RGB] {
+            doc.add_page(PdfPage::new_from_page_size(page_size).with_background(color));
+        }
+        doc.impose_booklet();
+        assert_eq!(doc.pages.len(), 2);
+        let expected = |color| {
+            PageBackground::from(color).into_object(OutlineRect::new_from_bottom_left(
+                PdfPosition::<Pt>::default(),
+                page_size,
+            ))
+        };
+        // The first page is on the right of the front. The blank page on the left has no content
+        let [PdfObject::Transformed(first)] = doc.pages[0].contents.as_slice() else {
+            panic!("Expected the first page on the front of the sheet");
+        };
+        assert_eq!(
+            first.transform,
+            vec![CurTransMat::Raw([1f32, 0f32, 0f32, 1f32, 100f32, 0f32])]
+        );
+        assert_eq!(first.contents, vec![expected(RED_RGB)]);
+        let [PdfObject::Transformed(second)] = doc.pages[1].contents.as_slice() else {
+            panic!("Expected the second page on the back of the sheet");
+        };
+        assert_eq!(
+            second.transform,
+            vec![CurTransMat::Raw([1f32, 0f32, 0f32, 1f32, 0f32, 0f32])]
+        );
+        assert_eq!(second.contents, vec![expected(BLUE_RGB)]);
+        assert!(doc.pages.iter().all(|sheet| sheet.background.is_none()));
+        Ok(())
+    }
+    #[test]
+    fn rotated_pages_and_viewports() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Imposed Rotation");
+        // Displayed as 150 by 100
+        let mut rotated = PdfPage::new_from_page_size(Size::new(100f32.pt(), 150f32.pt()))
+            .with_rotation(PageRotation::Clockwise90)
+            .with_viewport(PageViewport::new(
+                OutlineRect::new_from_bottom_left(
+                    (10f32.pt(), 20f32.pt()),
+                    Size::new(30f32.pt(), 40f32.pt()),
+                ),
+                RectilinearMeasure::scale(100.0, MeasureUnit::Meters),
+            ))
+            .with_viewport(PageViewport::new(
+                OutlineRect::new_from_bottom_left(
+                    PdfPosition::<Pt>::default(),
+                    Size::new(100f32.pt(), 150f32.pt()),
+                ),
+                GeospatialMeasure::new(1.0, 0.0, 1.0, 0.0),
+            ));
+        rotated.add_to_layer(OutlineRect::new_from_bottom_left(
+            PdfPosition::<Pt>::default(),
+            Size::new(100f32.pt(), 150f32.pt()),
+        ))?;
+        doc.add_page(rotated);
+        // Scaled down by half to fit its cell
+        let large = PdfPage::new_from_page_size(Size::new(300f32.pt(), 200f32.pt())).with_viewport(
+            PageViewport::new(
+                OutlineRect::new_from_bottom_left(
+                    PdfPosition::<Pt>::default(),
+                    Size::new(100f32.pt(), 100f32.pt()),
+                ),
+                RectilinearMeasure::scale(100.0, MeasureUnit::Meters),
+            ),
+        );
+        doc.add_page(large);
+        doc.add_named_destination("rotated", 0, PdfPosition::new(10f32.pt(), 20f32.pt()));
+
+        doc.impose(NUp::TwoUp);
+        let sheet = &doc.pages[0];
+        assert_eq!(sheet.size(), Size::new(300f32.pt(), 100f32.pt()));
+        assert_eq!(sheet.rotate, None);
+        let Some(PdfObject::Transformed(first)) = sheet.contents.first() else {
+            panic!("Expected the rotated page");
+        };
+        assert_eq!(
+            first.transform,
+            vec![CurTransMat::Raw([0f32, -1f32, 1f32, 0f32, 0f32, 100f32])]
+        );
+
+        // The geospatial viewport of the rotated page is dropped
+        let viewports: Vec<_> = sheet
+            .viewports
+            .iter()
+            .map(|viewport| {
+                let Measure::Rectilinear(measure) = &viewport.measure else {
+                    panic!("Expected only rectilinear viewports");
+                };
+                (viewport.bbox, measure.units_per_point)
+            })
+            .collect();
+        let units_per_point = RectilinearMeasure::scale(100.0, MeasureUnit::Meters).units_per_point;
+        assert_eq!(
+            viewports,
+            vec![
+                (
+                    OutlineRect::new_from_bottom_left(
+                        (20f32.pt(), 60f32.pt()),
+                        Size::new(40f32.pt(), 30f32.pt())
+                    ),
+                    units_per_point
+                ),
+                (
+                    OutlineRect::new_from_bottom_left(
+                        (150f32.pt(), 0f32.pt()),
+                        Size::new(50f32.pt(), 50f32.pt())
+                    ),
+                    units_per_point * 2f32
+                ),
+            ]
+        );
+        assert_eq!(
+            doc.metadata.catalog_info.named_destinations["rotated"].view,
+            DestinationView::FitR {
+                left: 0f32.pt(),
+                bottom: 0f32.pt(),
+                right: 150f32.pt(),
+                top: 100f32.pt(),
+            }
+        );
+        Ok(())
+    }
+    #[test]
+    fn booklet_page_order() {
+        assert_eq!(booklet_order(8), vec![7, 0, 1, 6, 5, 2, 3, 4]);
+        assert_eq!(booklet_order(3), vec![3, 0, 1, 2]);
+        assert!(booklet_order(0).is_empty());
+
+        let mut doc = PdfDocument::new("Booklet");
+        for _ in 0..3 {
+            doc.add_page(PdfPage::new_from_page_size(Size::new(
+                100f32.pt(),
+                150f32.pt(),
+            )));
+        }
+        doc.add_named_destination("last", 2, PdfPosition::default());
+        doc.order_for_booklet();
+        assert_eq!(doc.pages.len(), 4);
+        assert_eq!(doc.metadata.catalog_info.named_destinations["last"].page, 3);
+    }
+    #[test]
+    fn crop_marks_outside_trim_box() {
+        let mut page = PdfPage::new_from_page_size(Size::new(200f32.pt(), 200f32.pt()))
+            .with_trim_box(OutlineRect::new_from_bottom_left(
+                PdfPosition::new(50f32.pt(), 50f32.pt()),
+                Size::new(100f32.pt(), 100f32.pt()),
+            ));
+        page.add_print_marks(&PrintMarks::default());
+        let Some(PdfObject::Graphics(GraphicItems::Group(group))) = page.contents.first() else {
+            panic!("Expected a graphics group");
+        };
+        // Two marks at each corner
+        assert_eq!(group.items.len(), 8);
+        let GraphicItems::StraightLine(first) = &group.items[0] else {
+            panic!("Expected a line");
+        };
+        assert_eq!(first.start, PdfPosition::new(26f32.pt(), 50f32.pt()));
+        assert_eq!(first.points, vec![PdfPosition::new(44f32.pt(), 50f32.pt())]);
+        // The media box already fits the marks
+        assert_eq!(page.size(), Size::new(200f32.pt(), 200f32.pt()));
+    }
+    #[test]
+    fn print_marks_after_impose() {
+        let page_size = Size::new(100f32.pt(), 150f32.pt());
+        let mut doc = PdfDocument::new("Imposed Marks");
+        for _ in 0..2 {
+            doc.add_page(PdfPage::new_from_page_size(page_size));
+        }
+        doc.impose(NUp::TwoUp);
+        let sheet_size = Size::new(200f32.pt(), 150f32.pt());
+        assert_eq!(
+            doc.pages[0].trim_box,
+            Some(OutlineRect::new_from_bottom_left(
+                PdfPosition::<Pt>::default(),
+                sheet_size
+            ))
+        );
+
+        doc.add_print_marks(PrintMarks::default());
+        let sheet = &doc.pages[0];
+        let Some(PdfObject::Graphics(GraphicItems::Group(group))) = sheet.contents.last() else {
+            panic!("Expected the print marks");
+        };
+        assert_eq!(group.items.len(), 8);
+        // The sheet grows by the offset and length of the marks on each side
+        assert_eq!(
+            sheet.media_box,
+            OutlineRect::new_from_bottom_left(
+                (-24f32.pt(), -24f32.pt()),
+                Size::new(248f32.pt(), 198f32.pt())
+            )
+        );
+    }
+}
@@ -0,0 +1,395 @@
+//! Repeats a template page once per record with the fields of the record filled in
+use std::{
+    borrow::{Borrow, Cow},
+    collections::{BTreeMap, HashMap},
+    io::Write,
+};
+
+use crate::{
+    TuxPdfError,
+    error::{ErrorContext, ErrorContextExt},
+    page::PdfPage,
+};
+
+use super::{PageStreamWriter, PdfDocument};
+
+/// A record of a mail merge. Such as a row of a spreadsheet
+pub trait MergeRecord {
+    /// The value of the field. None if the record does not have the field
+    fn field(&self, name: &str) -> Option<Cow<'_, str>>;
+}
+impl<K, V, S> MergeRecord for HashMap<K, V, S>
+where
+    K: Borrow<str> + Eq + std::hash::Hash,
+    V: AsRef<str>,
+    S: std::hash::BuildHasher,
+{
+    fn field(&self, name: &str) -> Option<Cow<'_, str>> {
+        self.get(name).map(|value| Cow::Borrowed(value.as_ref()))
+    }
+}
+impl<K, V> MergeRecord for BTreeMap<K, V>
+where
+    K: Borrow<str> + Ord,
+    V: AsRef<str>,
+{
+    fn field(&self, name: &str) -> Option<Cow<'_, str>> {
+        self.get(name).map(|value| Cow::Borrowed(value.as_ref()))
+    }
+}
+impl<R: MergeRecord + ?Sized> MergeRecord for &R {
+    fn field(&self, name: &str) -> Option<Cow<'_, str>> {
+        (**self).field(name)
+    }
+}
+/// What happens to a `{field}` placeholder the record does not have
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissingField {
+    /// The placeholder is left in the text
+    #[default]
+    Keep,
+    /// The placeholder is removed
+    Empty,
+    /// The merge fails with [TuxPdfError::MissingMergeField]
+    Error,
+}
+/// The record a page is created for. Passed to the template of a [MailMerge]
+#[derive(Debug)]
+pub struct MergeFields<'record, R> {
+    record: &'record R,
+    missing_fields: MissingField,
+}
+impl<R: MergeRecord> MergeFields<'_, R> {
+    pub fn record(&self) -> &R {
+        self.record
+    }
+    /// The value of the field. None if the record does not have the field
+    pub fn field(&self, name: &str) -> Option<Cow<'_, str>> {
+        self.record.field(name)
+    }
+    /// Replaces every `{field}` placeholder in the text with the field of the record
+    ///
+    /// Placeholders the record does not have are handled as set by [MailMerge::missing_fields]
+    pub fn fill(&self, text: &str) -> Result<String, TuxPdfError> {
+        fill_text(text, self.record, self.missing_fields).map(Cow::into_owned)
+    }
+}
+type TemplateFn<R> =
+    dyn FnMut(&mut PdfDocument, &MergeFields<'_, R>) -> Result<PdfPage, TuxPdfError>;
+/// Creates one page per record from a template
+///
+/// The template fills the `{field}` placeholders of its text with [MergeFields::fill] before placing it.
+/// So the text is measured and anchored with the values of the record.
+/// It can also draw content from the record that is not text. Such as a QR code
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use tux_pdf::{
+///     document::{BuiltinFont, MailMerge, MergeFields, PdfDocument},
+///     graphics::{Anchor, TextBlock},
+///     page::{PdfPage, page_sizes::A4},
+///     units::UnitType,
+/// };
+/// let records = vec![
+///     HashMap::from([("name", "Jane Doe"), ("city", "Springfield")]),
+///     HashMap::from([("name", "John Smith"), ("city", "Shelbyville")]),
+/// ];
+/// type Record = HashMap<&'static str, &'static str>;
+/// let mut merge = MailMerge::new(|document: &mut PdfDocument, fields: &MergeFields<Record>| {
+///     let mut page = PdfPage::new_from_page_size(A4);
+///     page.place(
+///         document,
+///         TextBlock::from(fields.fill("Dear {name} of {city},")?),
+///         Anchor::TopRight,
+///         (72f32.pt(), 72f32.pt()),
+///     )?;
+///     Ok(page)
+/// });
+/// let mut document = PdfDocument::new("Letters");
+/// document.font_map().register_builtin_font(BuiltinFont::Helvetica);
+/// let pages = merge.merge_into(&mut document, &records)?;
+/// assert_eq!(pages, 2);
+/// # Ok::<(), tux_pdf::TuxPdfError>(())
+/// ```
+pub struct MailMerge<R> {
+    template: Box<TemplateFn<R>>,
+    pub missing_fields: MissingField,
+}
+impl<R> std::fmt::Debug for MailMerge<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MailMerge")
+            .field("missing_fields", &self.missing_fields)
+            .finish_non_exhaustive()
+    }
+}
+impl<R: MergeRecord> MailMerge<R> {
+    pub fn new(
+        template: impl FnMut(&mut PdfDocument, &MergeFields<'_, R>) -> Result<PdfPage, TuxPdfError>
+        + 'static,
+    ) -> Self {
+        Self {
+            template: Box::new(template),
+            missing_fields: MissingField::default(),
+        }
+    }
+    pub fn with_missing_fields(mut self, missing_fields: MissingField) -> Self {
+        self.missing_fields = missing_fields;
+        self
+    }
+    /// Creates the page of the record
+    pub fn page_for(
+        &mut self,
+        document: &mut PdfDocument,
+        record: &R,
+    ) -> Result<PdfPage, TuxPdfError> {
+        let fields = MergeFields {
+            record,
+            missing_fields: self.missing_fields,
+        };
+        (self.template)(document, &fields)
+    }
+    /// Adds a page for every record to the document
+    ///
+    /// Every page stays in memory until the document is saved. Use [Self::merge_in_chunks] for large merges
+    ///
+    /// Returns the number of pages added
+    pub fn merge_into<I>(
+        &mut self,
+        document: &mut PdfDocument,
+        records: I,
+    ) -> Result<usize, TuxPdfError>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<R>,
+    {
+        let mut pages = 0;
+        for (index, record) in records.into_iter().enumerate() {
+            let mut page = self
+                .page_for(document, record.borrow())
+                .context(ErrorContext::MergeRecord(index))?;
+            document.page_created(&mut page, 0);
+            document.add_page(page);
+            pages += 1;
+        }
+        Ok(pages)
+    }
+    /// Saves the document with a page for every record after its own pages
+    ///
+    /// The pages are written to `output` every `chunk_size` records and then released.
+    /// So only one chunk of pages is in memory. Use it for merges too large for memory.
+    /// Sections only decorate the pages the document had before the merge.
+    /// Objects are never deduplicated or removed. See [FinalizeOptions](super::FinalizeOptions)
+    ///
+    /// Returns the number of pages created
+    pub fn merge_in_chunks<I, W>(
+        &mut self,
+        document: PdfDocument,
+        records: I,
+        chunk_size: usize,
+        output: W,
+    ) -> Result<usize, TuxPdfError>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<R>,
+        W: Write,
+    {
+        let chunk_size = chunk_size.max(1);
+        let mut stream = PageStreamWriter::new(document, output)?;
+        let mut records = records.into_iter().enumerate().peekable();
+        let mut pages = 0;
+        while records.peek().is_some() {
+            let mut chunk = Vec::with_capacity(chunk_size);
+            for (index, record) in records.by_ref().take(chunk_size) {
+                // The written pages are no longer in the document
+                let pending_pages = stream.pages_written() + chunk.len();
+                let document = stream.document();
+                let mut page = self
+                    .page_for(document, record.borrow())
+                    .context(ErrorContext::MergeRecord(index))?;
+                document.page_created(&mut page, pending_pages);
+                chunk.push(page);
+            }
+            pages += chunk.len();
+            stream.write_pages(chunk)?;
+        }
+        stream.finish()?;
+        Ok(pages)
+    }
+}
+impl PdfDocument {
+    /// Adds a page for every record. See [MailMerge]
+    ///
+    /// Returns the number of pages added
+    pub fn mail_merge<R, I>(
+        &mut self,
+        template: impl FnMut(&mut PdfDocument, &MergeFields<'_, R>) -> Result<PdfPage, TuxPdfError>
+        + 'static,
+        records: I,
+    ) -> Result<usize, TuxPdfError>
+    where
+        R: MergeRecord,
+        I: IntoIterator,
+        I::Item: Borrow<R>,
+    {
+        MailMerge::new(template).merge_into(self, records)
+    }
+}
+/// Replaces every `{field}` in the text. Borrowed if there was nothing to replace
+fn fill_text<'text>(
+    text: &'text str,
+    record: &impl MergeRecord,
+    missing_fields: MissingField,
+) -> Result<Cow<'text, str>, TuxPdfError> {
+    if !text.contains('{') {
+        return Ok(Cow::Borrowed(text));
+    }
+    let mut filled = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        let Some(length) = rest[start..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 1..start + length];
+        filled.push_str(&rest[..start]);
+        match (record.field(name), missing_fields) {
+            (Some(value), _) => filled.push_str(&value),
+            (None, MissingField::Keep) => filled.push_str(&rest[start..=start + length]),
+            (None, MissingField::Empty) => {}
+            (None, MissingField::Error) => {
+                return Err(TuxPdfError::MissingMergeField(name.to_owned()));
+            }
+        }
+        rest = &rest[start + length + 1..];
+    }
+    filled.push_str(rest);
+    Ok(Cow::Owned(filled))
+}
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{BTreeMap, HashMap},
+        sync::{Arc, Mutex},
+    };
+
+    use super::{MailMerge, MergeFields, MissingField, fill_text};
+    use crate::{
+        TuxPdfError,
+        document::{BuiltinFont, PdfDocument},
+        graphics::{Anchor, PdfObject, TextBlock},
+        page::{PdfPage, page_sizes::A4},
+        units::UnitType,
+    };
+
+    #[test]
+    fn placeholders() -> anyhow::Result<()> {
+        let record = BTreeMap::from([("name", "Jane"), ("city", "Springfield")]);
+        assert_eq!(
+            fill_text("Dear {name} of {city}", &record, MissingField::Keep)?,
+            "Dear Jane of Springfield"
+        );
+        assert_eq!(
+            fill_text("{name}: {zip}", &record, MissingField::Keep)?,
+            "Jane: {zip}"
+        );
+        assert_eq!(
+            fill_text("{name}: {zip}", &record, MissingField::Empty)?,
+            "Jane: "
+        );
+        assert!(matches!(
+            fill_text("{zip}", &record, MissingField::Error),
+            Err(TuxPdfError::MissingMergeField(field)) if field == "zip"
+        ));
+        // An unclosed brace is left alone
+        assert_eq!(
+            fill_text("{name} {", &record, MissingField::Keep)?,
+            "Jane {"
+        );
+        Ok(())
+    }
+    type Record = HashMap<String, String>;
+    fn letter(
+        document: &mut PdfDocument,
+        fields: &MergeFields<Record>,
+    ) -> Result<PdfPage, TuxPdfError> {
+        let mut page = PdfPage::new_from_page_size(A4);
+        let text = TextBlock::from(fields.fill("Hello {name}")?);
+        page.place(document, text, Anchor::TopRight, (0f32.pt(), 0f32.pt()))?;
+        Ok(page)
+    }
+    fn records(count: usize) -> Vec<Record> {
+        (0..count)
+            .map(|index| HashMap::from([("name".to_owned(), format!("Person {index}"))]))
+            .collect()
+    }
+    fn document() -> PdfDocument {
+        let mut document = PdfDocument::new("Merge");
+        document
+            .font_map()
+            .register_builtin_font(BuiltinFont::Helvetica);
+        document
+    }
+    #[test]
+    fn page_per_record() -> anyhow::Result<()> {
+        let mut document = document();
+        let pages = document.mail_merge(letter, records(3))?;
+        assert_eq!(pages, 3);
+        let Some(PdfObject::TextBlock(text)) = document.pages()[2].contents.first() else {
+            panic!("The template text is on the page");
+        };
+        assert_eq!(text.content.text(), "Hello Person 2");
+
+        // The text is placed as if the value had been written in the template
+        let mut page = PdfPage::new_from_page_size(A4);
+        let position = page.place(
+            &document,
+            TextBlock::from("Hello Person 2"),
+            Anchor::TopRight,
+            (0f32.pt(), 0f32.pt()),
+        )?;
+        assert_eq!(text.position, position);
+        Ok(())
+    }
+    #[test]
+    fn chunks() -> anyhow::Result<()> {
+        let mut merge = MailMerge::new(letter);
+        let mut cover = document();
+        cover.add_page(PdfPage::new_from_page_size(A4));
+        let mut pdf = Vec::new();
+        let pages = merge.merge_in_chunks(cover, records(5), 2, &mut pdf)?;
+        assert_eq!(pages, 5);
+
+        let contains = |needle: &str| {
+            pdf.windows(needle.len())
+                .any(|window| window == needle.as_bytes())
+        };
+        assert!(contains("/Count 6"));
+        // Builtin fonts write their text as hex strings
+        let shows = |text: &str| {
+            let hex: String = text.bytes().map(|byte| format!("{byte:02X}")).collect();
+            contains(&format!("<{hex}> Tj"))
+        };
+        assert!(shows("Hello Person 0"));
+        assert!(shows("Hello Person 4"));
+        assert!(pdf.ends_with(b"%%EOF"));
+
+        // The pages are numbered after the pages written by the earlier chunks
+        let numbers = Arc::new(Mutex::new(Vec::new()));
+        let mut cover = document();
+        cover.add_page(PdfPage::new_from_page_size(A4));
+        let created = numbers.clone();
+        cover.on_page_created(move |_, number| created.lock().unwrap().push(number.0));
+        merge.merge_in_chunks(cover, records(5), 2, Vec::new())?;
+        assert_eq!(*numbers.lock().unwrap(), [2, 3, 4, 5, 6]);
+
+        let error = merge
+            .with_missing_fields(MissingField::Error)
+            .merge_into(&mut document(), [Record::new()])
+            .unwrap_err();
+        assert_eq!(error.to_string(), "merge record 0");
+        assert_eq!(
+            error.root_cause().to_string(),
+            "The merge record has no field named name"
+        );
+        Ok(())
+    }
+}
@@ -1,15 +1,19 @@
+use std::collections::BTreeMap;
 use strum::{Display, EnumString};
-use time::OffsetDateTime;
-use tux_pdf_low::types::{Dictionary, Object, ObjectId};
+
+use tux_pdf_low::types::{
+    Dictionary, Object, ObjectId, PdfVersion,
+    trailer::{DocumentId, DocumentIdGeneration},
+};
 
 use crate::{
-    time_impl::PdfDateTimeType,
+    time_impl::{PdfDateTime, PdfDateTimeType},
     utils::{IsEmpty, strum_into_name},
 };
 
 use super::{
     conformance::PdfConformance,
-    types::{CatalogObject, PdfAction},
+    types::{CatalogObject, JavascriptAction, PdfAction, PdfDestination},
 };
 
 #[derive(Debug, PartialEq, Clone, Default)]
@@ -20,8 +24,24 @@ pub struct PdfMetadata {
     pub xmp: Option<XmpMetadata>,
 
     pub catalog_info: CatalogInfo,
+    /// Version written in the header of the file. See [PdfDocument::set_pdf_version](super::PdfDocument::set_pdf_version)
+    pub(crate) pdf_version: PdfVersion,
+    /// The `/ID` written in the trailer. See [Self::set_document_id]
+    pub document_id: Option<DocumentId>,
+    /// How the `/ID` is created when [Self::document_id] is not set. A hash of the written document by default
+    pub document_id_generation: DocumentIdGeneration,
 }
 impl PdfMetadata {
+    /// Version written in the header of the file. See [PdfDocument::set_pdf_version](super::PdfDocument::set_pdf_version)
+    pub fn pdf_version(&self) -> PdfVersion {
+        self.pdf_version
+    }
+    /// Sets the `/ID` of the trailer instead of creating one when the document is written
+    ///
+    /// Use [DocumentId::hash_of] to derive it from your own data such as the hash of the source of the document
+    pub fn set_document_id(&mut self, id: DocumentId) {
+        self.document_id = Some(id);
+    }
     pub fn set_open_action(&mut self, action: impl Into<PdfAction>) {
         self.catalog_info.open_action = Some(action.into());
     }
@@ -33,6 +53,21 @@ impl PdfMetadata {
             self.set_open_action(action);
         }
     }
+    /// The page and view shown when the document is opened
+    ///
+    /// Ignored if an open action is set
+    pub fn set_open_destination(&mut self, destination: PdfDestination) {
+        self.catalog_info.open_destination = Some(destination);
+    }
+    pub fn set_page_layout(&mut self, page_layout: PageLayout) {
+        self.catalog_info.page_layout = page_layout;
+    }
+    pub fn set_page_mode(&mut self, page_mode: PageMode) {
+        self.catalog_info.page_mode = page_mode;
+    }
+    pub fn set_viewer_preferences(&mut self, preferences: ViewerPreferences) {
+        self.catalog_info.viewer_preferences = Some(preferences);
+    }
 }
 #[derive(Debug, PartialEq, Clone)]
 pub struct PdfDocumentInfo {
@@ -41,11 +76,11 @@ pub struct PdfDocumentInfo {
     /// PDF document version
     pub version: Option<i32>,
     /// Creation date of the document
-    pub creation_date: Option<OffsetDateTime>,
+    pub creation_date: Option<PdfDateTime>,
     /// Modification date of the document
-    pub modification_date: Option<OffsetDateTime>,
+    pub modification_date: Option<PdfDateTime>,
     /// Creation date of the metadata
-    pub metadata_date: Option<OffsetDateTime>,
+    pub metadata_date: Option<PdfDateTime>,
     /// PDF Standard
     pub conformance: PdfConformance,
     /// PDF document title
@@ -147,28 +182,460 @@ pub enum PageMode {
     UseAttachments,
 }
 strum_into_name!(PageMode);
+/// The order text and columns are read in
+///
+/// Viewers use it to order pages side by side. Layouts such as tables use it to mirror their columns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display, Default)]
+pub enum ReadingDirection {
+    /// Left to right
+    #[default]
+    L2R,
+    /// Right to left. Used for Arabic and Hebrew
+    R2L,
+}
+strum_into_name!(ReadingDirection);
+impl ReadingDirection {
+    pub fn is_right_to_left(&self) -> bool {
+        *self == ReadingDirection::R2L
+    }
+}
+/// How the viewer should display the document
+///
+/// Section 12.2 Table 150
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ViewerPreferences {
+    pub hide_toolbar: bool,
+    pub hide_menubar: bool,
+    /// Hide scrollbars and navigation controls
+    pub hide_window_ui: bool,
+    /// Resize the window to fit the first page
+    pub fit_window: bool,
+    pub center_window: bool,
+    /// Show the document title instead of the file name in the title bar
+    pub display_doc_title: bool,
+    /// The reading order of the document
+    pub direction: ReadingDirection,
+}
+impl ViewerPreferences {
+    pub fn with_hide_toolbar(mut self, hide_toolbar: bool) -> Self {
+        self.hide_toolbar = hide_toolbar;
+        self
+    }
+    pub fn with_hide_menubar(mut self, hide_menubar: bool) -> Self {
+        self.hide_menubar = hide_menubar;
+        self
+    }
+    pub fn with_hide_window_ui(mut self, hide_window_ui: bool) -> Self {
+        self.hide_window_ui = hide_window_ui;
+        self
+    }
+    pub fn with_fit_window(mut self, fit_window: bool) -> Self {
+        self.fit_window = fit_window;
+        self
+    }
+    pub fn with_center_window(mut self, center_window: bool) -> Self {
+        self.center_window = center_window;
+        self
+    }
+    pub fn with_display_doc_title(mut self, display_doc_title: bool) -> Self {
+        self.display_doc_title = display_doc_title;
+        self
+    }
+    pub fn with_direction(mut self, direction: ReadingDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+}
+impl From<ViewerPreferences> for Dictionary {
+    fn from(value: ViewerPreferences) -> Self {
+        let mut dict = Dictionary::new();
+        // Only true values are written as false is the default
+        for (key, enabled) in [
+            ("HideToolbar", value.hide_toolbar),
+            ("HideMenubar", value.hide_menubar),
+            ("HideWindowUI", value.hide_window_ui),
+            ("FitWindow", value.fit_window),
+            ("CenterWindow", value.center_window),
+            ("DisplayDocTitle", value.display_doc_title),
+        ] {
+            if enabled {
+                dict.set(key, true);
+            }
+        }
+        if value.direction != ReadingDirection::default() {
+            dict.set("Direction", value.direction);
+        }
+        dict
+    }
+}
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct CatalogInfo {
     pub page_layout: PageLayout,
     pub language: Option<String>,
     pub page_mode: PageMode,
     pub open_action: Option<PdfAction>,
+    /// The page and view shown when the document is opened
+    ///
+    /// Ignored if [CatalogInfo::open_action] is set
+    pub open_destination: Option<PdfDestination>,
+    pub viewer_preferences: Option<ViewerPreferences>,
+    /// Destinations that can be targeted by name. See [GoToAction](super::types::GoToAction)
+    pub named_destinations: BTreeMap<String, PdfDestination>,
+    /// Document level JavaScript. Runs when the document is opened
+    pub javascript: BTreeMap<String, JavascriptAction>,
+    /// Page numbers shown by viewers. Keyed by the zero based index of the first page the label applies to
+    ///
+    /// Filled in for each [Section](super::Section) when the document is written
+    pub page_labels: BTreeMap<usize, PageLabel>,
 }
 impl CatalogInfo {
-    pub fn create_catalog_object(self, pages: ObjectId) -> CatalogObject {
+    pub fn with_page_layout(mut self, page_layout: PageLayout) -> Self {
+        self.page_layout = page_layout;
+        self
+    }
+    pub fn with_page_mode(mut self, page_mode: PageMode) -> Self {
+        self.page_mode = page_mode;
+        self
+    }
+    pub fn with_language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+    pub fn with_open_action(mut self, action: impl Into<PdfAction>) -> Self {
+        self.open_action = Some(action.into());
+        self
+    }
+    pub fn with_open_destination(mut self, destination: PdfDestination) -> Self {
+        self.open_destination = Some(destination);
+        self
+    }
+    pub fn with_viewer_preferences(mut self, preferences: ViewerPreferences) -> Self {
+        self.viewer_preferences = Some(preferences);
+        self
+    }
+    pub fn with_named_destination(
+        mut self,
+        name: impl Into<String>,
+        destination: PdfDestination,
+    ) -> Self {
+        self.named_destinations.insert(name.into(), destination);
+        self
+    }
+    pub fn with_javascript(
+        mut self,
+        name: impl Into<String>,
+        script: impl Into<JavascriptAction>,
+    ) -> Self {
+        self.javascript.insert(name.into(), script.into());
+        self
+    }
+    pub fn with_page_label(mut self, first_page: usize, label: PageLabel) -> Self {
+        self.page_labels.insert(first_page, label);
+        self
+    }
+    /// Creates the `Names` dictionary. Returns `None` if there is nothing to put in it
+    ///
+    /// Destinations pointing to pages that do not exist are skipped
+    fn names_dictionary(
+        named_destinations: BTreeMap<String, PdfDestination>,
+        javascript: BTreeMap<String, JavascriptAction>,
+        page_ids: &[ObjectId],
+    ) -> Option<Dictionary> {
+        if named_destinations.is_empty() && javascript.is_empty() {
+            return None;
+        }
+        let mut names = Dictionary::new();
+        // Both are name trees with a single leaf. BTreeMap keeps the keys sorted as the spec requires
+        let destinations: Vec<Object> = named_destinations
+            .into_iter()
+            .filter_map(|(name, destination)| {
+                let destination = destination.to_object(page_ids)?;
+                Some([Object::string_literal_owned(name), destination])
+            })
+            .flatten()
+            .collect();
+        if !destinations.is_empty() {
+            names.set("Dests", name_tree(destinations));
+        }
+        if !javascript.is_empty() {
+            let scripts: Vec<Object> = javascript
+                .into_iter()
+                .flat_map(|(name, script)| {
+                    [
+                        Object::string_literal_owned(name),
+                        Dictionary::from(PdfAction::from(script)).into(),
+                    ]
+                })
+                .collect();
+            names.set("JavaScript", name_tree(scripts));
+        }
+        Some(names)
+    }
+    /// Creates the `PageLabels` number tree. Labels starting after the last page are skipped
+    ///
+    /// The tree must start at the first page. Without a label for it the pages before the first label are numbered with [PageLabel::default]
+    fn page_labels_dictionary(
+        mut page_labels: BTreeMap<usize, PageLabel>,
+        number_of_pages: usize,
+    ) -> Option<Dictionary> {
+        if page_labels.is_empty() {
+            return None;
+        }
+        page_labels.entry(0).or_default();
+        let numbers: Vec<Object> = page_labels
+            .into_iter()
+            .filter(|(first_page, _)| *first_page < number_of_pages)
+            .flat_map(|(first_page, label)| {
+                [
+                    Object::from(first_page as i64),
+                    Dictionary::from(label).into(),
+                ]
+            })
+            .collect();
+        if numbers.is_empty() {
+            return None;
+        }
+        let mut dict = Dictionary::new();
+        dict.set("Nums", Object::Array(numbers));
+        Some(dict)
+    }
+    /// `page_ids` are used to resolve the open destination
+    pub fn create_catalog_object(self, pages: ObjectId, page_ids: &[ObjectId]) -> CatalogObject {
         let Self {
             page_layout,
             language,
             page_mode,
             open_action,
+            open_destination,
+            viewer_preferences,
+            named_destinations,
+            javascript,
+            page_labels,
         } = self;
+        let open_action = match (open_action, open_destination) {
+            (Some(action), _) => Some(Object::from(action)),
+            (None, Some(destination)) => destination.to_object(page_ids),
+            (None, None) => None,
+        };
         CatalogObject {
             pages,
             page_layout,
             language,
             page_mode,
             open_action,
+            viewer_preferences: viewer_preferences.map(Dictionary::from),
+            names: Self::names_dictionary(named_destinations, javascript, page_ids),
+            page_labels: Self::page_labels_dictionary(page_labels, page_ids.len()),
             ..Default::default()
         }
     }
 }
+/// How page numbers are written
+///
+/// Section 12.4.2 Table 161
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PageNumberStyle {
+    /// 1, 2, 3
+    #[default]
+    Decimal,
+    /// i, ii, iii
+    LowerRoman,
+    /// I, II, III
+    UpperRoman,
+    /// a to z then aa to zz
+    LowerAlpha,
+    /// A to Z then AA to ZZ
+    UpperAlpha,
+    /// Only the prefix of the label is shown
+    None,
+}
+impl PageNumberStyle {
+    /// Formats a page number. Roman numerals fall back to decimal for 0
+    pub fn format(&self, number: usize) -> String {
+        match self {
+            PageNumberStyle::Decimal => number.to_string(),
+            PageNumberStyle::LowerRoman => to_roman(number).to_lowercase(),
+            PageNumberStyle::UpperRoman => to_roman(number),
+            PageNumberStyle::LowerAlpha => to_alpha(number).to_lowercase(),
+            PageNumberStyle::UpperAlpha => to_alpha(number),
+            PageNumberStyle::None => String::new(),
+        }
+    }
+    fn key(&self) -> Option<&'static str> {
+        match self {
+            PageNumberStyle::Decimal => Some("D"),
+            PageNumberStyle::LowerRoman => Some("r"),
+            PageNumberStyle::UpperRoman => Some("R"),
+            PageNumberStyle::LowerAlpha => Some("a"),
+            PageNumberStyle::UpperAlpha => Some("A"),
+            PageNumberStyle::None => None,
+        }
+    }
+}
+fn to_roman(mut number: usize) -> String {
+    if number == 0 {
+        return number.to_string();
+    }
+    const NUMERALS: [(usize, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    let mut roman = String::new();
+    for (value, numeral) in NUMERALS {
+        while number >= value {
+            roman.push_str(numeral);
+            number -= value;
+        }
+    }
+    roman
+}
+/// The letter is repeated once for every time the alphabet is passed. Same as PDF viewers
+fn to_alpha(number: usize) -> String {
+    if number == 0 {
+        return number.to_string();
+    }
+    let letter = (b'A' + ((number - 1) % 26) as u8) as char;
+    letter.to_string().repeat((number - 1) / 26 + 1)
+}
+/// The page number a viewer shows for a range of pages
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageLabel {
+    pub style: PageNumberStyle,
+    /// Text in front of the number. Such as `A-` for `A-1`
+    pub prefix: Option<String>,
+    /// The number of the first page in the range. Defaults to 1
+    pub first_number: usize,
+}
+impl Default for PageLabel {
+    fn default() -> Self {
+        Self::new(PageNumberStyle::default())
+    }
+}
+impl PageLabel {
+    pub fn new(style: PageNumberStyle) -> Self {
+        Self {
+            style,
+            prefix: None,
+            first_number: 1,
+        }
+    }
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+    pub fn with_first_number(mut self, first_number: usize) -> Self {
+        self.first_number = first_number;
+        self
+    }
+    /// The label of a page. `index` is the zero based index of the page in the range
+    pub fn format(&self, index: usize) -> String {
+        let number = self.style.format(self.first_number + index);
+        match &self.prefix {
+            Some(prefix) => format!("{prefix}{number}"),
+            None => number,
+        }
+    }
+}
+impl From<PageLabel> for Dictionary {
+    fn from(label: PageLabel) -> Self {
+        let mut dict = Dictionary::new();
+        if let Some(key) = label.style.key() {
+            dict.set("S", Object::name(key));
+        }
+        if let Some(prefix) = label.prefix {
+            dict.set("P", Object::string_literal_owned(prefix));
+        }
+        if label.first_number != 1 {
+            dict.set("St", label.first_number as i64);
+        }
+        dict
+    }
+}
+fn name_tree(names: Vec<Object>) -> Dictionary {
+    let mut dict = Dictionary::new();
+    dict.set("Names", Object::Array(names));
+    dict
+}
+#[cfg(test)]
+mod tests {
+    use tux_pdf_low::types::{Object, ObjectId};
+
+    use super::{CatalogInfo, PageLayout, ReadingDirection, ViewerPreferences};
+    use crate::document::types::{JavascriptAction, NamedAction, PdfDestination, PdfDirectoryType};
+
+    #[test]
+    fn catalog_viewer_options() {
+        let pages = [ObjectId::from(4u32)];
+        let catalog = CatalogInfo::default()
+            .with_page_layout(PageLayout::TwoColumnLeft)
+            .with_open_destination(PdfDestination::fit_page(0))
+            .with_viewer_preferences(
+                ViewerPreferences::default()
+                    .with_fit_window(true)
+                    .with_display_doc_title(true)
+                    .with_direction(ReadingDirection::R2L),
+            )
+            .create_catalog_object(ObjectId::from(1u32), &pages)
+            .into_dictionary();
+        assert_eq!(
+            catalog.get("PageLayout"),
+            Some(&Object::name("TwoColumnLeft"))
+        );
+        assert_eq!(
+            catalog.get("OpenAction"),
+            Some(&Object::Array(vec![
+                Object::Reference(ObjectId::from(4u32)),
+                Object::name("Fit")
+            ]))
+        );
+        let Some(Object::Dictionary(preferences)) = catalog.get("ViewerPreferences") else {
+            panic!("Missing viewer preferences");
+        };
+        assert_eq!(preferences.get("FitWindow"), Some(&Object::Boolean(true)));
+        assert_eq!(preferences.get("HideToolbar"), None);
+        assert_eq!(preferences.get("Direction"), Some(&Object::name("R2L")));
+    }
+    #[test]
+    fn names_dictionary() {
+        let pages = [ObjectId::from(4u32), ObjectId::from(5u32)];
+        let catalog = CatalogInfo::default()
+            .with_named_destination("chapter-2", PdfDestination::fit_page(1))
+            .with_named_destination("missing", PdfDestination::fit_page(9))
+            .with_javascript("print", JavascriptAction::print())
+            .with_open_action(NamedAction::Print)
+            .create_catalog_object(ObjectId::from(1u32), &pages)
+            .into_dictionary();
+        let Some(Object::Dictionary(names)) = catalog.get("Names") else {
+            panic!("Missing names dictionary");
+        };
+        let Some(Object::Dictionary(dests)) = names.get("Dests") else {
+            panic!("Missing destinations");
+        };
+        assert_eq!(
+            dests.get("Names"),
+            Some(&Object::Array(vec![
+                Object::string_literal_owned("chapter-2"),
+                Object::Array(vec![
+                    Object::Reference(ObjectId::from(5u32)),
+                    Object::name("Fit")
+                ]),
+            ]))
+        );
+        assert!(names.get("JavaScript").is_some());
+        let Some(Object::Dictionary(open_action)) = catalog.get("OpenAction") else {
+            panic!("Missing open action");
+        };
+        assert_eq!(open_action.get("N"), Some(&Object::name("Print")));
+    }
+}
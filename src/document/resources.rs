@@ -1,12 +1,16 @@
+mod ext_g_state;
 mod font;
 mod icc_profile;
 mod layers;
+mod raw;
 mod xobject;
 use std::fmt::Debug;
 
+pub use ext_g_state::*;
 pub use font::*;
 pub use icc_profile::*;
 pub use layers::*;
+pub use raw::*;
 use thiserror::Error;
 pub use xobject::*;
 
@@ -20,16 +24,12 @@ pub enum ResourceNotRegistered {
     XObjectId(XObjectId),
     #[error("Layer not registered: {0:?}")]
     LayerId(LayerId),
-}
-impl From<FontRef> for ResourceNotRegistered {
-    fn from(font_ref: FontRef) -> Self {
-        match font_ref {
-            FontRef::External(font_id) => ResourceNotRegistered::FontId(font_id),
-            FontRef::Builtin(builtin_font) => {
-                ResourceNotRegistered::BuiltinFontNotRegistered(builtin_font)
-            }
-        }
-    }
+    #[error("ExtGState not registered: {0:?}")]
+    ExtGStateId(ExtGStateId),
+    #[error("Blend mode not registered: {0:?}")]
+    BlendModeNotRegistered(BlendMode),
+    #[error("Opacity not registered: {0:?}")]
+    OpacityNotRegistered(Opacity),
 }
 
 #[derive(Debug, Default, Clone, PartialEq)]
@@ -39,13 +39,15 @@ pub struct PdfResources {
     /// XObjects (forms, images, embedded PDF contents, etc.)
     pub xobjects: XObjectMap,
     /// Map of explicit extended graphics states
-    //pub extgstates: ExtendedGraphicsStateMap,
+    pub ext_g_states: ExtGStateMap,
     /// Map of optional content groups
     pub layers: PdfLayerMap,
     /// Controls how color emoji fonts are rendered
     pub emoji_render_mode: EmojiRenderMode,
     /// Cache of rasterized emoji glyphs
     pub(crate) emoji_cache: EmojiGlyphCache,
+    /// Objects written as is
+    pub raw: RawObjects,
 }
 impl PdfResources {
     pub fn get_font_type(&self, font_id: &FontRef) -> Option<InternalFontTypes<'_>> {
@@ -164,3 +166,4 @@ macro_rules! object_id_type {
 object_id_type!(FontId);
 object_id_type!(XObjectId);
 object_id_type!(LayerId);
+object_id_type!(ExtGStateId);
@@ -0,0 +1,479 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use strum::{Display, EnumString};
+use tux_pdf_low::types::{Dictionary, Object};
+
+use crate::{
+    TuxPdfError,
+    document::{DocumentWriter, operations_to_content},
+    error::{ErrorContext, ErrorContextExt},
+    graphics::{OperationKeys, OperationWriter, PdfObject, PdfPosition, shapes::OutlineRect},
+    utils::strum_into_name,
+};
+
+use super::{IdType, ObjectMapType, PdfResources};
+/// The name of the gradient within the resources of a [SoftMask]
+const GRADIENT_SHADING_NAME: &str = "Sh0";
+
+#[derive(Debug, PartialEq, Clone, Eq, PartialOrd, Ord, Hash)]
+pub struct ExtGStateId(pub(crate) String);
+impl IdType for ExtGStateId {
+    fn new_random() -> Self {
+        Self(crate::utils::random::random_character_string(32))
+    }
+    fn add_random_suffix(self) -> Self {
+        Self(format!(
+            "{}{}",
+            self.0,
+            crate::utils::random::random_character_string(8)
+        ))
+    }
+    fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    fn into_string(self) -> String {
+        self.0
+    }
+
+    fn resource_category(&self) -> &'static str {
+        "ExtGState"
+    }
+}
+/// Extended graphics states. Applied to content with [MaskedGroup](crate::graphics::MaskedGroup)
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct ExtGStateMap {
+    pub map: BTreeMap<ExtGStateId, ExtGState>,
+    pub(crate) registered_blend_modes: BTreeSet<BlendMode>,
+    pub(crate) registered_opacities: BTreeSet<Opacity>,
+}
+impl ExtGStateMap {
+    pub fn add_ext_g_state(&mut self, state: ExtGState) -> ExtGStateId {
+        let id = self.new_id();
+        self.map.insert(id.clone(), state);
+        id
+    }
+    pub fn get_ext_g_state(&self, id: &ExtGStateId) -> Option<&ExtGState> {
+        self.map.get(id)
+    }
+    /// Register a blend mode so it can be used in [GraphicStyles](crate::graphics::GraphicStyles)
+    pub fn register_blend_mode(&mut self, blend_mode: BlendMode) -> BlendMode {
+        self.registered_blend_modes.insert(blend_mode);
+        blend_mode
+    }
+    pub fn is_blend_mode_registered(&self, blend_mode: &BlendMode) -> bool {
+        self.registered_blend_modes.contains(blend_mode)
+    }
+    /// Register an opacity so it can be used in [GraphicStyles](crate::graphics::GraphicStyles)
+    pub fn register_opacity(&mut self, opacity: Opacity) -> Opacity {
+        self.registered_opacities.insert(opacity);
+        opacity
+    }
+    pub fn is_opacity_registered(&self, opacity: &Opacity) -> bool {
+        self.registered_opacities.contains(opacity)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+            && self.registered_blend_modes.is_empty()
+            && self.registered_opacities.is_empty()
+    }
+    /// Writes the graphics states and their soft masks into the pdf file
+    ///
+    /// `resources` are used to write the content of the soft masks
+    pub(crate) fn dictionary(
+        self,
+        resources: &PdfResources,
+        writer: &mut DocumentWriter,
+    ) -> Result<Dictionary, TuxPdfError> {
+        let mut states = Dictionary::new();
+        let blend_modes = self
+            .registered_blend_modes
+            .into_iter()
+            .map(|mode| (mode.dedicated_ext_g_state_id(), ExtGState::from(mode)));
+        let opacities = self
+            .registered_opacities
+            .into_iter()
+            .map(|opacity| (opacity.dedicated_ext_g_state_id(), ExtGState::from(opacity)));
+        for (id, state) in self.map.into_iter().chain(blend_modes).chain(opacities) {
+            let mut dictionary = Dictionary::new();
+            dictionary.set("Type", Object::name("ExtGState"));
+            if let Some(blend_mode) = state.blend_mode {
+                dictionary.set("BM", blend_mode);
+            }
+            if let Some(opacity) = state.opacity {
+                dictionary.set("CA", opacity.value());
+                dictionary.set("ca", opacity.value());
+            }
+            if let Some(mask) = state.soft_mask {
+                let mask = mask
+                    .into_dictionary(resources, writer)
+                    .with_context(|| ErrorContext::Resource(id.to_string()))?;
+                dictionary.set("SMask", mask);
+            }
+            let object_id = writer.insert_object(dictionary.into());
+            states.set(id.to_string(), object_id);
+        }
+        Ok(states)
+    }
+}
+impl ObjectMapType for ExtGStateMap {
+    type IdType = ExtGStateId;
+
+    fn has_id(&self, id: &Self::IdType) -> bool {
+        self.map.contains_key(id)
+    }
+}
+/// Graphics state parameters set with the `gs` operator
+///
+/// Section 8.4.5 Table 57
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ExtGState {
+    /// Masks everything drawn while the state is active
+    pub soft_mask: Option<SoftMask>,
+    /// How colors are combined with what is already on the page
+    pub blend_mode: Option<BlendMode>,
+    /// The opacity of both stroking and filling operations
+    pub opacity: Option<Opacity>,
+}
+impl ExtGState {
+    pub fn with_soft_mask(mut self, soft_mask: SoftMask) -> Self {
+        self.soft_mask = Some(soft_mask);
+        self
+    }
+    pub fn with_blend_mode(mut self, blend_mode: BlendMode) -> Self {
+        self.blend_mode = Some(blend_mode);
+        self
+    }
+    pub fn with_opacity(mut self, opacity: Opacity) -> Self {
+        self.opacity = Some(opacity);
+        self
+    }
+}
+impl From<SoftMask> for ExtGState {
+    fn from(mask: SoftMask) -> Self {
+        Self {
+            soft_mask: Some(mask),
+            ..Default::default()
+        }
+    }
+}
+impl From<BlendMode> for ExtGState {
+    fn from(blend_mode: BlendMode) -> Self {
+        Self {
+            blend_mode: Some(blend_mode),
+            ..Default::default()
+        }
+    }
+}
+impl From<Opacity> for ExtGState {
+    fn from(opacity: Opacity) -> Self {
+        Self {
+            opacity: Some(opacity),
+            ..Default::default()
+        }
+    }
+}
+/// How the colors of new content are combined with the colors already on the page
+///
+/// Section 11.3.5 Table 136 and 137
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, EnumString, Display,
+)]
+pub enum BlendMode {
+    /// Paints over the page. The default
+    #[default]
+    Normal,
+    /// Darkens. White leaves the page unchanged. Useful for highlighting text
+    Multiply,
+    /// Lightens. Black leaves the page unchanged
+    Screen,
+    /// Multiply or screen depending on the color of the page
+    Overlay,
+    /// Keeps the darker of the two colors
+    Darken,
+    /// Keeps the lighter of the two colors
+    Lighten,
+    ColorDodge,
+    ColorBurn,
+    HardLight,
+    SoftLight,
+    Difference,
+    Exclusion,
+    Hue,
+    Saturation,
+    Color,
+    Luminosity,
+}
+strum_into_name!(BlendMode);
+impl BlendMode {
+    /// The name of the graphics state written for a [registered](ExtGStateMap::register_blend_mode) blend mode
+    pub(crate) fn dedicated_ext_g_state_id(&self) -> ExtGStateId {
+        ExtGStateId(format!("BM{self}"))
+    }
+}
+/// A constant opacity for all content. `0` is invisible and `255` is opaque
+///
+/// Stored as a byte so the same opacity always shares one graphics state.
+/// Matches the alpha channel of [AlphaColor](crate::graphics::color::AlphaColor)
+///
+/// Section 11.6.4.4
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Opacity(pub u8);
+impl Default for Opacity {
+    fn default() -> Self {
+        Self::OPAQUE
+    }
+}
+impl Opacity {
+    pub const OPAQUE: Opacity = Opacity(u8::MAX);
+    /// Converts an alpha between `0` and `1`. Values outside of the range are clamped
+    pub fn from_f32(alpha: f32) -> Self {
+        Self((alpha.clamp(0.0, 1.0) * 255.0).round() as u8)
+    }
+    /// The alpha between `0` and `1`
+    pub fn value(&self) -> f32 {
+        f32::from(self.0) / 255.0
+    }
+    pub fn is_opaque(&self) -> bool {
+        *self == Self::OPAQUE
+    }
+    /// The name of the graphics state written for a [registered](ExtGStateMap::register_opacity) opacity
+    pub(crate) fn dedicated_ext_g_state_id(&self) -> ExtGStateId {
+        ExtGStateId(format!("CA{}", self.0))
+    }
+}
+/// A luminosity soft mask. Content is drawn where the mask is white and hidden where it is black
+///
+/// Gray values in between make the content partially transparent. Used for fading out images or graphics
+///
+/// ```rust
+/// use tux_pdf::{
+///     document::{GrayGradient, PdfDocument, SoftMask},
+///     graphics::{MaskedGroup, PdfPosition, shapes::OutlineRect},
+///     page::{PdfPage, page_sizes::A4},
+///     units::UnitType,
+/// };
+/// let mut document = PdfDocument::new("Cover");
+/// let area = OutlineRect::new_from_bottom_left((0f32.pt(), 0f32.pt()), A4);
+/// // Fully visible at the top of the page. Fades out towards the bottom
+/// let fade = SoftMask::new(area).with_gradient(GrayGradient::new(
+///     PdfPosition::new(0f32.pt(), A4.height),
+///     PdfPosition::new(0f32.pt(), 0f32.pt()),
+/// ));
+/// let mask = document.add_soft_mask(fade);
+/// let mut page = PdfPage::new_from_page_size(A4);
+/// page.contents.push(MaskedGroup::new(mask).into());
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct SoftMask {
+    /// The area of the mask. Content outside of it uses the backdrop
+    pub bounds: OutlineRect,
+    /// Painted before the contents. Covers the entire bounds
+    pub gradient: Option<GrayGradient>,
+    /// Drawn in gray on top of the gradient. Colors are converted to their luminosity
+    pub contents: Vec<PdfObject>,
+    /// The gray level used where nothing was drawn. Defaults to black so the content is hidden
+    pub backdrop: f32,
+}
+impl SoftMask {
+    pub fn new(bounds: OutlineRect) -> Self {
+        Self {
+            bounds,
+            gradient: None,
+            contents: Vec::new(),
+            backdrop: 0.0,
+        }
+    }
+    pub fn with_gradient(mut self, gradient: GrayGradient) -> Self {
+        self.gradient = Some(gradient);
+        self
+    }
+    pub fn with_contents(mut self, contents: Vec<PdfObject>) -> Self {
+        self.contents = contents;
+        self
+    }
+    pub fn with_backdrop(mut self, backdrop: f32) -> Self {
+        self.backdrop = backdrop;
+        self
+    }
+    /// Writes the transparency group of the mask and creates the soft mask dictionary
+    fn into_dictionary(
+        self,
+        resources: &PdfResources,
+        writer: &mut DocumentWriter,
+    ) -> Result<Dictionary, TuxPdfError> {
+        let Self {
+            bounds,
+            gradient,
+            contents,
+            backdrop,
+        } = self;
+        let mut form_resources = Dictionary::new();
+        form_resources.set("Font", writer.font_id());
+        form_resources.set("XObject", writer.xobjects_id());
+        form_resources.set("ExtGState", writer.ext_g_states_id());
+
+        let mut operations = OperationWriter::with_capacity(contents.len() + 1);
+        if let Some(gradient) = gradient {
+            let mut shadings = Dictionary::new();
+            shadings.set(GRADIENT_SHADING_NAME, gradient.into_dictionary());
+            form_resources.set("Shading", shadings);
+            operations.add_operation(
+                OperationKeys::PaintShading,
+                vec![Object::name(GRADIENT_SHADING_NAME)],
+            );
+        }
+        operations_to_content(resources, contents, &mut operations)?;
+
+        let mut group = Dictionary::new();
+        group.set("S", Object::name("Transparency"));
+        group.set("CS", Object::name("DeviceGray"));
+        let mut form = Dictionary::new();
+        form.set("Type", Object::name("XObject"));
+        form.set("Subtype", Object::name("Form"));
+        let upper_right = bounds.upper_right();
+        form.set(
+            "BBox",
+            vec![
+                bounds.position.x.0,
+                bounds.position.y.0,
+                upper_right.x.0,
+                upper_right.y.0,
+            ],
+        );
+        form.set("Group", group);
+        form.set("Resources", form_resources);
+        let form_id = writer.insert_object(operations.into_stream(form)?.into());
+
+        let mut mask = Dictionary::new();
+        mask.set("Type", Object::name("Mask"));
+        mask.set("S", Object::name("Luminosity"));
+        mask.set("G", form_id);
+        mask.set("BC", vec![backdrop]);
+        Ok(mask)
+    }
+}
+/// A linear gradient between two gray levels. `0` is black and `1` is white
+///
+/// Extends past both points with the gray at that end
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct GrayGradient {
+    pub start: PdfPosition,
+    pub end: PdfPosition,
+    /// Gray level at the start. Defaults to white
+    pub start_gray: f32,
+    /// Gray level at the end. Defaults to black
+    pub end_gray: f32,
+}
+impl GrayGradient {
+    /// Fades from white at `start` to black at `end`
+    pub fn new(start: PdfPosition, end: PdfPosition) -> Self {
+        Self {
+            start,
+            end,
+            start_gray: 1.0,
+            end_gray: 0.0,
+        }
+    }
+    pub fn with_grays(mut self, start_gray: f32, end_gray: f32) -> Self {
+        self.start_gray = start_gray;
+        self.end_gray = end_gray;
+        self
+    }
+    /// An axial shading with an exponential interpolation function
+    ///
+    /// Section 8.7.4.5.3 and 7.10.3
+    fn into_dictionary(self) -> Dictionary {
+        let mut function = Dictionary::new();
+        function.set("FunctionType", 2);
+        function.set("Domain", vec![0f32, 1f32]);
+        function.set("C0", vec![self.start_gray]);
+        function.set("C1", vec![self.end_gray]);
+        function.set("N", 1);
+
+        let mut shading = Dictionary::new();
+        shading.set("ShadingType", 2);
+        shading.set("ColorSpace", Object::name("DeviceGray"));
+        shading.set(
+            "Coords",
+            vec![self.start.x.0, self.start.y.0, self.end.x.0, self.end.y.0],
+        );
+        shading.set("Function", function);
+        shading.set("Extend", vec![true, true]);
+        shading
+    }
+}
+#[cfg(test)]
+mod tests {
+    use tux_pdf_low::types::Object;
+
+    use super::{BlendMode, ExtGStateMap, GrayGradient, Opacity, SoftMask};
+    use crate::{
+        document::{DocumentWriter, PdfResources},
+        graphics::{PdfPosition, shapes::OutlineRect},
+        page::page_sizes::A4,
+        units::UnitType,
+    };
+
+    #[test]
+    fn luminosity_mask_dictionary() -> anyhow::Result<()> {
+        let mask = SoftMask::new(OutlineRect::new_from_bottom_left(
+            (0f32.pt(), 0f32.pt()),
+            A4,
+        ))
+        .with_gradient(GrayGradient::new(
+            PdfPosition::new(0f32.pt(), A4.height),
+            PdfPosition::default(),
+        ));
+        let mut writer = DocumentWriter::default();
+        let dictionary = mask.into_dictionary(&PdfResources::default(), &mut writer)?;
+        assert_eq!(dictionary.get("S"), Some(&Object::name("Luminosity")));
+        let Some(Object::Reference(form_id)) = dictionary.get("G") else {
+            panic!("The mask does not reference its group");
+        };
+        let Some(Object::Stream(form)) = writer.document.get_object(form_id) else {
+            panic!("The group is not a stream");
+        };
+        assert_eq!(form.dictionary.get("Subtype"), Some(&Object::name("Form")));
+        assert_eq!(String::from_utf8_lossy(&form.content).trim(), "/Sh0 sh");
+        Ok(())
+    }
+    #[test]
+    fn blend_mode_states() -> anyhow::Result<()> {
+        let mut states = ExtGStateMap::default();
+        states.register_blend_mode(BlendMode::Multiply);
+        states.register_blend_mode(BlendMode::Multiply);
+        assert!(states.is_blend_mode_registered(&BlendMode::Multiply));
+        assert!(!states.is_blend_mode_registered(&BlendMode::Screen));
+
+        let mut writer = DocumentWriter::default();
+        let dictionary = states.dictionary(&PdfResources::default(), &mut writer)?;
+        assert!(dictionary.get("BMScreen").is_none());
+        let Some(Object::Reference(state_id)) = dictionary.get("BMMultiply") else {
+            panic!("The blend mode state was not written");
+        };
+        let Some(Object::Dictionary(state)) = writer.document.get_object(state_id) else {
+            panic!("The state is not a dictionary");
+        };
+        assert_eq!(state.get("BM"), Some(&Object::name("Multiply")));
+        Ok(())
+    }
+    #[test]
+    fn opacity_states() -> anyhow::Result<()> {
+        assert_eq!(Opacity::from_f32(0.5), Opacity(128));
+        assert_eq!(Opacity::from_f32(2.0), Opacity::OPAQUE);
+
+        let mut states = ExtGStateMap::default();
+        states.register_opacity(Opacity(128));
+        let mut writer = DocumentWriter::default();
+        let dictionary = states.dictionary(&PdfResources::default(), &mut writer)?;
+        let Some(Object::Reference(state_id)) = dictionary.get("CA128") else {
+            panic!("The opacity state was not written");
+        };
+        let Some(Object::Dictionary(state)) = writer.document.get_object(state_id) else {
+            panic!("The state is not a dictionary");
+        };
+        assert_eq!(state.get("ca"), Some(&Object::Real(Opacity(128).value())));
+        Ok(())
+    }
+}
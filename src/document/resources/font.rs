@@ -2,12 +2,24 @@ use std::{
     borrow::Cow,
     collections::{BTreeMap, HashSet},
     fmt::Debug,
+    sync::{Arc, OnceLock},
 };
 mod builtin;
+mod builtin_widths;
+mod cache;
 pub(crate) mod emoji_rasterizer;
 mod font_type;
+mod metrics;
+mod metrics_cache;
+mod type3;
+mod winansi;
 pub use builtin::*;
+pub use cache::*;
 pub use font_type::*;
+pub use metrics::*;
+pub use metrics_cache::*;
+pub use type3::*;
+pub use winansi::*;
 pub mod owned_ttf_parser;
 pub mod static_ttf_parser;
 use tracing::debug;
@@ -17,7 +29,7 @@ use tux_pdf_low::{
 };
 
 use crate::{
-    TuxPdfError,
+    ResourceNotRegistered, TuxPdfError,
     document::{
         DocumentWriter,
         types::{
@@ -26,9 +38,12 @@ use crate::{
         },
     },
     graphics::size::Size,
-    units::{Pt, UnitType},
+    units::Pt,
 };
 
+use owned_ttf_parser::OwnedPdfTtfFont;
+use static_ttf_parser::StaticTtfFace;
+
 use super::{IdType, ObjectMapType, XObjectId};
 
 /// Controls how color emoji fonts are rendered in the PDF.
@@ -72,7 +87,7 @@ impl EmojiGlyphCache {
     }
 }
 
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct GlyphMetrics {
     /// The width of the glyph, typically the horizontal advance.
     pub width: u32,
@@ -139,12 +154,15 @@ impl From<String> for FontId {
 pub struct PdfFontMap {
     pub(crate) map: BTreeMap<FontId, ParsedFont>,
     pub(crate) registered_builtin_fonts: HashSet<BuiltinFont>,
+    pub(crate) builtin_fallback: BuiltinFontFallback,
+    pub(crate) default_font: Option<FontRef>,
+    pub(crate) type3_fonts: BTreeMap<FontId, Type3Font>,
 }
 
 impl ObjectMapType for PdfFontMap {
     type IdType = FontId;
     fn has_id(&self, id: &Self::IdType) -> bool {
-        self.map.contains_key(id)
+        self.map.contains_key(id) || self.type3_fonts.contains_key(id)
     }
 }
 impl PdfFontMap {
@@ -152,6 +170,9 @@ impl PdfFontMap {
         Self {
             map: BTreeMap::new(),
             registered_builtin_fonts: HashSet::new(),
+            builtin_fallback: BuiltinFontFallback::default(),
+            default_font: None,
+            type3_fonts: BTreeMap::new(),
         }
     }
 
@@ -160,6 +181,83 @@ impl PdfFontMap {
         self.registered_builtin_fonts.insert(font);
         FontRef::Builtin(font)
     }
+    /// Sets what happens to characters that are not in WinAnsiEncoding when a builtin font is used
+    ///
+    /// A fallback [BuiltinFontFallback::Font] must be registered in this map
+    pub fn set_builtin_fallback(
+        &mut self,
+        fallback: BuiltinFontFallback,
+    ) -> Result<(), TuxPdfError> {
+        if let BuiltinFontFallback::Font(font_id) = &fallback
+            && !self.map.contains_key(font_id)
+        {
+            return Err(ResourceNotRegistered::FontId(font_id.clone()).into());
+        }
+        self.builtin_fallback = fallback;
+        Ok(())
+    }
+    pub fn builtin_fallback(&self) -> &BuiltinFontFallback {
+        &self.builtin_fallback
+    }
+    /// Sets the font used by [FontRef::Default]
+    ///
+    /// Builtin fonts are registered if they have not been. External fonts must be registered in this map.
+    /// Passing [FontRef::Default] goes back to Helvetica
+    pub fn set_default_font(&mut self, font_ref: FontRef) -> Result<(), TuxPdfError> {
+        match &font_ref {
+            FontRef::Default => {
+                self.default_font = None;
+                return Ok(());
+            }
+            FontRef::Builtin(builtin) => {
+                self.registered_builtin_fonts.insert(*builtin);
+            }
+            FontRef::External(font_id) => {
+                if !self.map.contains_key(font_id) {
+                    return Err(self.not_registered(&font_ref).into());
+                }
+            }
+            FontRef::Type3(font_id) => {
+                if !self.type3_fonts.contains_key(font_id) {
+                    return Err(self.not_registered(&font_ref).into());
+                }
+            }
+        }
+        self.default_font = Some(font_ref);
+        Ok(())
+    }
+    /// The font used by [FontRef::Default]
+    pub fn default_font(&self) -> &FontRef {
+        self.default_font.as_ref().unwrap_or(&DEFAULT_FONT)
+    }
+    /// Replaces [FontRef::Default] with the default font of the document. Other fonts are returned as is
+    pub fn resolve<'font>(&'font self, font_ref: &'font FontRef) -> &'font FontRef {
+        match font_ref {
+            FontRef::Default => self.default_font(),
+            other => other,
+        }
+    }
+    /// The name of the font in the resources of a page. [FontRef::Default] is resolved first
+    pub fn resource_name(&self, font_ref: &FontRef) -> Object {
+        match self.resolve(font_ref) {
+            FontRef::External(id) | FontRef::Type3(id) => id.clone().into(),
+            FontRef::Builtin(builtin) => Object::name(builtin.dedicated_font_id()),
+            // The default font is never the default reference itself
+            FontRef::Default => self.resource_name(self.default_font()),
+        }
+    }
+    /// The error for a font that is not in this map. [FontRef::Default] is resolved first
+    pub fn not_registered(&self, font_ref: &FontRef) -> ResourceNotRegistered {
+        match self.resolve(font_ref) {
+            FontRef::External(font_id) | FontRef::Type3(font_id) => {
+                ResourceNotRegistered::FontId(font_id.clone())
+            }
+            FontRef::Builtin(builtin_font) => {
+                ResourceNotRegistered::BuiltinFontNotRegistered(*builtin_font)
+            }
+            FontRef::Default => self.not_registered(self.default_font()),
+        }
+    }
     pub fn is_built_in_registered(&self, font: &BuiltinFont) -> bool {
         self.registered_builtin_fonts.contains(font)
     }
@@ -173,18 +271,24 @@ impl PdfFontMap {
         } else {
             self.new_id()
         };
-        let has_color_glyphs = font.has_color_glyphs();
-        self.map.insert(
-            font_id.clone(),
-            ParsedFont {
-                font,
-                font_name: font_id.0.clone(),
-                has_color_glyphs,
-            },
-        );
+        self.map
+            .insert(font_id.clone(), ParsedFont::new(font, font_id.0.clone()));
 
         Ok(FontRef::External(font_id))
     }
+    /// Registers a TrueType or OpenType font from its bytes
+    ///
+    /// Static data such as `include_bytes!` is parsed in place. Owned data is moved into the font
+    pub fn register_font_bytes(
+        &mut self,
+        data: impl Into<Cow<'static, [u8]>>,
+    ) -> Result<FontRef, TuxPdfError> {
+        let font: ExternalFont = match data.into() {
+            Cow::Borrowed(data) => StaticTtfFace::from_slice(data, 0)?.into(),
+            Cow::Owned(data) => OwnedPdfTtfFont::new_vec(data, 0)?.into(),
+        };
+        self.register_external_font(font)
+    }
 
     pub fn register_external_font_with_name(
         &mut self,
@@ -193,15 +297,24 @@ impl PdfFontMap {
     ) -> Result<FontRef, TuxPdfError> {
         let font = font.into();
         let font_id = self.new_id_with_prefix(FontId(name.into()));
-        let has_color_glyphs = font.has_color_glyphs();
-        self.map.insert(
-            font_id.clone(),
-            ParsedFont {
-                font,
-                font_name: font_id.0.clone(),
-                has_color_glyphs,
-            },
-        );
+        self.map
+            .insert(font_id.clone(), ParsedFont::new(font, font_id.0.clone()));
+
+        Ok(FontRef::External(font_id))
+    }
+
+    /// Registers a font from a [FontCache]
+    ///
+    /// The parsed font and its prepared font data are shared with the cache instead of being created again
+    pub fn register_cached_font(&mut self, font: &CachedFont) -> Result<FontRef, TuxPdfError> {
+        let mut font = font.parsed_font().clone();
+        let font_id = if let Some(font_name) = font.font.font_name() {
+            self.new_id_with_prefix(FontId(font_name))
+        } else {
+            self.new_id()
+        };
+        font.font_name = font_id.0.clone();
+        self.map.insert(font_id.clone(), font);
 
         Ok(FontRef::External(font_id))
     }
@@ -209,7 +322,22 @@ impl PdfFontMap {
     pub fn get_external_font(&self, font_id: &FontId) -> Option<&ParsedFont> {
         self.map.get(font_id)
     }
-    pub(crate) fn dictionary(self, writer: &mut DocumentWriter) -> Dictionary {
+    /// Registers a font made of vector glyphs
+    ///
+    /// # Errors
+    /// If the font has more than [TYPE3_MAX_GLYPHS] glyphs
+    pub fn register_type3_font(&mut self, font: Type3Font) -> Result<FontRef, TuxPdfError> {
+        if font.number_of_glyphs() > TYPE3_MAX_GLYPHS {
+            return Err(TuxPdfError::TooManyType3Glyphs(font.number_of_glyphs()));
+        }
+        let font_id = self.new_id_with_prefix(FontId(font.name.clone()));
+        self.type3_fonts.insert(font_id.clone(), font);
+        Ok(FontRef::Type3(font_id))
+    }
+    pub fn get_type3_font(&self, font_id: &FontId) -> Option<&Type3Font> {
+        self.type3_fonts.get(font_id)
+    }
+    pub(crate) fn dictionary(self, writer: &mut DocumentWriter) -> Result<Dictionary, TuxPdfError> {
         let mut dict = Dictionary::new();
         for (font_id, font) in self.map {
             let font_dictionary = font.dictionary(writer);
@@ -224,11 +352,18 @@ impl PdfFontMap {
             let font_direct_id = writer.insert_object(font_def.into());
             dict.set(font_id.to_owned(), font_direct_id);
         }
-        dict
+        for (font_id, font) in self.type3_fonts {
+            let font_dictionary = font.dictionary(writer)?;
+            let font_direct_id = writer.insert_object(Object::from(font_dictionary));
+            dict.set(font_id.0, font_direct_id);
+        }
+        Ok(dict)
     }
     pub fn internal_font_type(&self, font_ref: &FontRef) -> Option<InternalFontTypes<'_>> {
-        match font_ref {
+        match self.resolve(font_ref) {
+            FontRef::Default => None,
             FontRef::External(id) => self.map.get(id).map(InternalFontTypes::External),
+            FontRef::Type3(id) => self.type3_fonts.get(id).map(InternalFontTypes::Type3),
             FontRef::Builtin(builtin) => {
                 if self.is_built_in_registered(builtin) {
                     Some(InternalFontTypes::Builtin(*builtin))
@@ -268,12 +403,14 @@ pub trait FontType {
 pub enum InternalFontTypes<'font> {
     External(&'font ParsedFont),
     Builtin(BuiltinFont),
+    Type3(&'font Type3Font),
 }
 impl FontType for InternalFontTypes<'_> {
     fn encode_text(&self, text: &str) -> Vec<u8> {
         match self {
             InternalFontTypes::External(font) => font.encode_text(text),
             InternalFontTypes::Builtin(builtin) => builtin.encode_text(text),
+            InternalFontTypes::Type3(font) => font.encode_text(text),
         }
     }
 
@@ -281,6 +418,7 @@ impl FontType for InternalFontTypes<'_> {
         match self {
             InternalFontTypes::External(font) => font.calculate_size_of_text(text, params),
             InternalFontTypes::Builtin(builtin) => builtin.calculate_size_of_text(text, params),
+            InternalFontTypes::Type3(font) => font.calculate_size_of_text(text, params),
         }
     }
 
@@ -288,6 +426,7 @@ impl FontType for InternalFontTypes<'_> {
         match self {
             InternalFontTypes::External(font) => font.size_of_char(c, params),
             InternalFontTypes::Builtin(builtin) => builtin.size_of_char(c, params),
+            InternalFontTypes::Type3(font) => font.size_of_char(c, params),
         }
     }
     fn calculate_height_of_text<P: FontRenderSizeParams>(&self, text: &str, params: &P) -> Pt {
@@ -299,36 +438,37 @@ pub struct ParsedFont {
     pub(crate) font: ExternalFont,
     pub(crate) font_name: String,
     pub(crate) has_color_glyphs: bool,
+    /// Built the first time text is measured with the font
+    pub(crate) metrics_cache: Arc<OnceLock<GlyphMetricsCache>>,
+    pub(crate) write_data: Arc<OnceLock<FontWriteData>>,
 }
 impl FontType for ParsedFont {
     fn calculate_size_of_text<P: FontRenderSizeParams>(&self, text: &str, params: &P) -> Size {
-        let mut width = Pt::default();
-        let mut height = f32::default();
+        // Sum in font units and scale once at the end
+        let mut width = 0u32;
+        let mut height = 0u32;
         for c in text.chars() {
             if let Some(glyph_id) = self.get_glyph_id(c)
-                && let Some(metrics) = self.font.glyph_metrics(glyph_id)
+                && let Some(metrics) = self.metrics_cache().glyph_metrics(&self.font, glyph_id)
             {
-                let (glyph_width, glyph_height) =
-                    metrics.glyph_size_in_points(self.font.units_per_em(), params.font_size());
-                width += glyph_width;
-                height = height.max(glyph_height.0);
+                width += metrics.width;
+                height = height.max(metrics.height);
             }
         }
+        let (width, height) = GlyphMetrics { width, height }
+            .glyph_size_in_points(self.metrics_cache().units_per_em(), params.font_size());
         debug!(
             "Size of text {text:?} Width: {:#?}, Height: {:#?}",
             width, height
         );
-        Size {
-            width: width.pt(),
-            height: height.pt(),
-        }
+        Size { width, height }
     }
     fn size_of_char<P: FontRenderSizeParams>(&self, c: char, params: &P) -> Option<Size> {
         if let Some(glyph_id) = self.get_glyph_id(c)
-            && let Some(metrics) = self.font.glyph_metrics(glyph_id)
+            && let Some(metrics) = self.metrics_cache().glyph_metrics(&self.font, glyph_id)
         {
-            let (glyph_width, glyph_height) =
-                metrics.glyph_size_in_points(self.font.units_per_em(), params.font_size());
+            let (glyph_width, glyph_height) = metrics
+                .glyph_size_in_points(self.metrics_cache().units_per_em(), params.font_size());
             return Some(Size {
                 width: glyph_width,
                 height: glyph_height,
@@ -345,6 +485,20 @@ impl FontType for ParsedFont {
     }
 }
 impl ParsedFont {
+    pub(crate) fn new(font: ExternalFont, font_name: String) -> Self {
+        let has_color_glyphs = font.has_color_glyphs();
+        Self {
+            font,
+            font_name,
+            has_color_glyphs,
+            metrics_cache: Arc::default(),
+            write_data: Arc::default(),
+        }
+    }
+    pub(crate) fn metrics_cache(&self) -> &GlyphMetricsCache {
+        self.metrics_cache
+            .get_or_init(|| GlyphMetricsCache::new(&self.font))
+    }
     pub(crate) fn dictionary(self, doc: &mut DocumentWriter) -> Dictionary {
         let bytes = self.font.font_bytes().to_vec();
         let font_stream = Stream::new(
@@ -354,20 +508,92 @@ impl ParsedFont {
             bytes,
         )
         .with_compression(false);
+        let write_data = self
+            .write_data
+            .get_or_init(|| FontWriteData::new(&self.font));
+
+        let cid_to_unicode_map =
+            generate_cid_to_unicode_map(&self.font_name, &write_data.cmap_blocks);
+
+        let cid_to_unicode_map_stream =
+            Stream::new(Dictionary::new(), cid_to_unicode_map.as_bytes().to_vec());
+        let cid_to_unicode_map_stream_id = doc.insert_object(cid_to_unicode_map_stream.into());
+
+        let cid_system_info = CIDSystemInfo {
+            registry: "Adobe".into(),
+            ordering: "Identity".into(),
+            supplement: 0,
+        };
+        let font_stream_id = doc.insert_object(font_stream.into());
+
+        let font_descriptor = FontDescriptorBuilder::default()
+            .font_name(&self.font_name)
+            .ascent(self.font.ascender())
+            .descent(self.font.descender())
+            .cap_height(self.font.ascender())
+            .italic_angle(self.font.italic_angle())
+            .flags(FontFlags::ITALIC)
+            .stem_v(80)
+            .font_b_box(Some(write_data.font_bbox.clone()))
+            .font_file2(font_stream_id)
+            .build()
+            .unwrap();
+        let descriptor_dict: Dictionary = font_descriptor.into();
+        let descriptor_id = doc.insert_object(descriptor_dict.into());
+        let cid_font_two = FontObject {
+            base_font: Cow::Borrowed(&self.font_name),
+            encoding: None,
+            sub_type: CidFontType2 {
+                cid_system_info,
+                font_descriptor: descriptor_id,
+                dw: Some(1000),
+                w: Some(write_data.widths.clone()),
+                dw2: None,
+                w2: None,
+                cid_to_gid_map: None,
+            },
+        };
+        let cid_font_two_dict = cid_font_two.into_dictionary();
+        let font_primary = FontObject {
+            base_font: Cow::Borrowed(&self.font_name),
+            encoding: Some(FontEncoding::IdentityH),
+            sub_type: Type0Font {
+                descendant_fonts: vec![cid_font_two_dict],
+                to_unicode: Some(cid_to_unicode_map_stream_id),
+            },
+        };
 
+        font_primary.into_dictionary()
+    }
+    pub fn get_glyph_id(&self, c: char) -> Option<u16> {
+        match self.metrics_cache().cached_glyph_id(c) {
+            Some(glyph_id) => glyph_id,
+            None => self.font.glyph_id(c),
+        }
+    }
+}
+/// The parts of the font dictionary that only depend on the font file
+///
+/// Shared by every clone of a [ParsedFont] so a font registered from a [FontCache] is only prepared once
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FontWriteData {
+    /// Glyph id to unicode blocks for the ToUnicode cmap
+    cmap_blocks: Vec<Vec<(u32, u32)>>,
+    /// The W array of the CID font
+    widths: Vec<Object>,
+    font_bbox: Vec<i64>,
+}
+impl FontWriteData {
+    fn new(font: &ExternalFont) -> Self {
         let mut max_height = 0;
         // Total width of all characters
         let mut total_width = 0;
-        // Widths (or heights, depends on self.vertical_writing)
-        // of the individual characters, indexed by glyph id
-        let mut widths = Vec::<(u32, u32)>::new();
-
         // Glyph IDs - (Unicode IDs - character width, character height)
         let mut cmap = BTreeMap::<u32, (u32, u32, u32)>::new();
         cmap.insert(0, (0, 1000, 1000));
 
-        for (glyph_id, c) in self.font.glyph_ids() {
-            if let Some(glyph_metrics) = self.font.glyph_metrics(glyph_id) {
+        for (glyph_id, c) in font.glyph_ids() {
+            if let Some(glyph_metrics) = font.glyph_metrics(glyph_id) {
                 if glyph_metrics.height > max_height {
                     max_height = glyph_metrics.height;
                 }
@@ -405,21 +631,13 @@ impl ParsedFont {
                     cur_first_bit = (*glyph_id >> 8) as u16;
                 }
 
-                let (unicode, width, _) = *unicode_width_tuple;
+                let (unicode, _, _) = *unicode_width_tuple;
                 current_cmap_block.push((*glyph_id, unicode));
-                widths.push((*glyph_id, width));
             }
 
             all_cmap_blocks.push(current_cmap_block);
         }
 
-        let cid_to_unicode_map =
-            generate_cid_to_unicode_map(self.font_name.clone(), all_cmap_blocks);
-
-        let cid_to_unicode_map_stream =
-            Stream::new(Dictionary::new(), cid_to_unicode_map.as_bytes().to_vec());
-        let cid_to_unicode_map_stream_id = doc.insert_object(cid_to_unicode_map_stream.into());
-
         // encode widths / heights so that they fit into what PDF expects
         // see page 439 in the PDF 1.7 reference
         // basically widths_list will contain objects like this:
@@ -432,10 +650,10 @@ impl ParsedFont {
         let mut current_width_vec = Vec::<Object>::new();
 
         // scale the font width so that it sort-of fits into an 1000 unit square
-        let percentage_font_scaling = 1000.0 / (self.font.units_per_em() as f32);
+        let percentage_font_scaling = 1000.0 / (font.units_per_em() as f32);
 
-        for gid in 0..self.font.glyph_count() {
-            if let Some(GlyphMetrics { width, .. }) = self.font.glyph_metrics(gid) {
+        for gid in 0..font.glyph_count() {
+            if let Some(GlyphMetrics { width, .. }) = font.glyph_metrics(gid) {
                 if gid == current_high_gid {
                     current_width_vec.push(Object::Integer(
                         (width as f32 * percentage_font_scaling) as i64,
@@ -464,75 +682,34 @@ impl ParsedFont {
             (total_width as i64),
             (max_height as i64),
         ];
-        let cid_system_info = CIDSystemInfo {
-            registry: "Adobe".into(),
-            ordering: "Identity".into(),
-            supplement: 0,
-        };
-        let font_stream_id = doc.insert_object(font_stream.into());
-
-        let font_descriptor = FontDescriptorBuilder::default()
-            .font_name(&self.font_name)
-            .ascent(self.font.ascender())
-            .descent(self.font.descender())
-            .cap_height(self.font.ascender())
-            .italic_angle(self.font.italic_angle())
-            .flags(FontFlags::ITALIC)
-            .stem_v(80)
-            .font_b_box(Some(font_bbox))
-            .font_file2(font_stream_id)
-            .build()
-            .unwrap();
-        let descriptor_dict: Dictionary = font_descriptor.into();
-        let descriptor_id = doc.insert_object(descriptor_dict.into());
-        let cid_font_two = FontObject {
-            base_font: Cow::Borrowed(&self.font_name),
-            encoding: None,
-            sub_type: CidFontType2 {
-                cid_system_info,
-                font_descriptor: descriptor_id,
-                dw: Some(1000),
-                w: Some(widths_list),
-                dw2: None,
-                w2: None,
-                cid_to_gid_map: None,
-            },
-        };
-        let cid_font_two_dict = cid_font_two.into_dictionary();
-        let font_primary = FontObject {
-            base_font: Cow::Borrowed(&self.font_name),
-            encoding: Some(FontEncoding::IdentityH),
-            sub_type: Type0Font {
-                descendant_fonts: vec![cid_font_two_dict],
-                to_unicode: Some(cid_to_unicode_map_stream_id),
-            },
-        };
-
-        font_primary.into_dictionary()
-    }
-    pub fn get_glyph_id(&self, c: char) -> Option<u16> {
-        self.font.glyph_id(c)
+        Self {
+            cmap_blocks: all_cmap_blocks,
+            widths: widths_list,
+            font_bbox,
+        }
     }
 }
 #[derive(Debug, Clone, PartialEq)]
 pub enum FontRef {
     External(FontId),
     Builtin(BuiltinFont),
+    /// A font registered with [PdfFontMap::register_type3_font]
+    Type3(FontId),
+    /// The default font of the document. Helvetica unless set with [PdfFontMap::set_default_font]
+    Default,
 }
+/// The font used by [FontRef::Default] when the document does not set one
+static DEFAULT_FONT: FontRef = FontRef::Builtin(BuiltinFont::Helvetica);
 
 impl FontRef {
-    pub fn id(&self) -> &str {
+    /// The name of the font in the resources of a page
+    ///
+    /// [None] for [FontRef::Default] as it depends on the document. See [PdfFontMap::resolve]
+    pub fn id(&self) -> Option<&str> {
         match self {
-            FontRef::External(id) => &id.0,
-            FontRef::Builtin(builtin) => builtin.dedicated_font_id(),
-        }
-    }
-}
-impl From<FontRef> for Object {
-    fn from(font_ref: FontRef) -> Self {
-        match font_ref {
-            FontRef::External(id) => id.into(),
-            FontRef::Builtin(builtin) => Object::name(builtin.dedicated_font_id()),
+            FontRef::External(id) | FontRef::Type3(id) => Some(&id.0),
+            FontRef::Builtin(builtin) => Some(builtin.dedicated_font_id()),
+            FontRef::Default => None,
         }
     }
 }
@@ -552,15 +729,15 @@ fn unicode_to_utf16be_hex(unicode: u32) -> String {
     }
 }
 
-fn generate_cid_to_unicode_map(face_name: String, all_cmap_blocks: Vec<Vec<(u32, u32)>>) -> String {
-    let mut cid_to_unicode_map = gid_to_unicode_beg(face_name.as_str()).to_string();
+fn generate_cid_to_unicode_map(face_name: &str, all_cmap_blocks: &[Vec<(u32, u32)>]) -> String {
+    let mut cid_to_unicode_map = gid_to_unicode_beg(face_name).to_string();
 
     for cmap_block in all_cmap_blocks
-        .into_iter()
+        .iter()
         .filter(|block| !block.is_empty() || block.len() < 100)
     {
         cid_to_unicode_map.push_str(format!("{} beginbfchar\r\n", cmap_block.len()).as_str());
-        for (glyph_id, unicode) in cmap_block {
+        for &(glyph_id, unicode) in cmap_block {
             cid_to_unicode_map.push_str(
                 format!("<{glyph_id:04X}> <{}>\n", unicode_to_utf16be_hex(unicode)).as_str(),
             );
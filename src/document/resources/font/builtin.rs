@@ -3,10 +3,10 @@ use tux_pdf_low::types::Dictionary;
 use crate::{
     document::types::{BuiltinFontSubType, FontEncoding, FontObject, PdfDirectoryType},
     graphics::size::Size,
-    units::{Pt, UnitType},
+    units::Pt,
 };
 
-use super::{FontRenderSizeParams, FontType};
+use super::{FontRenderSizeParams, FontType, GlyphMetrics, win_ansi_code};
 
 /// The 14 built-in fonts per the PDF specification.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -27,22 +27,29 @@ pub enum BuiltinFont {
     ZapfDingbats,
 }
 impl FontType for BuiltinFont {
+    /// Uses the widths from [BuiltinFont::glyph_width]
     fn calculate_size_of_text<P: FontRenderSizeParams>(&self, text: &str, params: &P) -> Size {
-        // TODO figure out how to calculate the size of text for built-in fonts
+        let width: u32 = text
+            .chars()
+            .map(|c| u32::from(self.glyph_width_or_default(c)))
+            .sum();
         Size {
-            width: (params.font_size() * Pt::from(text.len())) / 2f32.pt(),
+            width: width_in_points(width, params.font_size()),
             height: params.font_size(),
         }
     }
 
-    fn size_of_char<P: FontRenderSizeParams>(&self, _: char, params: &P) -> Option<Size> {
+    fn size_of_char<P: FontRenderSizeParams>(&self, c: char, params: &P) -> Option<Size> {
         Some(Size {
-            width: (params.font_size()) / 2f32.pt(),
+            width: width_in_points(self.glyph_width_or_default(c).into(), params.font_size()),
             height: params.font_size(),
         })
     }
+    /// Characters outside of WinAnsiEncoding are encoded as `?`
     fn encode_text(&self, text: &str) -> Vec<u8> {
-        text.as_bytes().to_vec()
+        text.chars()
+            .map(|c| win_ansi_code(c).unwrap_or(b'?'))
+            .collect()
     }
 }
 impl From<BuiltinFont> for Dictionary {
@@ -55,6 +62,12 @@ impl From<BuiltinFont> for Dictionary {
         .into_dictionary()
     }
 }
+/// The standard 14 fonts use 1000 units per em
+fn width_in_points(width: u32, font_size: Pt) -> Pt {
+    GlyphMetrics { width, height: 0 }
+        .glyph_size_in_points(1000, font_size)
+        .0
+}
 macro_rules! builtin_font {
     (
         $(
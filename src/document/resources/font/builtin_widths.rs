@@ -0,0 +1,205 @@
+use super::{BuiltinFont, win_ansi_code};
+
+/// Width of every Courier glyph
+const COURIER_WIDTH: u16 = 600;
+
+/// Widths for the glyphs without an entry in the tables.
+///
+/// Symbol and ZapfDingbats are not mapped to ASCII so they use this for every character
+const DEFAULT_WIDTH: u16 = 500;
+
+/// Helvetica and Helvetica-Oblique
+const HELVETICA: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667,
+    611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500,
+    222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+/// Helvetica-Bold and Helvetica-BoldOblique
+const HELVETICA_BOLD: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611, 975, 722, 722, 722, 722, 667,
+    611, 778, 722, 278, 556, 722, 611, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 333, 278, 333, 584, 556, 333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556,
+    278, 889, 611, 611, 611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+/// Times-Roman
+const TIMES_ROMAN: [u16; 95] = [
+    250, 333, 408, 500, 500, 833, 778, 180, 333, 333, 500, 564, 250, 333, 250, 278, 500, 500, 500,
+    500, 500, 500, 500, 500, 500, 500, 278, 278, 564, 564, 564, 444, 921, 722, 667, 667, 722, 611,
+    556, 722, 722, 333, 389, 722, 611, 889, 722, 722, 556, 722, 667, 556, 611, 722, 722, 944, 722,
+    722, 611, 333, 278, 333, 469, 500, 333, 444, 500, 444, 500, 444, 333, 500, 500, 278, 278, 500,
+    278, 778, 500, 500, 500, 500, 333, 389, 278, 500, 500, 722, 500, 500, 444, 480, 200, 480, 541,
+];
+
+/// Times-Bold
+const TIMES_BOLD: [u16; 95] = [
+    250, 333, 555, 500, 500, 1000, 833, 278, 333, 333, 500, 570, 250, 333, 250, 278, 500, 500, 500,
+    500, 500, 500, 500, 500, 500, 500, 333, 333, 570, 570, 570, 500, 930, 722, 667, 722, 722, 667,
+    611, 778, 778, 389, 500, 778, 667, 944, 722, 778, 611, 778, 722, 556, 667, 722, 722, 1000, 722,
+    722, 667, 333, 278, 333, 581, 500, 333, 500, 556, 444, 556, 444, 333, 500, 556, 278, 333, 556,
+    278, 833, 556, 500, 556, 556, 444, 389, 333, 556, 500, 722, 500, 500, 444, 394, 220, 394, 520,
+];
+
+/// Times-Italic
+const TIMES_ITALIC: [u16; 95] = [
+    250, 333, 420, 500, 500, 833, 778, 214, 333, 333, 500, 675, 250, 333, 250, 278, 500, 500, 500,
+    500, 500, 500, 500, 500, 500, 500, 333, 333, 675, 675, 675, 500, 920, 611, 611, 667, 722, 611,
+    611, 722, 722, 333, 444, 667, 556, 833, 667, 722, 611, 722, 611, 500, 556, 722, 611, 833, 611,
+    556, 556, 389, 278, 389, 422, 500, 333, 500, 500, 444, 500, 444, 278, 500, 500, 278, 278, 444,
+    278, 722, 500, 500, 500, 500, 389, 389, 278, 500, 444, 667, 444, 444, 389, 400, 275, 400, 541,
+];
+
+/// Times-BoldItalic
+const TIMES_BOLD_ITALIC: [u16; 95] = [
+    250, 389, 555, 500, 500, 833, 778, 278, 333, 333, 500, 570, 250, 333, 250, 278, 500, 500, 500,
+    500, 500, 500, 500, 500, 500, 500, 333, 333, 570, 570, 570, 500, 832, 667, 667, 667, 722, 667,
+    667, 722, 778, 389, 500, 667, 611, 889, 722, 722, 611, 722, 667, 556, 611, 722, 667, 889, 667,
+    611, 611, 333, 278, 333, 570, 500, 333, 500, 500, 444, 500, 444, 333, 500, 556, 278, 278, 500,
+    278, 778, 556, 500, 500, 500, 389, 389, 278, 556, 444, 667, 500, 444, 389, 348, 220, 348, 570,
+];
+
+/// Helvetica and Helvetica-Oblique. WinAnsiEncoding codes 0x80 to 0xFF. Zero for the undefined codes
+const HELVETICA_HIGH: [u16; 128] = [
+    556, 0, 222, 556, 333, 1000, 556, 556, 333, 1000, 667, 333, 1000, 0, 611, 0, 0, 222, 222, 333,
+    333, 350, 556, 1000, 333, 1000, 500, 333, 944, 0, 500, 667, 278, 333, 556, 556, 556, 556, 260,
+    556, 333, 737, 370, 556, 584, 333, 737, 333, 400, 584, 333, 333, 333, 556, 537, 278, 333, 333,
+    365, 556, 834, 834, 834, 611, 667, 667, 667, 667, 667, 667, 1000, 722, 667, 667, 667, 667, 278,
+    278, 278, 278, 722, 722, 778, 778, 778, 778, 778, 584, 778, 722, 722, 722, 722, 667, 667, 611,
+    556, 556, 556, 556, 556, 556, 889, 500, 556, 556, 556, 556, 278, 278, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 584, 611, 556, 556, 556, 556, 500, 556, 500,
+];
+
+/// Helvetica-Bold and Helvetica-BoldOblique. WinAnsiEncoding codes 0x80 to 0xFF. Zero for the undefined codes
+const HELVETICA_BOLD_HIGH: [u16; 128] = [
+    556, 0, 278, 556, 500, 1000, 556, 556, 333, 1000, 667, 333, 1000, 0, 611, 0, 0, 278, 278, 500,
+    500, 350, 556, 1000, 333, 1000, 556, 333, 944, 0, 500, 667, 278, 333, 556, 556, 556, 556, 280,
+    556, 333, 737, 370, 556, 584, 333, 737, 333, 400, 584, 333, 333, 333, 611, 556, 278, 333, 333,
+    365, 556, 834, 834, 834, 611, 722, 722, 722, 722, 722, 722, 1000, 722, 667, 667, 667, 667, 278,
+    278, 278, 278, 722, 722, 778, 778, 778, 778, 778, 584, 778, 722, 722, 722, 722, 667, 667, 611,
+    556, 556, 556, 556, 556, 556, 889, 556, 556, 556, 556, 556, 278, 278, 278, 278, 611, 611, 611,
+    611, 611, 611, 611, 584, 611, 611, 611, 611, 611, 556, 611, 556,
+];
+
+/// Times-Roman. WinAnsiEncoding codes 0x80 to 0xFF. Zero for the undefined codes
+const TIMES_ROMAN_HIGH: [u16; 128] = [
+    500, 0, 333, 500, 444, 1000, 500, 500, 333, 1000, 556, 333, 889, 0, 611, 0, 0, 333, 333, 444,
+    444, 350, 500, 1000, 333, 980, 389, 333, 722, 0, 444, 722, 250, 333, 500, 500, 500, 500, 200,
+    500, 333, 760, 276, 500, 564, 333, 760, 333, 400, 564, 300, 300, 333, 500, 453, 250, 333, 300,
+    310, 500, 750, 750, 750, 444, 722, 722, 722, 722, 722, 722, 889, 667, 611, 611, 611, 611, 333,
+    333, 333, 333, 722, 722, 722, 722, 722, 722, 722, 564, 722, 722, 722, 722, 722, 722, 556, 500,
+    444, 444, 444, 444, 444, 444, 667, 444, 444, 444, 444, 444, 278, 278, 278, 278, 500, 500, 500,
+    500, 500, 500, 500, 564, 500, 500, 500, 500, 500, 500, 500, 500,
+];
+
+/// Times-Bold. WinAnsiEncoding codes 0x80 to 0xFF. Zero for the undefined codes
+const TIMES_BOLD_HIGH: [u16; 128] = [
+    500, 0, 333, 500, 500, 1000, 500, 500, 333, 1000, 556, 333, 1000, 0, 667, 0, 0, 333, 333, 500,
+    500, 350, 500, 1000, 333, 1000, 389, 333, 722, 0, 444, 722, 250, 333, 500, 500, 500, 500, 220,
+    500, 333, 747, 300, 500, 570, 333, 747, 333, 400, 570, 300, 300, 333, 556, 540, 250, 333, 300,
+    330, 500, 750, 750, 750, 500, 722, 722, 722, 722, 722, 722, 1000, 722, 667, 667, 667, 667, 389,
+    389, 389, 389, 722, 722, 778, 778, 778, 778, 778, 570, 778, 722, 722, 722, 722, 722, 611, 556,
+    500, 500, 500, 500, 500, 500, 722, 444, 444, 444, 444, 444, 278, 278, 278, 278, 500, 556, 500,
+    500, 500, 500, 500, 570, 500, 556, 556, 556, 556, 500, 556, 500,
+];
+
+/// Times-Italic. WinAnsiEncoding codes 0x80 to 0xFF. Zero for the undefined codes
+const TIMES_ITALIC_HIGH: [u16; 128] = [
+    500, 0, 333, 500, 556, 889, 500, 500, 333, 1000, 500, 333, 944, 0, 556, 0, 0, 333, 333, 556,
+    556, 350, 500, 889, 333, 980, 389, 333, 667, 0, 389, 556, 250, 389, 500, 500, 500, 500, 275,
+    500, 333, 760, 276, 500, 675, 333, 760, 333, 400, 675, 300, 300, 333, 500, 523, 250, 333, 300,
+    310, 500, 750, 750, 750, 500, 611, 611, 611, 611, 611, 611, 889, 667, 611, 611, 611, 611, 333,
+    333, 333, 333, 722, 667, 722, 722, 722, 722, 722, 675, 722, 722, 722, 722, 722, 556, 611, 500,
+    500, 500, 500, 500, 500, 500, 667, 444, 444, 444, 444, 444, 278, 278, 278, 278, 500, 500, 500,
+    500, 500, 500, 500, 675, 500, 500, 500, 500, 500, 444, 500, 444,
+];
+
+/// Times-BoldItalic. WinAnsiEncoding codes 0x80 to 0xFF. Zero for the undefined codes
+const TIMES_BOLD_ITALIC_HIGH: [u16; 128] = [
+    500, 0, 333, 500, 500, 1000, 500, 500, 333, 1000, 556, 333, 944, 0, 611, 0, 0, 333, 333, 500,
+    500, 350, 500, 1000, 333, 1000, 389, 333, 722, 0, 389, 611, 250, 389, 500, 500, 500, 500, 220,
+    500, 333, 747, 266, 500, 606, 333, 747, 333, 400, 570, 300, 300, 333, 576, 500, 250, 333, 300,
+    300, 500, 750, 750, 750, 500, 667, 667, 667, 667, 667, 667, 944, 667, 667, 667, 667, 667, 389,
+    389, 389, 389, 722, 722, 722, 722, 722, 722, 722, 570, 722, 722, 722, 722, 722, 611, 611, 500,
+    500, 500, 500, 500, 500, 500, 722, 444, 444, 444, 444, 444, 278, 278, 278, 278, 500, 556, 500,
+    500, 500, 500, 500, 570, 500, 556, 556, 556, 556, 444, 500, 444,
+];
+
+impl BuiltinFont {
+    /// The advance width of the character in font units. The standard 14 fonts use 1000 units per em
+    ///
+    /// Widths come from the Adobe Font Metrics of the font. Every WinAnsiEncoding character is covered.
+    /// Other characters return [None]
+    pub fn glyph_width(&self, c: char) -> Option<u16> {
+        let (table, high_table) = match self {
+            BuiltinFont::Courier
+            | BuiltinFont::CourierOblique
+            | BuiltinFont::CourierBold
+            | BuiltinFont::CourierBoldOblique => {
+                return win_ansi_code(c).map(|_| COURIER_WIDTH);
+            }
+            BuiltinFont::Helvetica | BuiltinFont::HelveticaOblique => (&HELVETICA, &HELVETICA_HIGH),
+            BuiltinFont::HelveticaBold | BuiltinFont::HelveticaBoldOblique => {
+                (&HELVETICA_BOLD, &HELVETICA_BOLD_HIGH)
+            }
+            BuiltinFont::TimesRoman => (&TIMES_ROMAN, &TIMES_ROMAN_HIGH),
+            BuiltinFont::TimesBold => (&TIMES_BOLD, &TIMES_BOLD_HIGH),
+            BuiltinFont::TimesItalic => (&TIMES_ITALIC, &TIMES_ITALIC_HIGH),
+            BuiltinFont::TimesBoldItalic => (&TIMES_BOLD_ITALIC, &TIMES_BOLD_ITALIC_HIGH),
+            BuiltinFont::Symbol | BuiltinFont::ZapfDingbats => return None,
+        };
+        match win_ansi_code(c)? {
+            code @ 0x20..=0x7E => Some(table[code as usize - 0x20]),
+            code @ 0x80.. => Some(high_table[code as usize - 0x80]).filter(|width| *width != 0),
+            _ => None,
+        }
+    }
+    /// Same as [BuiltinFont::glyph_width] but characters without metrics use a width of half an em
+    pub(super) fn glyph_width_or_default(&self, c: char) -> u16 {
+        self.glyph_width(c).unwrap_or(DEFAULT_WIDTH)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::{
+        document::{BuiltinFont, FontType},
+        graphics::TextStyle,
+        units::UnitType,
+    };
+
+    #[test]
+    fn afm_widths() {
+        assert_eq!(BuiltinFont::Helvetica.glyph_width('W'), Some(944));
+        assert_eq!(
+            BuiltinFont::HelveticaBoldOblique.glyph_width('i'),
+            Some(278)
+        );
+        assert_eq!(BuiltinFont::TimesRoman.glyph_width(' '), Some(250));
+        assert_eq!(BuiltinFont::CourierBold.glyph_width('m'), Some(600));
+        assert_eq!(BuiltinFont::Courier.glyph_width('\u{2026}'), Some(600));
+        assert_eq!(BuiltinFont::Helvetica.glyph_width('\u{e9}'), Some(556));
+        assert_eq!(BuiltinFont::Helvetica.glyph_width('\u{2026}'), Some(1000));
+        assert_eq!(BuiltinFont::TimesItalic.glyph_width('\u{2026}'), Some(889));
+        assert_eq!(BuiltinFont::TimesBold.glyph_width('\u{20ac}'), Some(500));
+        assert_eq!(BuiltinFont::HelveticaBold.glyph_width('\u{df}'), Some(611));
+        assert_eq!(BuiltinFont::TimesRoman.glyph_width('\u{3a9}'), None);
+        assert_eq!(BuiltinFont::Symbol.glyph_width('a'), None);
+    }
+    #[test]
+    fn text_width_uses_metrics() {
+        let style = TextStyle {
+            font_size: 10f32.pt(),
+            ..Default::default()
+        };
+        // H = 722, e = 556, l = 222, o = 556
+        let size = BuiltinFont::Helvetica.calculate_size_of_text("Hello", &style);
+        assert_eq!(size.width, 22.78f32.pt());
+        assert_eq!(size.height, 10f32.pt());
+
+        let size = BuiltinFont::Courier.calculate_size_of_text("Hello", &style);
+        assert_eq!(size.width, 30f32.pt());
+    }
+}
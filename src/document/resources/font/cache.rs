@@ -0,0 +1,118 @@
+use std::sync::{Arc, PoisonError, RwLock};
+
+use ahash::{HashMap, HashMapExt};
+
+use crate::TuxPdfError;
+
+use super::{ExternalFont, ParsedFont};
+
+/// Parsed fonts that can be shared between documents
+///
+/// Parsing a font, building its glyph metrics and preparing its font dictionary happens once per font.
+/// Every document that registers the font with [PdfFontMap::register_cached_font](super::PdfFontMap::register_cached_font) reuses that work.
+///
+/// Cloning the cache is cheap and all clones share the same fonts. So one cache can be handed to every thread of a server
+#[derive(Debug, Clone, Default)]
+pub struct FontCache {
+    fonts: Arc<RwLock<HashMap<String, CachedFont>>>,
+}
+/// A font stored in a [FontCache]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedFont(ParsedFont);
+impl CachedFont {
+    pub(crate) fn parsed_font(&self) -> &ParsedFont {
+        &self.0
+    }
+}
+impl FontCache {
+    pub fn new() -> Self {
+        Self {
+            fonts: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+    pub fn get(&self, key: &str) -> Option<CachedFont> {
+        self.fonts
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(key)
+            .cloned()
+    }
+    /// Adds a font to the cache. Replacing any font with the same key
+    pub fn insert(&self, key: impl Into<String>, font: impl Into<ExternalFont>) -> CachedFont {
+        let key = key.into();
+        let font = CachedFont(ParsedFont::new(font.into(), key.clone()));
+        self.fonts
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(key, font.clone());
+        font
+    }
+    /// Returns the font stored under `key` or loads and stores it
+    ///
+    /// `load` is called without holding the lock. If two threads load the same key at once the first stored font is kept
+    pub fn get_or_load<F, T>(&self, key: &str, load: F) -> Result<CachedFont, TuxPdfError>
+    where
+        F: FnOnce() -> Result<T, TuxPdfError>,
+        T: Into<ExternalFont>,
+    {
+        if let Some(font) = self.get(key) {
+            return Ok(font);
+        }
+        let font = CachedFont(ParsedFont::new(load()?.into(), key.to_owned()));
+        let mut fonts = self.fonts.write().unwrap_or_else(PoisonError::into_inner);
+        Ok(fonts.entry(key.to_owned()).or_insert(font).clone())
+    }
+    pub fn remove(&self, key: &str) -> Option<CachedFont> {
+        self.fonts
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(key)
+    }
+    pub fn len(&self) -> usize {
+        self.fonts
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::FontCache;
+    use crate::{
+        document::{PdfDocument, owned_ttf_parser::OwnedPdfTtfFont},
+        page::{PdfPage, page_sizes::A4},
+    };
+
+    #[test]
+    fn documents_share_cached_font() -> anyhow::Result<()> {
+        let cache = FontCache::new();
+        let load = || {
+            let path = crate::tests::fonts_dir()
+                .join("Roboto")
+                .join("Roboto-Regular.ttf");
+            OwnedPdfTtfFont::new_from_reader(std::fs::File::open(path)?, 0)
+        };
+        let font = cache.get_or_load("roboto", load)?;
+        let again = cache.get("roboto").expect("Font was just cached");
+        assert!(Arc::ptr_eq(
+            &font.parsed_font().metrics_cache,
+            &again.parsed_font().metrics_cache
+        ));
+
+        for title in ["First", "Second"] {
+            let mut doc = PdfDocument::new(title);
+            doc.font_map().register_cached_font(&font)?;
+            doc.add_page(PdfPage::new_from_page_size(A4));
+            doc.write_into_pdf_document_writer()?;
+            // The font data prepared by the first document is kept in the cache
+            assert!(font.parsed_font().write_data.get().is_some());
+        }
+        assert_eq!(cache.len(), 1);
+        Ok(())
+    }
+}
@@ -45,6 +45,10 @@ pub trait ExternalLoadedFont {
 }
 pub(crate) trait TtfParserFont {
     fn as_face_ref(&self) -> &ttf_parser::Face<'_>;
+    /// The table directory of the font. Used for lookups that do not need the entire font parsed
+    fn raw_face(&self) -> ttf_parser::RawFace<'_> {
+        *self.as_face_ref().raw_face()
+    }
 }
 impl<T> ExternalLoadedFont for T
 where
@@ -113,19 +117,16 @@ where
         }
     }
     fn font_bytes(&self) -> &[u8] {
-        self.as_face_ref().raw_face().data
+        self.raw_face().data
     }
     fn font_name(&self) -> Option<String> {
-        let family_name = self
-            .as_face_ref()
-            .names()
-            .get(1)
-            .and_then(|name| name.to_string());
-        let sub_family_name = self
-            .as_face_ref()
-            .names()
-            .get(2)
-            .and_then(|name| name.to_string());
+        let names = self
+            .raw_face()
+            .table(ttf_parser::Tag::from_bytes(b"name"))
+            .and_then(ttf_parser::name::Table::parse)?
+            .names;
+        let family_name = names.get(1).and_then(|name| name.to_string());
+        let sub_family_name = names.get(2).and_then(|name| name.to_string());
         match (family_name, sub_family_name) {
             (Some(family_name), Some(sub_family_name)) => {
                 Some(format!("{}-{}", family_name, sub_family_name))
@@ -136,11 +137,10 @@ where
     }
 
     fn has_color_glyphs(&self) -> bool {
-        let face = self.as_face_ref();
-        face.tables().svg.is_some()
-            || face.tables().colr.is_some()
-            || face.tables().cbdt.is_some()
-            || face.tables().sbix.is_some()
+        let raw_face = self.raw_face();
+        [b"SVG ", b"COLR", b"CBDT", b"sbix"]
+            .into_iter()
+            .any(|tag| raw_face.table(ttf_parser::Tag::from_bytes(tag)).is_some())
     }
 
     fn glyph_svg_data(&self, glyph_id: u16) -> Option<&[u8]> {
@@ -0,0 +1,133 @@
+use crate::{document::ResourceNotRegistered, units::Pt};
+
+use super::{BuiltinFont, ExternalLoadedFont, FontRef, ParsedFont, PdfFontMap, Type3Font};
+
+/// Vertical metrics of a font in font units
+///
+/// Use [FontRef::metrics] to get the metrics of a registered font
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FontMetrics {
+    /// Number of font units in one em. Builtin fonts use 1000
+    pub units_per_em: u16,
+    /// Distance from the baseline to the top of the tallest glyphs
+    pub ascender: i16,
+    /// Distance from the baseline to the bottom of the lowest glyphs. Usually negative
+    pub descender: i16,
+}
+impl FontMetrics {
+    fn scale(&self, value: i16, font_size: Pt) -> Pt {
+        Pt(value as f32 * font_size.0 / self.units_per_em as f32)
+    }
+    /// The ascent at the given font size
+    pub fn ascent(&self, font_size: Pt) -> Pt {
+        self.scale(self.ascender, font_size)
+    }
+    /// The descent at the given font size. Negative if the font goes below the baseline
+    pub fn descent(&self, font_size: Pt) -> Pt {
+        self.scale(self.descender, font_size)
+    }
+    /// Distance between the ascent and the descent at the given font size
+    pub fn line_height(&self, font_size: Pt) -> Pt {
+        self.ascent(font_size) - self.descent(font_size)
+    }
+}
+impl BuiltinFont {
+    /// Ascender and descender taken from the Adobe font metrics of the standard 14 fonts
+    pub fn metrics(&self) -> FontMetrics {
+        let (ascender, descender) = match self {
+            BuiltinFont::TimesRoman
+            | BuiltinFont::TimesBold
+            | BuiltinFont::TimesItalic
+            | BuiltinFont::TimesBoldItalic => (683, -217),
+            BuiltinFont::Helvetica
+            | BuiltinFont::HelveticaBold
+            | BuiltinFont::HelveticaOblique
+            | BuiltinFont::HelveticaBoldOblique => (718, -207),
+            BuiltinFont::Courier
+            | BuiltinFont::CourierOblique
+            | BuiltinFont::CourierBold
+            | BuiltinFont::CourierBoldOblique => (629, -157),
+            // Symbol fonts do not define an ascender so the bounding box is used
+            BuiltinFont::Symbol => (1010, -293),
+            BuiltinFont::ZapfDingbats => (820, -143),
+        };
+        FontMetrics {
+            units_per_em: 1000,
+            ascender,
+            descender,
+        }
+    }
+}
+impl ParsedFont {
+    pub fn metrics(&self) -> FontMetrics {
+        FontMetrics {
+            units_per_em: self.font.units_per_em(),
+            ascender: self.font.ascender(),
+            descender: self.font.descender(),
+        }
+    }
+}
+impl FontRef {
+    /// The metrics of the font this reference points to
+    pub fn metrics(&self, fonts: &PdfFontMap) -> Result<FontMetrics, ResourceNotRegistered> {
+        match fonts.resolve(self) {
+            FontRef::External(font_id) => fonts
+                .get_external_font(font_id)
+                .map(ParsedFont::metrics)
+                .ok_or_else(|| fonts.not_registered(self)),
+            FontRef::Type3(font_id) => fonts
+                .get_type3_font(font_id)
+                .map(Type3Font::metrics)
+                .ok_or_else(|| fonts.not_registered(self)),
+            FontRef::Builtin(builtin) => Ok(builtin.metrics()),
+            FontRef::Default => fonts.default_font().metrics(fonts),
+        }
+    }
+}
+/// The result of [PdfDocument::measure_text](crate::document::PdfDocument::measure_text)
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct TextMetrics {
+    pub width: Pt,
+    pub height: Pt,
+    /// Ascent of the font at the measured font size
+    pub ascent: Pt,
+    /// Descent of the font at the measured font size. Negative if the font goes below the baseline
+    pub descent: Pt,
+}
+impl TextMetrics {
+    /// Distance between the ascent and the descent
+    pub fn line_height(&self) -> Pt {
+        self.ascent - self.descent
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::{
+        document::{BuiltinFont, PdfDocument},
+        graphics::{TextStyle, size::RenderSize},
+        units::UnitType,
+    };
+
+    #[test]
+    fn builtin_metrics() {
+        let metrics = BuiltinFont::Helvetica.metrics();
+        assert_eq!(metrics.ascent(10f32.pt()), 7.18f32.pt());
+        assert_eq!(metrics.descent(10f32.pt()), (-2.07f32).pt());
+        assert_eq!(metrics.line_height(10f32.pt()), 9.25f32.pt());
+    }
+    #[test]
+    fn measure_text() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Measure Test");
+        let font_ref = doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        let style = TextStyle {
+            font_ref,
+            font_size: 10f32.pt(),
+            ..Default::default()
+        };
+        let metrics = doc.measure_text("Hello", &style)?;
+        assert_eq!(metrics.width, "Hello".render_size(&doc, &style)?.width);
+        assert_eq!(metrics.ascent, 7.18f32.pt());
+        assert!(metrics.width < 40f32.mm().pt());
+        Ok(())
+    }
+}
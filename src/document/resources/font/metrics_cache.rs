@@ -0,0 +1,104 @@
+use std::sync::OnceLock;
+
+use super::{ExternalLoadedFont, GlyphMetrics};
+
+/// Number of code points that get a direct lookup table.
+///
+/// Covers ASCII and Latin-1 which is the vast majority of table/report content.
+const DIRECT_LOOKUP_SIZE: usize = 256;
+
+/// Glyph metrics that are looked up once per font.
+///
+/// Text measurement is done many times during layout (every cell of a table is measured at least once).
+/// Querying ttf-parser for each character means walking the cmap and hmtx tables every time.
+/// This cache turns that into array lookups.
+///
+/// The cache is created the first time text is measured with the font.
+/// Metrics are read from the font the first time a glyph is measured. So fonts with many glyphs only pay for the glyphs that are used
+///
+/// All values are stored in font units so the same cache works for every font size.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GlyphMetricsCache {
+    units_per_em: u16,
+    /// Glyph ids for the first [DIRECT_LOOKUP_SIZE] code points
+    direct_glyph_ids: Vec<Option<u16>>,
+    /// Metrics indexed by glyph id. Filled in as the glyphs are measured
+    metrics: Vec<OnceLock<Option<GlyphMetrics>>>,
+}
+impl GlyphMetricsCache {
+    pub fn new<F: ExternalLoadedFont>(font: &F) -> Self {
+        let direct_glyph_ids = (0..DIRECT_LOOKUP_SIZE as u32)
+            .map(|code_point| char::from_u32(code_point).and_then(|c| font.glyph_id(c)))
+            .collect();
+        let metrics = (0..font.glyph_count()).map(|_| OnceLock::new()).collect();
+        Self {
+            units_per_em: font.units_per_em(),
+            direct_glyph_ids,
+            metrics,
+        }
+    }
+    pub fn units_per_em(&self) -> u16 {
+        self.units_per_em
+    }
+    /// Returns the glyph id if the character is within the direct lookup table
+    ///
+    /// Returns `None` if the character is outside of the table.
+    /// `Some(None)` means the character is in the table but the font does not have a glyph for it
+    #[inline]
+    pub fn cached_glyph_id(&self, c: char) -> Option<Option<u16>> {
+        self.direct_glyph_ids.get(c as usize).copied()
+    }
+    /// Returns the metrics of the glyph. Read from `font` the first time the glyph is measured
+    ///
+    /// `font` must be the font the cache was created with
+    #[inline]
+    pub fn glyph_metrics<F: ExternalLoadedFont>(
+        &self,
+        font: &F,
+        glyph_id: u16,
+    ) -> Option<GlyphMetrics> {
+        *self
+            .metrics
+            .get(glyph_id as usize)?
+            .get_or_init(|| font.glyph_metrics(glyph_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::document::{ExternalLoadedFont, owned_ttf_parser::OwnedPdfTtfFont};
+
+    use super::GlyphMetricsCache;
+
+    #[test]
+    fn cache_matches_font() -> anyhow::Result<()> {
+        let roboto = std::fs::File::open(
+            crate::tests::fonts_dir()
+                .join("Roboto")
+                .join("Roboto-Regular.ttf"),
+        )?;
+        let roboto = OwnedPdfTtfFont::new_from_reader(roboto, 0)?;
+        let cache = GlyphMetricsCache::new(&roboto);
+
+        assert_eq!(cache.units_per_em(), roboto.units_per_em());
+        for c in "Hello World! 0123456789 ÄÖÜ".chars() {
+            let glyph_id = cache
+                .cached_glyph_id(c)
+                .expect("Character should be cached");
+            assert_eq!(glyph_id, roboto.glyph_id(c));
+            let glyph_id = glyph_id.expect("Roboto has latin glyphs");
+            assert_eq!(
+                cache.glyph_metrics(&roboto, glyph_id),
+                roboto.glyph_metrics(glyph_id)
+            );
+            // The second lookup comes from the cache
+            assert_eq!(
+                cache.glyph_metrics(&roboto, glyph_id),
+                roboto.glyph_metrics(glyph_id)
+            );
+        }
+        assert_eq!(cache.cached_glyph_id('😊'), None);
+        assert_eq!(cache.glyph_metrics(&roboto, u16::MAX), None);
+        Ok(())
+    }
+}
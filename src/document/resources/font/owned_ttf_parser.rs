@@ -3,8 +3,15 @@
 //! Based on [owned_ttf_parser](https://crates.io/crates/owned_ttf_parser)
 //!
 //! Why not just use owned_ttf_parser? Because I don't want to have to deal with another crate that is not a huge amount of code.
-use std::{fmt::Debug, marker::PhantomPinned, mem, ops::Deref, pin::Pin, sync::Arc};
-use ttf_parser::Face;
+use std::{
+    fmt::Debug,
+    marker::PhantomPinned,
+    mem,
+    ops::Deref,
+    pin::Pin,
+    sync::{Arc, OnceLock},
+};
+use ttf_parser::{Face, RawFace, Tag, head, hhea, maxp};
 
 use crate::TuxPdfError;
 
@@ -25,6 +32,9 @@ impl TtfParserFont for OwnedPdfTtfFont {
     fn as_face_ref(&self) -> &ttf_parser::Face<'_> {
         self.inner.as_face_ref()
     }
+    fn raw_face(&self) -> RawFace<'_> {
+        self.inner.raw_face()
+    }
 }
 
 impl OwnedPdfTtfFont {
@@ -43,6 +53,21 @@ impl OwnedPdfTtfFont {
         let font = OwnedFace::from_vec_inner(data, index)?;
         Ok(Self::new(font))
     }
+    /// Create a font from shared data, such as the bytes of a memory mapped file, without copying it
+    ///
+    /// Only the table directory and the `head`, `hhea` and `maxp` tables are read when the font is created.
+    /// The rest of the font is parsed the first time it is used. So registering a font that is never used is cheap
+    ///
+    /// ```rust,no_run
+    /// use std::sync::Arc;
+    /// use tux_pdf::document::owned_ttf_parser::OwnedPdfTtfFont;
+    /// let data: Arc<[u8]> = std::fs::read("Roboto-Regular.ttf").unwrap().into();
+    /// let font = OwnedPdfTtfFont::new_lazy(data, 0).unwrap();
+    /// ```
+    pub fn new_lazy(data: Arc<[u8]>, index: u32) -> Result<Self, TuxPdfError> {
+        let font = OwnedFace::from_shared_inner(FaceData::Shared(data), index)?;
+        Ok(Self::new(font))
+    }
     pub fn new(face: OwnedFace) -> Self {
         Self {
             inner: Arc::new(face),
@@ -60,7 +85,7 @@ impl PartialEq for OwnedFace {
 }
 impl Debug for OwnedFace {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("OwnedFace").finish()
+        f.debug_struct("OwnedFace").finish_non_exhaustive()
     }
 }
 
@@ -69,54 +94,82 @@ impl OwnedFace {
         data: Vec<u8>,
         index: u32,
     ) -> Result<Self, ttf_parser::FaceParsingError> {
-        let inner = SelfRefVecFace::try_from_vec(data, index)?;
+        let inner = SelfRefVecFace::try_new(FaceData::Vec(data), index)?;
+        Ok(Self(inner))
+    }
+    fn from_shared_inner(data: FaceData, index: u32) -> Result<Self, ttf_parser::FaceParsingError> {
+        let inner = SelfRefVecFace::try_new(data, index)?;
         Ok(Self(inner))
     }
     pub fn as_slice(&self) -> &[u8] {
-        &self.0.data
+        self.0.data.as_slice()
     }
     pub fn as_face_ref(&self) -> &ttf_parser::Face<'_> {
         self.0.inner_ref()
     }
+    /// The table directory of the font. Does not parse the font
+    pub fn raw_face(&self) -> ttf_parser::RawFace<'_> {
+        self.0.raw_face
+    }
+    /// Shared data is copied
     pub fn into_vec(self) -> Vec<u8> {
         self.0.into_vec()
     }
 }
+enum FaceData {
+    Vec(Vec<u8>),
+    Shared(Arc<[u8]>),
+}
+impl FaceData {
+    fn as_slice(&self) -> &[u8] {
+        match self {
+            FaceData::Vec(data) => data,
+            FaceData::Shared(data) => data,
+        }
+    }
+}
 
+/// The [Face] is parsed the first time it is used
 struct SelfRefVecFace {
-    data: Vec<u8>,
-    face: Option<Face<'static>>,
+    face: OnceLock<Face<'static>>,
+    raw_face: RawFace<'static>,
+    index: u32,
+    data: FaceData,
     _pin: PhantomPinned,
 }
 impl SelfRefVecFace {
-    fn try_from_vec(
-        data: Vec<u8>,
-        index: u32,
-    ) -> Result<Pin<Box<Self>>, ttf_parser::FaceParsingError> {
-        let face = Self {
+    fn try_new(data: FaceData, index: u32) -> Result<Pin<Box<Self>>, ttf_parser::FaceParsingError> {
+        // Safety: the bytes are owned by `data` and are not moved when `data` is moved.
+        // They live as long as `face` and `raw_face` which are never handed out with a `'static` lifetime
+        let slice: &'static [u8] = unsafe {
+            let bytes = data.as_slice();
+            std::slice::from_raw_parts(bytes.as_ptr(), bytes.len())
+        };
+        let raw_face = RawFace::parse(slice, index)?;
+        check_required_tables(&raw_face)?;
+        Ok(Box::pin(Self {
+            face: OnceLock::new(),
+            raw_face,
+            index,
             data,
-            face: None,
             _pin: PhantomPinned,
-        };
-        let mut b = Box::pin(face);
-        unsafe {
-            let slice: &'static [u8] = std::slice::from_raw_parts(b.data.as_ptr(), b.data.len());
-            let mut_ref: Pin<&mut Self> = Pin::as_mut(&mut b);
-            let mut_inner = mut_ref.get_unchecked_mut();
-            mut_inner.face = Some(ttf_parser::Face::parse(slice, index)?);
-        }
-        Ok(b)
+        }))
     }
 
     fn inner_ref<'a>(self: &'a Pin<Box<Self>>) -> &'a ttf_parser::Face<'a> {
-        // Safety: if you have a ref `face` is always Some
-        unsafe { self.face.as_ref().unwrap_unchecked() }
+        self.face.get_or_init(|| {
+            Face::parse(self.raw_face.data, self.index)
+                .expect("The tables that can fail to parse were checked when the font was created")
+        })
     }
     fn into_vec(self: Pin<Box<Self>>) -> Vec<u8> {
         // Safety: safe as `face` is dropped.
         let mut me = unsafe { Pin::into_inner_unchecked(self) };
         me.face.take(); // ensure dropped before taking `data`
-        mem::take(&mut me.data)
+        match mem::replace(&mut me.data, FaceData::Vec(Vec::new())) {
+            FaceData::Vec(data) => data,
+            FaceData::Shared(data) => data.to_vec(),
+        }
     }
 }
 impl Drop for SelfRefVecFace {
@@ -124,11 +177,24 @@ impl Drop for SelfRefVecFace {
         self.face.take();
     }
 }
-
+/// [Face::parse] only fails if the table directory or one of these tables can not be read
+fn check_required_tables(raw_face: &RawFace<'_>) -> Result<(), ttf_parser::FaceParsingError> {
+    let table = |tag: &[u8; 4]| raw_face.table(Tag::from_bytes(tag)).unwrap_or_default();
+    head::Table::parse(table(b"head")).ok_or(ttf_parser::FaceParsingError::NoHeadTable)?;
+    hhea::Table::parse(table(b"hhea")).ok_or(ttf_parser::FaceParsingError::NoHheaTable)?;
+    maxp::Table::parse(table(b"maxp")).ok_or(ttf_parser::FaceParsingError::NoMaxpTable)?;
+    Ok(())
+}
 #[cfg(test)]
 mod tests {
 
-    use crate::document::{font_tests::DebugFontType, owned_ttf_parser::OwnedFace};
+    use std::sync::Arc;
+
+    use crate::{
+        document::{PdfDocument, font_tests::DebugFontType, owned_ttf_parser::OwnedFace},
+        graphics::{TextStyle, size::RenderSize},
+        units::Pt,
+    };
 
     use super::OwnedPdfTtfFont;
     // TODO: Test for memory leaks
@@ -156,4 +222,32 @@ mod tests {
         }
         Ok(())
     }
+    #[test]
+    fn lazy_font() -> anyhow::Result<()> {
+        let path = crate::tests::fonts_dir()
+            .join("Roboto")
+            .join("Roboto-Regular.ttf");
+        let data: Arc<[u8]> = std::fs::read(path)?.into();
+        let font = OwnedPdfTtfFont::new_lazy(data.clone(), 0)?;
+
+        let mut doc = PdfDocument::new("Lazy Font");
+        let font_ref = doc.font_map().register_external_font(font.clone())?;
+        assert!(font_ref.id().is_some_and(|id| id.starts_with("Roboto")));
+        // Registering only reads the table directory
+        assert!(font.inner.0.face.get().is_none());
+
+        let size = "Hello".render_size(
+            &doc,
+            &TextStyle {
+                font_ref,
+                ..Default::default()
+            },
+        )?;
+        assert!(size.width > Pt(0.0));
+        assert!(font.inner.0.face.get().is_some());
+        assert_eq!(font.as_slice(), &data[..]);
+
+        assert!(OwnedPdfTtfFont::new_lazy(Arc::from(vec![0u8; 64]), 0).is_err());
+        Ok(())
+    }
 }
@@ -0,0 +1,344 @@
+use std::collections::BTreeMap;
+
+use tux_pdf_low::{
+    content::write_operations_to_vec,
+    dictionary,
+    types::{Dictionary, Name, Object, Stream},
+};
+
+use crate::{
+    document::{DocumentWriter, PdfResources},
+    graphics::{
+        OperationWriter, PdfObjectType, operation_keys,
+        primitives::{Path, PathSegment},
+        size::Size,
+    },
+    units::Pt,
+};
+
+use super::{
+    FontMetrics, FontRenderSizeParams, FontType, GID_TO_UNICODE_END, unicode_to_utf16be_hex,
+};
+
+/// Glyph space of a [Type3Font]. One unit in a glyph path is 1/1000 of the font size
+pub const TYPE3_UNITS_PER_EM: u16 = 1000;
+/// Type 3 fonts use single byte character codes
+pub const TYPE3_MAX_GLYPHS: usize = 256;
+
+operation_keys!(Type3GlyphOperations => {
+    /// Sets the width and bounding box of an uncolored glyph. The color comes from the text
+    SetCharWidthAndBoundingBox => "d1"
+});
+/// A glyph drawn with paths
+///
+/// The paths are in glyph space. The baseline is at y 0 and the em square is 1000 units wide.
+/// Colors are not set by the glyph. It is painted with the fill and outline color of the text
+#[derive(Debug, Clone, PartialEq)]
+pub struct Type3Glyph {
+    /// The horizontal advance in glyph units
+    pub width: u32,
+    pub paths: Vec<Path>,
+}
+impl Type3Glyph {
+    pub fn new(width: u32) -> Self {
+        Self {
+            width,
+            paths: Vec::new(),
+        }
+    }
+    pub fn with_path(mut self, path: impl Into<Path>) -> Self {
+        self.paths.push(path.into());
+        self
+    }
+    /// The smallest rectangle around every point of the paths. `[left, bottom, right, top]`
+    ///
+    /// Curve control points are included so the box may be larger than the glyph
+    pub fn bounding_box(&self) -> [f32; 4] {
+        let mut points = self
+            .paths
+            .iter()
+            .flat_map(|path| &path.segments)
+            .flat_map(|segment| match segment {
+                PathSegment::MoveTo(point) | PathSegment::LineTo(point) => vec![*point],
+                PathSegment::CurveTo {
+                    control_1,
+                    control_2,
+                    end,
+                } => vec![*control_1, *control_2, *end],
+                PathSegment::Close => Vec::new(),
+            })
+            .peekable();
+        if points.peek().is_none() {
+            return [0f32; 4];
+        }
+        points.fold(
+            [f32::MAX, f32::MAX, f32::MIN, f32::MIN],
+            |[left, bottom, right, top], point| {
+                [
+                    left.min(point.x.0),
+                    bottom.min(point.y.0),
+                    right.max(point.x.0),
+                    top.max(point.y.0),
+                ]
+            },
+        )
+    }
+    /// The glyph procedure. `d1` followed by the paths
+    fn content(&self, resources: &PdfResources) -> Result<Vec<u8>, crate::TuxPdfError> {
+        let [left, bottom, right, top] = self.bounding_box();
+        let mut writer = OperationWriter::default();
+        writer.add_operation(
+            Type3GlyphOperations::SetCharWidthAndBoundingBox,
+            vec![
+                self.width.into(),
+                0.into(),
+                left.into(),
+                bottom.into(),
+                right.into(),
+                top.into(),
+            ],
+        );
+        for path in self.paths.iter().cloned() {
+            path.write(resources, &mut writer)?;
+        }
+        Ok(write_operations_to_vec(writer.operations())?)
+    }
+}
+/// A font made of vector glyphs. For icons and symbols without creating a TrueType font
+///
+/// Register it with [PdfFontMap::register_type3_font](super::PdfFontMap::register_type3_font)
+/// and use the returned [FontRef](super::FontRef) in a [TextStyle](crate::graphics::TextStyle).
+/// Characters without a glyph are not drawn
+///
+/// ```rust
+/// use tux_pdf::{
+///     document::{PdfDocument, Type3Font, Type3Glyph},
+///     graphics::{
+///         TextBlock, TextStyle,
+///         primitives::{PaintMode, PathBuilder},
+///     },
+///     units::UnitType,
+/// };
+/// let mut document = PdfDocument::new("Icons");
+/// let diamond = Type3Glyph::new(1000).with_path(
+///     PathBuilder::new()
+///         .move_to((500f32.pt(), 0f32.pt()))
+///         .line_to((1000f32.pt(), 500f32.pt()))
+///         .line_to((500f32.pt(), 1000f32.pt()))
+///         .line_to((0f32.pt(), 500f32.pt()))
+///         .close()
+///         .with_mode(PaintMode::Fill),
+/// );
+/// let icons = document
+///     .font_map()
+///     .register_type3_font(Type3Font::new("Icons").with_glyph('◆', diamond))?;
+/// let text = TextBlock::from("◆◆◆").with_style(TextStyle {
+///     font_ref: icons,
+///     ..Default::default()
+/// });
+/// # Ok::<(), tux_pdf::TuxPdfError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Type3Font {
+    pub name: String,
+    /// In glyph units. Defaults to 800
+    pub ascender: i16,
+    /// In glyph units. Defaults to -200
+    pub descender: i16,
+    glyphs: BTreeMap<char, Type3Glyph>,
+}
+impl Type3Font {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ascender: 800,
+            descender: -200,
+            glyphs: BTreeMap::new(),
+        }
+    }
+    /// Replaces the glyph already set for the character
+    pub fn with_glyph(mut self, c: char, glyph: Type3Glyph) -> Self {
+        self.glyphs.insert(c, glyph);
+        self
+    }
+    pub fn with_metrics(mut self, ascender: i16, descender: i16) -> Self {
+        self.ascender = ascender;
+        self.descender = descender;
+        self
+    }
+    pub fn glyph(&self, c: char) -> Option<&Type3Glyph> {
+        self.glyphs.get(&c)
+    }
+    pub fn number_of_glyphs(&self) -> usize {
+        self.glyphs.len()
+    }
+    /// The single byte code of the character. Glyphs are numbered in the order of their characters
+    pub fn char_code(&self, c: char) -> Option<u8> {
+        self.glyphs
+            .keys()
+            .position(|glyph| *glyph == c)
+            .and_then(|index| u8::try_from(index).ok())
+    }
+    pub fn metrics(&self) -> FontMetrics {
+        FontMetrics {
+            units_per_em: TYPE3_UNITS_PER_EM,
+            ascender: self.ascender,
+            descender: self.descender,
+        }
+    }
+    fn width_in_points(width: u32, font_size: Pt) -> Pt {
+        Pt(width as f32 * font_size.0 / TYPE3_UNITS_PER_EM as f32)
+    }
+    pub(crate) fn dictionary(
+        self,
+        writer: &mut DocumentWriter,
+    ) -> Result<Dictionary, crate::TuxPdfError> {
+        let resources = PdfResources::default();
+        let mut char_procs = Dictionary::new();
+        let mut differences: Vec<Object> = vec![0.into()];
+        let mut widths: Vec<Object> = Vec::with_capacity(self.glyphs.len());
+        let mut font_bbox = [0f32; 4];
+        let mut unicode_entries = Vec::with_capacity(self.glyphs.len());
+        for (code, (c, glyph)) in self.glyphs.iter().enumerate() {
+            let glyph_name = Name::from(format!("g{code}"));
+            let [left, bottom, right, top] = glyph.bounding_box();
+            font_bbox = [
+                font_bbox[0].min(left),
+                font_bbox[1].min(bottom),
+                font_bbox[2].max(right),
+                font_bbox[3].max(top),
+            ];
+            let procedure = Stream::new(Dictionary::new(), glyph.content(&resources)?);
+            let procedure_id = writer.insert_object(procedure.into());
+            char_procs.set(glyph_name.clone(), procedure_id);
+            differences.push(glyph_name.into());
+            widths.push(glyph.width.into());
+            unicode_entries.push(format!(
+                "<{code:02X}> <{}>\n",
+                unicode_to_utf16be_hex(*c as u32)
+            ));
+        }
+        let to_unicode = to_unicode_cmap(&self.name, &unicode_entries);
+        let to_unicode_id =
+            writer.insert_object(Stream::new(Dictionary::new(), to_unicode.into_bytes()).into());
+        let scale = 1f32 / TYPE3_UNITS_PER_EM as f32;
+        Ok(dictionary! {
+            "Type" => Object::name("Font"),
+            "Subtype" => Object::name("Type3"),
+            "Name" => Object::name(self.name),
+            "FontBBox" => font_bbox,
+            "FontMatrix" => [scale, 0f32, 0f32, scale, 0f32, 0f32],
+            "CharProcs" => char_procs,
+            "Encoding" => dictionary! {
+                "Type" => Object::name("Encoding"),
+                "Differences" => differences
+            },
+            "FirstChar" => 0,
+            "LastChar" => self.glyphs.len().saturating_sub(1) as i64,
+            "Widths" => widths,
+            "Resources" => Dictionary::new(),
+            "ToUnicode" => to_unicode_id
+        })
+    }
+}
+/// Maps the single byte codes back to unicode so the text can be copied
+fn to_unicode_cmap(name: &str, entries: &[String]) -> String {
+    let mut cmap = format!(
+        "/CIDInit /ProcSet findresource begin\n12 dict begin\nbegincmap\n\
+         /CMapName /{name}-UCS def\n/CMapType 2 def\n\
+         1 begincodespacerange\n<00> <FF>\nendcodespacerange\n"
+    );
+    // A bfchar block can have at most 100 entries
+    for block in entries.chunks(100) {
+        cmap.push_str(&format!("{} beginbfchar\n", block.len()));
+        block.iter().for_each(|entry| cmap.push_str(entry));
+        cmap.push_str("endbfchar\n");
+    }
+    cmap.push_str(GID_TO_UNICODE_END);
+    cmap
+}
+impl FontType for Type3Font {
+    fn calculate_size_of_text<P: FontRenderSizeParams>(&self, text: &str, params: &P) -> Size {
+        let width: u32 = text
+            .chars()
+            .filter_map(|c| self.glyph(c))
+            .map(|glyph| glyph.width)
+            .sum();
+        Size {
+            width: Self::width_in_points(width, params.font_size()),
+            height: params.font_size(),
+        }
+    }
+
+    fn size_of_char<P: FontRenderSizeParams>(&self, c: char, params: &P) -> Option<Size> {
+        self.glyph(c).map(|glyph| Size {
+            width: Self::width_in_points(glyph.width, params.font_size()),
+            height: params.font_size(),
+        })
+    }
+    /// Characters without a glyph are skipped
+    fn encode_text(&self, text: &str) -> Vec<u8> {
+        text.chars().filter_map(|c| self.char_code(c)).collect()
+    }
+}
+#[cfg(test)]
+mod tests {
+    use tux_pdf_low::types::Object;
+
+    use super::{Type3Font, Type3Glyph};
+    use crate::{
+        document::{FontRef, FontType, PdfDocument},
+        graphics::{LayerType, TextBlock, TextStyle, primitives::PathBuilder},
+        units::UnitType,
+    };
+
+    fn square(width: u32) -> Type3Glyph {
+        Type3Glyph::new(width).with_path(
+            PathBuilder::new()
+                .move_to((100f32.pt(), 0f32.pt()))
+                .line_to((600f32.pt(), 0f32.pt()))
+                .line_to((600f32.pt(), 500f32.pt()))
+                .line_to((100f32.pt(), 500f32.pt()))
+                .close(),
+        )
+    }
+    #[test]
+    fn glyph_codes_and_widths() {
+        let font = Type3Font::new("Icons")
+            .with_glyph('b', square(700))
+            .with_glyph('a', square(500));
+        assert_eq!(font.char_code('a'), Some(0));
+        assert_eq!(font.char_code('b'), Some(1));
+        assert_eq!(font.encode_text("abz"), vec![0, 1]);
+        assert_eq!(square(700).bounding_box(), [100f32, 0f32, 600f32, 500f32]);
+
+        let style = TextStyle {
+            font_size: 10f32.pt(),
+            ..Default::default()
+        };
+        assert_eq!(font.calculate_size_of_text("ab", &style).width, 12f32.pt());
+    }
+    #[test]
+    fn written_as_a_font_resource() -> anyhow::Result<()> {
+        let mut document = PdfDocument::new("Type 3");
+        let font_ref = document
+            .font_map()
+            .register_type3_font(Type3Font::new("Icons").with_glyph('★', square(600)))?;
+        assert!(matches!(font_ref, FontRef::Type3(_)));
+        let mut page = crate::page::PdfPage::new_from_page_size(crate::page::page_sizes::A4);
+        page.add_to_layer(TextBlock::from("★").with_style(TextStyle {
+            font_ref: font_ref.clone(),
+            ..Default::default()
+        }))?;
+        document.add_page(page);
+        let pdf = document.write_into_pdf_document_writer()?;
+        let font = pdf
+            .objects()
+            .filter_map(|(_, object)| object.as_dictionary())
+            .find(|dictionary| dictionary.get("Subtype") == Some(&Object::name("Type3")))
+            .expect("The Type 3 font is written");
+        assert!(font.get("CharProcs").is_some());
+        assert_eq!(font.get("Widths"), Some(&Object::from(vec![600])));
+        Ok(())
+    }
+}
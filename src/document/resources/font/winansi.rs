@@ -0,0 +1,73 @@
+use super::FontId;
+
+/// Characters of WinAnsiEncoding between `0x80` and `0x9F`. The rest of the encoding matches Latin-1
+const WIN_ANSI_HIGH: [(char, u8); 27] = [
+    ('\u{20AC}', 0x80),
+    ('\u{201A}', 0x82),
+    ('\u{0192}', 0x83),
+    ('\u{201E}', 0x84),
+    ('\u{2026}', 0x85),
+    ('\u{2020}', 0x86),
+    ('\u{2021}', 0x87),
+    ('\u{02C6}', 0x88),
+    ('\u{2030}', 0x89),
+    ('\u{0160}', 0x8A),
+    ('\u{2039}', 0x8B),
+    ('\u{0152}', 0x8C),
+    ('\u{017D}', 0x8E),
+    ('\u{2018}', 0x91),
+    ('\u{2019}', 0x92),
+    ('\u{201C}', 0x93),
+    ('\u{201D}', 0x94),
+    ('\u{2022}', 0x95),
+    ('\u{2013}', 0x96),
+    ('\u{2014}', 0x97),
+    ('\u{02DC}', 0x98),
+    ('\u{2122}', 0x99),
+    ('\u{0161}', 0x9A),
+    ('\u{203A}', 0x9B),
+    ('\u{0153}', 0x9C),
+    ('\u{017E}', 0x9E),
+    ('\u{0178}', 0x9F),
+];
+/// The code of the character in WinAnsiEncoding. The encoding used by the [builtin fonts](super::BuiltinFont)
+///
+/// Returns [None] if the character is not part of the encoding
+pub fn win_ansi_code(c: char) -> Option<u8> {
+    match c {
+        ' '..='~' | '\u{A0}'..='\u{FF}' => Some(c as u8),
+        _ => WIN_ANSI_HIGH
+            .iter()
+            .find(|(high, _)| *high == c)
+            .map(|(_, code)| *code),
+    }
+}
+/// What happens to characters a builtin font can not encode
+///
+/// Set with [PdfFontMap::set_builtin_fallback](super::PdfFontMap::set_builtin_fallback)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuiltinFontFallback {
+    /// The character is drawn instead. `?` if it can not be encoded either
+    Substitute(char),
+    /// The characters are drawn with an external font
+    Font(FontId),
+}
+impl Default for BuiltinFontFallback {
+    fn default() -> Self {
+        Self::Substitute('?')
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::win_ansi_code;
+
+    #[test]
+    fn win_ansi_codes() {
+        assert_eq!(win_ansi_code('A'), Some(b'A'));
+        assert_eq!(win_ansi_code('é'), Some(0xE9));
+        assert_eq!(win_ansi_code('€'), Some(0x80));
+        assert_eq!(win_ansi_code('—'), Some(0x97));
+        assert_eq!(win_ansi_code('\n'), None);
+        assert_eq!(win_ansi_code('Ω'), None);
+    }
+}
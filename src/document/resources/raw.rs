@@ -0,0 +1,157 @@
+use std::collections::BTreeMap;
+
+use tux_pdf_low::types::{Dictionary, Object, ObjectId};
+
+use crate::{TuxPdfError, document::DocumentWriter};
+
+/// Resource categories the document writes itself
+const WRITTEN_CATEGORIES: [&str; 4] = ["Font", "XObject", "ExtGState", "Properties"];
+/// Objects written to the document as is
+///
+/// For features the rest of the library does not support yet.
+/// Nothing is checked. The objects must be valid PDF
+///
+/// Reserved objects are written first so their ids are known before the document is written.
+/// Use the id to reference the object from other raw objects or from [PdfObject::Raw](crate::graphics::PdfObject::Raw) content
+///
+/// ```rust
+/// use tux_pdf::{document::PdfDocument, graphics::PdfObject};
+/// use tux_pdf_low::{
+///     content::Operation,
+///     dictionary,
+///     types::{Dictionary, Object},
+/// };
+/// let mut document = PdfDocument::new("Raw");
+/// let shading: Dictionary = dictionary! {
+///     "ShadingType" => 2,
+///     "ColorSpace" => Object::name("DeviceGray"),
+///     "Coords" => vec![Object::from(0), 0.into(), 100.into(), 0.into()]
+/// };
+/// let shading_id = document.add_raw_object(shading);
+/// document.add_raw_resource("Shading", "Sh1", shading_id).unwrap();
+/// let gradient = PdfObject::Raw(vec![Operation::new("sh", vec![Object::name("Sh1")])]);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RawObjects {
+    /// Index `n` is object number `n + 1`. None if the object was reserved but not set yet
+    objects: Vec<Option<Object>>,
+    /// Keyed by the category such as `Shading` then the name used in content streams
+    resources: BTreeMap<String, Dictionary>,
+}
+impl RawObjects {
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty() && self.resources.is_empty()
+    }
+    /// Reserves an id for an object that will be set later with [Self::set]
+    ///
+    /// Useful for objects that reference each other
+    pub fn reserve_id(&mut self) -> ObjectId {
+        self.objects.push(None);
+        ObjectId::from(self.objects.len() as u32)
+    }
+    /// Adds an object and returns its id
+    pub fn add(&mut self, object: impl Into<Object>) -> ObjectId {
+        self.objects.push(Some(object.into()));
+        ObjectId::from(self.objects.len() as u32)
+    }
+    /// Sets the object of an id returned by [Self::reserve_id]. Replaces the object if it was already set
+    pub fn set(&mut self, id: ObjectId, object: impl Into<Object>) -> Result<(), TuxPdfError> {
+        let slot = (id.object_number() as usize)
+            .checked_sub(1)
+            .and_then(|index| self.objects.get_mut(index))
+            .ok_or_else(|| TuxPdfError::InvalidObjectId(format!("{id:?} was not reserved")))?;
+        *slot = Some(object.into());
+        Ok(())
+    }
+    /// Adds an entry to the resources of every page so raw content can use the object by name
+    ///
+    /// `Font`, `XObject`, `ExtGState` and `Properties` are written by the document and can not be added to
+    pub fn add_resource(
+        &mut self,
+        category: impl Into<String>,
+        name: impl Into<String>,
+        id: ObjectId,
+    ) -> Result<(), TuxPdfError> {
+        let category = category.into();
+        if WRITTEN_CATEGORIES.contains(&category.as_str()) {
+            return Err(TuxPdfError::ObjectCollectionError(format!(
+                "{category} resources are written by the document"
+            )));
+        }
+        self.resources
+            .entry(category)
+            .or_default()
+            .set(name.into(), id);
+        Ok(())
+    }
+    /// Writes the objects. Must be called before any other object is added to the writer
+    ///
+    /// Returns the entries to add to the resources dictionaries
+    pub(crate) fn write(
+        self,
+        writer: &mut DocumentWriter,
+    ) -> Result<Option<Dictionary>, TuxPdfError> {
+        for (index, object) in self.objects.into_iter().enumerate() {
+            let id = writer.new_object_id();
+            debug_assert_eq!(id.object_number() as usize, index + 1);
+            let object = object.ok_or_else(|| {
+                TuxPdfError::InvalidObjectId(format!("{id:?} was reserved but never set"))
+            })?;
+            writer.document.set_object(id, object);
+        }
+        if self.resources.is_empty() {
+            return Ok(None);
+        }
+        let mut resources = Dictionary::new();
+        for (category, entries) in self.resources {
+            resources.set(category, entries);
+        }
+        Ok(Some(resources))
+    }
+}
+#[cfg(test)]
+mod tests {
+    use tux_pdf_low::types::{Dictionary, Object, ObjectId};
+
+    use crate::{
+        document::PdfDocument,
+        graphics::PdfObject,
+        page::{PdfPage, page_sizes::A4},
+    };
+
+    #[test]
+    fn raw_objects_are_written_with_reserved_ids() -> anyhow::Result<()> {
+        let mut document = PdfDocument::new("Raw Objects");
+        let reserved = document.reserve_object_id();
+        let mut first = Dictionary::new();
+        first.set("Next", reserved);
+        let first_id = document.add_raw_object(first);
+        document.set_raw_object(reserved, Dictionary::new())?;
+        document.add_raw_resource("Pattern", "P1", first_id)?;
+        assert!(document.add_raw_resource("Font", "F1", first_id).is_err());
+        assert!(
+            document
+                .set_raw_object(ObjectId::from(10), Object::Null)
+                .is_err()
+        );
+
+        let mut page = PdfPage::new_from_page_size(A4);
+        page.contents.push(PdfObject::Raw(vec![]));
+        document.add_page(page);
+        let writer = document.write_into_pdf_document_writer()?;
+        let first = writer
+            .get_object(&first_id)
+            .and_then(Object::as_dictionary_or_stream_dictionary)
+            .expect("The raw object was written");
+        assert_eq!(first.get("Next"), Some(&Object::from(reserved)));
+        assert!(writer.get_object(&reserved).is_some());
+        Ok(())
+    }
+    #[test]
+    fn unset_reserved_object() {
+        let mut document = PdfDocument::new("Raw Objects");
+        document.reserve_object_id();
+        document.add_page(PdfPage::new_from_page_size(A4));
+        assert!(document.write_into_pdf_document_writer().is_err());
+    }
+}
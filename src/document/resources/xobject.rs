@@ -3,10 +3,17 @@ pub use pdf_image::*;
 
 mod form;
 pub use form::*;
+
+mod stream;
 use std::collections::BTreeMap;
+pub use stream::*;
 use tux_pdf_low::types::{Dictionary, Object};
 
-use crate::{TuxPdfError, document::DocumentWriter};
+use crate::{
+    TuxPdfError,
+    document::DocumentWriter,
+    error::{ErrorContext, ErrorContextExt},
+};
 
 use super::{IdType, ObjectMapType};
 #[derive(Debug, PartialEq, Clone, Eq, PartialOrd, Ord)]
@@ -60,7 +67,12 @@ impl XObjectMap {
         let mut xobject_dict = Dictionary::new();
         for (id, xobject) in self.map.into_iter() {
             let dictionary: Object = match xobject {
-                XObject::Image(image) => image.image.into_stream()?.into(),
+                XObject::Image(image) => image
+                    .image
+                    .into_stream()
+                    .with_context(|| ErrorContext::Resource(id.to_string()))?
+                    .into(),
+                XObject::Stream(stream) => stream.into_stream().into(),
                 XObject::Form(_) => {
                     todo!("FormXObject dictionary")
                 }
@@ -87,12 +99,15 @@ pub enum XObject {
     /// Form XObject, NOT A PDF FORM, this just allows repeatable content
     /// on a page
     Form(Box<FormXObject>),
+    /// An XObject stream built outside of this library
+    Stream(Box<StreamXObject>),
 }
 impl XObject {
     pub fn as_ref(&self) -> XObjectRef<'_> {
         match self {
             XObject::Image(image) => XObjectRef::Image(image),
             XObject::Form(form) => XObjectRef::Form(form),
+            XObject::Stream(stream) => XObjectRef::Stream(stream),
         }
     }
 }
@@ -102,6 +117,11 @@ impl From<PdfXObjectImage> for XObject {
         XObject::Image(Box::new(image))
     }
 }
+impl From<StreamXObject> for XObject {
+    fn from(stream: StreamXObject) -> Self {
+        XObject::Stream(Box::new(stream))
+    }
+}
 impl From<FormXObject> for XObject {
     fn from(form: FormXObject) -> Self {
         XObject::Form(Box::new(form))
@@ -112,4 +132,5 @@ impl From<FormXObject> for XObject {
 pub enum XObjectRef<'resources> {
     Image(&'resources PdfXObjectImage),
     Form(&'resources FormXObject),
+    Stream(&'resources StreamXObject),
 }
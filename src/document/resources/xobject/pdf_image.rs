@@ -10,9 +10,10 @@
 use image::{ColorType, DynamicImage, GenericImageView, ImageDecoder};
 use tux_pdf_low::{
     dictionary,
-    types::{Object, ObjectId, Stream},
+    types::{Dictionary, Object, ObjectId, Stream},
 };
-use utils::pull_alpha_out_of_rgb;
+use utils::{native_to_big_endian_u16, pull_alpha_out};
+mod bilevel;
 mod utils;
 use crate::{
     TuxPdfError,
@@ -82,14 +83,32 @@ impl PdfXObjectImageData {
         image_data: Vec<u8>,
         dim: (u32, u32),
     ) -> Result<(PdfXObjectImageData, Option<PdfXObjectImageData>), TuxPdfError> {
-        let (color_type, image_data, smask_data) = match color_type {
+        let (color_type, mut image_data, mut smask_data) = match color_type {
             ColorType::Rgba8 => {
-                let (rgb, alpha) = pull_alpha_out_of_rgb(image_data);
+                let (rgb, alpha) = pull_alpha_out(image_data, 3, 1);
                 (ColorType::Rgb8, rgb, Some(alpha))
             }
+            ColorType::La8 => {
+                let (luma, alpha) = pull_alpha_out(image_data, 1, 1);
+                (ColorType::L8, luma, Some(alpha))
+            }
+            ColorType::Rgba16 => {
+                let (rgb, alpha) = pull_alpha_out(image_data, 3, 2);
+                (ColorType::Rgb16, rgb, Some(alpha))
+            }
+            ColorType::La16 => {
+                let (luma, alpha) = pull_alpha_out(image_data, 1, 2);
+                (ColorType::L16, luma, Some(alpha))
+            }
             _ => (color_type, image_data, None),
         };
         let color_bits = ColorBits::try_from(color_type)?;
+        if color_bits == ColorBits::Bit16 {
+            native_to_big_endian_u16(&mut image_data);
+            if let Some(smask) = &mut smask_data {
+                native_to_big_endian_u16(smask);
+            }
+        }
         let color_space = ColorSpace::try_from(color_type)?;
         let size = Size {
             width: Px(dim.0 as i64),
@@ -108,7 +127,7 @@ impl PdfXObjectImageData {
         let img_mask = smask_data.map(|smask| PdfXObjectImageData {
             size,
             color_space: ColorSpace::Greyscale,
-            bits_per_component: ColorBits::Bit8,
+            bits_per_component: color_bits,
             interpolate: false,
             image_data: smask,
             image_filter: None,
@@ -139,6 +158,12 @@ impl PdfXObjectImageData {
         if let Some(smask) = self.smask {
             dictionary.set("SMask", Object::Reference(smask));
         }
+        if let Some(filter) = self.image_filter {
+            dictionary.set("Filter", Object::name(filter.name()));
+            if let Some(parameters) = filter.decode_parameters(self.size) {
+                dictionary.set("DecodeParms", parameters);
+            }
+        }
         let data = std::mem::take(&mut self.image_data);
 
         Ok(Stream::new(dictionary, data))
@@ -177,4 +202,80 @@ pub enum ImageFilter {
     Lzw,
     DCT,
     JPX,
+    /// CCITT Group 4 encoded 1-bit images. See [PdfXObjectImageData::ccitt_g4]
+    CcittG4,
+    /// JBIG2 encoded 1-bit images. See [PdfXObjectImageData::jbig2]
+    Jbig2 {
+        /// Stream with the global segments shared between images
+        globals: Option<ObjectId>,
+    },
+}
+impl ImageFilter {
+    /// The name of the filter in the stream dictionary
+    pub fn name(&self) -> &'static str {
+        match self {
+            ImageFilter::Ascii85 => "ASCII85Decode",
+            ImageFilter::Lzw => "LZWDecode",
+            ImageFilter::DCT => "DCTDecode",
+            ImageFilter::JPX => "JPXDecode",
+            ImageFilter::CcittG4 => "CCITTFaxDecode",
+            ImageFilter::Jbig2 { .. } => "JBIG2Decode",
+        }
+    }
+    fn decode_parameters(&self, size: Size<Px>) -> Option<Dictionary> {
+        match self {
+            ImageFilter::CcittG4 => Some(dictionary! {
+                // Negative K is pure two dimensional encoding. Group 4
+                "K" => -1,
+                "Columns" => size.width,
+                "Rows" => size.height
+            }),
+            ImageFilter::Jbig2 {
+                globals: Some(globals),
+            } => Some(dictionary! {
+                "JBIG2Globals" => *globals
+            }),
+            _ => None,
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use image::{DynamicImage, ImageBuffer, LumaA, Rgba};
+
+    use super::PdfXObjectImage;
+    use crate::graphics::color::{ColorBits, ColorSpace};
+
+    #[test]
+    fn sixteen_bit_samples_are_big_endian() -> anyhow::Result<()> {
+        let luma = ImageBuffer::from_raw(2, 1, vec![0x0102u16, 0xA0B0]).unwrap();
+        let image = PdfXObjectImage::load_from_dynamic_image(DynamicImage::ImageLuma16(luma))?;
+        assert_eq!(image.image.color_space, ColorSpace::Greyscale);
+        assert_eq!(image.image.bits_per_component, ColorBits::Bit16);
+        assert_eq!(image.image.image_data, vec![0x01, 0x02, 0xA0, 0xB0]);
+        assert!(image.mask.is_none());
+
+        let rgba = ImageBuffer::from_pixel(1, 1, Rgba([1u16, 2, 3, 0x0400]));
+        let image = PdfXObjectImage::load_from_dynamic_image(DynamicImage::ImageRgba16(rgba))?;
+        assert_eq!(image.image.color_space, ColorSpace::Rgb);
+        assert_eq!(image.image.image_data, vec![0, 1, 0, 2, 0, 3]);
+        let mask = image.mask.expect("The alpha is a soft mask");
+        assert_eq!(mask.bits_per_component, ColorBits::Bit16);
+        assert_eq!(mask.image_data, vec![0x04, 0x00]);
+        Ok(())
+    }
+    #[test]
+    fn grayscale_alpha() -> anyhow::Result<()> {
+        let luma_alpha = ImageBuffer::from_pixel(2, 2, LumaA([10u8, 128]));
+        let image =
+            PdfXObjectImage::load_from_dynamic_image(DynamicImage::ImageLumaA8(luma_alpha))?;
+        assert_eq!(image.image.color_space, ColorSpace::Greyscale);
+        assert_eq!(image.image.bits_per_component, ColorBits::Bit8);
+        assert_eq!(image.image.image_data, vec![10; 4]);
+        assert_eq!(
+            image.mask.expect("The alpha is a soft mask").image_data,
+            vec![128; 4]
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,144 @@
+//! 1-bit images. Such as scanned black and white documents
+#[cfg(feature = "ccitt")]
+use image::DynamicImage;
+use tux_pdf_low::types::ObjectId;
+
+use super::{ImageFilter, PdfXObjectImage, PdfXObjectImageData};
+#[cfg(feature = "ccitt")]
+use crate::TuxPdfError;
+use crate::{
+    graphics::{
+        color::{ColorBits, ColorSpace},
+        size::Size,
+    },
+    units::Px,
+};
+
+impl PdfXObjectImageData {
+    /// A 1-bit image that was already encoded with JBIG2. The data is embedded as is
+    ///
+    /// `globals` is the id of a stream holding the global segments shared between images.
+    /// Add it with [PdfDocument::add_raw_object](crate::document::PdfDocument::add_raw_object)
+    pub fn jbig2(size: Size<Px>, data: Vec<u8>, globals: Option<ObjectId>) -> Self {
+        Self::bilevel(size, data, ImageFilter::Jbig2 { globals })
+    }
+    /// Encodes a 1-bit image with CCITT Group 4
+    ///
+    /// `black` has one value per pixel. Row by row starting at the top left
+    #[cfg(feature = "ccitt")]
+    pub fn ccitt_g4(size: Size<Px>, black: &[bool]) -> Result<Self, TuxPdfError> {
+        use fax::{Color, VecWriter, encoder::Encoder};
+        let width = u16::try_from(size.width.0)
+            .ok()
+            .filter(|width| *width > 0)
+            .ok_or_else(|| {
+                TuxPdfError::InvalidXObject(format!(
+                    "CCITT images must be between 1 and {} pixels wide. Found {}",
+                    u16::MAX,
+                    size.width.0
+                ))
+            })?;
+        let pixels = width as usize * size.height.0.max(0) as usize;
+        if pixels == 0 || black.len() != pixels {
+            return Err(TuxPdfError::InvalidXObject(format!(
+                "Expected {pixels} pixels. Found {}",
+                black.len()
+            )));
+        }
+        let mut encoder = Encoder::new(VecWriter::with_capacity(pixels / 8));
+        for row in black.chunks_exact(width as usize) {
+            let pels = row
+                .iter()
+                .map(|black| if *black { Color::Black } else { Color::White });
+            encoder
+                .encode_line(pels, width)
+                .unwrap_or_else(|never| match never {});
+        }
+        let data = encoder
+            .finish()
+            .unwrap_or_else(|never| match never {})
+            .finish();
+        Ok(Self::bilevel(size, data, ImageFilter::CcittG4))
+    }
+    fn bilevel(size: Size<Px>, data: Vec<u8>, filter: ImageFilter) -> Self {
+        Self {
+            size,
+            color_space: ColorSpace::Greyscale,
+            bits_per_component: ColorBits::Bit1,
+            interpolate: false,
+            image_data: data,
+            image_filter: Some(filter),
+            smask: None,
+            clipping_bbox: None,
+        }
+    }
+}
+impl PdfXObjectImage {
+    /// Converts the image to black and white then encodes it with CCITT Group 4
+    ///
+    /// Pixels with a luminance below `threshold` are black. Transparency is ignored
+    #[cfg(feature = "ccitt")]
+    pub fn load_bilevel_from_dynamic_image(
+        image: &DynamicImage,
+        threshold: u8,
+    ) -> Result<Self, TuxPdfError> {
+        let luma = image.to_luma8();
+        let size = Size {
+            width: Px(luma.width() as i64),
+            height: Px(luma.height() as i64),
+        };
+        let black: Vec<bool> = luma.pixels().map(|pixel| pixel.0[0] < threshold).collect();
+        Ok(PdfXObjectImageData::ccitt_g4(size, &black)?.into())
+    }
+}
+impl From<PdfXObjectImageData> for PdfXObjectImage {
+    fn from(image: PdfXObjectImageData) -> Self {
+        Self { image, mask: None }
+    }
+}
+#[cfg(all(test, feature = "ccitt"))]
+mod tests {
+    use fax::{
+        Color,
+        decoder::{decode_g4, pels},
+    };
+    use tux_pdf_low::types::Object;
+
+    use crate::{
+        document::resources::xobject::pdf_image::{ImageFilter, PdfXObjectImageData},
+        graphics::size::Size,
+        units::Px,
+    };
+
+    #[test]
+    fn ccitt_round_trip() -> anyhow::Result<()> {
+        let size = Size {
+            width: Px(16),
+            height: Px(8),
+        };
+        // A black diagonal line
+        let black: Vec<bool> = (0..8)
+            .flat_map(|y| (0..16).map(move |x| x / 2 == y))
+            .collect();
+        let image = PdfXObjectImageData::ccitt_g4(size, &black)?;
+        assert_eq!(image.image_filter, Some(ImageFilter::CcittG4));
+
+        let mut decoded = Vec::new();
+        decode_g4(image.image_data.iter().copied(), 16, Some(8), |line| {
+            decoded.extend(pels(line, 16).map(|pixel| pixel == Color::Black));
+        });
+        assert_eq!(decoded, black);
+
+        let stream = image.into_stream()?;
+        assert_eq!(
+            stream.dictionary.get("Filter"),
+            Some(&Object::name("CCITTFaxDecode"))
+        );
+        assert_eq!(
+            stream.dictionary.get("BitsPerComponent"),
+            Some(&Object::from(1))
+        );
+        assert!(PdfXObjectImageData::ccitt_g4(size, &black[1..]).is_err());
+        Ok(())
+    }
+}
@@ -1,12 +1,30 @@
-pub(crate) fn pull_alpha_out_of_rgb(data: Vec<u8>) -> (Vec<u8>, Vec<u8>) {
-    let mut rgb = Vec::with_capacity(data.len() / 4 * 3);
-    let mut alpha = Vec::with_capacity(data.len() / 4);
-    for i in (0..data.len()).step_by(4) {
-        rgb.push(data[i]);
-        rgb.push(data[i + 1]);
-        rgb.push(data[i + 2]);
-        alpha.push(data[i + 3]);
+/// Splits interleaved samples into the color samples and the alpha samples
+///
+/// `color_channels` does not include the alpha channel
+pub(crate) fn pull_alpha_out(
+    data: Vec<u8>,
+    color_channels: usize,
+    bytes_per_sample: usize,
+) -> (Vec<u8>, Vec<u8>) {
+    let pixel_size = (color_channels + 1) * bytes_per_sample;
+    let color_size = color_channels * bytes_per_sample;
+    let pixels = data.len() / pixel_size;
+    let mut color = Vec::with_capacity(pixels * color_size);
+    let mut alpha = Vec::with_capacity(pixels * bytes_per_sample);
+    for pixel in data.chunks_exact(pixel_size) {
+        let (pixel_color, pixel_alpha) = pixel.split_at(color_size);
+        color.extend_from_slice(pixel_color);
+        alpha.extend_from_slice(pixel_alpha);
     }
 
-    (rgb, alpha)
+    (color, alpha)
+}
+/// PDF stores 16 bit samples big endian. The image crate uses the native byte order
+pub(crate) fn native_to_big_endian_u16(data: &mut [u8]) {
+    if cfg!(target_endian = "big") {
+        return;
+    }
+    for sample in data.chunks_exact_mut(2) {
+        sample.swap(0, 1);
+    }
 }
@@ -0,0 +1,300 @@
+use tux_pdf_low::{
+    dictionary,
+    types::{Dictionary, Object, Stream},
+};
+
+use crate::{
+    TuxPdfError,
+    graphics::{PdfPosition, primitives::ctm::CurTransMat, shapes::OutlineRect, size::Size},
+    units::{Pt, Px},
+};
+
+/// How the content of a [StreamXObject] is mapped onto the page
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StreamXObjectPlacement {
+    /// An image XObject. Drawn in the unit square and sized by its pixels like [PdfXObjectImage](super::PdfXObjectImage)
+    Image(Size<Px>),
+    /// A form XObject. Drawn in its `BBox`. The size of the box in points is the size of the form.
+    ///
+    /// The `Matrix` of the form is not taken into account
+    Form(OutlineRect),
+}
+/// An XObject stream built outside of this library. Such as a form XObject from another tool or a logo vectorized offline
+///
+/// The dictionary is written as is. The `Length` is set when it is written.
+/// Place it on a page with [PdfImage](crate::graphics::image::PdfImage) like any other image
+///
+/// ```rust
+/// use tux_pdf::{
+///     document::{PdfDocument, StreamXObject},
+///     graphics::{PdfPosition, image::PdfImage, shapes::OutlineRect, size::Size},
+///     units::Pt,
+/// };
+/// let mut document = PdfDocument::new("Logo");
+/// let bbox = OutlineRect {
+///     position: PdfPosition::default(),
+///     size: Size::new(Pt(100.0), Pt(50.0)),
+/// };
+/// let logo = StreamXObject::form(bbox, b"0 0 1 rg 0 0 100 50 re f".to_vec());
+/// let logo = document.add_xobject(logo);
+/// let image = PdfImage::new(logo).with_position(PdfPosition::new(Pt(20.0), Pt(20.0)));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamXObject {
+    dictionary: Dictionary,
+    content: Vec<u8>,
+    placement: StreamXObjectPlacement,
+}
+impl StreamXObject {
+    /// The dictionary must have a `Subtype` of `Form` with a `BBox` or `Image` with a `Width` and `Height`
+    ///
+    /// Resources used by a form must be in its own `Resources` dictionary. Use [PdfDocument::add_raw_object](crate::document::PdfDocument::add_raw_object) for the objects it references
+    pub fn new(dictionary: Dictionary, content: impl Into<Vec<u8>>) -> Result<Self, TuxPdfError> {
+        let placement = match dictionary.get("Subtype").and_then(Object::as_name) {
+            Some(subtype) if subtype.0 == b"Form" => dictionary
+                .get("BBox")
+                .and_then(rect_from_object)
+                .map(StreamXObjectPlacement::Form)
+                .ok_or_else(|| {
+                    TuxPdfError::InvalidXObject("Form XObjects need a BBox with a size".to_owned())
+                })?,
+            Some(subtype) if subtype.0 == b"Image" => {
+                let dimension = |key: &str| {
+                    dictionary
+                        .get(key)
+                        .and_then(Object::as_integer)
+                        .copied()
+                        .filter(|value: &i64| *value > 0)
+                        .map(Px)
+                };
+                let (Some(width), Some(height)) = (dimension("Width"), dimension("Height")) else {
+                    return Err(TuxPdfError::InvalidXObject(
+                        "Image XObjects need a Width and Height".to_owned(),
+                    ));
+                };
+                StreamXObjectPlacement::Image(Size::new(width, height))
+            }
+            _ => {
+                return Err(TuxPdfError::InvalidXObject(
+                    "The Subtype must be Form or Image".to_owned(),
+                ));
+            }
+        };
+        Ok(Self {
+            dictionary,
+            content: content.into(),
+            placement,
+        })
+    }
+    /// A form XObject with content drawn in `bbox`
+    pub fn form(bbox: OutlineRect, content: impl Into<Vec<u8>>) -> Self {
+        let dictionary = dictionary! {
+            "Type" => Object::name("XObject"),
+            "Subtype" => Object::name("Form"),
+            "BBox" => rect_to_object(bbox)
+        };
+        Self {
+            dictionary,
+            content: content.into(),
+            placement: StreamXObjectPlacement::Form(bbox),
+        }
+    }
+    /// Sets an entry of the dictionary such as `Resources` or `Group`
+    ///
+    /// Changing the `Subtype`, `BBox`, `Width` or `Height` does not change how the XObject is placed
+    pub fn with_entry(mut self, key: impl Into<String>, value: impl Into<Object>) -> Self {
+        self.dictionary.set(key.into(), value);
+        self
+    }
+    pub fn dictionary(&self) -> &Dictionary {
+        &self.dictionary
+    }
+    pub fn content(&self) -> &[u8] {
+        &self.content
+    }
+    pub fn placement(&self) -> StreamXObjectPlacement {
+        self.placement
+    }
+    /// The size before it is scaled. Images use the DPI
+    pub fn size(&self, dpi: f32) -> Size {
+        match self.placement {
+            StreamXObjectPlacement::Image(size) => size.into_pt_with_dpi(dpi),
+            StreamXObjectPlacement::Form(bbox) => bbox.size,
+        }
+    }
+    /// Maps the content onto `rect`
+    pub fn transforms(&self, rect: OutlineRect) -> Vec<CurTransMat> {
+        match self.placement {
+            StreamXObjectPlacement::Image(_) => vec![
+                CurTransMat::Scale(rect.size.width, rect.size.height),
+                CurTransMat::Position(rect.position),
+            ],
+            StreamXObjectPlacement::Form(bbox) => vec![
+                CurTransMat::Position(PdfPosition::new(-bbox.position.x, -bbox.position.y)),
+                CurTransMat::Scale(
+                    rect.size.width / bbox.size.width,
+                    rect.size.height / bbox.size.height,
+                ),
+                CurTransMat::Position(rect.position),
+            ],
+        }
+    }
+    pub(crate) fn into_stream(self) -> Stream {
+        Stream::new(self.dictionary, self.content)
+    }
+}
+fn rect_to_object(rect: OutlineRect) -> Object {
+    let OutlineRect { position, size } = rect;
+    Object::Array(vec![
+        position.x.into(),
+        position.y.into(),
+        (position.x + size.width).into(),
+        (position.y + size.height).into(),
+    ])
+}
+/// A rectangle from `[x1 y1 x2 y2]`. None if it is not a rectangle or has no size
+fn rect_from_object(object: &Object) -> Option<OutlineRect> {
+    let values = object
+        .as_array()?
+        .iter()
+        .map(|value| match value {
+            Object::Integer(value) => Some(*value as f32),
+            Object::Real(value) => Some(*value),
+            _ => None,
+        })
+        .collect::<Option<Vec<f32>>>()?;
+    let [x1, y1, x2, y2] = values.as_slice() else {
+        return None;
+    };
+    let (left, right) = (x1.min(*x2), x1.max(*x2));
+    let (bottom, top) = (y1.min(*y2), y1.max(*y2));
+    if right - left <= 0.0 || top - bottom <= 0.0 {
+        return None;
+    }
+    Some(OutlineRect {
+        position: PdfPosition::new(Pt(left), Pt(bottom)),
+        size: Size::new(Pt(right - left), Pt(top - bottom)),
+    })
+}
+#[cfg(test)]
+mod tests {
+    use tux_pdf_low::{
+        dictionary,
+        document::PdfDocumentWriter,
+        types::{Dictionary, Object},
+    };
+
+    use super::{StreamXObject, StreamXObjectPlacement, rect_from_object};
+    use crate::{
+        document::PdfDocument,
+        graphics::{
+            LayerType, PdfPosition,
+            image::{ImageFit, PdfImage},
+            primitives::ctm::CurTransMat,
+            shapes::OutlineRect,
+            size::Size,
+        },
+        page::{PdfPage, page_sizes::A4},
+        units::{Px, UnitType},
+    };
+
+    #[test]
+    fn placement() {
+        let missing_bbox = dictionary! {
+            "Subtype" => Object::name("Form")
+        };
+        assert!(StreamXObject::new(missing_bbox, Vec::new()).is_err());
+        let image = dictionary! {
+            "Subtype" => Object::name("Image"),
+            "Width" => 30,
+            "Height" => 20
+        };
+        let image = StreamXObject::new(image, vec![0; 600]).unwrap();
+        assert_eq!(
+            image.placement(),
+            StreamXObjectPlacement::Image(Size::new(Px(30), Px(20)))
+        );
+
+        let bbox = OutlineRect {
+            position: PdfPosition::new(10f32.pt(), 10f32.pt()),
+            size: Size::new(100f32.pt(), 50f32.pt()),
+        };
+        let form = StreamXObject::form(bbox, Vec::new());
+        let parsed = StreamXObject::new(form.dictionary().clone(), Vec::new()).unwrap();
+        assert_eq!(parsed.placement(), StreamXObjectPlacement::Form(bbox));
+        let matrix: CurTransMat = form
+            .transforms(OutlineRect {
+                position: PdfPosition::new(5f32.pt(), 0f32.pt()),
+                size: Size::new(200f32.pt(), 100f32.pt()),
+            })
+            .into_iter()
+            .product();
+        let matrix: [f32; 6] = matrix.into();
+        assert_eq!(matrix, [2.0, 0.0, 0.0, 2.0, -15.0, -20.0]);
+    }
+    #[test]
+    fn stream_xobject_is_written() -> anyhow::Result<()> {
+        let mut document = PdfDocument::new("Stream XObject");
+        let bbox = OutlineRect {
+            position: PdfPosition::default(),
+            size: Size::new(100f32.pt(), 50f32.pt()),
+        };
+        let logo = document.add_xobject(StreamXObject::form(
+            bbox,
+            b"0 0 1 rg 0 0 100 50 re f".to_vec(),
+        ));
+        let mut image = PdfImage::new(logo).with_fit(ImageFit::Contain);
+        image.set_fit_area(OutlineRect {
+            position: PdfPosition::new(10f32.pt(), 10f32.pt()),
+            size: Size::new(50f32.pt(), 50f32.pt()),
+        });
+        let mut page = PdfPage::new_from_page_size(A4);
+        page.add_to_layer(image)?;
+        document.add_page(page);
+        let writer = document.write_into_pdf_document_writer()?;
+
+        let (form_id, form) = writer
+            .objects()
+            .find_map(|(id, object)| {
+                let stream = object.as_stream()?;
+                (stream.dictionary.get("Subtype") == Some(&Object::name("Form")))
+                    .then_some((*id, stream))
+            })
+            .expect("The form XObject is written");
+        assert_eq!(form.dictionary.get("Type"), Some(&Object::name("XObject")));
+        assert_eq!(
+            form.dictionary.get("BBox").and_then(rect_from_object),
+            Some(bbox)
+        );
+        assert_eq!(form.content, b"0 0 1 rg 0 0 100 50 re f");
+
+        // Pages without layers inherit the shared resources of the page tree
+        fn resolve<'a>(
+            writer: &'a PdfDocumentWriter,
+            object: &'a Object,
+        ) -> Option<&'a Dictionary> {
+            match object {
+                Object::Reference(id) => writer.get_object(id).and_then(Object::as_dictionary),
+                object => object.as_dictionary(),
+            }
+        }
+        let pages = writer
+            .objects()
+            .filter_map(|(_, object)| object.as_dictionary())
+            .find(|dictionary| dictionary.get("Type") == Some(&Object::name("Pages")))
+            .expect("The page tree is written");
+        let xobjects = pages
+            .get("Resources")
+            .and_then(|resources| resolve(&writer, resources))
+            .and_then(|resources| resources.get("XObject"))
+            .and_then(|xobjects| resolve(&writer, xobjects))
+            .expect("The pages have XObject resources");
+        assert!(
+            xobjects
+                .iter()
+                .any(|(_, value)| value == &Object::Reference(form_id)),
+            "The form is not in the XObject resources"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,381 @@
+use std::{fmt::Debug, mem, sync::Arc};
+
+use crate::{
+    TuxPdfError,
+    graphics::{Anchor, AnchorOffset, TextBlock, TextStyle},
+    page::{PageSettings, PdfPage, page_sizes::A4},
+    units::Pt,
+};
+
+use super::{PageLabel, PageNumberStyle, PdfDocument};
+
+/// Where a page is within the document and its [Section]. Passed to a [PageDecorator]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageInfo<'section> {
+    /// Zero based index of the page in the document
+    pub page_index: usize,
+    /// Zero based index of the page in its section
+    pub index_in_section: usize,
+    pub pages_in_section: usize,
+    pub number_of_pages: usize,
+    /// The page number formatted with the [PageLabel] of the section. Such as `iv`
+    pub label: String,
+    pub section: &'section str,
+}
+/// Adds content such as headers and footers to every page of a [Section]
+///
+/// Decorators run when the document is written. Closures with the same arguments can be used as decorators
+pub trait PageDecorator {
+    fn decorate(
+        &self,
+        document: &PdfDocument,
+        page: &mut PdfPage,
+        info: &PageInfo<'_>,
+    ) -> Result<(), TuxPdfError>;
+}
+/// A line of text placed on every page such as `Page {page} of {pages}`
+///
+/// The template can contain `{page}` for the page label, `{pages}` for the number of pages in the section,
+/// `{total_pages}` for the number of pages in the document and `{section}` for the name of the section
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextDecorator {
+    pub template: String,
+    pub style: TextStyle,
+    /// The point of the [content area](PdfPage::content_area) the text is placed at
+    pub anchor: Anchor,
+    /// Moves the text away from the anchor. Uses a top left origin
+    ///
+    /// When not set text at the top or bottom is moved into the middle of the page margin
+    pub offset: Option<AnchorOffset>,
+}
+impl TextDecorator {
+    pub fn new(template: impl Into<String>, anchor: Anchor) -> Self {
+        Self {
+            template: template.into(),
+            style: TextStyle::default(),
+            anchor,
+            offset: None,
+        }
+    }
+    /// Centered in the top margin of the page
+    pub fn header(template: impl Into<String>) -> Self {
+        Self::new(template, Anchor::TopCenter)
+    }
+    /// Centered in the bottom margin of the page
+    pub fn footer(template: impl Into<String>) -> Self {
+        Self::new(template, Anchor::BottomCenter)
+    }
+    pub fn with_style(mut self, style: TextStyle) -> Self {
+        self.style = style;
+        self
+    }
+    pub fn with_offset(mut self, offset: impl Into<AnchorOffset>) -> Self {
+        self.offset = Some(offset.into());
+        self
+    }
+    /// The template with the placeholders replaced
+    pub fn text(&self, info: &PageInfo<'_>) -> String {
+        self.template
+            .replace("{page}", &info.label)
+            .replace("{pages}", &info.pages_in_section.to_string())
+            .replace("{total_pages}", &info.number_of_pages.to_string())
+            .replace("{section}", info.section)
+    }
+    fn default_offset(&self, page: &PdfPage) -> AnchorOffset {
+        let (_, vertical) = self.anchor.fractions();
+        let y = if vertical == 1f32 {
+            -page.margin.top.unwrap_or_default() / 2f32
+        } else if vertical == 0f32 {
+            page.margin.bottom.unwrap_or_default() / 2f32
+        } else {
+            Pt::default()
+        };
+        AnchorOffset::new(Pt::default(), y)
+    }
+}
+impl PageDecorator for TextDecorator {
+    fn decorate(
+        &self,
+        document: &PdfDocument,
+        page: &mut PdfPage,
+        info: &PageInfo<'_>,
+    ) -> Result<(), TuxPdfError> {
+        let block = TextBlock::from(self.text(info)).with_style(self.style.clone());
+        let offset = self.offset.unwrap_or_else(|| self.default_offset(page));
+        page.place(document, block, self.anchor, offset)?;
+        Ok(())
+    }
+}
+impl<F> PageDecorator for F
+where
+    F: Fn(&PdfDocument, &mut PdfPage, &PageInfo<'_>) -> Result<(), TuxPdfError>,
+{
+    fn decorate(
+        &self,
+        document: &PdfDocument,
+        page: &mut PdfPage,
+        info: &PageInfo<'_>,
+    ) -> Result<(), TuxPdfError> {
+        self(document, page, info)
+    }
+}
+/// A range of pages with its own page numbers, headers, footers and page template
+///
+/// A section starts at the page added after [PdfDocument::start_section] and ends where the next section starts
+///
+/// ```rust
+/// use tux_pdf::{
+///     document::{PageLabel, PageNumberStyle, PdfDocument, Section, TextDecorator},
+///     graphics::Margin,
+///     page::{PageSettings, page_sizes::A4},
+///     units::UnitType,
+/// };
+/// let mut document = PdfDocument::new("Manual");
+/// let template = PageSettings::portrait(A4).with_margin(Margin::all(20f32.mm().pt()));
+/// document.start_section(
+///     Section::new("Front Matter")
+///         .with_label(PageLabel::new(PageNumberStyle::LowerRoman))
+///         .with_template(template)
+///         .with_footer(TextDecorator::footer("{page}")),
+/// );
+/// document.add_page(document.new_section_page());
+/// document.start_section(
+///     Section::new("Body")
+///         .with_template(template)
+///         .with_header(TextDecorator::header("{section}"))
+///         .with_footer(TextDecorator::footer("Page {page} of {pages}")),
+/// );
+/// document.add_page(document.new_section_page());
+/// ```
+#[derive(Clone)]
+pub struct Section {
+    pub name: String,
+    /// Page numbers of the section. Also written as the page labels viewers show
+    pub label: PageLabel,
+    /// Used by [Section::new_page]
+    pub template: PageSettings,
+    pub header: Option<Arc<dyn PageDecorator>>,
+    pub footer: Option<Arc<dyn PageDecorator>>,
+}
+impl Debug for Section {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Section")
+            .field("name", &self.name)
+            .field("label", &self.label)
+            .field("template", &self.template)
+            .finish_non_exhaustive()
+    }
+}
+impl Section {
+    /// A4 pages numbered 1, 2, 3 without a header or footer
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            label: PageLabel::default(),
+            template: PageSettings::portrait(A4),
+            header: None,
+            footer: None,
+        }
+    }
+    pub fn with_label(mut self, label: PageLabel) -> Self {
+        self.label = label;
+        self
+    }
+    /// Changes the number style and keeps the prefix and first number of the label
+    pub fn with_number_style(mut self, style: PageNumberStyle) -> Self {
+        self.label.style = style;
+        self
+    }
+    pub fn with_template(mut self, template: impl Into<PageSettings>) -> Self {
+        self.template = template.into();
+        self
+    }
+    pub fn with_header(mut self, header: impl PageDecorator + 'static) -> Self {
+        self.header = Some(Arc::new(header));
+        self
+    }
+    pub fn with_footer(mut self, footer: impl PageDecorator + 'static) -> Self {
+        self.footer = Some(Arc::new(footer));
+        self
+    }
+    /// An empty page created from the template
+    pub fn new_page(&self) -> PdfPage {
+        PdfPage::new(self.template)
+    }
+}
+impl PdfDocument {
+    /// Starts a section at the next page added to the document. Replaces a section that starts at the same page
+    pub fn start_section(&mut self, section: Section) {
+        let first_page = self.pages.len();
+        self.sections.retain(|(start, _)| *start != first_page);
+        self.sections.push((first_page, section));
+    }
+    /// The sections and the index of their first page
+    pub fn sections(&self) -> &[(usize, Section)] {
+        &self.sections
+    }
+    /// The section the page belongs to. None if the page is before the first section
+    pub fn section_of_page(&self, page_index: usize) -> Option<&Section> {
+        self.sections
+            .iter()
+            .rev()
+            .find(|(start, _)| *start <= page_index)
+            .map(|(_, section)| section)
+    }
+    /// An empty page from the template of the current section. An A4 page if no section was started
+    pub fn new_section_page(&self) -> PdfPage {
+        self.sections
+            .last()
+            .map(|(_, section)| section.new_page())
+            .unwrap_or_else(|| PdfPage::new_from_page_size(A4))
+    }
+    /// Runs the decorators of each section and adds their page labels
+    ///
+    /// Called when the document is written. Sections are removed afterwards so decorators only run once
+    pub(crate) fn apply_sections(&mut self) -> Result<(), TuxPdfError> {
+        let sections = mem::take(&mut self.sections);
+        if sections.is_empty() {
+            return Ok(());
+        }
+        let mut pages = mem::take(&mut self.pages);
+        let number_of_pages = pages.len();
+        let mut result = Ok(());
+        for (index, (start, section)) in sections.iter().enumerate() {
+            let end = sections
+                .get(index + 1)
+                .map(|(next, _)| *next)
+                .unwrap_or(number_of_pages)
+                .min(number_of_pages);
+            if *start >= end {
+                continue;
+            }
+            self.metadata
+                .catalog_info
+                .page_labels
+                .insert(*start, section.label.clone());
+            result =
+                self.decorate_section(section, &mut pages[*start..end], *start, number_of_pages);
+            if result.is_err() {
+                break;
+            }
+        }
+        self.pages = pages;
+        result
+    }
+    fn decorate_section(
+        &self,
+        section: &Section,
+        pages: &mut [PdfPage],
+        first_page: usize,
+        number_of_pages: usize,
+    ) -> Result<(), TuxPdfError> {
+        let pages_in_section = pages.len();
+        for (index_in_section, page) in pages.iter_mut().enumerate() {
+            let info = PageInfo {
+                page_index: first_page + index_in_section,
+                index_in_section,
+                pages_in_section,
+                number_of_pages,
+                label: section.label.format(index_in_section),
+                section: &section.name,
+            };
+            for decorator in [&section.header, &section.footer].into_iter().flatten() {
+                decorator.decorate(self, page, &info)?;
+            }
+        }
+        Ok(())
+    }
+}
+#[cfg(test)]
+mod tests {
+    use tux_pdf_low::types::{Object, ObjectId};
+
+    use super::{PageInfo, Section, TextDecorator};
+    use crate::{
+        TuxPdfError,
+        document::{BuiltinFont, PageLabel, PageNumberStyle, PdfDocument},
+        graphics::{Margin, PdfObject, TextStyle},
+        page::{PageSettings, PdfPage, page_sizes::A5},
+        units::UnitType,
+    };
+
+    #[test]
+    fn number_styles() {
+        assert_eq!(PageNumberStyle::LowerRoman.format(14), "xiv");
+        assert_eq!(PageNumberStyle::UpperRoman.format(1994), "MCMXCIV");
+        assert_eq!(PageNumberStyle::LowerAlpha.format(28), "bb");
+        let label = PageLabel::new(PageNumberStyle::Decimal)
+            .with_prefix("A-")
+            .with_first_number(5);
+        assert_eq!(label.format(2), "A-7");
+    }
+    #[test]
+    fn decorate_sections() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Sections");
+        let font_ref = doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        let style = TextStyle {
+            font_ref,
+            ..Default::default()
+        };
+        let template = PageSettings::portrait(A5).with_margin(Margin::all(30f32.pt()));
+        // A page before the first section is not decorated
+        doc.add_page(PdfPage::new(template));
+        doc.start_section(
+            Section::new("Front")
+                .with_number_style(PageNumberStyle::LowerRoman)
+                .with_template(template)
+                .with_footer(TextDecorator::footer("{page}").with_style(style.clone())),
+        );
+        for _ in 0..2 {
+            doc.add_page(doc.new_section_page());
+        }
+        doc.start_section(Section::new("Body").with_header(
+            |_: &PdfDocument, page: &mut PdfPage, info: &PageInfo<'_>| {
+                assert_eq!(info.section, "Body");
+                page.contents.clear();
+                Ok::<_, TuxPdfError>(())
+            },
+        ));
+        doc.add_page(doc.new_section_page());
+        assert_eq!(
+            doc.section_of_page(0).map(|section| section.name.as_str()),
+            None
+        );
+        assert_eq!(doc.pages()[1].size(), A5);
+
+        doc.apply_sections()?;
+        assert!(doc.sections().is_empty());
+        assert!(doc.pages()[0].contents.is_empty());
+        let footers: Vec<_> = doc.pages()[1..3]
+            .iter()
+            .map(|page| match &page.contents[0] {
+                PdfObject::TextBlock(block) => block.content.text(),
+                _ => panic!("Expected the footer"),
+            })
+            .collect();
+        assert_eq!(footers, ["i", "ii"]);
+        // The footer is in the bottom margin
+        let PdfObject::TextBlock(footer) = &doc.pages()[1].contents[0] else {
+            unreachable!()
+        };
+        assert!(footer.position.y < 30f32.pt());
+
+        let labels = doc
+            .metadata
+            .catalog_info
+            .clone()
+            .create_catalog_object(ObjectId::from(1u32), &[ObjectId::from(2u32); 4]);
+        let Some(Object::Array(numbers)) = labels
+            .page_labels
+            .and_then(|labels| labels.get("Nums").cloned())
+        else {
+            panic!("Page labels are missing");
+        };
+        // The page before the first section gets a label as the tree has to start at the first page
+        assert_eq!(numbers.len(), 6);
+        assert_eq!(numbers[0], Object::Integer(0));
+        assert_eq!(numbers[2], Object::Integer(1));
+        assert_eq!(numbers[4], Object::Integer(3));
+        Ok(())
+    }
+}
@@ -5,12 +5,15 @@ mod font;
 pub use font::*;
 mod actions;
 pub use actions::*;
+mod destination;
+pub use destination::*;
 use tux_pdf_low::{
     dictionary,
     types::{Dictionary, Object, ObjectId, ReferenceOrObject},
 };
 
 use super::{PageLayout, PageMode};
+use crate::page::{PageTransition, PageViewport};
 pub trait PdfType {
     fn into_object(self) -> Object;
 }
@@ -85,8 +88,14 @@ pub struct CatalogObject {
     pub language: Option<String>,
 
     pub oc_properties: Option<OptionalContentProperties>,
+    /// An action dictionary or a destination array
+    pub open_action: Option<Object>,
 
-    pub open_action: Option<PdfAction>,
+    pub viewer_preferences: Option<Dictionary>,
+    /// Name trees such as named destinations and document JavaScript
+    pub names: Option<Dictionary>,
+    /// Number tree of page labels
+    pub page_labels: Option<Dictionary>,
 }
 impl PdfDirectoryType for CatalogObject {
     fn dictionary_type_key() -> &'static str {
@@ -100,6 +109,9 @@ impl PdfDirectoryType for CatalogObject {
             page_mode,
             oc_properties,
             open_action,
+            viewer_preferences,
+            names,
+            page_labels,
         } = self;
         let mut catalog: Dictionary = dictionary! {
             "Type" => Object::name(Self::dictionary_type_key()),
@@ -116,6 +128,15 @@ impl PdfDirectoryType for CatalogObject {
         if let Some(open_action) = open_action {
             catalog.set("OpenAction", open_action);
         }
+        if let Some(viewer_preferences) = viewer_preferences {
+            catalog.set("ViewerPreferences", viewer_preferences);
+        }
+        if let Some(names) = names {
+            catalog.set("Names", names);
+        }
+        if let Some(page_labels) = page_labels {
+            catalog.set("PageLabels", page_labels);
+        }
 
         catalog
     }
@@ -125,7 +146,10 @@ impl PdfDirectoryType for CatalogObject {
 pub struct Resources {
     pub font: Option<ReferenceOrObject<Dictionary>>,
     pub xobject: Option<ReferenceOrObject<Dictionary>>,
+    pub ext_g_state: Option<ReferenceOrObject<Dictionary>>,
     pub properties: Option<Dictionary>,
+    /// Categories added with [RawObjects::add_resource](crate::document::RawObjects::add_resource)
+    pub raw: Option<Dictionary>,
 }
 impl PdfDirectoryType for Resources {
     fn dictionary_type_key() -> &'static str {
@@ -135,7 +159,9 @@ impl PdfDirectoryType for Resources {
         let Resources {
             font,
             xobject,
+            ext_g_state,
             properties,
+            raw,
         } = self;
         let mut dict = Dictionary::new();
         if let Some(font) = font {
@@ -144,9 +170,15 @@ impl PdfDirectoryType for Resources {
         if let Some(xobject) = xobject {
             dict.set("XObject", xobject);
         }
+        if let Some(ext_g_state) = ext_g_state {
+            dict.set("ExtGState", ext_g_state);
+        }
         if let Some(properties) = properties {
             dict.set("Properties", properties);
         }
+        for (category, entries) in raw.into_iter().flatten() {
+            dict.set(category, entries);
+        }
         dict
     }
 }
@@ -167,6 +199,8 @@ pub struct Page {
     pub bleed_box: Option<Vec<Object>>,
     pub trim_box: Option<Vec<Object>>,
     pub rotation: Option<Object>,
+    pub transition: Option<PageTransition>,
+    pub viewports: Vec<PageViewport>,
 }
 impl PdfDirectoryType for Page {
     fn dictionary_type_key() -> &'static str {
@@ -184,6 +218,8 @@ impl PdfDirectoryType for Page {
             bleed_box,
             trim_box,
             rotation,
+            transition,
+            viewports,
         } = self;
         let mut dictionary = dictionary! {
             "Type" => Object::name("Page"),
@@ -210,6 +246,19 @@ impl PdfDirectoryType for Page {
         if let Some(rotation) = rotation {
             dictionary.set("Rotate", rotation);
         }
+        if let Some(transition) = transition {
+            dictionary.set("Trans", transition.dictionary());
+            if let Some(display_duration) = transition.display_duration {
+                dictionary.set("Dur", display_duration);
+            }
+        }
+        if !viewports.is_empty() {
+            let viewports: Vec<Object> = viewports
+                .iter()
+                .map(|viewport| viewport.dictionary().into())
+                .collect();
+            dictionary.set("VP", viewports);
+        }
         dictionary
     }
 }
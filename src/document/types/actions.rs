@@ -40,14 +40,73 @@ impl From<PdfAction> for Object {
 #[derive(Debug, Clone, PartialEq, From)]
 pub enum PdfActionType {
     JavaScript(JavascriptAction),
+    GoTo(GoToAction),
+    Named(NamedAction),
 }
 impl From<PdfActionType> for Dictionary {
     fn from(value: PdfActionType) -> Self {
         match value {
             PdfActionType::JavaScript(js) => js.into(),
+            PdfActionType::GoTo(go_to) => go_to.into(),
+            PdfActionType::Named(named) => named.into(),
         }
     }
 }
+/// Jumps to a named destination
+///
+/// See [PdfDocument::add_named_destination](crate::document::PdfDocument::add_named_destination)
+///
+/// Section 12.6.4.2
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoToAction {
+    pub destination: String,
+}
+impl GoToAction {
+    pub fn named(destination: impl Into<String>) -> Self {
+        Self {
+            destination: destination.into(),
+        }
+    }
+}
+impl From<GoToAction> for Dictionary {
+    fn from(value: GoToAction) -> Self {
+        let mut dict = Dictionary::new();
+        dict.set("S", Object::name("GoTo"));
+        dict.set("D", Object::string_literal_owned(value.destination));
+        dict
+    }
+}
+/// Actions every PDF viewer is expected to support
+///
+/// Section 12.6.4.11
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NamedAction {
+    NextPage,
+    PrevPage,
+    FirstPage,
+    LastPage,
+    /// Opens the print dialog. Not part of the specification but supported by most viewers
+    Print,
+}
+impl NamedAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            NamedAction::NextPage => "NextPage",
+            NamedAction::PrevPage => "PrevPage",
+            NamedAction::FirstPage => "FirstPage",
+            NamedAction::LastPage => "LastPage",
+            NamedAction::Print => "Print",
+        }
+    }
+}
+impl From<NamedAction> for Dictionary {
+    fn from(value: NamedAction) -> Self {
+        let mut dict = Dictionary::new();
+        dict.set("S", Object::name("Named"));
+        dict.set("N", Object::name(value.name()));
+        dict
+    }
+}
 #[derive(Debug, Clone, PartialEq)]
 pub struct JavascriptAction {
     pub action: Either<PdfString, Stream>,
@@ -59,6 +118,12 @@ impl From<&str> for JavascriptAction {
         }
     }
 }
+impl JavascriptAction {
+    /// Opens the print dialog
+    pub fn print() -> Self {
+        Self::from("this.print({bUI: true, bSilent: false, bShrinkToFit: true});")
+    }
+}
 impl From<String> for JavascriptAction {
     fn from(value: String) -> Self {
         Self {
@@ -0,0 +1,193 @@
+use tux_pdf_low::types::{Object, ObjectId};
+
+use crate::{
+    graphics::{PdfPosition, shapes::OutlineRect},
+    units::Pt,
+};
+
+/// How the page is shown when jumping to a [PdfDestination]
+///
+/// Section 12.3.2.2
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DestinationView {
+    /// Position the top left corner of the window at `left`,`top` with the zoom.
+    ///
+    /// `None` keeps the current value of the viewer. A zoom of `1.0` is 100%
+    Xyz {
+        left: Option<Pt>,
+        top: Option<Pt>,
+        zoom: Option<f32>,
+    },
+    /// Fit the whole page in the window
+    #[default]
+    Fit,
+    /// Fit the width of the page in the window with `top` at the top of the window
+    FitH { top: Option<Pt> },
+    /// Fit the height of the page in the window with `left` at the left of the window
+    FitV { left: Option<Pt> },
+    /// Fit the rectangle in the window
+    FitR {
+        left: Pt,
+        bottom: Pt,
+        right: Pt,
+        top: Pt,
+    },
+    /// Fit the bounding box of the page content in the window
+    FitB,
+}
+fn optional(value: Option<impl Into<Object>>) -> Object {
+    value.map(Into::into).unwrap_or(Object::Null)
+}
+impl DestinationView {
+    /// The view after the page was scaled by `scale` and moved by `offset`. Such as by [imposition](crate::document::PdfDocument::impose)
+    ///
+    /// `area` is where the page ended up. Views that fit the page fit the area instead
+    pub(crate) fn placed(self, scale: f32, offset: PdfPosition, area: OutlineRect) -> Self {
+        let x = |value: Pt| value * scale + offset.x;
+        let y = |value: Pt| value * scale + offset.y;
+        let PdfPosition {
+            x: area_left,
+            y: area_bottom,
+        } = area.position;
+        let PdfPosition {
+            x: area_right,
+            y: area_top,
+        } = area.upper_right();
+        match self {
+            DestinationView::Xyz { left, top, zoom } => DestinationView::Xyz {
+                left: left.map(x),
+                top: top.map(y),
+                zoom: zoom.map(|zoom| zoom / scale),
+            },
+            DestinationView::FitH { top } => DestinationView::FitR {
+                left: area_left,
+                bottom: area_bottom,
+                right: area_right,
+                top: top.map(y).unwrap_or(area_top),
+            },
+            DestinationView::FitV { left } => DestinationView::FitR {
+                left: left.map(x).unwrap_or(area_left),
+                bottom: area_bottom,
+                right: area_right,
+                top: area_top,
+            },
+            DestinationView::FitR {
+                left,
+                bottom,
+                right,
+                top,
+            } => DestinationView::FitR {
+                left: x(left),
+                bottom: y(bottom),
+                right: x(right),
+                top: y(top),
+            },
+            DestinationView::Fit | DestinationView::FitB => DestinationView::FitR {
+                left: area_left,
+                bottom: area_bottom,
+                right: area_right,
+                top: area_top,
+            },
+        }
+    }
+    fn write_to(self, array: &mut Vec<Object>) {
+        match self {
+            DestinationView::Xyz { left, top, zoom } => {
+                array.push(Object::name("XYZ"));
+                array.push(optional(left));
+                array.push(optional(top));
+                array.push(optional(zoom));
+            }
+            DestinationView::Fit => array.push(Object::name("Fit")),
+            DestinationView::FitH { top } => {
+                array.push(Object::name("FitH"));
+                array.push(optional(top));
+            }
+            DestinationView::FitV { left } => {
+                array.push(Object::name("FitV"));
+                array.push(optional(left));
+            }
+            DestinationView::FitR {
+                left,
+                bottom,
+                right,
+                top,
+            } => {
+                array.push(Object::name("FitR"));
+                array.extend([left.into(), bottom.into(), right.into(), top.into()]);
+            }
+            DestinationView::FitB => array.push(Object::name("FitB")),
+        }
+    }
+}
+/// A page and a view of that page
+///
+/// Pages are referenced by their zero based index in the document and resolved when the document is written.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PdfDestination {
+    pub page: usize,
+    pub view: DestinationView,
+}
+impl PdfDestination {
+    /// Show the whole page
+    pub fn fit_page(page: usize) -> Self {
+        Self {
+            page,
+            view: DestinationView::Fit,
+        }
+    }
+    /// Show the page at the zoom. A zoom of `1.0` is 100%
+    pub fn zoom(page: usize, zoom: f32) -> Self {
+        Self {
+            page,
+            view: DestinationView::Xyz {
+                left: None,
+                top: None,
+                zoom: Some(zoom),
+            },
+        }
+    }
+    /// Show the page fit to the width of the window
+    pub fn fit_width(page: usize) -> Self {
+        Self {
+            page,
+            view: DestinationView::FitH { top: None },
+        }
+    }
+    pub fn with_view(mut self, view: DestinationView) -> Self {
+        self.view = view;
+        self
+    }
+    /// Creates the destination array
+    ///
+    /// Returns `None` if the page does not exist
+    pub(crate) fn to_object(self, pages: &[ObjectId]) -> Option<Object> {
+        let page = *pages.get(self.page)?;
+        let mut array = vec![Object::from(page)];
+        self.view.write_to(&mut array);
+        Some(Object::Array(array))
+    }
+}
+#[cfg(test)]
+mod tests {
+    use tux_pdf_low::types::{Object, ObjectId};
+
+    use super::PdfDestination;
+
+    #[test]
+    fn destination_array() {
+        let pages = [ObjectId::from(3u32), ObjectId::from(7u32)];
+        let destination = PdfDestination::zoom(1, 1.5).to_object(&pages).unwrap();
+        assert_eq!(
+            destination,
+            Object::Array(vec![
+                Object::Reference(ObjectId::from(7u32)),
+                Object::name("XYZ"),
+                Object::Null,
+                Object::Null,
+                Object::Real(1.5),
+            ])
+        );
+        assert_eq!(PdfDestination::fit_page(2).to_object(&pages), None);
+    }
+}
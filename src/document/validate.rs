@@ -0,0 +1,325 @@
+use std::fmt::Display;
+
+use crate::{
+    graphics::{
+        GraphicItems, PdfObject, PdfPosition, TextBlock, primitives::PathSegment,
+        shapes::OutlineRect, text::TextModifier,
+    },
+    units::Pt,
+};
+
+use super::{FontRef, IdType, LayerId, PdfDocument, XObjectId};
+
+/// Longest name PDF readers are required to support
+pub const MAX_NAME_LENGTH: usize = 127;
+
+/// Where an object that caused a [ValidationWarning] lives
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObjectLocation {
+    /// Zero based page and object index
+    Page { page: usize, object: usize },
+    /// Zero based object index within the layer
+    Layer { layer: LayerId, object: usize },
+}
+impl Display for ObjectLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ObjectLocation::Page { page, object } => write!(f, "page {page} object {object}"),
+            ObjectLocation::Layer { layer, object } => {
+                write!(f, "layer {:?} object {object}", layer.as_str())
+            }
+        }
+    }
+}
+/// A likely mistake found by [PdfDocument::validate]
+///
+/// These will not always cause the document to fail to write but usually produce broken or blank output
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationWarning {
+    /// The media box of the page has no width or height
+    ZeroSizePage { page: usize },
+    /// The page has no content and no layers
+    EmptyPage { page: usize },
+    /// The object starts outside of the media box of the page
+    OutsideMediaBox {
+        location: ObjectLocation,
+        position: PdfPosition,
+    },
+    /// Text uses a font that was never registered with the document
+    UnregisteredFont {
+        location: ObjectLocation,
+        font: FontRef,
+    },
+    /// An image uses an XObject that was never added to the document
+    UnregisteredXObject {
+        location: ObjectLocation,
+        id: XObjectId,
+    },
+    /// Text with a font size of zero or less will not be visible
+    ZeroFontSize { location: ObjectLocation },
+    /// A resource name is longer than [MAX_NAME_LENGTH]
+    NameTooLong { name: String },
+}
+impl Display for ValidationWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationWarning::ZeroSizePage { page } => write!(f, "page {page} has a zero size"),
+            ValidationWarning::EmptyPage { page } => write!(f, "page {page} is empty"),
+            ValidationWarning::OutsideMediaBox { location, position } => write!(
+                f,
+                "{location} is positioned outside of the media box at ({}, {})",
+                position.x, position.y
+            ),
+            ValidationWarning::UnregisteredFont { location, font } => {
+                write!(f, "{location} uses unregistered font {font:?}")
+            }
+            ValidationWarning::UnregisteredXObject { location, id } => {
+                write!(f, "{location} uses unregistered xobject {id}")
+            }
+            ValidationWarning::ZeroFontSize { location } => {
+                write!(f, "{location} has a font size of zero")
+            }
+            ValidationWarning::NameTooLong { name } => write!(
+                f,
+                "name {name:?} is {} bytes long. The maximum is {MAX_NAME_LENGTH}",
+                name.len()
+            ),
+        }
+    }
+}
+
+impl PdfDocument {
+    /// Checks the document for common mistakes before it is written
+    ///
+    /// An empty result does not guarantee the document is valid.
+    pub fn validate(&self) -> Vec<ValidationWarning> {
+        let mut warnings = Vec::new();
+        self.validate_names(&mut warnings);
+        for (layer_id, layer) in &self.resources.layers.map {
+            for (index, object) in layer.operations.iter().enumerate() {
+                let location = ObjectLocation::Layer {
+                    layer: layer_id.clone(),
+                    object: index,
+                };
+                self.validate_object(object, &location, None, &mut warnings);
+            }
+        }
+        for (page_index, page) in self.pages.iter().enumerate() {
+            let size = page.size();
+            if size.width <= Pt::default() || size.height <= Pt::default() {
+                warnings.push(ValidationWarning::ZeroSizePage { page: page_index });
+            }
+            if page.contents.is_empty() && page.layers.is_empty() {
+                warnings.push(ValidationWarning::EmptyPage { page: page_index });
+            }
+            for (index, object) in page.contents.iter().enumerate() {
+                let location = ObjectLocation::Page {
+                    page: page_index,
+                    object: index,
+                };
+                self.validate_object(object, &location, Some(&page.media_box), &mut warnings);
+            }
+        }
+        warnings
+    }
+    fn validate_names(&self, warnings: &mut Vec<ValidationWarning>) {
+        let names = self
+            .resources
+            .fonts
+            .map
+            .keys()
+            .map(|id| id.as_str())
+            .chain(self.resources.xobjects.map.keys().map(|id| id.as_str()))
+            .chain(self.resources.layers.map.keys().map(|id| id.as_str()));
+        for name in names {
+            if name.len() > MAX_NAME_LENGTH {
+                warnings.push(ValidationWarning::NameTooLong {
+                    name: name.to_owned(),
+                });
+            }
+        }
+    }
+    fn validate_object(
+        &self,
+        object: &PdfObject,
+        location: &ObjectLocation,
+        media_box: Option<&OutlineRect>,
+        warnings: &mut Vec<ValidationWarning>,
+    ) {
+        if let (Some(media_box), Some(position)) = (media_box, object_origin(object))
+            && !contains(media_box, position)
+        {
+            warnings.push(ValidationWarning::OutsideMediaBox {
+                location: location.clone(),
+                position,
+            });
+        }
+        match object {
+            PdfObject::TextBlock(block) => self.validate_text(block, location, warnings),
+            PdfObject::PathText(text) => {
+                if self
+                    .resources
+                    .fonts
+                    .internal_font_type(&text.style.font_ref)
+                    .is_none()
+                {
+                    warnings.push(ValidationWarning::UnregisteredFont {
+                        location: location.clone(),
+                        font: self.resources.fonts.resolve(&text.style.font_ref).clone(),
+                    });
+                }
+                if text.style.font_size <= Pt::default() {
+                    warnings.push(ValidationWarning::ZeroFontSize {
+                        location: location.clone(),
+                    });
+                }
+            }
+            PdfObject::Image(image) => {
+                if self.resources.xobjects.get_xobject(&image.image).is_none() {
+                    warnings.push(ValidationWarning::UnregisteredXObject {
+                        location: location.clone(),
+                        id: image.image.clone(),
+                    });
+                }
+            }
+            PdfObject::Clipped(group) => {
+                for object in &group.contents {
+                    self.validate_object(object, location, media_box, warnings);
+                }
+            }
+            PdfObject::Masked(group) => {
+                for object in &group.contents {
+                    self.validate_object(object, location, media_box, warnings);
+                }
+            }
+            PdfObject::Transformed(group) => {
+                // Positions inside of the group are not in page space
+                for object in &group.contents {
+                    self.validate_object(object, location, None, warnings);
+                }
+            }
+            PdfObject::NewLine
+            | PdfObject::Graphics(_)
+            | PdfObject::Styles(_)
+            | PdfObject::Raw(_) => {}
+        }
+    }
+    fn validate_text(
+        &self,
+        block: &TextBlock,
+        location: &ObjectLocation,
+        warnings: &mut Vec<ValidationWarning>,
+    ) {
+        let mut fonts = vec![&block.style.font_ref];
+        let mut font_sizes = vec![block.style.font_size];
+        let modifiers = block.content.iter().flat_map(|line| {
+            line.modifiers
+                .iter()
+                .chain(line.items.iter().flat_map(|item| item.modifiers.iter()))
+        });
+        for modifier in modifiers {
+            match modifier {
+                TextModifier::Font(font) => fonts.push(font),
+                TextModifier::FontSize(size) => font_sizes.push(*size),
+                _ => {}
+            }
+        }
+        let mut checked: Vec<&FontRef> = Vec::with_capacity(fonts.len());
+        for font in fonts {
+            if checked.contains(&font) {
+                continue;
+            }
+            checked.push(font);
+            if self.resources.fonts.internal_font_type(font).is_none() {
+                warnings.push(ValidationWarning::UnregisteredFont {
+                    location: location.clone(),
+                    font: self.resources.fonts.resolve(font).clone(),
+                });
+            }
+        }
+        if font_sizes.iter().any(|size| *size <= Pt::default()) {
+            warnings.push(ValidationWarning::ZeroFontSize {
+                location: location.clone(),
+            });
+        }
+    }
+}
+/// The point an object starts drawing from
+fn object_origin(object: &PdfObject) -> Option<PdfPosition> {
+    match object {
+        PdfObject::TextBlock(block) => Some(block.position),
+        PdfObject::PathText(text) => Some(text.path.point_at(Pt::default()).0),
+        PdfObject::Image(image) => Some(image.transform.position),
+        PdfObject::Graphics(item) => graphic_origin(item),
+        PdfObject::NewLine
+        | PdfObject::Styles(_)
+        | PdfObject::Clipped(_)
+        | PdfObject::Masked(_)
+        | PdfObject::Transformed(_)
+        | PdfObject::Raw(_) => None,
+    }
+}
+fn graphic_origin(item: &GraphicItems) -> Option<PdfPosition> {
+    match item {
+        GraphicItems::StraightLine(line) => Some(line.start),
+        GraphicItems::Line(line) => Some(line.start),
+        GraphicItems::Rectangle(rect) => Some(rect.position),
+        GraphicItems::OutlineRectangle(rect) => Some(rect.position),
+        // Positions inside of a transformed group are not in page space
+        GraphicItems::Group(group) if !group.transform.is_empty() => None,
+        GraphicItems::Group(group) => group.items.first().and_then(graphic_origin),
+        GraphicItems::Path(path) => path.segments.iter().find_map(|segment| match segment {
+            PathSegment::MoveTo(point) => Some(*point),
+            _ => None,
+        }),
+    }
+}
+fn contains(media_box: &OutlineRect, position: PdfPosition) -> bool {
+    let PdfPosition { x, y } = media_box.position;
+    position.x >= x
+        && position.x <= x + media_box.size.width
+        && position.y >= y
+        && position.y <= y + media_box.size.height
+}
+#[cfg(test)]
+mod tests {
+    use super::{ObjectLocation, ValidationWarning};
+    use crate::{
+        document::{BuiltinFont, FontRef, PdfDocument},
+        graphics::{LayerType, PdfPosition, TextBlock, TextStyle},
+        page::{PdfPage, page_sizes::A4},
+        units::UnitType,
+    };
+
+    #[test]
+    fn finds_common_mistakes() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Validation Test");
+        let mut page = PdfPage::new_from_page_size(A4);
+        page.add_to_layer(TextBlock {
+            content: "Off the page".into(),
+            style: TextStyle {
+                font_ref: FontRef::Builtin(BuiltinFont::Courier),
+                font_size: 0f32.pt(),
+                ..Default::default()
+            },
+            position: PdfPosition::new((-20f32).pt(), 20f32.pt()),
+            draw_as_lines: false,
+        })?;
+        doc.add_page(page);
+        doc.add_page(PdfPage::new_from_page_size(A4));
+
+        let location = ObjectLocation::Page { page: 0, object: 0 };
+        let warnings = doc.validate();
+        assert!(warnings.contains(&ValidationWarning::OutsideMediaBox {
+            location: location.clone(),
+            position: PdfPosition::new((-20f32).pt(), 20f32.pt()),
+        }));
+        assert!(warnings.contains(&ValidationWarning::UnregisteredFont {
+            location: location.clone(),
+            font: FontRef::Builtin(BuiltinFont::Courier),
+        }));
+        assert!(warnings.contains(&ValidationWarning::ZeroFontSize { location }));
+        assert!(warnings.contains(&ValidationWarning::EmptyPage { page: 1 }));
+        Ok(())
+    }
+}
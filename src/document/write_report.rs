@@ -0,0 +1,197 @@
+use std::io::Write;
+
+use ahash::HashMap;
+use tux_pdf_low::{
+    document::PdfDocumentWriter,
+    types::{Dictionary, Object, ObjectId, PdfObjectType},
+};
+
+use crate::TuxPdfResult;
+
+/// Statistics about a written document
+///
+/// Created by [super::PdfDocument::write_into_pdf_document_writer_with_report]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WriteReport {
+    /// Number of objects in the document writer
+    pub number_of_objects: usize,
+    pub number_of_pages: usize,
+    /// Embedded fonts and the font dictionaries
+    pub fonts: ResourceSize,
+    /// Image XObjects
+    pub images: ResourceSize,
+    /// Page and layer content streams
+    pub content_streams: ResourceSize,
+    /// Everything else. Pages, resources, the catalog, etc.
+    pub other: ResourceSize,
+    /// Bytes saved by streams stored with a filter
+    ///
+    /// tux-pdf does not compress streams itself.
+    /// This only counts image data that was already compressed. Such as DCTDecode images
+    pub compression_savings: usize,
+}
+impl WriteReport {
+    /// Total bytes of all objects. Does not include the header, cross reference table and trailer
+    pub fn total_bytes(&self) -> usize {
+        self.fonts.bytes + self.images.bytes + self.content_streams.bytes + self.other.bytes
+    }
+}
+/// Number of objects and encoded bytes for one kind of resource
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResourceSize {
+    pub objects: usize,
+    pub bytes: usize,
+}
+impl ResourceSize {
+    fn add(&mut self, bytes: usize) {
+        self.objects += 1;
+        self.bytes += bytes;
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReportedResource {
+    Font,
+    Image,
+    ContentStream,
+}
+/// Tracks which objects belong to which resource while the document is written
+#[derive(Debug, Default)]
+pub(crate) struct WriteReportBuilder {
+    resources: HashMap<ObjectId, ReportedResource>,
+}
+impl WriteReportBuilder {
+    pub fn add(&mut self, id: ObjectId, resource: ReportedResource) {
+        self.resources.insert(id, resource);
+    }
+    /// Marks every object created after `previous_max` as `resource`
+    pub fn add_created_since(
+        &mut self,
+        previous_max: u32,
+        document: &PdfDocumentWriter,
+        resource: ReportedResource,
+    ) {
+        for object_number in (previous_max + 1)..=document.max_object_number() {
+            self.add(ObjectId::from(object_number), resource);
+        }
+    }
+    pub fn build(
+        self,
+        document: &PdfDocumentWriter,
+        number_of_pages: usize,
+    ) -> TuxPdfResult<WriteReport> {
+        let mut report = WriteReport {
+            number_of_pages,
+            ..Default::default()
+        };
+        for (id, object) in document.objects() {
+            report.number_of_objects += 1;
+            let mut counter = ByteCounter::default();
+            object.encode_borrowed(&mut counter)?;
+            let size = match self.resources.get(id) {
+                Some(ReportedResource::Font) => &mut report.fonts,
+                Some(ReportedResource::Image) => &mut report.images,
+                Some(ReportedResource::ContentStream) => &mut report.content_streams,
+                None => &mut report.other,
+            };
+            size.add(counter.0);
+            if let Object::Stream(stream) = object {
+                report.compression_savings += decoded_image_size(&stream.dictionary)
+                    .map(|decoded| decoded.saturating_sub(stream.content.len()))
+                    .unwrap_or_default();
+            }
+        }
+        Ok(report)
+    }
+}
+/// The size of the image data once decoded. None if it is not a filtered image or the size is unknown
+fn decoded_image_size(dictionary: &Dictionary) -> Option<usize> {
+    let is_image = dictionary
+        .get("Subtype")
+        .and_then(Object::as_name)
+        .is_some_and(|subtype| subtype.0 == b"Image");
+    if !is_image || dictionary.get("Filter").is_none() {
+        return None;
+    }
+    let width = dictionary.get("Width")?.as_integer()?;
+    let height = dictionary.get("Height")?.as_integer()?;
+    let bits_per_component = dictionary.get("BitsPerComponent")?.as_integer()?;
+    let components = match dictionary.get("ColorSpace")?.as_name()?.0.as_slice() {
+        b"DeviceGray" => 1,
+        b"DeviceRGB" => 3,
+        b"DeviceCMYK" => 4,
+        _ => return None,
+    };
+    let bits = width * height * components * bits_per_component;
+    usize::try_from((bits + 7) / 8).ok()
+}
+/// Counts the bytes written instead of storing them
+#[derive(Debug, Default)]
+struct ByteCounter(usize);
+impl Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+#[cfg(test)]
+mod tests {
+    use tux_pdf_low::{dictionary, types::Object};
+
+    use super::decoded_image_size;
+    use crate::{
+        document::{BuiltinFont, PdfDocument},
+        graphics::{LayerType, PdfPosition, TextBlock, TextStyle},
+        page::{PdfPage, page_sizes::A4},
+        units::UnitType,
+    };
+
+    #[test]
+    fn report_sizes() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Write Report");
+        let font = doc
+            .resources
+            .fonts
+            .register_builtin_font(BuiltinFont::Helvetica);
+        let mut page = PdfPage::new_from_page_size(A4);
+        page.add_to_layer(TextBlock {
+            content: "Hello".into(),
+            style: TextStyle {
+                font_ref: font,
+                ..Default::default()
+            },
+            position: PdfPosition::new(20f32.pt(), 20f32.pt()),
+            draw_as_lines: false,
+        })?;
+        doc.add_page(page);
+        doc.add_page(PdfPage::new_from_page_size(A4));
+
+        let (writer, report) = doc.write_into_pdf_document_writer_with_report()?;
+        assert_eq!(report.number_of_pages, 2);
+        assert_eq!(report.number_of_objects, writer.objects().count());
+        assert_eq!(report.fonts.objects, 1);
+        assert_eq!(report.content_streams.objects, 1);
+        assert!(report.content_streams.bytes > 0);
+        assert_eq!(report.images.objects, 0);
+        assert_eq!(report.compression_savings, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn filtered_image_size() {
+        let mut image = dictionary! {
+            "Subtype" => Object::name("Image"),
+            "Width" => 10,
+            "Height" => 10,
+            "BitsPerComponent" => 8,
+            "ColorSpace" => Object::name("DeviceRGB")
+        };
+        assert_eq!(decoded_image_size(&image), None);
+
+        image.set("Filter", Object::name("DCTDecode"));
+        assert_eq!(decoded_image_size(&image), Some(300));
+    }
+}
@@ -0,0 +1,167 @@
+//! Context for errors
+//!
+//! Errors that happen while laying out or writing a document are wrapped in [TuxPdfError::WithContext]
+//! so you can tell which page, layout or table cell caused the failure.
+//! Displaying the error only prints the outermost context. Error reporters walk [std::error::Error::source] for the rest.
+//!
+//! ```rust
+//! use std::error::Error;
+//! use tux_pdf::{TuxPdfError, error::{ErrorContext, ErrorContextExt}};
+//!
+//! let result: Result<(), TuxPdfError> = Err(TuxPdfError::NoPagesCreated);
+//! let error = result
+//!     .context(ErrorContext::TableRow(3))
+//!     .context(ErrorContext::Page(12))
+//!     .unwrap_err();
+//! assert_eq!(error.page_index(), Some(12));
+//! assert_eq!(error.to_string(), "page 12");
+//! let source = error.source().unwrap();
+//! assert_eq!(source.to_string(), "table row 3");
+//! assert_eq!(source.source().unwrap().to_string(), "No pages created");
+//! ```
+use std::fmt::Display;
+
+use crate::TuxPdfError;
+
+/// Where an error happened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorContext {
+    /// Zero based index of the page in the document
+    Page(usize),
+    /// Name of the layer
+    Layer(String),
+    /// Zero based index of the object within a page or layer
+    Object(usize),
+    /// Name of the layout that failed
+    Layout(&'static str),
+    /// Zero based index of the row within a table. Does not include the header row
+    TableRow(usize),
+    /// Zero based row and column of a table cell. Does not include the header row
+    TableCell { row: usize, column: usize },
+    /// Id of a resource such as a font or XObject
+    Resource(String),
+    /// Zero based index of the record in a [MailMerge](crate::document::MailMerge)
+    MergeRecord(usize),
+}
+impl Display for ErrorContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorContext::Page(index) => write!(f, "page {index}"),
+            ErrorContext::Layer(name) => write!(f, "layer {name:?}"),
+            ErrorContext::Object(index) => write!(f, "object {index}"),
+            ErrorContext::Layout(name) => write!(f, "{name} layout"),
+            ErrorContext::TableRow(row) => write!(f, "table row {row}"),
+            ErrorContext::TableCell { row, column } => {
+                write!(f, "table cell (row {row}, column {column})")
+            }
+            ErrorContext::Resource(id) => write!(f, "resource {id:?}"),
+            ErrorContext::MergeRecord(index) => write!(f, "merge record {index}"),
+        }
+    }
+}
+impl TuxPdfError {
+    /// Wraps the error with the context
+    pub fn with_context(self, context: ErrorContext) -> Self {
+        TuxPdfError::WithContext {
+            context,
+            source: Box::new(self),
+        }
+    }
+    /// All contexts attached to the error. Outermost first
+    pub fn contexts(&self) -> Vec<&ErrorContext> {
+        let mut contexts = Vec::new();
+        let mut current = self;
+        while let TuxPdfError::WithContext { context, source } = current {
+            contexts.push(context);
+            current = source.as_ref();
+        }
+        contexts
+    }
+    /// The error without any context
+    pub fn root_cause(&self) -> &TuxPdfError {
+        let mut current = self;
+        while let TuxPdfError::WithContext { source, .. } = current {
+            current = source.as_ref();
+        }
+        current
+    }
+    /// The page the error happened on if known
+    pub fn page_index(&self) -> Option<usize> {
+        self.contexts()
+            .into_iter()
+            .find_map(|context| match context {
+                ErrorContext::Page(index) => Some(*index),
+                _ => None,
+            })
+    }
+}
+/// Adds [ErrorContext] to a result
+pub trait ErrorContextExt<T> {
+    fn context(self, context: ErrorContext) -> Result<T, TuxPdfError>;
+    /// Only builds the context if there is an error
+    fn with_context<F>(self, context: F) -> Result<T, TuxPdfError>
+    where
+        F: FnOnce() -> ErrorContext;
+}
+impl<T, E> ErrorContextExt<T> for Result<T, E>
+where
+    E: Into<TuxPdfError>,
+{
+    fn context(self, context: ErrorContext) -> Result<T, TuxPdfError> {
+        self.map_err(|error| {
+            let error: TuxPdfError = error.into();
+            error.with_context(context)
+        })
+    }
+    fn with_context<F>(self, context: F) -> Result<T, TuxPdfError>
+    where
+        F: FnOnce() -> ErrorContext,
+    {
+        self.map_err(|error| {
+            let error: TuxPdfError = error.into();
+            error.with_context(context())
+        })
+    }
+}
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use super::{ErrorContext, ErrorContextExt};
+    use crate::TuxPdfError;
+
+    #[test]
+    fn context_chain() {
+        let result: Result<(), TuxPdfError> = Err(TuxPdfError::NoPagesCreated);
+        let error = result
+            .context(ErrorContext::TableCell { row: 4, column: 2 })
+            .context(ErrorContext::Layout("table"))
+            .with_context(|| ErrorContext::Page(7))
+            .unwrap_err();
+        assert_eq!(
+            error.contexts(),
+            vec![
+                &ErrorContext::Page(7),
+                &ErrorContext::Layout("table"),
+                &ErrorContext::TableCell { row: 4, column: 2 }
+            ]
+        );
+        assert!(matches!(error.root_cause(), TuxPdfError::NoPagesCreated));
+        assert_eq!(error.page_index(), Some(7));
+        let mut messages = vec![error.to_string()];
+        let mut source = error.source();
+        while let Some(error) = source {
+            messages.push(error.to_string());
+            source = error.source();
+        }
+        assert_eq!(
+            messages,
+            vec![
+                "page 7",
+                "table layout",
+                "table cell (row 4, column 2)",
+                "No pages created"
+            ]
+        );
+    }
+}
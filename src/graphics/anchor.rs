@@ -0,0 +1,82 @@
+use crate::units::Length;
+
+use super::{PdfPosition, size::Size};
+
+/// A point on a box such as the page or a layout item
+///
+/// Used with [PdfPage::place](crate::page::PdfPage::place) to position items without doing the bottom left origin math yourself.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Anchor {
+    #[default]
+    TopLeft,
+    TopCenter,
+    TopRight,
+    CenterLeft,
+    Center,
+    CenterRight,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+}
+impl Anchor {
+    /// How far along the width (x) and height (y) the anchor is. Measured from the bottom left
+    pub fn fractions(&self) -> (f32, f32) {
+        match self {
+            Anchor::TopLeft => (0f32, 1f32),
+            Anchor::TopCenter => (0.5f32, 1f32),
+            Anchor::TopRight => (1f32, 1f32),
+            Anchor::CenterLeft => (0f32, 0.5f32),
+            Anchor::Center => (0.5f32, 0.5f32),
+            Anchor::CenterRight => (1f32, 0.5f32),
+            Anchor::BottomLeft => (0f32, 0f32),
+            Anchor::BottomCenter => (0.5f32, 0f32),
+            Anchor::BottomRight => (1f32, 0f32),
+        }
+    }
+    /// The anchor point of a box with the given bottom left corner and size
+    pub fn point_of(&self, bottom_left: PdfPosition, size: Size) -> PdfPosition {
+        let (x, y) = self.fractions();
+        PdfPosition {
+            x: bottom_left.x + size.width * x,
+            y: bottom_left.y + size.height * y,
+        }
+    }
+    /// The bottom left corner of a box with the given size where this anchor is at `point`
+    pub fn bottom_left_from(&self, point: PdfPosition, size: Size) -> PdfPosition {
+        let (x, y) = self.fractions();
+        PdfPosition {
+            x: point.x - size.width * x,
+            y: point.y - size.height * y,
+        }
+    }
+}
+/// An offset using a top left origin. Positive `x` moves right and positive `y` moves down
+///
+/// Percentages are relative to the area the item is placed in. They are resolved when the item is placed
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct AnchorOffset {
+    pub x: Length,
+    pub y: Length,
+}
+impl AnchorOffset {
+    pub fn new(x: impl Into<Length>, y: impl Into<Length>) -> Self {
+        Self {
+            x: x.into(),
+            y: y.into(),
+        }
+    }
+    /// Applies the offset to a position that uses the bottom left origin
+    ///
+    /// `reference` is the size percentages are relative to
+    pub fn apply(&self, position: PdfPosition, reference: Size) -> PdfPosition {
+        PdfPosition {
+            x: position.x + self.x.resolve(reference.width),
+            y: position.y - self.y.resolve(reference.height),
+        }
+    }
+}
+impl<X: Into<Length>, Y: Into<Length>> From<(X, Y)> for AnchorOffset {
+    fn from((x, y): (X, Y)) -> Self {
+        Self::new(x, y)
+    }
+}
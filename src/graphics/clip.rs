@@ -0,0 +1,331 @@
+use crate::{TuxPdfError, document::PdfResources, units::Pt};
+
+use super::{
+    LayerType, OperationWriter, PdfObject, PdfObjectType, PdfPosition, TextBlock,
+    TextRenderingMode,
+    primitives::{PaintMode, PathConstructionOperators, PathPaintOperationKeys, WindingOrder},
+    size::Size,
+};
+/// Magic number for approximating a quarter circle with a cubic bezier curve
+const BEZIER_CIRCLE_FACTOR: f32 = 0.552_284_8;
+
+/// The shape of a clipping path.
+///
+/// Anything drawn after the clip is applied will only be visible inside of the region.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClipShape {
+    /// A rectangle. Position is the lower left corner
+    Rect { position: PdfPosition, size: Size },
+    /// A rectangle with rounded corners. Position is the lower left corner
+    RoundedRect {
+        position: PdfPosition,
+        size: Size,
+        radius: Pt,
+    },
+    /// A closed polygon made of straight lines
+    Polygon(Vec<PdfPosition>),
+    /// The outlines of the glyphs. The text is only drawn if it uses a clip mode that also paints it
+    ///
+    /// See [TextRenderingMode]. The winding order is ignored
+    Text(Box<TextBlock>),
+}
+/// A clipping region that can be applied to a [GraphicsGroup](super::GraphicsGroup) or a [ClippedGroup]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClipRegion {
+    pub shape: ClipShape,
+    pub winding_order: WindingOrder,
+}
+impl From<ClipShape> for ClipRegion {
+    fn from(shape: ClipShape) -> Self {
+        Self {
+            shape,
+            winding_order: WindingOrder::default(),
+        }
+    }
+}
+impl ClipRegion {
+    /// Position is the lower left corner
+    pub fn rect(position: PdfPosition, size: Size) -> Self {
+        ClipShape::Rect { position, size }.into()
+    }
+    /// Position is the lower left corner
+    ///
+    /// The radius is limited to half of the smallest side
+    pub fn rounded_rect(position: PdfPosition, size: Size, radius: Pt) -> Self {
+        ClipShape::RoundedRect {
+            position,
+            size,
+            radius,
+        }
+        .into()
+    }
+    pub fn polygon<P>(points: impl IntoIterator<Item = P>) -> Self
+    where
+        P: Into<PdfPosition>,
+    {
+        ClipShape::Polygon(points.into_iter().map(Into::into).collect()).into()
+    }
+    /// Clips to the glyphs of the text
+    ///
+    /// Uses [TextRenderingMode::Clip] unless the style of the text already uses a clip mode
+    pub fn text(text: impl Into<TextBlock>) -> Self {
+        ClipShape::Text(Box::new(text.into())).into()
+    }
+    pub fn with_winding_order(mut self, winding_order: WindingOrder) -> Self {
+        self.winding_order = winding_order;
+        self
+    }
+    /// Writes the path followed by `W`/`W*` and `n`
+    ///
+    /// Should be called after saving the graphics state so the clip can be removed with a restore.
+    pub(crate) fn write_clip(
+        &self,
+        resources: &PdfResources,
+        writer: &mut OperationWriter,
+    ) -> Result<(), TuxPdfError> {
+        match &self.shape {
+            ClipShape::Rect { position, size } => {
+                writer.add_operation(
+                    PathConstructionOperators::PathRectangle,
+                    vec![
+                        position.x.into(),
+                        position.y.into(),
+                        size.width.into(),
+                        size.height.into(),
+                    ],
+                );
+            }
+            ClipShape::RoundedRect {
+                position,
+                size,
+                radius,
+            } => {
+                write_rounded_rect(writer, *position, *size, *radius);
+            }
+            ClipShape::Polygon(points) => {
+                let mut points = points.iter();
+                let Some(first) = points.next() else {
+                    // An empty path clips everything
+                    writer.add_operation(
+                        PathConstructionOperators::PathRectangle,
+                        vec![0f32.into(), 0f32.into(), 0f32.into(), 0f32.into()],
+                    );
+                    writer.push_empty_op(PaintMode::Clip.operation_key(self.winding_order));
+                    writer.push_empty_op(PathPaintOperationKeys::PathPaintEnd);
+                    return Ok(());
+                };
+                writer.add_operation(PathConstructionOperators::PathMoveTo, first.into());
+                for point in points {
+                    writer.add_operation(PathConstructionOperators::PathLineTo, point.into());
+                }
+            }
+            ClipShape::Text(text) => {
+                // Text is added to the clipping path when the text object ends
+                let mut text = text.as_ref().clone();
+                if !text.style.rendering_mode.is_some_and(|mode| mode.is_clip()) {
+                    text.style.rendering_mode = Some(TextRenderingMode::Clip);
+                }
+                return text.write(resources, writer);
+            }
+        }
+        writer.push_empty_op(PaintMode::Clip.operation_key(self.winding_order));
+        writer.push_empty_op(PathPaintOperationKeys::PathPaintEnd);
+        Ok(())
+    }
+}
+fn write_rounded_rect(writer: &mut OperationWriter, position: PdfPosition, size: Size, radius: Pt) {
+    let radius = radius
+        .max(Pt::default())
+        .min(size.width.min(size.height) / 2f32);
+    let control = radius * (1f32 - BEZIER_CIRCLE_FACTOR);
+    let PdfPosition { x, y } = position;
+    let right = x + size.width;
+    let top = y + size.height;
+
+    writer.add_operation(
+        PathConstructionOperators::PathMoveTo,
+        vec![(x + radius).into(), y.into()],
+    );
+    writer.add_operation(
+        PathConstructionOperators::PathLineTo,
+        vec![(right - radius).into(), y.into()],
+    );
+    writer.add_operation(
+        PathConstructionOperators::BezierCurveFour,
+        vec![
+            (right - control).into(),
+            y.into(),
+            right.into(),
+            (y + control).into(),
+            right.into(),
+            (y + radius).into(),
+        ],
+    );
+    writer.add_operation(
+        PathConstructionOperators::PathLineTo,
+        vec![right.into(), (top - radius).into()],
+    );
+    writer.add_operation(
+        PathConstructionOperators::BezierCurveFour,
+        vec![
+            right.into(),
+            (top - control).into(),
+            (right - control).into(),
+            top.into(),
+            (right - radius).into(),
+            top.into(),
+        ],
+    );
+    writer.add_operation(
+        PathConstructionOperators::PathLineTo,
+        vec![(x + radius).into(), top.into()],
+    );
+    writer.add_operation(
+        PathConstructionOperators::BezierCurveFour,
+        vec![
+            (x + control).into(),
+            top.into(),
+            x.into(),
+            (top - control).into(),
+            x.into(),
+            (top - radius).into(),
+        ],
+    );
+    writer.add_operation(
+        PathConstructionOperators::PathLineTo,
+        vec![x.into(), (y + radius).into()],
+    );
+    writer.add_operation(
+        PathConstructionOperators::BezierCurveFour,
+        vec![
+            x.into(),
+            (y + control).into(),
+            (x + control).into(),
+            y.into(),
+            (x + radius).into(),
+            y.into(),
+        ],
+    );
+}
+/// A group of any pdf objects that are clipped to a [ClipRegion]
+///
+/// Unlike [GraphicsGroup](super::GraphicsGroup) this can contain text and images.
+/// Useful for cropping images or hiding overflowing content.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClippedGroup {
+    pub clip: ClipRegion,
+    pub contents: Vec<PdfObject>,
+}
+impl ClippedGroup {
+    pub fn new(clip: impl Into<ClipRegion>) -> Self {
+        Self {
+            clip: clip.into(),
+            contents: Vec::new(),
+        }
+    }
+}
+impl LayerType for ClippedGroup {
+    fn add_to_layer(&mut self, object: impl Into<PdfObject>) -> Result<(), TuxPdfError> {
+        self.contents.push(object.into());
+        Ok(())
+    }
+}
+impl From<ClippedGroup> for PdfObject {
+    fn from(group: ClippedGroup) -> Self {
+        PdfObject::Clipped(group)
+    }
+}
+impl PdfObjectType for ClippedGroup {
+    fn write(
+        self,
+        resources: &PdfResources,
+        writer: &mut OperationWriter,
+    ) -> Result<(), TuxPdfError> {
+        writer.save_graphics_state();
+        self.clip.write_clip(resources, writer)?;
+        for object in self.contents {
+            object.write(resources, writer)?;
+        }
+        writer.restore_graphics_state();
+        Ok(())
+    }
+    fn calculate_number_of_pdf_objects(&self) -> usize {
+        self.contents
+            .iter()
+            .map(|object| object.calculate_number_of_pdf_objects())
+            .sum::<usize>()
+            + 4
+    }
+}
+#[cfg(test)]
+mod tests {
+    use tux_pdf_low::types::Object;
+
+    use super::ClipRegion;
+    use crate::{
+        document::{BuiltinFont, PdfResources},
+        graphics::{OperationWriter, PdfPosition, TextBlock, size::Size},
+        units::UnitType,
+    };
+
+    #[test]
+    fn rect_clip_operations() {
+        let mut writer = OperationWriter::default();
+        ClipRegion::rect(
+            PdfPosition::new(10f32.pt(), 10f32.pt()),
+            Size::new(100f32.pt(), 50f32.pt()),
+        )
+        .write_clip(&PdfResources::default(), &mut writer)
+        .unwrap();
+        let operators: Vec<_> = writer
+            .operations()
+            .into_iter()
+            .map(|op| op.operation)
+            .collect();
+        assert_eq!(operators, vec!["re", "W", "n"]);
+    }
+    #[test]
+    fn rounded_rect_clip_operations() {
+        let mut writer = OperationWriter::default();
+        ClipRegion::rounded_rect(
+            PdfPosition::new(0f32.pt(), 0f32.pt()),
+            Size::new(100f32.pt(), 50f32.pt()),
+            10f32.pt(),
+        )
+        .write_clip(&PdfResources::default(), &mut writer)
+        .unwrap();
+        let operations = writer.operations();
+        assert_eq!(operations.first().unwrap().operation, "m");
+        assert_eq!(
+            operations.iter().filter(|op| op.operation == "c").count(),
+            4
+        );
+        assert_eq!(operations[operations.len() - 2].operation, "W");
+    }
+    #[test]
+    fn text_clip_operations() -> anyhow::Result<()> {
+        let mut resources = PdfResources::default();
+        let text = TextBlock::from("Clip");
+        resources
+            .fonts
+            .register_builtin_font(BuiltinFont::Helvetica);
+        let mut writer = OperationWriter::default();
+        ClipRegion::text(text).write_clip(&resources, &mut writer)?;
+        let operations = writer.operations();
+        let mode = operations
+            .iter()
+            .find(|op| op.operation == "Tr")
+            .expect("Text rendering mode is set");
+        assert_eq!(mode.arguments, vec![Object::Integer(7)]);
+        // The clip is not undone by a restore and the clipping mode is reset for later text
+        let end = operations
+            .iter()
+            .position(|op| op.operation == "ET")
+            .expect("Text object is ended");
+        assert!(!operations[end..].iter().any(|op| op.operation == "Q"));
+        let reset = operations.last().unwrap();
+        assert_eq!(reset.operation, "Tr");
+        assert_eq!(reset.arguments, vec![Object::Integer(0)]);
+        Ok(())
+    }
+}
@@ -0,0 +1,259 @@
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::{document::Opacity, graphics::GraphicStyles};
+
+use super::{Color, Rgb};
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ColorParseError {
+    #[error("Hex colors must have 3, 4, 6 or 8 digits. Found {0}")]
+    InvalidHexLength(usize),
+    #[error("Invalid hex color: {0:?}")]
+    InvalidHexDigit(String),
+    #[error("Invalid color function: {0:?}")]
+    InvalidFunction(String),
+    #[error("Unknown color name: {0:?}")]
+    UnknownName(String),
+    #[error("{0:?} is transparent. Parse it as an AlphaColor to keep the alpha")]
+    HasAlpha(String),
+}
+/// A color with an alpha channel
+///
+/// PDF colors do not have an alpha. The alpha is applied as an [Opacity] through the graphics state.
+/// Register the opacity with [PdfDocument::register_opacity](crate::document::PdfDocument::register_opacity)
+///
+/// ```rust
+/// use tux_pdf::{document::PdfDocument, graphics::color::AlphaColor};
+/// let mut document = PdfDocument::new("Highlights");
+/// let highlight: AlphaColor = "#ffeb3b80".parse().unwrap();
+/// document.register_opacity(highlight.opacity);
+/// let styles = highlight.fill_styles();
+/// assert_eq!(styles.opacity, Some(highlight.opacity));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlphaColor {
+    pub color: Color,
+    pub opacity: Opacity,
+}
+impl AlphaColor {
+    /// Parses `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa`. The `#` is optional
+    pub fn from_hex(hex: &str) -> Result<Self, ColorParseError> {
+        let digits = hex.trim().trim_start_matches('#');
+        let invalid = || ColorParseError::InvalidHexDigit(hex.to_owned());
+        if !digits.is_ascii() {
+            return Err(invalid());
+        }
+        let channels: Vec<u8> = match digits.len() {
+            3 | 4 => digits
+                .chars()
+                .map(|digit| digit.to_digit(16).map(|value| value as u8 * 17))
+                .collect::<Option<_>>()
+                .ok_or_else(invalid)?,
+            6 | 8 => (0..digits.len())
+                .step_by(2)
+                .map(|index| u8::from_str_radix(&digits[index..index + 2], 16).ok())
+                .collect::<Option<_>>()
+                .ok_or_else(invalid)?,
+            other => return Err(ColorParseError::InvalidHexLength(other)),
+        };
+        Ok(Self {
+            color: Color::from_rgb_u8(channels[0], channels[1], channels[2]),
+            opacity: Opacity(channels.get(3).copied().unwrap_or(u8::MAX)),
+        })
+    }
+    /// Parses a hex color, `rgb(...)`, `rgba(...)` or a basic CSS color name
+    ///
+    /// Both the comma separated and space separated forms of `rgb()` are supported.
+    /// Channels can be numbers between `0` and `255` or percentages
+    pub fn from_css(value: &str) -> Result<Self, ColorParseError> {
+        let value = value.trim();
+        if value.starts_with('#') {
+            return Self::from_hex(value);
+        }
+        let lowercase = value.to_ascii_lowercase();
+        if let Some(arguments) = lowercase
+            .strip_prefix("rgba(")
+            .or_else(|| lowercase.strip_prefix("rgb("))
+        {
+            return parse_rgb_function(arguments)
+                .ok_or_else(|| ColorParseError::InvalidFunction(value.to_owned()));
+        }
+        named_color(&lowercase).ok_or_else(|| ColorParseError::UnknownName(value.to_owned()))
+    }
+    pub fn is_opaque(&self) -> bool {
+        self.opacity.is_opaque()
+    }
+    /// Styles that fill with the color. The opacity is only set if the color is transparent
+    pub fn fill_styles(&self) -> GraphicStyles {
+        GraphicStyles {
+            fill_color: Some(self.color.clone()),
+            opacity: (!self.is_opaque()).then_some(self.opacity),
+            ..Default::default()
+        }
+    }
+    /// Styles that outline with the color. The opacity is only set if the color is transparent
+    pub fn outline_styles(&self) -> GraphicStyles {
+        GraphicStyles {
+            outline_color: Some(self.color.clone()),
+            opacity: (!self.is_opaque()).then_some(self.opacity),
+            ..Default::default()
+        }
+    }
+}
+impl From<Color> for AlphaColor {
+    fn from(color: Color) -> Self {
+        Self {
+            color,
+            opacity: Opacity::OPAQUE,
+        }
+    }
+}
+impl FromStr for AlphaColor {
+    type Err = ColorParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::from_css(value)
+    }
+}
+impl Color {
+    /// Parses `#rgb` or `#rrggbb`. The `#` is optional
+    ///
+    /// Hex colors with an alpha channel are only accepted if they are opaque. Use [AlphaColor::from_hex] for transparent colors
+    pub fn from_hex(hex: &str) -> Result<Self, ColorParseError> {
+        AlphaColor::from_hex(hex)?.into_opaque(hex)
+    }
+    /// Parses a hex color, `rgb(...)` or a basic CSS color name. See [AlphaColor::from_css]
+    pub fn from_css(value: &str) -> Result<Self, ColorParseError> {
+        AlphaColor::from_css(value)?.into_opaque(value)
+    }
+}
+impl FromStr for Color {
+    type Err = ColorParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::from_css(value)
+    }
+}
+impl AlphaColor {
+    fn into_opaque(self, source: &str) -> Result<Color, ColorParseError> {
+        if !self.is_opaque() {
+            return Err(ColorParseError::HasAlpha(source.to_owned()));
+        }
+        Ok(self.color)
+    }
+}
+/// Parses the arguments of `rgb(` up to and including the closing parenthesis
+fn parse_rgb_function(arguments: &str) -> Option<AlphaColor> {
+    let arguments = arguments.strip_suffix(')')?;
+    let parts: Vec<&str> = arguments
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .collect();
+    if parts.len() != 3 && parts.len() != 4 {
+        return None;
+    }
+    let channel = |part: &str| -> Option<f32> {
+        match part.strip_suffix('%') {
+            Some(percent) => percent.parse::<f32>().ok().map(|value| value / 100.0),
+            None => part.parse::<f32>().ok().map(|value| value / 255.0),
+        }
+    };
+    let opacity = match parts.get(3) {
+        Some(alpha) => {
+            let alpha = match alpha.strip_suffix('%') {
+                Some(percent) => percent.parse::<f32>().ok()? / 100.0,
+                None => alpha.parse::<f32>().ok()?,
+            };
+            Opacity::from_f32(alpha)
+        }
+        None => Opacity::OPAQUE,
+    };
+    let rgb = Rgb::new_no_profile(
+        channel(parts[0])?.clamp(0.0, 1.0),
+        channel(parts[1])?.clamp(0.0, 1.0),
+        channel(parts[2])?.clamp(0.0, 1.0),
+    );
+    Some(AlphaColor {
+        color: rgb.into(),
+        opacity,
+    })
+}
+/// The basic CSS color keywords
+fn named_color(name: &str) -> Option<AlphaColor> {
+    let (r, g, b) = match name {
+        "transparent" => {
+            return Some(AlphaColor {
+                color: Color::from_rgb_u8(0, 0, 0),
+                opacity: Opacity(0),
+            });
+        }
+        "black" => (0, 0, 0),
+        "silver" => (192, 192, 192),
+        "gray" | "grey" => (128, 128, 128),
+        "white" => (255, 255, 255),
+        "maroon" => (128, 0, 0),
+        "red" => (255, 0, 0),
+        "purple" => (128, 0, 128),
+        "fuchsia" | "magenta" => (255, 0, 255),
+        "green" => (0, 128, 0),
+        "lime" => (0, 255, 0),
+        "olive" => (128, 128, 0),
+        "yellow" => (255, 255, 0),
+        "navy" => (0, 0, 128),
+        "blue" => (0, 0, 255),
+        "teal" => (0, 128, 128),
+        "aqua" | "cyan" => (0, 255, 255),
+        "orange" => (255, 165, 0),
+        _ => return None,
+    };
+    Some(Color::from_rgb_u8(r, g, b).into())
+}
+#[cfg(test)]
+mod tests {
+    use super::{AlphaColor, ColorParseError};
+    use crate::{document::Opacity, graphics::color::Color};
+
+    #[test]
+    fn parse_hex() -> anyhow::Result<()> {
+        assert_eq!(
+            Color::from_hex("#1a73e8")?,
+            Color::from_rgb_u8(0x1a, 0x73, 0xe8)
+        );
+        assert_eq!(Color::from_hex("fff")?, Color::from_rgb_u8(255, 255, 255));
+
+        let transparent = AlphaColor::from_hex("#1a73e880")?;
+        assert_eq!(transparent.opacity, Opacity(0x80));
+        assert_eq!(transparent.color, Color::from_rgb_u8(0x1a, 0x73, 0xe8));
+
+        assert_eq!(
+            Color::from_hex("#1a73e880"),
+            Err(ColorParseError::HasAlpha("#1a73e880".to_owned()))
+        );
+        assert_eq!(
+            Color::from_hex("#1a73e"),
+            Err(ColorParseError::InvalidHexLength(5))
+        );
+        assert!(Color::from_hex("#zzzzzz").is_err());
+        Ok(())
+    }
+    #[test]
+    fn parse_css() -> anyhow::Result<()> {
+        assert_eq!(
+            "rgb(26, 115, 232)".parse::<Color>()?,
+            Color::from_rgb_u8(26, 115, 232)
+        );
+        let color: AlphaColor = "rgb(100% 0% 0% / 50%)".parse()?;
+        assert_eq!(color.color, Color::from_rgb_u8(255, 0, 0));
+        assert_eq!(color.opacity, Opacity(128));
+        assert_eq!(
+            "rgba(0, 0, 0, 0.25)".parse::<AlphaColor>()?.opacity,
+            Opacity::from_f32(0.25)
+        );
+        assert_eq!("Navy".parse::<Color>()?, Color::from_rgb_u8(0, 0, 128));
+        assert!("rgb(1, 2)".parse::<Color>().is_err());
+        assert!("not-a-color".parse::<Color>().is_err());
+        Ok(())
+    }
+}
@@ -38,6 +38,8 @@ impl TryFrom<ColorType> for ColorSpace {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(i64)]
 pub enum ColorBits {
+    /// Black and white. Used by CCITT and JBIG2 images
+    Bit1 = 1,
     /// 8-bit color
     Bit8 = 8,
     /// 16-bit color
@@ -63,6 +65,7 @@ impl TryFrom<ColorType> for ColorBits {
 impl From<ColorBits> for i64 {
     fn from(val: ColorBits) -> Self {
         match val {
+            ColorBits::Bit1 => 1,
             ColorBits::Bit8 => 8,
             ColorBits::Bit16 => 16,
         }
@@ -1,12 +1,16 @@
 use std::borrow::Cow;
 
 use derive_more::derive::From;
+mod css;
 mod image;
+mod spaces;
+pub use css::*;
 pub use image::*;
+pub use spaces::*;
 use tracing::error;
 use tux_pdf_low::types::Object;
 
-use crate::document::IccProfileId;
+use crate::document::{IccProfileId, Opacity};
 
 use super::{PdfObjectType, operation_keys};
 macro_rules! default_rgb_colors {
@@ -81,6 +85,12 @@ pub enum Color {
     Cmyk(Cmyk),
     Greyscale(Greyscale),
     SpotColor(SpotColor),
+    /// Written as DeviceRGB
+    Hsl(Hsl),
+    /// Written as DeviceRGB
+    Hsv(Hsv),
+    /// Written as DeviceRGB
+    Lab(Lab),
 }
 impl Color {
     pub fn has_color_profile(&self) -> bool {
@@ -88,7 +98,7 @@ impl Color {
             Color::Rgb(rgb) => rgb.icc_profile.is_some(),
             Color::Cmyk(cmyk) => cmyk.icc_profile.is_some(),
             Color::Greyscale(greyscale) => greyscale.icc_profile.is_some(),
-            Color::SpotColor(_) => false,
+            Color::SpotColor(_) | Color::Hsl(_) | Color::Hsv(_) | Color::Lab(_) => false,
         }
     }
     pub fn get_color_profile(&self) -> Option<&IccProfileId> {
@@ -96,7 +106,70 @@ impl Color {
             Color::Rgb(rgb) => rgb.icc_profile.as_ref(),
             Color::Cmyk(cmyk) => cmyk.icc_profile.as_ref(),
             Color::Greyscale(greyscale) => greyscale.icc_profile.as_ref(),
-            Color::SpotColor(_) => None,
+            Color::SpotColor(_) | Color::Hsl(_) | Color::Hsv(_) | Color::Lab(_) => None,
+        }
+    }
+    /// An RGB color from channels between `0` and `255`
+    pub fn from_rgb_u8(r: u8, g: u8, b: u8) -> Self {
+        Color::Rgb(Rgb::new_no_profile(
+            f32::from(r) / 255.0,
+            f32::from(g) / 255.0,
+            f32::from(b) / 255.0,
+        ))
+    }
+    /// Mixes the color with white. `0` keeps the color and `1` is white
+    ///
+    /// HSL, HSV and Lab colors are converted to RGB first
+    pub fn lighten(&self, amount: f32) -> Self {
+        let amount = amount.clamp(0.0, 1.0);
+        let towards_white = |value: f32| value + (1.0 - value) * amount;
+        let towards_zero = |value: f32| value * (1.0 - amount);
+        let mut color = self.clone();
+        match &mut color {
+            Color::Rgb(rgb) => {
+                rgb.r = towards_white(rgb.r);
+                rgb.g = towards_white(rgb.g);
+                rgb.b = towards_white(rgb.b);
+            }
+            Color::Greyscale(greyscale) => greyscale.percent = towards_white(greyscale.percent),
+            Color::Cmyk(Cmyk { c, m, y, k, .. }) | Color::SpotColor(SpotColor { c, m, y, k }) => {
+                for ink in [c, m, y, k] {
+                    *ink = towards_zero(*ink);
+                }
+            }
+            other @ (Color::Hsl(_) | Color::Hsv(_) | Color::Lab(_)) => {
+                *other = Color::Rgb(other.to_rgb()).lighten(amount);
+            }
+        }
+        color
+    }
+    /// Mixes the color with black. `0` keeps the color and `1` is black
+    ///
+    /// HSL, HSV and Lab colors are converted to RGB first
+    pub fn darken(&self, amount: f32) -> Self {
+        let amount = amount.clamp(0.0, 1.0);
+        let mut color = self.clone();
+        match &mut color {
+            Color::Rgb(rgb) => {
+                rgb.r *= 1.0 - amount;
+                rgb.g *= 1.0 - amount;
+                rgb.b *= 1.0 - amount;
+            }
+            Color::Greyscale(greyscale) => greyscale.percent *= 1.0 - amount,
+            Color::Cmyk(Cmyk { k, .. }) | Color::SpotColor(SpotColor { k, .. }) => {
+                *k += (1.0 - *k) * amount;
+            }
+            other @ (Color::Hsl(_) | Color::Hsv(_) | Color::Lab(_)) => {
+                *other = Color::Rgb(other.to_rgb()).darken(amount);
+            }
+        }
+        color
+    }
+    /// The color with an alpha between `0` and `1`
+    pub fn with_alpha(self, alpha: f32) -> AlphaColor {
+        AlphaColor {
+            color: self,
+            opacity: Opacity::from_f32(alpha),
         }
     }
 }
@@ -174,6 +247,12 @@ impl PdfObjectType for ColorWriter<'_> {
                 Color::Cmyk(cmyk) => {
                     writer.add_operation(ColorOperations::StrokingCMYK, cmyk.into());
                 }
+                Color::Hsl(_) | Color::Hsv(_) | Color::Lab(_) => {
+                    writer.add_operation(
+                        ColorOperations::StrokeDeviceRgb,
+                        outline_color.to_rgb().into(),
+                    );
+                }
                 _ => todo!("Implement SpotColor"),
             }
         }
@@ -191,6 +270,12 @@ impl PdfObjectType for ColorWriter<'_> {
                 Color::Cmyk(cmyk) => {
                     writer.add_operation(ColorOperations::NonStrokingCMYK, cmyk.into());
                 }
+                Color::Hsl(_) | Color::Hsv(_) | Color::Lab(_) => {
+                    writer.add_operation(
+                        ColorOperations::NonStrokingDeviceRgb,
+                        fill_color.to_rgb().into(),
+                    );
+                }
                 _ => todo!("Implement SpotColor"),
             }
         }
@@ -277,3 +362,28 @@ pub struct SpotColor {
     pub y: f32,
     pub k: f32,
 }
+#[cfg(test)]
+mod tests {
+    use super::{Color, Greyscale};
+    use crate::document::Opacity;
+
+    #[test]
+    fn lighten_and_darken() {
+        let color = Color::from_rgb_u8(0, 128, 255);
+        let Color::Rgb(lighter) = color.lighten(0.5) else {
+            panic!("Lightening changed the color space");
+        };
+        assert_eq!((lighter.r, lighter.b), (0.5, 1.0));
+        let Color::Rgb(darker) = color.darken(0.5) else {
+            panic!("Darkening changed the color space");
+        };
+        assert_eq!((darker.r, darker.b), (0.0, 0.5));
+
+        let grey = Color::Greyscale(Greyscale::new(0.5, None));
+        assert_eq!(
+            grey.darken(1.0),
+            Color::Greyscale(Greyscale::new(0.0, None))
+        );
+        assert_eq!(color.with_alpha(0.5).opacity, Opacity(128));
+    }
+}
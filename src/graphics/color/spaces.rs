@@ -0,0 +1,414 @@
+use super::{Cmyk, Color, Greyscale, Rgb, SpotColor};
+/// D65 reference white used to convert [Lab] colors
+const D65_WHITE: (f32, f32, f32) = (0.95047, 1.0, 1.08883);
+
+/// Hue, saturation and lightness
+///
+/// PDF has no HSL color space. The color is converted to DeviceRGB when it is written
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsl {
+    /// Hue in degrees. Wraps around at `360`
+    pub h: f32,
+    /// Saturation between `0` and `1`
+    pub s: f32,
+    /// Lightness between `0` and `1`. `0.5` is the most colorful
+    pub l: f32,
+}
+impl Hsl {
+    pub fn new(h: f32, s: f32, l: f32) -> Self {
+        Self { h, s, l }
+    }
+}
+impl From<Hsl> for Rgb {
+    fn from(Hsl { h, s, l }: Hsl) -> Self {
+        let (s, l) = (s.clamp(0.0, 1.0), l.clamp(0.0, 1.0));
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        hue_to_rgb(h, chroma, l - chroma / 2.0)
+    }
+}
+/// Hue, saturation and value
+///
+/// PDF has no HSV color space. The color is converted to DeviceRGB when it is written
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsv {
+    /// Hue in degrees. Wraps around at `360`
+    pub h: f32,
+    /// Saturation between `0` and `1`
+    pub s: f32,
+    /// Value between `0` and `1`. `1` is the brightest
+    pub v: f32,
+}
+impl Hsv {
+    pub fn new(h: f32, s: f32, v: f32) -> Self {
+        Self { h, s, v }
+    }
+}
+impl From<Hsv> for Rgb {
+    fn from(Hsv { h, s, v }: Hsv) -> Self {
+        let (s, v) = (s.clamp(0.0, 1.0), v.clamp(0.0, 1.0));
+        let chroma = v * s;
+        hue_to_rgb(h, chroma, v - chroma)
+    }
+}
+/// Places the chroma on the RGB channels based on the hue and adds the minimum
+fn hue_to_rgb(hue: f32, chroma: f32, min: f32) -> Rgb {
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u8 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    Rgb::new_no_profile(r + min, g + min, b + min)
+}
+/// CIE L\*a\*b\* with a D65 white point
+///
+/// Steps of the same size look like the same change in color. Good for ramps that should look even.
+/// Converted to sRGB when it is written. Colors outside of sRGB are clamped
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lab {
+    /// Lightness between `0` and `100`
+    pub l: f32,
+    /// Green (negative) to red (positive)
+    pub a: f32,
+    /// Blue (negative) to yellow (positive)
+    pub b: f32,
+}
+impl Lab {
+    pub fn new(l: f32, a: f32, b: f32) -> Self {
+        Self { l, a, b }
+    }
+}
+impl From<Lab> for Rgb {
+    fn from(Lab { l, a, b }: Lab) -> Self {
+        let inverse = |t: f32| {
+            const DELTA: f32 = 6.0 / 29.0;
+            if t > DELTA {
+                t.powi(3)
+            } else {
+                3.0 * DELTA * DELTA * (t - 4.0 / 29.0)
+            }
+        };
+        let fy = (l + 16.0) / 116.0;
+        let x = D65_WHITE.0 * inverse(fy + a / 500.0);
+        let y = D65_WHITE.1 * inverse(fy);
+        let z = D65_WHITE.2 * inverse(fy - b / 200.0);
+
+        let gamma = |linear: f32| {
+            let linear = linear.clamp(0.0, 1.0);
+            if linear <= 0.003_130_8 {
+                12.92 * linear
+            } else {
+                1.055 * linear.powf(1.0 / 2.4) - 0.055
+            }
+        };
+        Rgb::new_no_profile(
+            gamma(3.240_454_2 * x - 1.537_138_5 * y - 0.498_531_4 * z),
+            gamma(-0.969_266 * x + 1.876_010_8 * y + 0.041_556 * z),
+            gamma(0.055_643_4 * x - 0.204_025_9 * y + 1.057_225_2 * z),
+        )
+    }
+}
+impl Color {
+    /// See [Hsl]
+    pub fn hsl(h: f32, s: f32, l: f32) -> Self {
+        Hsl::new(h, s, l).into()
+    }
+    /// See [Hsv]
+    pub fn hsv(h: f32, s: f32, v: f32) -> Self {
+        Hsv::new(h, s, v).into()
+    }
+    /// See [Lab]
+    pub fn lab(l: f32, a: f32, b: f32) -> Self {
+        Lab::new(l, a, b).into()
+    }
+    /// Converts the color to DeviceRGB. The color profile is kept for RGB colors
+    ///
+    /// CMYK is converted without a color profile so the result is an approximation
+    pub fn to_rgb(&self) -> Rgb {
+        let from_cmyk = |c: f32, m: f32, y: f32, k: f32| {
+            Rgb::new_no_profile(
+                (1.0 - c) * (1.0 - k),
+                (1.0 - m) * (1.0 - k),
+                (1.0 - y) * (1.0 - k),
+            )
+        };
+        match self {
+            Color::Rgb(rgb) => rgb.clone(),
+            Color::Hsl(hsl) => (*hsl).into(),
+            Color::Hsv(hsv) => (*hsv).into(),
+            Color::Lab(lab) => (*lab).into(),
+            Color::Greyscale(greyscale) => {
+                Rgb::new_no_profile(greyscale.percent, greyscale.percent, greyscale.percent)
+            }
+            Color::Cmyk(Cmyk { c, m, y, k, .. }) | Color::SpotColor(SpotColor { c, m, y, k }) => {
+                from_cmyk(*c, *m, *y, *k)
+            }
+        }
+    }
+    /// Converts the color to DeviceCMYK. The color profile is kept for CMYK colors
+    ///
+    /// RGB is converted without a color profile so the result is an approximation
+    pub fn to_cmyk(&self) -> Cmyk {
+        let (r, g, b) = match self {
+            Color::Cmyk(cmyk) => return cmyk.clone(),
+            Color::SpotColor(SpotColor { c, m, y, k }) => {
+                return Cmyk {
+                    c: *c,
+                    m: *m,
+                    y: *y,
+                    k: *k,
+                    icc_profile: None,
+                };
+            }
+            Color::Greyscale(greyscale) => {
+                (greyscale.percent, greyscale.percent, greyscale.percent)
+            }
+            color => {
+                let rgb = color.to_rgb();
+                (rgb.r, rgb.g, rgb.b)
+            }
+        };
+        let k = 1.0 - r.max(g).max(b);
+        let ink = |channel: f32| {
+            if k >= 1.0 {
+                0.0
+            } else {
+                (1.0 - channel - k) / (1.0 - k)
+            }
+        };
+        Cmyk {
+            c: ink(r),
+            m: ink(g),
+            y: ink(b),
+            k,
+            icc_profile: None,
+        }
+    }
+}
+/// Interpolates between colors. Used to color cells or chart elements based on a value
+///
+/// Colors in the same color space are interpolated in that space. Otherwise both colors are converted to RGB
+///
+/// ```rust
+/// use tux_pdf::{
+///     graphics::color::{Color, ColorRamp},
+///     layouts::table::TableValueWithStyle,
+/// };
+/// let ramp = ColorRamp::new(Color::from_rgb_u8(255, 255, 255), Color::hsl(0.0, 0.8, 0.5));
+/// let cells: Vec<TableValueWithStyle> = [12.0, 50.0, 98.0]
+///     .into_iter()
+///     .map(|value| {
+///         TableValueWithStyle::from(format!("{value}"))
+///             .with_fill_color(ramp.color_for_value(value, 0.0, 100.0))
+///     })
+///     .collect();
+/// assert_eq!(cells.len(), 3);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorRamp {
+    /// Positions between `0` and `1` and their colors. Sorted by position
+    stops: Vec<(f32, Color)>,
+}
+impl ColorRamp {
+    pub fn new(start: impl Into<Color>, end: impl Into<Color>) -> Self {
+        Self {
+            stops: vec![(0.0, start.into()), (1.0, end.into())],
+        }
+    }
+    /// Evenly spaced colors. An empty list creates a ramp from black to white
+    pub fn from_colors(colors: impl IntoIterator<Item = Color>) -> Self {
+        let colors: Vec<Color> = colors.into_iter().collect();
+        match colors.len() {
+            0 => Self::new(Greyscale::new(0.0, None), Greyscale::new(1.0, None)),
+            1 => Self::new(colors[0].clone(), colors[0].clone()),
+            length => Self {
+                stops: colors
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, color)| (index as f32 / (length - 1) as f32, color))
+                    .collect(),
+            },
+        }
+    }
+    /// Adds a color at a position between `0` and `1`
+    pub fn with_stop(mut self, position: f32, color: impl Into<Color>) -> Self {
+        let position = position.clamp(0.0, 1.0);
+        let index = self
+            .stops
+            .partition_point(|(stop_position, _)| *stop_position <= position);
+        self.stops.insert(index, (position, color.into()));
+        self
+    }
+    pub fn stops(&self) -> &[(f32, Color)] {
+        &self.stops
+    }
+    /// The color at a position between `0` and `1`. Positions outside of the range are clamped
+    pub fn color_at(&self, position: f32) -> Color {
+        let position = if position.is_nan() {
+            0.0
+        } else {
+            position.clamp(0.0, 1.0)
+        };
+        let end = self
+            .stops
+            .iter()
+            .position(|(stop_position, _)| *stop_position >= position)
+            .unwrap_or(self.stops.len() - 1);
+        if end == 0 {
+            return self.stops[0].1.clone();
+        }
+        let (start_position, start) = &self.stops[end - 1];
+        let (end_position, end) = &self.stops[end];
+        let span = end_position - start_position;
+        if span <= f32::EPSILON {
+            return end.clone();
+        }
+        interpolate(start, end, (position - start_position) / span)
+    }
+    /// The color for a value between `min` and `max`
+    pub fn color_for_value(&self, value: f32, min: f32, max: f32) -> Color {
+        if max - min <= f32::EPSILON {
+            return self.color_at(0.0);
+        }
+        self.color_at((value - min) / (max - min))
+    }
+}
+fn interpolate(start: &Color, end: &Color, t: f32) -> Color {
+    let mix = |a: f32, b: f32| a + (b - a) * t;
+    match (start, end) {
+        (Color::Greyscale(start), Color::Greyscale(end)) => Color::Greyscale(Greyscale::new(
+            mix(start.percent, end.percent),
+            start.icc_profile.clone(),
+        )),
+        (Color::Cmyk(start), Color::Cmyk(end)) => Color::Cmyk(Cmyk {
+            c: mix(start.c, end.c),
+            m: mix(start.m, end.m),
+            y: mix(start.y, end.y),
+            k: mix(start.k, end.k),
+            icc_profile: start.icc_profile.clone(),
+        }),
+        (Color::Hsl(start), Color::Hsl(end)) => Color::Hsl(Hsl {
+            h: mix_hue(start.h, end.h, t),
+            s: mix(start.s, end.s),
+            l: mix(start.l, end.l),
+        }),
+        (Color::Hsv(start), Color::Hsv(end)) => Color::Hsv(Hsv {
+            h: mix_hue(start.h, end.h, t),
+            s: mix(start.s, end.s),
+            v: mix(start.v, end.v),
+        }),
+        (Color::Lab(start), Color::Lab(end)) => Color::Lab(Lab {
+            l: mix(start.l, end.l),
+            a: mix(start.a, end.a),
+            b: mix(start.b, end.b),
+        }),
+        (start, end) => {
+            let (start, end) = (start.to_rgb(), end.to_rgb());
+            Color::Rgb(Rgb {
+                r: mix(start.r, end.r),
+                g: mix(start.g, end.g),
+                b: mix(start.b, end.b),
+                icc_profile: start.icc_profile,
+            })
+        }
+    }
+}
+/// Moves around the color wheel the shorter way. Red to blue goes through magenta not green
+fn mix_hue(start: f32, end: f32, t: f32) -> f32 {
+    let difference = (end - start + 540.0).rem_euclid(360.0) - 180.0;
+    (start + difference * t).rem_euclid(360.0)
+}
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use tux_pdf_low::types::Object;
+
+    use super::{ColorRamp, Hsl};
+    use crate::{
+        document::PdfDocument,
+        graphics::{
+            OperationWriter, PdfObjectType,
+            color::{BLACK_RGB, Color, ColorWriter, GREEN_RGB, RED_RGB, Rgb, WHITE_RGB},
+        },
+    };
+
+    fn assert_rgb(color: Color, expected: (f32, f32, f32)) {
+        let rgb = color.to_rgb();
+        for (actual, expected) in [
+            (rgb.r, expected.0),
+            (rgb.g, expected.1),
+            (rgb.b, expected.2),
+        ] {
+            assert!(
+                (actual - expected).abs() < 0.01,
+                "Expected {expected:?} found {rgb:?}"
+            );
+        }
+    }
+    #[test]
+    fn color_spaces() {
+        assert_rgb(Color::hsl(0.0, 1.0, 0.5), (1.0, 0.0, 0.0));
+        assert_rgb(Color::hsl(240.0, 1.0, 0.25), (0.0, 0.0, 0.5));
+        assert_rgb(Color::hsv(120.0, 1.0, 1.0), (0.0, 1.0, 0.0));
+        assert_rgb(Color::hsv(-60.0, 1.0, 1.0), (1.0, 0.0, 1.0));
+        assert_rgb(Color::lab(100.0, 0.0, 0.0), (1.0, 1.0, 1.0));
+        // sRGB red
+        assert_rgb(Color::lab(53.24, 80.09, 67.2), (1.0, 0.0, 0.0));
+
+        // Kept until the color is written
+        assert_eq!(
+            Color::hsl(0.0, 1.0, 0.5),
+            Color::Hsl(Hsl::new(0.0, 1.0, 0.5))
+        );
+
+        let cmyk = RED_RGB.to_cmyk();
+        assert_eq!((cmyk.c, cmyk.m, cmyk.y, cmyk.k), (0.0, 1.0, 1.0, 0.0));
+        assert_rgb(Color::Cmyk(cmyk), (1.0, 0.0, 0.0));
+    }
+    #[test]
+    fn ramp() {
+        let ramp = ColorRamp::new(BLACK_RGB, WHITE_RGB);
+        assert_rgb(ramp.color_at(0.5), (0.5, 0.5, 0.5));
+        assert_rgb(ramp.color_for_value(150.0, 0.0, 100.0), (1.0, 1.0, 1.0));
+        assert_rgb(ramp.color_for_value(5.0, 5.0, 5.0), (0.0, 0.0, 0.0));
+
+        let ramp = ColorRamp::from_colors([
+            RED_RGB,
+            Rgb::new_no_profile(1.0, 1.0, 0.0).into(),
+            GREEN_RGB,
+        ]);
+        assert_rgb(ramp.color_at(0.5), (1.0, 1.0, 0.0));
+        assert_rgb(ramp.color_at(0.75), (0.5, 1.0, 0.0));
+
+        let ramp = ColorRamp::new(BLACK_RGB, WHITE_RGB).with_stop(0.5, RED_RGB);
+        assert_eq!(ramp.stops().len(), 3);
+        assert_rgb(ramp.color_at(0.25), (0.5, 0.0, 0.0));
+
+        // Hues are interpolated the short way around. Red to blue passes magenta
+        let ramp = ColorRamp::new(Color::hsl(0.0, 1.0, 0.5), Color::hsl(240.0, 1.0, 0.5));
+        assert_eq!(ramp.color_at(0.5), Color::hsl(300.0, 1.0, 0.5));
+    }
+    #[test]
+    fn written_as_rgb() -> anyhow::Result<()> {
+        let document = PdfDocument::new("Colors");
+        let mut writer = OperationWriter::default();
+        ColorWriter {
+            outline_color: None,
+            fill_color: Some(Cow::Owned(Color::hsv(120.0, 1.0, 1.0))),
+        }
+        .write(&document.resources, &mut writer)?;
+        let operations = writer.operations();
+        assert_eq!(operations.len(), 1);
+        assert_eq!(operations[0].operation, "rg");
+        assert_eq!(
+            operations[0].arguments,
+            vec![Object::Real(0.0), Object::Real(1.0), Object::Real(0.0)]
+        );
+        Ok(())
+    }
+}
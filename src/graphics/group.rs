@@ -3,9 +3,11 @@ use derive_more::derive::From;
 use crate::{TuxPdfError, document::PdfResources};
 
 use super::{
-    GraphicStyles, OperationKeys, OperationWriter, PdfObject, PdfObjectType,
-    primitives::{Line, StraightLine},
+    ClipRegion, GraphicStyles, OperationKeys, OperationWriter, PdfObject, PdfObjectType,
+    PdfPosition,
+    primitives::{Line, LinePoint, Path, PathSegment, StraightLine, ctm::CurTransMat},
     shapes::{OutlineRect, PaintedRect},
+    size::Size,
 };
 
 /// By default every graphic item you add to be rendered will end with a call to restore the graphics state.
@@ -17,6 +19,12 @@ pub struct GraphicsGroup {
     pub styles: Option<GraphicStyles>,
     pub items: Vec<GraphicItems>,
     pub section_name: Option<String>,
+    /// Clips every item in the group to this region
+    pub clip: Option<ClipRegion>,
+    /// Moves, rotates or scales every item in the group. Applied before the clip
+    ///
+    /// Empty means no transformation
+    pub transform: Vec<CurTransMat>,
 }
 impl<Item, Iter> From<Iter> for GraphicsGroup
 where
@@ -28,6 +36,8 @@ where
             styles: None,
             items: group.map(|item| item.into()).collect(),
             section_name: None,
+            clip: None,
+            transform: Vec::new(),
         }
     }
 }
@@ -50,6 +60,24 @@ impl GraphicsGroup {
         self.styles = Some(styles);
         self
     }
+    pub fn with_clip(mut self, clip: impl Into<ClipRegion>) -> Self {
+        self.clip = Some(clip.into());
+        self
+    }
+    pub fn with_transform(mut self, transform: impl Into<Vec<CurTransMat>>) -> Self {
+        self.transform = transform.into();
+        self
+    }
+    /// The smallest rectangle containing every item in the group
+    ///
+    /// Does not include the line width or the transform of the group. Bezier curves use their control points
+    pub fn bounds(&self) -> Option<OutlineRect> {
+        let mut points = Vec::new();
+        for item in &self.items {
+            item.collect_points(&mut points);
+        }
+        bounds_of_points(&points)
+    }
     pub fn add_item<I>(&mut self, item: I)
     where
         I: Into<GraphicItems>,
@@ -57,6 +85,21 @@ impl GraphicsGroup {
         self.items.push(item.into());
     }
 }
+/// The smallest rectangle containing every point
+fn bounds_of_points(points: &[PdfPosition]) -> Option<OutlineRect> {
+    let (first, rest) = points.split_first()?;
+    let (mut min, mut max) = (*first, *first);
+    for point in rest {
+        min.x = min.x.min(point.x);
+        min.y = min.y.min(point.y);
+        max.x = max.x.max(point.x);
+        max.y = max.y.max(point.y);
+    }
+    Some(OutlineRect {
+        position: min,
+        size: Size::new(max.x - min.x, max.y - min.y),
+    })
+}
 impl PdfObjectType for GraphicsGroup {
     fn write(
         self,
@@ -70,6 +113,12 @@ impl PdfObjectType for GraphicsGroup {
             false
         };
         writer.add_operation(OperationKeys::SaveGraphicsState, vec![]);
+        if !self.transform.is_empty() {
+            self.transform.write(resources, writer)?;
+        }
+        if let Some(clip) = &self.clip {
+            clip.write_clip(resources, writer)?;
+        }
         if let Some(styles) = self.styles {
             styles.write(resources, writer)?;
         }
@@ -93,6 +142,7 @@ pub enum GraphicItems {
     Rectangle(PaintedRect),
     OutlineRectangle(OutlineRect),
     Group(GraphicsGroup),
+    Path(Path),
 }
 
 impl<I> From<I> for PdfObject
@@ -103,6 +153,75 @@ where
         PdfObject::Graphics(item.into())
     }
 }
+impl GraphicItems {
+    /// The smallest rectangle containing the item
+    ///
+    /// Same limits as [GraphicsGroup::bounds]
+    pub fn bounds(&self) -> Option<OutlineRect> {
+        let mut points = Vec::new();
+        self.collect_points(&mut points);
+        bounds_of_points(&points)
+    }
+    /// Every point used to draw the item
+    fn collect_points(&self, points: &mut Vec<PdfPosition>) {
+        match self {
+            GraphicItems::StraightLine(line) => {
+                points.push(line.start);
+                points.extend_from_slice(&line.points);
+            }
+            GraphicItems::Line(line) => {
+                points.push(line.start);
+                for point in &line.points {
+                    match *point {
+                        LinePoint::Point(point) => points.push(point),
+                        LinePoint::V1Bezier { start, end } | LinePoint::V2Bezier { start, end } => {
+                            points.extend([start, end])
+                        }
+                        LinePoint::ThreePointBezier {
+                            start,
+                            end,
+                            new_control,
+                        } => points.extend([start, end, new_control]),
+                    }
+                }
+            }
+            GraphicItems::Rectangle(rect) => points.extend([
+                rect.position,
+                PdfPosition::new(
+                    rect.position.x + rect.size.width,
+                    rect.position.y + rect.size.height,
+                ),
+            ]),
+            GraphicItems::OutlineRectangle(rect) => points.extend([
+                rect.position,
+                PdfPosition::new(
+                    rect.position.x + rect.size.width,
+                    rect.position.y + rect.size.height,
+                ),
+            ]),
+            GraphicItems::Group(group) => {
+                for item in &group.items {
+                    item.collect_points(points);
+                }
+            }
+            GraphicItems::Path(path) => {
+                for segment in &path.segments {
+                    match *segment {
+                        PathSegment::MoveTo(point) | PathSegment::LineTo(point) => {
+                            points.push(point)
+                        }
+                        PathSegment::CurveTo {
+                            control_1,
+                            control_2,
+                            end,
+                        } => points.extend([control_1, control_2, end]),
+                        PathSegment::Close => {}
+                    }
+                }
+            }
+        }
+    }
+}
 impl PdfObjectType for GraphicItems {
     fn write(
         self,
@@ -115,6 +234,40 @@ impl PdfObjectType for GraphicItems {
             GraphicItems::Rectangle(rect) => rect.write(resources, writer),
             GraphicItems::OutlineRectangle(rect) => rect.write(resources, writer),
             GraphicItems::Group(group) => group.write(resources, writer),
+            GraphicItems::Path(path) => path.write(resources, writer),
         }
     }
 }
+#[cfg(test)]
+mod tests {
+    use super::GraphicsGroup;
+    use crate::{
+        document::PdfResources,
+        graphics::{
+            OperationWriter, PdfObjectType, PdfPosition, primitives::ctm::CurTransMat,
+            shapes::OutlineRect, size::Size,
+        },
+        units::{Pt, UnitType},
+    };
+
+    #[test]
+    fn transform_is_applied_once_for_the_group() {
+        let mut group = GraphicsGroup::new().with_transform(vec![CurTransMat::Rotate(90f32)]);
+        group.add_item(OutlineRect::new_from_bottom_left(
+            PdfPosition::<Pt>::default(),
+            Size::new(10f32.pt(), 10f32.pt()),
+        ));
+        let mut writer = OperationWriter::default();
+        group.write(&PdfResources::default(), &mut writer).unwrap();
+        let operations = writer.operations();
+        assert_eq!(operations[0].operation, "q");
+        assert_eq!(operations[1].operation, "cm");
+        assert_eq!(
+            operations
+                .iter()
+                .filter(|operation| operation.operation == "cm")
+                .count(),
+            1
+        );
+    }
+}
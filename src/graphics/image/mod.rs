@@ -1,13 +1,13 @@
 use crate::{
     TuxPdfError,
-    document::{ResourceNotRegistered, XObjectId, XObjectRef},
+    document::{ResourceNotRegistered, XObjectId, XObjectMap, XObjectRef},
     layouts::LayoutItemType,
     units::{Pt, Px},
 };
 
 use super::{
-    HasPosition, LayerType, PdfObject, PdfObjectType, PdfPosition, primitives::ctm::CurTransMat,
-    size::Size,
+    ClipRegion, HasPosition, LayerType, PdfObject, PdfObjectType, PdfPosition,
+    primitives::ctm::CurTransMat, shapes::OutlineRect, size::Size,
 };
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -55,26 +55,63 @@ pub struct ImageRotation<U = Px> {
     pub rotation_center_y: U,
 }
 
+/// How an image is sized within the area a layout gives it
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum ImageFit {
+    /// The image keeps the size from its DPI and scale
+    #[default]
+    None,
+    /// Scaled to fit inside of the area keeping the aspect ratio. The image is centered in the area
+    Contain,
+    /// Scaled to cover the whole area keeping the aspect ratio. The parts outside of the area are clipped
+    Cover,
+    /// Stretched to the size of the area
+    Stretch,
+}
+impl ImageFit {
+    /// The lower left corner and size of an image with the size `image_size` placed in the area
+    ///
+    /// Returns None for [ImageFit::None]
+    pub fn place(&self, image_size: Size, area: OutlineRect) -> Option<OutlineRect> {
+        let width_scale = area.size.width.0 / image_size.width.0;
+        let height_scale = area.size.height.0 / image_size.height.0;
+        let size = match self {
+            ImageFit::None => return None,
+            ImageFit::Stretch => return Some(area),
+            ImageFit::Contain => {
+                let scale = width_scale.min(height_scale);
+                image_size.scale(scale, scale)
+            }
+            ImageFit::Cover => {
+                let scale = width_scale.max(height_scale);
+                image_size.scale(scale, scale)
+            }
+        };
+        let position = PdfPosition {
+            x: area.position.x + (area.size.width - size.width) / 2f32,
+            y: area.position.y + (area.size.height - size.height) / 2f32,
+        };
+        Some(OutlineRect { position, size })
+    }
+}
 /// This is a struct used to show an image on the page/layer of the PDF
 #[derive(Debug, Clone, PartialEq)]
 pub struct PdfImage {
     pub image: XObjectId,
     pub transform: ImageTransform<Pt>,
+    pub fit: ImageFit,
+    /// The area the image is fit into. Set by the layout the image is placed in
+    ///
+    /// Ignored if [PdfImage::fit] is [ImageFit::None]
+    pub fit_area: Option<OutlineRect>,
 }
 impl LayoutItemType for PdfImage {
     fn calculate_size(
         &mut self,
         document: &crate::document::PdfDocument,
     ) -> Result<Size, TuxPdfError> {
-        let Some(x_object_ref) = document.resources.xobjects.get_xobject(&self.image) else {
-            return Err(ResourceNotRegistered::from(self.image.clone()).into());
-        };
-        let XObjectRef::Image(image) = x_object_ref else {
-            return Err(TuxPdfError::InvalidReference("Image"));
-        };
-
-        let scaled_size = self.scaled_size(image.image.size);
-        Ok(scaled_size)
+        let xobject = self.xobject(&document.resources.xobjects)?;
+        Ok(self.scaled_size_of(&xobject))
     }
 
     fn render<L: LayerType>(
@@ -104,10 +141,7 @@ impl From<PdfImage> for PdfObject {
 }
 impl From<XObjectId> for PdfImage {
     fn from(image_ref: XObjectId) -> Self {
-        Self {
-            image: image_ref,
-            transform: ImageTransform::default(),
-        }
+        Self::new(image_ref)
     }
 }
 impl PdfObjectType for PdfImage {
@@ -117,14 +151,17 @@ impl PdfObjectType for PdfImage {
         writer: &mut crate::graphics::OperationWriter,
     ) -> Result<(), TuxPdfError> {
         writer.save_graphics_state();
-        let Some(x_object_ref) = resources.xobjects.get_xobject(&self.image) else {
-            return Err(ResourceNotRegistered::from(self.image).into());
-        };
-        let XObjectRef::Image(image) = x_object_ref else {
-            return Err(TuxPdfError::InvalidReference("Image"));
-        };
+        let xobject = self.xobject(&resources.xobjects)?;
 
-        self.transforms(image.image.size).write(resources, writer)?;
+        if let (ImageFit::Cover, Some(area)) = (self.fit, self.fit_area) {
+            ClipRegion::rect(area.position, area.size).write_clip(resources, writer)?;
+        }
+        let rect = self.place_scaled(self.scaled_size_of(&xobject));
+        let transforms = match xobject {
+            XObjectRef::Stream(stream) => stream.transforms(rect),
+            _ => unit_square_transforms(rect),
+        };
+        transforms.write(resources, writer)?;
 
         writer.add_operation(
             crate::graphics::OperationKeys::PaintXObject,
@@ -140,6 +177,8 @@ impl PdfImage {
         Self {
             image: image_ref,
             transform: ImageTransform::default(),
+            fit: ImageFit::default(),
+            fit_area: None,
         }
     }
     pub fn dpi(&self) -> f32 {
@@ -169,13 +208,23 @@ impl PdfImage {
     pub fn set_position(&mut self, position: PdfPosition<Pt>) {
         self.transform.position = position;
     }
+    pub fn with_fit(mut self, fit: ImageFit) -> Self {
+        self.fit = fit;
+        self
+    }
+    /// Sets the area the image is fit into
+    pub fn set_fit_area(&mut self, area: OutlineRect) {
+        self.fit_area = Some(area);
+    }
 
     /// Returns the scaled size of the image
     ///
     /// After the DPI is applied, the image is scaled by the `scale_x` and `scale_y` values.
     pub fn scaled_size(&self, size: Size<Px>) -> Size<Pt> {
         let size: Size = size.into_pt_with_dpi(self.dpi());
-
+        self.apply_scale(size)
+    }
+    fn apply_scale(&self, size: Size) -> Size {
         match (self.transform.scale_x, self.transform.scale_y) {
             (Some(scale_x), Some(scale_y)) => size.scale(scale_x, scale_y),
             (Some(scale_x), None) => size.scale_width(scale_x),
@@ -183,14 +232,84 @@ impl PdfImage {
             (None, None) => size,
         }
     }
+    /// The registered XObject. Must be an image or a [StreamXObject](crate::document::StreamXObject)
+    fn xobject<'resources>(
+        &self,
+        xobjects: &'resources XObjectMap,
+    ) -> Result<XObjectRef<'resources>, TuxPdfError> {
+        match xobjects.get_xobject(&self.image) {
+            Some(XObjectRef::Form(_)) => Err(TuxPdfError::InvalidReference("Image")),
+            Some(xobject) => Ok(xobject),
+            None => Err(ResourceNotRegistered::from(self.image.clone()).into()),
+        }
+    }
+    /// The size of the XObject after the DPI and scale are applied
+    fn scaled_size_of(&self, xobject: &XObjectRef<'_>) -> Size {
+        match xobject {
+            XObjectRef::Image(image) => self.scaled_size(image.image.size),
+            XObjectRef::Stream(stream) => self.apply_scale(stream.size(self.dpi())),
+            XObjectRef::Form(_) => Size::default(),
+        }
+    }
+    /// The lower left corner and size the image is drawn at
+    ///
+    /// Uses the [fit](PdfImage::fit) when a fit area is set
+    pub fn placed_rect(&self, size: Size<Px>) -> OutlineRect {
+        self.place_scaled(self.scaled_size(size))
+    }
+    /// [PdfImage::placed_rect] of any XObject the image can draw
+    pub fn placed_rect_in(&self, xobjects: &XObjectMap) -> Result<OutlineRect, TuxPdfError> {
+        let xobject = self.xobject(xobjects)?;
+        Ok(self.place_scaled(self.scaled_size_of(&xobject)))
+    }
+    fn place_scaled(&self, scaled_size: Size) -> OutlineRect {
+        self.fit_area
+            .and_then(|area| self.fit.place(scaled_size, area))
+            .unwrap_or(OutlineRect {
+                position: self.transform.position,
+                size: scaled_size,
+            })
+    }
     /// Get the transforms for the image
     pub fn transforms(&self, size: Size<Px>) -> Vec<CurTransMat> {
-        let scaled_size = self.scaled_size(size);
-        let transforms = vec![
-            CurTransMat::Scale(scaled_size.width, scaled_size.height),
-            CurTransMat::Position(self.transform.position),
-        ];
+        unit_square_transforms(self.placed_rect(size))
+    }
+}
+/// Maps the unit square images are drawn in onto the rectangle
+fn unit_square_transforms(rect: OutlineRect) -> Vec<CurTransMat> {
+    let OutlineRect { position, size } = rect;
+    vec![
+        CurTransMat::Scale(size.width, size.height),
+        CurTransMat::Position(position),
+    ]
+}
+#[cfg(test)]
+mod tests {
+    use super::ImageFit;
+    use crate::{
+        graphics::{PdfPosition, shapes::OutlineRect, size::Size},
+        units::UnitType,
+    };
+
+    fn area() -> OutlineRect {
+        OutlineRect {
+            position: PdfPosition::new(10f32.pt(), 20f32.pt()),
+            size: Size::new(200f32.pt(), 100f32.pt()),
+        }
+    }
+
+    #[test]
+    fn image_fit_place() {
+        let image_size = Size::new(50f32.pt(), 50f32.pt());
+        assert_eq!(ImageFit::None.place(image_size, area()), None);
+        assert_eq!(ImageFit::Stretch.place(image_size, area()), Some(area()));
+
+        let contain = ImageFit::Contain.place(image_size, area()).unwrap();
+        assert_eq!(contain.size, Size::new(100f32.pt(), 100f32.pt()));
+        assert_eq!(contain.position, PdfPosition::new(60f32.pt(), 20f32.pt()));
 
-        transforms
+        let cover = ImageFit::Cover.place(image_size, area()).unwrap();
+        assert_eq!(cover.size, Size::new(200f32.pt(), 200f32.pt()));
+        assert_eq!(cover.position, PdfPosition::new(10f32.pt(), (-30f32).pt()));
     }
 }
@@ -0,0 +1,104 @@
+use crate::{
+    TuxPdfError,
+    document::{ExtGStateId, PdfResources, ResourceNotRegistered},
+};
+
+use super::{LayerType, OperationKeys, OperationWriter, PdfObject, PdfObjectType};
+
+/// A group of any pdf objects drawn through a [SoftMask](crate::document::SoftMask)
+///
+/// Register the mask with [PdfDocument::add_soft_mask](crate::document::PdfDocument::add_soft_mask).
+/// The mask uses page space so the same mask lines up with the content on every page it is used on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaskedGroup {
+    pub mask: ExtGStateId,
+    pub contents: Vec<PdfObject>,
+}
+impl MaskedGroup {
+    pub fn new(mask: ExtGStateId) -> Self {
+        Self {
+            mask,
+            contents: Vec::new(),
+        }
+    }
+    pub fn with_contents(mut self, contents: Vec<PdfObject>) -> Self {
+        self.contents = contents;
+        self
+    }
+}
+impl LayerType for MaskedGroup {
+    fn add_to_layer(&mut self, object: impl Into<PdfObject>) -> Result<(), TuxPdfError> {
+        self.contents.push(object.into());
+        Ok(())
+    }
+}
+impl From<MaskedGroup> for PdfObject {
+    fn from(group: MaskedGroup) -> Self {
+        PdfObject::Masked(group)
+    }
+}
+impl PdfObjectType for MaskedGroup {
+    fn write(
+        self,
+        resources: &PdfResources,
+        writer: &mut OperationWriter,
+    ) -> Result<(), TuxPdfError> {
+        if resources.ext_g_states.get_ext_g_state(&self.mask).is_none() {
+            return Err(ResourceNotRegistered::from(self.mask).into());
+        }
+        writer.save_graphics_state();
+        writer.add_operation(OperationKeys::SetExtGState, vec![self.mask.into()]);
+        for object in self.contents {
+            object.write(resources, writer)?;
+        }
+        writer.restore_graphics_state();
+        Ok(())
+    }
+    fn calculate_number_of_pdf_objects(&self) -> usize {
+        self.contents
+            .iter()
+            .map(|object| object.calculate_number_of_pdf_objects())
+            .sum::<usize>()
+            + 3
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::MaskedGroup;
+    use crate::{
+        document::{IdType, PdfDocument, SoftMask},
+        graphics::{OperationWriter, PdfObjectType, shapes::OutlineRect, size::Size},
+        page::{PdfPage, page_sizes::A4},
+        units::UnitType,
+    };
+
+    #[test]
+    fn mask_content() -> anyhow::Result<()> {
+        let mut document = PdfDocument::new("Soft Mask");
+        let area = OutlineRect::new_from_bottom_left((0f32.pt(), 0f32.pt()), A4);
+        let mask = document.add_soft_mask(SoftMask::new(area).with_contents(vec![
+            OutlineRect::new_from_bottom_left((0f32.pt(), 0f32.pt()), Size::new(
+                100f32.pt(),
+                100f32.pt(),
+            ))
+            .into(),
+        ]));
+        let group = MaskedGroup::new(mask.clone()).with_contents(vec![area.into()]);
+
+        let mut writer = OperationWriter::default();
+        group.clone().write(&document.resources, &mut writer)?;
+        let operations = writer.operations();
+        assert_eq!(operations[1].operation, "gs");
+        assert_eq!(operations.last().unwrap().operation, "Q");
+
+        let mut page = PdfPage::new_from_page_size(A4);
+        page.contents.push(group.into());
+        document.add_page(page);
+        let mut bytes = Vec::new();
+        document.save_to(&mut bytes)?;
+        let pdf = String::from_utf8_lossy(&bytes);
+        assert!(pdf.contains("/Luminosity"));
+        assert!(pdf.contains(mask.as_str()));
+        Ok(())
+    }
+}
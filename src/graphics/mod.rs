@@ -1,4 +1,12 @@
+mod anchor;
+mod clip;
+mod mask;
+mod transform;
+pub use anchor::*;
 mod group;
+pub use clip::*;
+pub use mask::*;
+pub use transform::*;
 pub mod primitives;
 pub use group::*;
 use tux_pdf_low::types::Object;
@@ -1,3 +1,30 @@
+/// Defines an enum of PDF operators. Each variant is written as its operator
+///
+/// Use it to write operators the library does not have a type for. Such as Type 3 font operators.
+/// Turn the keys into operations for [PdfObject::Raw](crate::graphics::PdfObject::Raw) with [OperationKeyType::to_operation]
+///
+/// ```rust
+/// use tux_pdf::{
+///     graphics::{OperationKeyType, PdfObject},
+///     operation_keys,
+/// };
+/// use tux_pdf_low::types::Object;
+///
+/// operation_keys!(
+///     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///     Type3Operations => {
+///         /// Set the width of a glyph
+///         SetGlyphWidth => "d0",
+///         /// Set the width and bounding box of a glyph
+///         SetCacheDevice => "d1"
+///     }
+/// );
+/// assert_eq!(Type3Operations::SetGlyphWidth.key(), "d0");
+/// let glyph = PdfObject::Raw(vec![
+///     Type3Operations::SetGlyphWidth.to_operation(vec![Object::from(500i64), Object::from(0i64)]),
+/// ]);
+/// ```
+#[macro_export]
 macro_rules! operation_keys {
     (
         $(
@@ -5,7 +32,7 @@ macro_rules! operation_keys {
             $variant:ident => $value:literal
         ),*
     ) => {
-        operation_keys! {
+        $crate::operation_keys! {
             OperationKeys => { $(
                 $(#[$docs])*
                 $variant => $value
@@ -15,6 +42,7 @@ macro_rules! operation_keys {
     };
 
     (
+       $(#[$group_meta:meta])*
        $key_group_name:ident => {
         $(
             $(#[$docs:meta])*
@@ -22,14 +50,15 @@ macro_rules! operation_keys {
         ),*
     }
     ) => {
+        $(#[$group_meta])*
         pub enum $key_group_name {
             $(
                 $(#[$docs])*
                 $variant
             ),*
         }
-        impl crate::graphics::ops::OperationKeyType for $key_group_name {
-            fn key(&self) -> &str {
+        impl $crate::graphics::OperationKeyType for $key_group_name {
+            fn key(&self) -> &'static str {
                 match self {
                     $(
                         $key_group_name::$variant => $value,
@@ -48,10 +77,10 @@ macro_rules! operation_keys {
         }
     };
 }
-pub(crate) use operation_keys;
+pub use crate::operation_keys;
 use tux_pdf_low::{content::Operation, types::Object};
 pub trait OperationKeyType {
-    fn key(&self) -> &str;
+    fn key(&self) -> &'static str;
     #[inline(always)]
     fn to_operation(&self, operands: Vec<Object>) -> Operation {
         Operation::new(self.key(), operands)
@@ -72,6 +101,10 @@ operation_keys!(
     CurrentTransformationMatrix => "cm",
     /// Render a xobject
     PaintXObject => "Do",
+    /// Set parameters from an ExtGState resource
+    SetExtGState => "gs",
+    /// Paint a shading across the current clipping path
+    PaintShading => "sh",
     BeginLayer => "BDC",
     BeginMarkedContent => "BMC",
     EndSection => "EMC"
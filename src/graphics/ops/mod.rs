@@ -1,15 +1,20 @@
 mod keys;
+use std::mem;
+
 use crate::{
     TuxPdfError,
     document::{LayerId, PdfResources},
 };
 pub use keys::*;
 use tux_pdf_low::{
-    content::Operation,
+    content::{Operation, write_operations_to_vec},
     types::{Dictionary, Object, PdfType, Stream},
 };
 
-use super::{GraphicStyles, TextBlock, TextOperations, group::GraphicItems, image::PdfImage};
+use super::{
+    ClippedGroup, GraphicStyles, MaskedGroup, PathText, TextBlock, TextOperations,
+    TransformedGroup, group::GraphicItems, image::PdfImage,
+};
 /// Operations that can occur in a PDF page
 #[derive(Debug, Clone, PartialEq)]
 pub enum PdfObject {
@@ -18,6 +23,14 @@ pub enum PdfObject {
     Graphics(GraphicItems),
     Styles(GraphicStyles),
     Image(PdfImage),
+    Clipped(ClippedGroup),
+    Transformed(TransformedGroup),
+    Masked(MaskedGroup),
+    PathText(PathText),
+    /// Operations written to the content stream as is
+    ///
+    /// Resources used by the operations must be added with [RawObjects::add_resource](crate::document::RawObjects::add_resource)
+    Raw(Vec<Operation>),
 }
 
 impl PdfObjectType for PdfObject {
@@ -42,6 +55,21 @@ impl PdfObjectType for PdfObject {
             PdfObject::Image(pdf_image_operation) => {
                 pdf_image_operation.write(resources, writer)?;
             }
+            PdfObject::Clipped(group) => {
+                group.write(resources, writer)?;
+            }
+            PdfObject::Transformed(group) => {
+                group.write(resources, writer)?;
+            }
+            PdfObject::Masked(group) => {
+                group.write(resources, writer)?;
+            }
+            PdfObject::PathText(text) => {
+                text.write(resources, writer)?;
+            }
+            PdfObject::Raw(operations) => {
+                writer.operations.extend(operations);
+            }
         }
         Ok(())
     }
@@ -55,6 +83,11 @@ impl PdfObjectType for PdfObject {
             PdfObject::Image(pdf_image_operation) => {
                 pdf_image_operation.calculate_number_of_pdf_objects()
             }
+            PdfObject::Clipped(group) => group.calculate_number_of_pdf_objects(),
+            PdfObject::Transformed(group) => group.calculate_number_of_pdf_objects(),
+            PdfObject::Masked(group) => group.calculate_number_of_pdf_objects(),
+            PdfObject::PathText(text) => text.calculate_number_of_pdf_objects(),
+            PdfObject::Raw(operations) => operations.len(),
         }
     }
 }
@@ -117,9 +150,41 @@ impl OperationWriter {
     }
 
     pub(crate) fn into_stream(self, dictionary: Dictionary) -> Result<Stream, TuxPdfError> {
-        let stream_content = self.operations.write_to_vec()?;
+        let stream_content = write_operations_to_vec(self.operations)?;
         Ok(Stream::new(dictionary, stream_content))
     }
+    /// Writes the operations into content streams of at most `max_bytes` each
+    ///
+    /// Streams are only split between operations. An operation larger than `max_bytes` gets a stream of its own.
+    /// With `None` everything is written into one stream
+    pub(crate) fn into_content_streams(
+        self,
+        max_bytes: Option<usize>,
+    ) -> Result<Vec<Stream>, TuxPdfError> {
+        let Some(max_bytes) = max_bytes else {
+            return Ok(vec![self.into_stream(Dictionary::default())?]);
+        };
+        let mut streams = Vec::new();
+        let mut content: Vec<u8> = Vec::new();
+        for operation in self.operations {
+            let start = content.len();
+            operation.write(&mut content)?;
+            // Every operation ends with white space so the operators do not run together once the streams are joined
+            content.push(b'\n');
+            if start != 0 && content.len() > max_bytes {
+                // The operation starts the next stream
+                let operation = content.split_off(start);
+                streams.push(Stream::new(
+                    Dictionary::default(),
+                    mem::replace(&mut content, operation),
+                ));
+            }
+        }
+        // Matches the content of a single stream when nothing was split
+        content.pop();
+        streams.push(Stream::new(Dictionary::default(), content));
+        Ok(streams)
+    }
 }
 /// A type that can be written to a pdf containing a few different types of objects
 ///
@@ -1,6 +1,16 @@
-use crate::{TuxPdfError, layouts::LayoutItemType};
+use crate::{
+    TuxPdfError,
+    layouts::LayoutItemType,
+    units::{Pt, UnitType},
+};
 
-use super::{HasPosition, PdfPosition, size::Size};
+use super::{
+    GraphicStyles, GraphicsGroup, HasPosition, Margin, PdfPosition,
+    color::{BLACK_RGB, Color},
+    primitives::PaintMode,
+    shapes::PaintedRect,
+    size::Size,
+};
 
 /// Blank space is good if you wanna leave a "text space" or writing space for later
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -46,3 +56,181 @@ impl LayoutItemType for BlankSpace {
         Ok(())
     }
 }
+/// Empty space with a fixed size
+///
+/// Use it to push items apart within a layout
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Spacer {
+    pub size: Size,
+    pub position: PdfPosition,
+}
+impl Spacer {
+    pub fn new(size: Size) -> Self {
+        Self {
+            size,
+            position: PdfPosition::default(),
+        }
+    }
+    /// Vertical space with no width
+    pub fn vertical(height: Pt) -> Self {
+        Self::new(Size::new(Pt::default(), height))
+    }
+    /// Horizontal space with no height
+    pub fn horizontal(width: Pt) -> Self {
+        Self::new(Size::new(width, Pt::default()))
+    }
+}
+impl From<Size> for Spacer {
+    fn from(size: Size) -> Self {
+        Self::new(size)
+    }
+}
+impl HasPosition for Spacer {
+    fn position(&self) -> PdfPosition {
+        self.position
+    }
+
+    fn set_position(&mut self, position: PdfPosition) {
+        self.position = position;
+    }
+}
+impl LayoutItemType for Spacer {
+    fn calculate_size(
+        &mut self,
+        _document: &crate::document::PdfDocument,
+    ) -> Result<Size, TuxPdfError> {
+        Ok(self.size)
+    }
+
+    fn render<L: super::LayerType>(
+        self,
+        _: &crate::document::PdfDocument,
+        _: &mut L,
+    ) -> Result<(), TuxPdfError>
+    where
+        Self: Sized,
+    {
+        Ok(())
+    }
+}
+#[derive(Debug, Clone, PartialEq)]
+pub struct DividerStyle {
+    pub thickness: Pt,
+    pub color: Color,
+    /// Space around the rule. Left and right shorten the rule
+    pub margin: Margin,
+}
+impl Default for DividerStyle {
+    fn default() -> Self {
+        Self {
+            thickness: 1f32.pt(),
+            color: BLACK_RGB,
+            margin: Margin::default(),
+        }
+    }
+}
+/// A horizontal rule
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Divider {
+    pub style: DividerStyle,
+    /// Width of the divider including the left and right margins
+    ///
+    /// Layouts that give the divider a width such as the taffy layout will set this
+    pub width: Pt,
+    /// Bottom left corner of the divider including the margins
+    pub position: PdfPosition,
+}
+impl Divider {
+    pub fn new(style: DividerStyle, width: Pt) -> Self {
+        Self {
+            style,
+            width,
+            position: PdfPosition::default(),
+        }
+    }
+    /// The height of the rule and the top and bottom margins
+    pub fn height(&self) -> Pt {
+        self.style.thickness
+            + self.style.margin.top.unwrap_or_default()
+            + self.style.margin.bottom.unwrap_or_default()
+    }
+}
+impl From<DividerStyle> for Divider {
+    fn from(style: DividerStyle) -> Self {
+        Self::new(style, Pt::default())
+    }
+}
+impl HasPosition for Divider {
+    fn position(&self) -> PdfPosition {
+        self.position
+    }
+
+    fn set_position(&mut self, position: PdfPosition) {
+        self.position = position;
+    }
+}
+impl LayoutItemType for Divider {
+    fn calculate_size(
+        &mut self,
+        _document: &crate::document::PdfDocument,
+    ) -> Result<Size, TuxPdfError> {
+        Ok(Size::new(self.width, self.height()))
+    }
+
+    fn render<L: super::LayerType>(
+        self,
+        _: &crate::document::PdfDocument,
+        page: &mut L,
+    ) -> Result<(), TuxPdfError>
+    where
+        Self: Sized,
+    {
+        let (left, right, _, bottom): (Pt, Pt, Pt, Pt) = self.style.margin.into();
+        let rule = PaintedRect {
+            position: PdfPosition {
+                x: self.position.x + left,
+                y: self.position.y + bottom,
+            },
+            size: Size::new(
+                (self.width - left - right).max(Pt::default()),
+                self.style.thickness,
+            ),
+            paint_mode: PaintMode::Fill,
+            ..Default::default()
+        };
+        page.add_to_layer(GraphicsGroup {
+            styles: Some(GraphicStyles {
+                fill_color: Some(self.style.color),
+                ..Default::default()
+            }),
+            items: vec![rule.into()],
+            ..Default::default()
+        })
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::{Divider, DividerStyle};
+    use crate::{
+        document::PdfDocument,
+        graphics::{Margin, size::Size},
+        layouts::LayoutItemType,
+        units::UnitType,
+    };
+
+    #[test]
+    fn divider_size_includes_margins() -> anyhow::Result<()> {
+        let document = PdfDocument::new("divider_size");
+        let mut divider = Divider::new(
+            DividerStyle {
+                thickness: 2f32.pt(),
+                margin: Margin::new(5f32.pt(), 5f32.pt(), 4f32.pt(), 6f32.pt()),
+                ..Default::default()
+            },
+            100f32.pt(),
+        );
+        let size = divider.calculate_size(&document)?;
+        assert_eq!(size, Size::new(100f32.pt(), 12f32.pt()));
+        Ok(())
+    }
+}
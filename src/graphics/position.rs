@@ -2,7 +2,7 @@ use std::ops::{Add, Sub};
 
 use tux_pdf_low::types::Object;
 
-use crate::units::{Mm, Pt};
+use crate::units::{In, Length, Mm, Pt};
 
 use super::size::Size;
 
@@ -92,6 +92,68 @@ impl PdfPosition<Mm> {
         }
     }
 }
+impl PdfPosition<Length> {
+    /// Creates a position where each axis can use a different unit
+    pub fn from_lengths(x: impl Into<Length>, y: impl Into<Length>) -> Self {
+        Self {
+            x: x.into(),
+            y: y.into(),
+        }
+    }
+    /// Converts the position into [Pt]
+    ///
+    /// Percentages on the x axis are relative to the width and on the y axis relative to the height of `reference`.
+    /// Usually the page size.
+    pub fn resolve(self, reference: Size) -> PdfPosition {
+        PdfPosition {
+            x: self.x.resolve(reference.width),
+            y: self.y.resolve(reference.height),
+        }
+    }
+}
+macro_rules! position_unit_into_pt {
+    (
+        $(
+            $unit:ty
+        ),*
+    ) => {
+        $(
+            impl From<PdfPosition<$unit>> for PdfPosition<Pt> {
+                fn from(position: PdfPosition<$unit>) -> Self {
+                    PdfPosition {
+                        x: position.x.into(),
+                        y: position.y.into(),
+                    }
+                }
+            }
+            impl From<($unit, $unit)> for PdfPosition<Pt> {
+                fn from((x, y): ($unit, $unit)) -> Self {
+                    PdfPosition {
+                        x: x.into(),
+                        y: y.into(),
+                    }
+                }
+            }
+            impl From<PdfPosition<$unit>> for PdfPosition<Length> {
+                fn from(position: PdfPosition<$unit>) -> Self {
+                    PdfPosition {
+                        x: position.x.into(),
+                        y: position.y.into(),
+                    }
+                }
+            }
+        )*
+    };
+}
+position_unit_into_pt!(Mm, In);
+impl From<PdfPosition<Pt>> for PdfPosition<Length> {
+    fn from(position: PdfPosition<Pt>) -> Self {
+        PdfPosition {
+            x: position.x.into(),
+            y: position.y.into(),
+        }
+    }
+}
 impl<U> Default for PdfPosition<U>
 where
     U: Default,
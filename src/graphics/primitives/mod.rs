@@ -1,7 +1,9 @@
 use super::{PdfPosition, operation_keys};
 pub mod ctm;
 mod line;
+mod path;
 pub use line::*;
+pub use path::*;
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct Polygon {
     /// 2D Points for the line. The `bool` indicates whether the next point is a bezier control point.
@@ -114,5 +116,7 @@ operation_keys!(PathConstructionOperators => {
     /// y - The y coordinate of the lower-left corner of the rectangle
     /// width - The width of the rectangle
     /// height - The height of the rectangle
-    PathRectangle => "re"
+    PathRectangle => "re",
+    /// Close the current subpath by appending a straight line from the current point to the start of the subpath
+    PathClose => "h"
 });
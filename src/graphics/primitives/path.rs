@@ -0,0 +1,298 @@
+use crate::{
+    document::PdfResources,
+    graphics::{OperationWriter, PdfObjectType, points_to_object_array},
+    units::Pt,
+};
+
+use super::{
+    PaintMode, PathConstructionOperators, PathPaintOperationKeys, PdfPosition, WindingOrder,
+};
+
+/// A single operation in a [Path]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathSegment {
+    /// Starts a new subpath. Maps to `m`
+    MoveTo(PdfPosition),
+    /// Maps to `l`
+    LineTo(PdfPosition),
+    /// Cubic bezier curve. Maps to `c`
+    CurveTo {
+        control_1: PdfPosition,
+        control_2: PdfPosition,
+        end: PdfPosition,
+    },
+    /// Closes the current subpath. Maps to `h`
+    Close,
+}
+impl PdfObjectType for PathSegment {
+    fn write(
+        self,
+        _: &PdfResources,
+        writer: &mut OperationWriter,
+    ) -> Result<(), crate::TuxPdfError> {
+        match self {
+            PathSegment::MoveTo(point) => {
+                writer.add_operation(PathConstructionOperators::PathMoveTo, point.into())
+            }
+            PathSegment::LineTo(point) => {
+                writer.add_operation(PathConstructionOperators::PathLineTo, point.into())
+            }
+            PathSegment::CurveTo {
+                control_1,
+                control_2,
+                end,
+            } => writer.add_operation(
+                PathConstructionOperators::BezierCurveFour,
+                points_to_object_array(&[control_1, control_2, end]),
+            ),
+            PathSegment::Close => writer.push_empty_op(PathConstructionOperators::PathClose),
+        }
+        Ok(())
+    }
+}
+/// An arbitrary path made of lines and curves
+///
+/// Use [PathBuilder] to create one
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Path {
+    pub segments: Vec<PathSegment>,
+    pub paint_mode: PaintMode,
+    pub winding_order: WindingOrder,
+}
+impl Path {
+    pub fn builder() -> PathBuilder {
+        PathBuilder::default()
+    }
+    pub fn with_mode(mut self, mode: PaintMode) -> Self {
+        self.paint_mode = mode;
+        self
+    }
+    pub fn with_winding_order(mut self, order: WindingOrder) -> Self {
+        self.winding_order = order;
+        self
+    }
+}
+impl PdfObjectType for Path {
+    fn write(
+        self,
+        resources: &PdfResources,
+        writer: &mut OperationWriter,
+    ) -> Result<(), crate::TuxPdfError> {
+        if self.segments.is_empty() {
+            return Ok(());
+        }
+        for segment in self.segments {
+            segment.write(resources, writer)?;
+        }
+        writer.push_empty_op(self.paint_mode.operation_key(self.winding_order));
+        if self.paint_mode == PaintMode::Clip {
+            writer.push_empty_op(PathPaintOperationKeys::PathPaintEnd);
+        }
+        Ok(())
+    }
+    fn calculate_number_of_pdf_objects(&self) -> usize {
+        self.segments.len() + 1
+    }
+}
+/// Builds a [Path]
+///
+/// ```rust
+/// use tux_pdf::{graphics::primitives::{PathBuilder, PaintMode}, units::UnitType};
+///
+/// let path = PathBuilder::new()
+///     .move_to((0f32.pt(), 0f32.pt()))
+///     .line_to((100f32.pt(), 0f32.pt()))
+///     .arc_to((100f32.pt(), 50f32.pt()), 50f32.pt(), -90f32, 90f32)
+///     .line_to((0f32.pt(), 100f32.pt()))
+///     .close()
+///     .with_mode(PaintMode::FillStroke)
+///     .build();
+/// assert_eq!(path.segments.len(), 6);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PathBuilder {
+    path: Path,
+    current_point: Option<PdfPosition>,
+    subpath_start: Option<PdfPosition>,
+}
+impl PathBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// The point the next segment will start from
+    pub fn current_point(&self) -> Option<PdfPosition> {
+        self.current_point
+    }
+    /// Starts a new subpath at the point
+    pub fn move_to(mut self, point: impl Into<PdfPosition>) -> Self {
+        let point = point.into();
+        self.path.segments.push(PathSegment::MoveTo(point));
+        self.current_point = Some(point);
+        self.subpath_start = Some(point);
+        self
+    }
+    /// Draws a straight line to the point
+    ///
+    /// If there is no current point this acts as a [Self::move_to]
+    pub fn line_to(mut self, point: impl Into<PdfPosition>) -> Self {
+        if self.current_point.is_none() {
+            return self.move_to(point);
+        }
+        let point = point.into();
+        self.path.segments.push(PathSegment::LineTo(point));
+        self.current_point = Some(point);
+        self
+    }
+    /// Draws a cubic bezier curve from the current point to `end`
+    ///
+    /// If there is no current point the curve will start at `control_1`
+    pub fn curve_to(
+        mut self,
+        control_1: impl Into<PdfPosition>,
+        control_2: impl Into<PdfPosition>,
+        end: impl Into<PdfPosition>,
+    ) -> Self {
+        let control_1 = control_1.into();
+        if self.current_point.is_none() {
+            self = self.move_to(control_1);
+        }
+        let end = end.into();
+        self.path.segments.push(PathSegment::CurveTo {
+            control_1,
+            control_2: control_2.into(),
+            end,
+        });
+        self.current_point = Some(end);
+        self
+    }
+    /// Draws a circular arc around `center`
+    ///
+    /// Angles are in degrees, counter clockwise starting from the positive x axis.
+    /// If `end_angle` is less than `start_angle` the arc is drawn clockwise.
+    ///
+    /// A line is drawn from the current point to the start of the arc. If there is no current point the arc starts a new subpath.
+    ///
+    /// PDF has no arc operator so the arc is approximated with one bezier curve per 90 degrees
+    pub fn arc_to(
+        mut self,
+        center: impl Into<PdfPosition>,
+        radius: Pt,
+        start_angle: f32,
+        end_angle: f32,
+    ) -> Self {
+        let center = center.into();
+        let point_at = |angle: f32| PdfPosition {
+            x: center.x + radius * angle.cos(),
+            y: center.y + radius * angle.sin(),
+        };
+        let start = start_angle.to_radians();
+        let sweep = (end_angle - start_angle).to_radians();
+        let start_point = point_at(start);
+        match self.current_point {
+            Some(current) if current == start_point => {}
+            Some(_) => self = self.line_to(start_point),
+            None => self = self.move_to(start_point),
+        }
+        if sweep == 0f32 {
+            return self;
+        }
+        let number_of_curves = (sweep.abs() / std::f32::consts::FRAC_PI_2).ceil() as usize;
+        let step = sweep / number_of_curves as f32;
+        // Distance of the control points along the tangent
+        let handle = 4f32 / 3f32 * (step / 4f32).tan();
+        for index in 0..number_of_curves {
+            let from = start + step * index as f32;
+            let to = from + step;
+            let from_point = point_at(from);
+            let to_point = point_at(to);
+            let control_1 = PdfPosition {
+                x: from_point.x - radius * (handle * from.sin()),
+                y: from_point.y + radius * (handle * from.cos()),
+            };
+            let control_2 = PdfPosition {
+                x: to_point.x + radius * (handle * to.sin()),
+                y: to_point.y - radius * (handle * to.cos()),
+            };
+            self = self.curve_to(control_1, control_2, to_point);
+        }
+        self
+    }
+    /// Closes the current subpath
+    ///
+    /// The current point goes back to the start of the subpath
+    pub fn close(mut self) -> Self {
+        if self.current_point.is_none() {
+            return self;
+        }
+        self.path.segments.push(PathSegment::Close);
+        self.current_point = self.subpath_start;
+        self
+    }
+    pub fn with_mode(mut self, mode: PaintMode) -> Self {
+        self.path.paint_mode = mode;
+        self
+    }
+    pub fn with_winding_order(mut self, order: WindingOrder) -> Self {
+        self.path.winding_order = order;
+        self
+    }
+    pub fn build(self) -> Path {
+        self.path
+    }
+}
+impl From<PathBuilder> for Path {
+    fn from(builder: PathBuilder) -> Self {
+        builder.build()
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::{PathBuilder, PathSegment};
+    use crate::{
+        document::PdfResources,
+        graphics::{OperationWriter, PdfObjectType, PdfPosition, primitives::PaintMode},
+        units::UnitType,
+    };
+
+    #[test]
+    fn path_operations() {
+        let path = PathBuilder::new()
+            .move_to((0f32.pt(), 0f32.pt()))
+            .line_to((10f32.pt(), 0f32.pt()))
+            .curve_to(
+                (15f32.pt(), 0f32.pt()),
+                (20f32.pt(), 5f32.pt()),
+                (20f32.pt(), 10f32.pt()),
+            )
+            .close()
+            .with_mode(PaintMode::Stroke)
+            .build();
+        let mut writer = OperationWriter::default();
+        path.write(&PdfResources::default(), &mut writer).unwrap();
+        let operators: Vec<_> = writer
+            .operations()
+            .into_iter()
+            .map(|op| op.operation)
+            .collect();
+        assert_eq!(operators, vec!["m", "l", "c", "h", "S"]);
+    }
+    #[test]
+    fn arc_ends_on_circle() {
+        let builder = PathBuilder::new().arc_to((0f32.pt(), 0f32.pt()), 10f32.pt(), 0f32, 180f32);
+        assert_eq!(
+            builder.current_point(),
+            Some(PdfPosition::new((-10f32).pt(), 0f32.pt()))
+        );
+        let path = builder.build();
+        assert_eq!(
+            path.segments[0],
+            PathSegment::MoveTo(PdfPosition::new(10f32.pt(), 0f32.pt()))
+        );
+        let curves = path
+            .segments
+            .iter()
+            .filter(|segment| matches!(segment, PathSegment::CurveTo { .. }))
+            .count();
+        assert_eq!(curves, 2);
+    }
+}
@@ -4,7 +4,7 @@ use tux_pdf_low::types::Object;
 
 use crate::{
     graphics::{
-        OperationWriter, PdfObjectType, PdfPosition,
+        Anchor, Margin, OperationWriter, PdfObjectType, PdfPosition,
         primitives::{
             PaintMode, PathConstructionOperators, PathPaintOperationKeys, Polygon, StraightLine,
             WindingOrder,
@@ -171,6 +171,60 @@ impl OutlineRect {
         };
         Self { position, size }
     }
+    /// The rectangle shrunk by the margin on each side. The size never goes below zero
+    pub fn inset(&self, margin: Margin) -> OutlineRect {
+        let (left, _, _, bottom): (Pt, Pt, Pt, Pt) = margin.into();
+        OutlineRect {
+            position: PdfPosition::new(self.position.x + left, self.position.y + bottom),
+            size: self.size.inset(margin),
+        }
+    }
+    /// The smallest rectangle containing both rectangles
+    pub fn union(&self, other: &OutlineRect) -> OutlineRect {
+        let (self_max, other_max) = (self.upper_right(), other.upper_right());
+        let lower_left = PdfPosition::new(
+            self.position.x.min(other.position.x),
+            self.position.y.min(other.position.y),
+        );
+        let upper_right =
+            PdfPosition::new(self_max.x.max(other_max.x), self_max.y.max(other_max.y));
+        OutlineRect {
+            position: lower_left,
+            size: Size::new(upper_right.x - lower_left.x, upper_right.y - lower_left.y),
+        }
+    }
+    /// The area both rectangles cover. None if they do not [intersect](OutlineRect::intersects)
+    ///
+    /// Rectangles that only share an edge have an intersection with a width or height of zero
+    pub fn intersection(&self, other: &OutlineRect) -> Option<OutlineRect> {
+        if !self.intersects(other) {
+            return None;
+        }
+        let (self_max, other_max) = (self.upper_right(), other.upper_right());
+        let lower_left = PdfPosition::new(
+            self.position.x.max(other.position.x),
+            self.position.y.max(other.position.y),
+        );
+        let upper_right =
+            PdfPosition::new(self_max.x.min(other_max.x), self_max.y.min(other_max.y));
+        Some(OutlineRect {
+            position: lower_left,
+            size: Size::new(upper_right.x - lower_left.x, upper_right.y - lower_left.y),
+        })
+    }
+    /// If the point is inside the rectangle. Points on the edges are inside
+    pub fn contains(&self, point: PdfPosition) -> bool {
+        let upper_right = self.upper_right();
+        (self.position.x..=upper_right.x).contains(&point.x)
+            && (self.position.y..=upper_right.y).contains(&point.y)
+    }
+    pub fn center(&self) -> PdfPosition {
+        Anchor::Center.point_of(self.position, self.size)
+    }
+    /// Moves the rectangle so its `anchor` point lines up with the same anchor point of `outer`. The size is kept
+    pub fn aligned_within(&self, outer: &OutlineRect, anchor: Anchor) -> OutlineRect {
+        self.size.aligned_within(outer, anchor)
+    }
 }
 copy_into!(OutlineRect => StraightLine);
 impl From<OutlineRect> for StraightLine {
@@ -237,6 +291,14 @@ impl<U> OutlineRect<U> {
 }
 
 impl OutlineRect {
+    /// If the rectangles overlap. Rectangles that only share an edge overlap
+    pub fn intersects(&self, other: &OutlineRect) -> bool {
+        let (self_max, other_max) = (self.upper_right(), other.upper_right());
+        self.position.x <= other_max.x
+            && other.position.x <= self_max.x
+            && self.position.y <= other_max.y
+            && other.position.y <= self_max.y
+    }
     pub fn to_array(&self) -> Vec<Object> {
         let PdfPosition { x, y } = self.position;
         let Size { width, height } = self.size;
@@ -315,3 +377,54 @@ where
     let bl = PdfPosition { x: left, y: bottom };
     vec![tl, tr, br, bl]
 }
+#[cfg(test)]
+mod tests {
+    use super::OutlineRect;
+    use crate::{
+        graphics::{Anchor, Margin, PdfPosition, size::Size},
+        units::UnitType,
+    };
+    fn rect(x: f32, y: f32, width: f32, height: f32) -> OutlineRect {
+        OutlineRect::new_from_bottom_left((x.pt(), y.pt()), (width.pt(), height.pt()))
+    }
+    #[test]
+    fn union_and_intersection() {
+        let a = rect(0f32, 0f32, 100f32, 50f32);
+        let b = rect(50f32, 25f32, 100f32, 100f32);
+        assert_eq!(a.union(&b), rect(0f32, 0f32, 150f32, 125f32));
+        assert_eq!(a.intersection(&b), Some(rect(50f32, 25f32, 50f32, 25f32)));
+        assert_eq!(b.intersection(&a), a.intersection(&b));
+
+        let touching = rect(100f32, 0f32, 10f32, 10f32);
+        assert_eq!(
+            a.intersection(&touching),
+            Some(rect(100f32, 0f32, 0f32, 10f32))
+        );
+        assert_eq!(a.intersection(&rect(200f32, 0f32, 10f32, 10f32)), None);
+    }
+    #[test]
+    fn inset_contains_and_center() {
+        let outer = rect(10f32, 10f32, 100f32, 60f32);
+        let inner = outer.inset(Margin::new(5f32.pt(), 15f32.pt(), 10f32.pt(), 20f32.pt()));
+        assert_eq!(inner, rect(15f32, 30f32, 80f32, 30f32));
+        assert_eq!(outer.inset(Margin::all(100f32.pt())).size, Size::default());
+
+        assert_eq!(outer.center(), PdfPosition::new(60f32.pt(), 40f32.pt()));
+        assert!(outer.contains(outer.center()));
+        assert!(outer.contains(PdfPosition::new(110f32.pt(), 70f32.pt())));
+        assert!(!outer.contains(PdfPosition::new(111f32.pt(), 70f32.pt())));
+    }
+    #[test]
+    fn aligned_within() {
+        let outer = rect(0f32, 0f32, 100f32, 100f32);
+        let item = rect(500f32, 500f32, 20f32, 10f32);
+        assert_eq!(
+            item.aligned_within(&outer, Anchor::TopRight),
+            rect(80f32, 90f32, 20f32, 10f32)
+        );
+        assert_eq!(
+            item.size.aligned_within(&outer, Anchor::Center),
+            rect(40f32, 45f32, 20f32, 10f32)
+        );
+    }
+}
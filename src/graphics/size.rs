@@ -7,8 +7,8 @@ use std::{
 
 use crate::{
     document::{FontRef, FontType, PdfDocument, ResourceNotRegistered},
-    graphics::{PdfPosition, TextStyle},
-    units::{Mm, Pt, Px},
+    graphics::{Anchor, Margin, PdfPosition, TextStyle},
+    units::{In, Length, Mm, Pt, Px},
 };
 
 use super::shapes::OutlineRect;
@@ -69,6 +69,67 @@ impl Size<Px> {
         }
     }
 }
+impl Size<Length> {
+    /// Creates a size where each axis can use a different unit
+    pub fn from_lengths(width: impl Into<Length>, height: impl Into<Length>) -> Self {
+        Self {
+            width: width.into(),
+            height: height.into(),
+        }
+    }
+    /// Converts the size into [Pt]
+    ///
+    /// Percentages of the width are relative to the width of `reference` and percentages of the height relative to its height.
+    pub fn resolve(self, reference: Size) -> Size {
+        Size {
+            width: self.width.resolve(reference.width),
+            height: self.height.resolve(reference.height),
+        }
+    }
+}
+macro_rules! size_unit_into_pt {
+    (
+        $(
+            $unit:ty
+        ),*
+    ) => {
+        $(
+            impl From<Size<$unit>> for Size<Pt> {
+                fn from(size: Size<$unit>) -> Self {
+                    Size {
+                        width: size.width.into(),
+                        height: size.height.into(),
+                    }
+                }
+            }
+            impl From<($unit, $unit)> for Size<Pt> {
+                fn from((width, height): ($unit, $unit)) -> Self {
+                    Size {
+                        width: width.into(),
+                        height: height.into(),
+                    }
+                }
+            }
+            impl From<Size<$unit>> for Size<Length> {
+                fn from(size: Size<$unit>) -> Self {
+                    Size {
+                        width: size.width.into(),
+                        height: size.height.into(),
+                    }
+                }
+            }
+        )*
+    };
+}
+size_unit_into_pt!(Mm, In);
+impl From<Size<Pt>> for Size<Length> {
+    fn from(size: Size<Pt>) -> Self {
+        Size {
+            width: size.width.into(),
+            height: size.height.into(),
+        }
+    }
+}
 impl<U> Size<U> {
     pub fn scale_width(&self, scale: f32) -> Size<U>
     where
@@ -156,6 +217,24 @@ impl<U> Size<U> {
         }
     }
 }
+impl Size {
+    /// The size minus the margin on each side. Never goes below zero
+    pub fn inset(&self, margin: Margin) -> Size {
+        let (left, right, top, bottom): (Pt, Pt, Pt, Pt) = margin.into();
+        Size::new(
+            (self.width - left - right).max(Pt::default()),
+            (self.height - top - bottom).max(Pt::default()),
+        )
+    }
+    /// A rectangle of this size placed so its `anchor` point lines up with the same anchor point of `outer`
+    pub fn aligned_within(&self, outer: &OutlineRect, anchor: Anchor) -> OutlineRect {
+        let point = anchor.point_of(outer.position, outer.size);
+        OutlineRect {
+            position: anchor.bottom_left_from(point, *self),
+            size: *self,
+        }
+    }
+}
 impl<U> From<(U, U)> for Size<U> {
     fn from((width, height): (U, U)) -> Self {
         Self { width, height }
@@ -214,19 +293,28 @@ impl RenderSize for &str {
         document: &PdfDocument,
         settings: &TextStyle,
     ) -> Result<Size, ResourceNotRegistered> {
-        let font = &settings.font_ref;
+        let font = document.resources.fonts.resolve(&settings.font_ref);
         match font {
             FontRef::External(font_id) => {
                 let font = document
                     .resources
                     .fonts
                     .get_external_font(font_id)
-                    .ok_or_else(|| ResourceNotRegistered::from(font.clone()))?;
+                    .ok_or_else(|| document.resources.fonts.not_registered(font))?;
+                Ok(font.calculate_size_of_text(self.as_ref(), settings))
+            }
+            FontRef::Type3(font_id) => {
+                let font = document
+                    .resources
+                    .fonts
+                    .get_type3_font(font_id)
+                    .ok_or_else(|| document.resources.fonts.not_registered(font))?;
                 Ok(font.calculate_size_of_text(self.as_ref(), settings))
             }
             FontRef::Builtin(builtin_font) => {
                 Ok(builtin_font.calculate_size_of_text(self.as_ref(), settings))
             }
+            FontRef::Default => Err(document.resources.fonts.not_registered(font)),
         }
     }
 }
@@ -6,6 +6,7 @@ use std::borrow::Cow;
 use std::ops::Add;
 
 use crate::{
+    document::{BlendMode, Opacity, PdfResources, ResourceNotRegistered},
     units::Pt,
     utils::{IsEmpty, PartailOrFull, PartialStruct},
 };
@@ -27,11 +28,17 @@ where
     }
 }
 
+/// Sets the graphics state for the following content
+///
+/// A [blend mode](BlendMode) must be registered with [PdfDocument::register_blend_mode](crate::document::PdfDocument::register_blend_mode)
+/// and an [opacity](Opacity) with [PdfDocument::register_opacity](crate::document::PdfDocument::register_opacity)
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct GraphicStyles {
     pub line_width: Option<Pt>,
     pub fill_color: Option<Color>,
     pub outline_color: Option<Color>,
+    pub blend_mode: Option<BlendMode>,
+    pub opacity: Option<Opacity>,
 }
 impl RectangleStyleType for GraphicStyles {
     fn has_fill_color(&self) -> bool {
@@ -50,12 +57,18 @@ impl From<GraphicStyles> for PdfObject {
 impl PdfObjectType for GraphicStyles {
     fn write(
         self,
-        resources: &crate::document::PdfResources,
+        resources: &PdfResources,
         writer: &mut OperationWriter,
     ) -> Result<(), crate::TuxPdfError> {
         if let Some(width) = self.line_width {
             writer.add_operation(OperationKeys::SetLineWidth, vec![width.into()]);
         }
+        if let Some(blend_mode) = self.blend_mode {
+            write_blend_mode(blend_mode, resources, writer)?;
+        }
+        if let Some(opacity) = self.opacity {
+            write_opacity(opacity, resources, writer)?;
+        }
         let color_writer = ColorWriter {
             outline_color: self.outline_color.map(Cow::Owned),
             fill_color: self.fill_color.map(Cow::Owned),
@@ -65,26 +78,70 @@ impl PdfObjectType for GraphicStyles {
     }
 }
 
+/// Sets the graphics state with the given blend mode. Registered blend modes have a dedicated state
+fn write_blend_mode(
+    blend_mode: BlendMode,
+    resources: &PdfResources,
+    writer: &mut OperationWriter,
+) -> Result<(), crate::TuxPdfError> {
+    if !resources.ext_g_states.is_blend_mode_registered(&blend_mode) {
+        return Err(ResourceNotRegistered::BlendModeNotRegistered(blend_mode).into());
+    }
+    writer.add_operation(
+        OperationKeys::SetExtGState,
+        vec![blend_mode.dedicated_ext_g_state_id().into()],
+    );
+    Ok(())
+}
+
+/// Sets the graphics state with the given opacity. Registered opacities have a dedicated state
+fn write_opacity(
+    opacity: Opacity,
+    resources: &PdfResources,
+    writer: &mut OperationWriter,
+) -> Result<(), crate::TuxPdfError> {
+    if !resources.ext_g_states.is_opacity_registered(&opacity) {
+        return Err(ResourceNotRegistered::OpacityNotRegistered(opacity).into());
+    }
+    writer.add_operation(
+        OperationKeys::SetExtGState,
+        vec![opacity.dedicated_ext_g_state_id().into()],
+    );
+    Ok(())
+}
+
 #[derive(Debug, PartialEq, Clone, Default)]
 pub struct PartialGraphicStyles {
     pub line_width: Option<Pt>,
     pub fill_color: Option<Color>,
     pub outline_color: Option<Color>,
+    pub blend_mode: Option<BlendMode>,
+    pub opacity: Option<Opacity>,
 }
 impl IsEmpty for PartialGraphicStyles {
     fn is_empty(&self) -> bool {
-        self.line_width.is_none() && self.fill_color.is_none() && self.outline_color.is_none()
+        self.line_width.is_none()
+            && self.fill_color.is_none()
+            && self.outline_color.is_none()
+            && self.blend_mode.is_none()
+            && self.opacity.is_none()
     }
 }
 impl PdfObjectType for PartialGraphicStyles {
     fn write(
         self,
-        resources: &crate::document::PdfResources,
+        resources: &PdfResources,
         writer: &mut OperationWriter,
     ) -> Result<(), crate::TuxPdfError> {
         if let Some(width) = self.line_width {
             writer.add_operation(OperationKeys::SetLineWidth, vec![width.into()]);
         }
+        if let Some(blend_mode) = self.blend_mode {
+            write_blend_mode(blend_mode, resources, writer)?;
+        }
+        if let Some(opacity) = self.opacity {
+            write_opacity(opacity, resources, writer)?;
+        }
         let color_writer = ColorWriter {
             outline_color: self.outline_color.map(Cow::Owned),
             fill_color: self.fill_color.map(Cow::Owned),
@@ -114,6 +171,12 @@ impl PartialStruct for PartialGraphicStyles {
         if let Some(outline_color) = self.outline_color.clone() {
             new.outline_color = Some(outline_color);
         }
+        if let Some(blend_mode) = self.blend_mode {
+            new.blend_mode = Some(blend_mode);
+        }
+        if let Some(opacity) = self.opacity {
+            new.opacity = Some(opacity);
+        }
         Cow::Owned(new)
     }
 }
@@ -1,4 +1,4 @@
-use std::{mem, ops::Deref};
+use std::{borrow::Cow, mem, ops::Deref};
 
 use crate::{
     TuxPdfError,
@@ -18,9 +18,22 @@ use tracing::debug;
 use tux_pdf_low::types::Object;
 
 use super::{
-    OperationWriter, TextBlockState, TextModifier, TextOperations, TextStyle, write_modifiers,
+    OperationWriter, ParagraphStyle, TabAlignment, TabStop, TextBlockState, TextModifier,
+    TextOperations, TextOverflow, TextStyle, write_modifiers,
 };
 
+/// Spaces text is never wrapped at. No-break space, figure space and narrow no-break space
+pub const NON_BREAKING_SPACES: [char; 3] = ['\u{A0}', '\u{2007}', '\u{202F}'];
+/// Added to the end of text cut by [TextOverflow::Ellipsis]
+pub const ELLIPSIS: char = '\u{2026}';
+/// Marks where text can be wrapped without drawing anything
+pub const ZERO_WIDTH_SPACE: char = '\u{200B}';
+/// Marks where a word can be wrapped. Only drawn, as a hyphen, if the word is wrapped there
+pub const SOFT_HYPHEN: char = '\u{AD}';
+/// Characters that only mark where text can be wrapped and are never drawn themselves
+pub(crate) fn is_break_marker(c: char) -> bool {
+    c == ZERO_WIDTH_SPACE || c == SOFT_HYPHEN
+}
 /// Tracks the absolute cursor position during text rendering.
 ///
 /// Used when emoji images need to be placed inline, requiring knowledge
@@ -42,6 +55,25 @@ pub(crate) struct TextCursor {
 pub struct TextItem {
     pub text: String,
     pub modifiers: Vec<TextModifier>,
+    /// Space added between individual characters of the text
+    pub adjustments: Vec<GlyphAdjustment>,
+}
+/// Space added before one character of a [TextItem]
+///
+/// Written as a number in a [TextOperations::ShowTextAdjusted] array
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlyphAdjustment {
+    /// Index of the character in the text. The number of characters to add the space after the last one
+    pub index: usize,
+    pub space: GlyphSpace,
+}
+/// The size of a [GlyphAdjustment]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GlyphSpace {
+    /// Thousandths of the font size. Negative values move the characters closer together
+    Thousandths(f32),
+    /// Widths of the digit zero. The same as a figure space in most fonts
+    Figures(f32),
 }
 impl From<String> for TextItem {
     fn from(text: String) -> Self {
@@ -57,6 +89,7 @@ impl TextItem {
         Self {
             text: text.into(),
             modifiers: Vec::new(),
+            adjustments: Vec::new(),
         }
     }
     pub fn with_font_size(mut self, size: Pt) -> Self {
@@ -80,6 +113,114 @@ impl TextItem {
         self.modifiers.push(TextModifier::WordSpacing(spacing));
         self
     }
+    /// See [TextModifier::Tracking]
+    pub fn with_tracking(mut self, tracking: f32) -> Self {
+        self.modifiers.push(TextModifier::Tracking(tracking));
+        self
+    }
+    /// Adds space before the character at `index`
+    pub fn with_adjustment(mut self, index: usize, space: GlyphSpace) -> Self {
+        self.adjustments.push(GlyphAdjustment { index, space });
+        self
+    }
+    /// Pads the start of the text to the width of `figures` digits
+    ///
+    /// Right aligns numbers in a column without padding them with spaces.
+    /// Every character is counted as one digit
+    pub fn with_figure_alignment(self, figures: usize) -> Self {
+        let padding = figures.saturating_sub(self.text.chars().count());
+        if padding == 0 {
+            return self;
+        }
+        self.with_adjustment(0, GlyphSpace::Figures(padding as f32))
+    }
+    /// The adjustments of the characters from `start` to `end` with the indices moved to start at `start`
+    fn adjustments_between(&self, start: usize, end: usize) -> Vec<GlyphAdjustment> {
+        self.adjustments
+            .iter()
+            .filter(|adjustment| adjustment.index >= start && adjustment.index <= end)
+            .map(|adjustment| GlyphAdjustment {
+                index: adjustment.index - start,
+                space: adjustment.space,
+            })
+            .collect()
+    }
+    /// Renders the text as superscript. See [TextModifier::Superscript]
+    ///
+    /// Apply after [Self::with_font_size] so the scale is relative to the new size
+    pub fn superscript(mut self) -> Self {
+        self.modifiers.push(TextModifier::Superscript);
+        self
+    }
+    /// Renders the text as subscript. See [TextModifier::Subscript]
+    ///
+    /// Apply after [Self::with_font_size] so the scale is relative to the new size
+    pub fn subscript(mut self) -> Self {
+        self.modifiers.push(TextModifier::Subscript);
+        self
+    }
+    /// Splits the text at each tab. The items keep the modifiers
+    fn split_tabs(self) -> Vec<Self> {
+        let mut start = 0;
+        self.text
+            .split('\t')
+            .map(|text| {
+                let length = text.chars().count();
+                let item = Self {
+                    text: text.to_owned(),
+                    modifiers: self.modifiers.clone(),
+                    adjustments: self.adjustments_between(start, start + length),
+                };
+                // Skips the tab
+                start += length + 1;
+                item
+            })
+            .collect()
+    }
+    /// Splits the text at each tab. Each tab is kept as its own item
+    fn separate_tabs(self) -> Vec<Self> {
+        if !self.text.contains('\t') {
+            return vec![self];
+        }
+        let modifiers = self.modifiers.clone();
+        let mut items = Vec::new();
+        for (index, part) in self.split_tabs().into_iter().enumerate() {
+            if index > 0 {
+                items.push(Self {
+                    text: "\t".to_owned(),
+                    modifiers: modifiers.clone(),
+                    adjustments: Vec::new(),
+                });
+            }
+            if !part.text.is_empty() {
+                items.push(part);
+            }
+        }
+        items
+    }
+    /// Removes the characters past the available width
+    ///
+    /// Returns the width of the text that is kept and if any characters were removed
+    fn cut_off_characters(
+        &mut self,
+        available_width: Pt,
+        current_state: &TextBlockState,
+    ) -> Result<(Pt, bool), TuxPdfError> {
+        let state = state_from_modifiers(&self.modifiers, current_state)?;
+        let mut width = Pt::default();
+        for (char_index, (index, c)) in self.text.char_indices().enumerate() {
+            let char_width = state.size_of_char(c).unwrap_or_default().width
+                + state.space_before(char_index, &self.adjustments);
+            if width + char_width > available_width {
+                self.text.truncate(index);
+                self.adjustments
+                    .retain(|adjustment| adjustment.index < char_index);
+                return Ok((width, true));
+            }
+            width += char_width;
+        }
+        Ok((width, false))
+    }
     /// Splits the text into two items once the available width is reached
     ///
     /// Returns the remaining text if the text was split
@@ -90,43 +231,71 @@ impl TextItem {
     ) -> Result<(Pt, Option<Self>), TuxPdfError> {
         let state = state_from_modifiers(&self.modifiers, current_state)?;
 
-        let text_size = state
-            .font_type
-            .calculate_size_of_text(&self.text, state.as_ref());
+        let text_size = state.size_of_adjusted_text(&self.text, &self.adjustments);
         if text_size.width < availalble_width {
             return Ok((text_size.width, None));
         }
 
-        let mut ending_index: Option<(usize, usize)> = Option::None;
+        let hyphen_width = state.size_of_char('-').unwrap_or_default().width;
+        let mut line_break: Option<LineBreak> = Option::None;
         let mut width = Pt::default();
         let mut last_good_break = None;
-        for (index, c) in self.text.chars().enumerate() {
-            let char_size = current_state
-                .font_type
-                .size_of_char(c, state.as_ref())
-                .unwrap_or_default();
-            if width + char_size.width > availalble_width {
-                if let Some(last_good_break) = last_good_break {
-                    ending_index = Some((last_good_break, last_good_break + 1));
-                    break;
-                } else {
-                    ending_index = Some((index, index));
-                    break;
-                }
+        // Byte indices so text outside of ASCII is split on character boundaries
+        for (char_index, (index, c)) in self.text.char_indices().enumerate() {
+            let char_size = state.size_of_char(c).unwrap_or_default();
+            let char_width = char_size.width + state.space_before(char_index, &self.adjustments);
+            if width + char_width > availalble_width {
+                line_break = Some(last_good_break.unwrap_or(LineBreak {
+                    end: index,
+                    start: index,
+                    hyphen: false,
+                }));
+                break;
             }
-            if c.is_whitespace() {
-                last_good_break = Some(index);
+            let next_index = index + c.len_utf8();
+            if c == SOFT_HYPHEN {
+                // Only a break if the hyphen fits on the line
+                if width + hyphen_width <= availalble_width {
+                    last_good_break = Some(LineBreak {
+                        end: index,
+                        start: next_index,
+                        hyphen: true,
+                    });
+                }
+            } else if c == ZERO_WIDTH_SPACE
+                || (c.is_whitespace() && !NON_BREAKING_SPACES.contains(&c))
+            {
+                last_good_break = Some(LineBreak {
+                    end: index,
+                    start: next_index,
+                    hyphen: false,
+                });
             }
-            width += char_size.width;
+            width += char_width;
         }
-        let (end, start) = ending_index.unwrap_or((self.text.len(), self.text.len()));
+        let LineBreak { end, start, hyphen } = line_break.unwrap_or(LineBreak {
+            end: self.text.len(),
+            start: self.text.len(),
+            hyphen: false,
+        });
 
         let remaining_text = self.text[start..].to_string();
         debug!("Remaining Text: {:?}", remaining_text);
-        self.text = self.text[..end].to_string();
+        let end_char = self.text[..end].chars().count();
+        let start_char = self.text[..start].chars().count();
+        let remaining_adjustments =
+            self.adjustments_between(start_char, start_char + remaining_text.chars().count());
+        self.adjustments
+            .retain(|adjustment| adjustment.index < end_char);
+        self.text = if hyphen {
+            format!("{}-", &self.text[..end])
+        } else {
+            self.text[..end].to_string()
+        };
         let new_line = Some(Self {
             text: remaining_text,
             modifiers: self.modifiers.clone(),
+            adjustments: remaining_adjustments,
         });
         Ok((width, new_line))
     }
@@ -136,9 +305,7 @@ impl TextItem {
         current_state: &TextBlockState,
     ) -> Result<Size, TuxPdfError> {
         let state = state_from_modifiers(&self.modifiers, current_state)?;
-        Ok(state
-            .font_type
-            .calculate_size_of_text(&self.text, state.as_ref()))
+        Ok(state.size_of_adjusted_text(&self.text, &self.adjustments))
     }
     fn write(
         self,
@@ -146,7 +313,11 @@ impl TextItem {
         writer: &mut OperationWriter,
         cursor: &mut TextCursor,
     ) -> Result<Size, TuxPdfError> {
-        let Self { text, modifiers } = self;
+        let Self {
+            text,
+            modifiers,
+            adjustments,
+        } = self;
         let state = write_modifiers(modifiers, current_state, writer)?;
 
         debug!(?state, "Text State for Text Item");
@@ -159,17 +330,13 @@ impl TextItem {
             return Self::write_emoji_images(&text, &state, parsed_font, writer, cursor);
         }
 
-        let text_size = state
-            .font_type
-            .calculate_size_of_text(&text, state.as_ref());
-        let text = state.font_type.encode_text(&text);
-
-        writer.add_operation(
-            TextOperations::ShowText,
-            vec![Object::String(tux_pdf_low::types::PdfString::Hexadecimal(
-                text,
-            ))],
-        );
+        let mut text_size = Size::<Pt>::default();
+        for run in state.adjusted_font_runs(&text, &adjustments) {
+            let run_size = run.size(&state);
+            run.write(&state, writer);
+            text_size.width += run_size.width;
+            text_size.height = text_size.height.max(run_size.height);
+        }
 
         cursor.x += text_size.width;
         Ok(text_size)
@@ -250,7 +417,7 @@ impl TextItem {
                     vec![
                         Object::Real(1.0),
                         Object::Real(0.0),
-                        Object::Real(0.0),
+                        Object::Real(state.synthesis.skew()),
                         Object::Real(1.0),
                         cursor.origin_x.into(),
                         cursor.y.into(),
@@ -268,7 +435,10 @@ impl TextItem {
                 // Re-set font (will be overridden by Q if inside a modifier block)
                 writer.add_operation(
                     TextOperations::TextFont,
-                    vec![state.font.clone().into(), font_size.into()],
+                    vec![
+                        state.resources.fonts.resource_name(&state.font),
+                        font_size.into(),
+                    ],
                 );
             } else {
                 // No cached image, fall back to text rendering
@@ -292,6 +462,16 @@ impl TextItem {
         })
     }
 }
+/// Where a text item is split when it is wrapped
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LineBreak {
+    /// The end of the text kept on the line
+    end: usize,
+    /// The start of the text moved to the next line
+    start: usize,
+    /// A hyphen is drawn at the end of the line
+    hyphen: bool,
+}
 #[derive(Debug, Clone, PartialEq)]
 enum LineMaxWidth {
     LeftoverSpace(Pt),
@@ -304,8 +484,22 @@ enum LineMaxWidth {
 pub struct TextLine {
     pub items: Vec<TextItem>,
     pub modifiers: Vec<TextModifier>,
+    /// When set this line starts a new paragraph
+    ///
+    /// If the line is wrapped only the first line receives the indent
+    pub paragraph: Option<ParagraphStyle>,
 }
 impl TextLine {
+    /// Marks the line as the start of a paragraph using the paragraph style of the [TextStyle]
+    pub fn as_paragraph(mut self) -> Self {
+        self.paragraph.get_or_insert_default();
+        self
+    }
+    /// Marks the line as the start of a paragraph with its own paragraph style
+    pub fn with_paragraph_style(mut self, style: ParagraphStyle) -> Self {
+        self.paragraph = Some(style);
+        self
+    }
     /// Adds an item to the line using a builder pattern
     ///
     /// If you want add an item without that pattern use `items.push(item)`
@@ -316,53 +510,209 @@ impl TextLine {
     pub(super) fn write(
         self,
         current_state: &TextBlockState,
+        tab_stops: &[TabStop],
         writer: &mut OperationWriter,
         cursor: &mut TextCursor,
     ) -> Result<Size, TuxPdfError> {
         let mut line_size: Size = Size::default();
-        write_modifiers(self.modifiers, current_state, writer)?;
+        let gaps = self.tab_gaps(tab_stops, current_state)?;
+        let line_state = write_modifiers(self.modifiers, current_state, writer)?;
 
+        let has_tabs = !gaps.is_empty();
+        let mut gaps = gaps.into_iter();
         for item in self.items {
-            let restore = if !item.modifiers.is_empty() {
-                writer.push_empty_op(OperationKeys::SaveGraphicsState);
-                true
+            let items = if has_tabs {
+                item.split_tabs()
             } else {
-                false
+                vec![item]
             };
-            let item_size = item.write(current_state, writer, cursor)?;
-            if restore {
-                writer.push_empty_op(OperationKeys::RestoreGraphicsState);
+            for (index, item) in items.into_iter().enumerate() {
+                if index > 0
+                    && let Some(gap) = gaps.next()
+                {
+                    line_size.width += gap.write(&line_state, writer, cursor)?;
+                }
+                let restore = if !item.modifiers.is_empty() {
+                    writer.push_empty_op(OperationKeys::SaveGraphicsState);
+                    true
+                } else {
+                    false
+                };
+                let item_size = item.write(current_state, writer, cursor)?;
+                if restore {
+                    writer.push_empty_op(OperationKeys::RestoreGraphicsState);
+                }
+                line_size.width += item_size.width;
+                line_size.height = line_size.height.max(item_size.height);
             }
-            line_size.width += item_size.width;
-            line_size.height = line_size.height.max(item_size.height);
         }
 
         Ok(line_size)
     }
-    fn calculate_size_of_text(&self, current_state: &TextBlockState) -> Result<Size, TuxPdfError> {
+    fn calculate_size_of_text(
+        &self,
+        current_state: &TextBlockState,
+        tab_stops: &[TabStop],
+    ) -> Result<Size, TuxPdfError> {
+        let gaps = self.tab_gaps(tab_stops, current_state)?;
         let state = state_from_modifiers(&self.modifiers, current_state)?;
         let mut line_size: Size = Size::default();
         for item in &self.items {
-            let item_size = item.calculate_size_of_text(state.as_ref())?;
+            let item_size = if gaps.is_empty() {
+                item.calculate_size_of_text(state.as_ref())?
+            } else {
+                let mut size = Size::<Pt>::default();
+                for part in item.clone().split_tabs() {
+                    let part_size = part.calculate_size_of_text(state.as_ref())?;
+                    size.width += part_size.width;
+                    size.height = size.height.max(part_size.height);
+                }
+                size
+            };
             line_size.width += item_size.width;
             line_size.height = line_size.height.max(item_size.height);
         }
+        line_size.width += gaps.iter().map(|gap| gap.width).sum::<Pt>();
         Ok(line_size)
     }
+    /// The space written in place of each tab in the line
+    ///
+    /// Empty if there are no tab stops
+    fn tab_gaps(
+        &self,
+        tab_stops: &[TabStop],
+        current_state: &TextBlockState,
+    ) -> Result<Vec<TabGap>, TuxPdfError> {
+        if tab_stops.is_empty() || !self.items.iter().any(|item| item.text.contains('\t')) {
+            return Ok(Vec::new());
+        }
+        let state = state_from_modifiers(&self.modifiers, current_state)?;
+        let mut segments: Vec<Vec<TabSegmentPart>> = vec![Vec::new()];
+        for item in &self.items {
+            let item_state = state_from_modifiers(&item.modifiers, state.as_ref())?;
+            for (index, part) in item.clone().split_tabs().into_iter().enumerate() {
+                if index > 0 {
+                    segments.push(Vec::new());
+                }
+                if let Some(segment) = segments.last_mut() {
+                    segment.push((part, item_state.clone()));
+                }
+            }
+        }
+        let mut x = segment_width(&segments[0], None);
+        let mut gaps = Vec::with_capacity(segments.len() - 1);
+        for segment in &segments[1..] {
+            let width = segment_width(segment, None);
+            // The nearest stop after the text. The stops do not have to be sorted
+            let next_stop = tab_stops
+                .iter()
+                .filter(|stop| stop.position > x)
+                .min_by(|a, b| a.position.0.total_cmp(&b.position.0));
+            let gap = match next_stop {
+                Some(stop) => {
+                    let offset = match stop.alignment {
+                        TabAlignment::Left => Pt::default(),
+                        TabAlignment::Right => width,
+                        TabAlignment::Center => Pt(width.0 / 2.0),
+                        TabAlignment::Decimal(separator) => segment_width(segment, Some(separator)),
+                    };
+                    TabGap {
+                        width: (stop.position - offset - x).max(Pt::default()),
+                        leader: stop.leader,
+                    }
+                }
+                // Past the last stop a tab is a space
+                None => TabGap {
+                    width: state.size_of_char(' ').unwrap_or_default().width,
+                    leader: None,
+                },
+            };
+            x += gap.width + width;
+            gaps.push(gap);
+        }
+        Ok(gaps)
+    }
 
+    /// Removes the text past `max_width`
+    ///
+    /// With `ellipsis` the line ends with [ELLIPSIS] if it was cut or `force_ellipsis` is set
+    fn truncate(
+        mut self,
+        max_width: Pt,
+        current_state: &TextBlockState,
+        ellipsis: bool,
+        force_ellipsis: bool,
+    ) -> Result<Self, TuxPdfError> {
+        let width = self.calculate_size_of_text(current_state, &[])?.width;
+        let cut = width > max_width;
+        if !cut && (!ellipsis || !force_ellipsis) {
+            return Ok(self);
+        }
+        let mut available_width = max_width;
+        if ellipsis {
+            available_width -= current_state.size_of_text(&ELLIPSIS.to_string()).width;
+        }
+        for mut item in mem::take(&mut self.items) {
+            let (item_width, was_cut) = item.cut_off_characters(available_width, current_state)?;
+            available_width -= item_width;
+            self.items.push(item);
+            if was_cut {
+                break;
+            }
+        }
+        if ellipsis {
+            match self.items.last_mut() {
+                Some(last) => {
+                    last.text.truncate(last.text.trim_end().len());
+                    last.text.push(ELLIPSIS);
+                }
+                None => self.items.push(TextItem::new(ELLIPSIS.to_string())),
+            }
+        }
+        Ok(self)
+    }
+    /// `indent` is removed from the available width of the first line only
+    ///
+    /// Tabs take up the space to their [TabStop]. A tab that does not fit moves to the next line with the text after it
     fn apply_max_width(
         mut self,
         max_width: Pt,
+        indent: Pt,
+        tab_stops: &[TabStop],
         current_state: &TextBlockState,
         lines: &mut Vec<TextLine>,
     ) -> Result<LineMaxWidth, TuxPdfError> {
-        let mut item_iterator = mem::take(&mut self.items).into_iter();
+        let gaps = self.tab_gaps(tab_stops, current_state)?;
+        let items = mem::take(&mut self.items);
+        let items = if gaps.is_empty() {
+            items
+        } else {
+            // Each tab becomes its own item so the width of its gap can be removed from the available width
+            items
+                .into_iter()
+                .flat_map(TextItem::separate_tabs)
+                .collect()
+        };
+        let mut gaps = gaps.into_iter();
+        let mut item_iterator = items.into_iter();
         // Read Line Items until the max width is reached
         let mut item = item_iterator.next();
         let mut extra_items = Vec::new();
 
-        let mut current_max_width = max_width;
+        let mut current_max_width = max_width - indent;
         while let Some(mut current_item) = item {
+            if current_item.text == "\t"
+                && let Some(gap) = gaps.next()
+            {
+                if gap.width > current_max_width && !self.items.is_empty() {
+                    extra_items.push(current_item);
+                    break;
+                }
+                current_max_width -= gap.width;
+                self.items.push(current_item);
+                item = item_iterator.next();
+                continue;
+            }
             let (width, new_item) =
                 current_item.cut_off_at_max(current_max_width, current_state)?;
             current_max_width -= width;
@@ -385,12 +735,83 @@ impl TextLine {
             let new_line = TextLine {
                 items: extra_items,
                 modifiers,
+                paragraph: None,
             };
-            new_line.apply_max_width(max_width, current_state, lines)?;
+            new_line.apply_max_width(max_width, Pt::default(), tab_stops, current_state, lines)?;
             Ok(LineMaxWidth::SplitLines)
         }
     }
 }
+/// Part of the text between two tabs with the state it is drawn with
+type TabSegmentPart<'state, 'resources> = (TextItem, Cow<'state, TextBlockState<'resources>>);
+/// The width of the segment up to the separator or the whole segment if it does not contain it
+fn segment_width(segment: &[TabSegmentPart], separator: Option<char>) -> Pt {
+    let mut width = Pt::default();
+    for (item, state) in segment {
+        if let Some(index) = separator.and_then(|separator| item.text.find(separator)) {
+            let adjustments = item.adjustments_between(0, item.text[..index].chars().count());
+            return width
+                + state
+                    .size_of_adjusted_text(&item.text[..index], &adjustments)
+                    .width;
+        }
+        width += state
+            .size_of_adjusted_text(&item.text, &item.adjustments)
+            .width;
+    }
+    width
+}
+/// The space a tab is replaced with
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TabGap {
+    width: Pt,
+    leader: Option<char>,
+}
+impl TabGap {
+    /// Fills the gap with the leader then moves the rest of the way with [TextOperations::ShowTextAdjusted]
+    ///
+    /// Returns the width of the gap
+    fn write(
+        self,
+        state: &TextBlockState,
+        writer: &mut OperationWriter,
+        cursor: &mut TextCursor,
+    ) -> Result<Pt, TuxPdfError> {
+        let mut remaining = self.width;
+        let leaders = self.leader.and_then(|leader| {
+            let leader_width = state
+                .size_of_char(leader)
+                .map(|size| size.width)
+                .filter(|width| *width > Pt::default())?;
+            let count = (self.width.0 / leader_width.0).floor();
+            (count >= 1.0).then_some((leader, count as usize, Pt(leader_width.0 * count)))
+        });
+        if let Some((_, _, leaders_width)) = leaders {
+            remaining -= leaders_width;
+        }
+        // Moved before the leaders so they end at the text after the tab
+        if remaining > Pt::default() && state.font_size > Pt::default() {
+            writer.add_operation(
+                TextOperations::ShowTextAdjusted,
+                vec![Object::Array(vec![Object::Real(
+                    -remaining.0 * 1000.0 / state.font_size.0,
+                )])],
+            );
+            cursor.x += remaining;
+        }
+        if let Some((leader, count, _)) = leaders {
+            TextItem::new(leader.to_string().repeat(count)).write(state, writer, cursor)?;
+        }
+        Ok(self.width)
+    }
+}
+/// Paragraph spacing resolved for a single line
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(super) struct LineSpacing {
+    /// Space between this line and the line above it on top of the normal line advance
+    pub space_above: Pt,
+    pub indent: Pt,
+}
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct TextBlockContent(pub Vec<TextLine>);
 impl TextBlockContent {
@@ -401,17 +822,143 @@ impl TextBlockContent {
         self.0.push(line.into());
         self
     }
+    /// Adds a line that starts a new paragraph
+    ///
+    /// Set [TextStyle::max_width] to wrap the paragraph
+    pub fn add_paragraph(mut self, line: impl Into<TextLine>) -> Self {
+        self.0.push(line.into().as_paragraph());
+        self
+    }
+    /// The text of every line joined with new lines
+    pub fn text(&self) -> String {
+        self.0
+            .iter()
+            .map(|line| {
+                line.items
+                    .iter()
+                    .map(|item| item.text.as_str())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+    /// The space above and indent of each line based on the paragraphs in the block
+    pub(super) fn line_spacings(&self, defaults: Option<&ParagraphStyle>) -> Vec<LineSpacing> {
+        let mut spacings = Vec::with_capacity(self.0.len());
+        // Space after the paragraph the previous line belongs to
+        let mut space_after_previous = Pt::default();
+        for (index, line) in self.0.iter().enumerate() {
+            let Some(paragraph) = &line.paragraph else {
+                spacings.push(LineSpacing::default());
+                continue;
+            };
+            let paragraph = paragraph.with_defaults(defaults);
+            let space_above = if index == 0 {
+                Pt::default()
+            } else {
+                space_after_previous + paragraph.space_before.unwrap_or_default()
+            };
+            space_after_previous = paragraph.space_after.unwrap_or_default();
+            spacings.push(LineSpacing {
+                space_above,
+                indent: paragraph.first_line_indent.unwrap_or_default(),
+            });
+        }
+        spacings
+    }
 
+    /// Wraps or cuts the lines at `max_width` then removes the lines past [TextStyle::max_height] based on [TextStyle::overflow]
+    pub(super) fn apply_limits(
+        &mut self,
+        max_width: Option<Pt>,
+        style: &TextStyle,
+        current_state: &TextBlockState,
+    ) -> Result<(), TuxPdfError> {
+        let ellipsis = style.overflow == TextOverflow::Ellipsis;
+        if let Some(max_width) = max_width {
+            if style.overflow.is_visible() || style.max_height.is_some() {
+                self.apply_max_width_inner(max_width, style, current_state)?;
+            } else {
+                let indents = self.line_spacings(style.paragraph.as_ref());
+                let old_lines = mem::take(&mut self.0);
+                for (line, spacing) in old_lines.into_iter().zip(indents) {
+                    let line =
+                        line.truncate(max_width - spacing.indent, current_state, ellipsis, false)?;
+                    self.0.push(line);
+                }
+            }
+        }
+        if let Some(max_height) = style.max_height
+            && !style.overflow.is_visible()
+        {
+            self.apply_max_height(max_height, max_width, style, current_state)?;
+        }
+        Ok(())
+    }
+    /// Removes the lines that do not fit in `max_height`. The first line is always kept
+    fn apply_max_height(
+        &mut self,
+        max_height: Pt,
+        max_width: Option<Pt>,
+        style: &TextStyle,
+        current_state: &TextBlockState,
+    ) -> Result<(), TuxPdfError> {
+        let spacings = self.line_spacings(style.paragraph.as_ref());
+        let mut height = Pt::default();
+        let mut fitting_lines = self.0.len();
+        for (index, (line, spacing)) in self.0.iter().zip(&spacings).enumerate() {
+            let line_height = line
+                .calculate_size_of_text(current_state, &style.tab_stops)?
+                .height;
+            height += if index == 0 {
+                line_height
+            } else {
+                style.line_advance(line_height) + spacing.space_above
+            };
+            if height > max_height {
+                fitting_lines = index.max(1);
+                break;
+            }
+        }
+        if fitting_lines == self.0.len() {
+            return Ok(());
+        }
+        self.0.truncate(fitting_lines);
+        if style.overflow == TextOverflow::Ellipsis
+            && let Some(last_line) = self.0.pop()
+        {
+            let indent = spacings[fitting_lines - 1].indent;
+            let max_width = max_width.map(|max_width| max_width - indent);
+            let line =
+                last_line.truncate(max_width.unwrap_or(Pt(f32::MAX)), current_state, true, true)?;
+            self.0.push(line);
+        }
+        Ok(())
+    }
     pub(super) fn apply_max_width_inner(
         &mut self,
         max_width: Pt,
+        style: &TextStyle,
         current_state: &TextBlockState,
     ) -> Result<(), TuxPdfError> {
         let old_lines = mem::take(&mut self.0);
 
         for line in old_lines {
-            let current_max_width = max_width;
-            let result = line.apply_max_width(current_max_width, current_state, &mut self.0)?;
+            let indent = line
+                .paragraph
+                .and_then(|paragraph| {
+                    paragraph
+                        .with_defaults(style.paragraph.as_ref())
+                        .first_line_indent
+                })
+                .unwrap_or_default();
+            let result = line.apply_max_width(
+                max_width,
+                indent,
+                &style.tab_stops,
+                current_state,
+                &mut self.0,
+            )?;
 
             debug!(?result, "Line Max Width Result");
         }
@@ -424,7 +971,7 @@ impl TextBlockContent {
         style: &TextStyle,
     ) -> Result<(), TuxPdfError> {
         let state = TextBlockState::new(&document.resources, style)?;
-        self.apply_max_width_inner(max_width, &state)?;
+        self.apply_limits(Some(max_width), style, &state)?;
         Ok(())
     }
 }
@@ -438,13 +985,30 @@ impl RenderSize for TextBlockContent {
         settings: &TextStyle,
     ) -> Result<Size, TuxPdfError> {
         let state = TextBlockState::new(&document.resources, settings)?;
-
+        self.size_with_state(&state, settings)
+    }
+}
+impl TextBlockContent {
+    /// The size of the lines as they are. Lines are not wrapped
+    pub(super) fn size_with_state(
+        &self,
+        state: &TextBlockState,
+        settings: &TextStyle,
+    ) -> Result<Size, TuxPdfError> {
+        let spacings = self.line_spacings(settings.paragraph.as_ref());
         let mut size: Size = Size::default();
+        // Skewed glyphs lean past the end of the line
+        let overhang = settings.synthesis.oblique_overhang(state.font_size);
 
-        for line in &self.0 {
-            let line_size = line.calculate_size_of_text(&state)?;
-            size.width = size.width.max(line_size.width);
-            size.height += line_size.height;
+        for (index, (line, spacing)) in self.0.iter().zip(spacings).enumerate() {
+            let line_size = line.calculate_size_of_text(state, &settings.tab_stops)?;
+            size.width = size.width.max(line_size.width + spacing.indent + overhang);
+            // The top line only adds its own height. Every other line moves the lines above it up
+            if index == 0 {
+                size.height += line_size.height;
+            } else {
+                size.height += settings.line_advance(line_size.height) + spacing.space_above;
+            }
         }
 
         Ok(size)
@@ -488,6 +1052,7 @@ impl From<String> for TextLine {
         Self {
             items: vec![TextItem::new(text)],
             modifiers: Vec::new(),
+            paragraph: None,
         }
     }
 }
@@ -501,6 +1066,7 @@ impl From<&str> for TextLine {
         Self {
             items: vec![TextItem::new(text)],
             modifiers: Vec::new(),
+            paragraph: None,
         }
     }
 }
@@ -509,6 +1075,7 @@ fn str_to_lines(text: &str) -> Vec<TextLine> {
         .map(|line| TextLine {
             items: vec![TextItem::new(line)],
             modifiers: Vec::new(),
+            paragraph: None,
         })
         .collect()
 }
@@ -0,0 +1,167 @@
+use std::borrow::Cow;
+
+use crate::{TuxPdfError, document::PdfResources, graphics::size::Size, units::Pt};
+
+use super::{TextBlockContent, TextBlockState, TextStyle};
+/// Font sizes closer than this are considered the same when searching for the size that fits
+const FIT_PRECISION: Pt = Pt(0.1);
+
+/// Changes the style of a [TextBlock](super::TextBlock) so its content fits in a box
+///
+/// ```rust
+/// use tux_pdf::{
+///     graphics::{size::Size, text::{TextBlock, TextFit}},
+///     units::Pt,
+/// };
+/// let name_tag = TextBlock::from("Bartholomew Fitzgerald").with_fit(TextFit::ShrinkToFit {
+///     size: Size::new(Pt(150.0), Pt(40.0)),
+///     min_size: Pt(8.0),
+///     shrink_line_spacing: true,
+/// });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextFit {
+    /// Reduces the font size until the content fits in `size`. Text that already fits is not changed
+    ///
+    /// The font size is never reduced below `min_size`. The text may overflow the box at that size.
+    /// Font sizes set with [TextModifier::FontSize](super::TextModifier::FontSize) are not changed
+    ShrinkToFit {
+        size: Size,
+        min_size: Pt,
+        /// Scales [TextStyle::line_spacing] and [TextStyle::leading] with the font size
+        shrink_line_spacing: bool,
+    },
+}
+impl TextFit {
+    /// The style the content is written with
+    pub(crate) fn fit_style<'style>(
+        &self,
+        content: &TextBlockContent,
+        resources: &PdfResources,
+        style: &'style TextStyle,
+    ) -> Result<Cow<'style, TextStyle>, TuxPdfError> {
+        match *self {
+            TextFit::ShrinkToFit {
+                size,
+                min_size,
+                shrink_line_spacing,
+            } => {
+                let fits = |font_size: Pt| -> Result<bool, TuxPdfError> {
+                    let style = scaled_style(style, font_size, shrink_line_spacing);
+                    let content_size = size_of_content(content, resources, &style)?;
+                    Ok(content_size.width <= size.width && content_size.height <= size.height)
+                };
+                if style.font_size <= min_size || fits(style.font_size)? {
+                    return Ok(Cow::Borrowed(style));
+                }
+                if fits(min_size)? {
+                    // The largest size that fits is between the two
+                    let mut fitting = min_size;
+                    let mut too_large = style.font_size;
+                    while too_large - fitting > FIT_PRECISION {
+                        let middle = Pt((fitting.0 + too_large.0) / 2.0);
+                        if fits(middle)? {
+                            fitting = middle;
+                        } else {
+                            too_large = middle;
+                        }
+                    }
+                    Ok(Cow::Owned(scaled_style(
+                        style,
+                        fitting,
+                        shrink_line_spacing,
+                    )))
+                } else {
+                    Ok(Cow::Owned(scaled_style(
+                        style,
+                        min_size,
+                        shrink_line_spacing,
+                    )))
+                }
+            }
+        }
+    }
+}
+fn scaled_style(style: &TextStyle, font_size: Pt, shrink_line_spacing: bool) -> TextStyle {
+    let mut scaled = style.clone();
+    scaled.font_size = font_size;
+    if shrink_line_spacing {
+        let scale = font_size.0 / style.font_size.0;
+        scaled.line_spacing = style.line_spacing.map(|spacing| Pt(spacing.0 * scale));
+        scaled.leading = style.leading.map(|leading| Pt(leading.0 * scale));
+    }
+    scaled
+}
+/// The size of the content after it is wrapped or cut to the limits of the style
+fn size_of_content(
+    content: &TextBlockContent,
+    resources: &PdfResources,
+    style: &TextStyle,
+) -> Result<Size, TuxPdfError> {
+    let state = TextBlockState::new(resources, style)?;
+    if style.max_width.is_none() && style.max_height.is_none() {
+        return content.size_with_state(&state, style);
+    }
+    let mut content = content.clone();
+    content.apply_limits(style.max_width, style, &state)?;
+    content.size_with_state(&state, style)
+}
+#[cfg(test)]
+mod tests {
+    use super::TextFit;
+    use crate::{
+        document::{BuiltinFont, PdfDocument},
+        graphics::{
+            LayerType, PdfPosition,
+            size::Size,
+            text::{TextBlock, TextStyle},
+        },
+        layouts::LayoutItemType,
+        page::{PdfPage, page_sizes::A4},
+        units::UnitType,
+    };
+
+    #[test]
+    fn shrink_to_fit() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Text Fit");
+        let courier = doc.font_map().register_builtin_font(BuiltinFont::Courier);
+        let style = TextStyle {
+            font_ref: courier,
+            font_size: 20f32.pt(),
+            leading: Some(24f32.pt()),
+            ..Default::default()
+        };
+        let fit = |width: f32, min_size: f32| TextFit::ShrinkToFit {
+            size: Size::new(width.pt(), 100f32.pt()),
+            min_size: min_size.pt(),
+            shrink_line_spacing: true,
+        };
+        // Ten Courier characters are 6pt wide for every point of font size
+        let mut block = TextBlock::from("abcdefghij").with_style(style.clone());
+        assert_eq!(block.calculate_size(&doc)?.width, 120f32.pt());
+
+        block = block.with_fit(fit(60.0, 4.0));
+        let fitted = block.calculate_size(&doc)?;
+        assert!(fitted.width <= 60f32.pt() && fitted.width > 59f32.pt());
+        let fitted_style = fit(60.0, 4.0).fit_style(&block.content, &doc.resources, &style)?;
+        assert!(fitted_style.leading < style.leading);
+
+        // Never smaller than the minimum size
+        block = block.with_fit(fit(30.0, 8.0));
+        assert_eq!(block.calculate_size(&doc)?.width, 48f32.pt());
+
+        // Text that fits is not changed
+        block = block.with_fit(fit(200.0, 8.0));
+        assert_eq!(block.calculate_size(&doc)?.width, 120f32.pt());
+
+        let mut page = PdfPage::new_from_page_size(A4);
+        page.add_to_layer(
+            block
+                .with_fit(fit(60.0, 4.0))
+                .with_position(PdfPosition::new(10f32.pt(), 10f32.pt())),
+        )?;
+        doc.add_page(page);
+        doc.write_into_pdf_document_writer()?;
+        Ok(())
+    }
+}
@@ -1,7 +1,11 @@
 mod content;
+mod fit;
+mod on_path;
 pub(crate) mod state;
 mod style;
 pub use content::*;
+pub use fit::*;
+pub use on_path::*;
 pub use style::*;
 
 use crate::{
@@ -12,6 +16,7 @@ use crate::{
 };
 use state::TextBlockState;
 use tracing::debug;
+use tux_pdf_low::types::Object;
 
 use super::{
     super::layouts::LayoutItemType,
@@ -42,7 +47,13 @@ impl LayoutItemType for TextBlock {
         &mut self,
         document: &crate::document::PdfDocument,
     ) -> Result<Size, TuxPdfError> {
-        self.content.render_size(document, &self.style)
+        match &self.style.fit {
+            Some(fit) => {
+                let style = fit.fit_style(&self.content, &document.resources, &self.style)?;
+                self.content.render_size(document, &style)
+            }
+            None => self.content.render_size(document, &self.style),
+        }
     }
     fn can_resize(&self) -> bool {
         true
@@ -112,42 +123,68 @@ impl TextBlock {
         self.style.font_size = font_size;
         self
     }
+    /// Sets [TextStyle::fit]. Replaced by [Self::with_style]
+    pub fn with_fit(mut self, fit: TextFit) -> Self {
+        self.style.fit = Some(fit);
+        self
+    }
+    /// Moves the start of the current line right by the indent
+    fn write_indent(indent: Pt, writer: &mut OperationWriter, cursor: &mut TextCursor) {
+        if indent == Pt::default() {
+            return;
+        }
+        writer.add_operation(
+            TextOperations::TextPosition,
+            PdfPosition {
+                x: indent,
+                y: Pt::default(),
+            }
+            .into(),
+        );
+        cursor.x += indent;
+        cursor.origin_x += indent;
+    }
     fn writer_many(
         lines: Vec<TextLine>,
+        spacings: Vec<LineSpacing>,
         current_state: TextBlockState,
-        line_spacing: Pt,
+        style: &TextStyle,
         writer: &mut OperationWriter,
         cursor: &mut TextCursor,
     ) -> Result<(), TuxPdfError> {
         writer.push_empty_op(OperationKeys::SaveGraphicsState);
         let start_x = cursor.x;
         // We reverse the array because the starting position is at the bottom left so we need to start from the bottom
-        let mut line_iterator = lines.into_iter().rev().peekable();
-        while let Some(line) = line_iterator.next() {
+        let mut line_iterator = lines.into_iter().zip(spacings).rev().peekable();
+        while let Some((line, spacing)) = line_iterator.next() {
+            Self::write_indent(spacing.indent, writer, cursor);
             let restore = if !line.modifiers.is_empty() {
                 writer.push_empty_op(OperationKeys::SaveGraphicsState);
                 true
             } else {
                 false
             };
-            let line_size = line.write(&current_state, writer, cursor)?;
+            let line_size = line.write(&current_state, &style.tab_stops, writer, cursor)?;
             if restore {
                 writer.push_empty_op(OperationKeys::RestoreGraphicsState);
             }
             if line_iterator.peek().is_some() {
-                let line_height = line_size.height + line_spacing;
+                let line_height = style.line_advance(line_size.height) + spacing.space_above;
 
                 debug!(?line_height, "Line Height");
+                // A skewed text matrix also moves the line sideways by the skew of the line height
+                let skew_offset = line_height * style.synthesis.skew();
                 writer.add_operation(
                     TextOperations::TextPosition,
                     PdfPosition {
-                        x: Pt::default(),
+                        x: -spacing.indent - skew_offset,
                         y: line_height,
                     }
                     .into(),
                 );
                 // Move cursor to next line start
                 cursor.x = start_x;
+                cursor.origin_x = start_x;
                 cursor.y += line_height;
             }
         }
@@ -158,10 +195,13 @@ impl TextBlock {
     fn write_one(
         current_state: TextBlockState,
         content: TextLine,
+        spacing: LineSpacing,
+        tab_stops: &[TabStop],
         writer: &mut OperationWriter,
         cursor: &mut TextCursor,
     ) -> Result<(), TuxPdfError> {
-        content.write(&current_state, writer, cursor)?;
+        Self::write_indent(spacing.indent, writer, cursor);
+        content.write(&current_state, tab_stops, writer, cursor)?;
         Ok(())
     }
     fn write_text(
@@ -171,16 +211,36 @@ impl TextBlock {
         resources: &PdfResources,
         writer: &mut OperationWriter,
     ) -> Result<(), TuxPdfError> {
+        // The text state survives ET so it is restored for the content after the block.
+        // Clipping text is not restored as that would also remove the clipping path
+        let clips = style.rendering_mode.is_some_and(|mode| mode.is_clip());
+        if !clips {
+            writer.push_empty_op(OperationKeys::SaveGraphicsState);
+        }
         writer.push_empty_op(TextOperations::BeginText);
-        writer.add_operation(TextOperations::TextPosition, position.into());
+        if style.synthesis.oblique {
+            writer.add_operation(
+                TextOperations::SetTextMatrix,
+                vec![
+                    Object::Real(1.0),
+                    Object::Real(0.0),
+                    Object::Real(style.synthesis.skew()),
+                    Object::Real(1.0),
+                    position.x.into(),
+                    position.y.into(),
+                ],
+            );
+        } else {
+            writer.add_operation(TextOperations::TextPosition, position.into());
+        }
 
         let writer_state = TextBlockState::new(resources, &style)?;
-        if let Some(max_width) = style.max_width {
-            lines.apply_max_width_inner(max_width, &writer_state)?;
+        if style.max_width.is_some() || style.max_height.is_some() {
+            lines.apply_limits(style.max_width, &style, &writer_state)?;
             debug!(?lines, "Lines after applying max width");
         }
-        let line_spacing = style.line_spacing.unwrap_or_default();
-        style.write(resources, writer)?;
+        let mut spacings = lines.line_spacings(style.paragraph.as_ref());
+        style.clone().write(resources, writer)?;
 
         let mut cursor = TextCursor {
             x: position.x,
@@ -190,13 +250,26 @@ impl TextBlock {
         };
 
         if lines.len() > 1 {
-            Self::writer_many(lines.0, writer_state, line_spacing, writer, &mut cursor)?;
+            Self::writer_many(lines.0, spacings, writer_state, &style, writer, &mut cursor)?;
         } else {
             let line = lines.0.remove(0);
-            Self::write_one(writer_state, line, writer, &mut cursor)?;
+            let spacing = spacings.remove(0);
+            Self::write_one(
+                writer_state,
+                line,
+                spacing,
+                &style.tab_stops,
+                writer,
+                &mut cursor,
+            )?;
         }
 
         writer.push_empty_op(TextOperations::EndText);
+        if clips {
+            style.reset_text_state(writer);
+        } else {
+            writer.push_empty_op(OperationKeys::RestoreGraphicsState);
+        }
         Ok(())
     }
 }
@@ -215,6 +288,10 @@ impl PdfObjectType for TextBlock {
         if content.is_empty() {
             return Ok(());
         }
+        let style = match style.fit {
+            Some(fit) => fit.fit_style(&content, resources, &style)?.into_owned(),
+            None => style,
+        };
         if draw_as_lines {
             todo!();
         } else {
@@ -244,24 +321,37 @@ operation_keys!(TextOperations => {
         WordSpace => "Tw",
         /// Text Rise
         TextRise => "Ts",
+        /// Text Rendering Mode
+        TextRenderingMode => "Tr",
         /// Text New Line
         TextNewLine => "T*",
         /// End Text
         EndText => "ET",
         /// Show Text
-        ShowText => "Tj"
+        ShowText => "Tj",
+        /// Show Text with individual glyph positioning. Numbers in the array move the next glyph left in thousandths of the font size
+        ShowTextAdjusted => "TJ"
 });
 #[cfg(test)]
 mod tests {
     use crate::{
-        document::{PdfDocument, owned_ttf_parser::OwnedPdfTtfFont},
-        graphics::{LayerType, PdfPosition},
+        document::{
+            BuiltinFont, BuiltinFontFallback, FontRef, PdfDocument, ResourceNotRegistered,
+            owned_ttf_parser::OwnedPdfTtfFont,
+        },
+        graphics::{LayerType, OperationWriter, PdfObjectType, PdfPosition, size::RenderSize},
         page::{PdfPage, page_sizes::A4},
         tests::{fonts_dir, init_logger},
-        units::UnitType,
+        units::{Pt, UnitType},
     };
 
-    use super::{TextBlock, TextStyle};
+    use tux_pdf_low::types::Object;
+
+    use super::{
+        GlyphSpace, ParagraphStyle, SCRIPT_FONT_SCALE, SMALL_CAPS_SCALE, TabStop, TextBlock,
+        TextBlockContent, TextBlockState, TextItem, TextLine, TextOverflow, TextRenderingMode,
+        TextStyle, TextSynthesis,
+    };
     #[test]
     fn max_width_test() -> anyhow::Result<()> {
         init_logger();
@@ -297,4 +387,559 @@ mod tests {
         pdf.save(&mut file)?;
         Ok(())
     }
+    #[test]
+    fn paragraph_spacing_size() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Paragraph Test");
+        let font_ref = doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        let style = TextStyle {
+            font_ref,
+            ..Default::default()
+        };
+        let plain = TextBlockContent::default()
+            .add_line("First paragraph")
+            .add_line("Second paragraph");
+        let plain_size = plain.render_size(&doc, &style)?;
+
+        let paragraphs = TextBlockContent::default()
+            .add_paragraph("First paragraph")
+            .add_line(
+                TextLine::from("Second paragraph")
+                    .with_paragraph_style(ParagraphStyle::default().with_space_before(4f32.pt())),
+            );
+        let paragraph_style = TextStyle {
+            paragraph: Some(
+                ParagraphStyle::default()
+                    .with_space_after(6f32.pt())
+                    .with_first_line_indent(20f32.pt()),
+            ),
+            ..style.clone()
+        };
+        let paragraph_size = paragraphs.render_size(&doc, &paragraph_style)?;
+        assert_eq!(paragraph_size.height, plain_size.height + 10f32.pt());
+        assert_eq!(paragraph_size.width, plain_size.width + 20f32.pt());
+
+        let leading_style = TextStyle {
+            leading: Some(30f32.pt()),
+            ..style
+        };
+        let first_line_height = TextBlockContent::from("First paragraph")
+            .render_size(&doc, &leading_style)?
+            .height;
+        let leading_size = plain.render_size(&doc, &leading_style)?;
+        assert_eq!(leading_size.height, first_line_height + 30f32.pt());
+        Ok(())
+    }
+    #[test]
+    fn superscript_uses_scaled_size() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Superscript Test");
+        let font_ref = doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        let style = TextStyle {
+            font_ref,
+            font_size: 12f32.pt(),
+            ..Default::default()
+        };
+        let base_width = TextBlockContent::from("x").render_size(&doc, &style)?.width;
+
+        let superscript = TextBlockContent::from(
+            TextLine::default()
+                .add_item(TextItem::new("x"))
+                .add_item(TextItem::new("x").superscript()),
+        );
+        let subscript = TextBlockContent::from(
+            TextLine::default()
+                .add_item(TextItem::new("x"))
+                .add_item(TextItem::new("x").with_font_size(20f32.pt()).subscript()),
+        );
+        assert_eq!(
+            superscript.render_size(&doc, &style)?.width,
+            base_width + base_width * SCRIPT_FONT_SCALE
+        );
+        // Scaled relative to the font size set before the subscript
+        assert_eq!(
+            subscript.render_size(&doc, &style)?.width,
+            base_width + base_width * (20f32 / 12f32) * SCRIPT_FONT_SCALE
+        );
+        Ok(())
+    }
+    #[test]
+    fn synthesis_size() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Synthesis Test");
+        let font_ref = doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        let style = TextStyle {
+            font_ref,
+            font_size: 12f32.pt(),
+            ..Default::default()
+        };
+        let plain = TextBlockContent::from("Hello").render_size(&doc, &style)?;
+
+        let bold_style = TextStyle {
+            synthesis: TextSynthesis::default().with_bold(),
+            ..style.clone()
+        };
+        let bold = TextBlockContent::from("Hello").render_size(&doc, &bold_style)?;
+        let stroke = bold_style.synthesis.bold_stroke_width(12f32.pt());
+        assert!((bold.width - (plain.width + stroke * 5f32)).0.abs() < 0.001);
+
+        let oblique_style = TextStyle {
+            synthesis: TextSynthesis::default().with_oblique(),
+            ..style.clone()
+        };
+        let oblique = TextBlockContent::from("Hello").render_size(&doc, &oblique_style)?;
+        let overhang = oblique_style.synthesis.oblique_overhang(12f32.pt());
+        assert!((oblique.width - (plain.width + overhang)).0.abs() < 0.001);
+
+        // Lowercase letters are drawn as smaller capitals
+        let small_caps_style = TextStyle {
+            synthesis: TextSynthesis::default().with_small_caps(),
+            ..style.clone()
+        };
+        let small_caps = TextBlockContent::from("Hello").render_size(&doc, &small_caps_style)?;
+        let capital = TextBlockContent::from("H").render_size(&doc, &style)?;
+        let scaled_capitals = TextBlockContent::from("ELLO").render_size(
+            &doc,
+            &TextStyle {
+                font_size: 12f32.pt() * SMALL_CAPS_SCALE,
+                ..style
+            },
+        )?;
+        assert!(
+            (small_caps.width - (capital.width + scaled_capitals.width))
+                .0
+                .abs()
+                < 0.001
+        );
+        Ok(())
+    }
+    #[test]
+    fn text_state_is_restored() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Text State Test");
+        let font_ref = doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        let text_block = TextBlock::from("Outline").with_style(TextStyle {
+            font_ref,
+            rendering_mode: Some(TextRenderingMode::Stroke),
+            character_spacing: Some(1f32.pt()),
+            ..Default::default()
+        });
+        let mut writer = OperationWriter::default();
+        text_block.write(&doc.resources, &mut writer)?;
+        let operations = writer.operations();
+        let operators: Vec<&str> = operations
+            .iter()
+            .map(|operation| AsRef::<str>::as_ref(&operation.operation))
+            .collect();
+        assert_eq!(&operators[..2], &["q", "BT"]);
+        assert_eq!(&operators[operators.len() - 2..], &["ET", "Q"]);
+        Ok(())
+    }
+    #[test]
+    fn synthesis_operations() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Synthesis Test");
+        let font_ref = doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        let text_block = TextBlock::from("Hi").with_style(TextStyle {
+            font_ref,
+            font_size: 10f32.pt(),
+            synthesis: TextSynthesis::default()
+                .with_bold()
+                .with_oblique()
+                .with_small_caps(),
+            ..Default::default()
+        });
+        let mut writer = OperationWriter::default();
+        text_block.write(&doc.resources, &mut writer)?;
+        let operations = writer.operations();
+        let operators: Vec<&str> = operations
+            .iter()
+            .map(|operation| AsRef::<str>::as_ref(&operation.operation))
+            .collect();
+        assert_eq!(operators[2], "Tm");
+        for operator in ["Tr", "w", "Tc"] {
+            assert!(operators.contains(&operator), "Missing {operator}");
+        }
+        // The font of the block then the switch to the small caps size and back
+        assert_eq!(
+            operators
+                .iter()
+                .filter(|operator| **operator == "Tj")
+                .count(),
+            2
+        );
+        assert_eq!(
+            operators
+                .iter()
+                .filter(|operator| **operator == "Tf")
+                .count(),
+            3
+        );
+        Ok(())
+    }
+    #[test]
+    fn synthetic_bold_does_not_leak() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Synthesis Leak Test");
+        let font_ref = doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        let bold = TextBlock::from("Bold").with_style(TextStyle {
+            font_ref: font_ref.clone(),
+            synthesis: TextSynthesis::default().with_bold(),
+            ..Default::default()
+        });
+        let regular = TextBlock::from("Regular").with_style(TextStyle {
+            font_ref,
+            ..Default::default()
+        });
+        let mut writer = OperationWriter::default();
+        bold.write(&doc.resources, &mut writer)?;
+        regular.write(&doc.resources, &mut writer)?;
+        let operations = writer.operations();
+        // Without a fill color the outline is black
+        let outline = operations
+            .iter()
+            .find(|op| op.operation == "RG")
+            .expect("Outline color is set");
+        assert_eq!(
+            outline.arguments,
+            vec![Object::Real(0.0), Object::Real(0.0), Object::Real(0.0)]
+        );
+        // The regular block starts after the bold state is restored
+        let restore = operations
+            .iter()
+            .position(|op| op.operation == "Q")
+            .expect("Bold text state is restored");
+        for operator in ["Tr", "w", "Tc"] {
+            assert!(
+                !operations[restore..]
+                    .iter()
+                    .any(|op| op.operation == operator),
+                "{operator} is set for the regular text"
+            );
+        }
+        Ok(())
+    }
+    #[test]
+    fn tracking_writes_adjusted_text() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Tracking Test");
+        let font_ref = doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        let style = TextStyle {
+            font_ref,
+            font_size: 10f32.pt(),
+            ..Default::default()
+        };
+        let plain = TextBlockContent::from("AB").render_size(&doc, &style)?;
+        let tracked_style = TextStyle {
+            tracking: Some(100f32),
+            ..style.clone()
+        };
+        let tracked = TextBlockContent::from("AB").render_size(&doc, &tracked_style)?;
+        assert!((tracked.width - (plain.width + 2f32.pt())).0.abs() < 0.001);
+
+        let mut writer = OperationWriter::default();
+        TextBlock::from("AB")
+            .with_style(tracked_style)
+            .write(&doc.resources, &mut writer)?;
+        let adjusted = writer
+            .operations()
+            .into_iter()
+            .find(|operation| operation.operation == "TJ")
+            .expect("Tracked text is written with TJ");
+        let Some(Object::Array(array)) = adjusted.arguments.first() else {
+            panic!("TJ without an array");
+        };
+        assert_eq!(array.len(), 4);
+        assert_eq!(array[1], Object::Real(-100f32));
+        assert_eq!(array[3], Object::Real(-100f32));
+        Ok(())
+    }
+    #[test]
+    fn figure_alignment() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Figure Test");
+        let font_ref = doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        let style = TextStyle {
+            font_ref,
+            ..Default::default()
+        };
+        let three_figures = TextBlockContent::from("000").render_size(&doc, &style)?;
+        let aligned = TextBlockContent::from(
+            TextLine::default().add_item(TextItem::new("7").with_figure_alignment(3)),
+        )
+        .render_size(&doc, &style)?;
+        assert!((aligned.width - three_figures.width).0.abs() < 0.001);
+
+        let kerned = TextBlockContent::from(
+            TextLine::default()
+                .add_item(TextItem::new("AV").with_adjustment(1, GlyphSpace::Thousandths(-80f32))),
+        )
+        .render_size(&doc, &style)?;
+        let plain = TextBlockContent::from("AV").render_size(&doc, &style)?;
+        assert!(
+            (plain.width - kerned.width - style.font_size * 0.08)
+                .0
+                .abs()
+                < 0.001
+        );
+        Ok(())
+    }
+    #[test]
+    fn builtin_font_fallback() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Fallback Test");
+        let helvetica = doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        let style = TextStyle {
+            font_ref: helvetica,
+            ..Default::default()
+        };
+        let state = TextBlockState::new(&doc.resources, &style)?;
+        let runs = state.font_runs("Größe Ω");
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].text, "Größe ?");
+
+        let roboto_font_reader =
+            std::fs::File::open(fonts_dir().join("Roboto").join("Roboto-Regular.ttf"))?;
+        let roboto_font = OwnedPdfTtfFont::new_from_reader(roboto_font_reader, 0)?;
+        let FontRef::External(roboto) = doc.font_map().register_external_font(roboto_font)? else {
+            panic!("Expected an external font");
+        };
+        doc.font_map()
+            .set_builtin_fallback(BuiltinFontFallback::Font(roboto))?;
+        let state = TextBlockState::new(&doc.resources, &style)?;
+        let runs = state.font_runs("Größe Ω");
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].text, "Größe ");
+        assert_eq!(runs[1].text, "Ω");
+        assert!(runs[1].fallback.is_some());
+
+        let mut page = PdfPage::new_from_page_size(A4);
+        page.add_to_layer(TextBlock {
+            content: "Größe Ω".into(),
+            style,
+            position: PdfPosition::new(10f32.pt(), 10f32.pt()),
+            draw_as_lines: false,
+        })?;
+        doc.add_page(page);
+        doc.write_into_pdf_document_writer()?;
+        Ok(())
+    }
+    #[test]
+    fn default_font() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Default Font");
+        let style = TextStyle::default();
+        assert_eq!(style.font_ref, FontRef::Default);
+        // Helvetica must be registered to be used as the default
+        assert!(TextBlockState::new(&doc.resources, &style).is_err());
+
+        doc.set_default_font(FontRef::Builtin(BuiltinFont::Courier))?;
+        let state = TextBlockState::new(&doc.resources, &style)?;
+        assert_eq!(state.font, FontRef::Builtin(BuiltinFont::Courier));
+        assert!(doc.font_map().is_built_in_registered(&BuiltinFont::Courier));
+        assert_eq!(FontRef::Default.id(), None);
+        assert_eq!(
+            doc.font_map().resource_name(&FontRef::Default),
+            Object::name(BuiltinFont::Courier.dedicated_font_id())
+        );
+        // Errors name the font the default resolves to
+        assert_eq!(
+            doc.font_map().not_registered(&FontRef::Default),
+            ResourceNotRegistered::BuiltinFontNotRegistered(BuiltinFont::Courier)
+        );
+
+        let roboto = doc.register_font_bytes(std::fs::read(
+            fonts_dir().join("Roboto").join("Roboto-Regular.ttf"),
+        )?)?;
+        doc.set_default_font(roboto.clone())?;
+        let state = TextBlockState::new(&doc.resources, &style)?;
+        assert_eq!(state.font, roboto);
+        assert_eq!(
+            doc.font_map().resource_name(&FontRef::Default),
+            Object::name(roboto.id().unwrap())
+        );
+
+        let mut page = PdfPage::new_from_page_size(A4);
+        page.add_to_layer(TextBlock {
+            content: "Hello".into(),
+            style,
+            position: PdfPosition::new(10f32.pt(), 10f32.pt()),
+            draw_as_lines: false,
+        })?;
+        doc.add_page(page);
+        doc.write_into_pdf_document_writer()?;
+        Ok(())
+    }
+    #[test]
+    fn tab_stops() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Tab Stops");
+        let courier = doc.font_map().register_builtin_font(BuiltinFont::Courier);
+        // Every Courier character is 600 units wide
+        let char_width = 12f32 * 0.6;
+        let style_with = |tab_stops| TextStyle {
+            font_ref: courier.clone(),
+            font_size: 12f32.pt(),
+            tab_stops,
+            ..Default::default()
+        };
+        let width = |text: &str, style: &TextStyle| -> anyhow::Result<Pt> {
+            Ok(TextBlockContent::from(text).render_size(&doc, style)?.width)
+        };
+        let left = style_with(vec![TabStop::left(100f32.pt())]);
+        assert_eq!(width("ab\tc", &left)?, (100.0 + char_width).pt());
+        // Past the last stop a tab is a space
+        assert_eq!(width("ab\tc\td", &left)?, (100.0 + char_width * 3.0).pt());
+
+        let right = style_with(vec![TabStop::right(100f32.pt()).with_leader('.')]);
+        assert_eq!(width("ab\tcd", &right)?, 100.0f32.pt());
+        let decimal = style_with(vec![TabStop::decimal(100f32.pt(), '.')]);
+        assert_eq!(width("a\t1.25", &decimal)?, (100.0 + char_width * 3.0).pt());
+        // Stops are ignored when there are no tabs
+        assert_eq!(width("abc", &right)?, (char_width * 3.0).pt());
+        // The stops do not have to be sorted
+        let unsorted = style_with(vec![TabStop::left(200f32.pt()), TabStop::left(100f32.pt())]);
+        assert_eq!(width("ab\tc\td", &unsorted)?, (200.0 + char_width).pt());
+
+        let mut page = PdfPage::new_from_page_size(A4);
+        page.add_to_layer(TextBlock {
+            content: TextBlockContent::default()
+                .add_line("Introduction\t1")
+                .add_line(
+                    TextLine::default()
+                        .add_item(TextItem::new("Getting Started\t"))
+                        .add_item(TextItem::new("12").with_font_size(14f32.pt())),
+                ),
+            style: right,
+            position: PdfPosition::new(10f32.pt(), 10f32.pt()),
+            draw_as_lines: false,
+        })?;
+        doc.add_page(page);
+        doc.write_into_pdf_document_writer()?;
+        Ok(())
+    }
+    #[test]
+    fn tabs_are_counted_when_wrapping() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Tab Wrapping");
+        let courier = doc.font_map().register_builtin_font(BuiltinFont::Courier);
+        let style = TextStyle {
+            font_ref: courier,
+            font_size: 12f32.pt(),
+            tab_stops: vec![TabStop::left(100f32.pt())],
+            ..Default::default()
+        };
+        // The text after the tab starts at 100. `ghij` would end at 164.8
+        let mut content = TextBlockContent::from("ab\tcdef ghij");
+        content.apply_max_width(150f32.pt(), &doc, &style)?;
+        assert_eq!(content.text(), "ab\tcdef\nghij");
+        assert!(content.render_size(&doc, &style)?.width <= 150f32.pt());
+
+        // Past the last stop the tab is a space. It does not fit so it moves to the next line where it advances to the stop
+        let mut content = TextBlockContent::from("abcdefghijklmno\tn");
+        content.apply_max_width(110f32.pt(), &doc, &style)?;
+        assert_eq!(content.text(), "abcdefghijklmno\n\tn");
+        Ok(())
+    }
+    #[test]
+    fn wrapping_break_characters() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Wrapping");
+        let courier = doc.font_map().register_builtin_font(BuiltinFont::Courier);
+        let style = TextStyle {
+            font_ref: courier,
+            font_size: 12f32.pt(),
+            ..Default::default()
+        };
+        // Every Courier character is 7.2pt wide
+        let wrap = |text: &str, max_width: f32| -> anyhow::Result<String> {
+            let mut content = TextBlockContent::from(text);
+            content.apply_max_width(max_width.pt(), &doc, &style)?;
+            Ok(content.text())
+        };
+        assert_eq!(
+            wrap("one two\u{A0}three four", 80.0)?,
+            "one\ntwo\u{A0}three\nfour"
+        );
+        assert_eq!(wrap("abc\u{200B}defghij", 60.0)?, "abc\ndefghij");
+        assert_eq!(wrap("extra\u{AD}ordinary", 60.0)?, "extra-\nordinary");
+        // Break markers are not drawn when the text is not wrapped there
+        assert_eq!(
+            TextBlockContent::from("extra\u{AD}ordinary\u{200B}")
+                .render_size(&doc, &style)?
+                .width,
+            (7.2f32 * 13.0).pt()
+        );
+        Ok(())
+    }
+    #[test]
+    fn overflow() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Overflow");
+        let courier = doc.font_map().register_builtin_font(BuiltinFont::Courier);
+        // Every Courier character is 7.2pt wide
+        let style = TextStyle {
+            font_ref: courier,
+            font_size: 12f32.pt(),
+            leading: Some(12f32.pt()),
+            ..Default::default()
+        };
+        let limit = |text: &str, style: &TextStyle| -> anyhow::Result<String> {
+            let mut content = TextBlockContent::from(text);
+            content.apply_max_width(style.max_width.unwrap_or_default(), &doc, style)?;
+            Ok(content.text())
+        };
+        let cut = TextStyle {
+            max_width: Some(50f32.pt()),
+            ..style.clone()
+        };
+        assert_eq!(
+            limit(
+                "abcdefghij\nabc",
+                &cut.clone().with_overflow(TextOverflow::Truncate)
+            )?,
+            "abcdef\nabc"
+        );
+        assert_eq!(
+            limit(
+                "abcdefghij\nabc",
+                &cut.with_overflow(TextOverflow::Ellipsis)
+            )?,
+            "abcde\u{2026}\nabc"
+        );
+
+        let first_line_height = TextBlockContent::from("one")
+            .render_size(&doc, &style)?
+            .height;
+        let clamped = TextStyle {
+            max_width: Some(100f32.pt()),
+            max_height: Some(first_line_height + 13f32.pt()),
+            ..style
+        };
+        let text = "one two three four five six";
+        assert_eq!(
+            limit(text, &clamped.clone().with_overflow(TextOverflow::Truncate))?,
+            "one two\nthree four"
+        );
+        assert_eq!(
+            limit(text, &clamped.clone().with_overflow(TextOverflow::Ellipsis))?,
+            "one two\nthree four\u{2026}"
+        );
+        // Visible text is only wrapped
+        assert_eq!(limit(text, &clamped)?, "one two\nthree four\nfive six");
+        Ok(())
+    }
+    #[test]
+    fn ellipsis_fits_proportional_fonts() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Ellipsis");
+        let helvetica = doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        let style = TextStyle {
+            font_ref: helvetica,
+            font_size: 12f32.pt(),
+            max_width: Some(54f32.pt()),
+            ..Default::default()
+        }
+        .with_overflow(TextOverflow::Ellipsis);
+        let mut content = TextBlockContent::from("WallpaperWallpaper");
+        content.apply_max_width(54f32.pt(), &doc, &style)?;
+        assert_eq!(content.text(), "Wallpa\u{2026}");
+        // The ellipsis is 1000 units wide in Helvetica. Measured at the default width one more letter would be kept and overflow
+        let width = content
+            .render_size(
+                &doc,
+                &TextStyle {
+                    max_width: None,
+                    ..style
+                },
+            )?
+            .width;
+        assert!(width <= 54f32.pt(), "{width:?} is wider than the limit");
+        Ok(())
+    }
 }
@@ -0,0 +1,457 @@
+use tux_pdf_low::types::Object;
+
+use crate::{
+    TuxPdfError,
+    document::PdfResources,
+    graphics::{
+        OperationWriter, PdfObject, PdfObjectType, PdfPosition,
+        primitives::{Path, PathSegment},
+        shapes::OutlineRect,
+        size::Size,
+    },
+    units::Pt,
+};
+
+use super::{TextBlockState, TextOperations, TextStyle};
+/// Number of straight pieces a bezier curve is split into when following a [Path]
+const CURVE_STEPS: usize = 16;
+
+/// The line text is placed along by [PathText]
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextPath {
+    /// Part of a circle.
+    ///
+    /// Angles are in degrees, counter clockwise starting from the positive x axis.
+    /// The text runs from `start_angle` to `end_angle`. If `end_angle` is less than `start_angle` the text runs clockwise
+    Arc {
+        center: PdfPosition,
+        radius: Pt,
+        start_angle: f32,
+        end_angle: f32,
+    },
+    /// Straight lines between the points
+    Polyline(Vec<PdfPosition>),
+}
+impl TextPath {
+    pub fn arc(
+        center: impl Into<PdfPosition>,
+        radius: Pt,
+        start_angle: f32,
+        end_angle: f32,
+    ) -> Self {
+        TextPath::Arc {
+            center: center.into(),
+            radius,
+            start_angle,
+            end_angle,
+        }
+    }
+    /// Follows the first subpath of the path. Curves are split into straight lines
+    pub fn from_path(path: &Path) -> Self {
+        let mut points: Vec<PdfPosition> = Vec::new();
+        for segment in &path.segments {
+            match *segment {
+                PathSegment::MoveTo(_) if !points.is_empty() => break,
+                PathSegment::MoveTo(point) | PathSegment::LineTo(point) => points.push(point),
+                PathSegment::CurveTo {
+                    control_1,
+                    control_2,
+                    end,
+                } => {
+                    let start = points.last().copied().unwrap_or(control_1);
+                    for step in 1..=CURVE_STEPS {
+                        let t = step as f32 / CURVE_STEPS as f32;
+                        points.push(cubic_bezier_point(start, control_1, control_2, end, t));
+                    }
+                }
+                PathSegment::Close => {
+                    if let Some(first) = points.first().copied() {
+                        points.push(first);
+                    }
+                    break;
+                }
+            }
+        }
+        TextPath::Polyline(points)
+    }
+    /// The length of the path
+    pub fn length(&self) -> Pt {
+        match self {
+            TextPath::Arc {
+                radius,
+                start_angle,
+                end_angle,
+                ..
+            } => *radius * (end_angle - start_angle).to_radians().abs(),
+            TextPath::Polyline(points) => points
+                .windows(2)
+                .map(|pair| distance(pair[0], pair[1]))
+                .sum(),
+        }
+    }
+    /// The point at the distance along the path and the direction of the path at that point in radians
+    ///
+    /// Distances outside of the path continue in the direction of the closest end
+    pub fn point_at(&self, distance_along: Pt) -> (PdfPosition, f32) {
+        match self {
+            TextPath::Arc {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+            } => {
+                let direction = if end_angle < start_angle { -1f32 } else { 1f32 };
+                let angle = start_angle.to_radians()
+                    + direction
+                        * if radius.0 == 0f32 {
+                            0f32
+                        } else {
+                            distance_along.0 / radius.0
+                        };
+                let point = PdfPosition {
+                    x: center.x + *radius * angle.cos(),
+                    y: center.y + *radius * angle.sin(),
+                };
+                (point, angle + direction * std::f32::consts::FRAC_PI_2)
+            }
+            TextPath::Polyline(points) => polyline_point_at(points, distance_along),
+        }
+    }
+    /// The area the path covers
+    fn bounds(&self) -> Option<OutlineRect> {
+        let points = match self {
+            TextPath::Arc { center, radius, .. } => vec![
+                PdfPosition::new(center.x - *radius, center.y - *radius),
+                PdfPosition::new(center.x + *radius, center.y + *radius),
+            ],
+            TextPath::Polyline(points) => points.clone(),
+        };
+        let first = points.first()?;
+        let (mut min, mut max) = (*first, *first);
+        for point in &points[1..] {
+            min = PdfPosition::new(min.x.min(point.x), min.y.min(point.y));
+            max = PdfPosition::new(max.x.max(point.x), max.y.max(point.y));
+        }
+        Some(OutlineRect {
+            position: min,
+            size: Size::new(max.x - min.x, max.y - min.y),
+        })
+    }
+}
+impl From<Vec<PdfPosition>> for TextPath {
+    fn from(points: Vec<PdfPosition>) -> Self {
+        TextPath::Polyline(points)
+    }
+}
+impl From<&Path> for TextPath {
+    fn from(path: &Path) -> Self {
+        TextPath::from_path(path)
+    }
+}
+fn distance(a: PdfPosition, b: PdfPosition) -> Pt {
+    Pt((b.x - a.x).0.hypot((b.y - a.y).0))
+}
+fn cubic_bezier_point(
+    start: PdfPosition,
+    control_1: PdfPosition,
+    control_2: PdfPosition,
+    end: PdfPosition,
+    t: f32,
+) -> PdfPosition {
+    let u = 1f32 - t;
+    let weights = [u * u * u, 3f32 * u * u * t, 3f32 * u * t * t, t * t * t];
+    let points = [start, control_1, control_2, end];
+    let (x, y) = points
+        .iter()
+        .zip(weights)
+        .fold((0f32, 0f32), |(x, y), (point, weight)| {
+            (x + point.x.0 * weight, y + point.y.0 * weight)
+        });
+    PdfPosition::new(Pt(x), Pt(y))
+}
+fn polyline_point_at(points: &[PdfPosition], distance_along: Pt) -> (PdfPosition, f32) {
+    let segments: Vec<_> = points
+        .windows(2)
+        .map(|pair| (pair[0], pair[1]))
+        .filter(|(start, end)| distance(*start, *end) > Pt::default())
+        .collect();
+    let Some(last_index) = segments.len().checked_sub(1) else {
+        return (points.first().copied().unwrap_or_default(), 0f32);
+    };
+    let mut remaining = distance_along;
+    for (index, (start, end)) in segments.iter().copied().enumerate() {
+        let length = distance(start, end);
+        // Before the first segment and past the last segment the text continues in a straight line
+        if remaining <= length || index == last_index {
+            let t = remaining.0 / length.0;
+            let angle = (end.y - start.y).0.atan2((end.x - start.x).0);
+            let point = PdfPosition {
+                x: start.x + (end.x - start.x) * t,
+                y: start.y + (end.y - start.y) * t,
+            };
+            return (point, angle);
+        }
+        remaining -= length;
+    }
+    unreachable!("The last segment always returns")
+}
+/// Where the text is placed along the path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PathTextAlignment {
+    /// The text starts at the start of the path
+    #[default]
+    Start,
+    /// The middle of the text is at the middle of the path
+    Center,
+    /// The text ends at the end of the path
+    End,
+}
+/// A single line of text that follows a [TextPath]. Used for seals, badges and certificates
+///
+/// Each character is placed and rotated on its own with a text matrix.
+/// The baseline of the text is on the path and the top of the characters is on the left side of the path.
+///
+/// ```rust
+/// use tux_pdf::{
+///     document::{BuiltinFont, PdfDocument},
+///     graphics::{PathText, PathTextAlignment, PdfPosition, TextPath, TextStyle},
+///     page::{PdfPage, page_sizes::A4},
+///     units::UnitType,
+/// };
+/// let mut document = PdfDocument::new("Seal");
+/// let font_ref = document.font_map().register_builtin_font(BuiltinFont::Helvetica);
+/// let center = PdfPosition::new(300f32.pt(), 400f32.pt());
+/// // Clockwise over the top of the circle so the text reads left to right
+/// let top = PathText::new("CERTIFIED", TextPath::arc(center, 80f32.pt(), 150.0, 30.0))
+///     .with_style(TextStyle {
+///         font_ref,
+///         ..Default::default()
+///     })
+///     .with_alignment(PathTextAlignment::Center);
+/// let mut page = PdfPage::new_from_page_size(A4);
+/// page.contents.push(top.into());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathText {
+    pub text: String,
+    pub style: TextStyle,
+    pub path: TextPath,
+    pub alignment: PathTextAlignment,
+    /// Moves the text further along the path after it is aligned
+    pub offset: Pt,
+}
+impl PathText {
+    pub fn new(text: impl Into<String>, path: impl Into<TextPath>) -> Self {
+        Self {
+            text: text.into(),
+            style: TextStyle::default(),
+            path: path.into(),
+            alignment: PathTextAlignment::default(),
+            offset: Pt::default(),
+        }
+    }
+    pub fn with_style(mut self, style: TextStyle) -> Self {
+        self.style = style;
+        self
+    }
+    pub fn with_alignment(mut self, alignment: PathTextAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+    pub fn with_offset(mut self, offset: Pt) -> Self {
+        self.offset = offset;
+        self
+    }
+    /// The area of the path grown by the font size on every side
+    pub fn bounds(&self) -> Option<OutlineRect> {
+        let bounds = self.path.bounds()?;
+        let font_size = self.style.font_size;
+        Some(OutlineRect {
+            position: PdfPosition::new(
+                bounds.position.x - font_size,
+                bounds.position.y - font_size,
+            ),
+            size: Size::new(
+                bounds.size.width + font_size * 2f32,
+                bounds.size.height + font_size * 2f32,
+            ),
+        })
+    }
+    /// The advance of every character including the character and word spacing of the style
+    fn advances(&self, state: &TextBlockState) -> Vec<(char, Pt)> {
+        let character_spacing = self.style.character_spacing.unwrap_or_default();
+        let word_spacing = self.style.word_spacing.unwrap_or_default();
+        self.text
+            .chars()
+            .map(|c| {
+                let mut advance = state.size_of_char(c).unwrap_or_default().width;
+                advance += character_spacing;
+                if c == ' ' {
+                    advance += word_spacing;
+                }
+                (c, advance)
+            })
+            .collect()
+    }
+}
+impl From<PathText> for PdfObject {
+    fn from(text: PathText) -> Self {
+        PdfObject::PathText(text)
+    }
+}
+impl PdfObjectType for PathText {
+    fn write(
+        self,
+        resources: &PdfResources,
+        writer: &mut OperationWriter,
+    ) -> Result<(), TuxPdfError> {
+        if self.text.is_empty() {
+            return Ok(());
+        }
+        let state = TextBlockState::new(resources, &self.style)?;
+        let advances = self.advances(&state);
+        let text_length: Pt = advances.iter().map(|(_, advance)| *advance).sum();
+        let free_space = self.path.length() - text_length;
+        let mut distance_along = self.offset
+            + match self.alignment {
+                PathTextAlignment::Start => Pt::default(),
+                PathTextAlignment::Center => free_space / 2f32,
+                PathTextAlignment::End => free_space,
+            };
+
+        writer.push_empty_op(TextOperations::BeginText);
+        self.style.clone().write(resources, writer)?;
+        for (c, advance) in advances {
+            // Rotated around the middle of the character so it sits evenly on curves
+            let (middle, angle) = self.path.point_at(distance_along + advance / 2f32);
+            distance_along += advance;
+            if c.is_whitespace() {
+                continue;
+            }
+            let (sin, cos) = angle.sin_cos();
+            let origin = PdfPosition {
+                x: middle.x - advance / 2f32 * cos,
+                y: middle.y - advance / 2f32 * sin,
+            };
+            // The rotation applied after the oblique skew
+            let skew = state.synthesis.skew();
+            writer.add_operation(
+                TextOperations::SetTextMatrix,
+                vec![
+                    Object::Real(cos),
+                    Object::Real(sin),
+                    Object::Real(skew * cos - sin),
+                    Object::Real(skew * sin + cos),
+                    origin.x.into(),
+                    origin.y.into(),
+                ],
+            );
+            for run in state.font_runs(&c.to_string()) {
+                run.write(&state, writer);
+            }
+        }
+        writer.push_empty_op(TextOperations::EndText);
+        Ok(())
+    }
+    fn calculate_number_of_pdf_objects(&self) -> usize {
+        // A text matrix and the text for every character
+        self.text.chars().count() * 2 + 2
+    }
+}
+#[cfg(test)]
+mod tests {
+    use tux_pdf_low::types::Object;
+
+    use super::{PathText, PathTextAlignment, TextPath};
+    use crate::{
+        document::{BuiltinFont, PdfDocument},
+        graphics::{
+            OperationWriter, PdfObjectType, PdfPosition, TextStyle, primitives::Path,
+            size::RenderSize,
+        },
+        units::{Pt, UnitType},
+    };
+
+    fn text_matrices(writer: OperationWriter) -> Vec<Vec<f32>> {
+        writer
+            .operations()
+            .into_iter()
+            .filter(|operation| operation.operation == "Tm")
+            .map(|operation| {
+                operation
+                    .arguments
+                    .into_iter()
+                    .map(|operand| match operand {
+                        Object::Real(value) => value,
+                        Object::Integer(value) => value as f32,
+                        other => panic!("Unexpected operand {other:?}"),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn straight_line_matches_text_width() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Path Text");
+        let font_ref = doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        let style = TextStyle {
+            font_ref,
+            ..Default::default()
+        };
+        let start = PdfPosition::new(10f32.pt(), 20f32.pt());
+        let path = TextPath::from(vec![start, PdfPosition::new(210f32.pt(), 20f32.pt())]);
+        let text = PathText::new("AV", path).with_style(style.clone());
+
+        let mut writer = OperationWriter::default();
+        text.write(&doc.resources, &mut writer)?;
+        let matrices = text_matrices(writer);
+        assert_eq!(matrices.len(), 2);
+        assert_eq!(matrices[0][..4], [1f32, 0f32, 0f32, 1f32]);
+        assert!((matrices[0][4] - start.x.0).abs() < 0.01);
+        assert!((matrices[0][5] - start.y.0).abs() < 0.01);
+        let width_of_a = crate::graphics::TextBlockContent::from("A")
+            .render_size(&doc, &style)?
+            .width;
+        assert!((matrices[1][4] - (start.x + width_of_a).0).abs() < 0.01);
+        Ok(())
+    }
+    #[test]
+    fn arc_rotates_characters() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Path Text");
+        let font_ref = doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        let center = PdfPosition::new(100f32.pt(), 100f32.pt());
+        // Clockwise over the top. The middle of the text is at the top of the circle
+        let text = PathText::new("OOO", TextPath::arc(center, 50f32.pt(), 180.0, 0.0))
+            .with_style(TextStyle {
+                font_ref,
+                ..Default::default()
+            })
+            .with_alignment(PathTextAlignment::Center);
+        assert_eq!(text.path.length(), Pt(50f32 * std::f32::consts::PI));
+
+        let mut writer = OperationWriter::default();
+        text.write(&doc.resources, &mut writer)?;
+        let matrices = text_matrices(writer);
+        assert_eq!(matrices.len(), 3);
+        // The middle character is upright
+        assert!((matrices[1][0] - 1f32).abs() < 0.001);
+        // Rotated counter clockwise before the top and clockwise after it
+        assert!(matrices[0][1] > 0f32);
+        assert!(matrices[2][1] < 0f32);
+        Ok(())
+    }
+    #[test]
+    fn follow_path() {
+        let path = Path::builder()
+            .move_to((0f32.pt(), 0f32.pt()))
+            .line_to((30f32.pt(), 0f32.pt()))
+            .line_to((30f32.pt(), 40f32.pt()))
+            .build();
+        let path = TextPath::from(&path);
+        assert_eq!(path.length(), 70f32.pt());
+        let (point, angle) = path.point_at(50f32.pt());
+        assert_eq!(point, PdfPosition::new(30f32.pt(), 20f32.pt()));
+        assert_eq!(angle, std::f32::consts::FRAC_PI_2);
+    }
+}
@@ -1,12 +1,21 @@
+use std::borrow::Cow;
+
 use crate::{
     TuxPdfError,
-    document::{FontRef, FontRenderSizeParams, InternalFontTypes, PdfResources},
-    graphics::OperationWriter,
+    document::{
+        BuiltinFontFallback, FontRef, FontRenderSizeParams, FontType, InternalFontTypes,
+        PdfResources, win_ansi_code,
+    },
+    graphics::{OperationKeys, OperationWriter, size::Size},
     units::Pt,
     utils::IsEmpty,
 };
+use tux_pdf_low::types::{Object, PdfString};
 
-use super::{TextOperations, TextStyle};
+use super::{
+    GlyphAdjustment, GlyphSpace, SMALL_CAPS_SCALE, TextOperations, TextStyle, TextSynthesis,
+    is_break_marker,
+};
 /// Used to store the current state of the text block
 ///
 /// This is only used for managing text size
@@ -19,26 +28,183 @@ pub(crate) struct TextBlockState<'resources> {
     pub(crate) word_spacing: Option<Pt>,
     pub(crate) character_spacing: Option<Pt>,
     pub(crate) text_rise: Option<Pt>,
+    /// Thousandths of the font size added after every glyph
+    pub(crate) tracking: Option<f32>,
+    pub(crate) synthesis: TextSynthesis,
 }
 impl<'resources> TextBlockState<'resources> {
     pub fn new(
         resources: &'resources PdfResources,
         styles: &TextStyle,
     ) -> Result<Self, TuxPdfError> {
+        let font = resources.fonts.resolve(&styles.font_ref);
         let font_type = resources
             .fonts
-            .internal_font_type(&styles.font_ref)
-            .ok_or_else(|| TuxPdfError::from(styles.font_ref.clone()))?;
+            .internal_font_type(font)
+            .ok_or_else(|| resources.fonts.not_registered(font))?;
         Ok(Self {
             resources,
-            font: styles.font_ref.clone(),
+            font: font.clone(),
             font_size: styles.font_size,
             font_type,
             word_spacing: styles.word_spacing,
             character_spacing: styles.character_spacing,
             text_rise: styles.text_rise,
+            tracking: styles.tracking,
+            synthesis: styles.synthesis,
         })
     }
+    /// How the character is drawn
+    ///
+    /// Characters a builtin font can not encode are substituted or drawn with the [BuiltinFontFallback]
+    pub fn resolve_char(&self, c: char) -> (char, Option<FallbackFont<'resources>>) {
+        if !matches!(self.font_type, InternalFontTypes::Builtin(_)) || win_ansi_code(c).is_some() {
+            return (c, None);
+        }
+        let fonts = &self.resources.fonts;
+        match fonts.builtin_fallback() {
+            BuiltinFontFallback::Substitute(substitute) => (*substitute, None),
+            BuiltinFontFallback::Font(font_id) => match fonts.get_external_font(font_id) {
+                Some(font) => (
+                    c,
+                    Some(FallbackFont {
+                        font: FontRef::External(font_id.clone()),
+                        font_type: InternalFontTypes::External(font),
+                    }),
+                ),
+                None => (c, None),
+            },
+        }
+    }
+    /// The characters drawn for `c` and if they are drawn as small caps
+    fn synthesized_chars(&self, c: char) -> (Vec<char>, bool) {
+        if self.synthesis.small_caps && c.is_lowercase() {
+            (c.to_uppercase().collect(), true)
+        } else {
+            (vec![c], false)
+        }
+    }
+    /// The state with the font size of small caps
+    fn small_caps_state(&self) -> Self {
+        Self {
+            font_size: self.font_size * SMALL_CAPS_SCALE,
+            ..self.clone()
+        }
+    }
+    /// Splits the text into runs that are drawn with the same font and font size
+    ///
+    /// Zero width spaces and soft hyphens are removed
+    pub fn font_runs(&self, text: &str) -> Vec<FontRun<'resources>> {
+        self.adjusted_font_runs(text, &[])
+    }
+    /// [Self::font_runs] with the space of the tracking and the adjustments between the glyphs
+    pub fn adjusted_font_runs(
+        &self,
+        text: &str,
+        adjustments: &[GlyphAdjustment],
+    ) -> Vec<FontRun<'resources>> {
+        let tracking = self.tracking_space();
+        let mut runs: Vec<FontRun<'resources>> = Vec::new();
+        // Space added before the next glyph
+        let mut pending = Pt::default();
+        for (index, c) in text.chars().enumerate() {
+            pending += self.space_before(index, adjustments);
+            if is_break_marker(c) {
+                continue;
+            }
+            let (chars, small_caps) = self.synthesized_chars(c);
+            for c in chars {
+                let (c, fallback) = self.resolve_char(c);
+                // Compared by reference so the fonts themselves are not compared
+                let continues_run = runs.last().is_some_and(|run| {
+                    run.small_caps == small_caps
+                        && run.fallback.as_ref().map(|fallback| &fallback.font)
+                            == fallback.as_ref().map(|fallback| &fallback.font)
+                });
+                if !continues_run {
+                    runs.push(FontRun {
+                        fallback,
+                        small_caps,
+                        text: String::new(),
+                        spaces: Vec::new(),
+                    });
+                }
+                if let Some(run) = runs.last_mut() {
+                    run.add_space(pending);
+                    run.text.push(c);
+                    run.add_space(tracking);
+                }
+                pending = Pt::default();
+            }
+        }
+        pending += self.space_before(text.chars().count(), adjustments);
+        if let Some(run) = runs.last_mut() {
+            run.add_space(pending);
+        }
+        runs
+    }
+    /// Space added after every glyph by the tracking
+    pub fn tracking_space(&self) -> Pt {
+        self.tracking
+            .map(|tracking| self.font_size * (tracking / 1000f32))
+            .unwrap_or_default()
+    }
+    pub fn glyph_space(&self, space: GlyphSpace) -> Pt {
+        match space {
+            GlyphSpace::Thousandths(thousandths) => self.font_size * (thousandths / 1000f32),
+            GlyphSpace::Figures(figures) => {
+                self.size_of_char('0').unwrap_or_default().width * figures
+            }
+        }
+    }
+    /// The space of the adjustments before the character at `index`
+    pub fn space_before(&self, index: usize, adjustments: &[GlyphAdjustment]) -> Pt {
+        adjustments
+            .iter()
+            .filter(|adjustment| adjustment.index == index)
+            .map(|adjustment| self.glyph_space(adjustment.space))
+            .sum()
+    }
+    /// The size of the text including characters drawn with a fallback font and synthesized styles
+    pub fn size_of_text(&self, text: &str) -> Size {
+        self.size_of_adjusted_text(text, &[])
+    }
+    pub fn size_of_adjusted_text(&self, text: &str, adjustments: &[GlyphAdjustment]) -> Size {
+        let mut size = Size::<Pt>::default();
+        for run in self.adjusted_font_runs(text, adjustments) {
+            let run_size = run.size(self);
+            size.width += run_size.width;
+            size.height = size.height.max(run_size.height);
+        }
+        size
+    }
+    pub fn size_of_char(&self, c: char) -> Option<Size> {
+        if is_break_marker(c) {
+            return Some(Size::default());
+        }
+        let (chars, small_caps) = self.synthesized_chars(c);
+        let state = if small_caps {
+            Cow::Owned(self.small_caps_state())
+        } else {
+            Cow::Borrowed(self)
+        };
+        let mut size: Option<Size> = None;
+        for c in chars {
+            let (c, fallback) = self.resolve_char(c);
+            let char_size = match fallback {
+                Some(fallback) => fallback.font_type.size_of_char(c, state.as_ref()),
+                None => self.font_type.size_of_char(c, state.as_ref()),
+            }?;
+            let size = size.get_or_insert_default();
+            size.width += char_size.width + self.bold_advance() + self.tracking_space();
+            size.height = size.height.max(char_size.height);
+        }
+        size
+    }
+    /// Extra advance of every glyph of synthesized bold text
+    pub fn bold_advance(&self) -> Pt {
+        self.synthesis.bold_stroke_width(self.font_size)
+    }
     pub fn create_updating<'state>(&'state self) -> UpdatingTextBlockState<'state, 'resources> {
         UpdatingTextBlockState {
             original: self,
@@ -47,6 +213,7 @@ impl<'resources> TextBlockState<'resources> {
             word_spacing: None,
             character_spacing: None,
             text_rise: None,
+            tracking: None,
         }
     }
 }
@@ -65,6 +232,128 @@ impl FontRenderSizeParams for TextBlockState<'_> {
         self.text_rise
     }
 }
+/// The font used for characters a builtin font can not encode
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FallbackFont<'resources> {
+    pub(crate) font: FontRef,
+    pub(crate) font_type: InternalFontTypes<'resources>,
+}
+/// Text that is drawn with one font and font size
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FontRun<'resources> {
+    /// None if the font of the state is used
+    pub(crate) fallback: Option<FallbackFont<'resources>>,
+    /// Lowercase letters converted to uppercase and drawn at [SMALL_CAPS_SCALE]
+    pub(crate) small_caps: bool,
+    pub(crate) text: String,
+    /// Space added before the character at the index of the text. The length of the text for after the last one
+    pub(crate) spaces: Vec<(usize, Pt)>,
+}
+impl<'resources> FontRun<'resources> {
+    pub fn font_type(&self, state: &TextBlockState<'resources>) -> InternalFontTypes<'resources> {
+        self.fallback
+            .as_ref()
+            .map(|fallback| fallback.font_type)
+            .unwrap_or(state.font_type)
+    }
+    pub fn font_size(&self, state: &TextBlockState<'resources>) -> Pt {
+        if self.small_caps {
+            state.font_size * SMALL_CAPS_SCALE
+        } else {
+            state.font_size
+        }
+    }
+    /// Adds space after the characters already in the run
+    fn add_space(&mut self, space: Pt) {
+        if space == Pt::default() {
+            return;
+        }
+        let index = self.text.chars().count();
+        match self.spaces.last_mut() {
+            Some((last_index, last_space)) if *last_index == index => *last_space += space,
+            _ => self.spaces.push((index, space)),
+        }
+    }
+    /// The size of the run including the advance added by synthesized bold and the spaces
+    pub fn size(&self, state: &TextBlockState<'resources>) -> Size {
+        let mut size = if self.small_caps {
+            self.font_type(state)
+                .calculate_size_of_text(&self.text, &state.small_caps_state())
+        } else {
+            self.font_type(state)
+                .calculate_size_of_text(&self.text, state)
+        };
+        size.width += state.bold_advance() * self.text.chars().count() as f32;
+        size.width += self.spaces.iter().map(|(_, space)| *space).sum::<Pt>();
+        size
+    }
+    /// Shows the text of the run. Switches to the font of the run and back to the font of the state
+    pub fn write(&self, state: &TextBlockState<'resources>, writer: &mut OperationWriter) {
+        let switches_font = self.fallback.is_some() || self.small_caps;
+        if switches_font {
+            let font = self
+                .fallback
+                .as_ref()
+                .map(|fallback| &fallback.font)
+                .unwrap_or(&state.font);
+            writer.add_operation(
+                TextOperations::TextFont,
+                vec![
+                    state.resources.fonts.resource_name(font),
+                    self.font_size(state).into(),
+                ],
+            );
+        }
+        if self.spaces.is_empty() {
+            writer.add_operation(
+                TextOperations::ShowText,
+                vec![Object::String(PdfString::Hexadecimal(
+                    self.font_type(state).encode_text(&self.text),
+                ))],
+            );
+        } else {
+            writer.add_operation(
+                TextOperations::ShowTextAdjusted,
+                vec![Object::Array(self.adjusted_array(state))],
+            );
+        }
+        if switches_font {
+            writer.add_operation(
+                TextOperations::TextFont,
+                vec![
+                    state.resources.fonts.resource_name(&state.font),
+                    state.font_size.into(),
+                ],
+            );
+        }
+    }
+    /// The text split at the spaces. Each space is a number in thousandths of the font size of the run
+    fn adjusted_array(&self, state: &TextBlockState<'resources>) -> Vec<Object> {
+        let font_type = self.font_type(state);
+        let font_size = self.font_size(state);
+        let chars: Vec<char> = self.text.chars().collect();
+        let mut array = Vec::with_capacity(self.spaces.len() * 2 + 1);
+        let mut start = 0;
+        for (index, space) in &self.spaces {
+            if *index > start {
+                let text: String = chars[start..*index].iter().collect();
+                array.push(Object::String(PdfString::Hexadecimal(
+                    font_type.encode_text(&text),
+                )));
+                start = *index;
+            }
+            // Positive numbers move the next glyph left
+            array.push(Object::Real(-space.0 / font_size.0 * 1000f32));
+        }
+        if start < chars.len() {
+            let text: String = chars[start..].iter().collect();
+            array.push(Object::String(PdfString::Hexadecimal(
+                font_type.encode_text(&text),
+            )));
+        }
+        array
+    }
+}
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct UpdatingTextBlockState<'state, 'resources> {
     pub(crate) original: &'state TextBlockState<'resources>,
@@ -73,6 +362,7 @@ pub(crate) struct UpdatingTextBlockState<'state, 'resources> {
     pub(crate) word_spacing: Option<Pt>,
     pub(crate) character_spacing: Option<Pt>,
     pub(crate) text_rise: Option<Pt>,
+    pub(crate) tracking: Option<f32>,
 }
 impl IsEmpty for UpdatingTextBlockState<'_, '_> {
     fn is_empty(&self) -> bool {
@@ -81,6 +371,7 @@ impl IsEmpty for UpdatingTextBlockState<'_, '_> {
             && self.word_spacing.is_none()
             && self.character_spacing.is_none()
             && self.text_rise.is_none()
+            && self.tracking.is_none()
     }
 }
 impl<'resources> UpdatingTextBlockState<'_, 'resources> {
@@ -92,33 +383,51 @@ impl<'resources> UpdatingTextBlockState<'_, 'resources> {
         if self.is_empty() {
             return Ok(None);
         }
-        let font_type = if let Some(font) = self.font.as_ref() {
-            self.original
-                .resources
-                .fonts
+        let fonts = &self.original.resources.fonts;
+        let resolved_font = self.font.as_ref().map(|font| fonts.resolve(font).clone());
+        let font_type = if let Some(font) = resolved_font.as_ref() {
+            fonts
                 .internal_font_type(font)
-                .ok_or_else(|| TuxPdfError::from(font.clone()))?
+                .ok_or_else(|| fonts.not_registered(font))?
         } else {
             self.original.font_type
         };
-        if let Some(writer) = writer
-            && (self.font.is_some() || self.font_size.is_some())
-        {
-            let font = self.font.as_ref().unwrap_or(&self.original.font);
+        let synthesis = self.original.synthesis;
+        if let Some(writer) = writer {
             let font_size = self.font_size.unwrap_or(self.original.font_size);
-            writer.add_operation(
-                TextOperations::TextFont,
-                vec![font.clone().into(), font_size.into()],
-            );
+            if self.font.is_some() || self.font_size.is_some() {
+                let font = resolved_font.as_ref().unwrap_or(&self.original.font);
+                writer.add_operation(
+                    TextOperations::TextFont,
+                    vec![fonts.resource_name(font), font_size.into()],
+                );
+            }
+            // The bold stroke and the spacing it adds follow the font size
+            let bold_stroke = synthesis.bold_stroke_width(font_size);
+            if synthesis.bold && self.font_size.is_some() {
+                writer.add_operation(OperationKeys::SetLineWidth, vec![bold_stroke.into()]);
+            }
+            if self.character_spacing.is_some() || (synthesis.bold && self.font_size.is_some()) {
+                let character_spacing = self
+                    .character_spacing
+                    .or(self.original.character_spacing)
+                    .unwrap_or_default();
+                writer.add_operation(
+                    TextOperations::CharacterSpace,
+                    vec![(character_spacing + bold_stroke).into()],
+                );
+            }
         }
         let result = TextBlockState {
             resources: self.original.resources,
-            font: self.font.unwrap_or_else(|| self.original.font.clone()),
+            font: resolved_font.unwrap_or_else(|| self.original.font.clone()),
             font_size: self.font_size.unwrap_or(self.original.font_size),
             font_type,
             word_spacing: self.word_spacing.or(self.original.word_spacing),
             character_spacing: self.character_spacing.or(self.original.character_spacing),
             text_rise: self.text_rise.or(self.original.text_rise),
+            tracking: self.tracking.or(self.original.tracking),
+            synthesis,
         };
         Ok(Some(result))
     }
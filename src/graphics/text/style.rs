@@ -2,17 +2,17 @@ use std::borrow::Cow;
 mod modifiers;
 use crate::{
     TuxPdfError,
-    document::{BuiltinFont, FontRef, FontRenderSizeParams, PdfResources},
+    document::{FontRef, FontRenderSizeParams, PdfResources},
     graphics::{
-        OperationWriter, PdfObjectType,
-        color::{Color, ColorWriter, HasColorParams},
+        OperationKeys, OperationWriter, PdfObjectType,
+        color::{BLACK_RGB, Color, ColorWriter, HasColorParams},
     },
     units::Pt,
     utils::{IsEmpty, PartailOrFull, PartialStruct},
 };
 pub use modifiers::*;
 
-use super::TextOperations;
+use super::{TextFit, TextOperations};
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct TextStyle {
@@ -39,6 +39,19 @@ pub struct TextStyle {
     ///
     /// See [TextOperations::TextRise] for more information
     pub text_rise: Option<Pt>,
+    /// Space added after every glyph in thousandths of the font size
+    ///
+    /// Written as numbers in [TextOperations::ShowTextAdjusted] arrays instead of [TextOperations::CharacterSpace]
+    /// so it is applied once per glyph for fonts with multi-byte character codes
+    pub tracking: Option<f32>,
+    /// How the glyphs are painted
+    ///
+    /// See [TextOperations::TextRenderingMode] for more information
+    pub rendering_mode: Option<TextRenderingMode>,
+    /// Bold, oblique and small caps drawn with the regular face of the font
+    ///
+    /// See [TextSynthesis]
+    pub synthesis: TextSynthesis,
 
     /// Space between lines
     ///
@@ -47,6 +60,25 @@ pub struct TextStyle {
     ///
     /// Where when multiple lines in one text block are rendered, this is the space between them.
     pub line_spacing: Option<Pt>,
+    /// Distance between the baselines of two lines
+    ///
+    /// ## Note
+    /// This is not a pdf feature.
+    ///
+    /// When set this replaces the measured line height and [TextStyle::line_spacing]
+    pub leading: Option<Pt>,
+    /// Default paragraph formatting for lines that start a paragraph
+    ///
+    /// See [TextLine::paragraph](super::TextLine::paragraph)
+    pub paragraph: Option<ParagraphStyle>,
+    /// Positions a tab (`\t`) in a line advances to. Measured from the start of the line
+    ///
+    /// ## Note
+    /// This is not a pdf feature.
+    ///
+    /// The stops can be in any order. A tab advances to the nearest stop past the text before it.
+    /// If empty tabs are written as is
+    pub tab_stops: Vec<TabStop>,
     /// Maximum width of text block
     ///
     /// ## Note
@@ -54,16 +86,276 @@ pub struct TextStyle {
     /// This is not a pdf feature. This is a feature of this library where it attempts to wrap text.
     /// This is a work in progress and may not work as expected.
     ///
-    /// ### What about max height?
-    ///
-    /// I do not see a use case for this currently. If you have one please open an issue.
-    ///
     /// But the main use case for this is to put text in a a table cell.
+    ///
+    /// See [TextStyle::overflow] to cut lines instead of wrapping them
     pub max_width: Option<Pt>,
     /// Minimum width of text block
     pub min_width: Option<Pt>,
+    /// Maximum height of the text block. Only used when [TextStyle::overflow] is not [TextOverflow::Visible]
+    ///
+    /// ## Note
+    /// This is not a pdf feature.
+    pub max_height: Option<Pt>,
+    /// What happens to text that does not fit in [TextStyle::max_width] and [TextStyle::max_height]
+    pub overflow: TextOverflow,
+    /// Changes the style of a [TextBlock](super::TextBlock) so its content fits in a box. See [TextFit]
+    ///
+    /// ## Note
+    /// This is not a pdf feature.
+    pub fit: Option<TextFit>,
+}
+/// What happens to text that exceeds the max width or height of its [TextStyle]
+///
+/// With a [TextStyle::max_height] lines are wrapped and the lines that do not fit are removed.
+/// Without one each line is cut at [TextStyle::max_width] instead of wrapping
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum TextOverflow {
+    /// Lines are wrapped and drawn past the max height
+    #[default]
+    Visible,
+    /// The text that does not fit is removed
+    Truncate,
+    /// The text that does not fit is removed and replaced with [ELLIPSIS](super::ELLIPSIS)
+    Ellipsis,
+}
+impl TextOverflow {
+    pub fn is_visible(&self) -> bool {
+        matches!(self, TextOverflow::Visible)
+    }
 }
 
+/// How text is painted. Written with [TextOperations::TextRenderingMode]
+///
+/// The clip modes add the text to the clipping path once the text object ends.
+/// The clip stays active until the graphics state is restored. Use [ClipRegion::text](crate::graphics::ClipRegion::text) to limit it to a group
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum TextRenderingMode {
+    #[default]
+    Fill,
+    /// Only the outline of the glyphs is drawn
+    Stroke,
+    FillStroke,
+    /// The text is neither filled nor stroked but it can still be selected and searched.
+    ///
+    /// Used to place a searchable text layer over a scanned image
+    Invisible,
+    FillClip,
+    StrokeClip,
+    FillStrokeClip,
+    /// Only adds the text to the clipping path
+    Clip,
+}
+impl TextRenderingMode {
+    /// The value of the `Tr` operand
+    pub fn value(&self) -> i64 {
+        match self {
+            TextRenderingMode::Fill => 0,
+            TextRenderingMode::Stroke => 1,
+            TextRenderingMode::FillStroke => 2,
+            TextRenderingMode::Invisible => 3,
+            TextRenderingMode::FillClip => 4,
+            TextRenderingMode::StrokeClip => 5,
+            TextRenderingMode::FillStrokeClip => 6,
+            TextRenderingMode::Clip => 7,
+        }
+    }
+    /// True if the text is added to the clipping path
+    pub fn is_clip(&self) -> bool {
+        matches!(
+            self,
+            TextRenderingMode::FillClip
+                | TextRenderingMode::StrokeClip
+                | TextRenderingMode::FillStrokeClip
+                | TextRenderingMode::Clip
+        )
+    }
+}
+/// Stroke width of [TextSynthesis::bold] as a fraction of the font size
+pub const SYNTHETIC_BOLD_STROKE: f32 = 0.03;
+/// Horizontal skew of [TextSynthesis::oblique]. The tangent of 12 degrees
+pub const SYNTHETIC_OBLIQUE_SKEW: f32 = 0.2126;
+/// Font size of the lowercase letters of [TextSynthesis::small_caps] as a fraction of the font size
+pub const SMALL_CAPS_SCALE: f32 = 0.7;
+/// Styles faked from the regular face of a font. For fonts without a bold or italic face
+///
+/// Use a real face when there is one. Synthesized styles are not as readable
+///
+/// ```rust
+/// use tux_pdf::graphics::text::{TextBlock, TextStyle, TextSynthesis};
+/// let heading = TextBlock::from("Chapter One").with_style(TextStyle {
+///     synthesis: TextSynthesis::default().with_bold().with_small_caps(),
+///     ..Default::default()
+/// });
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct TextSynthesis {
+    /// The glyphs are filled and stroked with the fill color. See [SYNTHETIC_BOLD_STROKE]
+    ///
+    /// Without a fill color or outline color the outline is black
+    ///
+    /// Every glyph advances by the stroke width so the glyphs do not run into each other
+    pub bold: bool,
+    /// The glyphs are slanted to the right. See [SYNTHETIC_OBLIQUE_SKEW]
+    pub oblique: bool,
+    /// Lowercase letters are drawn as smaller uppercase letters. See [SMALL_CAPS_SCALE]
+    pub small_caps: bool,
+}
+impl TextSynthesis {
+    pub fn with_bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+    pub fn with_oblique(mut self) -> Self {
+        self.oblique = true;
+        self
+    }
+    pub fn with_small_caps(mut self) -> Self {
+        self.small_caps = true;
+        self
+    }
+    /// Width of the stroke around the glyphs. Zero if not bold
+    pub fn bold_stroke_width(&self, font_size: Pt) -> Pt {
+        if self.bold {
+            font_size * SYNTHETIC_BOLD_STROKE
+        } else {
+            Pt::default()
+        }
+    }
+    /// The `c` value of the text matrix. Zero if not oblique
+    pub fn skew(&self) -> f32 {
+        if self.oblique {
+            SYNTHETIC_OBLIQUE_SKEW
+        } else {
+            0f32
+        }
+    }
+    /// How far the top of the last glyph leans past its advance
+    pub fn oblique_overhang(&self, font_size: Pt) -> Pt {
+        font_size * self.skew()
+    }
+}
+impl TextStyle {
+    pub fn with_rendering_mode(mut self, mode: TextRenderingMode) -> Self {
+        self.rendering_mode = Some(mode);
+        self
+    }
+    pub fn with_overflow(mut self, overflow: TextOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+    pub fn with_tab_stops(mut self, tab_stops: Vec<TabStop>) -> Self {
+        self.tab_stops = tab_stops;
+        self
+    }
+    /// The distance to move up from the baseline of a line with the given height to the baseline of the line above it
+    pub fn line_advance(&self, line_height: Pt) -> Pt {
+        self.leading
+            .unwrap_or_else(|| line_height + self.line_spacing.unwrap_or_default())
+    }
+}
+/// Formatting for a paragraph within a [TextBlockContent](super::TextBlockContent)
+///
+/// Unset values fall back to [TextStyle::paragraph]
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct ParagraphStyle {
+    /// Extra space above the paragraph. Not applied to the first line of a block
+    pub space_before: Option<Pt>,
+    /// Extra space below the paragraph. Not applied to the last line of a block
+    pub space_after: Option<Pt>,
+    /// Indentation of the first line of the paragraph
+    pub first_line_indent: Option<Pt>,
+}
+impl ParagraphStyle {
+    pub fn with_space_before(mut self, space: Pt) -> Self {
+        self.space_before = Some(space);
+        self
+    }
+    pub fn with_space_after(mut self, space: Pt) -> Self {
+        self.space_after = Some(space);
+        self
+    }
+    pub fn with_first_line_indent(mut self, indent: Pt) -> Self {
+        self.first_line_indent = Some(indent);
+        self
+    }
+    /// Fills any unset values from `defaults`
+    pub fn with_defaults(&self, defaults: Option<&ParagraphStyle>) -> ParagraphStyle {
+        let Some(defaults) = defaults else {
+            return *self;
+        };
+        ParagraphStyle {
+            space_before: self.space_before.or(defaults.space_before),
+            space_after: self.space_after.or(defaults.space_after),
+            first_line_indent: self.first_line_indent.or(defaults.first_line_indent),
+        }
+    }
+}
+/// How the text after a tab is placed relative to its [TabStop]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum TabAlignment {
+    /// The text starts at the stop
+    #[default]
+    Left,
+    /// The text ends at the stop
+    Right,
+    /// The text is centered on the stop
+    Center,
+    /// The first occurrence of the separator is placed at the stop.
+    ///
+    /// Text without the separator ends at the stop
+    Decimal(char),
+}
+/// A position a tab advances to. See [TextStyle::tab_stops]
+///
+/// ```rust
+/// use tux_pdf::{graphics::text::{TabStop, TextBlock, TextStyle}, units::Pt};
+/// let style = TextStyle {
+///     tab_stops: vec![TabStop::right(Pt(200.0)).with_leader('.')],
+///     ..Default::default()
+/// };
+/// let contents = TextBlock::from("Introduction\t1\nGetting Started\t12").with_style(style);
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct TabStop {
+    pub position: Pt,
+    pub alignment: TabAlignment,
+    /// Repeated to fill the space before the stop. Such as `.` for dot leaders
+    pub leader: Option<char>,
+}
+impl TabStop {
+    pub fn left(position: Pt) -> Self {
+        Self {
+            position,
+            ..Default::default()
+        }
+    }
+    pub fn right(position: Pt) -> Self {
+        Self {
+            position,
+            alignment: TabAlignment::Right,
+            ..Default::default()
+        }
+    }
+    pub fn center(position: Pt) -> Self {
+        Self {
+            position,
+            alignment: TabAlignment::Center,
+            ..Default::default()
+        }
+    }
+    pub fn decimal(position: Pt, separator: char) -> Self {
+        Self {
+            position,
+            alignment: TabAlignment::Decimal(separator),
+            ..Default::default()
+        }
+    }
+    pub fn with_leader(mut self, leader: char) -> Self {
+        self.leader = Some(leader);
+        self
+    }
+}
 impl HasColorParams for TextStyle {
     fn set_fill_color(&mut self, color: Color) {
         self.fill_color = Some(color);
@@ -81,6 +373,29 @@ impl HasColorParams for TextStyle {
         self.outline_color.take()
     }
 }
+impl TextStyle {
+    /// Resets the text state parameters this style changed back to their defaults.
+    ///
+    /// Used after clipping text where the graphics state can not be restored
+    pub(crate) fn reset_text_state(&self, writer: &mut OperationWriter) {
+        if self.text_rise.is_some() {
+            writer.add_operation(TextOperations::TextRise, vec![Pt(0.0).into()]);
+        }
+        if self.character_spacing.is_some() || self.synthesis.bold {
+            writer.add_operation(TextOperations::CharacterSpace, vec![Pt(0.0).into()]);
+        }
+        if self.word_spacing.is_some() {
+            writer.add_operation(TextOperations::WordSpace, vec![Pt(0.0).into()]);
+        }
+        if self.synthesis.bold {
+            writer.add_operation(OperationKeys::SetLineWidth, vec![Pt(1.0).into()]);
+        }
+        writer.add_operation(
+            TextOperations::TextRenderingMode,
+            vec![TextRenderingMode::Fill.value().into()],
+        );
+    }
+}
 impl PdfObjectType for TextStyle {
     fn write(
         self,
@@ -89,22 +404,44 @@ impl PdfObjectType for TextStyle {
     ) -> Result<(), TuxPdfError> {
         writer.add_operation(
             TextOperations::TextFont,
-            vec![self.font_ref.into(), self.font_size.into()],
+            vec![
+                resources.fonts.resource_name(&self.font_ref),
+                self.font_size.into(),
+            ],
         );
         if let Some(text_rise) = self.text_rise {
             writer.add_operation(TextOperations::TextRise, vec![text_rise.into()]);
         }
-        if let Some(character_spacing) = self.character_spacing {
+        let bold_stroke = self.synthesis.bold_stroke_width(self.font_size);
+        if self.character_spacing.is_some() || self.synthesis.bold {
             writer.add_operation(
                 TextOperations::CharacterSpace,
-                vec![character_spacing.into()],
+                vec![(self.character_spacing.unwrap_or_default() + bold_stroke).into()],
             );
         }
         if let Some(word_spacing) = self.word_spacing {
             writer.add_operation(TextOperations::WordSpace, vec![word_spacing.into()]);
         }
+        let mut rendering_mode = self.rendering_mode;
+        let mut outline_color = self.outline_color;
+        if self.synthesis.bold {
+            // Strokes the outline of filled text with the fill color to thicken it
+            if matches!(rendering_mode, None | Some(TextRenderingMode::Fill)) {
+                rendering_mode = Some(TextRenderingMode::FillStroke);
+                // The outline can not follow an unset fill color so it is black
+                outline_color =
+                    outline_color.or_else(|| Some(self.fill_color.clone().unwrap_or(BLACK_RGB)));
+            }
+            writer.add_operation(OperationKeys::SetLineWidth, vec![bold_stroke.into()]);
+        }
+        if let Some(rendering_mode) = rendering_mode {
+            writer.add_operation(
+                TextOperations::TextRenderingMode,
+                vec![rendering_mode.value().into()],
+            );
+        }
         let color_writer = ColorWriter {
-            outline_color: self.outline_color.map(Cow::Owned),
+            outline_color: outline_color.map(Cow::Owned),
             fill_color: self.fill_color.map(Cow::Owned),
         };
         color_writer.write(resources, writer)?;
@@ -134,15 +471,24 @@ impl Default for TextStyle {
     fn default() -> Self {
         Self {
             font_size: Pt(12.0),
-            font_ref: FontRef::Builtin(BuiltinFont::Helvetica),
+            font_ref: FontRef::Default,
             fill_color: None,
             outline_color: None,
             word_spacing: None,
             line_spacing: None,
+            leading: None,
+            paragraph: None,
+            tab_stops: Vec::new(),
             max_width: None,
             character_spacing: None,
             text_rise: None,
+            tracking: None,
+            rendering_mode: None,
+            synthesis: TextSynthesis::default(),
             min_width: None,
+            max_height: None,
+            overflow: TextOverflow::Visible,
+            fit: None,
         }
     }
 }
@@ -157,6 +503,7 @@ pub struct PartialTextStyle {
     pub word_spacing: Option<Pt>,
 
     pub max_width: Option<Pt>,
+    pub overflow: Option<TextOverflow>,
 }
 impl IsEmpty for PartialTextStyle {
     fn is_empty(&self) -> bool {
@@ -166,6 +513,7 @@ impl IsEmpty for PartialTextStyle {
             && self.outline_color.is_none()
             && self.word_spacing.is_none()
             && self.max_width.is_none()
+            && self.overflow.is_none()
     }
 }
 pub type PartialOrFullTextStyle = PartailOrFull<PartialTextStyle>;
@@ -198,6 +546,9 @@ impl PartialStruct for PartialTextStyle {
         if let Some(max_width) = self.max_width {
             new.max_width = Some(max_width);
         }
+        if let Some(overflow) = self.overflow {
+            new.overflow = overflow;
+        }
         Cow::Owned(new)
     }
 }
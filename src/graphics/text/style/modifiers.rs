@@ -14,6 +14,40 @@ pub enum TextModifier {
     TextRise(Pt),
     CharacterSpacing(Pt),
     WordSpacing(Pt),
+    /// Thousandths of the font size added after every glyph. See [TextStyle::tracking](super::TextStyle::tracking)
+    Tracking(f32),
+    /// Shrinks the text and raises it above the baseline.
+    ///
+    /// Relative to the font size at the point the modifier is applied.
+    /// See [SCRIPT_FONT_SCALE] and [SUPERSCRIPT_RISE]
+    Superscript,
+    /// Shrinks the text and lowers it below the baseline.
+    ///
+    /// Relative to the font size at the point the modifier is applied.
+    /// See [SCRIPT_FONT_SCALE] and [SUBSCRIPT_RISE]
+    Subscript,
+}
+/// Font size of superscript and subscript text as a fraction of the current font size
+pub const SCRIPT_FONT_SCALE: f32 = 0.58;
+/// Text rise of superscript text as a fraction of the current font size
+pub const SUPERSCRIPT_RISE: f32 = 0.33;
+/// Text rise of subscript text as a fraction of the current font size
+pub const SUBSCRIPT_RISE: f32 = -0.14;
+
+/// The font size and text rise for superscript or subscript text based on the current font size
+fn script_size_and_rise(current_font_size: Pt, rise: f32) -> (Pt, Pt) {
+    (
+        current_font_size * SCRIPT_FONT_SCALE,
+        current_font_size * rise,
+    )
+}
+impl TextModifier {
+    fn script_rise_fraction(&self) -> f32 {
+        match self {
+            TextModifier::Subscript => SUBSCRIPT_RISE,
+            _ => SUPERSCRIPT_RISE,
+        }
+    }
 }
 
 pub(crate) fn write_modifiers<'state, 'resources>(
@@ -38,13 +72,26 @@ pub(crate) fn write_modifiers<'state, 'resources>(
                 writer.add_operation(TextOperations::TextRise, vec![rise.into()]);
             }
             TextModifier::CharacterSpacing(spacing) => {
+                // Written once the state is built so synthesized bold spacing can be added
                 updating_state.character_spacing = Some(spacing);
-                writer.add_operation(TextOperations::CharacterSpace, vec![spacing.into()]);
             }
             TextModifier::WordSpacing(spacing) => {
                 updating_state.word_spacing = Some(spacing);
                 writer.add_operation(TextOperations::WordSpace, vec![spacing.into()]);
             }
+            TextModifier::Tracking(tracking) => {
+                updating_state.tracking = Some(tracking);
+            }
+            TextModifier::Superscript | TextModifier::Subscript => {
+                let current_size = updating_state
+                    .font_size
+                    .unwrap_or(updating_state.original.font_size);
+                let (size, rise) =
+                    script_size_and_rise(current_size, modifier.script_rise_fraction());
+                updating_state.font_size = Some(size);
+                updating_state.text_rise = Some(rise);
+                writer.add_operation(TextOperations::TextRise, vec![rise.into()]);
+            }
         }
     }
     if let Some(new_state) = updating_state.build(Some(writer))? {
@@ -79,6 +126,18 @@ pub(crate) fn state_from_modifiers<'state, 'resources>(
             TextModifier::WordSpacing(spacing) => {
                 block_state.word_spacing = Some(*spacing);
             }
+            TextModifier::Tracking(tracking) => {
+                block_state.tracking = Some(*tracking);
+            }
+            TextModifier::Superscript | TextModifier::Subscript => {
+                let current_size = block_state
+                    .font_size
+                    .unwrap_or(block_state.original.font_size);
+                let (size, rise) =
+                    script_size_and_rise(current_size, modifier.script_rise_fraction());
+                block_state.font_size = Some(size);
+                block_state.text_rise = Some(rise);
+            }
         }
     }
     if let Some(new_state) = block_state.build(None)? {
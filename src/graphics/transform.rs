@@ -0,0 +1,94 @@
+use crate::{TuxPdfError, document::PdfResources};
+
+use super::{LayerType, OperationWriter, PdfObject, PdfObjectType, primitives::ctm::CurTransMat};
+
+/// A group of any pdf objects drawn with a transformation applied
+///
+/// The transformation is applied with `cm` and removed afterwards by restoring the graphics state.
+/// Used to move, scale or rotate content after it has been laid out.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TransformedGroup {
+    /// Multiplied together in order
+    pub transform: Vec<CurTransMat>,
+    pub contents: Vec<PdfObject>,
+}
+impl TransformedGroup {
+    pub fn new(transform: impl Into<Vec<CurTransMat>>) -> Self {
+        Self {
+            transform: transform.into(),
+            contents: Vec::new(),
+        }
+    }
+    pub fn with_contents(mut self, contents: Vec<PdfObject>) -> Self {
+        self.contents = contents;
+        self
+    }
+}
+impl LayerType for TransformedGroup {
+    fn add_to_layer(&mut self, object: impl Into<PdfObject>) -> Result<(), TuxPdfError> {
+        self.contents.push(object.into());
+        Ok(())
+    }
+}
+impl From<TransformedGroup> for PdfObject {
+    fn from(group: TransformedGroup) -> Self {
+        PdfObject::Transformed(group)
+    }
+}
+impl PdfObjectType for TransformedGroup {
+    fn write(
+        self,
+        resources: &PdfResources,
+        writer: &mut OperationWriter,
+    ) -> Result<(), TuxPdfError> {
+        writer.save_graphics_state();
+        if !self.transform.is_empty() {
+            self.transform.write(resources, writer)?;
+        }
+        for object in self.contents {
+            object.write(resources, writer)?;
+        }
+        writer.restore_graphics_state();
+        Ok(())
+    }
+    fn calculate_number_of_pdf_objects(&self) -> usize {
+        self.contents
+            .iter()
+            .map(|object| object.calculate_number_of_pdf_objects())
+            .sum::<usize>()
+            + 3
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::TransformedGroup;
+    use crate::{
+        document::PdfResources,
+        graphics::{
+            OperationWriter, PdfObjectType, PdfPosition, primitives::ctm::CurTransMat,
+            shapes::OutlineRect, size::Size,
+        },
+        units::UnitType,
+    };
+
+    #[test]
+    fn wraps_contents_in_cm() {
+        let mut group = TransformedGroup::new(vec![CurTransMat::Position(PdfPosition::new(
+            10f32.pt(),
+            20f32.pt(),
+        ))]);
+        group.contents.push(
+            OutlineRect {
+                position: PdfPosition::default(),
+                size: Size::new(5f32.pt(), 5f32.pt()),
+            }
+            .into(),
+        );
+        let mut writer = OperationWriter::default();
+        group.write(&PdfResources::default(), &mut writer).unwrap();
+        let operations = writer.operations();
+        assert_eq!(operations.first().unwrap().operation, "q");
+        assert_eq!(operations[1].operation, "cm");
+        assert_eq!(operations.last().unwrap().operation, "Q");
+    }
+}
@@ -0,0 +1,334 @@
+//! Tiles the same sized item across as many pages as needed. Such as tickets, badges and name cards
+//!
+//! Every record is drawn by a [PdfComponent] or a closure in its own tile. Cut marks show where to cut the sheets apart
+//!
+//! ```rust
+//! use tux_pdf::{
+//!     document::{BuiltinFont, PdfDocument},
+//!     graphics::{TextBlock, size::Size},
+//!     layouts::batch::{BatchLayout, CutMarks},
+//!     units::UnitType,
+//! };
+//! let mut document = PdfDocument::new("Badges");
+//! document.font_map().register_builtin_font(BuiltinFont::Helvetica);
+//! let attendees = ["Ada Lovelace", "Grace Hopper", "Alan Turing"];
+//! let pages = BatchLayout::new(Size::new(3f32.inch().pt(), 2f32.inch().pt()))
+//!     .with_cut_marks(CutMarks::default())
+//!     .render(&mut document, attendees, TextBlock::from)?;
+//! assert_eq!(pages, 1);
+//! # Ok::<(), tux_pdf::TuxPdfError>(())
+//! ```
+use crate::{
+    TuxPdfError,
+    document::PdfDocument,
+    graphics::{
+        Anchor, GraphicStyles, GraphicsGroup, LayerType, Margin, PdfObject, PdfPosition,
+        color::{BLACK_RGB, Color},
+        primitives::StraightLine,
+        shapes::OutlineRect,
+        size::Size,
+    },
+    page::{PdfPage, page_sizes::A4},
+    units::{Pt, UnitType},
+};
+
+use super::{LayoutError, NewPage, PdfComponent, grid::GridLayout};
+
+/// Short lines drawn outside of the tiles in line with their edges
+#[derive(Debug, Clone, PartialEq)]
+pub struct CutMarks {
+    pub length: Pt,
+    /// Space between the tiles and the marks
+    pub offset: Pt,
+    pub line_width: Pt,
+    pub color: Color,
+}
+impl Default for CutMarks {
+    fn default() -> Self {
+        Self {
+            length: 9f32.pt(),
+            offset: 3f32.pt(),
+            line_width: 0.25f32.pt(),
+            color: BLACK_RGB,
+        }
+    }
+}
+impl CutMarks {
+    /// Space the marks need around the tiles
+    fn reach(&self) -> Pt {
+        self.offset + self.length
+    }
+    /// Marks above and below every column edge and left and right of every row edge
+    fn draw(&self, grid: &GridLayout) -> GraphicsGroup {
+        let mut x_edges = Vec::with_capacity(grid.columns() * 2);
+        for column in 0..grid.columns() {
+            let cell = grid.cell_rect(0, column);
+            x_edges.extend([cell.position.x, cell.upper_right().x]);
+        }
+        let mut y_edges = Vec::with_capacity(grid.rows() * 2);
+        for row in 0..grid.rows() {
+            let cell = grid.cell_rect(row, 0);
+            y_edges.extend([cell.position.y, cell.upper_right().y]);
+        }
+        // Tiles without a gap share an edge
+        x_edges.dedup();
+        y_edges.dedup();
+
+        let area = grid.area;
+        let top = area.upper_right().y;
+        let right = area.upper_right().x;
+        let mut group = GraphicsGroup::new().with_styles(GraphicStyles {
+            line_width: Some(self.line_width),
+            outline_color: Some(self.color.clone()),
+            ..Default::default()
+        });
+        for x in x_edges {
+            group.add_item(StraightLine::from(vec![
+                PdfPosition::new(x, top + self.offset),
+                PdfPosition::new(x, top + self.reach()),
+            ]));
+            group.add_item(StraightLine::from(vec![
+                PdfPosition::new(x, area.position.y - self.offset),
+                PdfPosition::new(x, area.position.y - self.reach()),
+            ]));
+        }
+        for y in y_edges {
+            group.add_item(StraightLine::from(vec![
+                PdfPosition::new(area.position.x - self.offset, y),
+                PdfPosition::new(area.position.x - self.reach(), y),
+            ]));
+            group.add_item(StraightLine::from(vec![
+                PdfPosition::new(right + self.offset, y),
+                PdfPosition::new(right + self.reach(), y),
+            ]));
+        }
+        group
+    }
+}
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BatchStyles {
+    /// Space between columns of tiles
+    pub column_gap: Pt,
+    /// Space between rows of tiles
+    pub row_gap: Pt,
+    pub cut_marks: Option<CutMarks>,
+}
+/// Draws one tile per record. Tiles fill each page from left to right and top to bottom
+///
+/// As many tiles as fit are centered in the [content area](PdfPage::content_area) of every page.
+/// Room for the cut marks is left around the tiles
+#[derive(Debug, Clone)]
+pub struct BatchLayout {
+    /// The size of one tile
+    pub item_size: Size,
+    pub styles: BatchStyles,
+    /// Creates every page. Defaults to A4 pages with a half inch margin
+    pub new_page: NewPage<PdfPage>,
+}
+impl BatchLayout {
+    pub fn new(item_size: Size) -> Self {
+        Self {
+            item_size,
+            styles: BatchStyles::default(),
+            new_page: NewPage::new(|_| {
+                Ok(PdfPage::new_from_page_size(A4).with_margin(Margin::all(36f32.pt())))
+            }),
+        }
+    }
+    pub fn with_styles(mut self, styles: BatchStyles) -> Self {
+        self.styles = styles;
+        self
+    }
+    /// Space between the columns and the rows of tiles
+    pub fn with_gutter(mut self, column_gap: Pt, row_gap: Pt) -> Self {
+        self.styles.column_gap = column_gap;
+        self.styles.row_gap = row_gap;
+        self
+    }
+    pub fn with_cut_marks(mut self, cut_marks: CutMarks) -> Self {
+        self.styles.cut_marks = Some(cut_marks);
+        self
+    }
+    pub fn with_new_page(
+        mut self,
+        new_page: impl FnMut(&mut PdfDocument) -> Result<PdfPage, TuxPdfError> + Send + 'static,
+    ) -> Self {
+        self.new_page = NewPage::new(new_page);
+        self
+    }
+    /// The tiles that fit on the page
+    pub fn grid_for(&self, page: &PdfPage) -> Result<GridLayout, TuxPdfError> {
+        let reach = self
+            .styles
+            .cut_marks
+            .as_ref()
+            .map(CutMarks::reach)
+            .unwrap_or_default();
+        let available = page.content_area().inset(Margin::all(reach));
+        let count = |available: Pt, item: Pt, gap: Pt| {
+            if item.0 <= 0f32 {
+                return 0;
+            }
+            ((available + gap) / (item + gap)).0.floor().max(0f32) as usize
+        };
+        let columns = count(
+            available.size.width,
+            self.item_size.width,
+            self.styles.column_gap,
+        );
+        let rows = count(
+            available.size.height,
+            self.item_size.height,
+            self.styles.row_gap,
+        );
+        if columns == 0 || rows == 0 {
+            return Err(LayoutError::BatchItemDoesNotFit.into());
+        }
+        let tiles = Size::new(
+            self.item_size.width * columns as f32 + self.styles.column_gap * (columns - 1) as f32,
+            self.item_size.height * rows as f32 + self.styles.row_gap * (rows - 1) as f32,
+        );
+        Ok(GridLayout::new(
+            rows,
+            columns,
+            tiles.aligned_within(&available, Anchor::Center),
+        )
+        .with_gutter(self.styles.column_gap, self.styles.row_gap))
+    }
+    /// Draws every record with the component returned by `component` and adds the pages to the document
+    ///
+    /// Returns the number of pages added
+    pub fn render<T, C>(
+        &self,
+        document: &mut PdfDocument,
+        records: impl IntoIterator<Item = T>,
+        mut component: impl FnMut(T) -> C,
+    ) -> Result<usize, TuxPdfError>
+    where
+        C: PdfComponent,
+    {
+        self.render_with(document, records, |document, record, area| {
+            component(record).render(document, area)
+        })
+    }
+    /// Draws every record with `draw` and adds the pages to the document
+    ///
+    /// `draw` is given the area of the tile. Returns the number of pages added
+    pub fn render_with<T>(
+        &self,
+        document: &mut PdfDocument,
+        records: impl IntoIterator<Item = T>,
+        mut draw: impl FnMut(&PdfDocument, T, OutlineRect) -> Result<Vec<PdfObject>, TuxPdfError>,
+    ) -> Result<usize, TuxPdfError> {
+        let mut records = records.into_iter().peekable();
+        let mut pages = 0;
+        while records.peek().is_some() {
+            let mut page = self.new_page.create(document)?;
+            document.page_created(&mut page, 0);
+            let grid = self.grid_for(&page)?;
+            let cells = grid.rows() * grid.columns();
+            for (index, record) in records.by_ref().take(cells).enumerate() {
+                let area = grid.cell_rect(index / grid.columns(), index % grid.columns());
+                for object in draw(document, record, area)? {
+                    page.add_to_layer(object)?;
+                }
+            }
+            if let Some(cut_marks) = &self.styles.cut_marks {
+                page.add_to_layer(cut_marks.draw(&grid))?;
+            }
+            document.add_page(page);
+            pages += 1;
+        }
+        Ok(pages)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::{BatchLayout, CutMarks};
+    use crate::{
+        document::PdfDocument,
+        graphics::{PdfPosition, size::Size},
+        layouts::LayoutError,
+        page::PdfPage,
+        units::UnitType,
+    };
+
+    fn page() -> PdfPage {
+        PdfPage::new_from_page_size(Size::new(300f32.pt(), 200f32.pt()))
+    }
+    #[test]
+    fn tiles_per_page() -> anyhow::Result<()> {
+        let layout =
+            BatchLayout::new(Size::new(90f32.pt(), 90f32.pt())).with_gutter(10f32.pt(), 10f32.pt());
+        let grid = layout.grid_for(&page())?;
+        assert_eq!((grid.rows(), grid.columns()), (2, 3));
+        // Centered on the page
+        assert_eq!(grid.area.position, PdfPosition::new(5f32.pt(), 5f32.pt()));
+
+        // The cut marks need room
+        let grid = layout
+            .with_cut_marks(CutMarks::default())
+            .grid_for(&page())?;
+        assert_eq!((grid.rows(), grid.columns()), (1, 2));
+
+        let too_large = BatchLayout::new(Size::new(400f32.pt(), 10f32.pt())).grid_for(&page());
+        assert!(matches!(
+            too_large,
+            Err(crate::TuxPdfError::LayoutError(
+                LayoutError::BatchItemDoesNotFit
+            ))
+        ));
+        Ok(())
+    }
+    #[test]
+    fn records_continue_on_new_pages() -> anyhow::Result<()> {
+        let mut document = PdfDocument::new("Tickets");
+        let layout = BatchLayout::new(Size::new(90f32.pt(), 90f32.pt()))
+            .with_gutter(10f32.pt(), 10f32.pt())
+            .with_cut_marks(CutMarks::default())
+            .with_new_page(|_| Ok(page()));
+        let mut areas = Vec::new();
+        let pages = layout.render_with(&mut document, 0..5, |_, record, area| {
+            areas.push((record, area));
+            Ok(Vec::new())
+        })?;
+        // Two tiles fit on a page
+        assert_eq!(pages, 3);
+        assert_eq!(document.number_of_pages(), 3);
+        assert_eq!(areas.len(), 5);
+        assert_eq!(areas[0].1.position.y, areas[1].1.position.y);
+        assert!(areas[0].1.position.x < areas[1].1.position.x);
+        // The third record is the first tile of the second page
+        assert_eq!(areas[2].1, areas[0].1);
+
+        assert_eq!(
+            layout.render_with(&mut document, 0..0, |_, _, _| Ok(Vec::new()))?,
+            0
+        );
+        Ok(())
+    }
+    #[test]
+    fn cut_marks_at_tile_edges() -> anyhow::Result<()> {
+        let cut_marks = CutMarks::default();
+        let layout = BatchLayout::new(Size::new(90f32.pt(), 90f32.pt()))
+            .with_gutter(10f32.pt(), 10f32.pt())
+            .with_cut_marks(cut_marks.clone());
+        let grid = layout.grid_for(&page())?;
+        let marks = cut_marks.draw(&grid);
+        // Four column edges and two row edges. Each has a mark on both sides of the tiles
+        assert_eq!(marks.items.len(), 12);
+        let bounds = marks.bounds().expect("Marks are drawn");
+        assert_eq!(
+            bounds.size.width,
+            grid.area.size.width + cut_marks.reach() * 2f32
+        );
+
+        // Tiles without a gap share the mark between them
+        let grid = BatchLayout::new(Size::new(90f32.pt(), 90f32.pt()))
+            .with_cut_marks(cut_marks.clone())
+            .grid_for(&page())?;
+        assert_eq!(grid.columns(), 3);
+        assert_eq!(cut_marks.draw(&grid).items.len(), 4 * 2 + 2 * 2);
+        Ok(())
+    }
+}
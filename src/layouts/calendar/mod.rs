@@ -0,0 +1,228 @@
+//! Calendar layouts
+//!
+//! [MonthCalendar] calculates the structure of a month. [MonthCalendarLayout] renders it with events.
+//! [YearCalendarLayout] renders all twelve months and [WeekCalendarLayout] renders a week with hourly rows.
+use crate::time_impl::{PdfDate, Weekday};
+mod month;
+mod week;
+mod year;
+pub use month::*;
+pub use week::*;
+pub use year::*;
+
+/// The day a week starts on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WeekStartDay {
+    Sunday,
+    #[default]
+    Monday,
+}
+impl WeekStartDay {
+    /// The days of the week in the order they are displayed
+    pub fn weekdays(&self) -> [Weekday; 7] {
+        let mut day = match self {
+            WeekStartDay::Sunday => Weekday::Sunday,
+            WeekStartDay::Monday => Weekday::Monday,
+        };
+        std::array::from_fn(|_| {
+            let current = day;
+            day = day.succ();
+            current
+        })
+    }
+    /// The first day of the week the date is in
+    pub fn first_day_of_week(&self, date: impl Into<PdfDate>) -> PdfDate {
+        let date = date.into();
+        date.add_days(-(self.column_of(date.weekday()) as i64))
+    }
+    /// The column the weekday is placed in
+    pub fn column_of(&self, weekday: Weekday) -> usize {
+        match self {
+            WeekStartDay::Sunday => weekday.num_days_from_sunday() as usize,
+            WeekStartDay::Monday => weekday.num_days_from_monday() as usize,
+        }
+    }
+}
+/// How the weekday headings are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WeekdayFormat {
+    /// Mon, Tue, Wed
+    #[default]
+    Short,
+    /// Monday, Tuesday, Wednesday
+    Long,
+}
+impl WeekdayFormat {
+    pub fn format(&self, weekday: Weekday) -> &'static str {
+        match self {
+            WeekdayFormat::Short => match weekday {
+                Weekday::Monday => "Mon",
+                Weekday::Tuesday => "Tue",
+                Weekday::Wednesday => "Wed",
+                Weekday::Thursday => "Thu",
+                Weekday::Friday => "Fri",
+                Weekday::Saturday => "Sat",
+                Weekday::Sunday => "Sun",
+            },
+            WeekdayFormat::Long => match weekday {
+                Weekday::Monday => "Monday",
+                Weekday::Tuesday => "Tuesday",
+                Weekday::Wednesday => "Wednesday",
+                Weekday::Thursday => "Thursday",
+                Weekday::Friday => "Friday",
+                Weekday::Saturday => "Saturday",
+                Weekday::Sunday => "Sunday",
+            },
+        }
+    }
+}
+/// Helpers for working with months in chrono
+#[cfg(feature = "chrono")]
+pub trait ChronoSpecialUtils: Sized {
+    /// The first day of the month the date is in
+    fn first_day_of_month(&self) -> Self;
+    /// The last day of the month the date is in
+    fn last_day_of_month(&self) -> Self;
+    /// The number of days in the month the date is in
+    fn days_in_month(&self) -> u32;
+    /// The first day of the week the date is in
+    ///
+    /// None if the week starts before the first date chrono supports
+    fn first_day_of_week(&self, week_start: WeekStartDay) -> Option<Self>;
+}
+#[cfg(feature = "chrono")]
+impl ChronoSpecialUtils for chrono::NaiveDate {
+    fn first_day_of_month(&self) -> Self {
+        use chrono::Datelike;
+        self.with_day(1).expect("Every month has a first day")
+    }
+    fn last_day_of_month(&self) -> Self {
+        use chrono::Datelike;
+        self.with_day(self.days_in_month())
+            .expect("The last day of the month exists")
+    }
+    fn days_in_month(&self) -> u32 {
+        PdfDate::from(*self).days_in_month()
+    }
+    fn first_day_of_week(&self, week_start: WeekStartDay) -> Option<Self> {
+        use chrono::Datelike;
+        let column = week_start.column_of(self.weekday().into());
+        self.checked_sub_days(chrono::Days::new(column as u64))
+    }
+}
+/// The structure of a month split into weeks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonthCalendar {
+    pub year: i32,
+    pub month: u32,
+    pub week_start: WeekStartDay,
+    /// Each week of the month. Days outside of the month are `None`
+    pub weeks: Vec<[Option<PdfDate>; 7]>,
+}
+impl MonthCalendar {
+    /// Calculates the weeks of a month
+    ///
+    /// Returns `None` if the month is invalid
+    pub fn new(year: i32, month: u32, week_start: WeekStartDay) -> Option<Self> {
+        let first_day = PdfDate::new(year, month, 1)?;
+        let mut weeks = Vec::with_capacity(6);
+        let mut week = [None; 7];
+        for day in first_day
+            .iter_days()
+            .take(first_day.days_in_month() as usize)
+        {
+            let column = week_start.column_of(day.weekday());
+            week[column] = Some(day);
+            if column == 6 {
+                weeks.push(std::mem::take(&mut week));
+            }
+        }
+        if week.iter().any(Option::is_some) {
+            weeks.push(week);
+        }
+        Some(Self {
+            year,
+            month,
+            week_start,
+            weeks,
+        })
+    }
+    /// The calendar for the month the date is in
+    pub fn for_date(date: impl Into<PdfDate>, week_start: WeekStartDay) -> Self {
+        let date = date.into();
+        Self::new(date.year(), date.month(), week_start).expect("Date is always a valid month")
+    }
+    pub fn number_of_weeks(&self) -> usize {
+        self.weeks.len()
+    }
+    /// Returns true if the date is within this month
+    pub fn contains(&self, date: impl Into<PdfDate>) -> bool {
+        let date = date.into();
+        date.year() == self.year && date.month() == self.month
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::time_impl::{PdfDate, Weekday};
+
+    use super::{MonthCalendar, WeekStartDay};
+
+    #[test]
+    fn month_weeks() {
+        // September 2024 starts on a Sunday
+        let calendar = MonthCalendar::new(2024, 9, WeekStartDay::Monday).unwrap();
+        assert_eq!(calendar.number_of_weeks(), 6);
+        assert_eq!(calendar.weeks[0][6], PdfDate::new(2024, 9, 1));
+        assert_eq!(calendar.weeks[0][0], None);
+
+        let calendar = MonthCalendar::new(2024, 9, WeekStartDay::Sunday).unwrap();
+        assert_eq!(calendar.number_of_weeks(), 5);
+        assert_eq!(calendar.weeks[0][0], PdfDate::new(2024, 9, 1));
+        assert_eq!(calendar.weeks[4][1], PdfDate::new(2024, 9, 30));
+    }
+    #[test]
+    fn first_day_of_week() {
+        // Wednesday
+        let date = PdfDate::new(2024, 9, 18).unwrap();
+        assert_eq!(
+            WeekStartDay::Monday.first_day_of_week(date),
+            PdfDate::new(2024, 9, 16).unwrap()
+        );
+        assert_eq!(
+            WeekStartDay::Sunday.first_day_of_week(date),
+            PdfDate::new(2024, 9, 15).unwrap()
+        );
+    }
+    #[test]
+    fn week_start_order() {
+        assert_eq!(WeekStartDay::Sunday.weekdays()[0], Weekday::Sunday);
+        assert_eq!(WeekStartDay::Sunday.weekdays()[6], Weekday::Saturday);
+        assert_eq!(WeekStartDay::Monday.weekdays()[6], Weekday::Sunday);
+    }
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_special_utils() {
+        use super::ChronoSpecialUtils;
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 2, 10).unwrap();
+        assert_eq!(date.days_in_month(), 29);
+        assert_eq!(
+            date.first_day_of_month(),
+            chrono::NaiveDate::from_ymd_opt(2024, 2, 1).unwrap()
+        );
+        assert_eq!(
+            date.last_day_of_month(),
+            chrono::NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+        );
+        // Saturday
+        assert_eq!(
+            date.first_day_of_week(WeekStartDay::Monday),
+            chrono::NaiveDate::from_ymd_opt(2024, 2, 5)
+        );
+        // The week of the first date chrono supports starts before it
+        assert_eq!(
+            chrono::NaiveDate::MIN.first_day_of_week(WeekStartDay::Sunday),
+            None
+        );
+    }
+}
@@ -0,0 +1,368 @@
+use std::{borrow::Cow, collections::BTreeMap};
+
+use tracing::debug;
+
+use crate::{
+    TuxPdfError,
+    document::PdfDocument,
+    graphics::{
+        Anchor, GraphicItems, GraphicStyles, GraphicsGroup, HasPosition, LayerType,
+        PartialOrFullTextStyle, PartialTextStyle, PdfPosition, TextBlock, TextBlockContent,
+        TextStyle,
+        color::{BLACK_RGB, Color},
+        shapes::{OutlineRect, PaintedRect},
+        size::{RenderSize, Size},
+        styles::Padding,
+    },
+    layouts::{LayoutItemType, Theme},
+    time_impl::PdfDate,
+    units::{Pt, UnitType},
+};
+
+use super::{MonthCalendar, WeekdayFormat};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarStyles {
+    /// The default text style
+    pub text_styles: TextStyle,
+    /// Overrides for the weekday headings
+    pub header_text_styles: Option<PartialOrFullTextStyle>,
+    /// Overrides for the day numbers
+    pub day_number_text_styles: Option<PartialOrFullTextStyle>,
+    /// Overrides for the hour labels of a [WeekCalendarLayout](super::WeekCalendarLayout)
+    pub hour_text_styles: Option<PartialOrFullTextStyle>,
+    /// Overrides for the event text
+    pub event_text_styles: Option<PartialOrFullTextStyle>,
+    pub weekday_format: WeekdayFormat,
+    pub cell_padding: Padding,
+    pub border_color: Color,
+    pub border_width: Pt,
+    /// Background of the weekday headings
+    pub header_background_color: Option<Color>,
+    /// Background of the cells that are not part of the month
+    pub outside_month_color: Option<Color>,
+    /// Background of the events in a [WeekCalendarLayout](super::WeekCalendarLayout)
+    pub event_background_color: Option<Color>,
+    /// Shown when not all events fit in a day cell.
+    ///
+    /// Rendered as `+{hidden events} {more_indicator}`
+    pub more_indicator: String,
+}
+impl CalendarStyles {
+    /// Merges the overrides with the default text style
+    pub fn text_style_for<'s>(
+        &'s self,
+        overrides: &'s Option<PartialOrFullTextStyle>,
+    ) -> Cow<'s, TextStyle> {
+        match overrides {
+            Some(overrides) => overrides.merge_with_full(&self.text_styles),
+            None => Cow::Borrowed(&self.text_styles),
+        }
+    }
+}
+impl CalendarStyles {
+    /// Weekday headings in the primary color and events in the accent color
+    pub fn from_theme(theme: &Theme) -> Self {
+        let colors = &theme.colors;
+        let on_primary: PartialOrFullTextStyle = PartialTextStyle {
+            font_ref: Some(theme.fonts.heading.clone()),
+            fill_color: Some(colors.on_primary.clone()),
+            ..Default::default()
+        }
+        .into();
+        Self {
+            text_styles: theme.text_style(),
+            header_text_styles: Some(on_primary.clone()),
+            event_text_styles: Some(on_primary),
+            border_color: colors.border.clone(),
+            border_width: theme.border_width,
+            header_background_color: Some(colors.primary.clone()),
+            outside_month_color: Some(colors.table_stripe.clone()),
+            event_background_color: Some(colors.accent.clone()),
+            ..Default::default()
+        }
+    }
+}
+impl Default for CalendarStyles {
+    fn default() -> Self {
+        Self {
+            text_styles: TextStyle::default(),
+            header_text_styles: None,
+            day_number_text_styles: None,
+            hour_text_styles: None,
+            event_text_styles: None,
+            weekday_format: WeekdayFormat::default(),
+            cell_padding: Padding::all(2f32.pt()),
+            border_color: BLACK_RGB,
+            border_width: 1f32.pt(),
+            header_background_color: None,
+            outside_month_color: None,
+            event_background_color: None,
+            more_indicator: "more…".to_owned(),
+        }
+    }
+}
+/// Renders a [MonthCalendar] as a grid with weekday headings, day numbers and events
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonthCalendarLayout {
+    pub calendar: MonthCalendar,
+    /// Events for each day. Rendered in the order they were added
+    pub events: BTreeMap<PdfDate, Vec<TextBlockContent>>,
+    pub styles: CalendarStyles,
+    /// The top left corner of the calendar
+    pub position: PdfPosition,
+    /// The size of the entire calendar including the weekday headings
+    pub size: Size,
+}
+impl MonthCalendarLayout {
+    pub fn new(calendar: MonthCalendar, position: PdfPosition, size: Size) -> Self {
+        Self {
+            calendar,
+            events: BTreeMap::new(),
+            styles: CalendarStyles::default(),
+            position,
+            size,
+        }
+    }
+    pub fn with_styles(mut self, styles: CalendarStyles) -> Self {
+        self.styles = styles;
+        self
+    }
+    /// Adds an event to a day
+    ///
+    /// Events for days outside of the month are ignored when rendering
+    pub fn add_event(&mut self, date: impl Into<PdfDate>, event: impl Into<TextBlockContent>) {
+        self.events
+            .entry(date.into())
+            .or_default()
+            .push(event.into());
+    }
+    /// The events for a day
+    pub fn events_on(&self, date: impl Into<PdfDate>) -> &[TextBlockContent] {
+        self.events
+            .get(&date.into())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+    fn header_height(&self, document: &PdfDocument) -> Result<Pt, TuxPdfError> {
+        let style = self.styles.text_style_for(&self.styles.header_text_styles);
+        let text_height = TextBlockContent::from("Mon")
+            .render_size(document, &style)?
+            .height;
+        Ok(text_height
+            + self
+                .styles
+                .cell_padding
+                .vertical_value()
+                .unwrap_or_default())
+    }
+    /// The size of each day cell
+    pub fn cell_size(&self, document: &PdfDocument) -> Result<Size, TuxPdfError> {
+        let header_height = self.header_height(document)?;
+        let weeks = self.calendar.number_of_weeks().max(1) as f32;
+        Ok(Size {
+            width: self.size.width / 7f32,
+            height: (self.size.height - header_height) / weeks,
+        })
+    }
+    fn render_header<L: LayerType>(
+        &self,
+        header_height: Pt,
+        cell_width: Pt,
+        page: &mut L,
+    ) -> Result<(), TuxPdfError> {
+        let padding = self.styles.cell_padding;
+        let left = padding.left.unwrap_or_default();
+        let bottom = padding.bottom.unwrap_or_default();
+        let style = self.styles.text_style_for(&self.styles.header_text_styles);
+        let header_bottom = self.position.y - header_height;
+        if let Some(background_color) = &self.styles.header_background_color {
+            let background = PaintedRect {
+                position: PdfPosition {
+                    x: self.position.x,
+                    y: header_bottom,
+                },
+                size: Size::new(self.size.width, header_height),
+                ..Default::default()
+            };
+            page.add_to_layer(GraphicsGroup {
+                styles: Some(GraphicStyles {
+                    fill_color: Some(background_color.clone()),
+                    ..Default::default()
+                }),
+                items: vec![background.into()],
+                ..Default::default()
+            })?;
+        }
+        for (column, weekday) in self.calendar.week_start.weekdays().into_iter().enumerate() {
+            let content = TextBlockContent::from(self.styles.weekday_format.format(weekday));
+            let x = self.position.x + cell_width * column as f32 + left;
+            debug!(?weekday, ?x, "Rendering weekday heading");
+            page.add_to_layer(TextBlock {
+                content,
+                style: style.clone().into_owned(),
+                position: PdfPosition {
+                    x,
+                    y: header_bottom + bottom,
+                },
+                draw_as_lines: false,
+            })?;
+        }
+        Ok(())
+    }
+    /// Renders the day number and as many events as fit in the cell
+    fn render_day<L: LayerType>(
+        &self,
+        document: &PdfDocument,
+        day: PdfDate,
+        cell_top_left: PdfPosition,
+        cell_size: Size,
+        page: &mut L,
+    ) -> Result<(), TuxPdfError> {
+        let padding = self.styles.cell_padding;
+        let left = padding.left.unwrap_or_default();
+        let top = padding.top.unwrap_or_default();
+        let bottom = padding.bottom.unwrap_or_default();
+        let content_width = cell_size.width - padding.horizontal_value().unwrap_or_default();
+        let content_bottom = cell_top_left.y - cell_size.height + bottom;
+        let x = cell_top_left.x + left;
+
+        let day_number_style = self
+            .styles
+            .text_style_for(&self.styles.day_number_text_styles);
+        let day_number = TextBlockContent::from(day.day().to_string());
+        let day_number_height = day_number.render_size(document, &day_number_style)?.height;
+        let mut cursor_y = cell_top_left.y - top - day_number_height;
+        page.add_to_layer(TextBlock {
+            content: day_number,
+            style: day_number_style.into_owned(),
+            position: PdfPosition { x, y: cursor_y },
+            draw_as_lines: false,
+        })?;
+
+        let events = self.events_on(day);
+        if events.is_empty() {
+            return Ok(());
+        }
+        let event_style = self.styles.text_style_for(&self.styles.event_text_styles);
+        let more_height = TextBlockContent::from(self.styles.more_indicator.as_str())
+            .render_size(document, &event_style)?
+            .height;
+        for (index, event) in events.iter().enumerate() {
+            let mut event = event.clone();
+            event.apply_max_width(content_width, document, &event_style)?;
+            let event_height = event.render_size(document, &event_style)?.height;
+            let is_last = index + 1 == events.len();
+            // Leave room for the more indicator unless this is the last event
+            let reserved = if is_last { Pt::default() } else { more_height };
+            if cursor_y - event_height - reserved < content_bottom {
+                let hidden = events.len() - index;
+                debug!(?day, ?hidden, "Not all events fit in the cell");
+                page.add_to_layer(TextBlock {
+                    content: format!("+{} {}", hidden, self.styles.more_indicator).into(),
+                    style: event_style.clone().into_owned(),
+                    position: PdfPosition {
+                        x,
+                        y: (cursor_y - more_height).max(content_bottom),
+                    },
+                    draw_as_lines: false,
+                })?;
+                break;
+            }
+            cursor_y -= event_height;
+            page.add_to_layer(TextBlock {
+                content: event,
+                style: event_style.clone().into_owned(),
+                position: PdfPosition { x, y: cursor_y },
+                draw_as_lines: false,
+            })?;
+        }
+        Ok(())
+    }
+}
+impl HasPosition for MonthCalendarLayout {
+    fn position(&self) -> PdfPosition {
+        self.position
+    }
+    fn set_position(&mut self, position: PdfPosition) {
+        self.position = position;
+    }
+}
+impl LayoutItemType for MonthCalendarLayout {
+    fn position_anchor(&self) -> Anchor {
+        Anchor::TopLeft
+    }
+    fn calculate_size(&mut self, _document: &PdfDocument) -> Result<Size, TuxPdfError> {
+        Ok(self.size)
+    }
+    fn render<L: LayerType>(self, document: &PdfDocument, page: &mut L) -> Result<(), TuxPdfError>
+    where
+        Self: Sized,
+    {
+        let header_height = self.header_height(document)?;
+        let cell_size = self.cell_size(document)?;
+        self.render_header(header_height, cell_size.width, page)?;
+
+        let mut outside_month: Vec<GraphicItems> = Vec::new();
+        let mut borders: Vec<GraphicItems> = vec![
+            OutlineRect {
+                position: self.position,
+                size: self.size,
+            }
+            .into(),
+        ];
+        let grid_top = self.position.y - header_height;
+        for (week_index, week) in self.calendar.weeks.iter().enumerate() {
+            for (column, day) in week.iter().enumerate() {
+                let cell_top_left = PdfPosition {
+                    x: self.position.x + cell_size.width * column as f32,
+                    y: grid_top - cell_size.height * week_index as f32,
+                };
+                borders.push(
+                    OutlineRect {
+                        position: cell_top_left,
+                        size: cell_size,
+                    }
+                    .into(),
+                );
+                match day {
+                    Some(day) => {
+                        self.render_day(document, *day, cell_top_left, cell_size, page)?;
+                    }
+                    None => outside_month.push(
+                        PaintedRect {
+                            position: PdfPosition {
+                                x: cell_top_left.x,
+                                y: cell_top_left.y - cell_size.height,
+                            },
+                            size: cell_size,
+                            ..Default::default()
+                        }
+                        .into(),
+                    ),
+                }
+            }
+        }
+        if let Some(outside_month_color) = &self.styles.outside_month_color {
+            page.add_to_layer(GraphicsGroup {
+                styles: Some(GraphicStyles {
+                    fill_color: Some(outside_month_color.clone()),
+                    ..Default::default()
+                }),
+                items: outside_month,
+                ..Default::default()
+            })?;
+        }
+        page.add_to_layer(GraphicsGroup {
+            styles: Some(GraphicStyles {
+                outline_color: Some(self.styles.border_color.clone()),
+                line_width: Some(self.styles.border_width),
+                ..Default::default()
+            }),
+            items: borders,
+            section_name: Some("Calendar".to_owned()),
+            ..Default::default()
+        })?;
+        Ok(())
+    }
+}
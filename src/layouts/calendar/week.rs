@@ -0,0 +1,392 @@
+use std::ops::Range;
+
+use tracing::{debug, warn};
+
+use crate::{
+    TuxPdfError,
+    document::PdfDocument,
+    graphics::{
+        Anchor, ClipRegion, ClippedGroup, GraphicItems, GraphicStyles, GraphicsGroup, HasPosition,
+        LayerType, PdfPosition, TextBlock, TextBlockContent,
+        shapes::{OutlineRect, PaintedRect},
+        size::{RenderSize, Size},
+    },
+    layouts::LayoutItemType,
+    time_impl::{PdfDate, PdfDateTime},
+    units::{Pt, UnitType},
+};
+
+use super::{CalendarStyles, WeekStartDay};
+
+/// An event placed in a [WeekCalendarLayout]
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeekEvent {
+    pub start: PdfDateTime,
+    pub end: PdfDateTime,
+    pub content: TextBlockContent,
+}
+/// A week with a column for each day and a row for each hour
+///
+/// Good for schedules
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeekCalendarLayout {
+    /// The first day shown
+    pub first_day: PdfDate,
+    /// The hours shown. Default is 8 to 18
+    pub hours: Range<u32>,
+    pub events: Vec<WeekEvent>,
+    pub styles: CalendarStyles,
+    /// The width of the column containing the hour labels
+    pub time_column_width: Pt,
+    /// The top left corner of the calendar
+    pub position: PdfPosition,
+    /// The size of the entire calendar including the headings
+    pub size: Size,
+}
+impl WeekCalendarLayout {
+    /// Creates a layout for the week that contains the date
+    pub fn new(
+        date: impl Into<PdfDate>,
+        week_start: WeekStartDay,
+        position: PdfPosition,
+        size: Size,
+    ) -> Self {
+        Self {
+            first_day: week_start.first_day_of_week(date),
+            hours: 8..18,
+            events: Vec::new(),
+            styles: CalendarStyles::default(),
+            time_column_width: 40f32.pt(),
+            position,
+            size,
+        }
+    }
+    pub fn with_styles(mut self, styles: CalendarStyles) -> Self {
+        self.styles = styles;
+        self
+    }
+    pub fn with_hours(mut self, hours: Range<u32>) -> Self {
+        self.hours = hours;
+        self
+    }
+    /// Adds an event
+    ///
+    /// Events outside of the week or hours are ignored. Events partially outside are cut off.
+    pub fn add_event(
+        &mut self,
+        start: impl Into<PdfDateTime>,
+        end: impl Into<PdfDateTime>,
+        content: impl Into<TextBlockContent>,
+    ) {
+        self.events.push(WeekEvent {
+            start: start.into(),
+            end: end.into(),
+            content: content.into(),
+        });
+    }
+    /// The days of the week in the order they are displayed
+    pub fn days(&self) -> [PdfDate; 7] {
+        std::array::from_fn(|index| self.first_day.add_days(index as i64))
+    }
+    fn header_height(&self, document: &PdfDocument) -> Result<Pt, TuxPdfError> {
+        let style = self.styles.text_style_for(&self.styles.header_text_styles);
+        let text_height = TextBlockContent::from("Mon")
+            .render_size(document, &style)?
+            .height;
+        Ok(text_height
+            + self
+                .styles
+                .cell_padding
+                .vertical_value()
+                .unwrap_or_default())
+    }
+    fn day_width(&self) -> Pt {
+        (self.size.width - self.time_column_width) / 7f32
+    }
+    fn hour_height(&self, header_height: Pt) -> Pt {
+        let hours = self.hours.len().max(1) as f32;
+        (self.size.height - header_height) / hours
+    }
+    /// The distance from the top of the grid in hours. Clamped to the visible hours
+    fn hours_from_top(&self, time: PdfDateTime) -> f32 {
+        let hours = time.hour as f32 + time.minute as f32 / 60f32;
+        (hours - self.hours.start as f32).clamp(0f32, self.hours.len() as f32)
+    }
+    fn render_event<L: LayerType>(
+        &self,
+        document: &PdfDocument,
+        event: &WeekEvent,
+        grid_top: Pt,
+        hour_height: Pt,
+        page: &mut L,
+    ) -> Result<(), TuxPdfError> {
+        let Some(column) = self.days().iter().position(|day| *day == event.start.date) else {
+            debug!(?event.start, "Event is outside of the week");
+            return Ok(());
+        };
+        if event.end.date != event.start.date {
+            warn!(?event.start, ?event.end, "Events spanning multiple days are cut off at midnight");
+        }
+        let start = self.hours_from_top(event.start);
+        let end = if event.end.date == event.start.date {
+            self.hours_from_top(event.end)
+        } else {
+            self.hours.len() as f32
+        };
+        if end <= start {
+            debug!(?event.start, "Event is outside of the hours");
+            return Ok(());
+        }
+        let day_width = self.day_width();
+        let top_left = PdfPosition {
+            x: self.position.x + self.time_column_width + day_width * column as f32,
+            y: grid_top - hour_height * start,
+        };
+        let size = Size::new(day_width, hour_height * (end - start));
+        let bottom_left = PdfPosition {
+            x: top_left.x,
+            y: top_left.y - size.height,
+        };
+        let mut items: Vec<GraphicItems> = Vec::with_capacity(2);
+        if self.styles.event_background_color.is_some() {
+            items.push(
+                PaintedRect {
+                    position: bottom_left,
+                    size,
+                    ..Default::default()
+                }
+                .into(),
+            );
+        }
+        items.push(
+            OutlineRect {
+                position: top_left,
+                size,
+            }
+            .into(),
+        );
+        page.add_to_layer(GraphicsGroup {
+            styles: Some(GraphicStyles {
+                fill_color: self.styles.event_background_color.clone(),
+                outline_color: Some(self.styles.border_color.clone()),
+                line_width: Some(self.styles.border_width),
+                ..Default::default()
+            }),
+            items,
+            ..Default::default()
+        })?;
+
+        let padding = self.styles.cell_padding;
+        let event_style = self.styles.text_style_for(&self.styles.event_text_styles);
+        let mut content = event.content.clone();
+        content.apply_max_width(
+            day_width - padding.horizontal_value().unwrap_or_default(),
+            document,
+            &event_style,
+        )?;
+        let content_height = content.render_size(document, &event_style)?.height;
+        // Short events can not fit all of the text. Anything below the event is hidden
+        let mut text = ClippedGroup::new(ClipRegion::rect(bottom_left, size));
+        text.add_to_layer(TextBlock {
+            content,
+            style: event_style.into_owned(),
+            position: PdfPosition {
+                x: top_left.x + padding.left.unwrap_or_default(),
+                y: top_left.y - padding.top.unwrap_or_default() - content_height,
+            },
+            draw_as_lines: false,
+        })?;
+        page.add_to_layer(text)?;
+        Ok(())
+    }
+}
+impl HasPosition for WeekCalendarLayout {
+    fn position(&self) -> PdfPosition {
+        self.position
+    }
+    fn set_position(&mut self, position: PdfPosition) {
+        self.position = position;
+    }
+}
+impl LayoutItemType for WeekCalendarLayout {
+    fn position_anchor(&self) -> Anchor {
+        Anchor::TopLeft
+    }
+    fn calculate_size(&mut self, _document: &PdfDocument) -> Result<Size, TuxPdfError> {
+        Ok(self.size)
+    }
+    fn render<L: LayerType>(self, document: &PdfDocument, page: &mut L) -> Result<(), TuxPdfError>
+    where
+        Self: Sized,
+    {
+        let header_height = self.header_height(document)?;
+        let hour_height = self.hour_height(header_height);
+        let day_width = self.day_width();
+        let grid_top = self.position.y - header_height;
+        let padding = self.styles.cell_padding;
+        let left = padding.left.unwrap_or_default();
+        let bottom = padding.bottom.unwrap_or_default();
+
+        let header_style = self.styles.text_style_for(&self.styles.header_text_styles);
+        let mut borders: Vec<GraphicItems> = vec![
+            OutlineRect {
+                position: self.position,
+                size: self.size,
+            }
+            .into(),
+        ];
+        for (column, day) in self.days().into_iter().enumerate() {
+            let x = self.position.x + self.time_column_width + day_width * column as f32;
+            let heading = format!(
+                "{} {}",
+                self.styles.weekday_format.format(day.weekday()),
+                day.day()
+            );
+            page.add_to_layer(TextBlock {
+                content: heading.into(),
+                style: header_style.clone().into_owned(),
+                position: PdfPosition {
+                    x: x + left,
+                    y: grid_top + bottom,
+                },
+                draw_as_lines: false,
+            })?;
+            borders.push(
+                OutlineRect {
+                    position: PdfPosition {
+                        x,
+                        y: self.position.y,
+                    },
+                    size: Size::new(day_width, self.size.height),
+                }
+                .into(),
+            );
+        }
+
+        let time_style = self.styles.text_style_for(&self.styles.hour_text_styles);
+        for (row, hour) in self.hours.clone().enumerate() {
+            let row_top = grid_top - hour_height * row as f32;
+            let label = TextBlockContent::from(format!("{hour:02}:00"));
+            let label_height = label.render_size(document, &time_style)?.height;
+            page.add_to_layer(TextBlock {
+                content: label,
+                style: time_style.clone().into_owned(),
+                position: PdfPosition {
+                    x: self.position.x + left,
+                    y: row_top - padding.top.unwrap_or_default() - label_height,
+                },
+                draw_as_lines: false,
+            })?;
+            borders.push(
+                OutlineRect {
+                    position: PdfPosition {
+                        x: self.position.x,
+                        y: row_top,
+                    },
+                    size: Size::new(self.size.width, hour_height),
+                }
+                .into(),
+            );
+        }
+        page.add_to_layer(GraphicsGroup {
+            styles: Some(GraphicStyles {
+                outline_color: Some(self.styles.border_color.clone()),
+                line_width: Some(self.styles.border_width),
+                ..Default::default()
+            }),
+            items: borders,
+            section_name: Some("WeekCalendar".to_owned()),
+            ..Default::default()
+        })?;
+
+        for event in &self.events {
+            self.render_event(document, event, grid_top, hour_height, page)?;
+        }
+        Ok(())
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::WeekCalendarLayout;
+    use crate::{
+        document::{BuiltinFont, PdfDocument},
+        graphics::{ClipShape, PartialTextStyle, PdfObject, PdfPosition, TextStyle, size::Size},
+        layouts::{
+            LayoutItemType,
+            calendar::{CalendarStyles, WeekStartDay},
+        },
+        page::{PdfPage, page_sizes::A4},
+        time_impl::{PdfDate, PdfDateTime},
+        units::UnitType,
+    };
+
+    #[test]
+    fn hour_labels_and_clipped_events() -> anyhow::Result<()> {
+        let mut document = PdfDocument::new("Week Calendar");
+        let helvetica = document
+            .resources
+            .fonts
+            .register_builtin_font(BuiltinFont::Helvetica);
+        let day = PdfDate::new(2024, 9, 16).unwrap();
+        let mut layout = WeekCalendarLayout::new(
+            day,
+            WeekStartDay::Monday,
+            PdfPosition::new(0f32.pt(), 500f32.pt()),
+            Size::new(740f32.pt(), 400f32.pt()),
+        )
+        .with_hours(8..10)
+        .with_styles(CalendarStyles {
+            text_styles: TextStyle {
+                font_ref: helvetica,
+                font_size: 10f32.pt(),
+                ..Default::default()
+            },
+            hour_text_styles: Some(
+                PartialTextStyle {
+                    font_size: Some(14f32.pt()),
+                    ..Default::default()
+                }
+                .into(),
+            ),
+            ..Default::default()
+        });
+        let at = |minute| PdfDateTime::new(day, 8, minute, 0).unwrap();
+        layout.add_event(at(0), at(15), "A short event with more text than fits");
+        let header_height = layout.header_height(&document)?;
+        let hour_height = layout.hour_height(header_height);
+
+        let mut page = PdfPage::new_from_page_size(A4);
+        layout.render(&document, &mut page)?;
+
+        let font_sizes: Vec<_> = page
+            .contents
+            .iter()
+            .filter_map(|object| match object {
+                PdfObject::TextBlock(text) => Some(text.style.font_size),
+                _ => None,
+            })
+            .collect();
+        // Seven weekday headings and then the two hour labels
+        assert_eq!(font_sizes.len(), 9);
+        assert!(font_sizes[..7].iter().all(|size| *size == 10f32.pt()));
+        assert!(font_sizes[7..].iter().all(|size| *size == 14f32.pt()));
+
+        let Some(PdfObject::Clipped(event)) = page.contents.last() else {
+            panic!("The event text is not clipped");
+        };
+        let ClipShape::Rect { position, size } = event.clip.shape else {
+            panic!("The event text is not clipped to a rectangle");
+        };
+        let grid_top = 500f32.pt() - header_height;
+        assert_eq!(
+            position,
+            PdfPosition::new(40f32.pt(), grid_top - hour_height * 0.25)
+        );
+        assert_eq!(size, Size::new(100f32.pt(), hour_height * 0.25));
+        assert!(matches!(
+            event.contents.as_slice(),
+            [PdfObject::TextBlock(_)]
+        ));
+        Ok(())
+    }
+}
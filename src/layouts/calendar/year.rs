@@ -0,0 +1,147 @@
+use tracing::debug;
+
+use crate::{
+    TuxPdfError,
+    document::PdfDocument,
+    graphics::{
+        Anchor, HasPosition, LayerType, PartialOrFullTextStyle, PdfPosition, TextBlock,
+        TextBlockContent,
+        size::{RenderSize, Size},
+    },
+    layouts::LayoutItemType,
+    units::{Pt, UnitType},
+};
+
+use super::{CalendarStyles, MonthCalendar, MonthCalendarLayout, WeekStartDay};
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+/// Twelve small month grids arranged in rows
+#[derive(Debug, Clone, PartialEq)]
+pub struct YearCalendarLayout {
+    pub year: i32,
+    pub week_start: WeekStartDay,
+    /// The number of months in each row
+    ///
+    /// Default is 3
+    pub months_per_row: usize,
+    /// The space between the months
+    pub gap: Pt,
+    /// Styles used for each month
+    pub styles: CalendarStyles,
+    /// Overrides for the month names
+    pub title_text_styles: Option<PartialOrFullTextStyle>,
+    /// The top left corner of the calendar
+    pub position: PdfPosition,
+    /// The size of the entire year
+    pub size: Size,
+}
+impl YearCalendarLayout {
+    pub fn new(year: i32, position: PdfPosition, size: Size) -> Self {
+        Self {
+            year,
+            week_start: WeekStartDay::default(),
+            months_per_row: 3,
+            gap: 10f32.pt(),
+            styles: CalendarStyles::default(),
+            title_text_styles: None,
+            position,
+            size,
+        }
+    }
+    pub fn with_styles(mut self, styles: CalendarStyles) -> Self {
+        self.styles = styles;
+        self
+    }
+    pub fn with_week_start(mut self, week_start: WeekStartDay) -> Self {
+        self.week_start = week_start;
+        self
+    }
+    pub fn with_months_per_row(mut self, months_per_row: usize) -> Self {
+        self.months_per_row = months_per_row;
+        self
+    }
+    /// The size of each month including its title
+    pub fn month_size(&self) -> Size {
+        let months_per_row = self.months_per_row.clamp(1, 12);
+        let rows = 12usize.div_ceil(months_per_row);
+        Size {
+            width: (self.size.width - self.gap * (months_per_row - 1) as f32)
+                / months_per_row as f32,
+            height: (self.size.height - self.gap * (rows - 1) as f32) / rows as f32,
+        }
+    }
+}
+impl HasPosition for YearCalendarLayout {
+    fn position(&self) -> PdfPosition {
+        self.position
+    }
+    fn set_position(&mut self, position: PdfPosition) {
+        self.position = position;
+    }
+}
+impl LayoutItemType for YearCalendarLayout {
+    fn position_anchor(&self) -> Anchor {
+        Anchor::TopLeft
+    }
+    fn calculate_size(&mut self, _document: &PdfDocument) -> Result<Size, TuxPdfError> {
+        Ok(self.size)
+    }
+    fn render<L: LayerType>(self, document: &PdfDocument, page: &mut L) -> Result<(), TuxPdfError>
+    where
+        Self: Sized,
+    {
+        let months_per_row = self.months_per_row.clamp(1, 12);
+        let month_size = self.month_size();
+        let title_style = self.styles.text_style_for(&self.title_text_styles);
+        for (index, month) in (1..=12u32).enumerate() {
+            let Some(calendar) = MonthCalendar::new(self.year, month, self.week_start) else {
+                continue;
+            };
+            let top_left = PdfPosition {
+                x: self.position.x
+                    + (month_size.width + self.gap) * (index % months_per_row) as f32,
+                y: self.position.y
+                    - (month_size.height + self.gap) * (index / months_per_row) as f32,
+            };
+            let name = MONTH_NAMES[index];
+            let title = TextBlockContent::from(name);
+            let title_height = title.render_size(document, &title_style)?.height;
+            debug!(?month, ?top_left, "Rendering month of year");
+            page.add_to_layer(TextBlock {
+                content: title,
+                style: title_style.clone().into_owned(),
+                position: PdfPosition {
+                    x: top_left.x,
+                    y: top_left.y - title_height,
+                },
+                draw_as_lines: false,
+            })?;
+
+            let month_position = PdfPosition {
+                x: top_left.x,
+                y: top_left.y - title_height - self.styles.border_width,
+            };
+            let month_size = Size {
+                width: month_size.width,
+                height: month_size.height - title_height - self.styles.border_width,
+            };
+            MonthCalendarLayout::new(calendar, month_position, month_size)
+                .with_styles(self.styles.clone())
+                .render(document, page)?;
+        }
+        Ok(())
+    }
+}
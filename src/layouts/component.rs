@@ -0,0 +1,378 @@
+use std::{fmt::Debug, sync::Arc};
+
+use crate::{
+    TuxPdfError,
+    document::PdfDocument,
+    graphics::{
+        GraphicsGroup, HasPosition, LayerType, Margin, PdfObject, PdfPosition, TextBlock,
+        image::{ImageFit, PdfImage},
+        primitives::ctm::CurTransMat,
+        shapes::OutlineRect,
+        size::Size,
+    },
+};
+
+use super::{
+    LayoutItemType,
+    table::{HorizontalOverflow, Table, TablePageRules},
+};
+/// A reusable widget that can measure itself and draw within a rectangle
+///
+/// Implement this to build your own widgets such as address blocks or signature lines.
+/// Wrap the component in a [ComponentItem] to place it on a page or within a layout.
+///
+/// ```rust
+/// use tux_pdf::{
+///     TuxPdfError,
+///     document::PdfDocument,
+///     graphics::{PdfObject, PdfPosition, primitives::StraightLine, shapes::OutlineRect, size::Size},
+///     layouts::PdfComponent,
+///     units::UnitType,
+/// };
+/// #[derive(Debug)]
+/// struct SignatureLine;
+/// impl PdfComponent for SignatureLine {
+///     fn measure(&self, _: &PdfDocument, available: Size) -> Result<Size, TuxPdfError> {
+///         Ok(Size::new(available.width, 20f32.pt()))
+///     }
+///     fn render(&self, _: &PdfDocument, area: OutlineRect) -> Result<Vec<PdfObject>, TuxPdfError> {
+///         let line = StraightLine {
+///             start: area.position,
+///             points: vec![PdfPosition::new(area.position.x + area.size.width, area.position.y)],
+///             is_closed: false,
+///         };
+///         Ok(vec![line.into()])
+///     }
+/// }
+/// ```
+pub trait PdfComponent: Debug {
+    /// The size the component needs
+    ///
+    /// `available` is the largest size the component can use
+    fn measure(&self, document: &PdfDocument, available: Size) -> Result<Size, TuxPdfError>;
+    /// Creates the objects that draw the component inside of `area`
+    fn render(
+        &self,
+        document: &PdfDocument,
+        area: OutlineRect,
+    ) -> Result<Vec<PdfObject>, TuxPdfError>;
+}
+impl LayerType for Vec<PdfObject> {
+    fn add_to_layer(&mut self, object: impl Into<PdfObject>) -> Result<(), TuxPdfError> {
+        self.push(object.into());
+        Ok(())
+    }
+}
+/// Renders the item with its top left corner at the top left of the area
+fn render_layout_item<I: LayoutItemType>(
+    mut item: I,
+    document: &PdfDocument,
+    area: OutlineRect,
+) -> Result<Vec<PdfObject>, TuxPdfError> {
+    let size = item.calculate_size(document)?;
+    let bottom_left = PdfPosition::new(
+        area.position.x,
+        area.position.y + area.size.height - size.height,
+    );
+    item.set_position(item.position_anchor().point_of(bottom_left, size));
+    let mut objects = Vec::new();
+    item.render(document, &mut objects)?;
+    Ok(objects)
+}
+/// Text is wrapped to the available width
+impl PdfComponent for TextBlock {
+    fn measure(&self, document: &PdfDocument, available: Size) -> Result<Size, TuxPdfError> {
+        let mut text = self.clone();
+        text.content
+            .apply_max_width(available.width, document, &text.style)?;
+        text.calculate_size(document)
+    }
+    fn render(
+        &self,
+        document: &PdfDocument,
+        area: OutlineRect,
+    ) -> Result<Vec<PdfObject>, TuxPdfError> {
+        let mut text = self.clone();
+        text.content
+            .apply_max_width(area.size.width, document, &text.style)?;
+        render_layout_item(text, document, area)
+    }
+}
+/// Images with an [ImageFit] fill the available width and are fit into the area
+impl PdfComponent for PdfImage {
+    fn measure(&self, document: &PdfDocument, available: Size) -> Result<Size, TuxPdfError> {
+        let size = self.clone().calculate_size(document)?;
+        if self.fit == ImageFit::None {
+            return Ok(size);
+        }
+        Ok(Size::new(
+            available.width,
+            size.height * (available.width / size.width),
+        ))
+    }
+    fn render(
+        &self,
+        document: &PdfDocument,
+        area: OutlineRect,
+    ) -> Result<Vec<PdfObject>, TuxPdfError> {
+        if self.fit == ImageFit::None {
+            return render_layout_item(self.clone(), document, area);
+        }
+        let mut image = self.clone();
+        image.set_position(area.position);
+        image.set_fit_area(area);
+        Ok(vec![image.into()])
+    }
+}
+/// The table must fit within the area. It will not create new pages.
+///
+/// [HorizontalOverflow::Paginate] is ignored
+impl PdfComponent for Table {
+    fn measure(&self, document: &PdfDocument, available: Size) -> Result<Size, TuxPdfError> {
+        let mut table = self.clone().into_visible()?;
+        table.styles.horizontal_overflow = HorizontalOverflow::Overflow;
+        let page_rules = TablePageRules {
+            page_size: available,
+            table_start_y: None,
+            table_stop_y: None,
+            margin: None,
+        };
+        let (_, layout) = table.layout_single_page(document, &page_rules)?;
+        Ok(layout.final_size)
+    }
+    fn render(
+        &self,
+        document: &PdfDocument,
+        area: OutlineRect,
+    ) -> Result<Vec<PdfObject>, TuxPdfError> {
+        let mut table = self.clone().into_visible()?;
+        table.styles.horizontal_overflow = HorizontalOverflow::Overflow;
+        if table.is_right_to_left(document) {
+            table = table.mirrored();
+        }
+        let top = area.position.y + area.size.height;
+        let page_rules = TablePageRules {
+            page_size: Size::new(area.position.x + area.size.width, top),
+            table_start_y: Some(top),
+            table_stop_y: Some(area.position.y),
+            margin: Some(Margin {
+                left: Some(area.position.x),
+                ..Default::default()
+            }),
+        };
+        let (rows, mut layout) = table.layout_single_page(document, &page_rules)?;
+        let mut objects = Vec::new();
+        table.draw_page(document, rows, &mut layout, 0, &mut objects)?;
+        Ok(objects)
+    }
+}
+/// The group is moved so the top left of its [bounds](GraphicsGroup::bounds) is at the top left of the area
+impl PdfComponent for GraphicsGroup {
+    fn measure(&self, _: &PdfDocument, _: Size) -> Result<Size, TuxPdfError> {
+        Ok(self.bounds().map(|bounds| bounds.size).unwrap_or_default())
+    }
+    fn render(&self, _: &PdfDocument, area: OutlineRect) -> Result<Vec<PdfObject>, TuxPdfError> {
+        let Some(bounds) = self.bounds() else {
+            return Ok(Vec::new());
+        };
+        let offset = PdfPosition::new(
+            area.position.x - bounds.position.x,
+            area.position.y + area.size.height - bounds.size.height - bounds.position.y,
+        );
+        let mut group = self.clone();
+        group.transform.insert(0, CurTransMat::Position(offset));
+        Ok(vec![group.into()])
+    }
+}
+/// Places a [PdfComponent] within a page or layout
+#[derive(Debug, Clone)]
+pub struct ComponentItem {
+    pub component: Arc<dyn PdfComponent>,
+    /// The largest size the component can use
+    pub available: Size,
+    pub position: PdfPosition,
+    size: Option<Size>,
+}
+impl PartialEq for ComponentItem {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.component, &other.component)
+            && self.available == other.available
+            && self.position == other.position
+    }
+}
+impl ComponentItem {
+    pub fn new(component: impl PdfComponent + 'static, available: Size) -> Self {
+        Self {
+            component: Arc::new(component),
+            available,
+            position: PdfPosition::default(),
+            size: None,
+        }
+    }
+}
+impl HasPosition for ComponentItem {
+    fn position(&self) -> PdfPosition {
+        self.position
+    }
+    fn set_position(&mut self, position: PdfPosition) {
+        self.position = position;
+    }
+}
+impl LayoutItemType for ComponentItem {
+    fn calculate_size(&mut self, document: &PdfDocument) -> Result<Size, TuxPdfError> {
+        let size = self.component.measure(document, self.available)?;
+        self.size = Some(size);
+        Ok(size)
+    }
+    fn render<L: LayerType>(self, document: &PdfDocument, page: &mut L) -> Result<(), TuxPdfError>
+    where
+        Self: Sized,
+    {
+        let size = match self.size {
+            Some(size) => size,
+            None => self.component.measure(document, self.available)?,
+        };
+        let area = OutlineRect {
+            position: self.position,
+            size,
+        };
+        for object in self.component.render(document, area)? {
+            page.add_to_layer(object)?;
+        }
+        Ok(())
+    }
+}
+#[cfg(test)]
+mod tests {
+    use image::DynamicImage;
+
+    use super::{ComponentItem, PdfComponent};
+    use crate::{
+        document::{BuiltinFont, PdfDocument, PdfXObjectImage},
+        graphics::{
+            Anchor, GraphicItems, GraphicsGroup, PdfObject, PdfPosition, TextBlock,
+            image::{ImageFit, PdfImage},
+            primitives::ctm::CurTransMat,
+            shapes::OutlineRect,
+            size::Size,
+        },
+        layouts::table::{Row, Table},
+        page::{PdfPage, page_sizes::A4},
+        units::UnitType,
+    };
+    fn document() -> PdfDocument {
+        let mut document = PdfDocument::new("Components");
+        document
+            .font_map()
+            .register_builtin_font(BuiltinFont::Helvetica);
+        document
+    }
+    #[test]
+    fn text_wraps_to_area() -> anyhow::Result<()> {
+        let document = document();
+        let text = TextBlock::from("The quick brown fox jumps over the lazy dog");
+        let one_line = text.measure(&document, A4)?;
+        let narrow = text.measure(&document, Size::new(100f32.pt(), A4.height))?;
+        assert!(narrow.width <= 100f32.pt());
+        assert!(narrow.height > one_line.height);
+
+        let area = OutlineRect::new_from_bottom_left(
+            PdfPosition::new(50f32.pt(), 100f32.pt()),
+            Size::new(100f32.pt(), 200f32.pt()),
+        );
+        let objects = text.render(&document, area)?;
+        let [PdfObject::TextBlock(rendered)] = objects.as_slice() else {
+            panic!("Expected the text to be rendered");
+        };
+        assert!(rendered.content.len() > 1);
+        assert_eq!(rendered.position.x, area.position.x);
+        Ok(())
+    }
+    #[test]
+    fn image_fills_width() -> anyhow::Result<()> {
+        let mut document = document();
+        let image = document.add_xobject(PdfXObjectImage::load_from_dynamic_image(
+            DynamicImage::new_rgb8(4, 2),
+        )?);
+        let image = PdfImage::new(image).with_fit(ImageFit::Contain);
+        let size = image.measure(&document, Size::new(200f32.pt(), 500f32.pt()))?;
+        assert_eq!(size, Size::new(200f32.pt(), 100f32.pt()));
+
+        let area =
+            OutlineRect::new_from_bottom_left(PdfPosition::new(10f32.pt(), 20f32.pt()), size);
+        let objects = image.render(&document, area)?;
+        let [PdfObject::Image(rendered)] = objects.as_slice() else {
+            panic!("Expected the image to be rendered");
+        };
+        assert_eq!(rendered.fit_area, Some(area));
+        assert_eq!(rendered.transform.position, area.position);
+        Ok(())
+    }
+    #[test]
+    fn table_stays_in_area() -> anyhow::Result<()> {
+        let document = document();
+        let mut table = Table::default();
+        table.add_column("Name".into());
+        table.add_column("Quantity".into());
+        for index in 0..3 {
+            table.add_row(Row::from(vec![format!("Item {index}"), index.to_string()]));
+        }
+        let size = PdfComponent::measure(&table, &document, Size::new(300f32.pt(), 500f32.pt()))?;
+        assert!(size.width <= 300f32.pt());
+        assert!(size.height > 0f32.pt());
+
+        let area =
+            OutlineRect::new_from_bottom_left(PdfPosition::new(50f32.pt(), 100f32.pt()), size);
+        let objects = PdfComponent::render(&table, &document, area)?;
+        let text: Vec<_> = objects
+            .iter()
+            .filter_map(|object| match object {
+                PdfObject::TextBlock(text) => Some(text),
+                _ => None,
+            })
+            .collect();
+        // The headers and every cell
+        assert_eq!(text.len(), 8);
+        for text in text {
+            assert!(
+                area.contains(text.position),
+                "{:?} is outside of the table",
+                text.position
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn group_moves_to_area() -> anyhow::Result<()> {
+        let document = PdfDocument::new("Components");
+        let mut group = GraphicsGroup::new();
+        group.add_item(OutlineRect::new_from_bottom_left(
+            PdfPosition::new(10f32.pt(), 10f32.pt()),
+            Size::new(20f32.pt(), 30f32.pt()),
+        ));
+        assert_eq!(
+            group.measure(&document, A4)?,
+            Size::new(20f32.pt(), 30f32.pt())
+        );
+        let mut page = PdfPage::new_from_page_size(A4);
+        let item = ComponentItem::new(group, A4);
+        page.place(
+            &document,
+            item,
+            Anchor::BottomLeft,
+            (50f32.pt(), -50f32.pt()),
+        )?;
+        let Some(PdfObject::Graphics(GraphicItems::Group(placed))) = page.contents.first() else {
+            panic!("Expected the group to be rendered");
+        };
+        assert_eq!(
+            placed.transform,
+            vec![CurTransMat::Position(PdfPosition::new(
+                40f32.pt(),
+                40f32.pt()
+            ))]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,296 @@
+//! A fixed number of rows and columns within a rectangle
+//!
+//! Good for label sheets and photo contact sheets. Every cell is the same size and holds one [LayoutItem]
+//!
+//! ```rust
+//! use tux_pdf::{
+//!     document::{BuiltinFont, PdfDocument},
+//!     graphics::{Anchor, Margin, TextBlock},
+//!     layouts::grid::GridLayout,
+//!     page::{PdfPage, page_sizes::LETTER},
+//!     units::UnitType,
+//! };
+//! let mut document = PdfDocument::new("Labels");
+//! document.font_map().register_builtin_font(BuiltinFont::Helvetica);
+//! let mut page = PdfPage::new_from_page_size(LETTER);
+//! // 3 x 10 address labels
+//! let mut labels = GridLayout::new(10, 3, page.content_area())
+//!     .with_gutter(0.125f32.inch().pt(), 0f32.pt())
+//!     .with_cell_padding(Margin::all(6f32.pt()))
+//!     .with_alignment(Anchor::CenterLeft);
+//! labels.add_item(TextBlock::from("Jane Doe\n123 Main St\nSpringfield"))?;
+//! labels.set_item(2, 1, TextBlock::from("John Smith\n42 Elm St\nShelbyville"))?;
+//! page.place(&document, labels, Anchor::BottomLeft, (0f32.pt(), 0f32.pt()))?;
+//! # Ok::<(), tux_pdf::TuxPdfError>(())
+//! ```
+use crate::{
+    TuxPdfError,
+    document::PdfDocument,
+    graphics::{
+        Anchor, HasPosition, LayerType, Margin, PdfPosition, image::ImageFit, shapes::OutlineRect,
+        size::Size,
+    },
+    units::Pt,
+};
+
+use super::{LayoutError, LayoutItem, LayoutItemType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct GridLayoutStyles {
+    /// Space between columns
+    pub column_gap: Pt,
+    /// Space between rows
+    pub row_gap: Pt,
+    /// Space between the edges of a cell and its item
+    pub cell_padding: Margin,
+    /// Where the item sits within its cell. Defaults to the top left
+    pub alignment: Anchor,
+}
+/// Rows and columns of equally sized cells
+///
+/// Text is wrapped to the width of its cell and images with an [ImageFit] are fit into the cell.
+/// Other items keep their size
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridLayout {
+    rows: usize,
+    columns: usize,
+    /// The rectangle the grid fills
+    pub area: OutlineRect,
+    pub styles: GridLayoutStyles,
+    /// The items from left to right and top to bottom
+    cells: Vec<Option<LayoutItem>>,
+}
+impl GridLayout {
+    pub fn new(rows: usize, columns: usize, area: OutlineRect) -> Self {
+        Self {
+            rows,
+            columns,
+            area,
+            styles: GridLayoutStyles::default(),
+            cells: vec![None; rows * columns],
+        }
+    }
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+    pub fn with_styles(mut self, styles: GridLayoutStyles) -> Self {
+        self.styles = styles;
+        self
+    }
+    /// Space between the columns and the rows
+    pub fn with_gutter(mut self, column_gap: Pt, row_gap: Pt) -> Self {
+        self.styles.column_gap = column_gap;
+        self.styles.row_gap = row_gap;
+        self
+    }
+    pub fn with_cell_padding(mut self, padding: Margin) -> Self {
+        self.styles.cell_padding = padding;
+        self
+    }
+    pub fn with_alignment(mut self, alignment: Anchor) -> Self {
+        self.styles.alignment = alignment;
+        self
+    }
+    /// Puts the item in the cell. Row 0 is the top row
+    ///
+    /// Replaces the item already in the cell
+    pub fn set_item(
+        &mut self,
+        row: usize,
+        column: usize,
+        item: impl Into<LayoutItem>,
+    ) -> Result<(), TuxPdfError> {
+        if row >= self.rows || column >= self.columns {
+            return Err(LayoutError::GridCellOutOfBounds { row, column }.into());
+        }
+        self.cells[row * self.columns + column] = Some(item.into());
+        Ok(())
+    }
+    /// Puts the item in the first empty cell. Cells are filled from left to right and top to bottom
+    pub fn add_item(&mut self, item: impl Into<LayoutItem>) -> Result<(), TuxPdfError> {
+        let cell = self
+            .cells
+            .iter_mut()
+            .find(|cell| cell.is_none())
+            .ok_or(LayoutError::GridFull)?;
+        *cell = Some(item.into());
+        Ok(())
+    }
+    pub fn item(&self, row: usize, column: usize) -> Option<&LayoutItem> {
+        if row >= self.rows || column >= self.columns {
+            return None;
+        }
+        self.cells[row * self.columns + column].as_ref()
+    }
+    /// The size of every cell including the padding
+    pub fn cell_size(&self) -> Size {
+        let gaps = |count: usize, gap: Pt| gap * count.saturating_sub(1) as f32;
+        let columns = self.columns.max(1) as f32;
+        let rows = self.rows.max(1) as f32;
+        Size::new(
+            ((self.area.size.width - gaps(self.columns, self.styles.column_gap)) / columns)
+                .max(Pt::default()),
+            ((self.area.size.height - gaps(self.rows, self.styles.row_gap)) / rows)
+                .max(Pt::default()),
+        )
+    }
+    /// The rectangle of the cell including the padding. Row 0 is the top row
+    pub fn cell_rect(&self, row: usize, column: usize) -> OutlineRect {
+        let size = self.cell_size();
+        let top = self.area.position.y + self.area.size.height;
+        OutlineRect {
+            position: PdfPosition::new(
+                self.area.position.x + (size.width + self.styles.column_gap) * column as f32,
+                top - (size.height + self.styles.row_gap) * row as f32 - size.height,
+            ),
+            size,
+        }
+    }
+    /// Sizes the item for the content area of a cell and aligns it within the area
+    fn place_item(
+        &self,
+        item: &mut LayoutItem,
+        document: &PdfDocument,
+        content: OutlineRect,
+    ) -> Result<(), TuxPdfError> {
+        match item {
+            LayoutItem::Text(text) => {
+                text.content
+                    .apply_max_width(content.size.width, document, &text.style)?;
+            }
+            LayoutItem::Image(image) if image.fit != ImageFit::None => {
+                image.set_position(content.position);
+                image.set_fit_area(content);
+                return Ok(());
+            }
+            LayoutItem::Divider(divider) => {
+                divider.width = content.size.width;
+            }
+            _ => {}
+        }
+        let size = item.calculate_size(document)?;
+        let placed = size.aligned_within(&content, self.styles.alignment);
+        item.set_position(item.position_anchor().point_of(placed.position, size));
+        Ok(())
+    }
+}
+impl HasPosition for GridLayout {
+    fn position(&self) -> PdfPosition {
+        self.area.position
+    }
+
+    fn set_position(&mut self, position: PdfPosition) {
+        self.area.position = position;
+    }
+}
+impl LayoutItemType for GridLayout {
+    fn calculate_size(&mut self, _: &PdfDocument) -> Result<Size, TuxPdfError> {
+        Ok(self.area.size)
+    }
+
+    fn render<L: LayerType>(
+        mut self,
+        document: &PdfDocument,
+        page: &mut L,
+    ) -> Result<(), TuxPdfError>
+    where
+        Self: Sized,
+    {
+        let cells = std::mem::take(&mut self.cells);
+        for (index, cell) in cells.into_iter().enumerate() {
+            let Some(mut item) = cell else {
+                continue;
+            };
+            let content = self
+                .cell_rect(index / self.columns, index % self.columns)
+                .inset(self.styles.cell_padding);
+            self.place_item(&mut item, document, content)?;
+            item.render(document, page)?;
+        }
+        Ok(())
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::GridLayout;
+    use crate::{
+        document::{BuiltinFont, PdfDocument},
+        graphics::{
+            Anchor, BlankSpace, Margin, PdfObject, PdfPosition, TextBlock, shapes::OutlineRect,
+            size::Size,
+        },
+        layouts::{LayoutError, LayoutItemType},
+        units::UnitType,
+    };
+
+    fn grid() -> GridLayout {
+        let area = OutlineRect {
+            position: PdfPosition::new(10f32.pt(), 20f32.pt()),
+            size: Size::new(320f32.pt(), 210f32.pt()),
+        };
+        GridLayout::new(2, 3, area).with_gutter(10f32.pt(), 10f32.pt())
+    }
+    #[test]
+    fn cell_rects() {
+        let grid = grid();
+        assert_eq!(grid.cell_size(), Size::new(100f32.pt(), 100f32.pt()));
+        // Row 0 is the top row
+        assert_eq!(
+            grid.cell_rect(0, 0).position,
+            PdfPosition::new(10f32.pt(), 130f32.pt())
+        );
+        assert_eq!(
+            grid.cell_rect(1, 2).position,
+            PdfPosition::new(230f32.pt(), 20f32.pt())
+        );
+    }
+    fn space() -> BlankSpace {
+        BlankSpace {
+            set_size: Some(Size::new(1f32.pt(), 1f32.pt())),
+            ..Default::default()
+        }
+    }
+    #[test]
+    fn items_fill_cells_in_order() -> anyhow::Result<()> {
+        let mut grid = grid();
+        grid.set_item(0, 0, space())?;
+        for _ in 0..5 {
+            grid.add_item(space())?;
+        }
+        assert!(grid.item(1, 2).is_some());
+        let full = grid.add_item(space()).unwrap_err();
+        assert!(matches!(
+            full,
+            crate::TuxPdfError::LayoutError(LayoutError::GridFull)
+        ));
+        assert!(grid.set_item(2, 0, space()).is_err());
+        Ok(())
+    }
+    #[test]
+    fn items_are_aligned_within_the_padding() -> anyhow::Result<()> {
+        let mut document = PdfDocument::new("Grid");
+        document
+            .font_map()
+            .register_builtin_font(BuiltinFont::Helvetica);
+        let mut grid = grid()
+            .with_cell_padding(Margin::all(5f32.pt()))
+            .with_alignment(Anchor::Center);
+        grid.set_item(1, 1, TextBlock::from("Label"))?;
+        let mut objects: Vec<PdfObject> = Vec::new();
+        grid.render(&document, &mut objects)?;
+        let [PdfObject::TextBlock(text)] = objects.as_slice() else {
+            panic!("Only the label is drawn");
+        };
+        let cell = OutlineRect {
+            position: PdfPosition::new(120f32.pt(), 20f32.pt()),
+            size: Size::new(100f32.pt(), 100f32.pt()),
+        };
+        assert!(text.position.x > cell.position.x + 5f32.pt());
+        assert!(text.position.y > cell.position.y + 5f32.pt());
+        assert!(text.position.y < cell.center().y + 12f32.pt());
+        Ok(())
+    }
+}
@@ -0,0 +1,178 @@
+use crate::{
+    TuxPdfError,
+    document::PdfDocument,
+    graphics::{Anchor, HasPosition, LayerType, PdfPosition, size::Size},
+    units::Pt,
+};
+
+use super::{LayoutItem, LayoutItemType};
+/// Layout items that are moved together
+///
+/// The offset of each child is where the bottom left corner of the child goes, measured up and to the right from the bottom left corner of the group.
+/// This is the same for every child no matter its [Anchor], so a [Anchor::TopLeft] child at `(0, 0)` sits on the bottom edge of the group instead of hanging below it.
+/// The size is the bounding box of the children measured from that corner, so children should use positive offsets.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LayoutGroup {
+    pub items: Vec<LayoutItem>,
+    /// Bottom left corner of the group
+    pub position: PdfPosition,
+}
+impl LayoutGroup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Adds an item using a builder pattern
+    ///
+    /// `offset` is the bottom left corner of the item relative to the bottom left of the group
+    pub fn with_item(mut self, item: impl Into<LayoutItem>, offset: PdfPosition) -> Self {
+        self.add_item(item, offset);
+        self
+    }
+    /// `offset` is the bottom left corner of the item relative to the bottom left of the group
+    ///
+    /// The offset is kept as the position of the item until the group is rendered
+    pub fn add_item(&mut self, item: impl Into<LayoutItem>, offset: PdfPosition) {
+        let mut item = item.into();
+        item.set_position(offset);
+        self.items.push(item);
+    }
+}
+impl HasPosition for LayoutGroup {
+    fn position(&self) -> PdfPosition {
+        self.position
+    }
+
+    fn set_position(&mut self, position: PdfPosition) {
+        self.position = position;
+    }
+}
+impl LayoutItemType for LayoutGroup {
+    fn position_anchor(&self) -> Anchor {
+        Anchor::BottomLeft
+    }
+    fn calculate_size(&mut self, document: &PdfDocument) -> Result<Size, TuxPdfError> {
+        let mut size = Size::<Pt>::default();
+        for item in &mut self.items {
+            let item_size = item.calculate_size(document)?;
+            let offset = item.position();
+            size.width = size.width.max(offset.x + item_size.width);
+            size.height = size.height.max(offset.y + item_size.height);
+        }
+        Ok(size)
+    }
+
+    fn render<L: LayerType>(self, document: &PdfDocument, page: &mut L) -> Result<(), TuxPdfError>
+    where
+        Self: Sized,
+    {
+        for mut item in self.items {
+            let size = item.calculate_size(document)?;
+            let position =
+                child_position(item.position_anchor(), self.position, item.position(), size);
+            item.set_position(position);
+            item.render(document, page)?;
+        }
+        Ok(())
+    }
+}
+/// The position for a child with `anchor` so its bottom left corner is at `offset` from the group
+fn child_position(
+    anchor: Anchor,
+    group: PdfPosition,
+    offset: PdfPosition,
+    size: Size,
+) -> PdfPosition {
+    anchor.point_of(group + offset, size)
+}
+#[cfg(test)]
+mod tests {
+    use super::{LayoutGroup, child_position};
+    use crate::{
+        document::PdfDocument,
+        graphics::{
+            Anchor, BlankSpace, GraphicItems, GraphicsGroup, HasPosition, PdfObject, PdfPosition,
+            primitives::ctm::CurTransMat, shapes::OutlineRect, size::Size,
+        },
+        layouts::{ComponentItem, LayoutItemType},
+        page::{PdfPage, page_sizes::A4},
+        units::{Pt, UnitType},
+    };
+
+    #[test]
+    fn group_size_is_bounding_box() -> anyhow::Result<()> {
+        let document = PdfDocument::new("group_size");
+        let mut group = LayoutGroup::new()
+            .with_item(
+                BlankSpace {
+                    set_size: Some(Size::new(20f32.pt(), 10f32.pt())),
+                    ..Default::default()
+                },
+                PdfPosition::new(0f32.pt(), 0f32.pt()),
+            )
+            .with_item(
+                BlankSpace {
+                    set_size: Some(Size::new(10f32.pt(), 30f32.pt())),
+                    ..Default::default()
+                },
+                PdfPosition::new(40f32.pt(), 5f32.pt()),
+            );
+        let size = group.calculate_size(&document)?;
+        assert_eq!(size, Size::new(50f32.pt(), 35f32.pt()));
+        Ok(())
+    }
+    #[test]
+    fn offset_is_bottom_left_for_every_anchor() {
+        let group = PdfPosition::new(100f32.pt(), 200f32.pt());
+        let offset = PdfPosition::new(10f32.pt(), 5f32.pt());
+        let size = Size::new(20f32.pt(), 30f32.pt());
+        for anchor in [
+            Anchor::TopLeft,
+            Anchor::TopCenter,
+            Anchor::TopRight,
+            Anchor::CenterLeft,
+            Anchor::Center,
+            Anchor::CenterRight,
+            Anchor::BottomLeft,
+            Anchor::BottomCenter,
+            Anchor::BottomRight,
+        ] {
+            let position = child_position(anchor, group, offset, size);
+            assert_eq!(
+                anchor.bottom_left_from(position, size),
+                PdfPosition::new(110f32.pt(), 205f32.pt()),
+                "{anchor:?}"
+            );
+        }
+        assert_eq!(
+            child_position(Anchor::TopLeft, group, offset, size),
+            PdfPosition::new(110f32.pt(), 235f32.pt())
+        );
+    }
+    #[test]
+    fn children_are_moved_by_the_group() -> anyhow::Result<()> {
+        let document = PdfDocument::new("group_render");
+        let mut rect = GraphicsGroup::new();
+        rect.add_item(OutlineRect::new_from_bottom_left(
+            PdfPosition::<Pt>::default(),
+            Size::new(20f32.pt(), 30f32.pt()),
+        ));
+        let mut group = LayoutGroup::new().with_item(
+            ComponentItem::new(rect, A4),
+            PdfPosition::new(10f32.pt(), 5f32.pt()),
+        );
+        group.set_position(PdfPosition::new(100f32.pt(), 200f32.pt()));
+        let mut page = PdfPage::new_from_page_size(A4);
+        group.render(&document, &mut page)?;
+        let Some(PdfObject::Graphics(GraphicItems::Group(placed))) = page.contents.first() else {
+            panic!("Expected the child to be rendered");
+        };
+        assert_eq!(
+            placed.transform,
+            vec![CurTransMat::Position(PdfPosition::new(
+                110f32.pt(),
+                205f32.pt()
+            ))]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,360 @@
+//! Label and value pairs drawn as two aligned columns
+//!
+//! Good for the invoice number and dates at the top of an invoice.
+//!
+//! ```rust
+//! use tux_pdf::layouts::{key_value::KeyValueBlock, table::CellAlignment};
+//! let block = KeyValueBlock::default()
+//!     .add_pair("Invoice #", "1042")
+//!     .add_pair("Date", "2024-05-01")
+//!     .add_pair("Due Date", "2024-05-31")
+//!     .with_label_alignment(CellAlignment::Right);
+//! assert_eq!(block.pairs.len(), 3);
+//! // Place it in the top right corner of a page with `page.place(&document, block, Anchor::TopRight, (0f32.pt(), 0f32.pt()))`
+//! ```
+use crate::{
+    TuxPdfError,
+    document::PdfDocument,
+    graphics::{
+        Anchor, HasPosition, LayerType, PdfObject, PdfPosition, TextBlock, TextBlockContent,
+        TextStyle,
+        shapes::OutlineRect,
+        size::{RenderSize, Size},
+    },
+    units::{Pt, UnitType},
+};
+
+use super::{LayoutItemType, PdfComponent, Theme, table::CellAlignment};
+
+/// A label and its value
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct KeyValuePair {
+    pub label: TextBlockContent,
+    pub value: TextBlockContent,
+}
+impl KeyValuePair {
+    pub fn new(label: impl Into<TextBlockContent>, value: impl Into<TextBlockContent>) -> Self {
+        Self {
+            label: label.into(),
+            value: value.into(),
+        }
+    }
+}
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyValueStyles {
+    pub label_style: TextStyle,
+    pub value_style: TextStyle,
+    /// Space between the label column and the value column
+    pub column_gap: Pt,
+    /// Vertical space between pairs
+    pub row_gap: Pt,
+    /// Alignment of the labels within the label column
+    pub label_alignment: CellAlignment,
+    /// Alignment of the values within the value column
+    pub value_alignment: CellAlignment,
+}
+impl KeyValueStyles {
+    /// Labels in the heading font and the values as body text
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            label_style: TextStyle {
+                font_ref: theme.fonts.heading.clone(),
+                ..theme.text_style()
+            },
+            value_style: theme.text_style(),
+            ..Default::default()
+        }
+    }
+}
+impl Default for KeyValueStyles {
+    fn default() -> Self {
+        Self {
+            label_style: TextStyle::default(),
+            value_style: TextStyle::default(),
+            column_gap: 12f32.pt(),
+            row_gap: 2f32.pt(),
+            label_alignment: CellAlignment::Left,
+            value_alignment: CellAlignment::Left,
+        }
+    }
+}
+/// Label and value pairs. Labels are drawn in one column and the values in a second column
+///
+/// Place it with [PdfPage::place](crate::page::PdfPage::place) or wrap it in a [ComponentItem](super::ComponentItem) to use it within a layout
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct KeyValueBlock {
+    pub pairs: Vec<KeyValuePair>,
+    pub styles: KeyValueStyles,
+    /// The top left corner of the block
+    pub position: PdfPosition,
+}
+/// The sizes of the columns and rows after measuring the pairs
+///
+/// The heights include the descent of the font so the lowest glyphs stay within the row
+struct KeyValueLayout {
+    label_sizes: Vec<Size>,
+    value_sizes: Vec<Size>,
+    /// How far the label glyphs go below the baseline
+    label_depth: Pt,
+    /// How far the value glyphs go below the baseline
+    value_depth: Pt,
+    label_width: Pt,
+    value_width: Pt,
+    size: Size,
+}
+impl KeyValueBlock {
+    pub fn add_pair(
+        mut self,
+        label: impl Into<TextBlockContent>,
+        value: impl Into<TextBlockContent>,
+    ) -> Self {
+        self.pairs.push(KeyValuePair::new(label, value));
+        self
+    }
+    pub fn with_styles(mut self, styles: KeyValueStyles) -> Self {
+        self.styles = styles;
+        self
+    }
+    pub fn with_label_style(mut self, style: TextStyle) -> Self {
+        self.styles.label_style = style;
+        self
+    }
+    pub fn with_value_style(mut self, style: TextStyle) -> Self {
+        self.styles.value_style = style;
+        self
+    }
+    pub fn with_column_gap(mut self, gap: Pt) -> Self {
+        self.styles.column_gap = gap;
+        self
+    }
+    pub fn with_label_alignment(mut self, alignment: CellAlignment) -> Self {
+        self.styles.label_alignment = alignment;
+        self
+    }
+    pub fn with_value_alignment(mut self, alignment: CellAlignment) -> Self {
+        self.styles.value_alignment = alignment;
+        self
+    }
+    fn layout(&self, document: &PdfDocument) -> Result<KeyValueLayout, TuxPdfError> {
+        let label_depth = depth(document, &self.styles.label_style)?;
+        let value_depth = depth(document, &self.styles.value_style)?;
+        let mut label_sizes = Vec::with_capacity(self.pairs.len());
+        let mut value_sizes = Vec::with_capacity(self.pairs.len());
+        for pair in &self.pairs {
+            let mut label_size = pair.label.render_size(document, &self.styles.label_style)?;
+            label_size.height += label_depth;
+            label_sizes.push(label_size);
+            let mut value_size = pair.value.render_size(document, &self.styles.value_style)?;
+            value_size.height += value_depth;
+            value_sizes.push(value_size);
+        }
+        let label_width = label_sizes
+            .iter()
+            .map(|size| size.width)
+            .fold(Pt::default(), Pt::max);
+        let value_width = value_sizes
+            .iter()
+            .map(|size| size.width)
+            .fold(Pt::default(), Pt::max);
+        let rows_height: Pt = label_sizes
+            .iter()
+            .zip(&value_sizes)
+            .map(|(label, value)| label.height.max(value.height))
+            .fold(Pt::default(), |total, height| total + height);
+        let gaps = self.styles.row_gap * self.pairs.len().saturating_sub(1) as f32;
+        let width = if self.pairs.is_empty() {
+            Pt::default()
+        } else {
+            label_width + self.styles.column_gap + value_width
+        };
+        Ok(KeyValueLayout {
+            label_sizes,
+            value_sizes,
+            label_depth,
+            value_depth,
+            label_width,
+            value_width,
+            size: Size::new(width, rows_height + gaps),
+        })
+    }
+    /// Creates the text blocks with the top left corner of the block at `top_left`
+    fn text_blocks(
+        &self,
+        document: &PdfDocument,
+        top_left: PdfPosition,
+    ) -> Result<Vec<TextBlock>, TuxPdfError> {
+        let layout = self.layout(document)?;
+        let value_x = top_left.x + layout.label_width + self.styles.column_gap;
+        let mut blocks = Vec::with_capacity(self.pairs.len() * 2);
+        let mut row_top = top_left.y;
+        for ((pair, label_size), value_size) in self
+            .pairs
+            .iter()
+            .zip(&layout.label_sizes)
+            .zip(&layout.value_sizes)
+        {
+            // Both sides start at the top of the row so multi line values line up with their label
+            blocks.push(TextBlock {
+                content: pair.label.clone(),
+                style: self.styles.label_style.clone(),
+                position: PdfPosition::new(
+                    top_left.x
+                        + self
+                            .styles
+                            .label_alignment
+                            .offset(label_size.width, layout.label_width),
+                    row_top - label_size.height + layout.label_depth,
+                ),
+                draw_as_lines: false,
+            });
+            blocks.push(TextBlock {
+                content: pair.value.clone(),
+                style: self.styles.value_style.clone(),
+                position: PdfPosition::new(
+                    value_x
+                        + self
+                            .styles
+                            .value_alignment
+                            .offset(value_size.width, layout.value_width),
+                    row_top - value_size.height + layout.value_depth,
+                ),
+                draw_as_lines: false,
+            });
+            row_top -= label_size.height.max(value_size.height) + self.styles.row_gap;
+        }
+        Ok(blocks)
+    }
+}
+/// Distance from the baseline to the bottom of the lowest glyphs of the font
+fn depth(document: &PdfDocument, style: &TextStyle) -> Result<Pt, TuxPdfError> {
+    let metrics = style.font_ref.metrics(&document.resources.fonts)?;
+    Ok(-metrics.descent(style.font_size))
+}
+impl HasPosition for KeyValueBlock {
+    fn position(&self) -> PdfPosition {
+        self.position
+    }
+    fn set_position(&mut self, position: PdfPosition) {
+        self.position = position;
+    }
+}
+impl LayoutItemType for KeyValueBlock {
+    fn position_anchor(&self) -> Anchor {
+        Anchor::TopLeft
+    }
+    fn calculate_size(&mut self, document: &PdfDocument) -> Result<Size, TuxPdfError> {
+        Ok(self.layout(document)?.size)
+    }
+    fn render<L: LayerType>(self, document: &PdfDocument, page: &mut L) -> Result<(), TuxPdfError>
+    where
+        Self: Sized,
+    {
+        for block in self.text_blocks(document, self.position)? {
+            page.add_to_layer(block)?;
+        }
+        Ok(())
+    }
+}
+/// The block is drawn at the top left of the area
+impl PdfComponent for KeyValueBlock {
+    fn measure(&self, document: &PdfDocument, _: Size) -> Result<Size, TuxPdfError> {
+        Ok(self.layout(document)?.size)
+    }
+    fn render(
+        &self,
+        document: &PdfDocument,
+        area: OutlineRect,
+    ) -> Result<Vec<PdfObject>, TuxPdfError> {
+        let top_left = PdfPosition::new(area.position.x, area.position.y + area.size.height);
+        Ok(self
+            .text_blocks(document, top_left)?
+            .into_iter()
+            .map(PdfObject::from)
+            .collect())
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::KeyValueBlock;
+    use crate::{
+        document::{BuiltinFont, PdfDocument},
+        graphics::{Anchor, PdfObject, PdfPosition, shapes::OutlineRect, size::RenderSize},
+        layouts::{LayoutItemType, PdfComponent, table::CellAlignment},
+        page::{PdfPage, page_sizes::A4},
+        units::{Pt, UnitType},
+    };
+
+    #[test]
+    fn values_line_up() -> anyhow::Result<()> {
+        let mut document = PdfDocument::new("Key Value");
+        document
+            .font_map()
+            .register_builtin_font(BuiltinFont::Helvetica);
+        let mut block = KeyValueBlock::default()
+            .add_pair("Invoice #", "1042")
+            .add_pair("Due Date", "2024-05-31")
+            .with_value_alignment(CellAlignment::Right);
+        let style = block.styles.value_style.clone();
+        let size = block.calculate_size(&document)?;
+        // "Due Date" is the widest label in Helvetica
+        let label_width = "Due Date".render_size(&document, &style)?.width;
+        let date_width = "2024-05-31".render_size(&document, &style)?.width;
+        assert_eq!(size.width, label_width + 12f32.pt() + date_width);
+
+        let mut page = PdfPage::new_from_page_size(A4);
+        page.place(&document, block, Anchor::TopRight, (0f32.pt(), 0f32.pt()))?;
+        let values: Vec<_> = page
+            .contents
+            .iter()
+            .filter_map(|object| match object {
+                PdfObject::TextBlock(block) => Some(block),
+                _ => None,
+            })
+            .skip(1)
+            .step_by(2)
+            .collect();
+        assert_eq!(values.len(), 2);
+        // Right aligned values end at the right edge of the page
+        let number_width = "1042".render_size(&document, &style)?.width;
+        assert_eq!(values[0].position.x + number_width, A4.width);
+        assert_eq!(values[1].position.x + date_width, A4.width);
+        assert!(values[1].position.y < values[0].position.y);
+        Ok(())
+    }
+    #[test]
+    fn descenders_stay_in_block() -> anyhow::Result<()> {
+        let mut document = PdfDocument::new("Key Value");
+        document
+            .font_map()
+            .register_builtin_font(BuiltinFont::Helvetica);
+        let mut block = KeyValueBlock::default()
+            .add_pair("Shipping", "Aug 9")
+            .add_pair("Paying", "July 2");
+        let style = block.styles.value_style.clone();
+        let size = block.calculate_size(&document)?;
+        let descent = style
+            .font_ref
+            .metrics(&document.resources.fonts)?
+            .descent(style.font_size);
+        assert!(descent < 0f32.pt());
+        let line_height = "Aug 9".render_size(&document, &style)?.height;
+        assert_eq!(
+            size.height,
+            (line_height - descent) * 2f32 + block.styles.row_gap
+        );
+
+        let area =
+            OutlineRect::new_from_bottom_left(PdfPosition::new(50f32.pt(), 100f32.pt()), size);
+        let objects = PdfComponent::render(&block, &document, area)?;
+        let lowest = objects
+            .iter()
+            .filter_map(|object| match object {
+                PdfObject::TextBlock(block) => Some(block.position.y + descent),
+                _ => None,
+            })
+            .fold(f32::MAX.pt(), Pt::min);
+        // The bottom of `g` and `y` in the last row is the bottom of the block
+        assert_eq!(lowest, area.position.y);
+        Ok(())
+    }
+}
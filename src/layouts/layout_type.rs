@@ -2,12 +2,15 @@ use crate::{
     TuxPdfError,
     document::PdfDocument,
     graphics::{
-        BlankSpace, HasPosition, LayerType, PdfPosition, TextBlock, image::PdfImage, size::Size,
+        Anchor, BlankSpace, Divider, HasPosition, LayerType, PdfPosition, Spacer, TextBlock,
+        image::PdfImage, size::Size,
     },
     units::Pt,
 };
 
 use super::LayoutError;
+#[cfg(feature = "taffy")]
+use crate::error::{ErrorContext, ErrorContextExt};
 /// A layout item is a item that lives within a layout
 pub trait LayoutItemType: HasPosition {
     /// Minimum size of the layout item if it has one
@@ -34,6 +37,10 @@ pub trait LayoutItemType: HasPosition {
     fn resize(&mut self, _new_size: Size<Option<Pt>>) -> Result<(), TuxPdfError> {
         Err(LayoutError::UnableToResize.into())
     }
+    /// Which point of the item's bounds [HasPosition::position] refers to
+    fn position_anchor(&self) -> Anchor {
+        Anchor::BottomLeft
+    }
     fn calculate_size(&mut self, document: &PdfDocument) -> Result<Size, TuxPdfError>;
     /// Can the layout item be resized?
     fn render<L: LayerType>(self, document: &PdfDocument, page: &mut L) -> Result<(), TuxPdfError>
@@ -45,9 +52,13 @@ pub trait LayoutItemType: HasPosition {
 pub enum LayoutItem {
     #[cfg(feature = "taffy")]
     TaffyLayout(Box<super::taffy_layout::PdfTaffyLayout>),
-    Text(TextBlock),
+    Text(Box<TextBlock>),
     Image(PdfImage),
     BlankSpace(BlankSpace),
+    Spacer(Spacer),
+    Divider(Divider),
+    Component(super::ComponentItem),
+    Group(super::LayoutGroup),
 }
 macro_rules! from {
     (
@@ -81,9 +92,15 @@ macro_rules! from {
     };
 }
 from! {
-    TextBlock => Text,
+    TextBlock => boxed(Text),
+}
+from! {
     PdfImage => Image,
-    BlankSpace => BlankSpace
+    BlankSpace => BlankSpace,
+    Spacer => Spacer,
+    Divider => Divider,
+    super::ComponentItem => Component,
+    super::LayoutGroup => Group
 }
 #[cfg(feature = "taffy")]
 from! {
@@ -96,6 +113,10 @@ impl HasPosition for LayoutItem {
             LayoutItem::Text(text) => text.position(),
             LayoutItem::Image(image) => image.position(),
             LayoutItem::BlankSpace(blank_space) => blank_space.position(),
+            LayoutItem::Spacer(spacer) => spacer.position(),
+            LayoutItem::Divider(divider) => divider.position(),
+            LayoutItem::Component(component) => component.position(),
+            LayoutItem::Group(group) => group.position(),
             #[cfg(feature = "taffy")]
             LayoutItem::TaffyLayout(layout) => layout.position(),
         }
@@ -106,19 +127,42 @@ impl HasPosition for LayoutItem {
             LayoutItem::Text(text) => text.set_position(position),
             LayoutItem::Image(image) => image.set_position(position),
             LayoutItem::BlankSpace(blank_space) => blank_space.set_position(position),
+            LayoutItem::Spacer(spacer) => spacer.set_position(position),
+            LayoutItem::Divider(divider) => divider.set_position(position),
+            LayoutItem::Component(component) => component.set_position(position),
+            LayoutItem::Group(group) => group.set_position(position),
             #[cfg(feature = "taffy")]
             LayoutItem::TaffyLayout(layout) => layout.set_position(position),
         }
     }
 }
 impl LayoutItemType for LayoutItem {
+    fn position_anchor(&self) -> Anchor {
+        match self {
+            LayoutItem::Text(text) => text.position_anchor(),
+            LayoutItem::Image(image) => image.position_anchor(),
+            LayoutItem::BlankSpace(bs) => bs.position_anchor(),
+            LayoutItem::Spacer(spacer) => spacer.position_anchor(),
+            LayoutItem::Divider(divider) => divider.position_anchor(),
+            LayoutItem::Component(component) => component.position_anchor(),
+            LayoutItem::Group(group) => group.position_anchor(),
+            #[cfg(feature = "taffy")]
+            LayoutItem::TaffyLayout(layout) => layout.position_anchor(),
+        }
+    }
     fn calculate_size(&mut self, document: &PdfDocument) -> Result<Size, TuxPdfError> {
         match self {
             LayoutItem::Text(text) => text.calculate_size(document),
             LayoutItem::Image(image) => image.calculate_size(document),
             LayoutItem::BlankSpace(bs) => bs.calculate_size(document),
+            LayoutItem::Spacer(spacer) => spacer.calculate_size(document),
+            LayoutItem::Divider(divider) => divider.calculate_size(document),
+            LayoutItem::Component(component) => component.calculate_size(document),
+            LayoutItem::Group(group) => group.calculate_size(document),
             #[cfg(feature = "taffy")]
-            LayoutItem::TaffyLayout(layout) => layout.calculate_size(document),
+            LayoutItem::TaffyLayout(layout) => layout
+                .calculate_size(document)
+                .context(ErrorContext::Layout("taffy")),
         }
     }
 
@@ -130,8 +174,14 @@ impl LayoutItemType for LayoutItem {
             LayoutItem::Text(text) => text.render(document, page),
             LayoutItem::Image(image) => image.render(document, page),
             LayoutItem::BlankSpace(bs) => bs.render(document, page),
+            LayoutItem::Spacer(spacer) => spacer.render(document, page),
+            LayoutItem::Divider(divider) => divider.render(document, page),
+            LayoutItem::Component(component) => component.render(document, page),
+            LayoutItem::Group(group) => group.render(document, page),
             #[cfg(feature = "taffy")]
-            LayoutItem::TaffyLayout(layout) => layout.render(document, page),
+            LayoutItem::TaffyLayout(layout) => layout
+                .render(document, page)
+                .context(ErrorContext::Layout("taffy")),
         }
     }
 }
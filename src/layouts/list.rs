@@ -0,0 +1,415 @@
+//! Bullet and numbered lists
+//!
+//! ```rust
+//! use tux_pdf::{
+//!     graphics::PdfPosition,
+//!     layouts::list::{ListItem, ListLayout, ListMarker},
+//!     units::UnitType,
+//! };
+//!
+//! let list = ListLayout::new(PdfPosition::new(20f32.pt(), 800f32.pt()), 300f32.pt())
+//!     .with_markers(vec![ListMarker::Decimal, ListMarker::LowerRoman])
+//!     .add_item("First")
+//!     .add_item(ListItem::from("Second").add_child("Nested").add_child("Also nested"));
+//! assert_eq!(list.items.len(), 2);
+//! ```
+use crate::{
+    TuxPdfError,
+    document::PdfDocument,
+    graphics::{
+        Anchor, GraphicStyles, GraphicsGroup, HasPosition, LayerType, PdfPosition, TextBlock,
+        TextBlockContent, TextStyle,
+        color::{BLACK_RGB, Color},
+        primitives::{PaintMode, PathBuilder},
+        size::{RenderSize, Size},
+    },
+    units::{Pt, UnitType},
+};
+
+use super::{LayoutItemType, Theme};
+
+/// The marker drawn in front of each list item
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ListMarker {
+    /// A filled circle
+    #[default]
+    Disc,
+    /// `-`
+    Dash,
+    /// `1.` `2.` `3.`
+    Decimal,
+    /// `i.` `ii.` `iii.`
+    LowerRoman,
+    /// `I.` `II.` `III.`
+    UpperRoman,
+}
+impl ListMarker {
+    /// The text of the marker for the item at the zero based `index`
+    ///
+    /// Returns `None` for markers that are drawn as shapes
+    pub fn marker_text(&self, index: usize) -> Option<String> {
+        let number = index + 1;
+        match self {
+            ListMarker::Disc => None,
+            ListMarker::Dash => Some("-".to_owned()),
+            ListMarker::Decimal => Some(format!("{number}.")),
+            ListMarker::LowerRoman => Some(format!("{}.", to_roman(number).to_lowercase())),
+            ListMarker::UpperRoman => Some(format!("{}.", to_roman(number))),
+        }
+    }
+}
+fn to_roman(mut number: usize) -> String {
+    const NUMERALS: [(usize, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    let mut result = String::new();
+    for (value, numeral) in NUMERALS {
+        while number >= value {
+            result.push_str(numeral);
+            number -= value;
+        }
+    }
+    result
+}
+/// A single entry in a list. Can contain a nested list
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ListItem {
+    pub content: TextBlockContent,
+    pub children: Vec<ListItem>,
+}
+impl ListItem {
+    pub fn new(content: impl Into<TextBlockContent>) -> Self {
+        Self {
+            content: content.into(),
+            children: Vec::new(),
+        }
+    }
+    pub fn add_child(mut self, child: impl Into<ListItem>) -> Self {
+        self.children.push(child.into());
+        self
+    }
+}
+impl<T> From<T> for ListItem
+where
+    T: Into<TextBlockContent>,
+{
+    fn from(content: T) -> Self {
+        Self::new(content)
+    }
+}
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListStyles {
+    pub text_styles: TextStyle,
+    /// Markers used for each depth of the list. Repeats once the list is deeper than the number of markers
+    ///
+    /// Default is [ListMarker::Disc] then [ListMarker::Dash]
+    pub markers: Vec<ListMarker>,
+    /// How far each nested level is moved to the right
+    pub indent_per_level: Pt,
+    /// Space between the marker and the item text
+    pub marker_gap: Pt,
+    /// Vertical space between items
+    pub item_spacing: Pt,
+    pub marker_color: Color,
+}
+impl Default for ListStyles {
+    fn default() -> Self {
+        Self {
+            text_styles: TextStyle::default(),
+            markers: vec![ListMarker::Disc, ListMarker::Dash],
+            indent_per_level: 18f32.pt(),
+            marker_gap: 6f32.pt(),
+            item_spacing: 2f32.pt(),
+            marker_color: BLACK_RGB,
+        }
+    }
+}
+impl ListStyles {
+    /// Body text with markers in the accent color
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            text_styles: theme.text_style(),
+            marker_color: theme.colors.accent.clone(),
+            ..Default::default()
+        }
+    }
+    pub fn marker_for_depth(&self, depth: usize) -> ListMarker {
+        if self.markers.is_empty() {
+            return ListMarker::default();
+        }
+        self.markers[depth % self.markers.len()]
+    }
+}
+/// A list of items with markers and hanging indents
+///
+/// Wrapped lines of an item line up with the start of the item's text instead of the marker.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListLayout {
+    pub items: Vec<ListItem>,
+    pub styles: ListStyles,
+    /// The top left corner of the list
+    pub position: PdfPosition,
+    /// The width available to the list. Items are wrapped to fit
+    pub width: Pt,
+}
+/// The marker and text of an item after layout
+#[derive(Debug, Clone, PartialEq)]
+struct PlacedListItem {
+    marker: PlacedMarker,
+    content: TextBlockContent,
+    /// Bottom left baseline of the text
+    text_position: PdfPosition,
+}
+#[derive(Debug, Clone, PartialEq)]
+enum PlacedMarker {
+    Text { text: String, position: PdfPosition },
+    Disc { center: PdfPosition, radius: Pt },
+}
+impl ListLayout {
+    pub fn new(position: PdfPosition, width: Pt) -> Self {
+        Self {
+            items: Vec::new(),
+            styles: ListStyles::default(),
+            position,
+            width,
+        }
+    }
+    pub fn with_styles(mut self, styles: ListStyles) -> Self {
+        self.styles = styles;
+        self
+    }
+    pub fn with_markers(mut self, markers: Vec<ListMarker>) -> Self {
+        self.styles.markers = markers;
+        self
+    }
+    pub fn add_item(mut self, item: impl Into<ListItem>) -> Self {
+        self.items.push(item.into());
+        self
+    }
+    fn disc_radius(&self) -> Pt {
+        self.styles.text_styles.font_size * 0.18f32
+    }
+    /// Width of the widest marker in a set of siblings
+    fn marker_column_width(
+        &self,
+        document: &PdfDocument,
+        marker: ListMarker,
+        count: usize,
+    ) -> Result<Pt, TuxPdfError> {
+        if marker == ListMarker::Disc {
+            return Ok(self.disc_radius() * 2f32);
+        }
+        let mut width = Pt::default();
+        for index in 0..count {
+            if let Some(text) = marker.marker_text(index) {
+                width = width.max(text.render_size(document, &self.styles.text_styles)?.width);
+            }
+        }
+        Ok(width)
+    }
+    /// Positions every item starting at `cursor_y`, moving the cursor down
+    fn layout_items(
+        &self,
+        document: &PdfDocument,
+        items: &[ListItem],
+        depth: usize,
+        cursor_y: &mut Pt,
+        placed: &mut Vec<PlacedListItem>,
+    ) -> Result<(), TuxPdfError> {
+        let style = &self.styles.text_styles;
+        let marker = self.styles.marker_for_depth(depth);
+        let marker_width = self.marker_column_width(document, marker, items.len())?;
+        let marker_x = self.position.x + self.styles.indent_per_level * depth as f32;
+        let text_x = marker_x + marker_width + self.styles.marker_gap;
+        let text_width = (self.width - (text_x - self.position.x)).max(Pt::default());
+
+        for (index, item) in items.iter().enumerate() {
+            if !placed.is_empty() {
+                *cursor_y -= self.styles.item_spacing;
+            }
+            let mut content = item.content.clone();
+            content.apply_max_width(text_width, document, style)?;
+            let size = content.render_size(document, style)?;
+            let first_line_height = match content.first() {
+                Some(line) => {
+                    TextBlockContent::from(line.clone())
+                        .render_size(document, style)?
+                        .height
+                }
+                None => style.font_size,
+            };
+            let first_baseline = *cursor_y - first_line_height;
+            let placed_marker = match marker.marker_text(index) {
+                Some(text) => {
+                    let text_size = text.render_size(document, style)?;
+                    // Numbers are right aligned so the dots line up
+                    PlacedMarker::Text {
+                        position: PdfPosition {
+                            x: marker_x + marker_width - text_size.width,
+                            y: first_baseline,
+                        },
+                        text,
+                    }
+                }
+                None => {
+                    let radius = self.disc_radius();
+                    PlacedMarker::Disc {
+                        center: PdfPosition {
+                            x: marker_x + radius,
+                            y: first_baseline + style.font_size * 0.3f32,
+                        },
+                        radius,
+                    }
+                }
+            };
+            *cursor_y -= size.height;
+            placed.push(PlacedListItem {
+                marker: placed_marker,
+                content,
+                text_position: PdfPosition {
+                    x: text_x,
+                    y: *cursor_y,
+                },
+            });
+            self.layout_items(document, &item.children, depth + 1, cursor_y, placed)?;
+        }
+        Ok(())
+    }
+    fn layout(&self, document: &PdfDocument) -> Result<(Vec<PlacedListItem>, Pt), TuxPdfError> {
+        let mut cursor_y = self.position.y;
+        let mut placed = Vec::new();
+        self.layout_items(document, &self.items, 0, &mut cursor_y, &mut placed)?;
+        Ok((placed, self.position.y - cursor_y))
+    }
+}
+impl HasPosition for ListLayout {
+    fn position(&self) -> PdfPosition {
+        self.position
+    }
+    fn set_position(&mut self, position: PdfPosition) {
+        self.position = position;
+    }
+}
+impl LayoutItemType for ListLayout {
+    fn position_anchor(&self) -> Anchor {
+        Anchor::TopLeft
+    }
+    fn calculate_size(&mut self, document: &PdfDocument) -> Result<Size, TuxPdfError> {
+        let (_, height) = self.layout(document)?;
+        Ok(Size::new(self.width, height))
+    }
+    fn render<L: LayerType>(self, document: &PdfDocument, page: &mut L) -> Result<(), TuxPdfError>
+    where
+        Self: Sized,
+    {
+        let (placed, _) = self.layout(document)?;
+        let mut discs = GraphicsGroup::new().with_styles(GraphicStyles {
+            fill_color: Some(self.styles.marker_color.clone()),
+            ..Default::default()
+        });
+        let mut marker_style = self.styles.text_styles.clone();
+        marker_style.fill_color = Some(self.styles.marker_color.clone());
+        for item in placed {
+            match item.marker {
+                PlacedMarker::Text { text, position } => {
+                    page.add_to_layer(TextBlock {
+                        content: text.into(),
+                        style: marker_style.clone(),
+                        position,
+                        draw_as_lines: false,
+                    })?;
+                }
+                PlacedMarker::Disc { center, radius } => {
+                    discs.add_item(
+                        PathBuilder::new()
+                            .arc_to(center, radius, 0f32, 360f32)
+                            .close()
+                            .with_mode(PaintMode::Fill)
+                            .build(),
+                    );
+                }
+            }
+            page.add_to_layer(TextBlock {
+                content: item.content,
+                style: self.styles.text_styles.clone(),
+                position: item.text_position,
+                draw_as_lines: false,
+            })?;
+        }
+        if !discs.items.is_empty() {
+            page.add_to_layer(discs)?;
+        }
+        Ok(())
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::{ListMarker, ListStyles, to_roman};
+
+    #[test]
+    fn roman_numerals() {
+        assert_eq!(to_roman(4), "IV");
+        assert_eq!(to_roman(9), "IX");
+        assert_eq!(to_roman(14), "XIV");
+        assert_eq!(to_roman(1994), "MCMXCIV");
+        assert_eq!(
+            ListMarker::LowerRoman.marker_text(2).as_deref(),
+            Some("iii.")
+        );
+        assert_eq!(ListMarker::Disc.marker_text(0), None);
+    }
+
+    #[test]
+    fn marker_texts() {
+        let markers = [
+            ListMarker::Disc,
+            ListMarker::Dash,
+            ListMarker::Decimal,
+            ListMarker::LowerRoman,
+            ListMarker::UpperRoman,
+        ];
+        let texts: Vec<_> = markers
+            .iter()
+            .map(|marker| marker.marker_text(11))
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                None,
+                Some("-".to_owned()),
+                Some("12.".to_owned()),
+                Some("xii.".to_owned()),
+                Some("XII.".to_owned()),
+            ]
+        );
+        assert_eq!(ListMarker::Decimal.marker_text(0).as_deref(), Some("1."));
+        assert_eq!(ListMarker::Dash.marker_text(41).as_deref(), Some("-"));
+    }
+    #[test]
+    fn markers_cycle_by_depth() {
+        let styles = ListStyles {
+            markers: vec![ListMarker::Decimal, ListMarker::LowerRoman],
+            ..Default::default()
+        };
+        assert_eq!(styles.marker_for_depth(0), ListMarker::Decimal);
+        assert_eq!(styles.marker_for_depth(1), ListMarker::LowerRoman);
+        assert_eq!(styles.marker_for_depth(2), ListMarker::Decimal);
+        let empty = ListStyles {
+            markers: Vec::new(),
+            ..Default::default()
+        };
+        assert_eq!(empty.marker_for_depth(3), ListMarker::Disc);
+    }
+}
@@ -7,14 +7,37 @@
  *
  * ## Available Layouts
  * - [Table Layout](table::Table)
+ * - [Month Calendar Layout](calendar::MonthCalendarLayout)
+ * - [List Layout](list::ListLayout) bullet and numbered lists
+ * - [Key Value Block](key_value::KeyValueBlock) aligned label and value pairs
+ * - [Layout Group](LayoutGroup) moves layout items together
+ * - [Slide Layout](slide::SlideLayout) full page slides for presentations
+ * - [Grid Layout](grid::GridLayout) equally sized cells for label sheets and contact sheets
+ * - [Batch Layout](batch::BatchLayout) tickets and badges tiled across pages with cut marks
+ *
+ * Every layout can be styled from a shared [Theme]
  * - [Taffy Layout](taffy_layout::PdfTaffyLayout) (Requires the `taffy` feature) will allow you to create flex boxes and grid layouts
  *
 */
 use thiserror::Error;
 
+mod component;
+mod group;
 mod layout_type;
+mod new_page;
+mod theme;
+pub use component::*;
+pub use group::*;
 pub use layout_type::*;
+pub use new_page::*;
+pub use theme::*;
 
+pub mod batch;
+pub mod calendar;
+pub mod grid;
+pub mod key_value;
+pub mod list;
+pub mod slide;
 pub mod table;
 #[cfg(feature = "taffy")]
 pub mod taffy_layout;
@@ -71,6 +94,18 @@ pub enum LayoutError {
     #[cfg(feature = "taffy")]
     #[error(transparent)]
     TaffyError(#[from] taffy::TaffyError),
+    /// The items do not fit on the page and the layout can not create more pages
+    #[error("Layout is not allowed to create more pages")]
+    NoNewPageAllowed,
+    /// The cell is outside of the grid
+    #[error("Cell at row {row} and column {column} is outside of the grid")]
+    GridCellOutOfBounds { row: usize, column: usize },
+    /// Every cell of the grid has an item
+    #[error("Every cell of the grid is full")]
+    GridFull,
+    /// Not even one tile of the batch fits on the page
+    #[error("The batch item does not fit on the page")]
+    BatchItemDoesNotFit,
     /// An error occurred within the table layout
     #[error(transparent)]
     TableError(#[from] table::TableError),
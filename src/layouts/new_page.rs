@@ -0,0 +1,61 @@
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use crate::{TuxPdfError, document::PdfDocument};
+
+type NewPageClosure<T> = dyn FnMut(&mut PdfDocument) -> Result<T, TuxPdfError> + Send;
+/// Creates the pages a paginating layout continues on
+///
+/// The closure can capture state. Such as fonts, styles or a page counter.
+/// Clones share the same closure and its state
+///
+/// ```rust
+/// # fn main() -> Result<(), tux_pdf::TuxPdfError> {
+/// use std::sync::{
+///     Arc,
+///     atomic::{AtomicUsize, Ordering},
+/// };
+/// use tux_pdf::{
+///     document::PdfDocument,
+///     layouts::table::{NewPageFn, TablePageRules},
+///     page::{PdfPage, page_sizes::A4},
+/// };
+/// let created = Arc::new(AtomicUsize::new(0));
+/// let counter = created.clone();
+/// let new_page = NewPageFn::new(move |_| {
+///     counter.fetch_add(1, Ordering::Relaxed);
+///     let page = PdfPage::new_from_page_size(A4);
+///     Ok((TablePageRules::for_page(&page), page))
+/// });
+/// let mut document = PdfDocument::new("Report");
+/// new_page.create(&mut document)?;
+/// new_page.clone().create(&mut document)?;
+/// assert_eq!(created.load(Ordering::Relaxed), 2);
+/// # Ok(())
+/// # }
+/// ```
+pub struct NewPage<T>(Arc<Mutex<NewPageClosure<T>>>);
+impl<T> NewPage<T> {
+    pub fn new(
+        new_page: impl FnMut(&mut PdfDocument) -> Result<T, TuxPdfError> + Send + 'static,
+    ) -> Self {
+        Self(Arc::new(Mutex::new(new_page)))
+    }
+    /// Calls the closure
+    pub fn create(&self, document: &mut PdfDocument) -> Result<T, TuxPdfError> {
+        let mut new_page = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        new_page(document)
+    }
+}
+impl<T> Clone for NewPage<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+impl<T> Debug for NewPage<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NewPage").finish_non_exhaustive()
+    }
+}
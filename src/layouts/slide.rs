@@ -0,0 +1,200 @@
+//! Full page slides for exporting presentations
+//!
+//! Every slide is its own page. Set the page mode to [PageMode::FullScreen](crate::document::PageMode::FullScreen) so viewers open the document as a presentation.
+//!
+//! ```rust
+//! use tux_pdf::{
+//!     document::{BuiltinFont, PageMode, PdfDocument},
+//!     layouts::{Theme, slide::{SlideLayout, SlideStyles}},
+//!     page::PageTransition,
+//! };
+//! let mut document = PdfDocument::new("Quarterly Review");
+//! document.font_map().register_builtin_font(BuiltinFont::Helvetica);
+//! document.font_map().register_builtin_font(BuiltinFont::HelveticaBold);
+//! document.metadata.set_page_mode(PageMode::FullScreen);
+//!
+//! let slides = SlideLayout::default()
+//!     .with_styles(SlideStyles::from_theme(&Theme::dark()))
+//!     .with_transition(PageTransition::dissolve());
+//! let slide = slides
+//!     .slide(&document, "Revenue", "Up 12% from last quarter")
+//!     .unwrap();
+//! document.add_page(slide);
+//! ```
+use crate::{
+    TuxPdfError,
+    document::PdfDocument,
+    graphics::{Anchor, Margin, TextBlock, TextBlockContent, TextStyle, size::Size},
+    page::{PageBackground, PageTransition, PdfPage, page_sizes::SLIDE_16_9},
+    units::{Pt, UnitType},
+};
+
+use super::{LayoutItemType, Theme};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlideStyles {
+    pub title_style: TextStyle,
+    pub body_style: TextStyle,
+    /// Space between the title and the body
+    pub title_gap: Pt,
+    pub margin: Margin,
+    pub background: Option<PageBackground>,
+}
+impl SlideStyles {
+    /// A large heading and body text on the background of the theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            title_style: TextStyle {
+                font_size: theme.heading_font_size * 2f32,
+                ..theme.heading_style()
+            },
+            body_style: TextStyle {
+                font_size: theme.font_size * 2f32,
+                ..theme.text_style()
+            },
+            background: Some(theme.colors.background.clone().into()),
+            ..Default::default()
+        }
+    }
+}
+impl Default for SlideStyles {
+    fn default() -> Self {
+        Self {
+            title_style: TextStyle {
+                font_size: 36f32.pt(),
+                ..Default::default()
+            },
+            body_style: TextStyle {
+                font_size: 24f32.pt(),
+                ..Default::default()
+            },
+            title_gap: 24f32.pt(),
+            margin: Margin::all(48f32.pt()),
+            background: None,
+        }
+    }
+}
+/// Creates slide sized pages with a title and a body
+///
+/// Use [SlideLayout::page] for a blank slide and place your own content with [PdfPage::place]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlideLayout {
+    /// Defaults to [SLIDE_16_9]
+    pub size: Size,
+    pub styles: SlideStyles,
+    /// Applied to every slide
+    pub transition: Option<PageTransition>,
+}
+impl Default for SlideLayout {
+    fn default() -> Self {
+        Self {
+            size: SLIDE_16_9,
+            styles: SlideStyles::default(),
+            transition: None,
+        }
+    }
+}
+impl SlideLayout {
+    pub fn with_size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+    pub fn with_styles(mut self, styles: SlideStyles) -> Self {
+        self.styles = styles;
+        self
+    }
+    pub fn with_transition(mut self, transition: impl Into<PageTransition>) -> Self {
+        self.transition = Some(transition.into());
+        self
+    }
+    /// An empty slide with the margin, background and transition of the layout
+    pub fn page(&self) -> PdfPage {
+        let mut page = PdfPage::new_from_page_size(self.size).with_margin(self.styles.margin);
+        page.background = self.styles.background.clone();
+        page.transition = self.transition;
+        page
+    }
+    /// A slide with the title at the top and the body below it
+    ///
+    /// Both wrap to the width of the content area
+    pub fn slide(
+        &self,
+        document: &PdfDocument,
+        title: impl Into<TextBlockContent>,
+        body: impl Into<TextBlockContent>,
+    ) -> Result<PdfPage, TuxPdfError> {
+        let mut page = self.page();
+        let width = page.content_area().size.width;
+        let mut title = TextBlock {
+            content: title.into(),
+            style: TextStyle {
+                max_width: Some(width),
+                ..self.styles.title_style.clone()
+            },
+            ..Default::default()
+        };
+        let title_height = title.calculate_size(document)?.height;
+        page.place(
+            document,
+            title,
+            Anchor::TopLeft,
+            (Pt::default(), Pt::default()),
+        )?;
+
+        let body = TextBlock {
+            content: body.into(),
+            style: TextStyle {
+                max_width: Some(width),
+                ..self.styles.body_style.clone()
+            },
+            ..Default::default()
+        };
+        page.place(
+            document,
+            body,
+            Anchor::TopLeft,
+            (Pt::default(), title_height + self.styles.title_gap),
+        )?;
+        Ok(page)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::SlideLayout;
+    use crate::{
+        document::{BuiltinFont, PdfDocument},
+        graphics::{PdfObject, TextBlock},
+        page::{PageTransition, page_sizes::SLIDE_16_9},
+        units::UnitType,
+    };
+
+    #[test]
+    fn title_above_body() -> anyhow::Result<()> {
+        let mut document = PdfDocument::new("Slides");
+        document
+            .font_map()
+            .register_builtin_font(BuiltinFont::Helvetica);
+        let layout = SlideLayout::default().with_transition(PageTransition::fade());
+        let page = layout.slide(&document, "Title", "Body")?;
+        assert_eq!(page.size(), SLIDE_16_9);
+        assert_eq!(page.transition, Some(PageTransition::fade()));
+
+        let blocks: Vec<&TextBlock> = page
+            .contents
+            .iter()
+            .filter_map(|object| match object {
+                PdfObject::TextBlock(block) => Some(block),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(blocks.len(), 2);
+        let (title, body) = (blocks[0], blocks[1]);
+        assert_eq!(title.position.x, 48f32.pt());
+        assert!(body.position.y < title.position.y);
+        assert_eq!(title.style.max_width, Some(SLIDE_16_9.width - 96f32.pt()));
+
+        document.add_page(page);
+        document.write_into_pdf_document_writer()?;
+        Ok(())
+    }
+}
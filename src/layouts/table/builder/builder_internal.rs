@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use tracing::{debug, error, warn};
 
 use super::{TableLayout, types::*};
@@ -20,6 +22,7 @@ pub struct TableLayoutBuilder {
     styles: GridStyles,
     rows: Vec<GridBuilderRow>,
     columns: Vec<GridBuilderColumn>,
+    header_groups: Vec<GridBuilderHeaderGroupRow>,
 }
 impl TableLayoutBuilder {
     pub fn new(
@@ -27,6 +30,7 @@ impl TableLayoutBuilder {
         styles: GridStyles,
         columns: Vec<NewTableColumn>,
         header_row_styles: Option<GridStyleGroup>,
+        header_groups: Vec<NewHeaderGroupRow>,
     ) -> Result<Self, TableError> {
         let TablePageRules {
             page_size,
@@ -63,8 +67,14 @@ impl TableLayoutBuilder {
             styles,
             rows: Default::default(),
             columns: Default::default(),
+            header_groups: Vec::with_capacity(header_groups.len()),
         };
         debug!(?builder, "Grid Layout created");
+        for group_row in header_groups {
+            if !builder.push_header_group_row(group_row) {
+                return Err(TableError::HeaderDoesNotFit);
+            }
+        }
         if !builder.initialize_columns(columns)? {
             return Err(TableError::HeaderDoesNotFit);
         }
@@ -80,6 +90,38 @@ impl TableLayoutBuilder {
             self.current_y - self.max_grid_size.height,
         )
     }
+    /// Places a row of grouped headings above the column headers
+    ///
+    /// Must be called before the columns are initialized. Returns false if the row does not fit
+    fn push_header_group_row(&mut self, group_row: NewHeaderGroupRow) -> bool {
+        let vertical_padding = self
+            .styles
+            .cell_content_padding
+            .vertical_value()
+            .unwrap_or_default();
+        let row_height = group_row
+            .cells
+            .iter()
+            .map(|cell| cell.initial_size.height)
+            .fold(Pt::default(), Pt::max)
+            + vertical_padding;
+        let next_y = self.current_y - row_height;
+        if next_y < self.max_grid_size.height {
+            debug!(?next_y, ?self.max_grid_size, "Header group row does not fit");
+            return false;
+        }
+        self.current_y = next_y;
+        self.header_groups.push(GridBuilderHeaderGroupRow {
+            row: GridBuilderRow {
+                y: self.current_y + vertical_padding,
+                content_start_y: self.current_y,
+                height: row_height,
+                styles: group_row.styles,
+            },
+            cells: group_row.cells,
+        });
+        true
+    }
     /// Calculates the initial columns widths and x positions
     fn initialize_columns(&mut self, columns: Vec<NewTableColumn>) -> Result<bool, TableError> {
         let column_sizes = columns
@@ -124,7 +166,7 @@ impl TableLayoutBuilder {
             self.columns.push(column);
         }
 
-        self.next_row(&column_sizes, None)
+        self.next_row(&column_sizes, None, self.styles.header_row_height)
     }
     /// Checks if any columns need to be recalculated
     ///
@@ -185,6 +227,7 @@ impl TableLayoutBuilder {
         &mut self,
         column_sizes: &[Size],
         style: Option<GridStyleGroup>,
+        height_limits: RowHeightLimits,
     ) -> Result<bool, TableError> {
         if self.columns.is_empty() {
             return Err(TableError::GridBuilderColumnsNotInitialized);
@@ -206,12 +249,13 @@ impl TableLayoutBuilder {
             warn!("No row height base");
             return Ok(false);
         };
-        let row_height = row_height_base
+        let content_height = row_height_base
             + self
                 .styles
                 .cell_content_padding
                 .vertical_value()
                 .unwrap_or_default();
+        let row_height = height_limits.apply(content_height);
 
         debug!(?row_height, "Next row");
         // Remember 0,0 is the bottom left corner so we subtract the row height
@@ -231,7 +275,8 @@ impl TableLayoutBuilder {
                     .cell_content_padding
                     .vertical_value()
                     .unwrap_or_default(),
-            content_start_y: self.current_y,
+            // Content starts at the top of rows that are taller or shorter than it
+            content_start_y: self.current_y + row_height - content_height,
             height: row_height,
             styles: style,
         });
@@ -245,10 +290,40 @@ impl TableLayoutBuilder {
     }
 
     pub fn calculate_full_height(&self) -> Pt {
-        self.rows
+        self.header_groups
             .iter()
+            .map(|group_row| &group_row.row)
+            .chain(self.rows.iter())
             .fold(0f32.pt(), |acc, cell| acc + cell.height)
     }
+    /// Widens the last column of a group if the group heading is wider than the columns it spans
+    fn fit_columns_to_header_groups(&mut self) {
+        let horizontal_padding = self
+            .styles
+            .cell_content_padding
+            .horizontal_value()
+            .unwrap_or_default();
+        let mut changed = false;
+        for group_row in &self.header_groups {
+            let mut first_column = 0;
+            for cell in &group_row.cells {
+                let spanned = first_column..(first_column + cell.span).min(self.columns.len());
+                first_column += cell.span;
+                let Some(last_column) = spanned.clone().last() else {
+                    continue;
+                };
+                let spanned_width = self.columns[spanned].iter().map(|c| c.width).sum::<Pt>();
+                let required_width = cell.initial_size.width + horizontal_padding;
+                if spanned_width < required_width {
+                    self.columns[last_column].width += required_width - spanned_width;
+                    changed = true;
+                }
+            }
+        }
+        if changed {
+            self.recalculate_column_x_positions();
+        }
+    }
     fn calculate_final_column_width_starting_at(&mut self, start_index: usize) {
         let mut x = if start_index == 0 {
             self.start.x
@@ -325,6 +400,7 @@ impl TableLayoutBuilder {
         }
     }
     pub fn build(mut self) -> TableLayout {
+        self.fit_columns_to_header_groups();
         self.apply_column_width_overrides();
         let final_size = Size::new(self.calculate_full_width(), self.calculate_full_height());
 
@@ -351,16 +427,42 @@ impl TableLayoutBuilder {
             };
             columns.push(result);
         }
-        let rows: Vec<TableRow> = self
-            .rows
+        let into_table_row = |row: GridBuilderRow| TableRow {
+            content_y: row.content_start_y + top_padding,
+            border_line_y: row.y - vertical_padding,
+            height: row.height,
+            styles: row.styles,
+        };
+        let header_groups = self
+            .header_groups
             .into_iter()
-            .map(|row| TableRow {
-                content_y: row.content_start_y + top_padding,
-                border_line_y: row.y - vertical_padding,
-                height: row.height,
-                styles: row.styles,
+            .map(|group_row| {
+                let mut first_column = 0;
+                let cells = group_row
+                    .cells
+                    .into_iter()
+                    .filter_map(|cell| {
+                        let start = first_column;
+                        first_column += cell.span;
+                        let spanned = columns.get(start..(start + cell.span).min(columns.len()))?;
+                        let first = spanned.first()?;
+                        Some(TableHeaderGroupCell {
+                            first_column: start,
+                            span: spanned.len(),
+                            x: first.x,
+                            width: spanned.iter().map(|column| column.width).sum(),
+                            content_x: first.content_x,
+                            styles: cell.styles,
+                        })
+                    })
+                    .collect();
+                TableHeaderGroupRow {
+                    row: into_table_row(group_row.row),
+                    cells,
+                }
             })
             .collect();
+        let rows: Vec<TableRow> = self.rows.into_iter().map(into_table_row).collect();
 
         TableLayout {
             final_size,
@@ -368,6 +470,8 @@ impl TableLayoutBuilder {
             styles: self.styles,
             rows,
             columns,
+            header_groups,
+            cell_fills: BTreeMap::new(),
         }
     }
 }
@@ -4,13 +4,18 @@ pub use builder_internal::*;
 use tracing::{debug, error};
 pub use types::*;
 
+use std::collections::BTreeMap;
+
 use crate::{
     graphics::{
         GraphicItems, GraphicStyles, GraphicsGroup, PdfPosition,
+        color::Color,
         primitives::StraightLine,
         shapes::{OutlineRect, PaintedRect, RectangleStyleType},
         size::Size,
     },
+    layouts::table::CellRenderArea,
+    units::Pt,
     utils::Merge,
 };
 mod types;
@@ -68,8 +73,20 @@ pub struct TableLayout {
     pub(crate) styles: GridStyles,
     pub(crate) rows: Vec<TableRow>,
     pub(crate) columns: Vec<GridColumn>,
+    pub(crate) header_groups: Vec<TableHeaderGroupRow>,
+    /// Backgrounds of single cells. Keyed by row and column
+    pub(crate) cell_fills: BTreeMap<(usize, usize), Color>,
 }
 impl TableLayout {
+    /// Fills the background of a cell. Row `0` is the header row
+    ///
+    /// Drawn over the background of the row and under the borders
+    pub fn set_cell_fill(&mut self, row: usize, column: usize, color: Color) {
+        self.cell_fills.insert((row, column), color);
+    }
+    pub fn cell_fill(&self, row: usize, column: usize) -> Option<&Color> {
+        self.cell_fills.get(&(row, column))
+    }
     /// Gets the column location for a row and column
     pub fn get_cell_location(&self, row: usize, column: usize) -> Option<PdfPosition> {
         if row >= self.rows.len() {
@@ -86,6 +103,38 @@ impl TableLayout {
         Some(PdfPosition { x, y })
     }
 
+    /// Gets the area of a cell for a [CellRenderer](super::CellRenderer)
+    pub fn cell_area(&self, row: usize, column: usize) -> Option<CellRenderArea> {
+        let row = self.rows.get(row)?;
+        let column = self.columns.get(column)?;
+        let padding = self.styles.cell_content_padding;
+        let cell_position = PdfPosition {
+            x: column.x,
+            y: row.border_line_y,
+        };
+        let cell_size = Size {
+            width: column.width,
+            height: row.height,
+        };
+        let content_position = PdfPosition {
+            x: cell_position.x + padding.left.unwrap_or_default(),
+            y: cell_position.y + padding.bottom.unwrap_or_default(),
+        };
+        let content_size = Size {
+            width: column.width_no_padding,
+            height: row.height - padding.vertical_value().unwrap_or_default(),
+        };
+        Some(CellRenderArea {
+            cell_position,
+            cell_size,
+            content_position,
+            content_size,
+        })
+    }
+    /// The rows of grouped headings above the column headers
+    pub fn header_group_rows(&self) -> &[TableHeaderGroupRow] {
+        &self.header_groups
+    }
     pub fn row_iter(&self) -> TableRowPlacementIter<'_> {
         TableRowPlacementIter {
             table_rect: self,
@@ -142,7 +191,8 @@ impl TableLayout {
     fn rows(&self) -> Vec<GraphicItems> {
         let mut rows = Vec::new();
         let row_styles = self.styles.row_styles.clone();
-        for row in &self.rows {
+        let header_group_rows = self.header_groups.iter().map(|group_row| &group_row.row);
+        for row in header_group_rows.chain(self.rows.iter()) {
             let row_height = row.height;
             let paint_mode = row
                 .styles
@@ -212,11 +262,98 @@ impl TableLayout {
         }
         cells
     }
+    /// The cell backgrounds. Kept inside of the row borders so they are not painted over
+    fn cell_backgrounds(&self) -> Vec<GraphicItems> {
+        let mut backgrounds = Vec::with_capacity(self.cell_fills.len());
+        for (&(row, column), color) in &self.cell_fills {
+            let Some(area) = self.cell_area(row, column) else {
+                continue;
+            };
+            let mut row_styles = self.styles.row_styles.clone().unwrap_or_default();
+            row_styles.merge_with_option(self.rows[row].styles.clone());
+            let inset = if row_styles.border_color.is_some() {
+                row_styles.border_width.unwrap_or_default() / 2f32
+            } else {
+                Pt::default()
+            };
+            let background = PaintedRect {
+                position: PdfPosition {
+                    x: area.cell_position.x + inset,
+                    y: area.cell_position.y + inset,
+                },
+                size: Size {
+                    width: area.cell_size.width - inset * 2f32,
+                    height: area.cell_size.height - inset * 2f32,
+                },
+                ..Default::default()
+            };
+            backgrounds.push(
+                GraphicsGroup {
+                    styles: Some(GraphicStyles {
+                        fill_color: Some(color.clone()),
+                        ..Default::default()
+                    }),
+                    items: vec![background.into()],
+                    ..Default::default()
+                }
+                .into(),
+            );
+        }
+        backgrounds
+    }
+    fn header_group_cells(&self) -> Vec<GraphicItems> {
+        let mut cells = Vec::new();
+        for group_row in &self.header_groups {
+            let row = &group_row.row;
+            for cell in &group_row.cells {
+                if let Some(styles) = &cell.styles
+                    && let Some(paint_mode) = styles.paint_mode()
+                {
+                    let cell_rect = PaintedRect {
+                        position: PdfPosition {
+                            x: cell.x,
+                            y: row.border_line_y,
+                        },
+                        size: Size {
+                            width: cell.width,
+                            height: row.height,
+                        },
+                        paint_mode,
+                        ..Default::default()
+                    };
+                    cells.push(
+                        GraphicsGroup {
+                            styles: Some(styles.into()),
+                            items: vec![cell_rect.into()],
+                            ..Default::default()
+                        }
+                        .into(),
+                    );
+                }
+                if self.styles.cell_styles.is_some() {
+                    let cell_box = OutlineRect {
+                        position: PdfPosition {
+                            x: cell.x,
+                            y: row.border_line_y + row.height,
+                        },
+                        size: Size {
+                            width: cell.width,
+                            height: row.height,
+                        },
+                    };
+                    cells.push(cell_box.into());
+                }
+            }
+        }
+        cells
+    }
     /// Creates the Graphics to render the table
     ///
     /// Such as Backgrounds and borders, and other lines
     pub fn table_graphics(&self) -> GraphicsGroup {
         let mut lines: Vec<GraphicItems> = self.rows();
+        lines.extend(self.cell_backgrounds());
+        lines.extend(self.header_group_cells());
         lines.extend(self.cells());
         lines.extend(self.table_outline());
 
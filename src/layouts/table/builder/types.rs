@@ -69,6 +69,40 @@ pub struct TableRow {
     pub height: Pt,
     pub styles: Option<GridStyleGroup>,
 }
+/// A row of grouped headings placed above the column headers
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NewHeaderGroupRow {
+    pub cells: Vec<NewHeaderGroupCell>,
+    pub styles: Option<GridStyleGroup>,
+}
+/// A grouped heading based on the size of its text
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewHeaderGroupCell {
+    /// The number of columns this cell spans
+    pub span: usize,
+    pub initial_size: Size,
+    pub styles: Option<GridStyleGroup>,
+}
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct GridBuilderHeaderGroupRow {
+    pub row: GridBuilderRow,
+    pub cells: Vec<NewHeaderGroupCell>,
+}
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableHeaderGroupRow {
+    pub row: TableRow,
+    pub cells: Vec<TableHeaderGroupCell>,
+}
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableHeaderGroupCell {
+    /// The index of the first column this cell spans
+    pub first_column: usize,
+    pub span: usize,
+    pub x: Pt,
+    pub width: Pt,
+    pub content_x: Pt,
+    pub styles: Option<GridStyleGroup>,
+}
 /// A new Grid Column based on the the size of the column header text
 #[derive(Debug, Clone, PartialEq)]
 pub struct NewTableColumn {
@@ -111,6 +145,36 @@ pub struct GridStyles {
     pub cell_styles: Option<GridStyleGroup>,
     /// The styles for the rows
     pub row_styles: Option<GridStyleGroup>,
+    /// Limits the height of the header row
+    pub header_row_height: RowHeightLimits,
+}
+/// Limits the height of a row. The height includes the cell padding
+///
+/// Content taller than the max height overflows the bottom of the row
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RowHeightLimits {
+    pub min: Option<Pt>,
+    pub max: Option<Pt>,
+}
+impl RowHeightLimits {
+    /// The row is always the height
+    pub fn fixed(height: Pt) -> Self {
+        Self {
+            min: Some(height),
+            max: Some(height),
+        }
+    }
+    /// The height of the row for its content. The min height wins if it is larger than the max height
+    pub fn apply(&self, content_height: Pt) -> Pt {
+        let height = match self.max {
+            Some(max) => content_height.min(max),
+            None => content_height,
+        };
+        match self.min {
+            Some(min) => height.max(min),
+            None => height,
+        }
+    }
 }
 impl From<GridStyleGroup> for GraphicStyles {
     fn from(value: GridStyleGroup) -> Self {
@@ -118,6 +182,7 @@ impl From<GridStyleGroup> for GraphicStyles {
             line_width: value.border_width,
             fill_color: value.background_color,
             outline_color: value.border_color,
+            ..Default::default()
         }
     }
 }
@@ -127,6 +192,7 @@ impl From<&GridStyleGroup> for GraphicStyles {
             line_width: value.border_width,
             fill_color: value.background_color.clone(),
             outline_color: value.border_color.clone(),
+            ..Default::default()
         }
     }
 }
@@ -0,0 +1,95 @@
+use std::{fmt::Debug, sync::Arc};
+
+use crate::{
+    TuxPdfError,
+    document::PdfDocument,
+    graphics::{PdfObject, PdfPosition, TextStyle, size::Size},
+};
+
+use super::{BuiltinIcon, TableValue};
+
+/// The area of a cell that a [CellRenderer] draws in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CellRenderArea {
+    /// The bottom left corner of the cell including the padding
+    pub cell_position: PdfPosition,
+    /// The size of the cell including the padding
+    pub cell_size: Size,
+    /// The bottom left corner of the content area
+    pub content_position: PdfPosition,
+    /// The size of the cell without the padding
+    pub content_size: Size,
+}
+/// Draws custom content inside of a table cell.
+///
+/// Allows drawing sparklines, progress bars, rating stars, etc.
+///
+/// ```rust,no_run
+/// use tux_pdf::{
+///     TuxPdfError,
+///     document::PdfDocument,
+///     graphics::{PdfObject, TextStyle, shapes::PaintedRect, size::Size},
+///     layouts::table::{CellRenderArea, CellRenderer},
+///     units::UnitType,
+/// };
+/// #[derive(Debug)]
+/// struct ProgressBar(f32);
+/// impl CellRenderer for ProgressBar {
+///     fn content_size(&self, _: &PdfDocument, _: &TextStyle) -> Result<Size, TuxPdfError> {
+///         Ok(Size::new(100f32.pt(), 10f32.pt()))
+///     }
+///     fn render(
+///         &self,
+///         _: &PdfDocument,
+///         area: CellRenderArea,
+///         _: &TextStyle,
+///     ) -> Result<Vec<PdfObject>, TuxPdfError> {
+///         let size = Size::new(area.content_size.width * self.0, area.content_size.height);
+///         let bar = PaintedRect {
+///             position: area.content_position,
+///             size,
+///             ..Default::default()
+///         };
+///         Ok(vec![bar.into()])
+///     }
+/// }
+/// ```
+pub trait CellRenderer: Debug {
+    /// The size of the content without padding.
+    ///
+    /// Used to size the row and column the cell is in
+    fn content_size(
+        &self,
+        document: &PdfDocument,
+        text_style: &TextStyle,
+    ) -> Result<Size, TuxPdfError>;
+    /// Creates the objects that are drawn in the cell
+    ///
+    /// Called after the table layout has been calculated.
+    ///
+    /// The document is borrowed immutably so tables can also be drawn as a [PdfComponent](crate::layouts::PdfComponent).
+    /// Fonts and images a renderer uses must be registered before the table is rendered.
+    fn render(
+        &self,
+        document: &PdfDocument,
+        area: CellRenderArea,
+        text_style: &TextStyle,
+    ) -> Result<Vec<PdfObject>, TuxPdfError>;
+}
+
+impl TableValue {
+    /// Creates a value that is drawn by a [CellRenderer]
+    pub fn custom(renderer: impl CellRenderer + 'static) -> Self {
+        Self::Custom(Arc::new(renderer))
+    }
+    /// The [CellRenderer] that draws the value. None for text and blank space
+    pub(crate) fn renderer(&self) -> Option<&dyn CellRenderer> {
+        match self {
+            TableValue::Custom(renderer) => Some(renderer.as_ref()),
+            TableValue::Checkbox(checked) => Some(BuiltinIcon::checkbox(*checked)),
+            TableValue::Icon(icon) => Some(icon),
+            TableValue::Progress(bar) => Some(bar),
+            TableValue::Text(_) | TableValue::BlankSpace => None,
+        }
+    }
+}
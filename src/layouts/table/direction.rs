@@ -0,0 +1,97 @@
+use crate::document::PdfDocument;
+
+use super::{CellAlignment, Table};
+
+impl CellAlignment {
+    /// Swaps left and right. Center and decimal alignment are unchanged
+    pub fn mirrored(self) -> Self {
+        match self {
+            CellAlignment::Left => CellAlignment::Right,
+            CellAlignment::Right => CellAlignment::Left,
+            other => other,
+        }
+    }
+}
+impl Table {
+    /// True if the table is drawn right to left. Falls back to the reading direction of the document
+    pub(crate) fn is_right_to_left(&self, document: &PdfDocument) -> bool {
+        self.styles
+            .direction
+            .unwrap_or_else(|| document.reading_direction())
+            .is_right_to_left()
+    }
+    /// Reverses the columns, the values of each row and the header groups. Alignments are mirrored
+    ///
+    /// The first column ends up on the right side of the page
+    pub(crate) fn mirrored(mut self) -> Table {
+        self.columns.reverse();
+        for column in &mut self.columns {
+            let alignment = column.alignment().mirrored();
+            column.styles.get_or_insert_default().alignment = Some(alignment);
+        }
+        for row in &mut self.rows {
+            row.values.reverse();
+        }
+        for group_row in &mut self.header_groups {
+            group_row.groups.reverse();
+            for group in &mut group_row.groups {
+                group.alignment = group.alignment.mirrored();
+            }
+        }
+        self
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::{
+        document::{BuiltinFont, PdfDocument, ReadingDirection},
+        layouts::table::{
+            CellAlignment, Column, ColumnGroup, HeaderGroupRow, Row, Table, TablePageRules,
+            TableValue,
+        },
+        page::{PdfPage, page_sizes::A4},
+    };
+
+    fn invoice() -> Table {
+        let mut table = Table::default();
+        table.add_column("Item".into());
+        table.add_column("Quantity".into());
+        table.add_column(Column::from("Price").with_alignment(CellAlignment::Right));
+        table.add_header_group_row(HeaderGroupRow::from(vec![
+            ColumnGroup::new("Product", 1),
+            ColumnGroup::new("Sale", 2),
+        ]));
+        table.add_row(Row::from(vec!["Widget", "2", "5.00"]));
+        table
+    }
+
+    #[test]
+    fn mirror_columns() -> anyhow::Result<()> {
+        let mirrored = invoice().mirrored();
+        assert_eq!(mirrored.column_index("Item"), Some(2));
+        assert_eq!(mirrored.columns[0].alignment(), CellAlignment::Left);
+        assert_eq!(mirrored.columns[2].alignment(), CellAlignment::Right);
+        assert_eq!(mirrored.header_groups[0].groups[0].span, 2);
+        assert_eq!(
+            mirrored.header_groups[0].groups[1].alignment,
+            CellAlignment::Right
+        );
+        let TableValue::Text(price) = &mirrored.rows[0].values[0].value else {
+            panic!("Expected text");
+        };
+        assert_eq!(price.text(), "5.00");
+
+        // The report keeps the indexes of the columns as they were defined
+        let mut doc = PdfDocument::new("Right to left");
+        doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        doc.set_reading_direction(ReadingDirection::R2L);
+        let page = PdfPage::new_from_page_size(A4);
+        let report = invoice().render(&mut doc, (TablePageRules::for_page(&page), page))?;
+        let page = &report.pages[0];
+        assert_eq!(page.columns, [2, 1, 0]);
+        let item = page.cell(0, 0).expect("Item cell");
+        let price = page.cell(0, 2).expect("Price cell");
+        assert!(item.cell_position.x > price.cell_position.x);
+        Ok(())
+    }
+}
@@ -0,0 +1,204 @@
+use std::{fmt::Debug, sync::Arc};
+
+use crate::graphics::color::{Color, ColorRamp};
+
+use super::{Column, Row, Table, TableValue, TableValueWithStyle, builder::TableLayout};
+
+/// Called with the value of a cell. See [CellColorRule::custom]
+pub type CellColorFn = Arc<dyn Fn(&TableValue) -> Option<Color> + Send + Sync>;
+/// Colors the background of the cells of a column based on their value
+///
+/// ```rust
+/// use tux_pdf::{
+///     graphics::color::{Color, ColorRamp},
+///     layouts::table::{CellColorRule, Column},
+/// };
+/// let ramp = ColorRamp::new(Color::hsl(120.0, 0.6, 0.8), Color::hsl(0.0, 0.6, 0.8));
+/// let usage = Column::from("CPU Usage").with_color_rule(CellColorRule::scale(0.0, 100.0, ramp));
+/// // Anything over the limit is painted red
+/// let errors = Column::from("Errors").with_color_rule(CellColorRule::custom(|value| {
+///     let errors: u32 = value.text()?.parse().ok()?;
+///     (errors > 0).then(|| Color::from_rgb_u8(255, 200, 200))
+/// }));
+/// ```
+#[derive(Clone)]
+pub enum CellColorRule {
+    /// Numbers are colored by where they are between `min` and `max`. Other values are not colored
+    ///
+    /// Numbers can have a leading currency symbol, a trailing percent sign and thousands separators
+    Scale { min: f32, max: f32, ramp: ColorRamp },
+    /// Called with the value of every cell in the column. None leaves the background unchanged
+    Custom(CellColorFn),
+}
+impl CellColorRule {
+    pub fn scale(min: f32, max: f32, ramp: ColorRamp) -> Self {
+        CellColorRule::Scale { min, max, ramp }
+    }
+    pub fn custom(rule: impl Fn(&TableValue) -> Option<Color> + Send + Sync + 'static) -> Self {
+        CellColorRule::Custom(Arc::new(rule))
+    }
+    /// The background of a cell with the value
+    pub fn color_for(&self, value: &TableValue) -> Option<Color> {
+        match self {
+            CellColorRule::Scale { min, max, ramp } => {
+                let number = parse_number(&value.text()?)?;
+                Some(ramp.color_for_value(number as f32, *min, *max))
+            }
+            CellColorRule::Custom(rule) => rule(value),
+        }
+    }
+}
+impl Debug for CellColorRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CellColorRule::Scale { min, max, ramp } => f
+                .debug_struct("Scale")
+                .field("min", min)
+                .field("max", max)
+                .field("ramp", ramp)
+                .finish(),
+            CellColorRule::Custom(_) => f.debug_struct("Custom").finish_non_exhaustive(),
+        }
+    }
+}
+impl PartialEq for CellColorRule {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                CellColorRule::Scale { min, max, ramp },
+                CellColorRule::Scale {
+                    min: other_min,
+                    max: other_max,
+                    ramp: other_ramp,
+                },
+            ) => min == other_min && max == other_max && ramp == other_ramp,
+            (CellColorRule::Custom(rule), CellColorRule::Custom(other)) => Arc::ptr_eq(rule, other),
+            _ => false,
+        }
+    }
+}
+/// Accepts a leading currency symbol, a trailing percent sign and thousands separators. `$1,234.50` or `-12%`
+fn parse_number(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let value = value
+        .strip_suffix('%')
+        .unwrap_or(value)
+        .trim_start_matches(['$', '€', '£', '¥']);
+    value.replace([',', '_'], "").parse().ok()
+}
+impl Column {
+    pub fn with_color_rule(mut self, rule: CellColorRule) -> Self {
+        self.styles.get_or_insert_with(Default::default).color_rule = Some(rule);
+        self
+    }
+}
+impl Table {
+    /// The background of the cell.
+    ///
+    /// The style of the value takes priority over the [color rule](CellColorRule) and the cell styles of the column
+    fn cell_fill_color(&self, value: &TableValueWithStyle, column_index: usize) -> Option<Color> {
+        if let Some(fill_color) = value
+            .style
+            .as_ref()
+            .and_then(|style| style.fill_color.clone())
+        {
+            return Some(fill_color);
+        }
+        let column_styles = self.columns.get(column_index)?.styles.as_ref()?;
+        column_styles
+            .color_rule
+            .as_ref()
+            .and_then(|rule| rule.color_for(&value.value))
+            .or_else(|| column_styles.cell_styles.as_ref()?.fill_color.clone())
+    }
+    /// Adds the cell backgrounds of the rows on a page to its layout
+    pub(super) fn apply_cell_fills(&self, rows: &[Row], grid_layout: &mut TableLayout) {
+        for (row_index, row) in rows.iter().enumerate() {
+            for (column_index, value) in row.values.iter().enumerate() {
+                if let Some(fill_color) = self.cell_fill_color(value, column_index) {
+                    // The first row of the layout is the header row
+                    grid_layout.set_cell_fill(row_index + 1, column_index, fill_color);
+                }
+            }
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::{CellColorRule, parse_number};
+    use crate::{
+        document::{BuiltinFont, PdfDocument},
+        graphics::{
+            GraphicItems,
+            color::{BLACK_RGB, Color, ColorRamp, WHITE_RGB},
+        },
+        layouts::table::{Column, Row, Table, TablePageRules, TableValue, TableValueWithStyle},
+        page::{PdfPage, page_sizes::A4},
+    };
+
+    #[test]
+    fn numbers() {
+        assert_eq!(parse_number("$1,234.50"), Some(1234.5));
+        assert_eq!(parse_number(" -12% "), Some(-12.0));
+        assert_eq!(parse_number("twelve"), None);
+    }
+    #[test]
+    fn scale_rule() {
+        let rule = CellColorRule::scale(0.0, 10.0, ColorRamp::new(BLACK_RGB, WHITE_RGB));
+        let Some(Color::Rgb(color)) = rule.color_for(&TableValue::from("5")) else {
+            panic!("A number was not colored");
+        };
+        assert_eq!(color.r, 0.5);
+        assert_eq!(rule.color_for(&TableValue::from("n/a")), None);
+        assert_eq!(rule.color_for(&TableValue::BlankSpace), None);
+    }
+    #[test]
+    fn cells_are_filled_in_layout() -> anyhow::Result<()> {
+        let red = Color::from_rgb_u8(255, 0, 0);
+        let rule_red = red.clone();
+        let mut table = Table {
+            columns: vec![
+                Column::from("Name"),
+                Column::from("Score").with_color_rule(CellColorRule::custom(move |value| {
+                    (value.text()? == "0").then(|| rule_red.clone())
+                })),
+            ],
+            rows: vec![
+                Row::from(vec!["a", "0"]),
+                Row::from(vec!["b", "1"]),
+                Row {
+                    values: vec![
+                        TableValueWithStyle::from("c").with_fill_color(WHITE_RGB),
+                        TableValueWithStyle::from("0").with_fill_color(BLACK_RGB),
+                    ],
+                    styles: None,
+                },
+            ],
+            ..Default::default()
+        };
+        let mut document = PdfDocument::new("Heatmap");
+        document
+            .font_map()
+            .register_builtin_font(BuiltinFont::Helvetica);
+        let page = PdfPage::new_from_page_size(A4);
+        let (rows, mut layout) =
+            table.layout_single_page(&document, &TablePageRules::for_page(&page))?;
+        table.apply_cell_fills(&rows, &mut layout);
+        assert_eq!(layout.cell_fill(1, 1), Some(&red));
+        assert_eq!(layout.cell_fill(2, 1), None);
+        assert_eq!(layout.cell_fill(3, 0), Some(&WHITE_RGB));
+        assert_eq!(layout.cell_fill(3, 1), Some(&BLACK_RGB));
+
+        let fills = layout
+            .table_graphics()
+            .items
+            .into_iter()
+            .filter(|item| {
+                matches!(item, GraphicItems::Group(group)
+                    if group.styles.as_ref().is_some_and(|styles| styles.fill_color.as_ref() == Some(&red)))
+            })
+            .count();
+        assert_eq!(fills, 1);
+        Ok(())
+    }
+}
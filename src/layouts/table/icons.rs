@@ -0,0 +1,181 @@
+use crate::{
+    TuxPdfError,
+    document::PdfDocument,
+    graphics::{
+        GraphicStyles, GraphicsGroup, PdfObject, PdfPosition, TextStyle,
+        primitives::{PaintMode, PathBuilder, StraightLine},
+        shapes::OutlineRect,
+        size::Size,
+    },
+    units::Pt,
+};
+
+use super::{CellRenderArea, CellRenderer};
+
+/// Small glyphs drawn with vector shapes. So no font is needed for ✓ or ✗
+///
+/// Icons are a square as tall as the font size of the row. Centered horizontally and at the top of the cell.
+/// They are drawn with the fill color of the row text style
+///
+/// ```rust
+/// use tux_pdf::layouts::table::{BuiltinIcon, Row, TableValue};
+/// let row = Row::from(vec![
+///     TableValue::from("Shipped"),
+///     TableValue::Checkbox(true),
+///     TableValue::Icon(BuiltinIcon::Cross),
+/// ]);
+/// assert_eq!(row.number_of_columns(), 3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuiltinIcon {
+    Check,
+    Cross,
+    Dot,
+    /// An empty checkbox
+    Box,
+    /// A checkbox with a check in it
+    CheckedBox,
+}
+impl BuiltinIcon {
+    /// The icon used for [TableValue::Checkbox](super::TableValue::Checkbox)
+    pub fn checkbox(checked: bool) -> &'static Self {
+        if checked {
+            &BuiltinIcon::CheckedBox
+        } else {
+            &BuiltinIcon::Box
+        }
+    }
+    /// The shapes of the icon inside of a square with the lower left corner at `position`
+    fn items(&self, position: PdfPosition, side: Pt) -> GraphicsGroup {
+        let PdfPosition { x, y } = position;
+        let line_width = side * 0.1f32;
+        // Positions as fractions of the square
+        let at = |fraction_x: f32, fraction_y: f32| {
+            PdfPosition::new(x + side * fraction_x, y + side * fraction_y)
+        };
+        let check = || StraightLine {
+            start: at(0.22, 0.5),
+            points: vec![at(0.42, 0.28), at(0.78, 0.74)],
+            is_closed: false,
+        };
+        let outline = || OutlineRect {
+            position: at(0.05, 0.05),
+            size: Size::new(side * 0.9f32, side * 0.9f32),
+        };
+        let items = match self {
+            BuiltinIcon::Check => vec![check().into()],
+            BuiltinIcon::Cross => vec![
+                StraightLine::from(vec![at(0.25, 0.25), at(0.75, 0.75)]).into(),
+                StraightLine::from(vec![at(0.25, 0.75), at(0.75, 0.25)]).into(),
+            ],
+            BuiltinIcon::Dot => vec![
+                PathBuilder::new()
+                    .arc_to(at(0.5, 0.5), side * 0.3f32, 0f32, 360f32)
+                    .close()
+                    .with_mode(PaintMode::Fill)
+                    .build()
+                    .into(),
+            ],
+            BuiltinIcon::Box => vec![outline().into()],
+            BuiltinIcon::CheckedBox => vec![outline().into(), check().into()],
+        };
+        GraphicsGroup {
+            items,
+            ..Default::default()
+        }
+        .with_styles(GraphicStyles {
+            line_width: Some(line_width),
+            ..Default::default()
+        })
+    }
+}
+impl CellRenderer for BuiltinIcon {
+    fn content_size(&self, _: &PdfDocument, text_style: &TextStyle) -> Result<Size, TuxPdfError> {
+        Ok(Size::new(text_style.font_size, text_style.font_size))
+    }
+
+    fn render(
+        &self,
+        _: &PdfDocument,
+        area: CellRenderArea,
+        text_style: &TextStyle,
+    ) -> Result<Vec<PdfObject>, TuxPdfError> {
+        let CellRenderArea {
+            content_position,
+            content_size,
+            ..
+        } = area;
+        let side = text_style
+            .font_size
+            .min(content_size.width)
+            .min(content_size.height);
+        let position = PdfPosition::new(
+            content_position.x + (content_size.width - side) * 0.5f32,
+            content_position.y + content_size.height - side,
+        );
+        let mut group = self.items(position, side);
+        if let Some(styles) = group.styles.as_mut() {
+            styles.fill_color = text_style.fill_color.clone();
+            styles.outline_color = text_style.fill_color.clone();
+        }
+        Ok(vec![group.into()])
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::BuiltinIcon;
+    use crate::{
+        document::PdfDocument,
+        graphics::{
+            GraphicItems, PdfObject, PdfPosition, TextStyle, shapes::OutlineRect, size::Size,
+        },
+        layouts::table::{CellRenderArea, CellRenderer},
+        units::UnitType,
+    };
+
+    #[test]
+    fn drawn_inside_of_the_cell() -> anyhow::Result<()> {
+        let document = PdfDocument::new("Icons");
+        let text_style = TextStyle {
+            font_size: 10f32.pt(),
+            ..Default::default()
+        };
+        let area = CellRenderArea {
+            cell_position: PdfPosition::new(0f32.pt(), 0f32.pt()),
+            cell_size: Size::new(60f32.pt(), 30f32.pt()),
+            content_position: PdfPosition::new(5f32.pt(), 5f32.pt()),
+            content_size: Size::new(50f32.pt(), 20f32.pt()),
+        };
+        let content = OutlineRect {
+            position: area.content_position,
+            size: area.content_size,
+        };
+        for icon in [
+            BuiltinIcon::Check,
+            BuiltinIcon::Cross,
+            BuiltinIcon::Dot,
+            BuiltinIcon::Box,
+            BuiltinIcon::CheckedBox,
+        ] {
+            assert_eq!(
+                icon.content_size(&document, &text_style)?,
+                Size::new(10f32.pt(), 10f32.pt())
+            );
+            let objects = icon.render(&document, area, &text_style)?;
+            let [PdfObject::Graphics(GraphicItems::Group(group))] = objects.as_slice() else {
+                panic!("{icon:?} is drawn as one group");
+            };
+            let bounds = group.bounds().expect("The icon has shapes");
+            assert_eq!(
+                content.intersection(&bounds),
+                Some(bounds),
+                "{icon:?} is inside of the content area"
+            );
+            // Centered horizontally and at the top
+            assert_eq!(bounds.center().x, content.center().x);
+            assert!(bounds.upper_right().y <= content.upper_right().y);
+            assert!(bounds.position.y >= 15f32.pt());
+        }
+        Ok(())
+    }
+}
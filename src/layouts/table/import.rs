@@ -0,0 +1,344 @@
+use std::io::Read;
+
+use crate::{
+    TuxPdfError,
+    document::PdfDocument,
+    graphics::{
+        PartialTextStyle,
+        color::{BLACK_RGB, GRAY_RGB, WHITE_RGB},
+    },
+    page::PdfPage,
+};
+
+use super::{
+    CellAlignment, Column, ColumnSizing, NewPageFn, Row, RowStyles, Table, TableError,
+    TablePageRules, TableStyles, TableValueWithStyle, builder::TableColumnMaxWidth,
+    no_new_page_allowed,
+};
+/// Columns are never weighted as narrower or wider than this many characters
+const MIN_COLUMN_WEIGHT: usize = 4;
+const MAX_COLUMN_WEIGHT: usize = 40;
+/// Options for [from_csv]
+#[derive(Debug, Clone)]
+pub struct TableImportOptions {
+    /// The first record is used as the column headers.
+    ///
+    /// Otherwise the columns are named `Column 1`, `Column 2`, ...
+    pub has_headers: bool,
+    pub delimiter: u8,
+    /// Columns where every non empty value is a number are [right aligned](CellAlignment::Right)
+    pub align_numbers: bool,
+    /// Replaces the header styles of [Self::styles]
+    ///
+    /// Defaults to white text on a black background
+    pub header_styles: Option<RowStyles>,
+    /// The styles of the created table
+    ///
+    /// Defaults to rows [striped](TableStyles::striped_rows) gray and white
+    pub styles: TableStyles,
+    pub new_page: NewPageFn,
+}
+impl Default for TableImportOptions {
+    fn default() -> Self {
+        Self {
+            has_headers: true,
+            delimiter: b',',
+            align_numbers: true,
+            header_styles: Some(RowStyles {
+                text_style: Some(
+                    PartialTextStyle {
+                        fill_color: Some(WHITE_RGB),
+                        ..Default::default()
+                    }
+                    .into(),
+                ),
+                background_color: Some(BLACK_RGB),
+                ..Default::default()
+            }),
+            styles: TableStyles {
+                striped_rows: Some([GRAY_RGB, WHITE_RGB]),
+                column_sizing: ColumnSizing::MeasureSample(100),
+                ..Default::default()
+            },
+            new_page: NewPageFn::new(no_new_page_allowed),
+        }
+    }
+}
+impl TableImportOptions {
+    pub fn with_new_page(
+        mut self,
+        new_page: impl FnMut(&mut PdfDocument) -> Result<(TablePageRules, PdfPage), TuxPdfError>
+        + Send
+        + 'static,
+    ) -> Self {
+        self.new_page = NewPageFn::new(new_page);
+        self
+    }
+    pub fn with_styles(mut self, styles: TableStyles) -> Self {
+        self.styles = styles;
+        self
+    }
+}
+/// Creates a table from CSV data
+///
+/// Column widths are limited to a share of the table width based on the length of their content.
+/// So long text columns wrap instead of pushing the other columns off of the page.
+/// Numeric columns are never wrapped.
+///
+/// Returns [TableError::ColumnValueMismatch] if a record does not have a value for every column
+///
+/// ```rust,no_run
+/// # fn main() -> Result<(), tux_pdf::TuxPdfError> {
+/// use tux_pdf::{
+///     document::PdfDocument,
+///     layouts::table::{TableImportOptions, TablePageRules, from_csv},
+///     page::{PdfPage, page_sizes::A4},
+/// };
+/// let mut doc = PdfDocument::new("Report");
+/// let csv = std::fs::File::open("report.csv")?;
+/// let table = from_csv(csv, TableImportOptions::default())?;
+///
+/// let page = PdfPage::new_from_page_size(A4);
+/// table.render(&mut doc, (TablePageRules::for_page(&page), page))?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn from_csv<R: Read>(reader: R, options: TableImportOptions) -> Result<Table, TuxPdfError> {
+    let TableImportOptions {
+        has_headers,
+        delimiter,
+        align_numbers,
+        header_styles,
+        mut styles,
+        new_page,
+    } = options;
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_reader(reader);
+    let mut records = reader.records();
+
+    let mut headers: Vec<String> = Vec::new();
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    if has_headers && let Some(record) = records.next() {
+        headers = record?.iter().map(str::to_owned).collect();
+    }
+    for record in records {
+        rows.push(record?.iter().map(str::to_owned).collect());
+    }
+    if !has_headers {
+        let columns = rows.first().map(Vec::len).unwrap_or_default();
+        headers = (1..=columns)
+            .map(|index| format!("Column {index}"))
+            .collect();
+    }
+    for (row_index, row) in rows.iter().enumerate() {
+        if row.len() != headers.len() {
+            return Err(TableError::ColumnValueMismatch {
+                columns: headers.len(),
+                values: row.len(),
+                in_row: Some(row_index),
+            }
+            .into());
+        }
+    }
+
+    let weights: Vec<usize> = (0..headers.len())
+        .map(|column| column_weight(&headers[column], rows.iter().map(|row| &row[column])))
+        .collect();
+    let total_weight: usize = weights.iter().sum();
+    let columns = headers
+        .into_iter()
+        .enumerate()
+        .map(|(index, header)| {
+            let column = Column::from(header);
+            let is_numeric = is_numeric_column(rows.iter().map(|row| &row[index]));
+            if is_numeric {
+                // Numbers are short and should never be split over multiple lines
+                if align_numbers {
+                    return column.with_alignment(CellAlignment::Right);
+                }
+                return column;
+            }
+            column.with_max_width(TableColumnMaxWidth::Percentage(
+                weights[index] as f32 / total_weight as f32,
+            ))
+        })
+        .collect();
+
+    let rows = rows
+        .into_iter()
+        .map(|values| Row {
+            values: values.into_iter().map(TableValueWithStyle::from).collect(),
+            styles: None,
+        })
+        .collect();
+    if header_styles.is_some() {
+        styles.header_styles = header_styles;
+    }
+    Ok(Table {
+        columns,
+        header_groups: Vec::new(),
+        rows,
+        styles,
+        new_page,
+    })
+}
+/// The number of characters of the longest value. Clamped so one long value does not take the entire table
+fn column_weight<'a>(header: &str, values: impl Iterator<Item = &'a String>) -> usize {
+    values
+        .map(|value| value.chars().count())
+        .chain(std::iter::once(header.chars().count()))
+        .max()
+        .unwrap_or_default()
+        .clamp(MIN_COLUMN_WEIGHT, MAX_COLUMN_WEIGHT)
+}
+/// True if there is at least one value and every non empty value is a number
+fn is_numeric_column<'a>(values: impl Iterator<Item = &'a String>) -> bool {
+    let mut has_number = false;
+    for value in values {
+        let value = value.trim();
+        if value.is_empty() {
+            continue;
+        }
+        if !is_number(value) {
+            return false;
+        }
+        has_number = true;
+    }
+    has_number
+}
+/// Accepts a leading currency symbol, a trailing percent sign and thousands separators. `$1,234.50` or `-12%`
+fn is_number(value: &str) -> bool {
+    let value = value
+        .strip_suffix('%')
+        .unwrap_or(value)
+        .trim_start_matches(['$', '€', '£', '¥']);
+    let value = value.replace([',', '_'], "");
+    !value.is_empty() && value.parse::<f64>().is_ok()
+}
+#[cfg(test)]
+mod tests {
+    use super::{TableImportOptions, from_csv, is_number};
+    use crate::{
+        TuxPdfError,
+        document::{BuiltinFont, PdfDocument},
+        graphics::{
+            GraphicItems, TextBlockContent,
+            color::{BLACK_RGB, GRAY_RGB, WHITE_RGB},
+        },
+        layouts::{
+            LayoutError,
+            table::{
+                CellAlignment, TableError, TablePageRules, TableValue, builder::TableColumnMaxWidth,
+            },
+        },
+        page::{PdfPage, page_sizes::A4},
+    };
+
+    static CSV: &str = "Name,Description,Price\n\
+        Widget,A small widget used for testing,$1.50\n\
+        Gadget,,\"1,200\"\n";
+
+    #[test]
+    fn numbers() {
+        assert!(is_number("12"));
+        assert!(is_number("-1.5"));
+        assert!(is_number("$1,234.50"));
+        assert!(is_number("15%"));
+        assert!(!is_number("$"));
+        assert!(!is_number("12 apples"));
+    }
+
+    #[test]
+    fn import_csv() -> anyhow::Result<()> {
+        let table = from_csv(CSV.as_bytes(), TableImportOptions::default())?;
+        assert_eq!(table.number_of_columns(), 3);
+        assert_eq!(table.number_of_rows(), 2);
+        assert_eq!(table.columns[0].alignment(), CellAlignment::Left);
+        assert_eq!(table.columns[2].alignment(), CellAlignment::Right);
+        let headers: Vec<_> = table.columns.iter().map(|column| &column.header).collect();
+        assert_eq!(
+            headers,
+            [
+                &TextBlockContent::from("Name"),
+                &TextBlockContent::from("Description"),
+                &TextBlockContent::from("Price")
+            ]
+        );
+        let values: Vec<_> = table.rows[1]
+            .values
+            .iter()
+            .map(|value| &value.value)
+            .collect();
+        assert_eq!(
+            values,
+            [
+                &TableValue::Text("Gadget".into()),
+                &TableValue::Text("".into()),
+                &TableValue::Text("1,200".into())
+            ]
+        );
+
+        let max_widths: Vec<_> = table
+            .columns
+            .iter()
+            .map(|column| column.styles.as_ref().and_then(|style| style.max_width))
+            .collect();
+        // Weighted by the longest value. `Widget` is 6 characters and the description 31.
+        // The numeric price column keeps its share of 5 but is never wrapped
+        assert_eq!(
+            max_widths,
+            [
+                Some(TableColumnMaxWidth::Percentage(6.0 / 42.0)),
+                Some(TableColumnMaxWidth::Percentage(31.0 / 42.0)),
+                None
+            ]
+        );
+
+        let mut doc = PdfDocument::new("CSV");
+        doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        let page = PdfPage::new_from_page_size(A4);
+        table.render(&mut doc, (TablePageRules::for_page(&page), page))?;
+        assert_eq!(doc.extract_text().len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn striped_rows() -> anyhow::Result<()> {
+        let csv = "Name\na\nb\nc\n";
+        let mut table = from_csv(csv.as_bytes(), TableImportOptions::default())?;
+        let mut doc = PdfDocument::new("CSV");
+        doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        let page = PdfPage::new_from_page_size(A4);
+        let (_, layout) = table.layout_single_page(&doc, &TablePageRules::for_page(&page))?;
+
+        let fills: Vec<_> = layout
+            .table_graphics()
+            .items
+            .into_iter()
+            .filter_map(|item| match item {
+                GraphicItems::Group(group) => group.styles?.fill_color,
+                _ => None,
+            })
+            .collect();
+        // The header then the rows alternating from the first color
+        assert_eq!(&fills[..4], [BLACK_RGB, GRAY_RGB, WHITE_RGB, GRAY_RGB]);
+        Ok(())
+    }
+    #[test]
+    fn mismatched_row() {
+        let result = from_csv("a,b\n1,2,3\n".as_bytes(), TableImportOptions::default());
+        assert!(matches!(
+            result,
+            Err(TuxPdfError::LayoutError(LayoutError::TableError(
+                TableError::ColumnValueMismatch {
+                    columns: 2,
+                    values: 3,
+                    in_row: Some(0)
+                }
+            )))
+        ));
+    }
+}
@@ -3,26 +3,53 @@ use std::{borrow::Cow, mem};
 use crate::{
     TuxPdfError,
     document::PdfDocument,
+    error::{ErrorContext, ErrorContextExt},
     graphics::{
-        LayerType, Margin, TextBlock, TextStyle,
+        LayerType, Margin, PdfPosition, TextBlock, TextBlockContent, TextStyle,
+        shapes::OutlineRect,
         size::{RenderSize, Size},
     },
+    layouts::NewPage,
     page::{PdfPage, page_sizes::A4},
     units::Pt,
     utils::Merge,
 };
 pub mod builder;
 mod style;
-use builder::{GridStyleGroup, TableColumnMaxWidth, TableLayout};
+use builder::{
+    GridStyleGroup, NewHeaderGroupCell, NewHeaderGroupRow, TableColumnMaxWidth, TableLayout,
+};
 pub use style::*;
+mod cell_renderer;
+mod direction;
+mod heatmap;
+mod icons;
+#[cfg(feature = "csv")]
+mod import;
+mod number;
+mod overflow;
+mod progress;
+mod report;
 mod rows;
+mod visibility;
 
 use crate::layouts::table::builder::{
-    GridColumnRules, GridStyles, NewTableColumn, TableLayoutBuilder,
+    GridColumnRules, GridStyles, NewTableColumn, RowHeightLimits, TableLayoutBuilder,
 };
+pub use cell_renderer::*;
+pub use heatmap::*;
+pub use icons::*;
+#[cfg(feature = "csv")]
+pub use import::*;
+use number::DecimalColumn;
+pub use number::*;
+pub use overflow::*;
+pub use progress::*;
+pub use report::*;
 pub use rows::*;
 use thiserror::Error;
 use tracing::{Level, debug, info};
+pub use visibility::*;
 
 #[derive(Debug, Clone, PartialEq, Error)]
 pub enum TableError {
@@ -43,6 +70,14 @@ pub enum TableError {
 
     #[error("Table is not allowed to create more pages")]
     NoNewPageAllowed,
+    #[error("The empty message does not fit below the header")]
+    EmptyMessageDoesNotFit,
+    #[error("Header group row {in_row} spans {spans} columns but the table has {columns}")]
+    HeaderGroupSpanMismatch {
+        columns: usize,
+        spans: usize,
+        in_row: usize,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -63,8 +98,26 @@ impl Default for TablePageRules {
         }
     }
 }
-pub type NewPageFn =
-    fn(document: &mut PdfDocument) -> Result<(TablePageRules, PdfPage), TuxPdfError>;
+impl TablePageRules {
+    /// Rules that keep the table within the [content area](PdfPage::content_area) of the page
+    pub fn for_page(page: &PdfPage) -> Self {
+        Self::for_region(page, page.content_area())
+    }
+    /// Rules that keep the table within a region of the page. Such as the space left below other content
+    pub fn for_region(page: &PdfPage, area: OutlineRect) -> Self {
+        Self {
+            page_size: Size::new(area.position.x + area.size.width, page.size().height),
+            table_start_y: Some(area.position.y + area.size.height),
+            table_stop_y: Some(area.position.y),
+            margin: Some(Margin {
+                left: Some(area.position.x),
+                ..Default::default()
+            }),
+        }
+    }
+}
+/// Creates the pages a table continues on. See [NewPage]
+pub type NewPageFn = NewPage<(TablePageRules, PdfPage)>;
 
 pub fn no_new_page_allowed(_: &mut PdfDocument) -> Result<(TablePageRules, PdfPage), TuxPdfError> {
     Err(TableError::NoNewPageAllowed.into())
@@ -73,6 +126,10 @@ pub fn no_new_page_allowed(_: &mut PdfDocument) -> Result<(TablePageRules, PdfPa
 #[derive(Debug, Clone)]
 pub struct Table {
     pub columns: Vec<Column>,
+    /// Rows of grouped headings rendered above the column headers.
+    ///
+    /// The first row is the top most row
+    pub header_groups: Vec<HeaderGroupRow>,
     pub rows: Vec<Row>,
     pub styles: TableStyles,
     pub new_page: NewPageFn,
@@ -81,9 +138,10 @@ impl Default for Table {
     fn default() -> Self {
         Self {
             columns: Default::default(),
+            header_groups: Default::default(),
             rows: Default::default(),
             styles: Default::default(),
-            new_page: no_new_page_allowed,
+            new_page: NewPageFn::new(no_new_page_allowed),
         }
     }
 }
@@ -94,6 +152,9 @@ impl Table {
     pub fn add_row(&mut self, row: Row) {
         self.rows.push(row);
     }
+    pub fn add_header_group_row(&mut self, row: HeaderGroupRow) {
+        self.header_groups.push(row);
+    }
     pub fn number_of_columns(&self) -> usize {
         self.columns.len()
     }
@@ -102,6 +163,16 @@ impl Table {
     }
     pub fn validate(&self) -> Result<(), TableError> {
         let columns = self.number_of_columns();
+        for (row_index, group_row) in self.header_groups.iter().enumerate() {
+            let spans = group_row.number_of_columns();
+            if spans > columns || group_row.groups.iter().any(|group| group.span == 0) {
+                return Err(TableError::HeaderGroupSpanMismatch {
+                    columns,
+                    spans,
+                    in_row: row_index,
+                });
+            }
+        }
         for (row_index, row) in self.rows.iter().enumerate() {
             let values = row.number_of_columns();
             if columns != values {
@@ -127,9 +198,62 @@ impl Table {
             Cow::Borrowed(&self.styles.text_styles)
         }
     }
+    fn header_group_text_styles(
+        &self,
+        group_row: &HeaderGroupRow,
+        group: &ColumnGroup,
+    ) -> TextStyle {
+        let header_text_styles = self.header_text_styles();
+        let row_text_styles = group_row
+            .styles
+            .as_ref()
+            .and_then(|style| style.text_style.as_ref());
+        let text_styles = match row_text_styles {
+            Some(row_text_styles) => row_text_styles.merge_with_full(&header_text_styles),
+            None => Cow::Borrowed(header_text_styles.as_ref()),
+        };
+        match group
+            .styles
+            .as_ref()
+            .and_then(|style| style.text_style.as_ref())
+        {
+            Some(group_text_styles) => group_text_styles.merge_with_full(&text_styles).into_owned(),
+            None => text_styles.into_owned(),
+        }
+    }
+    fn size_of_header_groups(
+        &self,
+        document: &PdfDocument,
+        header_row_styles: &GridStyleGroup,
+    ) -> Result<Vec<NewHeaderGroupRow>, TuxPdfError> {
+        self.header_groups
+            .iter()
+            .map(|group_row| {
+                let cells = group_row
+                    .groups
+                    .iter()
+                    .map(|group| {
+                        let style = self.header_group_text_styles(group_row, group);
+                        let size = group.header.render_size(document, &style)?;
+                        Ok(NewHeaderGroupCell {
+                            span: group.span,
+                            initial_size: size,
+                            styles: group.styles.as_ref().map(GridStyleGroup::from),
+                        })
+                    })
+                    .collect::<Result<Vec<_>, TuxPdfError>>()?;
+                let row_styles =
+                    header_row_styles.merge_with_option_into_new(group_row.styles.clone());
+                Ok(NewHeaderGroupRow {
+                    cells,
+                    styles: Some(row_styles),
+                })
+            })
+            .collect()
+    }
     fn size_of_header_columns(
         &self,
-        document: &mut PdfDocument,
+        document: &PdfDocument,
     ) -> Result<Vec<NewTableColumn>, TuxPdfError> {
         let style = self.header_text_styles();
         self.columns
@@ -147,9 +271,32 @@ impl Table {
             })
             .collect()
     }
+    /// Measures the widest value of each column based on [ColumnSizing]
+    ///
+    /// Returns None if the columns should be sized incrementally
+    fn measure_column_widths(
+        &self,
+        document: &PdfDocument,
+    ) -> Result<Option<Vec<Pt>>, TuxPdfError> {
+        let rows_to_measure = match self.styles.column_sizing {
+            ColumnSizing::Incremental => return Ok(None),
+            ColumnSizing::MeasureAll | ColumnSizing::MeasureSample(0) => self.rows.len(),
+            ColumnSizing::MeasureSample(sample) => sample,
+        };
+        let step = (self.rows.len() / rows_to_measure.max(1)).max(1);
+        let mut widths = vec![Pt::default(); self.columns.len()];
+        for row in self.rows.iter().step_by(step).take(rows_to_measure) {
+            let sizes = row.calculate_sizes(document, &self.row_text_style(row))?;
+            for (width, size) in widths.iter_mut().zip(sizes) {
+                *width = width.max(size.width);
+            }
+        }
+        debug!(?widths, "Measured column widths");
+        Ok(Some(widths))
+    }
     fn prepare_content(
         &mut self,
-        document: &mut PdfDocument,
+        document: &PdfDocument,
         available_size: Size,
     ) -> Result<(), TuxPdfError> {
         let header_text_styles: TextStyle = self.header_text_styles().into_owned();
@@ -180,13 +327,12 @@ impl Table {
         }
         Ok(())
     }
-    fn build_pages(
+    /// Creates the grid for the first page
+    fn start_grid(
         &mut self,
-        document: &mut PdfDocument,
-        first_page: (TablePageRules, PdfPage),
-    ) -> Result<Vec<InternalTablePage>, TuxPdfError> {
-        let mut pages = Vec::with_capacity(1);
-        let mut page = first_page.1;
+        document: &PdfDocument,
+        page_rules: &TablePageRules,
+    ) -> Result<(GridSetup, TableLayoutBuilder), TuxPdfError> {
         let header_row_as_column_group: Option<GridStyleGroup> =
             self.styles.header_styles.as_ref().map(|s| s.into());
         let header_row_styles = self
@@ -198,48 +344,200 @@ impl Table {
             outer_styles: self.styles.outer_styles.clone(),
             row_styles: None,
             cell_styles: self.styles.cell_styles.clone(),
+            header_row_height: self.row_height_limits(self.styles.header_styles.as_ref()),
         };
         // Initialize the first grid builder
         let column_sizes = self.size_of_header_columns(document)?;
+        let header_groups = self.size_of_header_groups(document, &header_row_styles)?;
 
         let mut grid_builder = TableLayoutBuilder::new(
-            &first_page.0,
+            page_rules,
             grid_styles.clone(),
-            column_sizes,
+            column_sizes.clone(),
             Some(header_row_styles.clone()),
+            header_groups.clone(),
         )?;
 
         self.prepare_content(document, grid_builder.available_size())?;
+        // Content has to be measured after it has been wrapped to the max widths
+        let measured_widths = self.measure_column_widths(document)?;
+        if let Some(measured_widths) = &measured_widths {
+            grid_builder = TableLayoutBuilder::new(
+                page_rules,
+                grid_styles.clone(),
+                apply_measured_widths(column_sizes, measured_widths),
+                Some(header_row_styles.clone()),
+                header_groups.clone(),
+            )?;
+        }
+        self.push_empty_message_row(document, &mut grid_builder)?;
         info!(?grid_builder);
-        let mut rows = Vec::with_capacity(5);
+        let setup = GridSetup {
+            grid_styles,
+            header_row_styles,
+            header_groups,
+            measured_widths,
+        };
+        Ok((setup, grid_builder))
+    }
+    /// Creates the grid for every page after the first
+    fn next_page_grid(
+        &self,
+        document: &PdfDocument,
+        setup: &GridSetup,
+        page_rules: &TablePageRules,
+    ) -> Result<TableLayoutBuilder, TuxPdfError> {
+        let mut header_column_sizes = self.size_of_header_columns(document)?;
+        if let Some(measured_widths) = &setup.measured_widths {
+            header_column_sizes = apply_measured_widths(header_column_sizes, measured_widths);
+        }
+        let grid_builder = TableLayoutBuilder::new(
+            page_rules,
+            setup.grid_styles.clone(),
+            header_column_sizes,
+            Some(setup.header_row_styles.clone()),
+            setup.header_groups.clone(),
+        )?;
+        Ok(grid_builder)
+    }
+    /// Adds the row the [empty message](TableStyles::empty_message) is drawn in if the table has no rows
+    ///
+    /// The row has no values so it is left out of the rows of the table
+    fn push_empty_message_row(
+        &self,
+        document: &PdfDocument,
+        grid_builder: &mut TableLayoutBuilder,
+    ) -> Result<(), TuxPdfError> {
+        let Some(message) = self.styles.empty_message.as_ref() else {
+            return Ok(());
+        };
+        if !self.rows.is_empty() {
+            return Ok(());
+        }
+        let row_styles = self.styles.empty_row_styles.as_ref();
+        let horizontal_padding = self
+            .styles
+            .cell_content_padding
+            .horizontal_value()
+            .unwrap_or_default();
+        // Columns only get wider after this. So the message never needs more lines than measured here
+        let style = TextStyle {
+            max_width: Some(grid_builder.calculate_full_width() - horizontal_padding),
+            ..self.text_style_for(row_styles)
+        };
+        let height = message.render_size(document, &style)?.height;
+        let column_sizes = vec![Size::new(Pt::default(), height); self.columns.len()];
+        let mut grid_styling: GridStyleGroup = self.styles.row_styles.clone();
+        grid_styling.merge_with_option(row_styles.map(GridStyleGroup::from));
+        if !grid_builder.next_row(
+            &column_sizes,
+            Some(grid_styling),
+            self.row_height_limits(row_styles),
+        )? {
+            return Err(TableError::EmptyMessageDoesNotFit.into());
+        }
+        Ok(())
+    }
+    /// Draws the empty message centered across the row added by [Self::push_empty_message_row]
+    fn draw_empty_message<L: LayerType>(
+        &self,
+        document: &PdfDocument,
+        grid_layout: &TableLayout,
+        page: &mut L,
+    ) -> Result<(), TuxPdfError> {
+        let Some(message) = self.styles.empty_message.as_ref() else {
+            return Ok(());
+        };
+        let Some(location) = grid_layout.get_cell_location(1, 0) else {
+            return Ok(());
+        };
+        let horizontal_padding = self
+            .styles
+            .cell_content_padding
+            .horizontal_value()
+            .unwrap_or_default();
+        let available_width = grid_layout.final_size.width - horizontal_padding;
+        let style = TextStyle {
+            max_width: Some(available_width),
+            ..self.text_style_for(self.styles.empty_row_styles.as_ref())
+        };
+        let width = message.render_size(document, &style)?.width;
+        page.add_to_layer(TextBlock {
+            content: message.clone(),
+            position: PdfPosition {
+                x: location.x + CellAlignment::Center.offset(width, available_width),
+                ..location
+            },
+            style,
+            draw_as_lines: false,
+        })?;
+        Ok(())
+    }
+    fn row_height_limits(&self, row_styles: Option<&RowStyles>) -> RowHeightLimits {
+        RowHeightLimits {
+            min: row_styles
+                .and_then(|styles| styles.min_height)
+                .or(self.styles.min_row_height),
+            max: row_styles.and_then(|styles| styles.max_height),
+        }
+    }
+    fn body_row_height_limits(&self, row: &Row) -> RowHeightLimits {
+        match self.styles.uniform_row_height {
+            Some(height) => RowHeightLimits::fixed(height),
+            None => self.row_height_limits(row.styles.as_ref()),
+        }
+    }
+    fn row_layout(
+        &self,
+        document: &PdfDocument,
+        row: &Row,
+        row_index: usize,
+    ) -> Result<(Vec<Size>, GridStyleGroup), TuxPdfError> {
+        let column_sizes = row
+            .calculate_sizes(document, &self.row_text_style(row))
+            .context(ErrorContext::TableRow(row_index))?;
+        let mut grid_styling: GridStyleGroup = self.styles.row_styles.clone();
+        if let Some(colors) = &self.styles.striped_rows {
+            grid_styling.background_color = Some(colors[row_index % 2].clone());
+        }
+        grid_styling.merge_with_option(row.grid_row_styles());
 
-        for row in mem::take(&mut self.rows).into_iter() {
-            let column_sizes = row.calculate_sizes(document, &self.styles.text_styles)?;
-            let grid_styling: GridStyleGroup = self
-                .styles
-                .row_styles
-                .merge_with_option_into_new(row.grid_row_styles());
+        debug!(?grid_styling, "Row Styling");
+        Ok((column_sizes, grid_styling))
+    }
+    fn build_pages(
+        &mut self,
+        document: &mut PdfDocument,
+        first_page: (TablePageRules, PdfPage),
+    ) -> Result<Vec<InternalTablePage>, TuxPdfError> {
+        let mut pages = Vec::with_capacity(1);
+        let (mut page_rules, mut page) = first_page;
+        let (setup, mut grid_builder) = self.start_grid(document, &page_rules)?;
+        let mut rows = Vec::with_capacity(5);
 
-            debug!(?grid_styling, "Row Styling");
+        for (row_index, row) in mem::take(&mut self.rows).into_iter().enumerate() {
+            let (column_sizes, grid_styling) = self.row_layout(document, &row, row_index)?;
+            let height_limits = self.body_row_height_limits(&row);
 
-            if !grid_builder.next_row(&column_sizes, Some(grid_styling.clone()))? {
+            if !grid_builder
+                .next_row(&column_sizes, Some(grid_styling.clone()), height_limits)
+                .context(ErrorContext::TableRow(row_index))?
+            {
                 pages.push(InternalTablePage {
                     page,
+                    page_rules,
                     rows: mem::take(&mut rows),
                     grid_layout: grid_builder.build(),
                 });
-                let (page_rules, new_page) = (self.new_page)(document)?;
-
-                let header_column_sizes = self.size_of_header_columns(document)?;
+                let (new_page_rules, mut new_page) = self.new_page.create(document)?;
+                document.page_created(&mut new_page, pages.len());
 
-                grid_builder = TableLayoutBuilder::new(
-                    &page_rules,
-                    grid_styles.clone(),
-                    header_column_sizes,
-                    Some(header_row_styles.clone()),
-                )?;
-                grid_builder.next_row(&column_sizes, Some(grid_styling))?;
+                grid_builder = self.next_page_grid(document, &setup, &new_page_rules)?;
+                grid_builder
+                    .next_row(&column_sizes, Some(grid_styling), height_limits)
+                    .context(ErrorContext::TableRow(row_index))?;
                 page = new_page;
+                page_rules = new_page_rules;
             }
             rows.push(row);
         }
@@ -247,83 +545,364 @@ impl Table {
 
         pages.push(InternalTablePage {
             page,
+            page_rules,
             rows,
             grid_layout: grid,
         });
         Ok(pages)
     }
+    /// Lays out every row within the page rules without creating new pages
+    ///
+    /// Returns [TableError::NoNewPageAllowed] if the rows do not fit
+    pub(crate) fn layout_single_page(
+        &mut self,
+        document: &PdfDocument,
+        page_rules: &TablePageRules,
+    ) -> Result<(Vec<Row>, TableLayout), TuxPdfError> {
+        let (_, mut grid_builder) = self.start_grid(document, page_rules)?;
+        let rows = mem::take(&mut self.rows);
+        for (row_index, row) in rows.iter().enumerate() {
+            let (column_sizes, grid_styling) = self.row_layout(document, row, row_index)?;
+            let height_limits = self.body_row_height_limits(row);
+            if !grid_builder
+                .next_row(&column_sizes, Some(grid_styling), height_limits)
+                .context(ErrorContext::TableRow(row_index))?
+            {
+                return Err(TableError::NoNewPageAllowed)
+                    .context(ErrorContext::TableRow(row_index));
+            }
+        }
+        Ok((rows, grid_builder.build()))
+    }
+    /// Renders the table starting on the first page. Hidden columns and filtered rows are left out
+    ///
+    /// The pages are added to the document. The report describes where the table was placed on each of them
     pub fn render(
+        self,
+        document: &mut PdfDocument,
+        first_page: (TablePageRules, PdfPage),
+    ) -> Result<TableRenderReport, TuxPdfError> {
+        let (report, last_page) = self.into_visible()?.render_pages(document, first_page)?;
+        document.add_page(last_page.page);
+        Ok(report)
+    }
+    /// Renders the table without adding the page it ends on to the document
+    ///
+    /// Content can continue below the table on that page. Every other page of the table is added to the document.
+    /// Use [TablePageRules::for_region] to place the table in part of a page.
+    pub fn render_and_continue(
+        self,
+        document: &mut PdfDocument,
+        first_page: (TablePageRules, PdfPage),
+    ) -> Result<TableContinuation, TuxPdfError> {
+        let (report, last_page) = self.into_visible()?.render_pages(document, first_page)?;
+        let remaining = last_page.remaining_area(report.end_y());
+        Ok(TableContinuation {
+            page: last_page.page,
+            remaining,
+            report,
+        })
+    }
+    /// Draws every page of the table. All pages but the last are added to the document
+    fn render_pages(
         mut self,
         document: &mut PdfDocument,
         first_page: (TablePageRules, PdfPage),
-    ) -> Result<(), TuxPdfError> {
-        self.validate()?;
+    ) -> Result<(TableRenderReport, TableEndPage), TuxPdfError> {
+        if let HorizontalOverflow::Paginate { repeated_columns } = self.styles.horizontal_overflow {
+            return self.render_horizontal_pages(document, first_page, repeated_columns);
+        }
+        // Mirrored after the columns are split so the first set of pages still starts with the first columns
+        let right_to_left = self.is_right_to_left(document);
+        if right_to_left {
+            self = self.mirrored();
+        }
         let pages = self.build_pages(document, first_page)?;
+        let mut report = TableRenderReport::default();
+        let mut last_page = None;
+        // Index of the first row on the current page within the whole table
+        let mut first_row_index = 0;
         for table_page in pages {
+            if let Some(TableEndPage { page, .. }) = last_page.take() {
+                document.add_page(page);
+            }
             let InternalTablePage {
                 mut page,
+                page_rules,
                 rows,
-                grid_layout,
+                mut grid_layout,
             } = table_page;
-            // Todo: Use actual styles
-            let graphics_items = grid_layout.table_graphics();
-            if tracing::enabled!(Level::TRACE) {
-                tracing::trace!(?graphics_items);
-            }
-            page.add_to_layer(graphics_items)?;
-            let mut row_iter = grid_layout.row_iter();
-            {
-                // Render head row
-                let header_row_locations = row_iter.next().unwrap();
-                let header_styles = self.header_text_styles();
-
-                for text in
-                    self.columns
-                        .iter()
-                        .zip(header_row_locations)
-                        .map(|(column, location)| TextBlock {
-                            content: column.header.clone(),
-                            position: location,
-                            style: header_styles.clone().into_owned(),
-                            draw_as_lines: false,
-                        })
-                {
-                    page.add_to_layer(text)?;
-                }
+            let rows_on_page = first_row_index..first_row_index + rows.len();
+            self.draw_page(document, rows, &mut grid_layout, first_row_index, &mut page)?;
+            let mut page_report = TablePageReport::new(
+                document.number_of_pages(),
+                rows_on_page.clone(),
+                &grid_layout,
+            );
+            if right_to_left {
+                // Report the columns as they were defined. Not as they were drawn
+                page_report.columns.reverse();
             }
-            for (row, locations) in rows.into_iter().zip(row_iter) {
-                let row_text_style = if let Some(styles) = row.styles.and_then(|s| s.text_style) {
-                    styles
-                        .merge_with_full(&self.styles.text_styles)
-                        .into_owned()
+            report.pages.push(page_report);
+            first_row_index = rows_on_page.end;
+            last_page = Some(TableEndPage { page, page_rules });
+        }
+        let last_page = last_page.expect("build_pages always creates a page");
+        Ok((report, last_page))
+    }
+    /// Draws the rows of one page of the table
+    pub(crate) fn draw_page<L: LayerType>(
+        &self,
+        document: &PdfDocument,
+        rows: Vec<Row>,
+        grid_layout: &mut TableLayout,
+        first_row_index: usize,
+        page: &mut L,
+    ) -> Result<(), TuxPdfError> {
+        self.apply_cell_fills(&rows, grid_layout);
+        // Todo: Use actual styles
+        let graphics_items = grid_layout.table_graphics();
+        if tracing::enabled!(Level::TRACE) {
+            tracing::trace!(?graphics_items);
+        }
+        page.add_to_layer(graphics_items)?;
+        for (group_row, group_row_layout) in self
+            .header_groups
+            .iter()
+            .zip(grid_layout.header_group_rows())
+        {
+            for (group, cell) in group_row.groups.iter().zip(&group_row_layout.cells) {
+                let style = self.header_group_text_styles(group_row, group);
+                let offset = if group.alignment == CellAlignment::Left {
+                    Pt::default()
                 } else {
-                    self.styles.text_styles.clone()
+                    let padding = self
+                        .styles
+                        .cell_content_padding
+                        .horizontal_value()
+                        .unwrap_or_default();
+                    let width = group.header.render_size(document, &style)?.width;
+                    group.alignment.offset(width, cell.width - padding)
                 };
-                for (column, location) in row.values.into_iter().zip(locations) {
-                    #[allow(clippy::single_match)]
-                    match column.value {
-                        TableValue::Text(value) => {
-                            let text = TextBlock {
-                                content: value,
-                                position: location,
-                                style: row_text_style.clone(),
-                                draw_as_lines: false,
-                            };
-                            page.add_to_layer(text)?;
+                let text = TextBlock {
+                    content: group.header.clone(),
+                    position: PdfPosition {
+                        x: cell.content_x + offset,
+                        y: group_row_layout.row.content_y,
+                    },
+                    style,
+                    draw_as_lines: false,
+                };
+                page.add_to_layer(text)?;
+            }
+        }
+        let mut row_iter = grid_layout.row_iter();
+        {
+            // Render head row
+            let header_row_locations = row_iter.next().unwrap();
+            let header_styles = self.header_text_styles();
+
+            for (column_index, (column, location)) in
+                self.columns.iter().zip(header_row_locations).enumerate()
+            {
+                let style = header_styles.clone().into_owned();
+                let offset = self.alignment_offset(
+                    document,
+                    grid_layout,
+                    (0, column_index),
+                    &column.header,
+                    &style,
+                    None,
+                )?;
+                page.add_to_layer(TextBlock {
+                    content: column.header.clone(),
+                    position: PdfPosition {
+                        x: location.x + offset,
+                        ..location
+                    },
+                    style,
+                    draw_as_lines: false,
+                })?;
+            }
+        }
+        // Only the row of the empty message is in the layout
+        if rows.is_empty() && grid_layout.rows.len() > 1 {
+            self.draw_empty_message(document, grid_layout, page)?;
+        }
+        let decimal_columns = self.decimal_columns(document, &rows)?;
+        // The first row of the layout is the header row
+        for (row_index, (row, locations)) in rows.into_iter().zip(row_iter).enumerate() {
+            let row_text_style = self.row_text_style(&row);
+            for (column_index, (column, location)) in
+                row.values.into_iter().zip(locations).enumerate()
+            {
+                match column.value {
+                    TableValue::Text(value) => {
+                        let offset = self.alignment_offset(
+                            document,
+                            grid_layout,
+                            (row_index + 1, column_index),
+                            &value,
+                            &row_text_style,
+                            decimal_columns[column_index].as_ref(),
+                        )?;
+                        let text = TextBlock {
+                            content: value,
+                            position: PdfPosition {
+                                x: location.x + offset,
+                                ..location
+                            },
+                            style: row_text_style.clone(),
+                            draw_as_lines: false,
+                        };
+                        page.add_to_layer(text)?;
+                    }
+                    TableValue::BlankSpace => {}
+                    other => {
+                        let Some(renderer) = other.renderer() else {
+                            continue;
+                        };
+                        let Some(area) = grid_layout.cell_area(row_index + 1, column_index) else {
+                            continue;
+                        };
+                        let objects = renderer.render(document, area, &row_text_style).context(
+                            ErrorContext::TableCell {
+                                row: first_row_index + row_index,
+                                column: column_index,
+                            },
+                        )?;
+                        for object in objects {
+                            page.add_to_layer(object)?;
                         }
-                        _ => {}
                     }
                 }
             }
-            document.add_page(page);
         }
-
         Ok(())
     }
+    fn row_text_style(&self, row: &Row) -> TextStyle {
+        self.text_style_for(row.styles.as_ref())
+    }
+    fn text_style_for(&self, row_styles: Option<&RowStyles>) -> TextStyle {
+        match row_styles.and_then(|s| s.text_style.as_ref()) {
+            Some(styles) => styles
+                .merge_with_full(&self.styles.text_styles)
+                .into_owned(),
+            None => self.styles.text_styles.clone(),
+        }
+    }
+    /// Measures the text values of the [CellAlignment::Decimal] columns on a page
+    fn decimal_columns(
+        &self,
+        document: &PdfDocument,
+        rows: &[Row],
+    ) -> Result<Vec<Option<DecimalColumn>>, TuxPdfError> {
+        let mut decimal_columns: Vec<_> = self
+            .columns
+            .iter()
+            .map(|column| match column.alignment() {
+                CellAlignment::Decimal(separator) => Some(DecimalColumn::new(separator)),
+                _ => None,
+            })
+            .collect();
+        if decimal_columns.iter().all(Option::is_none) {
+            return Ok(decimal_columns);
+        }
+        for row in rows {
+            let style = self.row_text_style(row);
+            for (decimal_column, value) in decimal_columns.iter_mut().zip(&row.values) {
+                if let (Some(decimal_column), TableValue::Text(text)) =
+                    (decimal_column, &value.value)
+                {
+                    decimal_column.add_value(document, text, &style)?;
+                }
+            }
+        }
+        Ok(decimal_columns)
+    }
+    /// How far the text is moved right within its cell based on the alignment of the column
+    fn alignment_offset(
+        &self,
+        document: &PdfDocument,
+        grid_layout: &TableLayout,
+        (row_index, column_index): (usize, usize),
+        content: &TextBlockContent,
+        style: &TextStyle,
+        decimal_column: Option<&DecimalColumn>,
+    ) -> Result<Pt, TuxPdfError> {
+        let alignment = self
+            .columns
+            .get(column_index)
+            .map(Column::alignment)
+            .unwrap_or_default();
+        if alignment == CellAlignment::Left {
+            return Ok(Pt::default());
+        }
+        let Some(area) = grid_layout.cell_area(row_index, column_index) else {
+            return Ok(Pt::default());
+        };
+        if let Some(decimal_column) = decimal_column {
+            return decimal_column.offset(document, content, style, area.content_size.width);
+        }
+        let width = content.render_size(document, style)?.width;
+        Ok(alignment.offset(width, area.content_size.width))
+    }
 }
 
+/// Widens the header columns to the measured content widths
+fn apply_measured_widths(
+    mut columns: Vec<NewTableColumn>,
+    measured_widths: &[Pt],
+) -> Vec<NewTableColumn> {
+    for (column, width) in columns.iter_mut().zip(measured_widths) {
+        column.initial_size.width = column.initial_size.width.max(*width);
+    }
+    columns
+}
+/// Styles shared by the grid of every page
+struct GridSetup {
+    grid_styles: GridStyles,
+    header_row_styles: GridStyleGroup,
+    header_groups: Vec<NewHeaderGroupRow>,
+    measured_widths: Option<Vec<Pt>>,
+}
 struct InternalTablePage {
     page: PdfPage,
+    page_rules: TablePageRules,
     rows: Vec<Row>,
     grid_layout: TableLayout,
 }
+#[cfg(test)]
+mod tests {
+    use super::{ColumnSizing, Row, Table};
+    use crate::{
+        document::{BuiltinFont, PdfDocument},
+        units::Pt,
+    };
+
+    fn measured_widths(column_sizing: ColumnSizing) -> anyhow::Result<Option<Vec<Pt>>> {
+        let mut document = PdfDocument::new("Column Sizing");
+        document
+            .font_map()
+            .register_builtin_font(BuiltinFont::Helvetica);
+        let mut table = Table::default();
+        table.add_column("Name".into());
+        for index in 0..9 {
+            table.add_row(Row::from(vec![format!("Item {index}")]));
+        }
+        table.add_row(Row::from(vec![
+            "The widest value is in the last row".to_owned(),
+        ]));
+        table.styles.column_sizing = column_sizing;
+        Ok(table.measure_column_widths(&document)?)
+    }
+    #[test]
+    fn empty_sample_measures_all_rows() -> anyhow::Result<()> {
+        let all = measured_widths(ColumnSizing::MeasureAll)?;
+        assert!(all.is_some());
+        assert_eq!(measured_widths(ColumnSizing::MeasureSample(0))?, all);
+        assert_ne!(measured_widths(ColumnSizing::MeasureSample(3))?, all);
+        assert_eq!(measured_widths(ColumnSizing::Incremental)?, None);
+        Ok(())
+    }
+}
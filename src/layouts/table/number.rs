@@ -0,0 +1,230 @@
+use crate::{
+    TuxPdfError,
+    document::PdfDocument,
+    graphics::{TextBlockContent, TextStyle, size::RenderSize},
+    units::Pt,
+};
+
+use super::CellAlignment;
+
+/// Formats numbers with the separators of a locale
+///
+/// ```rust
+/// use tux_pdf::layouts::table::NumberFormat;
+/// assert_eq!(NumberFormat::en_us().format(1234567.891), "1,234,567.89");
+/// assert_eq!(NumberFormat::de_de().format(-1234.5), "-1.234,50");
+/// assert_eq!(NumberFormat::en_us().with_decimals(0).format(999.6), "1,000");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// Placed between every group of three digits. None to not group digits
+    pub thousands_separator: Option<char>,
+    pub decimal_separator: char,
+    /// The number of digits after the decimal separator
+    pub decimals: usize,
+}
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self::en_us()
+    }
+}
+impl NumberFormat {
+    /// `1,234.56`
+    pub const fn en_us() -> Self {
+        Self {
+            thousands_separator: Some(','),
+            decimal_separator: '.',
+            decimals: 2,
+        }
+    }
+    /// `1.234,56`
+    pub const fn de_de() -> Self {
+        Self {
+            thousands_separator: Some('.'),
+            decimal_separator: ',',
+            decimals: 2,
+        }
+    }
+    /// `1 234,56` using a narrow no-break space so the number is never split over two lines
+    pub const fn fr_fr() -> Self {
+        Self {
+            thousands_separator: Some('\u{202F}'),
+            decimal_separator: ',',
+            decimals: 2,
+        }
+    }
+    /// `1'234.56`
+    pub const fn de_ch() -> Self {
+        Self {
+            thousands_separator: Some('\''),
+            decimal_separator: '.',
+            decimals: 2,
+        }
+    }
+    pub const fn with_decimals(mut self, decimals: usize) -> Self {
+        self.decimals = decimals;
+        self
+    }
+    pub const fn with_thousands_separator(mut self, separator: Option<char>) -> Self {
+        self.thousands_separator = separator;
+        self
+    }
+    /// Aligns the values of a column on the decimal separator of this format
+    pub const fn alignment(&self) -> CellAlignment {
+        CellAlignment::Decimal(self.decimal_separator)
+    }
+    pub fn format(&self, value: f64) -> String {
+        let formatted = format!("{:.*}", self.decimals, value.abs());
+        let (integer, fraction) = match formatted.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (formatted.as_str(), None),
+        };
+        let mut result = String::with_capacity(formatted.len() + integer.len() / 3 + 1);
+        // Rounding can turn a small negative number into zero. Which should not be signed
+        if value.is_sign_negative() && formatted.bytes().any(|digit| matches!(digit, b'1'..=b'9')) {
+            result.push('-');
+        }
+        for (index, digit) in integer.chars().enumerate() {
+            let remaining = integer.len() - index;
+            if index > 0
+                && remaining % 3 == 0
+                && let Some(separator) = self.thousands_separator
+            {
+                result.push(separator);
+            }
+            result.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            result.push(self.decimal_separator);
+            result.push_str(fraction);
+        }
+        result
+    }
+}
+/// The widths used to line up the values of a column on their decimal separator
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct DecimalColumn {
+    separator: char,
+    /// The widest text before the separator
+    integer_width: Pt,
+    /// The widest text starting at the separator
+    fraction_width: Pt,
+}
+impl DecimalColumn {
+    pub(super) fn new(separator: char) -> Self {
+        Self {
+            separator,
+            integer_width: Pt::default(),
+            fraction_width: Pt::default(),
+        }
+    }
+    /// The width before the separator and the width of the rest of the value
+    fn split_widths(
+        &self,
+        document: &PdfDocument,
+        content: &TextBlockContent,
+        style: &TextStyle,
+    ) -> Result<(Pt, Pt), TuxPdfError> {
+        let width = content.render_size(document, style)?.width;
+        let text = content.text();
+        let integer_width = match text.rfind(self.separator) {
+            Some(index) => {
+                TextBlockContent::from(&text[..index])
+                    .render_size(document, style)?
+                    .width
+            }
+            None => width,
+        };
+        Ok((integer_width, width - integer_width))
+    }
+    pub(super) fn add_value(
+        &mut self,
+        document: &PdfDocument,
+        content: &TextBlockContent,
+        style: &TextStyle,
+    ) -> Result<(), TuxPdfError> {
+        let (integer_width, fraction_width) = self.split_widths(document, content, style)?;
+        self.integer_width = self.integer_width.max(integer_width);
+        self.fraction_width = self.fraction_width.max(fraction_width);
+        Ok(())
+    }
+    /// The offset from the left of the content area. The separators of the column line up and the widest value touches the right side
+    pub(super) fn offset(
+        &self,
+        document: &PdfDocument,
+        content: &TextBlockContent,
+        style: &TextStyle,
+        available_width: Pt,
+    ) -> Result<Pt, TuxPdfError> {
+        if content.len() > 1 {
+            // Multiple lines do not have a single separator to line up
+            let width = content.render_size(document, style)?.width;
+            return Ok(CellAlignment::Right.offset(width, available_width));
+        }
+        let (integer_width, _) = self.split_widths(document, content, style)?;
+        let start =
+            CellAlignment::Right.offset(self.integer_width + self.fraction_width, available_width);
+        Ok(start + self.integer_width - integer_width)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::{DecimalColumn, NumberFormat};
+    use crate::{
+        document::{BuiltinFont, PdfDocument},
+        graphics::{TextBlockContent, TextStyle, size::RenderSize},
+        units::UnitType,
+    };
+
+    #[test]
+    fn format_numbers() {
+        let format = NumberFormat::en_us();
+        assert_eq!(format.format(0.0), "0.00");
+        assert_eq!(format.format(123.0), "123.00");
+        assert_eq!(format.format(1234.0), "1,234.00");
+        assert_eq!(format.format(123456.0), "123,456.00");
+        assert_eq!(format.format(-0.001), "0.00");
+        assert_eq!(
+            format.with_thousands_separator(None).format(1234.5),
+            "1234.50"
+        );
+        assert_eq!(NumberFormat::fr_fr().format(1234.5), "1\u{202F}234,50");
+        assert_eq!(
+            NumberFormat::de_ch().with_decimals(1).format(-98765.43),
+            "-98'765.4"
+        );
+    }
+
+    #[test]
+    fn separators_line_up() -> anyhow::Result<()> {
+        let mut document = PdfDocument::new("Decimal");
+        document
+            .font_map()
+            .register_builtin_font(BuiltinFont::Helvetica);
+        let style = TextStyle::default();
+        let values: Vec<TextBlockContent> = ["1.5", "1,234.75", "12"]
+            .into_iter()
+            .map(TextBlockContent::from)
+            .collect();
+        let mut column = DecimalColumn::new('.');
+        for value in &values {
+            column.add_value(&document, value, &style)?;
+        }
+        let available = 200f32.pt();
+        let separator_x = |text: &str, value: &TextBlockContent| -> anyhow::Result<_> {
+            let integer = text.split('.').next().unwrap_or_default();
+            Ok(column.offset(&document, value, &style, available)?
+                + integer.render_size(&document, &style)?.width)
+        };
+        let first = separator_x("1.5", &values[0])?;
+        assert_eq!(first, separator_x("1,234.75", &values[1])?);
+        assert_eq!(first, separator_x("12", &values[2])?);
+        // The value with the longest fraction touches the right side
+        let widest = values[1].render_size(&document, &style)?.width;
+        assert_eq!(
+            column.offset(&document, &values[1], &style, available)? + widest,
+            available
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,234 @@
+use std::mem;
+
+use crate::{
+    TuxPdfError, document::PdfDocument, graphics::size::RenderSize, page::PdfPage, units::Pt,
+};
+
+use super::{
+    ColumnGroup, HeaderGroupRow, Row, Table, TableEndPage, TablePageRules, TableRenderReport,
+    TableValue,
+    builder::{TableColumnMaxWidth, TableColumnMinWidth},
+};
+
+/// How a table that is wider than the page is handled
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum HorizontalOverflow {
+    /// All columns are placed on the same page. Even if they do not fit
+    #[default]
+    Overflow,
+    /// Columns that do not fit are moved to another set of pages.
+    ///
+    /// The table is rendered with the columns that fit. Then the remaining columns are rendered starting on a new page
+    Paginate {
+        /// The number of leading columns that are repeated on every set of pages.
+        ///
+        /// Good for an identifier column such as an order number
+        repeated_columns: usize,
+    },
+}
+
+impl Table {
+    /// Estimates the final width of each column including the cell padding
+    fn estimate_column_widths(
+        &self,
+        document: &PdfDocument,
+        available_width: Pt,
+    ) -> Result<Vec<Pt>, TuxPdfError> {
+        let header_text_styles = self.header_text_styles();
+        let horizontal_padding = self
+            .styles
+            .cell_content_padding
+            .horizontal_value()
+            .unwrap_or_default();
+        let mut widths = Vec::with_capacity(self.columns.len());
+        for (index, column) in self.columns.iter().enumerate() {
+            let mut width = column
+                .header
+                .render_size(document, header_text_styles.as_ref())?
+                .width;
+            for row in &self.rows {
+                let value_width = match row.values.get(index).map(|value| &value.value) {
+                    Some(TableValue::Text(text)) => {
+                        text.render_size(document, &self.styles.text_styles)?.width
+                    }
+                    Some(other) => match other.renderer() {
+                        Some(renderer) => {
+                            renderer
+                                .content_size(document, &self.styles.text_styles)?
+                                .width
+                        }
+                        None => continue,
+                    },
+                    None => continue,
+                };
+                width = width.max(value_width);
+            }
+            let styles = column.styles.as_ref();
+            if let Some(max_width) = styles.and_then(|s| s.max_width) {
+                width = match max_width {
+                    TableColumnMaxWidth::Fixed(pt) => width.min(pt),
+                    TableColumnMaxWidth::Percentage(percentage) => {
+                        width.min(available_width * percentage)
+                    }
+                };
+            }
+            match styles.and_then(|s| s.min_width) {
+                Some(TableColumnMinWidth::Fixed(pt)) => width = width.max(pt),
+                Some(TableColumnMinWidth::Percentage(percentage)) => {
+                    width = width.max(available_width * percentage)
+                }
+                _ => {}
+            }
+            widths.push(width + horizontal_padding);
+        }
+        Ok(widths)
+    }
+    /// Creates a new table only containing the selected columns
+    ///
+    /// Header groups are shrunk to the selected columns they span
+    pub(super) fn select_columns(&self, columns: &[usize]) -> Table {
+        let header_groups = self
+            .header_groups
+            .iter()
+            .map(|group_row| {
+                let mut first_column = 0;
+                let groups = group_row
+                    .groups
+                    .iter()
+                    .filter_map(|group| {
+                        let spanned = first_column..first_column + group.span;
+                        first_column += group.span;
+                        let span = columns
+                            .iter()
+                            .filter(|&&column| spanned.contains(&column))
+                            .count();
+                        (span > 0).then(|| ColumnGroup {
+                            span,
+                            ..group.clone()
+                        })
+                    })
+                    .collect();
+                HeaderGroupRow {
+                    groups,
+                    styles: group_row.styles.clone(),
+                }
+            })
+            .collect();
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| Row {
+                values: columns
+                    .iter()
+                    .map(|&column| row.values[column].clone())
+                    .collect(),
+                styles: row.styles.clone(),
+            })
+            .collect();
+        Table {
+            columns: columns
+                .iter()
+                .map(|&column| self.columns[column].clone())
+                .collect(),
+            header_groups,
+            rows,
+            styles: self.styles.clone(),
+            new_page: self.new_page.clone(),
+        }
+    }
+    /// Renders the table as multiple sets of pages. Each containing the columns that fit
+    pub(super) fn render_horizontal_pages(
+        self,
+        document: &mut PdfDocument,
+        first_page: (TablePageRules, PdfPage),
+        repeated_columns: usize,
+    ) -> Result<(TableRenderReport, TableEndPage), TuxPdfError> {
+        let (left, right, _, _): (Pt, Pt, Pt, Pt) = first_page.0.margin.unwrap_or_default().into();
+        let available_width = first_page.0.page_size.width - left - right;
+        let widths = self.estimate_column_widths(document, available_width)?;
+
+        let mut report = TableRenderReport::default();
+        let mut last_page = None;
+        let mut next_page = Some(first_page);
+        for columns in horizontal_column_sets(&widths, available_width, repeated_columns) {
+            if let Some(TableEndPage { page, .. }) = last_page.take() {
+                document.add_page(page);
+            }
+            let page = match next_page.take() {
+                Some(page) => page,
+                None => {
+                    let (page_rules, mut page) = self.new_page.create(document)?;
+                    document.page_created(&mut page, 0);
+                    (page_rules, page)
+                }
+            };
+            let mut table = self.select_columns(&columns);
+            table.styles.horizontal_overflow = HorizontalOverflow::Overflow;
+            let (set_report, set_last_page) = table.render_pages(document, page)?;
+            last_page = Some(set_last_page);
+            // The report of the set only knows about the selected columns
+            report
+                .pages
+                .extend(set_report.pages.into_iter().map(|mut page| {
+                    page.columns = page.columns.iter().map(|&index| columns[index]).collect();
+                    page
+                }));
+        }
+        // A table without columns is never drawn. So the first page is left as it is
+        let last_page = last_page
+            .or_else(|| next_page.map(|(page_rules, page)| TableEndPage { page, page_rules }))
+            .expect("Either a set was rendered or the first page was not used");
+        Ok((report, last_page))
+    }
+}
+/// Splits the columns into sets that fit within the available width
+///
+/// Every set starts with the repeated columns. A column that does not fit on its own gets a set to itself.
+fn horizontal_column_sets(
+    widths: &[Pt],
+    available_width: Pt,
+    repeated_columns: usize,
+) -> Vec<Vec<usize>> {
+    let repeated_columns = repeated_columns.min(widths.len());
+    let repeated_width: Pt = widths[..repeated_columns].iter().copied().sum();
+    let mut sets = Vec::new();
+    let mut current: Vec<usize> = (0..repeated_columns).collect();
+    let mut current_width = repeated_width;
+    for (index, width) in widths.iter().enumerate().skip(repeated_columns) {
+        if current.len() > repeated_columns && current_width + *width > available_width {
+            sets.push(mem::replace(&mut current, (0..repeated_columns).collect()));
+            current_width = repeated_width;
+        }
+        current.push(index);
+        current_width += *width;
+    }
+    if current.len() > repeated_columns || sets.is_empty() {
+        sets.push(current);
+    }
+    sets
+}
+#[cfg(test)]
+mod tests {
+    use crate::units::{Pt, UnitType};
+
+    use super::horizontal_column_sets;
+
+    #[test]
+    fn column_sets_repeat_leading_columns() {
+        let widths: Vec<Pt> = vec![50f32.pt(), 100f32.pt(), 100f32.pt(), 100f32.pt()];
+        let sets = horizontal_column_sets(&widths, 260f32.pt(), 1);
+        assert_eq!(sets, vec![vec![0, 1, 2], vec![0, 3]]);
+    }
+    #[test]
+    fn column_too_wide_gets_own_set() {
+        let widths: Vec<Pt> = vec![300f32.pt(), 50f32.pt()];
+        let sets = horizontal_column_sets(&widths, 200f32.pt(), 0);
+        assert_eq!(sets, vec![vec![0], vec![1]]);
+    }
+    #[test]
+    fn everything_fits() {
+        let widths: Vec<Pt> = vec![50f32.pt(), 50f32.pt()];
+        let sets = horizontal_column_sets(&widths, 200f32.pt(), 1);
+        assert_eq!(sets, vec![vec![0, 1]]);
+    }
+}
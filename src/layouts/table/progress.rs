@@ -0,0 +1,203 @@
+use crate::{
+    TuxPdfError,
+    document::PdfDocument,
+    graphics::{
+        GraphicStyles, GraphicsGroup, PdfObject, PdfPosition, TextStyle,
+        color::{BLACK_RGB, Color, GRAY_RGB},
+        shapes::PaintedRect,
+        size::Size,
+    },
+    units::Pt,
+};
+
+use super::{CellRenderArea, CellRenderer};
+
+/// A bar filled to `fraction`. The value of a [TableValue::Progress](super::TableValue::Progress)
+///
+/// The bar stretches across the cell without its padding. So the column decides how long it is
+///
+/// ```rust
+/// use tux_pdf::{
+///     graphics::color::GREEN_RGB,
+///     layouts::table::{ProgressBar, ProgressBarStyle, Row, TableValue},
+/// };
+/// let row = Row::from(vec![
+///     TableValue::from("Migration"),
+///     TableValue::Progress(ProgressBar {
+///         fraction: 0.75,
+///         style: ProgressBarStyle {
+///             bar_color: Some(GREEN_RGB),
+///             ..Default::default()
+///         },
+///     }),
+/// ]);
+/// assert_eq!(row.number_of_columns(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ProgressBar {
+    /// Between `0` and `1`. Values outside of the range are clamped
+    pub fraction: f32,
+    pub style: ProgressBarStyle,
+}
+impl ProgressBar {
+    /// A progress bar with the default styles
+    pub fn new(fraction: f32) -> Self {
+        Self {
+            fraction,
+            style: ProgressBarStyle::default(),
+        }
+    }
+}
+impl CellRenderer for ProgressBar {
+    fn content_size(&self, _: &PdfDocument, text_style: &TextStyle) -> Result<Size, TuxPdfError> {
+        Ok(self.style.content_size(text_style))
+    }
+    fn render(
+        &self,
+        _: &PdfDocument,
+        area: CellRenderArea,
+        text_style: &TextStyle,
+    ) -> Result<Vec<PdfObject>, TuxPdfError> {
+        Ok(self.style.render(self.fraction, area, text_style))
+    }
+}
+/// Styles of a [ProgressBar]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ProgressBarStyle {
+    /// The unfilled part of the bar. Defaults to a light gray
+    pub track_color: Option<Color>,
+    /// Defaults to the fill color of the row text style or black
+    pub bar_color: Option<Color>,
+    /// Defaults to the font size of the row. Limited to the height of the cell
+    pub height: Option<Pt>,
+    /// The width used when sizing the column. Defaults to five times the font size
+    pub min_width: Option<Pt>,
+}
+impl ProgressBarStyle {
+    /// The size of the bar without padding
+    fn content_size(&self, text_style: &TextStyle) -> Size {
+        Size::new(
+            self.min_width.unwrap_or(text_style.font_size * 5f32),
+            self.height.unwrap_or(text_style.font_size),
+        )
+    }
+    /// The track and the bar filled to `fraction`. Placed at the top of the content area
+    fn render(
+        &self,
+        fraction: f32,
+        area: CellRenderArea,
+        text_style: &TextStyle,
+    ) -> Vec<PdfObject> {
+        let CellRenderArea {
+            content_position,
+            content_size,
+            ..
+        } = area;
+        let height = self
+            .height
+            .unwrap_or(text_style.font_size)
+            .min(content_size.height);
+        let position = PdfPosition {
+            y: content_position.y + content_size.height - height,
+            ..content_position
+        };
+        let fraction = if fraction.is_nan() {
+            0f32
+        } else {
+            fraction.clamp(0f32, 1f32)
+        };
+        let rect = |width: Pt, color: Color| -> PdfObject {
+            GraphicsGroup {
+                items: vec![
+                    PaintedRect {
+                        position,
+                        size: Size::new(width, height),
+                        ..Default::default()
+                    }
+                    .into(),
+                ],
+                ..Default::default()
+            }
+            .with_styles(GraphicStyles {
+                fill_color: Some(color),
+                ..Default::default()
+            })
+            .into()
+        };
+        let track_color = self
+            .track_color
+            .clone()
+            .unwrap_or_else(|| GRAY_RGB.lighten(0.6));
+        let bar_color = self
+            .bar_color
+            .clone()
+            .or_else(|| text_style.fill_color.clone())
+            .unwrap_or(BLACK_RGB);
+        let mut objects = vec![rect(content_size.width, track_color)];
+        if fraction > 0f32 {
+            objects.push(rect(content_size.width * fraction, bar_color));
+        }
+        objects
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::{ProgressBar, ProgressBarStyle};
+    use crate::{
+        document::PdfDocument,
+        graphics::{
+            GraphicItems, PdfObject, PdfPosition, TextStyle, color::GREEN_RGB, shapes::OutlineRect,
+            size::Size,
+        },
+        layouts::table::{CellRenderArea, CellRenderer, TableValue},
+        units::UnitType,
+    };
+
+    fn bounds(object: &PdfObject) -> OutlineRect {
+        let PdfObject::Graphics(GraphicItems::Group(group)) = object else {
+            panic!("Rectangles are drawn in a group");
+        };
+        group.bounds().expect("The group has a rectangle")
+    }
+    #[test]
+    fn filled_to_fraction() -> anyhow::Result<()> {
+        let document = PdfDocument::new("Progress");
+        let text_style = TextStyle {
+            font_size: 10f32.pt(),
+            ..Default::default()
+        };
+        let area = CellRenderArea {
+            cell_position: PdfPosition::new(0f32.pt(), 0f32.pt()),
+            cell_size: Size::new(110f32.pt(), 30f32.pt()),
+            content_position: PdfPosition::new(5f32.pt(), 5f32.pt()),
+            content_size: Size::new(100f32.pt(), 20f32.pt()),
+        };
+        let bar = |fraction: f32| ProgressBar {
+            fraction,
+            style: ProgressBarStyle {
+                bar_color: Some(GREEN_RGB),
+                ..Default::default()
+            },
+        };
+        assert_eq!(
+            bar(0.25).content_size(&document, &text_style)?,
+            Size::new(50f32.pt(), 10f32.pt())
+        );
+
+        let objects = bar(0.25).render(&document, area, &text_style)?;
+        assert_eq!(objects.len(), 2);
+        let track = bounds(&objects[0]);
+        assert_eq!(track.position, PdfPosition::new(5f32.pt(), 15f32.pt()));
+        assert_eq!(track.size, Size::new(100f32.pt(), 10f32.pt()));
+        assert_eq!(bounds(&objects[1]).size.width, 25f32.pt());
+
+        // Out of range values are clamped
+        assert_eq!(bar(-1.0).render(&document, area, &text_style)?.len(), 1);
+        let full = bar(1.5).render(&document, area, &text_style)?;
+        assert_eq!(bounds(&full[1]).size.width, 100f32.pt());
+
+        // Tables draw progress values through the renderer
+        assert!(TableValue::progress(0.5).renderer().is_some());
+        Ok(())
+    }
+}
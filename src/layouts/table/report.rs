@@ -0,0 +1,298 @@
+use std::ops::Range;
+
+use crate::{
+    graphics::{PdfPosition, shapes::OutlineRect, size::Size},
+    page::PdfPage,
+    units::Pt,
+};
+
+use super::{CellRenderArea, TablePageRules, builder::TableLayout};
+
+/// Where a table was placed. Returned by [Table::render](super::Table::render)
+///
+/// Row and column indexes do not include hidden columns or filtered rows
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TableRenderReport {
+    /// One entry per page the table was drawn on. In the order the pages were added to the document
+    pub pages: Vec<TablePageReport>,
+}
+impl TableRenderReport {
+    /// The page the table ended on
+    pub fn last_page(&self) -> Option<&TablePageReport> {
+        self.pages.last()
+    }
+    /// The bottom of the table on the last page. Content below the table can start here
+    pub fn end_y(&self) -> Option<Pt> {
+        self.last_page().map(TablePageReport::end_y)
+    }
+}
+/// The page a table ended on. Returned by [Table::render_and_continue](super::Table::render_and_continue)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableContinuation {
+    /// The page the table ended on. It has not been added to the document
+    ///
+    /// The [page index](TablePageReport::page_index) of the last page in the report assumes it is the next page added
+    pub page: PdfPage,
+    /// The space below the table within the page rules of the page
+    pub remaining: OutlineRect,
+    pub report: TableRenderReport,
+}
+/// The last page of a table before it is added to the document
+pub(super) struct TableEndPage {
+    pub(super) page: PdfPage,
+    pub(super) page_rules: TablePageRules,
+}
+impl TableEndPage {
+    /// The area between the bottom of the table and the bottom of the page rules
+    pub(super) fn remaining_area(&self, table_end_y: Option<Pt>) -> OutlineRect {
+        let rules = &self.page_rules;
+        let (left, right, _top, _bottom) = rules.margin.unwrap_or_default().into();
+        let bottom = rules.table_stop_y.unwrap_or_default();
+        let top = table_end_y
+            .or(rules.table_start_y)
+            .unwrap_or(rules.page_size.height);
+        OutlineRect {
+            position: PdfPosition::new(left, bottom),
+            size: Size::new(
+                rules.page_size.width - left - right,
+                (top - bottom).max(Pt::default()),
+            ),
+        }
+    }
+}
+/// The part of a table drawn on one page
+#[derive(Debug, Clone, PartialEq)]
+pub struct TablePageReport {
+    /// Index of the page within the document
+    pub page_index: usize,
+    /// The columns on this page. Every column unless [HorizontalOverflow::Paginate](super::HorizontalOverflow::Paginate) split them
+    pub columns: Vec<usize>,
+    /// The width of each column on this page including the cell padding
+    pub column_widths: Vec<Pt>,
+    /// The rows on this page
+    pub rows: Range<usize>,
+    /// The cells of the header row
+    pub header_cells: Vec<CellRenderArea>,
+    /// The cells of each row on this page. `cells[0]` is the row at `rows.start`
+    pub cells: Vec<Vec<CellRenderArea>>,
+    /// The outline of the table including the header
+    pub bounds: OutlineRect,
+}
+impl TablePageReport {
+    pub(super) fn new(page_index: usize, rows: Range<usize>, layout: &TableLayout) -> Self {
+        let columns = 0..layout.columns.len();
+        let row_cells = |row: usize| {
+            columns
+                .clone()
+                .filter_map(|column| layout.cell_area(row, column))
+                .collect::<Vec<_>>()
+        };
+        // The first row of the layout is the header row. The row of an empty message is left out
+        let cells = (1..=rows.len()).map(row_cells).collect();
+        Self {
+            page_index,
+            columns: columns.clone().collect(),
+            column_widths: layout.columns.iter().map(|column| column.width).collect(),
+            rows,
+            header_cells: row_cells(0),
+            cells,
+            bounds: OutlineRect {
+                position: PdfPosition::new(
+                    layout.start.x,
+                    layout.start.y - layout.final_size.height,
+                ),
+                size: layout.final_size,
+            },
+        }
+    }
+    /// The bottom of the table on this page
+    pub fn end_y(&self) -> Pt {
+        self.bounds.position.y
+    }
+    /// The cell of a row on this page. Returns None if the row is on another page
+    pub fn cell(&self, row: usize, column: usize) -> Option<&CellRenderArea> {
+        if !self.rows.contains(&row) {
+            return None;
+        }
+        let column = self.columns.iter().position(|&index| index == column)?;
+        self.cells.get(row - self.rows.start)?.get(column)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::{
+        document::{BuiltinFont, PdfDocument},
+        graphics::{PdfObject, PdfPosition, styles::Margin},
+        layouts::table::{Row, RowStyles, Table, TablePageRules, TableStyles},
+        page::{PdfPage, page_sizes::A4},
+        units::UnitType,
+    };
+
+    #[test]
+    fn report_matches_layout() -> anyhow::Result<()> {
+        let mut table = Table::default();
+        table.add_column("Name".into());
+        table.add_column("Quantity".into());
+        for index in 0..3 {
+            table.add_row(Row::from(vec![format!("Item {index}"), index.to_string()]));
+        }
+        let mut doc = PdfDocument::new("Report");
+        doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        doc.add_page(PdfPage::new_from_page_size(A4));
+        let page = PdfPage::new_from_page_size(A4);
+        let report = table.render(&mut doc, (TablePageRules::for_page(&page), page))?;
+
+        assert_eq!(report.pages.len(), 1);
+        let page = &report.pages[0];
+        assert_eq!(page.page_index, 1);
+        assert_eq!(page.rows, 0..3);
+        assert_eq!(page.columns, [0, 1]);
+        assert_eq!(page.header_cells.len(), 2);
+        let last_cell = page.cell(2, 1).expect("The last cell is on the page");
+        assert!(last_cell.cell_position.y >= page.end_y());
+        assert!(page.cell(3, 0).is_none());
+        assert_eq!(report.end_y(), Some(page.end_y()));
+        Ok(())
+    }
+
+    #[test]
+    fn continue_below_table() -> anyhow::Result<()> {
+        let mut table = Table::default();
+        table.add_column("Name".into());
+        table.add_row(Row::from(vec!["Item"]));
+        let mut doc = PdfDocument::new("Continue");
+        doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        let page = PdfPage::new_from_page_size(A4).with_margin(Margin::all(20f32.pt()));
+        // Leave room above the table for a heading
+        let mut region = page.content_area();
+        region.size.height -= 100f32.pt();
+        let rules = TablePageRules::for_region(&page, region);
+
+        let continuation = table.render_and_continue(&mut doc, (rules, page))?;
+        assert_eq!(doc.number_of_pages(), 0);
+        let end_y = continuation.report.end_y().expect("The table was drawn");
+        let remaining = continuation.remaining;
+        assert_eq!(remaining.position, PdfPosition::new(20f32.pt(), 20f32.pt()));
+        assert_eq!(remaining.size.height, end_y - 20f32.pt());
+        assert_eq!(remaining.size.width, region.size.width);
+        assert!(end_y < region.position.y + region.size.height);
+        doc.add_page(continuation.page);
+        Ok(())
+    }
+    #[test]
+    fn row_height_limits() -> anyhow::Result<()> {
+        let mut table = Table {
+            styles: TableStyles {
+                min_row_height: Some(40f32.pt()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        table.add_column("Name".into());
+        table.add_row(Row::from(vec!["Natural"]));
+        table.add_row(Row {
+            styles: Some(RowStyles::fixed_height(60f32.pt())),
+            ..Row::from(vec!["Fixed"])
+        });
+        table.add_row(Row {
+            styles: Some(RowStyles {
+                max_height: Some(10f32.pt()),
+                min_height: Some(5f32.pt()),
+                ..Default::default()
+            }),
+            ..Row::from(vec!["Clamped"])
+        });
+        let mut doc = PdfDocument::new("Row Heights");
+        doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        let page = PdfPage::new_from_page_size(A4);
+        let report = table.render(&mut doc, (TablePageRules::for_page(&page), page))?;
+        let page = &report.pages[0];
+        let height = |row| page.cell(row, 0).map(|cell| cell.cell_size.height);
+        assert_eq!(page.header_cells[0].cell_size.height, 40f32.pt());
+        assert_eq!(height(0), Some(40f32.pt()));
+        assert_eq!(height(1), Some(60f32.pt()));
+        assert_eq!(height(2), Some(10f32.pt()));
+        Ok(())
+    }
+    #[test]
+    fn uniform_row_height() -> anyhow::Result<()> {
+        let mut table = Table {
+            styles: TableStyles {
+                uniform_row_height: Some(30f32.pt()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        table.add_column("Name".into());
+        table.add_column("Signature".into());
+        // An empty sign in sheet
+        for _ in 0..5 {
+            table.add_row(Row::from(vec!["", ""]));
+        }
+        table.add_row(Row {
+            styles: Some(RowStyles::fixed_height(100f32.pt())),
+            ..Row::from(vec!["", ""])
+        });
+        let mut doc = PdfDocument::new("Sign In");
+        doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        let page = PdfPage::new_from_page_size(A4);
+        let report = table.render(&mut doc, (TablePageRules::for_page(&page), page))?;
+        let page = &report.pages[0];
+        assert!(page.header_cells[0].cell_size.height < 30f32.pt());
+        assert!(
+            page.cells
+                .iter()
+                .flatten()
+                .all(|cell| cell.cell_size.height == 30f32.pt())
+        );
+        Ok(())
+    }
+    #[test]
+    fn empty_message() -> anyhow::Result<()> {
+        let empty_table = |empty_message: Option<&str>| {
+            let mut table = Table {
+                styles: TableStyles {
+                    empty_message: empty_message.map(Into::into),
+                    empty_row_styles: Some(RowStyles {
+                        min_height: Some(100f32.pt()),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            table.add_column("Date".into());
+            table.add_column("Amount".into());
+            table
+        };
+        let mut doc = PdfDocument::new("Empty");
+        doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        let page = PdfPage::new_from_page_size(A4);
+        let without_message = empty_table(None)
+            .render_and_continue(&mut doc, (TablePageRules::for_page(&page), page.clone()))?;
+        let with_message = empty_table(Some("No records for this period"))
+            .render_and_continue(&mut doc, (TablePageRules::for_page(&page), page))?;
+
+        let header_height = without_message.report.pages[0].bounds.size.height;
+        let report = &with_message.report.pages[0];
+        assert_eq!(report.rows, 0..0);
+        assert!(report.cells.is_empty());
+        assert_eq!(report.bounds.size.height, header_height + 100f32.pt());
+
+        let texts: Vec<_> = with_message
+            .page
+            .contents
+            .iter()
+            .filter_map(|object| match object {
+                PdfObject::TextBlock(block) => Some(block),
+                _ => None,
+            })
+            .collect();
+        // The two headers and the message
+        assert_eq!(texts.len(), 3);
+        let message = texts[2];
+        assert!(message.position.y < report.bounds.position.y + 100f32.pt());
+        assert!(message.position.x > report.bounds.position.x);
+        Ok(())
+    }
+}
@@ -1,19 +1,26 @@
+use std::sync::Arc;
+
 use crate::{
     TuxPdfError,
     document::PdfDocument,
     graphics::{
         TextBlockContent, TextStyle,
+        color::Color,
         size::{RenderSize, Size},
     },
     layouts::table::builder::{GridStyleGroup, TableColumnMaxWidth, TableColumnMinWidth},
 };
 
-use super::{CellStyle, ColumnStyle, RowStyles};
+use super::{
+    BuiltinIcon, CellAlignment, CellRenderer, CellStyle, ColumnStyle, ProgressBar, RowStyles,
+};
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct Column {
     pub header: TextBlockContent,
     pub styles: Option<ColumnStyle>,
+    /// Hidden columns and their values are left out when the table is rendered
+    pub hidden: bool,
 }
 impl Column {
     pub fn with_cell_styles(mut self, styles: CellStyle) -> Self {
@@ -39,6 +46,24 @@ impl Column {
         }
         self
     }
+    pub fn with_alignment(mut self, alignment: CellAlignment) -> Self {
+        if let Some(column_styles) = self.styles.as_mut() {
+            column_styles.alignment = Some(alignment);
+        } else {
+            self.styles = Some(ColumnStyle {
+                alignment: Some(alignment),
+                ..Default::default()
+            });
+        }
+        self
+    }
+    /// The alignment of the column. Left if not set
+    pub fn alignment(&self) -> CellAlignment {
+        self.styles
+            .as_ref()
+            .and_then(|styles| styles.alignment)
+            .unwrap_or_default()
+    }
     pub fn with_min_width(mut self, min_width: TableColumnMinWidth) -> Self {
         if let Some(column_styles) = self.styles.as_mut() {
             column_styles.min_width = Some(min_width);
@@ -64,12 +89,105 @@ where
     }
 }
 
+/// A heading that spans multiple columns.
+///
+/// Rendered above the column headers
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ColumnGroup {
+    pub header: TextBlockContent,
+    /// The number of columns this group spans
+    pub span: usize,
+    /// Override the styles of the [HeaderGroupRow] for this group
+    pub styles: Option<RowStyles>,
+    /// Horizontal alignment of the header within the columns it spans
+    pub alignment: CellAlignment,
+}
+impl ColumnGroup {
+    pub fn new(header: impl Into<TextBlockContent>, span: usize) -> Self {
+        Self {
+            header: header.into(),
+            span,
+            styles: None,
+            alignment: CellAlignment::Left,
+        }
+    }
+    pub fn with_styles(mut self, styles: RowStyles) -> Self {
+        self.styles = Some(styles);
+        self
+    }
+    pub fn with_alignment(mut self, alignment: CellAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+}
+/// A row of [ColumnGroup]s that is placed above the column headers
+///
+/// Repeated on every page the table is rendered on.
 #[derive(Debug, Clone, PartialEq, Default)]
+pub struct HeaderGroupRow {
+    pub groups: Vec<ColumnGroup>,
+    /// Override the header styles for this row
+    pub styles: Option<RowStyles>,
+}
+impl HeaderGroupRow {
+    /// Adds styles to the row
+    pub fn with_styles(mut self, styles: RowStyles) -> Self {
+        self.styles = Some(styles);
+        self
+    }
+    /// The number of columns spanned by all the groups in this row
+    pub fn number_of_columns(&self) -> usize {
+        self.groups.iter().map(|group| group.span).sum()
+    }
+}
+impl From<Vec<ColumnGroup>> for HeaderGroupRow {
+    fn from(groups: Vec<ColumnGroup>) -> Self {
+        Self {
+            groups,
+            styles: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
 pub enum TableValue {
     Text(TextBlockContent),
+    /// Content drawn by a [CellRenderer]
+    Custom(Arc<dyn CellRenderer>),
+    /// A box that is checked when `true`. See [BuiltinIcon::checkbox]
+    Checkbox(bool),
+    Icon(BuiltinIcon),
+    /// A bar filled to a fraction. See [ProgressBar]
+    Progress(ProgressBar),
     #[default]
     BlankSpace,
 }
+impl TableValue {
+    /// A progress bar with the default styles
+    pub fn progress(fraction: f32) -> Self {
+        Self::Progress(ProgressBar::new(fraction))
+    }
+    /// The text of a [TableValue::Text]
+    pub fn text(&self) -> Option<String> {
+        match self {
+            TableValue::Text(text) => Some(text.text()),
+            _ => None,
+        }
+    }
+}
+impl PartialEq for TableValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Text(a), Self::Text(b)) => a == b,
+            (Self::Custom(a), Self::Custom(b)) => Arc::ptr_eq(a, b),
+            (Self::Checkbox(a), Self::Checkbox(b)) => a == b,
+            (Self::Icon(a), Self::Icon(b)) => a == b,
+            (Self::Progress(a), Self::Progress(b)) => a == b,
+            (Self::BlankSpace, Self::BlankSpace) => true,
+            _ => false,
+        }
+    }
+}
 impl<T> From<T> for TableValue
 where
     T: Into<TextBlockContent>,
@@ -89,6 +207,13 @@ pub struct TableValueWithStyle {
     pub style: Option<CellStyle>,
 }
 
+impl TableValueWithStyle {
+    /// Fills the background of the cell. See [ColorRamp](crate::graphics::color::ColorRamp) for value based colors
+    pub fn with_fill_color(mut self, fill_color: impl Into<Color>) -> Self {
+        self.style.get_or_insert_with(CellStyle::default).fill_color = Some(fill_color.into());
+        self
+    }
+}
 impl<T> From<T> for TableValueWithStyle
 where
     T: Into<TableValue>,
@@ -125,9 +250,10 @@ impl Row {
     ) -> Result<Vec<Size>, TuxPdfError> {
         self.values
             .iter()
-            .map(|value| match &value.value {
-                TableValue::Text(text) => (*text).render_size(document, default_text_style),
-                TableValue::BlankSpace => Ok(().render_size(document, &()).unwrap()),
+            .map(|value| match (&value.value, value.value.renderer()) {
+                (_, Some(renderer)) => renderer.content_size(document, default_text_style),
+                (TableValue::Text(text), None) => (*text).render_size(document, default_text_style),
+                (_, None) => Ok(().render_size(document, &()).unwrap()),
             })
             .collect()
     }
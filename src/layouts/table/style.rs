@@ -1,20 +1,25 @@
 use crate::{
+    document::ReadingDirection,
     graphics::{
-        PartialOrFullTextStyle, TextStyle,
+        PartialOrFullTextStyle, PartialTextStyle, TextBlockContent, TextStyle,
         color::{BLACK_RGB, Color, GRAY_RGB},
         styles::Padding,
     },
-    layouts::table::{GridStyleGroup, TableColumnMaxWidth},
+    layouts::{
+        Theme,
+        table::{GridStyleGroup, HorizontalOverflow, TableColumnMaxWidth},
+    },
     units::{Pt, UnitType},
     utils::Merge,
 };
 
-use super::builder::TableColumnMinWidth;
+use super::{CellColorRule, RowFilter, builder::TableColumnMinWidth};
 /// A cell is where the area where the row and column intersect
 ///
-/// Currently cell styles are not supported
+/// Currently only the fill color is supported
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct CellStyle {
+    /// Painted over the row background. Set on a [value](super::TableValueWithStyle) or on every cell of a [column](super::Column::with_cell_styles)
     pub fill_color: Option<Color>,
     pub border_color: Option<Color>,
     pub border_width: Option<Pt>,
@@ -27,6 +32,34 @@ pub struct ColumnStyle {
     pub min_width: Option<TableColumnMinWidth>,
     /// Cell Styling Options
     pub cell_styles: Option<CellStyle>,
+    /// Horizontal alignment of the text in the header and the cells of the column
+    pub alignment: Option<CellAlignment>,
+    /// Colors the background of each cell based on its value
+    pub color_rule: Option<CellColorRule>,
+}
+/// Horizontal alignment of text within a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CellAlignment {
+    #[default]
+    Left,
+    Center,
+    /// Good for numbers
+    Right,
+    /// Values of the column line up on the decimal separator. Used for financial tables
+    ///
+    /// The values are right aligned as a group. Outside of a table body this is the same as [CellAlignment::Right]
+    Decimal(char),
+}
+impl CellAlignment {
+    /// The offset from the left of the content area for content with the given width
+    pub fn offset(&self, content_width: Pt, available_width: Pt) -> Pt {
+        let free = (available_width - content_width).max(Pt::default());
+        match self {
+            CellAlignment::Left => Pt::default(),
+            CellAlignment::Center => free / 2f32,
+            CellAlignment::Right | CellAlignment::Decimal(_) => free,
+        }
+    }
 }
 /// Row Styles are the styles that are applied to the entire row
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -35,6 +68,20 @@ pub struct RowStyles {
     pub background_color: Option<Color>,
     pub border_color: Option<Color>,
     pub border_width: Option<Pt>,
+    /// The row is at least this tall. Replaces [TableStyles::min_row_height]
+    pub min_height: Option<Pt>,
+    /// The row is at most this tall. Content that does not fit overflows the bottom of the row
+    pub max_height: Option<Pt>,
+}
+impl RowStyles {
+    /// A row that is always the height. Such as the empty rows of a sign in sheet
+    pub fn fixed_height(height: Pt) -> Self {
+        Self {
+            min_height: Some(height),
+            max_height: Some(height),
+            ..Default::default()
+        }
+    }
 }
 impl From<RowStyles> for GridStyleGroup {
     fn from(row_styles: RowStyles) -> Self {
@@ -68,6 +115,12 @@ impl Merge for RowStyles {
         if let Some(other) = other.border_width {
             self.border_width = Some(other);
         }
+        if let Some(other) = other.min_height {
+            self.min_height = Some(other);
+        }
+        if let Some(other) = other.max_height {
+            self.max_height = Some(other);
+        }
     }
 }
 impl Merge<RowStyles> for GridStyleGroup {
@@ -84,6 +137,25 @@ impl Merge<RowStyles> for GridStyleGroup {
     }
 }
 
+/// How the column widths are calculated before the table is laid out
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ColumnSizing {
+    /// Columns start at the size of the header and grow as rows are added.
+    ///
+    /// Column widths can differ between pages
+    #[default]
+    Incremental,
+    /// Every row is measured before the layout is built.
+    ///
+    /// All pages of the table will have identical column widths
+    MeasureAll,
+    /// Measures an evenly spaced sample of the given number of rows before the layout is built
+    ///
+    /// Faster than [ColumnSizing::MeasureAll] for large tables. Columns can still grow if a row outside of the sample is wider
+    ///
+    /// A sample of 0 measures every row like [ColumnSizing::MeasureAll]
+    MeasureSample(usize),
+}
 #[derive(Debug, Clone, PartialEq)]
 pub struct TableStyles {
     /// Overrides for styles for the header
@@ -94,7 +166,19 @@ pub struct TableStyles {
     pub text_styles: TextStyle,
     /// If less than what is required it will be ignored.
     /// This is used if you want to have extra space
+    ///
+    /// Applies to the header and every row. A row can replace it with [RowStyles::min_height]
     pub min_row_height: Option<Pt>,
+    /// Every row other than the header is exactly this tall. Ignores the heights set on the rows
+    ///
+    /// Used for forms with ruled lines. Such as a sign in sheet of empty rows
+    pub uniform_row_height: Option<Pt>,
+    /// Shown below the header spanning every column when the table has no rows. Such as `No records for this period`
+    pub empty_message: Option<TextBlockContent>,
+    /// Styles of the row the [empty message](Self::empty_message) is shown in
+    ///
+    /// Use [RowStyles::min_height] to keep space for the body of an empty table
+    pub empty_row_styles: Option<RowStyles>,
     /// The padding around the cell content
     ///
     /// Recommended to be at least 5pt in all directions
@@ -108,8 +192,55 @@ pub struct TableStyles {
     pub cell_styles: Option<GridStyleGroup>,
     /// Base Row Styles. Any overrides will inherit from this
     pub row_styles: GridStyleGroup,
+    /// Background colors the rows alternate between. Starting with the first row
+    ///
+    /// Replaces the background of [Self::row_styles]. Styles set on a row still take priority
+    pub striped_rows: Option<[Color; 2]>,
     /// If true then the header will be repeated on a new page
     pub repeat_header_on_new_page: bool,
+    /// How columns that do not fit on the page are handled
+    pub horizontal_overflow: HorizontalOverflow,
+    /// How the column widths are calculated
+    pub column_sizing: ColumnSizing,
+    /// Right to left mirrors the order of the columns and their alignment
+    ///
+    /// If None the [reading direction](crate::document::PdfDocument::reading_direction) of the document is used
+    pub direction: Option<ReadingDirection>,
+    /// Only rows the filter returns true for are rendered. See [Table::with_row_filter](super::Table::with_row_filter)
+    pub row_filter: Option<RowFilter>,
+}
+impl TableStyles {
+    /// Header in the primary color with striped rows
+    pub fn from_theme(theme: &Theme) -> Self {
+        let colors = &theme.colors;
+        Self {
+            header_styles: Some(RowStyles {
+                text_style: Some(
+                    PartialTextStyle {
+                        font_ref: Some(theme.fonts.heading.clone()),
+                        fill_color: Some(colors.on_primary.clone()),
+                        ..Default::default()
+                    }
+                    .into(),
+                ),
+                background_color: Some(colors.primary.clone()),
+                ..Default::default()
+            }),
+            text_styles: theme.text_style(),
+            outer_styles: Some(GridStyleGroup {
+                border_color: Some(colors.border.clone()),
+                border_width: Some(theme.border_width),
+                ..Default::default()
+            }),
+            row_styles: GridStyleGroup {
+                background_color: Some(colors.background.clone()),
+                border_color: Some(colors.border.clone()),
+                border_width: Some(theme.border_width),
+            },
+            striped_rows: Some([colors.background.clone(), colors.table_stripe.clone()]),
+            ..Default::default()
+        }
+    }
 }
 impl Default for TableStyles {
     fn default() -> Self {
@@ -127,9 +258,17 @@ impl Default for TableStyles {
                 border_color: Some(BLACK_RGB),
                 border_width: Some(1f32.pt()),
             },
+            striped_rows: None,
             repeat_header_on_new_page: true,
             cell_styles: None,
             min_row_height: None,
+            uniform_row_height: None,
+            empty_message: None,
+            empty_row_styles: None,
+            horizontal_overflow: HorizontalOverflow::Overflow,
+            column_sizing: ColumnSizing::Incremental,
+            direction: None,
+            row_filter: None,
         }
     }
 }
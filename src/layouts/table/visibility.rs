@@ -0,0 +1,153 @@
+use std::{fmt::Debug, sync::Arc};
+
+use super::{Row, Table, TableError};
+
+/// Decides which rows of a [Table] are rendered. Rows the predicate returns false for are skipped
+///
+/// ```rust
+/// use tux_pdf::layouts::table::{Row, RowFilter, Table, TableValue};
+/// let mut table = Table::default();
+/// table.add_column("Name".into());
+/// table.add_column("Internal".into());
+/// table.add_row(Row::from(vec!["Public", "no"]));
+/// table.add_row(Row::from(vec!["Secret", "yes"]));
+///
+/// let mut customer_copy = table.clone();
+/// customer_copy.hide_column("Internal");
+/// customer_copy.styles.row_filter = Some(RowFilter::new(|row| {
+///     !matches!(&row.values[1].value, TableValue::Text(text) if text.text() == "yes")
+/// }));
+/// ```
+#[derive(Clone)]
+pub struct RowFilter(Arc<dyn Fn(&Row) -> bool + Send + Sync>);
+impl RowFilter {
+    pub fn new(predicate: impl Fn(&Row) -> bool + Send + Sync + 'static) -> Self {
+        Self(Arc::new(predicate))
+    }
+    /// True if the row should be rendered
+    pub fn is_visible(&self, row: &Row) -> bool {
+        (self.0)(row)
+    }
+}
+impl PartialEq for RowFilter {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+impl Debug for RowFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RowFilter").finish_non_exhaustive()
+    }
+}
+impl Table {
+    /// The index of the first column with the header text
+    pub fn column_index(&self, header: &str) -> Option<usize> {
+        self.columns
+            .iter()
+            .position(|column| column.header.text() == header)
+    }
+    /// Hides every column with the header text when the table is rendered
+    ///
+    /// Returns false if no column has the header
+    pub fn hide_column(&mut self, header: &str) -> bool {
+        self.set_column_hidden(header, true)
+    }
+    /// Shows a column hidden by [Table::hide_column]
+    ///
+    /// Returns false if no column has the header
+    pub fn show_column(&mut self, header: &str) -> bool {
+        self.set_column_hidden(header, false)
+    }
+    fn set_column_hidden(&mut self, header: &str, hidden: bool) -> bool {
+        let mut found = false;
+        for column in self
+            .columns
+            .iter_mut()
+            .filter(|column| column.header.text() == header)
+        {
+            column.hidden = hidden;
+            found = true;
+        }
+        found
+    }
+    /// Only rows the filter returns true for are rendered
+    pub fn with_row_filter(
+        mut self,
+        predicate: impl Fn(&Row) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.styles.row_filter = Some(RowFilter::new(predicate));
+        self
+    }
+    /// Validates the table and removes the hidden columns and filtered rows
+    pub(crate) fn into_visible(mut self) -> Result<Table, TableError> {
+        self.validate()?;
+        if let Some(filter) = self.styles.row_filter.take() {
+            self.rows.retain(|row| filter.is_visible(row));
+        }
+        if !self.columns.iter().any(|column| column.hidden) {
+            return Ok(self);
+        }
+        let visible: Vec<usize> = self
+            .columns
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| !column.hidden)
+            .map(|(index, _)| index)
+            .collect();
+        Ok(self.select_columns(&visible))
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::{
+        document::{BuiltinFont, PdfDocument},
+        layouts::table::{ColumnGroup, HeaderGroupRow, Row, Table, TablePageRules, TableValue},
+        page::{PdfPage, page_sizes::A4},
+    };
+
+    fn products() -> Table {
+        let mut table = Table::default();
+        table.add_column("Name".into());
+        table.add_column("Internal ID".into());
+        table.add_column("Price".into());
+        table.add_header_group_row(HeaderGroupRow::from(vec![
+            ColumnGroup::new("Product", 2),
+            ColumnGroup::new("Sales", 1),
+        ]));
+        table.add_row(Row::from(vec!["Widget", "1", "5"]));
+        table.add_row(Row::from(vec!["Gadget", "2", "0"]));
+        table
+    }
+
+    #[test]
+    fn hidden_columns_and_filtered_rows() -> anyhow::Result<()> {
+        let mut table = products().with_row_filter(
+            |row| !matches!(&row.values[2].value, TableValue::Text(price) if price.text() == "0"),
+        );
+        assert!(table.hide_column("Internal ID"));
+        assert!(!table.hide_column("Missing"));
+
+        let visible = table.clone().into_visible()?;
+        assert_eq!(visible.number_of_columns(), 2);
+        assert_eq!(visible.column_index("Price"), Some(1));
+        assert_eq!(visible.header_groups[0].number_of_columns(), 2);
+        assert_eq!(visible.number_of_rows(), 1);
+        assert_eq!(visible.rows[0].values.len(), 2);
+
+        // The definition is untouched so it can be rendered again with other settings
+        assert!(table.show_column("Internal ID"));
+        table.styles.row_filter = None;
+        let visible = table.into_visible()?;
+        assert_eq!(visible.number_of_columns(), 3);
+        assert_eq!(visible.number_of_rows(), 2);
+
+        let mut doc = PdfDocument::new("Visibility");
+        doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        let mut without_price = products();
+        without_price.hide_column("Price");
+        let page = PdfPage::new_from_page_size(A4);
+        without_price.render(&mut doc, (TablePageRules::for_page(&page), page))?;
+        assert_eq!(doc.extract_text().len(), 1);
+        Ok(())
+    }
+}
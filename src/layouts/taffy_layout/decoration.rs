@@ -0,0 +1,208 @@
+use crate::{
+    graphics::{
+        GraphicItems, GraphicStyles, GraphicsGroup, PdfPosition,
+        color::Color,
+        primitives::PathBuilder,
+        shapes::{PaintedRect, RectangleStyleType},
+        size::Size,
+    },
+    units::{Pt, UnitType},
+};
+
+/// The box of a taffy node that a [TaffyNodeDecoration] is drawn around
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DecorationBox {
+    /// The outer edge of the border
+    #[default]
+    BorderBox,
+    /// Inside of the border including the padding
+    PaddingBox,
+    /// Inside of the padding
+    ContentBox,
+}
+/// Background and border drawn behind a taffy node
+///
+/// The rect comes from the final layout of the node
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TaffyNodeDecoration {
+    pub background_color: Option<Color>,
+    pub border_color: Option<Color>,
+    pub border_width: Option<Pt>,
+    /// Curves every corner of the rect.
+    ///
+    /// The radius is limited to half of the shortest side
+    pub corner_radius: Option<Pt>,
+    pub decoration_box: DecorationBox,
+}
+impl TaffyNodeDecoration {
+    pub fn with_background_color(mut self, color: impl Into<Color>) -> Self {
+        self.background_color = Some(color.into());
+        self
+    }
+    pub fn with_border(mut self, color: impl Into<Color>, width: Pt) -> Self {
+        self.border_color = Some(color.into());
+        self.border_width = Some(width);
+        self
+    }
+    pub fn with_corner_radius(mut self, radius: Pt) -> Self {
+        self.corner_radius = Some(radius);
+        self
+    }
+    pub fn with_decoration_box(mut self, decoration_box: DecorationBox) -> Self {
+        self.decoration_box = decoration_box;
+        self
+    }
+    /// The rect of the node that is decorated
+    ///
+    /// Returns the offset from the top left of the node and the size of the rect
+    pub(super) fn rect(&self, layout: &taffy::Layout) -> (taffy::Point<f32>, taffy::Size<f32>) {
+        let inset = match self.decoration_box {
+            DecorationBox::BorderBox => taffy::Rect::ZERO,
+            DecorationBox::PaddingBox => layout.border,
+            DecorationBox::ContentBox => layout.border + layout.padding,
+        };
+        let offset = taffy::Point {
+            x: inset.left,
+            y: inset.top,
+        };
+        let size = taffy::Size {
+            width: (layout.size.width - inset.left - inset.right).max(0f32),
+            height: (layout.size.height - inset.top - inset.bottom).max(0f32),
+        };
+        (offset, size)
+    }
+    /// Creates the graphics for the decoration
+    ///
+    /// `position` is the lower left corner of the rect
+    pub(super) fn graphics(&self, position: PdfPosition, size: Size) -> Option<GraphicsGroup> {
+        let paint_mode = self.paint_mode()?;
+        let radius = self
+            .corner_radius
+            .unwrap_or_default()
+            .min(size.width / 2f32.pt())
+            .min(size.height / 2f32.pt());
+        let item: GraphicItems = if radius.0 <= 0f32 {
+            PaintedRect {
+                position,
+                size,
+                paint_mode,
+                ..Default::default()
+            }
+            .into()
+        } else {
+            let PdfPosition { x, y } = position;
+            let Size { width, height } = size;
+            let left = x + radius;
+            let right = x + width - radius;
+            let bottom = y + radius;
+            let top = y + height - radius;
+            PathBuilder::new()
+                .arc_to((right, bottom), radius, -90f32, 0f32)
+                .arc_to((right, top), radius, 0f32, 90f32)
+                .arc_to((left, top), radius, 90f32, 180f32)
+                .arc_to((left, bottom), radius, 180f32, 270f32)
+                .close()
+                .with_mode(paint_mode)
+                .build()
+                .into()
+        };
+        Some(GraphicsGroup {
+            styles: Some(self.into()),
+            items: vec![item],
+            ..Default::default()
+        })
+    }
+}
+impl RectangleStyleType for TaffyNodeDecoration {
+    fn has_fill_color(&self) -> bool {
+        self.background_color.is_some()
+    }
+
+    fn has_outline_color(&self) -> bool {
+        self.border_color.is_some()
+    }
+}
+impl From<&TaffyNodeDecoration> for GraphicStyles {
+    fn from(value: &TaffyNodeDecoration) -> Self {
+        Self {
+            line_width: value.border_width,
+            fill_color: value.background_color.clone(),
+            outline_color: value.border_color.clone(),
+            ..Default::default()
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::{DecorationBox, TaffyNodeDecoration};
+    use crate::{
+        graphics::{GraphicItems, PdfPosition, color::BLACK_RGB, size::Size},
+        units::UnitType,
+    };
+
+    fn layout() -> taffy::Layout {
+        taffy::Layout {
+            size: taffy::Size {
+                width: 100f32,
+                height: 50f32,
+            },
+            border: taffy::Rect {
+                left: 1f32,
+                right: 1f32,
+                top: 1f32,
+                bottom: 1f32,
+            },
+            padding: taffy::Rect {
+                left: 4f32,
+                right: 4f32,
+                top: 2f32,
+                bottom: 2f32,
+            },
+            ..taffy::Layout::new()
+        }
+    }
+
+    #[test]
+    fn decoration_box_rects() {
+        let layout = layout();
+        let (offset, size) = TaffyNodeDecoration::default().rect(&layout);
+        assert_eq!((offset.x, offset.y), (0f32, 0f32));
+        assert_eq!((size.width, size.height), (100f32, 50f32));
+
+        let (offset, size) = TaffyNodeDecoration::default()
+            .with_decoration_box(DecorationBox::PaddingBox)
+            .rect(&layout);
+        assert_eq!((offset.x, offset.y), (1f32, 1f32));
+        assert_eq!((size.width, size.height), (98f32, 48f32));
+
+        let (offset, size) = TaffyNodeDecoration::default()
+            .with_decoration_box(DecorationBox::ContentBox)
+            .rect(&layout);
+        assert_eq!((offset.x, offset.y), (5f32, 3f32));
+        assert_eq!((size.width, size.height), (90f32, 44f32));
+    }
+
+    #[test]
+    fn decoration_graphics() {
+        let position = PdfPosition::default();
+        let size = Size::new(100f32.pt(), 50f32.pt());
+        assert!(
+            TaffyNodeDecoration::default()
+                .graphics(position, size)
+                .is_none()
+        );
+
+        let square = TaffyNodeDecoration::default()
+            .with_background_color(BLACK_RGB)
+            .graphics(position, size)
+            .unwrap();
+        assert!(matches!(square.items[0], GraphicItems::Rectangle(_)));
+
+        let rounded = TaffyNodeDecoration::default()
+            .with_border(BLACK_RGB, 1f32.pt())
+            .with_corner_radius(5f32.pt())
+            .graphics(position, size)
+            .unwrap();
+        assert!(matches!(rounded.items[0], GraphicItems::Path(_)));
+    }
+}
@@ -1,40 +1,63 @@
+use std::mem;
+
 use super::LayoutError;
-use taffy::{Dimension, NodeId, PrintTree, TaffyTree};
+use taffy::{AvailableSpace, Dimension, NodeId, PrintTree, TaffyTree};
+mod decoration;
 mod style_builders;
 use crate::{
     TuxPdfError,
     document::PdfDocument,
     graphics::{
-        GraphicItems, GraphicStyles, GraphicsGroup, HasPosition, LayerType, PdfPosition,
-        color::BLACK_RGB, shapes::OutlineRect, size::Size,
+        Anchor, GraphicItems, GraphicStyles, GraphicsGroup, HasPosition, LayerType, PdfPosition,
+        TextBlock, TextBlockContent,
+        color::BLACK_RGB,
+        image::ImageFit,
+        shapes::OutlineRect,
+        size::{RenderSize, Size},
     },
     page::PdfPage,
     units::{Pt, UnitType},
 };
+pub use decoration::*;
 pub use style_builders::*;
 
 use tracing::{debug, info};
 type TaffyStyle = taffy::Style;
-use super::{LayoutItem, LayoutItemType};
+use super::{LayoutItem, LayoutItemType, NewPage};
 #[derive(Debug, Clone, PartialEq)]
 pub struct PdfTaffyItem {
     item: LayoutItem,
     node_id: NodeId,
+    decoration: Option<TaffyNodeDecoration>,
 }
 #[derive(Debug, Clone)]
 pub struct PdfTaffyLayout {
     items: Vec<PdfTaffyItem>,
     styling: TaffyStyle,
-    taffy_tree: TaffyTree<()>,
+    /// The context of each leaf is the index of its item in `items`
+    taffy_tree: TaffyTree<usize>,
     position: Option<PdfPosition>,
     page_size: Size,
     max_size: Size,
     root_node: Option<NodeId>,
+    /// Drawn behind every item using the layout of the root node
+    decoration: Option<TaffyNodeDecoration>,
+    /// Creates the next page when the items do not fit within [PdfTaffyLayout::render_pages]
+    pub new_page: TaffyNewPageFn,
+}
+/// Creates the pages the layout continues on. See [NewPage]
+pub type TaffyNewPageFn = NewPage<PdfPage>;
+
+pub fn no_new_page_allowed(_: &mut PdfDocument) -> Result<PdfPage, TuxPdfError> {
+    Err(LayoutError::NoNewPageAllowed.into())
 }
 impl LayoutItemType for PdfTaffyLayout {
+    fn position_anchor(&self) -> Anchor {
+        Anchor::TopLeft
+    }
     fn calculate_size(&mut self, document: &PdfDocument) -> Result<Size, TuxPdfError> {
         self.calculate_sizes(document)?;
-        let root = self.compute_layout()?;
+        let root = self.compute_layout(document)?;
 
         Ok(self.taffy_tree.get_final_layout(root).size.into())
     }
@@ -48,37 +71,8 @@ impl LayoutItemType for PdfTaffyLayout {
         Self: Sized,
     {
         self.calculate_sizes(document)?;
-        self.compute_layout()?;
-        let Self {
-            taffy_tree, items, ..
-        } = self;
-
-        for item in items {
-            let node = taffy_tree.get_final_layout(item.node_id);
-
-            let content_y: Pt = node.content_box_y().into();
-            let content_x: Pt = node.content_box_x().into();
-            debug!(?content_x, ?content_y, "Content Box Position");
-            let content_y = content_y + node.content_box_height();
-
-            let position: PdfPosition = if let Some(position) = self.position {
-                PdfPosition {
-                    x: position.x + content_x,
-                    y: position.y - content_y,
-                }
-            } else {
-                PdfPosition {
-                    x: content_x,
-                    y: content_y,
-                }
-            };
-            let mut item: LayoutItem = item.item;
-            item.set_position(position);
-            debug!(?item, ?position, "Rendering Item");
-
-            item.render(document, page)?;
-        }
-        Ok(())
+        self.compute_layout(document)?;
+        self.render_items(document, page)
     }
 }
 impl HasPosition for PdfTaffyLayout {
@@ -97,6 +91,7 @@ impl PartialEq for PdfTaffyLayout {
             && self.position == other.position
             && self.page_size == other.page_size
             && self.max_size == other.max_size
+            && self.decoration == other.decoration
     }
 }
 impl PdfTaffyLayout {
@@ -109,8 +104,22 @@ impl PdfTaffyLayout {
             max_size,
             root_node: None,
             position: None,
+            decoration: None,
+            new_page: TaffyNewPageFn::new(no_new_page_allowed),
         }
     }
+    pub fn with_new_page(
+        mut self,
+        new_page: impl FnMut(&mut PdfDocument) -> Result<PdfPage, TuxPdfError> + Send + 'static,
+    ) -> Self {
+        self.new_page = TaffyNewPageFn::new(new_page);
+        self
+    }
+    /// Decorates the root node
+    pub fn with_decoration(mut self, decoration: TaffyNodeDecoration) -> Self {
+        self.decoration = Some(decoration);
+        self
+    }
     pub fn update_styling(&mut self, styling: TaffyStyle) {
         self.styling = styling;
         if let Some(root) = self.root_node {
@@ -119,11 +128,34 @@ impl PdfTaffyLayout {
     }
     pub fn add_item(&mut self, item: impl Into<LayoutItem>, taffy_styles: taffy::Style) {
         let item = item.into();
-        let node_id = self.taffy_tree.new_leaf(taffy_styles).unwrap();
-        self.items.push(PdfTaffyItem { item, node_id });
+        let node_id = self
+            .taffy_tree
+            .new_leaf_with_context(taffy_styles, self.items.len())
+            .unwrap();
+        self.items.push(PdfTaffyItem {
+            item,
+            node_id,
+            decoration: None,
+        });
     }
+    /// Adds an item with a background and border drawn behind it
+    pub fn add_decorated_item(
+        &mut self,
+        item: impl Into<LayoutItem>,
+        taffy_styles: taffy::Style,
+        decoration: TaffyNodeDecoration,
+    ) {
+        self.add_item(item, taffy_styles);
+        if let Some(last) = self.items.last_mut() {
+            last.decoration = Some(decoration);
+        }
+    }
+    /// Sets a fixed size for every item that is not measured by [measure_item]
     fn calculate_sizes(&mut self, document: &PdfDocument) -> Result<(), TuxPdfError> {
         for item in &mut self.items {
+            if is_measured(&item.item) {
+                continue;
+            }
             let size = item.item.calculate_size(document)?;
             let taffy_size: taffy::Size<Dimension> = size.into();
             let node = self.taffy_tree.style(item.node_id).unwrap();
@@ -138,35 +170,205 @@ impl PdfTaffyLayout {
         }
         Ok(())
     }
-    fn compute_layout(&mut self) -> Result<NodeId, LayoutError> {
+    fn compute_layout(&mut self, document: &PdfDocument) -> Result<NodeId, TuxPdfError> {
         if let Some(root) = self.root_node {
-            if !self.taffy_tree.dirty(root)? {
+            if !self.taffy_tree.dirty(root).map_err(LayoutError::from)? {
                 return Ok(root);
             }
-            self.taffy_tree.remove(root)?;
+            self.taffy_tree.remove(root).map_err(LayoutError::from)?;
+        }
+        let root = self
+            .taffy_tree
+            .new_with_children(
+                self.styling.clone(),
+                &self
+                    .items
+                    .iter()
+                    .map(|item| item.node_id)
+                    .collect::<Vec<_>>(),
+            )
+            .map_err(LayoutError::from)?;
+        let items = &self.items;
+        // The measure function can not return an error so the first one is kept
+        let mut error = None;
+        self.taffy_tree
+            .compute_layout_with_measure(
+                root,
+                self.max_size.into(),
+                |known_dimensions, available_space, _, item_index, _| {
+                    let Some(item) = item_index.and_then(|index| items.get(*index)) else {
+                        return known_dimensions.unwrap_or(taffy::Size::ZERO);
+                    };
+                    match measure_item(&item.item, document, known_dimensions, available_space) {
+                        Ok(size) => size,
+                        Err(err) => {
+                            error.get_or_insert(err);
+                            known_dimensions.unwrap_or(taffy::Size::ZERO)
+                        }
+                    }
+                },
+            )
+            .map_err(LayoutError::from)?;
+        if let Some(error) = error {
+            return Err(error);
         }
-        let root = self.taffy_tree.new_with_children(
-            self.styling.clone(),
-            &self
-                .items
-                .iter()
-                .map(|item| item.node_id)
-                .collect::<Vec<_>>(),
-        )?;
-        self.taffy_tree.compute_layout(root, self.max_size.into())?;
         #[cfg(test)]
         self.taffy_tree.print_tree(root);
         self.root_node = Some(root);
 
         Ok(root)
     }
+    /// Renders the layout across as many pages as needed and adds them to the document
+    ///
+    /// Items that end below [PdfTaffyLayout::max_size] are moved to a page created by [PdfTaffyLayout::new_page].
+    /// The remaining items are laid out again at the same position on the new page.
+    /// An item taller than the max size is still rendered on a page by itself
+    ///
+    /// Without a position the layout starts at the top left of the [content area](PdfPage::content_area) of each page
+    pub fn render_pages(
+        mut self,
+        document: &mut PdfDocument,
+        first_page: PdfPage,
+    ) -> Result<(), TuxPdfError> {
+        let fixed_position = self.position;
+        let mut page = first_page;
+        loop {
+            let content_area = page.content_area();
+            self.position = fixed_position.or_else(|| {
+                Some(Anchor::TopLeft.point_of(content_area.position, content_area.size))
+            });
+            self.calculate_sizes(document)?;
+            self.compute_layout(document)?;
+            let Some(split_at) = self.first_overflowing_item() else {
+                self.render_items(document, &mut page)?;
+                document.add_page(page);
+                return Ok(());
+            };
+            debug!(?split_at, "Items overflow the page");
+            let remaining = self.items.split_off(split_at);
+            self.reset_root()?;
+            self.compute_layout(document)?;
+            self.render_items(document, &mut page)?;
+            let mut next_page = self.new_page.create(document)?;
+            // The current page is added after the next page is created
+            document.page_created(&mut next_page, 1);
+            document.add_page(mem::replace(&mut page, next_page));
+
+            self.items = remaining;
+            self.reset_root()?;
+        }
+    }
+    /// Index of the first item that ends below the max size.
+    ///
+    /// The first item is never returned so every page has at least one item
+    fn first_overflowing_item(&self) -> Option<usize> {
+        let max_height = self.max_size.height.0;
+        self.items
+            .iter()
+            .enumerate()
+            .skip(1)
+            .find(|(_, item)| {
+                let node = self.taffy_tree.get_final_layout(item.node_id);
+                node.location.y + node.size.height > max_height
+            })
+            .map(|(index, _)| index)
+    }
+    /// Removes the root node so the next layout only contains the current items
+    fn reset_root(&mut self) -> Result<(), LayoutError> {
+        if let Some(root) = self.root_node.take() {
+            self.taffy_tree.remove(root)?;
+        }
+        Ok(())
+    }
+    /// Renders the items using the last computed layout
+    fn render_items<L: LayerType>(
+        &mut self,
+        document: &PdfDocument,
+        page: &mut L,
+    ) -> Result<(), TuxPdfError> {
+        if let (Some(decoration), Some(root)) = (&self.decoration, self.root_node) {
+            self.render_decoration(decoration, root, page)?;
+        }
+        for item in mem::take(&mut self.items) {
+            if let Some(decoration) = &item.decoration {
+                self.render_decoration(decoration, item.node_id, page)?;
+            }
+            let node = self.taffy_tree.get_final_layout(item.node_id);
+
+            let content_y: Pt = node.content_box_y().into();
+            let content_x: Pt = node.content_box_x().into();
+            debug!(?content_x, ?content_y, "Content Box Position");
+            let content_y = content_y + node.content_box_height();
+
+            let mut position: PdfPosition = if let Some(position) = self.position {
+                PdfPosition {
+                    x: position.x + content_x,
+                    y: position.y - content_y,
+                }
+            } else {
+                PdfPosition {
+                    x: content_x,
+                    y: content_y,
+                }
+            };
+            let mut item: LayoutItem = item.item;
+            match &mut item {
+                LayoutItem::Text(text) => {
+                    // Taffy gives the position of the bottom of the text. The first line is drawn at the top
+                    position.y += wrap_text(text, document, node.content_box_width().into())?;
+                }
+                LayoutItem::Image(image) if image.fit != ImageFit::None => {
+                    image.set_fit_area(OutlineRect {
+                        position,
+                        size: Size {
+                            width: node.content_box_width().into(),
+                            height: node.content_box_height().into(),
+                        },
+                    });
+                }
+                LayoutItem::Divider(divider) => {
+                    divider.width = node.content_box_width().into();
+                }
+                _ => {}
+            }
+            item.set_position(position);
+            debug!(?item, ?position, "Rendering Item");
+
+            item.render(document, page)?;
+        }
+        Ok(())
+    }
+    /// Draws the decoration using the final layout of the node
+    fn render_decoration<L: LayerType>(
+        &self,
+        decoration: &TaffyNodeDecoration,
+        node_id: NodeId,
+        page: &mut L,
+    ) -> Result<(), TuxPdfError> {
+        let node = self.taffy_tree.get_final_layout(node_id);
+        let (offset, size) = decoration.rect(node);
+        let x: Pt = (node.location.x + offset.x).into();
+        let bottom: Pt = (node.location.y + offset.y + size.height).into();
+        let position = if let Some(position) = self.position {
+            PdfPosition {
+                x: position.x + x,
+                y: position.y - bottom,
+            }
+        } else {
+            PdfPosition { x, y: bottom }
+        };
+        if let Some(graphics) = decoration.graphics(position, size.into()) {
+            page.add_to_layer(graphics)?;
+        }
+        Ok(())
+    }
     pub fn draw_grid(
         &mut self,
         document: &PdfDocument,
         page: &mut PdfPage,
     ) -> Result<(), TuxPdfError> {
         self.calculate_sizes(document)?;
-        let root = self.compute_layout()?;
+        let root = self.compute_layout(document)?;
 
         let Self {
             taffy_tree, items, ..
@@ -218,12 +420,124 @@ impl PdfTaffyLayout {
                 ..Default::default()
             }),
             section_name: Some("LayoutOutline".to_string()),
+            ..Default::default()
         };
 
         page.add_to_layer(graphics_items)?;
         Ok(())
     }
 }
+/// Items that are sized by [measure_item] instead of a fixed size
+fn is_measured(item: &LayoutItem) -> bool {
+    match item {
+        LayoutItem::Text(_) => true,
+        LayoutItem::Image(image) => image.fit != ImageFit::None,
+        LayoutItem::Divider(_) => true,
+        _ => false,
+    }
+}
+/// Reports the size of items that depend on the space taffy gives them
+///
+/// Text wraps to the width it is given. Images with a fit keep their aspect ratio when one side is known.
+/// Dividers fill the width they are given.
+/// Other items use the fixed size set by [PdfTaffyLayout::calculate_sizes]
+fn measure_item(
+    item: &LayoutItem,
+    document: &PdfDocument,
+    known_dimensions: taffy::Size<Option<f32>>,
+    available_space: taffy::Size<AvailableSpace>,
+) -> Result<taffy::Size<f32>, TuxPdfError> {
+    if let taffy::Size {
+        width: Some(width),
+        height: Some(height),
+    } = known_dimensions
+    {
+        return Ok(taffy::Size { width, height });
+    }
+    let text = match item {
+        LayoutItem::Text(text) => text,
+        LayoutItem::Image(image) => {
+            let size = image.clone().calculate_size(document)?;
+            let (width, height) = (size.width.0, size.height.0);
+            return Ok(match known_dimensions {
+                taffy::Size {
+                    width: Some(known), ..
+                } => taffy::Size {
+                    width: known,
+                    height: height * known / width,
+                },
+                taffy::Size {
+                    height: Some(known),
+                    ..
+                } => taffy::Size {
+                    width: width * known / height,
+                    height: known,
+                },
+                _ => taffy::Size { width, height },
+            });
+        }
+        LayoutItem::Divider(divider) => {
+            let width = known_dimensions
+                .width
+                .unwrap_or(match available_space.width {
+                    AvailableSpace::Definite(width) => width,
+                    AvailableSpace::MinContent | AvailableSpace::MaxContent => divider.width.0,
+                });
+            return Ok(taffy::Size {
+                width,
+                height: known_dimensions.height.unwrap_or(divider.height().0),
+            });
+        }
+        _ => return Ok(known_dimensions.unwrap_or(taffy::Size::ZERO)),
+    };
+    let width = known_dimensions.width.or(match available_space.width {
+        AvailableSpace::Definite(width) => Some(width),
+        AvailableSpace::MinContent => Some(0f32),
+        AvailableSpace::MaxContent => None,
+    });
+    let mut text = text.clone();
+    if let Some(width) = width {
+        wrap_text(&mut text, document, width.into())?;
+    } else if let Some(max_width) = text.style.max_width {
+        wrap_text(&mut text, document, max_width)?;
+    }
+    let size = text.content.render_size(document, &text.style)?;
+    Ok(taffy::Size {
+        width: known_dimensions.width.unwrap_or(size.width.0),
+        height: known_dimensions.height.unwrap_or(size.height.0),
+    })
+}
+/// Wraps the text to the width. Words are never split so the text can be wider than `width`
+///
+/// Returns the height of every line after the first
+fn wrap_text(text: &mut TextBlock, document: &PdfDocument, width: Pt) -> Result<Pt, TuxPdfError> {
+    let mut width = width.max(longest_word(text, document)?);
+    if let Some(max_width) = text.style.max_width {
+        width = width.min(max_width);
+    }
+    text.content.apply_max_width(width, document, &text.style)?;
+    let Some(first_line) = text.content.first() else {
+        return Ok(Pt::default());
+    };
+    let first_line_height = TextBlockContent(vec![first_line.clone()])
+        .render_size(document, &text.style)?
+        .height;
+    let height = text.content.render_size(document, &text.style)?.height;
+    Ok(height - first_line_height)
+}
+/// Width of the widest word. The narrowest the text can be wrapped to
+fn longest_word(text: &TextBlock, document: &PdfDocument) -> Result<Pt, TuxPdfError> {
+    let mut longest = Pt::default();
+    let words = text
+        .content
+        .iter()
+        .flat_map(|line| line.items.iter())
+        .flat_map(|item| item.text.split_whitespace());
+    for word in words {
+        longest = longest.max(word.render_size(document, &text.style)?.width);
+    }
+    Ok(longest)
+}
 #[cfg(test)]
 mod tests {
     use std::fs::File;
@@ -231,14 +545,19 @@ mod tests {
     use taffy::prelude::*;
 
     use crate::{
-        document::owned_ttf_parser::OwnedPdfTtfFont,
-        graphics::{HasPosition, TextBlock, TextStyle},
-        layouts::LayoutItemType,
+        TuxPdfError,
+        document::{BuiltinFont, PdfDocument, owned_ttf_parser::OwnedPdfTtfFont},
+        graphics::{
+            HasPosition, Margin, PdfObject, TextBlock, TextStyle,
+            color::{BLACK_RGB, GRAY_RGB},
+        },
+        layouts::{LayoutError, LayoutItemType},
         page::{PdfPage, page_sizes::A4},
         tests::{create_test_document, fonts_dir, save_pdf_doc},
+        units::UnitType,
     };
 
-    use super::{PdfTaffyLayout, TaffyStyle};
+    use super::{PdfTaffyLayout, TaffyNodeDecoration, TaffyStyle};
 
     #[test]
     fn test_grid() -> anyhow::Result<()> {
@@ -397,4 +716,132 @@ mod tests {
         save_pdf_doc(doc, "test_footer_flex_box")?;
         Ok(())
     }
+
+    #[test]
+    fn test_text_wraps_to_column() -> anyhow::Result<()> {
+        crate::tests::init_logger();
+        let mut doc = create_test_document("test_text_wraps_to_column");
+        let roboto_loaded = File::open(fonts_dir().join("Roboto").join("Roboto-Regular.ttf"))?;
+        let roboto = OwnedPdfTtfFont::new_from_reader(roboto_loaded, 0)?;
+        let roboto = doc.font_map().register_external_font(roboto)?;
+        let style = TextStyle {
+            font_ref: roboto.clone(),
+            font_size: 12.0.into(),
+            ..Default::default()
+        };
+        let mut grid = PdfTaffyLayout::new(
+            TaffyStyle {
+                display: taffy::Display::Flex,
+                size: taffy::Size {
+                    width: length(300f32),
+                    height: auto(),
+                },
+                // Stretching would give both columns the height of the tallest one
+                align_items: Some(taffy::AlignItems::FlexStart),
+                ..Default::default()
+            },
+            A4,
+            A4,
+        )
+        .with_position(A4.top_left_point())
+        .with_decoration(TaffyNodeDecoration::default().with_border(BLACK_RGB, 1f32.pt()));
+        grid.add_item(
+            TextBlock::from("Short").with_style(style.clone()),
+            TaffyStyle {
+                flex_basis: length(150f32),
+                ..Default::default()
+            },
+        );
+        grid.add_decorated_item(
+            TextBlock::from(
+                "This sentence is far too long to fit on a single line of the narrow column",
+            )
+            .with_style(style.clone()),
+            TaffyStyle {
+                flex_basis: length(150f32),
+                ..Default::default()
+            },
+            TaffyNodeDecoration::default()
+                .with_background_color(GRAY_RGB)
+                .with_corner_radius(4f32.pt()),
+        );
+        let mut page = PdfPage::new_from_page_size(A4);
+        grid.draw_grid(&doc, &mut page)?;
+        let wrapped = grid.taffy_tree.layout(grid.items[1].node_id)?;
+        assert!(wrapped.size.width <= 150f32);
+        let short = grid.taffy_tree.layout(grid.items[0].node_id)?;
+        assert!(wrapped.size.height > short.size.height);
+        grid.render(&doc, &mut page)?;
+
+        doc.add_page(page);
+
+        save_pdf_doc(doc, "test_text_wraps_to_column")?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_column_across_pages() -> anyhow::Result<()> {
+        crate::tests::init_logger();
+        let mut doc = create_test_document("test_taffy_column_across_pages");
+        let roboto_loaded = File::open(fonts_dir().join("Roboto").join("Roboto-Regular.ttf"))?;
+        let roboto = OwnedPdfTtfFont::new_from_reader(roboto_loaded, 0)?;
+        let roboto = doc.font_map().register_external_font(roboto)?;
+        let mut grid = PdfTaffyLayout::new(
+            TaffyStyle {
+                display: taffy::Display::Flex,
+                flex_direction: taffy::FlexDirection::Column,
+                ..Default::default()
+            },
+            A4,
+            A4,
+        )
+        .with_position(A4.top_left_point());
+        for index in 0..100 {
+            grid.add_item(
+                TextBlock::from(format!("Line {index}")).with_style(TextStyle {
+                    font_ref: roboto.clone(),
+                    font_size: 24.0.into(),
+                    ..Default::default()
+                }),
+                TaffyStyle::default(),
+            );
+        }
+        let error = grid
+            .clone()
+            .render_pages(&mut doc, PdfPage::new_from_page_size(A4))
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            TuxPdfError::LayoutError(LayoutError::NoNewPageAllowed)
+        ));
+        grid.with_new_page(|_| Ok(PdfPage::new_from_page_size(A4)))
+            .render_pages(&mut doc, PdfPage::new_from_page_size(A4))?;
+
+        save_pdf_doc(doc, "test_taffy_column_across_pages")?;
+        Ok(())
+    }
+    #[test]
+    fn starts_in_content_area() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Taffy Content Area");
+        let helvetica = doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        let mut layout = PdfTaffyLayout::new(TaffyStyle::default(), A4, A4);
+        layout.add_item(
+            TextBlock::from("Inside the margin").with_style(TextStyle {
+                font_ref: helvetica,
+                font_size: 12.0.into(),
+                ..Default::default()
+            }),
+            TaffyStyle::default(),
+        );
+        let page = PdfPage::new_from_page_size(A4).with_margin(Margin::all(36f32.pt()));
+        let content_area = page.content_area();
+        layout.render_pages(&mut doc, page)?;
+
+        let [PdfObject::TextBlock(text)] = doc.pages()[0].contents.as_slice() else {
+            panic!("Expected a single text block");
+        };
+        assert_eq!(text.position.x, 36f32.pt());
+        assert!(content_area.contains(text.position));
+        Ok(())
+    }
 }
@@ -0,0 +1,225 @@
+//! Named colors and fonts shared by the layouts
+//!
+//! Build the styles of each layout from one [Theme] to restyle an entire document by swapping the theme.
+//!
+//! ```rust
+//! use tux_pdf::{
+//!     graphics::color::Rgb,
+//!     layouts::{Theme, list::ListStyles, table::TableStyles},
+//! };
+//! let brand = Theme::light()
+//!     .with_primary(Rgb::new_no_profile(0.55, 0.1, 0.35))
+//!     .with_color("warning", Rgb::new_no_profile(0.9, 0.6, 0.0));
+//!
+//! assert!(TableStyles::from_theme(&brand).striped_rows.is_some());
+//! let list = ListStyles::from_theme(&brand);
+//! assert_eq!(list.marker_color, brand.colors.accent);
+//! assert!(brand.color("warning").is_some());
+//! ```
+use std::collections::BTreeMap;
+
+use crate::{
+    document::{BuiltinFont, FontRef},
+    graphics::{
+        TextStyle,
+        color::{BLACK_RGB, Color, Rgb, WHITE_RGB},
+    },
+    units::{Pt, UnitType},
+};
+/// The semantic colors of a [Theme]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThemeColors {
+    /// Used for headings and the header of tables
+    pub primary: Color,
+    /// Text drawn on top of [Self::primary]
+    pub on_primary: Color,
+    /// Highlights such as list markers and calendar events
+    pub accent: Color,
+    pub text: Color,
+    /// The background of table rows and other filled areas
+    pub background: Color,
+    /// The background of every other table row
+    pub table_stripe: Color,
+    pub border: Color,
+}
+/// The fonts of a [Theme]
+///
+/// The fonts must be registered with the document. Even the builtin fonts
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThemeFonts {
+    pub body: FontRef,
+    pub heading: FontRef,
+    pub monospace: FontRef,
+}
+impl Default for ThemeFonts {
+    fn default() -> Self {
+        Self {
+            body: FontRef::Builtin(BuiltinFont::Helvetica),
+            heading: FontRef::Builtin(BuiltinFont::HelveticaBold),
+            monospace: FontRef::Builtin(BuiltinFont::Courier),
+        }
+    }
+}
+/// Colors, fonts and sizes that layouts are styled with
+///
+/// Use the `from_theme` functions of the layout styles such as [TableStyles::from_theme](super::table::TableStyles::from_theme)
+///
+/// Layouts do not paint the page. Fill the page with [ThemeColors::background] when using a dark theme
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub colors: ThemeColors,
+    /// Additional colors that can be looked up with [Theme::color]
+    pub named_colors: BTreeMap<String, Color>,
+    pub fonts: ThemeFonts,
+    pub font_size: Pt,
+    pub heading_font_size: Pt,
+    pub border_width: Pt,
+}
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+impl Theme {
+    /// Dark text on a white background
+    pub fn light() -> Self {
+        Self {
+            colors: ThemeColors {
+                primary: Rgb::new_no_profile(0.16, 0.32, 0.6).into(),
+                on_primary: WHITE_RGB,
+                accent: Rgb::new_no_profile(0.9, 0.45, 0.1).into(),
+                text: BLACK_RGB,
+                background: WHITE_RGB,
+                table_stripe: Rgb::new_no_profile(0.93, 0.93, 0.93).into(),
+                border: Rgb::new_no_profile(0.7, 0.7, 0.7).into(),
+            },
+            named_colors: BTreeMap::new(),
+            fonts: ThemeFonts::default(),
+            font_size: 12f32.pt(),
+            heading_font_size: 16f32.pt(),
+            border_width: 1f32.pt(),
+        }
+    }
+    /// Light text on a dark background
+    pub fn dark() -> Self {
+        Self {
+            colors: ThemeColors {
+                primary: Rgb::new_no_profile(0.3, 0.5, 0.85).into(),
+                on_primary: WHITE_RGB,
+                accent: Rgb::new_no_profile(1.0, 0.65, 0.3).into(),
+                text: Rgb::new_no_profile(0.92, 0.92, 0.92).into(),
+                background: Rgb::new_no_profile(0.12, 0.12, 0.14).into(),
+                table_stripe: Rgb::new_no_profile(0.19, 0.19, 0.22).into(),
+                border: Rgb::new_no_profile(0.4, 0.4, 0.45).into(),
+            },
+            ..Self::light()
+        }
+    }
+    pub fn with_primary(mut self, primary: impl Into<Color>) -> Self {
+        self.colors.primary = primary.into();
+        self
+    }
+    pub fn with_accent(mut self, accent: impl Into<Color>) -> Self {
+        self.colors.accent = accent.into();
+        self
+    }
+    pub fn with_fonts(mut self, fonts: ThemeFonts) -> Self {
+        self.fonts = fonts;
+        self
+    }
+    /// Adds a color that can be looked up by name. Replaces a color with the same name
+    pub fn with_color(mut self, name: impl Into<String>, color: impl Into<Color>) -> Self {
+        self.named_colors.insert(name.into(), color.into());
+        self
+    }
+    /// Looks up a color by name
+    ///
+    /// The semantic colors are named `primary`, `on-primary`, `accent`, `text`, `background`, `table-stripe` and `border`.
+    /// Any other name is looked up in [Self::named_colors]
+    pub fn color(&self, name: &str) -> Option<&Color> {
+        let colors = &self.colors;
+        match name {
+            "primary" => Some(&colors.primary),
+            "on-primary" => Some(&colors.on_primary),
+            "accent" => Some(&colors.accent),
+            "text" => Some(&colors.text),
+            "background" => Some(&colors.background),
+            "table-stripe" => Some(&colors.table_stripe),
+            "border" => Some(&colors.border),
+            other => self.named_colors.get(other),
+        }
+    }
+    /// The style for body text
+    pub fn text_style(&self) -> TextStyle {
+        TextStyle {
+            font_size: self.font_size,
+            font_ref: self.fonts.body.clone(),
+            fill_color: Some(self.colors.text.clone()),
+            ..Default::default()
+        }
+    }
+    /// The style for headings. Drawn in the primary color
+    pub fn heading_style(&self) -> TextStyle {
+        TextStyle {
+            font_size: self.heading_font_size,
+            font_ref: self.fonts.heading.clone(),
+            fill_color: Some(self.colors.primary.clone()),
+            ..Default::default()
+        }
+    }
+    /// The style for code and other fixed width text
+    pub fn monospace_style(&self) -> TextStyle {
+        TextStyle {
+            font_ref: self.fonts.monospace.clone(),
+            ..self.text_style()
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::Theme;
+    use crate::{
+        graphics::color::WHITE_RGB,
+        layouts::{
+            calendar::CalendarStyles, key_value::KeyValueStyles, list::ListStyles,
+            table::TableStyles,
+        },
+    };
+
+    #[test]
+    fn named_colors() {
+        let theme = Theme::light().with_color("warning", WHITE_RGB);
+        assert_eq!(
+            theme.color("table-stripe"),
+            Some(&theme.colors.table_stripe)
+        );
+        assert_eq!(theme.color("warning"), Some(&WHITE_RGB));
+        assert_eq!(theme.color("missing"), None);
+    }
+    #[test]
+    fn layouts_use_theme() {
+        let theme = Theme::dark();
+        let table = TableStyles::from_theme(&theme);
+        assert_eq!(
+            table.striped_rows,
+            Some([
+                theme.colors.background.clone(),
+                theme.colors.table_stripe.clone()
+            ])
+        );
+        assert_eq!(
+            table
+                .header_styles
+                .and_then(|header| header.background_color),
+            Some(theme.colors.primary.clone())
+        );
+        assert_eq!(table.text_styles, theme.text_style());
+
+        let list = ListStyles::from_theme(&theme);
+        assert_eq!(list.marker_color, theme.colors.accent);
+        let key_value = KeyValueStyles::from_theme(&theme);
+        assert_eq!(key_value.value_style, theme.text_style());
+        let calendar = CalendarStyles::from_theme(&theme);
+        assert_eq!(calendar.border_color, theme.colors.border);
+    }
+}
@@ -1,11 +1,15 @@
-use document::{FontRef, ResourceNotRegistered};
+use document::ResourceNotRegistered;
 pub mod layouts;
 use layouts::{LayoutError, table::TableError};
 use thiserror::Error;
+use tux_pdf_low::types::PdfVersion;
 
 pub mod document;
+pub mod error;
 pub mod graphics;
 pub mod page;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
 pub mod time_impl;
 pub mod units;
 pub(crate) mod utils;
@@ -15,8 +19,15 @@ pub enum TuxPdfError {
     ResourceNotRegistered(#[from] ResourceNotRegistered),
     #[error("Invalid Reference. Expected reference to {0}")]
     InvalidReference(&'static str),
+    #[error("Invalid XObject: {0}")]
+    InvalidXObject(String),
     #[error("No pages created")]
     NoPagesCreated,
+    #[error("Page {index} does not exist. The document has {number_of_pages} pages")]
+    PageIndexOutOfBounds {
+        index: usize,
+        number_of_pages: usize,
+    },
     #[error("Invalid object id: {0}")]
     InvalidObjectId(String),
     #[error("ObjectID already exists: {0}")]
@@ -32,12 +43,36 @@ pub enum TuxPdfError {
     #[error(transparent)]
     LayoutError(#[from] LayoutError),
     #[error(transparent)]
+    PageBoxError(#[from] page::PageBoxError),
+    #[error(transparent)]
+    ColorParseError(#[from] graphics::color::ColorParseError),
+    #[error(transparent)]
+    DateOutOfRange(#[from] time_impl::DateOutOfRange),
+    #[cfg(feature = "csv")]
+    #[error(transparent)]
+    CsvError(#[from] csv::Error),
+    #[error(transparent)]
     InternalError(#[from] tux_pdf_low::LowTuxPdfError),
-}
-impl From<FontRef> for TuxPdfError {
-    fn from(font_ref: FontRef) -> Self {
-        ResourceNotRegistered::from(font_ref).into()
-    }
+    #[error("The merge record has no field named {0}")]
+    MissingMergeField(String),
+    #[error("Type 3 fonts can have at most 256 glyphs. The font has {0}")]
+    TooManyType3Glyphs(usize),
+    #[error("PDF version {0} does not exist")]
+    UnsupportedPdfVersion(PdfVersion),
+    #[error("{feature} requires PDF {required} or newer. The document is PDF {version}")]
+    NotAllowedInPdfVersion {
+        feature: &'static str,
+        required: PdfVersion,
+        version: PdfVersion,
+    },
+    /// Another error with information about where it happened. See [error::ErrorContext]
+    ///
+    /// Only the context is displayed. The wrapped error is returned by [std::error::Error::source]
+    #[error("{context}")]
+    WithContext {
+        context: error::ErrorContext,
+        source: Box<TuxPdfError>,
+    },
 }
 impl From<TableError> for TuxPdfError {
     fn from(table_error: TableError) -> Self {
@@ -56,8 +91,9 @@ pub(crate) mod tests {
         let mut doc = crate::document::PdfDocument::new(name);
         doc.metadata.info.author = Some("tux-pdf tests".to_string());
         doc.metadata.info.creator = Some("tux-pdf tests".to_string());
-        doc.metadata.info.creation_date =
-            Some(time::OffsetDateTime::now_local().unwrap_or(time::OffsetDateTime::now_utc()));
+        doc.metadata.info.creation_date = Some(crate::time_impl::PdfDateTime::from(
+            std::time::SystemTime::now(),
+        ));
         doc
     }
 
@@ -0,0 +1,167 @@
+use crate::{
+    document::XObjectId,
+    graphics::{
+        GraphicStyles, GraphicsGroup, PdfObject,
+        color::Color,
+        image::{ImageFit, PdfImage},
+        primitives::PaintMode,
+        shapes::{OutlineRect, PaintedRect},
+    },
+};
+
+use super::PdfPage;
+
+/// Drawn beneath all of the content and layers of a page. Covers the whole media box
+#[derive(Debug, Clone, PartialEq)]
+pub enum PageBackground {
+    Color(Color),
+    /// An image registered with the document. [ImageFit::None] places the image at the bottom left corner of the page
+    Image {
+        image: XObjectId,
+        fit: ImageFit,
+    },
+}
+impl PageBackground {
+    /// The object that draws the background over the media box
+    pub(crate) fn into_object(self, media_box: OutlineRect) -> PdfObject {
+        match self {
+            PageBackground::Color(color) => GraphicsGroup {
+                styles: Some(GraphicStyles {
+                    fill_color: Some(color),
+                    ..Default::default()
+                }),
+                items: vec![
+                    PaintedRect {
+                        position: media_box.position,
+                        size: media_box.size,
+                        paint_mode: PaintMode::Fill,
+                        ..Default::default()
+                    }
+                    .into(),
+                ],
+                ..Default::default()
+            }
+            .into(),
+            PageBackground::Image { image, fit } => {
+                let mut image = PdfImage::new(image)
+                    .with_position(media_box.position)
+                    .with_fit(fit);
+                image.set_fit_area(media_box);
+                image.into()
+            }
+        }
+    }
+}
+impl From<Color> for PageBackground {
+    fn from(color: Color) -> Self {
+        PageBackground::Color(color)
+    }
+}
+impl PdfPage {
+    /// Fills the whole page with the color beneath everything else on the page
+    pub fn set_background(&mut self, color: Color) {
+        self.background = Some(PageBackground::Color(color));
+    }
+    /// Draws the image beneath everything else on the page. [ImageFit::Cover] or [ImageFit::Stretch] fill the whole page
+    pub fn set_background_image(&mut self, image: XObjectId, fit: ImageFit) {
+        self.background = Some(PageBackground::Image { image, fit });
+    }
+    pub fn with_background(mut self, background: impl Into<PageBackground>) -> Self {
+        self.background = Some(background.into());
+        self
+    }
+}
+#[cfg(test)]
+mod tests {
+    use image::DynamicImage;
+    use tux_pdf_low::types::Object;
+
+    use super::PageBackground;
+    use crate::{
+        document::{PdfDocument, PdfXObjectImage},
+        graphics::{
+            GraphicStyles, GraphicsGroup, LayerType, PdfObject,
+            color::{BLUE_RGB, Color, GREEN_RGB, RED_RGB},
+            image::ImageFit,
+            shapes::PaintedRect,
+        },
+        page::{PdfPage, page_sizes::A4},
+        units::UnitType,
+    };
+    fn filled_rect(color: Color) -> GraphicsGroup {
+        GraphicsGroup {
+            styles: Some(GraphicStyles {
+                fill_color: Some(color),
+                ..Default::default()
+            }),
+            items: vec![PaintedRect::new(0f32.pt(), 0f32.pt(), 10f32.pt(), 10f32.pt()).into()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn background_is_written_first() -> anyhow::Result<()> {
+        let mut document = PdfDocument::new("Background");
+        let layer_id = document.create_layer("Layer");
+        document
+            .resources
+            .layers
+            .get_layer_mut(&layer_id)
+            .unwrap()
+            .add_to_layer(filled_rect(BLUE_RGB))?;
+        let mut page = PdfPage::new_from_page_size(A4);
+        page.add_to_layer(filled_rect(GREEN_RGB))?;
+        page.add_layer(layer_id);
+        page.set_background(RED_RGB);
+        document.add_page(page);
+
+        let writer = document.write_into_pdf_document_writer()?;
+        let page = writer
+            .objects()
+            .filter_map(|(_, object)| object.as_dictionary())
+            .find(|dictionary| dictionary.get("Type") == Some(&Object::name("Page")))
+            .expect("A page is written");
+        let Some(Object::Array(contents)) = page.get("Contents") else {
+            panic!("The page has no contents");
+        };
+        let mut content = Vec::new();
+        for stream in contents {
+            let Object::Reference(id) = stream else {
+                panic!("Contents are not references");
+            };
+            let stream = writer
+                .get_object(id)
+                .and_then(Object::as_stream)
+                .expect("Content stream");
+            content.extend_from_slice(&stream.content);
+        }
+        let content = String::from_utf8(content)?;
+        let fills: Vec<_> = ["1 0 0 rg", "0 1 0 rg", "0 0 1 rg"]
+            .into_iter()
+            .map(|fill| content.find(fill).expect("The fill color is written"))
+            .collect();
+        // The background, then the page content and then the layers
+        assert!(fills.is_sorted(), "{content}");
+        Ok(())
+    }
+    #[test]
+    fn image_background_fills_page() -> anyhow::Result<()> {
+        let mut document = PdfDocument::new("Background");
+        let image = PdfXObjectImage::load_from_dynamic_image(DynamicImage::new_rgb8(4, 4))?;
+        let image = document.add_xobject(image);
+        let mut page = PdfPage::new_from_page_size(A4);
+        page.set_background_image(image.clone(), ImageFit::Cover);
+        document.add_page(page);
+
+        let background = PageBackground::Image {
+            image,
+            fit: ImageFit::Stretch,
+        };
+        let PdfObject::Image(image) = background.into_object(A4.into()) else {
+            panic!("The image background is not an image");
+        };
+        assert_eq!(image.fit_area, Some(A4.into()));
+        document.write_into_pdf_document_writer()?;
+        Ok(())
+    }
+}
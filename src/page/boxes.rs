@@ -0,0 +1,325 @@
+use std::fmt::Display;
+
+use thiserror::Error;
+
+use crate::{
+    graphics::{Margin, PdfPosition, shapes::OutlineRect, size::Size},
+    units::{Length, Pt},
+};
+
+use super::PdfPage;
+/// Differences smaller than this are ignored. Boxes are written rounded to whole points
+const BOX_TOLERANCE: Pt = Pt(0.01);
+
+/// The boxes of a page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PageBoxKind {
+    Media,
+    /// The visible area when displayed or printed
+    Crop,
+    /// The area including the bleed that is clipped when producing print output
+    Bleed,
+    /// The size of the finished page after trimming
+    Trim,
+    /// The meaningful content of the page
+    Art,
+}
+impl Display for PageBoxKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            PageBoxKind::Media => "MediaBox",
+            PageBoxKind::Crop => "CropBox",
+            PageBoxKind::Bleed => "BleedBox",
+            PageBoxKind::Trim => "TrimBox",
+            PageBoxKind::Art => "ArtBox",
+        };
+        f.write_str(name)
+    }
+}
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum PageBoxError {
+    #[error("The {0} has no width or height")]
+    Empty(PageBoxKind),
+    #[error("The {inner} must be inside of the {outer}")]
+    NotContained {
+        inner: PageBoxKind,
+        outer: PageBoxKind,
+    },
+}
+/// How a page box is placed on the page
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PageBoxSpec {
+    /// Distance from each edge of the media box. Percentages are relative to the media box size
+    Inset(Margin<Length>),
+    /// An explicit rectangle. Percentages are relative to the media box size
+    Rect(OutlineRect<Length>),
+}
+impl PageBoxSpec {
+    /// The same distance from every edge of the media box
+    pub fn inset(inset: impl Into<Length>) -> Self {
+        PageBoxSpec::Inset(Margin::all(inset.into()))
+    }
+    fn resolve(self, media_box: OutlineRect) -> OutlineRect {
+        let size = media_box.size;
+        match self {
+            PageBoxSpec::Inset(margin) => {
+                let (left, right, top, bottom): (Length, Length, Length, Length) = margin.into();
+                let (left, right) = (left.resolve(size.width), right.resolve(size.width));
+                let (top, bottom) = (top.resolve(size.height), bottom.resolve(size.height));
+                OutlineRect {
+                    position: PdfPosition::new(
+                        media_box.position.x + left,
+                        media_box.position.y + bottom,
+                    ),
+                    size: Size::new(size.width - left - right, size.height - top - bottom),
+                }
+            }
+            PageBoxSpec::Rect(rect) => OutlineRect {
+                position: rect.position.resolve(size),
+                size: rect.size.resolve(size),
+            },
+        }
+    }
+}
+impl From<Margin<Length>> for PageBoxSpec {
+    fn from(margin: Margin<Length>) -> Self {
+        PageBoxSpec::Inset(margin)
+    }
+}
+impl From<OutlineRect<Length>> for PageBoxSpec {
+    fn from(rect: OutlineRect<Length>) -> Self {
+        PageBoxSpec::Rect(rect)
+    }
+}
+impl From<OutlineRect> for PageBoxSpec {
+    fn from(rect: OutlineRect) -> Self {
+        PageBoxSpec::Rect(OutlineRect {
+            position: PdfPosition::from_lengths(rect.position.x, rect.position.y),
+            size: Size::from_lengths(rect.size.width, rect.size.height),
+        })
+    }
+}
+/// Builds the page boxes of a page and checks they are nested the way print tools expect
+///
+/// Every box must be inside of the media box and the crop box. The trim and art boxes must be inside of the bleed box.
+///
+/// ```rust
+/// # fn main() -> Result<(), tux_pdf::TuxPdfError> {
+/// use tux_pdf::{
+///     graphics::size::Size,
+///     page::{PageBoxSpec, PageBoxes, PdfPage, page_sizes::A4},
+///     units::UnitType,
+/// };
+/// // A4 with 3mm of bleed on every side
+/// let media = Size::new(A4.width + 6f32.mm().pt(), A4.height + 6f32.mm().pt());
+/// let page = PageBoxes::new(media)
+///     .with_bleed_box(PageBoxSpec::inset(0f32.pt()))
+///     .with_trim_box(PageBoxSpec::inset(3f32.mm()))
+///     .apply(PdfPage::new_from_page_size(media))?;
+/// assert!(page.trim_box.is_some());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageBoxes {
+    pub media_box: OutlineRect,
+    pub crop_box: Option<PageBoxSpec>,
+    pub bleed_box: Option<PageBoxSpec>,
+    pub trim_box: Option<PageBoxSpec>,
+    pub art_box: Option<PageBoxSpec>,
+}
+impl PageBoxes {
+    /// Boxes for a media box of the size starting at the origin
+    pub fn new(media_size: Size) -> Self {
+        Self::from_media_box(media_size.into())
+    }
+    pub fn from_media_box(media_box: OutlineRect) -> Self {
+        Self {
+            media_box,
+            crop_box: None,
+            bleed_box: None,
+            trim_box: None,
+            art_box: None,
+        }
+    }
+    pub fn with_crop_box(mut self, spec: impl Into<PageBoxSpec>) -> Self {
+        self.crop_box = Some(spec.into());
+        self
+    }
+    pub fn with_bleed_box(mut self, spec: impl Into<PageBoxSpec>) -> Self {
+        self.bleed_box = Some(spec.into());
+        self
+    }
+    pub fn with_trim_box(mut self, spec: impl Into<PageBoxSpec>) -> Self {
+        self.trim_box = Some(spec.into());
+        self
+    }
+    pub fn with_art_box(mut self, spec: impl Into<PageBoxSpec>) -> Self {
+        self.art_box = Some(spec.into());
+        self
+    }
+    /// Resolves the boxes to points and validates them
+    pub fn build(&self) -> Result<ResolvedPageBoxes, PageBoxError> {
+        let resolve = |spec: Option<PageBoxSpec>| spec.map(|spec| spec.resolve(self.media_box));
+        let boxes = ResolvedPageBoxes {
+            media_box: self.media_box,
+            crop_box: resolve(self.crop_box),
+            bleed_box: resolve(self.bleed_box),
+            trim_box: resolve(self.trim_box),
+            art_box: resolve(self.art_box),
+        };
+        boxes.validate()?;
+        Ok(boxes)
+    }
+    /// Replaces the boxes of the page. The page keeps its content
+    pub fn apply(&self, page: PdfPage) -> Result<PdfPage, PageBoxError> {
+        Ok(self.build()?.apply(page))
+    }
+}
+/// Page boxes in points. Created by [PageBoxes::build]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResolvedPageBoxes {
+    pub media_box: OutlineRect,
+    pub crop_box: Option<OutlineRect>,
+    pub bleed_box: Option<OutlineRect>,
+    pub trim_box: Option<OutlineRect>,
+    pub art_box: Option<OutlineRect>,
+}
+impl ResolvedPageBoxes {
+    /// Checks that every box has a size and is nested within the boxes around it
+    pub fn validate(&self) -> Result<(), PageBoxError> {
+        let boxes = [
+            (PageBoxKind::Media, Some(self.media_box)),
+            (PageBoxKind::Crop, self.crop_box),
+            (PageBoxKind::Bleed, self.bleed_box),
+            (PageBoxKind::Trim, self.trim_box),
+            (PageBoxKind::Art, self.art_box),
+        ];
+        for (kind, rect) in boxes {
+            if let Some(rect) = rect
+                && (rect.size.width <= BOX_TOLERANCE || rect.size.height <= BOX_TOLERANCE)
+            {
+                return Err(PageBoxError::Empty(kind));
+            }
+        }
+        let outer_boxes = [
+            (PageBoxKind::Crop, &[PageBoxKind::Media][..]),
+            (PageBoxKind::Bleed, &[PageBoxKind::Media, PageBoxKind::Crop]),
+            (
+                PageBoxKind::Trim,
+                &[PageBoxKind::Media, PageBoxKind::Crop, PageBoxKind::Bleed],
+            ),
+            (
+                PageBoxKind::Art,
+                &[PageBoxKind::Media, PageBoxKind::Crop, PageBoxKind::Bleed],
+            ),
+        ];
+        for (inner, outers) in outer_boxes {
+            let Some(inner_rect) = self.get(inner) else {
+                continue;
+            };
+            for &outer in outers {
+                if let Some(outer_rect) = self.get(outer)
+                    && !contains(&outer_rect, &inner_rect)
+                {
+                    return Err(PageBoxError::NotContained { inner, outer });
+                }
+            }
+        }
+        Ok(())
+    }
+    pub fn get(&self, kind: PageBoxKind) -> Option<OutlineRect> {
+        match kind {
+            PageBoxKind::Media => Some(self.media_box),
+            PageBoxKind::Crop => self.crop_box,
+            PageBoxKind::Bleed => self.bleed_box,
+            PageBoxKind::Trim => self.trim_box,
+            PageBoxKind::Art => self.art_box,
+        }
+    }
+    pub fn apply(self, mut page: PdfPage) -> PdfPage {
+        page.media_box = self.media_box;
+        page.crop_box = self.crop_box;
+        page.bleed_box = self.bleed_box;
+        page.trim_box = self.trim_box;
+        page.art_box = self.art_box;
+        page
+    }
+}
+fn contains(outer: &OutlineRect, inner: &OutlineRect) -> bool {
+    let (outer_max, inner_max) = (outer.upper_right(), inner.upper_right());
+    inner.position.x + BOX_TOLERANCE >= outer.position.x
+        && inner.position.y + BOX_TOLERANCE >= outer.position.y
+        && inner_max.x <= outer_max.x + BOX_TOLERANCE
+        && inner_max.y <= outer_max.y + BOX_TOLERANCE
+}
+impl PdfPage {
+    /// The boxes of the page. Use [ResolvedPageBoxes::validate] to check boxes set with [PdfPage::with_crop_box] and the other box methods
+    pub fn page_boxes(&self) -> ResolvedPageBoxes {
+        ResolvedPageBoxes {
+            media_box: self.media_box,
+            crop_box: self.crop_box,
+            bleed_box: self.bleed_box,
+            trim_box: self.trim_box,
+            art_box: self.art_box,
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::{PageBoxError, PageBoxKind, PageBoxSpec, PageBoxes};
+    use crate::{
+        graphics::{Margin, PdfPosition, shapes::OutlineRect, size::Size},
+        page::page_sizes::A4,
+        units::{Length, Percentage, UnitType},
+    };
+
+    #[test]
+    fn resolve_insets() -> anyhow::Result<()> {
+        let boxes = PageBoxes::new(A4)
+            .with_bleed_box(PageBoxSpec::inset(1f32.mm()))
+            .with_trim_box(PageBoxSpec::inset(3f32.mm()))
+            .with_art_box(Margin::<Length>::all(Percentage(0.1).into()))
+            .build()?;
+        let trim = boxes.trim_box.expect("Trim box is set");
+        assert_eq!(
+            trim.position,
+            PdfPosition::new(3f32.mm().pt(), 3f32.mm().pt())
+        );
+        assert_eq!(trim.size.width, A4.width - 6f32.mm().pt());
+        let art = boxes.art_box.expect("Art box is set");
+        assert_eq!(art.size.height, A4.height * 0.8f32);
+        Ok(())
+    }
+    #[test]
+    fn invalid_nesting() {
+        let outside = OutlineRect {
+            position: PdfPosition::new(-5f32.pt(), 0f32.pt()),
+            size: Size::new(100f32.pt(), 100f32.pt()),
+        };
+        assert_eq!(
+            PageBoxes::new(A4).with_crop_box(outside).build(),
+            Err(PageBoxError::NotContained {
+                inner: PageBoxKind::Crop,
+                outer: PageBoxKind::Media
+            })
+        );
+        // The trim box must be inside of the bleed box
+        assert_eq!(
+            PageBoxes::new(A4)
+                .with_bleed_box(PageBoxSpec::inset(20f32.pt()))
+                .with_trim_box(PageBoxSpec::inset(10f32.pt()))
+                .build(),
+            Err(PageBoxError::NotContained {
+                inner: PageBoxKind::Trim,
+                outer: PageBoxKind::Bleed
+            })
+        );
+        assert_eq!(
+            PageBoxes::new(A4)
+                .with_crop_box(PageBoxSpec::inset(Percentage(0.5)))
+                .build(),
+            Err(PageBoxError::Empty(PageBoxKind::Crop))
+        );
+    }
+}
@@ -0,0 +1,253 @@
+use crate::{
+    graphics::{
+        GraphicItems, GraphicStyles, GraphicsGroup, PdfObject, PdfPosition, TextBlock,
+        TextBlockContent, TextStyle,
+        color::Color,
+        primitives::{PaintMode, PathBuilder, StraightLine},
+        shapes::PaintedRect,
+        size::Size,
+    },
+    units::Pt,
+};
+
+use super::PdfPage;
+
+/// The most recently drawn item. Kept until the next item is drawn so it can be styled
+#[derive(Debug)]
+enum CanvasItem {
+    Graphic {
+        item: GraphicItems,
+        styles: GraphicStyles,
+    },
+    Text(TextBlock),
+}
+/// Draws onto a page with chained calls
+///
+/// Style calls such as [Canvas::fill] apply to the most recently drawn item.
+/// Items are added to the page when the next item is drawn or the canvas is dropped.
+///
+/// ```rust
+/// use tux_pdf::{
+///     graphics::{TextStyle, color::{BLACK_RGB, RED_RGB}, size::Size},
+///     page::{PdfPage, page_sizes::A4},
+///     units::UnitType,
+/// };
+///
+/// let mut page = PdfPage::new_from_page_size(A4);
+/// page.canvas()
+///     .rect((50f32.pt(), 50f32.pt()), Size::new(100f32.pt(), 20f32.pt()))
+///     .fill(RED_RGB)
+///     .text("Hi", TextStyle::default())
+///     .at(55f32.pt(), 55f32.pt())
+///     .line((50f32.pt(), 40f32.pt()), (150f32.pt(), 40f32.pt()))
+///     .stroke(BLACK_RGB);
+/// assert_eq!(page.contents.len(), 3);
+/// ```
+#[derive(Debug)]
+pub struct Canvas<'page> {
+    page: &'page mut PdfPage,
+    current: Option<CanvasItem>,
+}
+impl PdfPage {
+    /// Starts drawing onto the page. See [Canvas]
+    pub fn canvas(&mut self) -> Canvas<'_> {
+        Canvas {
+            page: self,
+            current: None,
+        }
+    }
+}
+impl Canvas<'_> {
+    fn push(mut self, item: CanvasItem) -> Self {
+        self.flush();
+        self.current = Some(item);
+        self
+    }
+    fn push_graphic(self, item: impl Into<GraphicItems>) -> Self {
+        self.push(CanvasItem::Graphic {
+            item: item.into(),
+            styles: GraphicStyles::default(),
+        })
+    }
+    /// Adds the current item to the page
+    fn flush(&mut self) {
+        let Some(item) = self.current.take() else {
+            return;
+        };
+        let object: PdfObject = match item {
+            CanvasItem::Graphic { item, styles } if styles == GraphicStyles::default() => {
+                item.into()
+            }
+            CanvasItem::Graphic { item, styles } => GraphicsGroup {
+                styles: Some(styles),
+                items: vec![item],
+                ..Default::default()
+            }
+            .into(),
+            CanvasItem::Text(text) => text.into(),
+        };
+        self.page.contents.push(object);
+    }
+    /// Draws a rectangle with its lower left corner at `position`
+    ///
+    /// Filled with the current fill color unless [Canvas::fill] or [Canvas::stroke] is called
+    pub fn rect(self, position: impl Into<PdfPosition>, size: impl Into<Size>) -> Self {
+        let position = position.into();
+        let size = size.into();
+        self.push_graphic(PaintedRect {
+            position,
+            size,
+            ..Default::default()
+        })
+    }
+    /// Draws a straight line between the two points
+    pub fn line(self, start: impl Into<PdfPosition>, end: impl Into<PdfPosition>) -> Self {
+        self.push_graphic(StraightLine {
+            start: start.into(),
+            points: vec![end.into()],
+            is_closed: false,
+        })
+    }
+    /// Draws a circle around `center`
+    ///
+    /// Stroked with the current stroke color unless [Canvas::fill] or [Canvas::stroke] is called
+    pub fn circle(self, center: impl Into<PdfPosition>, radius: Pt) -> Self {
+        self.push_graphic(
+            PathBuilder::new()
+                .arc_to(center, radius, 0f32, 360f32)
+                .close()
+                .with_mode(PaintMode::Stroke)
+                .build(),
+        )
+    }
+    /// Draws text at the origin of the page. Use [Canvas::at] to move it
+    pub fn text(self, content: impl Into<TextBlockContent>, style: TextStyle) -> Self {
+        self.push(CanvasItem::Text(TextBlock {
+            content: content.into(),
+            style,
+            ..Default::default()
+        }))
+    }
+    /// Moves the current text or rectangle so it starts at `x`,`y`
+    ///
+    /// Lines and circles are drawn at the points they were given and are not moved
+    pub fn at(mut self, x: Pt, y: Pt) -> Self {
+        let position = PdfPosition::new(x, y);
+        match &mut self.current {
+            Some(CanvasItem::Text(text)) => text.position = position,
+            Some(CanvasItem::Graphic {
+                item: GraphicItems::Rectangle(rect),
+                ..
+            }) => rect.position = position,
+            _ => {}
+        }
+        self
+    }
+    /// Fills the current item with the color
+    ///
+    /// Lines can not be filled and are not changed
+    pub fn fill(mut self, color: impl Into<Color>) -> Self {
+        let color = color.into();
+        match &mut self.current {
+            Some(CanvasItem::Text(text)) => text.style.fill_color = Some(color),
+            Some(CanvasItem::Graphic { item, styles }) => {
+                let has_outline = styles.outline_color.is_some();
+                let mode = if has_outline {
+                    PaintMode::FillStroke
+                } else {
+                    PaintMode::Fill
+                };
+                match item {
+                    GraphicItems::Rectangle(rect) => rect.paint_mode = mode,
+                    GraphicItems::Path(path) => path.paint_mode = mode,
+                    _ => return self,
+                }
+                styles.fill_color = Some(color);
+            }
+            None => {}
+        }
+        self
+    }
+    /// Outlines the current item with the color
+    pub fn stroke(mut self, color: impl Into<Color>) -> Self {
+        let color = color.into();
+        match &mut self.current {
+            Some(CanvasItem::Text(text)) => text.style.outline_color = Some(color),
+            Some(CanvasItem::Graphic { item, styles }) => {
+                let has_fill = styles.fill_color.is_some();
+                let mode = if has_fill {
+                    PaintMode::FillStroke
+                } else {
+                    PaintMode::Stroke
+                };
+                match item {
+                    GraphicItems::Rectangle(rect) => rect.paint_mode = mode,
+                    GraphicItems::Path(path) => path.paint_mode = mode,
+                    _ => {}
+                }
+                styles.outline_color = Some(color);
+            }
+            None => {}
+        }
+        self
+    }
+    /// Sets the width of the outline of the current item
+    pub fn line_width(mut self, width: Pt) -> Self {
+        if let Some(CanvasItem::Graphic { styles, .. }) = &mut self.current {
+            styles.line_width = Some(width);
+        }
+        self
+    }
+    /// Adds any object to the page
+    pub fn object(mut self, object: impl Into<PdfObject>) -> Self {
+        self.flush();
+        self.page.contents.push(object.into());
+        self
+    }
+}
+impl Drop for Canvas<'_> {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::{
+        graphics::{
+            GraphicItems, PdfObject, PdfPosition, TextStyle,
+            color::{BLUE_RGB, RED_RGB},
+            primitives::PaintMode,
+            size::Size,
+        },
+        page::{PdfPage, page_sizes::A4},
+        units::UnitType,
+    };
+
+    #[test]
+    fn chained_drawing() {
+        let mut page = PdfPage::new_from_page_size(A4);
+        page.canvas()
+            .rect((0f32.pt(), 0f32.pt()), Size::new(10f32.pt(), 10f32.pt()))
+            .fill(RED_RGB)
+            .stroke(BLUE_RGB)
+            .text("Hi", TextStyle::default())
+            .at(20f32.pt(), 30f32.pt())
+            .line((0f32.pt(), 0f32.pt()), (10f32.pt(), 10f32.pt()));
+        assert_eq!(page.contents.len(), 3);
+        let PdfObject::Graphics(GraphicItems::Group(group)) = &page.contents[0] else {
+            panic!("Expected styled group");
+        };
+        let GraphicItems::Rectangle(rect) = &group.items[0] else {
+            panic!("Expected rectangle");
+        };
+        assert_eq!(rect.paint_mode, PaintMode::FillStroke);
+        let PdfObject::TextBlock(text) = &page.contents[1] else {
+            panic!("Expected text");
+        };
+        assert_eq!(text.position, PdfPosition::new(20f32.pt(), 30f32.pt()));
+        assert!(matches!(
+            page.contents[2],
+            PdfObject::Graphics(GraphicItems::StraightLine(_))
+        ));
+    }
+}
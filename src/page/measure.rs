@@ -0,0 +1,327 @@
+//! Viewports that give regions of a page a real world scale. Such as floor plans and maps
+//!
+//! Viewers use the viewport under the cursor to show distances and areas in the unit of the drawing
+use tux_pdf_low::{
+    dictionary,
+    types::{Dictionary, Object},
+};
+
+use super::PdfPage;
+use crate::graphics::{PdfPosition, shapes::OutlineRect};
+
+/// Meters in one point on the page
+const METERS_PER_POINT: f32 = 0.0254 / 72.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MeasureUnit {
+    Millimeters,
+    Centimeters,
+    #[default]
+    Meters,
+    Kilometers,
+    Inches,
+    Feet,
+    Yards,
+    Miles,
+}
+impl MeasureUnit {
+    /// The label viewers show next to the values
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            MeasureUnit::Millimeters => "mm",
+            MeasureUnit::Centimeters => "cm",
+            MeasureUnit::Meters => "m",
+            MeasureUnit::Kilometers => "km",
+            MeasureUnit::Inches => "in",
+            MeasureUnit::Feet => "ft",
+            MeasureUnit::Yards => "yd",
+            MeasureUnit::Miles => "mi",
+        }
+    }
+    /// Meters in one of the unit
+    pub fn meters(&self) -> f32 {
+        match self {
+            MeasureUnit::Millimeters => 0.001,
+            MeasureUnit::Centimeters => 0.01,
+            MeasureUnit::Meters => 1.0,
+            MeasureUnit::Kilometers => 1000.0,
+            MeasureUnit::Inches => 0.0254,
+            MeasureUnit::Feet => 0.3048,
+            MeasureUnit::Yards => 0.9144,
+            MeasureUnit::Miles => 1609.344,
+        }
+    }
+}
+/// A drawing with the same scale in both directions
+///
+/// ```rust
+/// use tux_pdf::page::{MeasureUnit, RectilinearMeasure};
+/// // 1cm on the page is 1m in the real world
+/// let measure = RectilinearMeasure::scale(100.0, MeasureUnit::Meters);
+/// assert_eq!(measure.ratio, "1:100");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RectilinearMeasure {
+    /// Shown by viewers to describe the scale
+    pub ratio: String,
+    pub unit: MeasureUnit,
+    /// Real world units in one point on the page
+    pub units_per_point: f32,
+    /// Values are rounded to `1 / precision`. Defaults to 100
+    pub precision: u32,
+}
+impl RectilinearMeasure {
+    /// A `1:scale` drawing. Every length on the page is `scale` times longer in the real world
+    pub fn scale(scale: f32, unit: MeasureUnit) -> Self {
+        Self {
+            ratio: format!("1:{scale}"),
+            unit,
+            units_per_point: METERS_PER_POINT * scale / unit.meters(),
+            precision: 100,
+        }
+    }
+    pub fn with_precision(mut self, precision: u32) -> Self {
+        self.precision = precision;
+        self
+    }
+    fn dictionary(&self) -> Dictionary {
+        let number_format = |unit: String, conversion: f32| {
+            Object::Dictionary(dictionary! {
+                "Type" => Object::name("NumberFormat"),
+                "U" => Object::string_literal_owned(unit),
+                "C" => conversion,
+                "D" => self.precision as i64
+            })
+        };
+        let unit = self.unit.abbreviation();
+        dictionary! {
+            "Type" => Object::name("Measure"),
+            "Subtype" => Object::name("RL"),
+            "R" => Object::string_literal_owned(self.ratio.clone()),
+            "X" => vec![number_format(unit.to_owned(), self.units_per_point)],
+            // Distances and areas are converted from the units of X
+            "D" => vec![number_format(unit.to_owned(), 1f32)],
+            "A" => vec![number_format(format!("sq {unit}"), 1f32)]
+        }
+    }
+}
+/// A map. The corners of the viewport are placed at geographic coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeospatialMeasure {
+    /// The EPSG code of the geographic coordinate system. Defaults to 4326 (WGS 84)
+    pub epsg: u32,
+    /// Latitude of the top edge in degrees
+    pub north: f32,
+    /// Latitude of the bottom edge in degrees
+    pub south: f32,
+    /// Longitude of the right edge in degrees
+    pub east: f32,
+    /// Longitude of the left edge in degrees
+    pub west: f32,
+}
+impl GeospatialMeasure {
+    pub fn new(north: f32, south: f32, east: f32, west: f32) -> Self {
+        Self {
+            epsg: 4326,
+            north,
+            south,
+            east,
+            west,
+        }
+    }
+    fn dictionary(&self) -> Dictionary {
+        // Corners of the viewport as fractions of its size. Bottom left, top left, top right, bottom right
+        let corners = [0f32, 0f32, 0f32, 1f32, 1f32, 1f32, 1f32, 0f32];
+        let Self {
+            north,
+            south,
+            east,
+            west,
+            ..
+        } = *self;
+        // Latitude and longitude pairs in the same order as the corners
+        let points = [south, west, north, west, north, east, south, east];
+        dictionary! {
+            "Type" => Object::name("Measure"),
+            "Subtype" => Object::name("GEO"),
+            "Bounds" => corners.map(Object::from).to_vec(),
+            "GCS" => Object::Dictionary(dictionary! {
+                "Type" => Object::name("GEOGCS"),
+                "EPSG" => self.epsg as i64
+            }),
+            "GPTS" => points.map(Object::from).to_vec(),
+            "LPTS" => corners.map(Object::from).to_vec()
+        }
+    }
+}
+#[derive(Debug, Clone, PartialEq)]
+pub enum Measure {
+    Rectilinear(RectilinearMeasure),
+    Geospatial(GeospatialMeasure),
+}
+impl Measure {
+    fn dictionary(&self) -> Dictionary {
+        match self {
+            Measure::Rectilinear(measure) => measure.dictionary(),
+            Measure::Geospatial(measure) => measure.dictionary(),
+        }
+    }
+}
+impl From<RectilinearMeasure> for Measure {
+    fn from(measure: RectilinearMeasure) -> Self {
+        Self::Rectilinear(measure)
+    }
+}
+impl From<GeospatialMeasure> for Measure {
+    fn from(measure: GeospatialMeasure) -> Self {
+        Self::Geospatial(measure)
+    }
+}
+/// A region of the page with its own scale
+///
+/// When viewports overlap viewers use the last one added
+///
+/// ```rust
+/// use tux_pdf::{
+///     graphics::{PdfPosition, shapes::OutlineRect, size::Size},
+///     page::{MeasureUnit, PageViewport, PdfPage, RectilinearMeasure, page_sizes::A4},
+///     units::UnitType,
+/// };
+/// let floor_plan = OutlineRect {
+///     position: PdfPosition::new(20f32.mm().pt(), 20f32.mm().pt()),
+///     size: Size::new(170f32.mm().pt(), 120f32.mm().pt()),
+/// };
+/// let page = PdfPage::new_from_page_size(A4).with_viewport(
+///     PageViewport::new(
+///         floor_plan,
+///         RectilinearMeasure::scale(100.0, MeasureUnit::Meters),
+///     )
+///     .with_name("Ground Floor"),
+/// );
+/// assert_eq!(page.viewports.len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageViewport {
+    pub bbox: OutlineRect,
+    pub name: Option<String>,
+    pub measure: Measure,
+}
+impl PageViewport {
+    pub fn new(bbox: OutlineRect, measure: impl Into<Measure>) -> Self {
+        Self {
+            bbox,
+            name: None,
+            measure: measure.into(),
+        }
+    }
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+    /// The viewport dictionary of the page `/VP` array
+    pub(crate) fn dictionary(&self) -> Dictionary {
+        let PdfPosition { x, y } = self.bbox.position;
+        let upper_right = self.bbox.upper_right();
+        let mut dictionary = dictionary! {
+            "Type" => Object::name("Viewport"),
+            "BBox" => vec![
+                Object::from(x.0),
+                Object::from(y.0),
+                Object::from(upper_right.x.0),
+                Object::from(upper_right.y.0)
+            ],
+            "Measure" => Object::Dictionary(self.measure.dictionary())
+        };
+        if let Some(name) = &self.name {
+            dictionary.set("Name", Object::string_literal_owned(name.clone()));
+        }
+        dictionary
+    }
+}
+impl PdfPage {
+    pub fn add_viewport(&mut self, viewport: PageViewport) {
+        self.viewports.push(viewport);
+    }
+    pub fn with_viewport(mut self, viewport: PageViewport) -> Self {
+        self.add_viewport(viewport);
+        self
+    }
+}
+#[cfg(test)]
+mod tests {
+    use tux_pdf_low::types::Object;
+
+    use super::{GeospatialMeasure, MeasureUnit, PageViewport, RectilinearMeasure};
+    use crate::{
+        document::PdfDocument,
+        graphics::{PdfPosition, shapes::OutlineRect, size::Size},
+        page::{PdfPage, page_sizes::A4},
+        units::UnitType,
+    };
+
+    #[test]
+    fn scale_conversion() {
+        let measure = RectilinearMeasure::scale(100.0, MeasureUnit::Meters);
+        // 1cm on the page is 1m
+        let one_cm = 10f32.mm().pt().0 * measure.units_per_point;
+        assert!((one_cm - 1.0).abs() < 0.001);
+
+        let feet = RectilinearMeasure::scale(48.0, MeasureUnit::Feet);
+        // A quarter inch on the page is a foot
+        assert!((18.0 * feet.units_per_point - 1.0).abs() < 0.001);
+    }
+    #[test]
+    fn written_to_page() -> anyhow::Result<()> {
+        let bbox = OutlineRect {
+            position: PdfPosition::new(10f32.pt(), 20f32.pt()),
+            size: Size::new(100f32.pt(), 50f32.pt()),
+        };
+        let mut document = PdfDocument::new("Viewports");
+        document.add_page(
+            PdfPage::new_from_page_size(A4)
+                .with_viewport(
+                    PageViewport::new(bbox, RectilinearMeasure::scale(100.0, MeasureUnit::Meters))
+                        .with_name("Plan"),
+                )
+                .with_viewport(PageViewport::new(
+                    bbox,
+                    GeospatialMeasure::new(1.0, 0.0, 1.0, 0.0),
+                )),
+        );
+        let writer = document.write_into_pdf_document_writer()?;
+        let page = writer
+            .objects()
+            .filter_map(|(_, object)| object.as_dictionary())
+            .find(|dictionary| dictionary.get("Type") == Some(&Object::name("Page")))
+            .expect("A page is written");
+        let Some(Object::Array(viewports)) = page.get("VP") else {
+            panic!("The page has viewports");
+        };
+        assert_eq!(viewports.len(), 2);
+
+        let plan = viewports[0].as_dictionary().expect("Viewport dictionary");
+        assert_eq!(
+            plan.get("BBox"),
+            Some(&Object::Array(
+                [10f32, 20f32, 110f32, 70f32].map(Object::from).to_vec()
+            ))
+        );
+        let measure = plan
+            .get("Measure")
+            .and_then(Object::as_dictionary)
+            .expect("Measure dictionary");
+        assert_eq!(measure.get("Subtype"), Some(&Object::name("RL")));
+        assert_eq!(
+            measure.get("R"),
+            Some(&Object::string_literal_owned("1:100"))
+        );
+
+        let map = viewports[1]
+            .as_dictionary()
+            .and_then(|viewport| viewport.get("Measure"))
+            .and_then(Object::as_dictionary)
+            .expect("Measure dictionary");
+        assert_eq!(map.get("Subtype"), Some(&Object::name("GEO")));
+        Ok(())
+    }
+}
@@ -1,9 +1,30 @@
 use crate::{
-    document::LayerId,
-    graphics::{LayerType, PdfObject, shapes::OutlineRect, size::Size},
+    TuxPdfError,
+    document::{LayerId, PdfDocument},
+    graphics::{
+        Anchor, AnchorOffset, LayerType, Margin, PdfObject, PdfPosition, TransformedGroup,
+        primitives::ctm::CurTransMat, shapes::OutlineRect, size::Size,
+    },
+    layouts::LayoutItemType,
+    units::Length,
 };
 
+mod background;
+mod boxes;
+mod canvas;
+mod measure;
 pub mod page_sizes;
+mod redact;
+mod scan;
+mod settings;
+mod transition;
+pub use background::*;
+pub use boxes::*;
+pub use canvas::*;
+pub use measure::*;
+pub use scan::*;
+pub use settings::*;
+pub use transition::*;
 
 #[derive(Debug, Default, PartialEq, Clone)]
 pub struct PdfPage {
@@ -13,10 +34,18 @@ pub struct PdfPage {
     pub trim_box: Option<OutlineRect>,
     pub crop_box: Option<OutlineRect>,
     pub rotate: Option<i64>,
+    /// Space kept free around the edges of the page. See [PdfPage::content_area]
+    pub margin: Margin,
     /// You can think of this as the "content" of the page
     pub contents: Vec<PdfObject>,
     /// Layers that are present on this page
     pub layers: Vec<LayerId>,
+    /// Drawn beneath the contents and layers. See [PdfPage::set_background]
+    pub background: Option<PageBackground>,
+    /// Used by viewers when presenting the document. See [PdfPage::set_transition]
+    pub transition: Option<PageTransition>,
+    /// Regions with a real world scale. See [PdfPage::add_viewport]
+    pub viewports: Vec<PageViewport>,
 }
 impl LayerType for PdfPage {
     fn add_to_layer(&mut self, object: impl Into<PdfObject>) -> Result<(), crate::TuxPdfError> {
@@ -25,6 +54,15 @@ impl LayerType for PdfPage {
     }
 }
 impl PdfPage {
+    /// Creates a page with the size, orientation, margin and rotation of the settings
+    ///
+    /// Accepts a [Size] for a page without a margin
+    pub fn new(settings: impl Into<PageSettings>) -> Self {
+        let settings = settings.into();
+        let mut page = Self::new_from_page_size(settings.page_size()).with_margin(settings.margin);
+        page.set_rotation(settings.rotation);
+        page
+    }
     /// Create a new page with the given size
     pub fn new_from_page_size(size: Size) -> Self {
         let media_box = size.into();
@@ -52,8 +90,230 @@ impl PdfPage {
         self.trim_box = Some(trim_box);
         self
     }
+    pub fn with_margin(mut self, margin: Margin) -> Self {
+        self.margin = margin;
+        self
+    }
+    pub fn with_rotation(mut self, rotation: PageRotation) -> Self {
+        self.set_rotation(rotation);
+        self
+    }
+    pub fn set_rotation(&mut self, rotation: PageRotation) {
+        self.rotate = match rotation {
+            PageRotation::None => None,
+            rotation => Some(rotation.degrees()),
+        };
+    }
+    /// The rotation of the page. Values that are not a multiple of 90 are treated as no rotation
+    pub fn rotation(&self) -> PageRotation {
+        self.rotate
+            .and_then(PageRotation::from_degrees)
+            .unwrap_or_default()
+    }
+    /// Rotates the page another 90 degrees clockwise when displayed
+    pub fn rotate_clockwise(&mut self) {
+        self.set_rotation(self.rotation().clockwise());
+    }
+    /// Rotates the page another 90 degrees counter clockwise when displayed
+    pub fn rotate_counter_clockwise(&mut self) {
+        self.set_rotation(self.rotation().counter_clockwise());
+    }
+    /// The settings the page could be recreated with. The size is the size of the media box
+    pub fn settings(&self) -> PageSettings {
+        PageSettings::new(self.size())
+            .with_margin(self.margin)
+            .with_rotation(self.rotation())
+    }
     /// Add a layer to the page
     pub fn add_layer(&mut self, layer: LayerId) {
         self.layers.push(layer);
     }
+    /// The size of the page based on the media box
+    pub fn size(&self) -> Size {
+        self.media_box.size
+    }
+    /// The media box minus the margin of the page
+    ///
+    /// Layouts such as [PdfPage::place] and [TablePageRules::for_page](crate::layouts::table::TablePageRules::for_page) stay within this area.
+    /// Without a margin it is the media box
+    pub fn content_area(&self) -> OutlineRect {
+        self.media_box.inset(self.margin)
+    }
+    /// Converts a position that uses a top left origin into the bottom left origin PDF uses
+    pub fn from_top_left(&self, position: PdfPosition) -> PdfPosition {
+        position.invert_from_page_size(self.size())
+    }
+    /// Places an item on the page so its `anchor` point lines up with the same anchor point of the [content area](PdfPage::content_area)
+    ///
+    /// The offset uses a top left origin. So `Anchor::TopLeft` with an offset of `(20, 20)` places the item 20pt from the top and left edges.
+    /// Use negative values to move away from the right or bottom edges.
+    /// Percentages in the offset are relative to the size of the content area.
+    ///
+    /// Returns the position the item was rendered at
+    pub fn place<I>(
+        &mut self,
+        document: &PdfDocument,
+        mut item: I,
+        anchor: Anchor,
+        offset: impl Into<AnchorOffset>,
+    ) -> Result<PdfPosition, TuxPdfError>
+    where
+        I: LayoutItemType,
+    {
+        let item_size = item.calculate_size(document)?;
+        let area = self.content_area();
+        let page_point = offset
+            .into()
+            .apply(anchor.point_of(area.position, area.size), area.size);
+        let bottom_left = anchor.bottom_left_from(page_point, item_size);
+        let position = item.position_anchor().point_of(bottom_left, item_size);
+        item.set_position(position);
+        item.render(document, self)?;
+        Ok(position)
+    }
+    /// Wraps everything currently on the page in the transformation
+    ///
+    /// Useful for shifting content for binding margins after layout is done.
+    /// Content added after this call is not transformed. Layers are not transformed.
+    pub fn transform(&mut self, transform: impl Into<Vec<CurTransMat>>) {
+        if self.contents.is_empty() {
+            return;
+        }
+        let contents = std::mem::take(&mut self.contents);
+        self.contents.push(
+            TransformedGroup::new(transform)
+                .with_contents(contents)
+                .into(),
+        );
+    }
+    /// Converts a position using any unit into [Pt](crate::units::Pt)
+    ///
+    /// Percentages are relative to the page size
+    pub fn resolve_position(&self, position: impl Into<PdfPosition<Length>>) -> PdfPosition {
+        position.into().resolve(self.size())
+    }
+    /// Converts a size using any unit into [Pt](crate::units::Pt)
+    ///
+    /// Percentages are relative to the page size
+    pub fn resolve_size(&self, size: impl Into<Size<Length>>) -> Size {
+        size.into().resolve(self.size())
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::{PdfPage, page_sizes::A4};
+    use crate::{
+        document::PdfDocument,
+        graphics::{Anchor, AnchorOffset, BlankSpace, Margin, PdfPosition, size::Size},
+        units::{Length, Percentage, UnitType},
+    };
+
+    #[test]
+    fn place_with_anchor() {
+        let document = PdfDocument::new("Anchors");
+        let mut page = PdfPage::new_from_page_size(A4);
+        let item = BlankSpace {
+            set_size: Some(Size::new(100f32.pt(), 50f32.pt())),
+            ..Default::default()
+        };
+        let position = page
+            .place(
+                &document,
+                item.clone(),
+                Anchor::TopLeft,
+                (10f32.pt(), 20f32.pt()),
+            )
+            .unwrap();
+        assert_eq!(
+            position,
+            PdfPosition::new(10f32.pt(), A4.height - 70f32.pt())
+        );
+
+        let position = page
+            .place(
+                &document,
+                item.clone(),
+                Anchor::Center,
+                AnchorOffset::default(),
+            )
+            .unwrap();
+        assert_eq!(
+            position,
+            PdfPosition::new(
+                (A4.width - 100f32.pt()) / 2f32,
+                (A4.height - 50f32.pt()) / 2f32
+            )
+        );
+
+        let position = page
+            .place(
+                &document,
+                item.clone(),
+                Anchor::BottomRight,
+                (-10f32.pt(), -10f32.pt()),
+            )
+            .unwrap();
+        assert_eq!(
+            position,
+            PdfPosition::new(A4.width - 110f32.pt(), 10f32.pt())
+        );
+
+        // Percentages are resolved against the content area when the item is placed
+        let mut page = PdfPage::new_from_page_size(A4).with_margin(Margin::all(10f32.mm().pt()));
+        let area = page.content_area();
+        let position = page
+            .place(
+                &document,
+                item,
+                Anchor::TopLeft,
+                (Percentage(0.5), 10f32.mm()),
+            )
+            .unwrap();
+        assert_eq!(
+            position,
+            PdfPosition::new(
+                area.position.x + area.size.width * 0.5f32,
+                area.position.y + area.size.height - 10f32.mm().pt() - 50f32.pt()
+            )
+        );
+    }
+    #[test]
+    fn place_within_margin() {
+        let document = PdfDocument::new("Margins");
+        let mut page = PdfPage::new_from_page_size(A4).with_margin(Margin::all(10f32.mm().pt()));
+        let area = page.content_area();
+        assert_eq!(
+            area.position,
+            PdfPosition::new(10f32.mm().pt(), 10f32.mm().pt())
+        );
+        assert_eq!(
+            area.size,
+            Size::new(A4.width - 20f32.mm().pt(), A4.height - 20f32.mm().pt())
+        );
+
+        let item = BlankSpace {
+            set_size: Some(Size::new(100f32.pt(), 50f32.pt())),
+            ..Default::default()
+        };
+        let position = page
+            .place(&document, item, Anchor::TopLeft, AnchorOffset::default())
+            .unwrap();
+        assert_eq!(
+            position,
+            PdfPosition::new(10f32.mm().pt(), A4.height - 10f32.mm().pt() - 50f32.pt())
+        );
+    }
+    #[test]
+    fn resolve_relative_to_page() {
+        let page = PdfPage::new_from_page_size(A4);
+        let position = page.resolve_position(PdfPosition::<Length>::from_lengths(
+            Percentage(0.5),
+            10f32.mm(),
+        ));
+        assert_eq!(position.x, A4.width * 0.5f32);
+        assert_eq!(position.y, 10f32.mm().pt());
+
+        let size = page.resolve_size(Size::<Length>::from_lengths(1f32.inch(), Percentage(1.0)));
+        assert_eq!(size, Size::new(72f32.pt(), A4.height));
+    }
 }
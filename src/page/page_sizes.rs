@@ -2,3 +2,15 @@ use crate::{graphics::size::Size, units::Pt};
 
 /// A4 paper size
 pub const A4: Size = Size::new(Pt(595f32), Pt(842f32));
+/// A3 paper size. Twice the size of A4
+pub const A3: Size = Size::new(Pt(842f32), Pt(1191f32));
+/// A5 paper size. Half the size of A4
+pub const A5: Size = Size::new(Pt(420f32), Pt(595f32));
+/// US Letter paper size
+pub const LETTER: Size = Size::new(Pt(612f32), Pt(792f32));
+/// US Legal paper size
+pub const LEGAL: Size = Size::new(Pt(612f32), Pt(1008f32));
+/// 16:9 presentation slide. The size of widescreen slides in presentation software
+pub const SLIDE_16_9: Size = Size::new(Pt(960f32), Pt(540f32));
+/// 4:3 presentation slide
+pub const SLIDE_4_3: Size = Size::new(Pt(720f32), Pt(540f32));
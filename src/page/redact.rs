@@ -0,0 +1,281 @@
+use std::mem;
+
+use crate::{
+    TuxPdfError,
+    document::{IdType, Layer, LayerId, PdfDocument},
+    graphics::{
+        GraphicItems, GraphicStyles, GraphicsGroup, PdfObject, PdfPosition, TextBlock,
+        color::Color,
+        image::PdfImage,
+        shapes::{OutlineRect, PaintedRect},
+        size::{RenderSize, Size},
+    },
+};
+
+use super::PdfPage;
+
+impl PdfPage {
+    /// Removes the content within the regions and covers each region with a filled box
+    ///
+    /// Removal is conservative. An object is dropped entirely if its bounds touch a region, so a text block is never partially kept.
+    /// Objects whose bounds are unknown, such as [TransformedGroup](crate::graphics::TransformedGroup)s, are dropped as well.
+    /// Line widths are not included in the bounds of graphics.
+    ///
+    /// Layers are shared between pages. A layer of this page with content in a region is copied, with the same name, and the content is removed from the copy.
+    /// The page uses the copy and the other pages keep the original layer.
+    /// The cover boxes are added to the contents of the page, which are drawn beneath the layers. So layer content is only ever removed, never covered.
+    ///
+    /// Returns the number of objects removed
+    pub fn redact(
+        &mut self,
+        document: &mut PdfDocument,
+        regions: &[OutlineRect],
+        fill_color: impl Into<Color>,
+    ) -> Result<usize, TuxPdfError> {
+        if regions.is_empty() {
+            return Ok(0);
+        }
+        let mut removed = 0;
+        let contents = mem::take(&mut self.contents);
+        self.contents = redact_objects(document, contents, regions, &mut removed)?;
+        for layer_id in &mut self.layers {
+            let Some(layer) = document.resources.layers.get_layer(layer_id).cloned() else {
+                // Left for the writer to report
+                continue;
+            };
+            let mut removed_from_layer = 0;
+            let operations =
+                redact_objects(document, layer.operations, regions, &mut removed_from_layer)?;
+            if removed_from_layer == 0 {
+                continue;
+            }
+            let redacted = Layer {
+                operations,
+                ..layer
+            };
+            let redacted_id = LayerId::new_random();
+            document
+                .resources
+                .layers
+                .map
+                .insert(redacted_id.clone(), redacted);
+            *layer_id = redacted_id;
+            removed += removed_from_layer;
+        }
+        let boxes = GraphicsGroup {
+            styles: Some(GraphicStyles {
+                fill_color: Some(fill_color.into()),
+                ..Default::default()
+            }),
+            items: regions
+                .iter()
+                .map(|region| {
+                    PaintedRect {
+                        position: region.position,
+                        size: region.size,
+                        ..Default::default()
+                    }
+                    .into()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        self.contents.push(boxes.into());
+        Ok(removed)
+    }
+}
+fn redact_objects(
+    document: &PdfDocument,
+    objects: Vec<PdfObject>,
+    regions: &[OutlineRect],
+    removed: &mut usize,
+) -> Result<Vec<PdfObject>, TuxPdfError> {
+    let mut kept = Vec::with_capacity(objects.len());
+    for object in objects {
+        let bounds = match object {
+            PdfObject::NewLine | PdfObject::Styles(_) => {
+                kept.push(object);
+                continue;
+            }
+            PdfObject::Clipped(mut group) => {
+                // The clip does not move the contents so they can be checked one by one
+                group.contents = redact_objects(document, group.contents, regions, removed)?;
+                kept.push(group.into());
+                continue;
+            }
+            PdfObject::Masked(mut group) => {
+                group.contents = redact_objects(document, group.contents, regions, removed)?;
+                kept.push(group.into());
+                continue;
+            }
+            PdfObject::Graphics(item) => {
+                if let Some(item) = redact_graphic(item, regions, removed) {
+                    kept.push(PdfObject::Graphics(item));
+                }
+                continue;
+            }
+            PdfObject::TextBlock(ref block) => Some(text_bounds(document, block)?),
+            PdfObject::PathText(ref text) => text.bounds(),
+            PdfObject::Image(ref image) => Some(image_bounds(document, image)?),
+            // Where raw operations draw is not known
+            PdfObject::Transformed(_) | PdfObject::Raw(_) => None,
+        };
+        if bounds.is_some_and(|bounds| !intersects_any(&bounds, regions)) {
+            kept.push(object);
+        } else {
+            *removed += 1;
+        }
+    }
+    Ok(kept)
+}
+/// Returns None if the item was removed
+fn redact_graphic(
+    item: GraphicItems,
+    regions: &[OutlineRect],
+    removed: &mut usize,
+) -> Option<GraphicItems> {
+    match item {
+        // The items of the group are in page space so they can be checked one by one
+        GraphicItems::Group(mut group) if group.transform.is_empty() => {
+            let had_items = !group.items.is_empty();
+            group.items = mem::take(&mut group.items)
+                .into_iter()
+                .filter_map(|item| redact_graphic(item, regions, removed))
+                .collect();
+            if had_items && group.items.is_empty() {
+                return None;
+            }
+            Some(group.into())
+        }
+        item => match item.bounds() {
+            Some(bounds) if !intersects_any(&bounds, regions) => Some(item),
+            _ => {
+                *removed += 1;
+                None
+            }
+        },
+    }
+}
+/// Text starts at the baseline of the last line. So the descent is added below the position
+fn text_bounds(document: &PdfDocument, block: &TextBlock) -> Result<OutlineRect, TuxPdfError> {
+    let size = block.content.render_size(document, &block.style)?;
+    let descent = block
+        .style
+        .font_ref
+        .metrics(&document.resources.fonts)?
+        .descent(block.style.font_size);
+    Ok(OutlineRect {
+        position: PdfPosition::new(block.position.x, block.position.y + descent),
+        size: Size::new(size.width, size.height - descent),
+    })
+}
+fn image_bounds(document: &PdfDocument, image: &PdfImage) -> Result<OutlineRect, TuxPdfError> {
+    image.placed_rect_in(&document.resources.xobjects)
+}
+fn intersects_any(bounds: &OutlineRect, regions: &[OutlineRect]) -> bool {
+    regions.iter().any(|region| region.intersects(bounds))
+}
+#[cfg(test)]
+mod tests {
+    use crate::{
+        document::{BuiltinFont, PdfDocument},
+        graphics::{
+            GraphicItems, LayerType, PdfObject, PdfPosition, TextBlock, TextStyle,
+            color::BLACK_RGB,
+            shapes::{OutlineRect, PaintedRect},
+            size::Size,
+        },
+        page::{PdfPage, page_sizes::A4},
+        units::UnitType,
+    };
+
+    #[test]
+    fn removes_overlapping_content() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Redaction");
+        let font_ref = doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        let mut page = PdfPage::new_from_page_size(A4);
+        for y in [100f32, 500f32] {
+            page.add_to_layer(TextBlock {
+                content: "Secret".into(),
+                style: TextStyle {
+                    font_ref: font_ref.clone(),
+                    ..Default::default()
+                },
+                position: PdfPosition::new(50f32.pt(), y.pt()),
+                draw_as_lines: false,
+            })?;
+        }
+        page.add_to_layer(PaintedRect {
+            position: PdfPosition::new(300f32.pt(), 100f32.pt()),
+            size: Size::new(20f32.pt(), 20f32.pt()),
+            ..Default::default()
+        })?;
+
+        let region = OutlineRect {
+            position: PdfPosition::new(40f32.pt(), 90f32.pt()),
+            size: Size::new(100f32.pt(), 30f32.pt()),
+        };
+        let removed = page.redact(&mut doc, &[region], BLACK_RGB)?;
+        assert_eq!(removed, 1);
+        // The other text block, the rectangle and the redaction box
+        assert_eq!(page.contents.len(), 3);
+        let PdfObject::TextBlock(kept) = &page.contents[0] else {
+            panic!("Expected the text outside of the region to be kept");
+        };
+        assert_eq!(kept.position.y, 500f32.pt());
+        assert!(matches!(
+            page.contents[2],
+            PdfObject::Graphics(GraphicItems::Group(_))
+        ));
+        Ok(())
+    }
+    #[test]
+    fn removes_layer_content_from_a_copy() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Redaction");
+        let font_ref = doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+        let layer_id = doc.create_layer("Notes");
+        let layer = doc.resources.layers.get_layer_mut(&layer_id).unwrap();
+        for y in [100f32, 500f32] {
+            layer.add_to_layer(TextBlock {
+                content: "Secret".into(),
+                style: TextStyle {
+                    font_ref: font_ref.clone(),
+                    ..Default::default()
+                },
+                position: PdfPosition::new(50f32.pt(), y.pt()),
+                draw_as_lines: false,
+            })?;
+        }
+        let mut redacted = PdfPage::new_from_page_size(A4);
+        redacted.add_layer(layer_id.clone());
+        let mut other = PdfPage::new_from_page_size(A4);
+        other.add_layer(layer_id.clone());
+
+        let region = OutlineRect {
+            position: PdfPosition::new(40f32.pt(), 90f32.pt()),
+            size: Size::new(100f32.pt(), 30f32.pt()),
+        };
+        let removed = redacted.redact(&mut doc, &[region], BLACK_RGB)?;
+        assert_eq!(removed, 1);
+
+        let [copy_id] = redacted.layers.as_slice() else {
+            panic!("Expected the page to keep one layer");
+        };
+        assert_ne!(copy_id, &layer_id);
+        let copy = doc.resources.layers.get_layer(copy_id).unwrap();
+        assert_eq!(copy.name, "Notes");
+        let [PdfObject::TextBlock(kept)] = copy.operations.as_slice() else {
+            panic!("Expected only the text outside of the region in the copy");
+        };
+        assert_eq!(kept.position.y, 500f32.pt());
+        // The original layer is still used by the other page
+        let original = doc.resources.layers.get_layer(&layer_id).unwrap();
+        assert_eq!(original.operations.len(), 2);
+        assert_eq!(other.layers, vec![layer_id]);
+
+        doc.add_page(redacted);
+        doc.add_page(other);
+        doc.write_into_pdf_document_writer()?;
+        Ok(())
+    }
+}
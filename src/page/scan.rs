@@ -0,0 +1,209 @@
+use crate::{
+    TuxPdfError,
+    document::{BuiltinFont, FontRef, PdfDocument, ResourceNotRegistered, XObjectId, XObjectRef},
+    graphics::{
+        PdfPosition, TextBlock, TextRenderingMode, TextStyle,
+        image::{ImageFit, PdfImage},
+        size::RenderSize,
+    },
+    units::Pt,
+};
+
+use super::PdfPage;
+
+/// A word recognized by an OCR engine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OcrWord {
+    pub text: String,
+    /// Left edge of the word in image pixels
+    pub x: u32,
+    /// Top edge of the word in image pixels. Measured from the top of the image like most OCR engines report it
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+impl OcrWord {
+    pub fn new(text: impl Into<String>, x: u32, y: u32, width: u32, height: u32) -> Self {
+        Self {
+            text: text.into(),
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+/// A scanned page with a hidden text layer so the scan can be searched and copied from
+///
+/// ```rust,no_run
+/// # fn main() -> Result<(), tux_pdf::TuxPdfError> {
+/// use tux_pdf::{
+///     document::{PdfDocument, PdfXObjectImage},
+///     page::{OcrWord, SearchableScan},
+/// };
+/// let mut doc = PdfDocument::new("Scan");
+/// let scan = image::open("scan.png").expect("Image could not be read");
+/// let image = doc.add_xobject(PdfXObjectImage::load_from_dynamic_image(scan)?);
+///
+/// let page = SearchableScan::new(image)
+///     .add_word(OcrWord::new("Invoice", 120, 80, 310, 64))
+///     .into_page(&mut doc)?;
+/// doc.add_page(page);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchableScan {
+    pub image: XObjectId,
+    /// DPI the image was scanned at. Decides the size of the page
+    ///
+    /// Default is 300.0
+    pub dpi: f32,
+    pub words: Vec<OcrWord>,
+    /// Font of the hidden text. Only the width of the font matters as the text is not drawn
+    ///
+    /// Defaults to Helvetica
+    pub font_ref: FontRef,
+}
+impl SearchableScan {
+    pub fn new(image: XObjectId) -> Self {
+        Self {
+            image,
+            dpi: 300.0,
+            words: Vec::new(),
+            font_ref: FontRef::Builtin(BuiltinFont::Helvetica),
+        }
+    }
+    pub fn with_dpi(mut self, dpi: f32) -> Self {
+        self.dpi = dpi;
+        self
+    }
+    pub fn with_font(mut self, font_ref: FontRef) -> Self {
+        self.font_ref = font_ref;
+        self
+    }
+    pub fn add_word(mut self, word: OcrWord) -> Self {
+        self.words.push(word);
+        self
+    }
+    pub fn with_words(mut self, words: impl IntoIterator<Item = OcrWord>) -> Self {
+        self.words.extend(words);
+        self
+    }
+    /// Creates a page the size of the image with the image covering the page
+    ///
+    /// Every word is placed as invisible text. Its font size is picked so the text is as tall as the box
+    /// and the character spacing is picked so it is as wide as the box.
+    ///
+    /// Builtin fonts are registered if they have not been
+    pub fn into_page(self, document: &mut PdfDocument) -> Result<PdfPage, TuxPdfError> {
+        let Self {
+            image,
+            dpi,
+            words,
+            font_ref,
+        } = self;
+        let font_ref = document.resources.fonts.resolve(&font_ref).clone();
+        if let FontRef::Builtin(builtin) = &font_ref {
+            document.font_map().register_builtin_font(*builtin);
+        }
+        let image_size = match document.resources.xobjects.get_xobject(&image) {
+            Some(XObjectRef::Image(xobject)) => xobject.image.size,
+            Some(_) => return Err(TuxPdfError::InvalidReference("Image")),
+            None => return Err(ResourceNotRegistered::from(image).into()),
+        };
+        let page_size = image_size.into_pt_with_dpi(dpi);
+        let mut page = PdfPage::new_from_page_size(page_size);
+        let mut scan = PdfImage::new(image).with_fit(ImageFit::Stretch);
+        scan.set_fit_area(page.media_box);
+        page.contents.push(scan.into());
+
+        // Pixels to points. Equal on both axes as the page has the aspect ratio of the image
+        let scale = 72.0 / dpi;
+        let metrics = font_ref.metrics(&document.resources.fonts)?;
+        let base_style = TextStyle {
+            font_ref,
+            rendering_mode: Some(TextRenderingMode::Invisible),
+            ..Default::default()
+        };
+        for word in words {
+            let characters = word.text.chars().count();
+            if characters == 0 || word.height == 0 {
+                continue;
+            }
+            let height = Pt(word.height as f32 * scale);
+            let width = Pt(word.width as f32 * scale);
+            let font_size = height / metrics.line_height(Pt(1.0));
+            let mut style = TextStyle {
+                font_size,
+                ..base_style.clone()
+            };
+            let natural_width = word.text.as_str().render_size(document, &style)?.width;
+            style.character_spacing = Some((width - natural_width) / Pt(characters as f32));
+
+            let bottom = page_size.height - Pt((word.y + word.height) as f32 * scale);
+            // The descent is negative. So the baseline sits above the bottom of the box
+            let baseline = bottom - metrics.descent(font_size);
+            page.contents.push(
+                TextBlock {
+                    content: word.text.into(),
+                    style,
+                    position: PdfPosition::new(Pt(word.x as f32 * scale), baseline),
+                    draw_as_lines: false,
+                }
+                .into(),
+            );
+        }
+        Ok(page)
+    }
+}
+impl From<XObjectId> for SearchableScan {
+    fn from(image: XObjectId) -> Self {
+        Self::new(image)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use image::DynamicImage;
+
+    use super::{OcrWord, SearchableScan};
+    use crate::{
+        document::{PdfDocument, PdfXObjectImage},
+        graphics::{OperationWriter, PdfObject, PdfObjectType, TextRenderingMode, size::Size},
+        units::UnitType,
+    };
+
+    #[test]
+    fn words_fill_their_boxes() -> anyhow::Result<()> {
+        let mut doc = PdfDocument::new("Scan");
+        let image = PdfXObjectImage::load_from_dynamic_image(DynamicImage::new_rgb8(600, 300))?;
+        let image = doc.add_xobject(image);
+
+        let page = SearchableScan::new(image)
+            .with_dpi(72.0)
+            .add_word(OcrWord::new("Hello", 100, 50, 120, 30))
+            .add_word(OcrWord::new("", 0, 0, 10, 10))
+            .into_page(&mut doc)?;
+        assert_eq!(page.size(), Size::new(600f32.pt(), 300f32.pt()));
+        // The image and one word
+        assert_eq!(page.contents.len(), 2);
+        let PdfObject::TextBlock(word) = &page.contents[1] else {
+            panic!("Expected the word to be a text block");
+        };
+        assert_eq!(
+            word.style.rendering_mode,
+            Some(TextRenderingMode::Invisible)
+        );
+        assert_eq!(word.position.x, 100f32.pt());
+        let metrics = word.style.font_ref.metrics(&doc.resources.fonts)?;
+        assert_eq!(metrics.line_height(word.style.font_size), 30f32.pt());
+
+        // The invisible mode and the spacing do not reach content drawn after the word
+        let mut writer = OperationWriter::default();
+        word.clone().write(&doc.resources, &mut writer)?;
+        let operations = writer.operations();
+        assert_eq!(operations.first().unwrap().operation, "q");
+        assert_eq!(operations.last().unwrap().operation, "Q");
+        Ok(())
+    }
+}
@@ -0,0 +1,179 @@
+use crate::graphics::{Margin, size::Size};
+
+/// Which side of the page is the longest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PageOrientation {
+    /// Taller than it is wide
+    #[default]
+    Portrait,
+    /// Wider than it is tall
+    Landscape,
+}
+impl PageOrientation {
+    /// Swaps the width and height of the size if it does not match the orientation
+    pub fn apply(self, size: Size) -> Size {
+        let is_landscape = size.width > size.height;
+        match (self, is_landscape) {
+            (PageOrientation::Portrait, true) | (PageOrientation::Landscape, false) => {
+                Size::new(size.height, size.width)
+            }
+            _ => size,
+        }
+    }
+    /// The orientation of a size. Square sizes are portrait
+    pub fn of(size: Size) -> Self {
+        if size.width > size.height {
+            PageOrientation::Landscape
+        } else {
+            PageOrientation::Portrait
+        }
+    }
+}
+/// Clockwise rotation applied by the viewer when the page is displayed or printed
+///
+/// The content is laid out on the unrotated page. Written as the `Rotate` entry of the page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PageRotation {
+    #[default]
+    None,
+    Clockwise90,
+    Rotate180,
+    Clockwise270,
+}
+impl PageRotation {
+    pub fn degrees(self) -> i64 {
+        match self {
+            PageRotation::None => 0,
+            PageRotation::Clockwise90 => 90,
+            PageRotation::Rotate180 => 180,
+            PageRotation::Clockwise270 => 270,
+        }
+    }
+    /// Any multiple of 90. Negative values rotate counter clockwise. Returns None for other values
+    pub fn from_degrees(degrees: i64) -> Option<Self> {
+        match degrees.rem_euclid(360) {
+            0 => Some(PageRotation::None),
+            90 => Some(PageRotation::Clockwise90),
+            180 => Some(PageRotation::Rotate180),
+            270 => Some(PageRotation::Clockwise270),
+            _ => None,
+        }
+    }
+    /// Another 90 degrees clockwise
+    pub fn clockwise(self) -> Self {
+        Self::from_degrees(self.degrees() + 90).unwrap_or_default()
+    }
+    /// Another 90 degrees counter clockwise
+    pub fn counter_clockwise(self) -> Self {
+        Self::from_degrees(self.degrees() - 90).unwrap_or_default()
+    }
+}
+/// Everything needed to create a [PdfPage](super::PdfPage)
+///
+/// ```rust
+/// use tux_pdf::{
+///     graphics::Margin,
+///     page::{PageOrientation, PageSettings, PdfPage, page_sizes::{A3, A4}},
+///     units::UnitType,
+/// };
+/// let content = PageSettings::portrait(A4).with_margin(Margin::all(20f32.mm().pt()));
+/// let appendix = PageSettings::landscape(A3).with_margin(Margin::all(10f32.mm().pt()));
+///
+/// let page = PdfPage::new(appendix);
+/// assert_eq!(page.size().width, A3.height);
+/// assert_eq!(page.settings().orientation, PageOrientation::Landscape);
+/// # let _ = PdfPage::new(content);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PageSettings {
+    /// The paper size. Such as [A4](super::page_sizes::A4)
+    pub size: Size,
+    /// The size is flipped to match the orientation
+    pub orientation: PageOrientation,
+    /// Space kept free around the edges of the page
+    pub margin: Margin,
+    pub rotation: PageRotation,
+}
+impl PageSettings {
+    /// Keeps the orientation of the size
+    pub fn new(size: Size) -> Self {
+        Self {
+            size,
+            orientation: PageOrientation::of(size),
+            ..Default::default()
+        }
+    }
+    pub fn portrait(size: Size) -> Self {
+        Self::new(size).with_orientation(PageOrientation::Portrait)
+    }
+    pub fn landscape(size: Size) -> Self {
+        Self::new(size).with_orientation(PageOrientation::Landscape)
+    }
+    pub fn with_orientation(mut self, orientation: PageOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+    pub fn with_margin(mut self, margin: Margin) -> Self {
+        self.margin = margin;
+        self
+    }
+    pub fn with_rotation(mut self, rotation: PageRotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+    /// The size of the page after the orientation is applied
+    pub fn page_size(&self) -> Size {
+        self.orientation.apply(self.size)
+    }
+}
+impl From<Size> for PageSettings {
+    fn from(size: Size) -> Self {
+        Self::new(size)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::{PageOrientation, PageRotation, PageSettings};
+    use crate::{
+        graphics::Margin,
+        page::{
+            PdfPage,
+            page_sizes::{A3, A4},
+        },
+        units::UnitType,
+    };
+
+    #[test]
+    fn mixed_orientations() {
+        let portrait =
+            PdfPage::new(PageSettings::landscape(A4).with_orientation(PageOrientation::Portrait));
+        assert_eq!(portrait.size(), A4);
+
+        let settings = PageSettings::landscape(A3)
+            .with_margin(Margin::all(10f32.pt()))
+            .with_rotation(PageRotation::Clockwise90);
+        let mut appendix = PdfPage::new(settings);
+        assert_eq!(appendix.size().width, A3.height);
+        assert_eq!(appendix.content_area().size.height, A3.width - 20f32.pt());
+        assert_eq!(appendix.rotate, Some(90));
+        assert_eq!(appendix.settings().page_size(), settings.page_size());
+        assert_eq!(appendix.settings().margin, settings.margin);
+
+        appendix.rotate_counter_clockwise();
+        appendix.rotate_counter_clockwise();
+        assert_eq!(appendix.rotation(), PageRotation::Clockwise270);
+    }
+    #[test]
+    fn rotation_degrees() {
+        assert_eq!(
+            PageRotation::from_degrees(-90),
+            Some(PageRotation::Clockwise270)
+        );
+        assert_eq!(
+            PageRotation::from_degrees(450),
+            Some(PageRotation::Clockwise90)
+        );
+        assert_eq!(PageRotation::from_degrees(45), None);
+        assert_eq!(PageRotation::Clockwise270.clockwise(), PageRotation::None);
+    }
+}
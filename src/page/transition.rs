@@ -0,0 +1,270 @@
+use tux_pdf_low::{
+    dictionary,
+    types::{Dictionary, Object, PdfVersion},
+};
+
+use super::PdfPage;
+
+/// The direction a [TransitionStyle::Wipe] moves in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TransitionDirection {
+    #[default]
+    LeftToRight,
+    BottomToTop,
+    RightToLeft,
+    TopToBottom,
+}
+impl TransitionDirection {
+    /// The `/Di` value. Degrees counter clockwise from left to right
+    pub fn degrees(&self) -> i64 {
+        match self {
+            TransitionDirection::LeftToRight => 0,
+            TransitionDirection::BottomToTop => 90,
+            TransitionDirection::RightToLeft => 180,
+            TransitionDirection::TopToBottom => 270,
+        }
+    }
+}
+/// The direction a [TransitionStyle::Push], [TransitionStyle::Cover] or [TransitionStyle::Uncover] moves in
+///
+/// Only [TransitionStyle::Wipe] can move from bottom to top or from right to left
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SlideDirection {
+    #[default]
+    LeftToRight,
+    TopToBottom,
+}
+impl SlideDirection {
+    /// The `/Di` value. Degrees counter clockwise from left to right
+    pub fn degrees(&self) -> i64 {
+        match self {
+            SlideDirection::LeftToRight => 0,
+            SlideDirection::TopToBottom => 270,
+        }
+    }
+}
+impl From<SlideDirection> for TransitionDirection {
+    fn from(direction: SlideDirection) -> Self {
+        match direction {
+            SlideDirection::LeftToRight => TransitionDirection::LeftToRight,
+            SlideDirection::TopToBottom => TransitionDirection::TopToBottom,
+        }
+    }
+}
+/// The effect used to move to a page
+///
+/// `Fade`, `Push`, `Cover` and `Uncover` require PDF 1.5. Older viewers replace the page instead
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TransitionStyle {
+    /// The new page replaces the old one
+    #[default]
+    Replace,
+    /// The old page dissolves into the new one
+    Dissolve,
+    /// The new page fades in
+    Fade,
+    /// A single line sweeps across the page revealing the new page
+    Wipe(TransitionDirection),
+    /// The new page pushes the old one off the screen
+    Push(SlideDirection),
+    /// The new page slides on top of the old one
+    Cover(SlideDirection),
+    /// The old page slides off revealing the new one
+    Uncover(SlideDirection),
+    /// Two lines sweep across the page
+    Split { vertical: bool, outward: bool },
+    /// Multiple lines sweep across the page like blinds
+    Blinds { vertical: bool },
+    /// A rectangle grows or shrinks from the center of the page
+    Box { outward: bool },
+}
+impl TransitionStyle {
+    /// The PDF version the style was added in. None if every version supports it
+    pub fn required_pdf_version(&self) -> Option<PdfVersion> {
+        match self {
+            TransitionStyle::Fade
+            | TransitionStyle::Push(_)
+            | TransitionStyle::Cover(_)
+            | TransitionStyle::Uncover(_) => Some(PdfVersion::PDF_1_5),
+            _ => None,
+        }
+    }
+    fn name(&self) -> &'static str {
+        match self {
+            TransitionStyle::Replace => "R",
+            TransitionStyle::Dissolve => "Dissolve",
+            TransitionStyle::Fade => "Fade",
+            TransitionStyle::Wipe(_) => "Wipe",
+            TransitionStyle::Push(_) => "Push",
+            TransitionStyle::Cover(_) => "Cover",
+            TransitionStyle::Uncover(_) => "Uncover",
+            TransitionStyle::Split { .. } => "Split",
+            TransitionStyle::Blinds { .. } => "Blinds",
+            TransitionStyle::Box { .. } => "Box",
+        }
+    }
+}
+/// How a viewer moves to the page when presenting the document. Such as in full screen mode
+///
+/// ```rust
+/// use tux_pdf::page::{PageTransition, PdfPage, TransitionDirection, page_sizes::SLIDE_16_9};
+/// let page = PdfPage::new_from_page_size(SLIDE_16_9).with_transition(
+///     PageTransition::wipe(TransitionDirection::TopToBottom)
+///         .with_duration(0.5)
+///         .with_display_duration(10.0),
+/// );
+/// assert!(page.transition.is_some());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PageTransition {
+    pub style: TransitionStyle,
+    /// Seconds the transition takes. Viewers default to 1 second
+    pub duration: Option<f32>,
+    /// Seconds the page is shown before the viewer advances to the next page
+    ///
+    /// Written as the `/Dur` of the page. None waits for the reader
+    pub display_duration: Option<f32>,
+}
+impl PageTransition {
+    pub fn new(style: TransitionStyle) -> Self {
+        Self {
+            style,
+            ..Default::default()
+        }
+    }
+    pub fn dissolve() -> Self {
+        Self::new(TransitionStyle::Dissolve)
+    }
+    pub fn fade() -> Self {
+        Self::new(TransitionStyle::Fade)
+    }
+    pub fn wipe(direction: TransitionDirection) -> Self {
+        Self::new(TransitionStyle::Wipe(direction))
+    }
+    pub fn push(direction: SlideDirection) -> Self {
+        Self::new(TransitionStyle::Push(direction))
+    }
+    pub fn with_duration(mut self, seconds: f32) -> Self {
+        self.duration = Some(seconds);
+        self
+    }
+    pub fn with_display_duration(mut self, seconds: f32) -> Self {
+        self.display_duration = Some(seconds);
+        self
+    }
+    /// The `/Trans` dictionary of the page
+    pub(crate) fn dictionary(&self) -> Dictionary {
+        let mut dictionary = dictionary! {
+            "Type" => Object::name("Trans"),
+            "S" => Object::name(self.style.name())
+        };
+        if let Some(duration) = self.duration {
+            dictionary.set("D", duration);
+        }
+        let dimension = |vertical: bool| Object::name(if vertical { "V" } else { "H" });
+        let motion = |outward: bool| Object::name(if outward { "O" } else { "I" });
+        match self.style {
+            TransitionStyle::Wipe(direction) => {
+                dictionary.set("Di", direction.degrees());
+            }
+            TransitionStyle::Push(direction)
+            | TransitionStyle::Cover(direction)
+            | TransitionStyle::Uncover(direction) => {
+                dictionary.set("Di", direction.degrees());
+            }
+            TransitionStyle::Split { vertical, outward } => {
+                dictionary.set("Dm", dimension(vertical));
+                dictionary.set("M", motion(outward));
+            }
+            TransitionStyle::Blinds { vertical } => {
+                dictionary.set("Dm", dimension(vertical));
+            }
+            TransitionStyle::Box { outward } => {
+                dictionary.set("M", motion(outward));
+            }
+            TransitionStyle::Replace | TransitionStyle::Dissolve | TransitionStyle::Fade => {}
+        }
+        dictionary
+    }
+}
+impl From<TransitionStyle> for PageTransition {
+    fn from(style: TransitionStyle) -> Self {
+        Self::new(style)
+    }
+}
+impl PdfPage {
+    pub fn set_transition(&mut self, transition: impl Into<PageTransition>) {
+        self.transition = Some(transition.into());
+    }
+    pub fn with_transition(mut self, transition: impl Into<PageTransition>) -> Self {
+        self.set_transition(transition);
+        self
+    }
+}
+#[cfg(test)]
+mod tests {
+    use tux_pdf_low::types::Object;
+
+    use super::{PageTransition, SlideDirection, TransitionDirection, TransitionStyle};
+    use crate::{
+        document::PdfDocument,
+        page::{PdfPage, page_sizes::SLIDE_16_9},
+    };
+
+    #[test]
+    fn transition_dictionary() {
+        let wipe = PageTransition::wipe(TransitionDirection::TopToBottom)
+            .with_duration(0.5)
+            .dictionary();
+        assert_eq!(wipe.get("S"), Some(&Object::name("Wipe")));
+        assert_eq!(wipe.get("Di"), Some(&Object::from(270)));
+        assert_eq!(wipe.get("D"), Some(&Object::from(0.5f32)));
+
+        let split = PageTransition::new(TransitionStyle::Split {
+            vertical: true,
+            outward: false,
+        })
+        .dictionary();
+        assert_eq!(split.get("Dm"), Some(&Object::name("V")));
+        assert_eq!(split.get("M"), Some(&Object::name("I")));
+        assert_eq!(split.get("D"), None);
+
+        let push = PageTransition::push(SlideDirection::TopToBottom).dictionary();
+        assert_eq!(push.get("S"), Some(&Object::name("Push")));
+        assert_eq!(push.get("Di"), Some(&Object::from(270)));
+    }
+    #[test]
+    fn slide_directions_match_wipe() {
+        for direction in [SlideDirection::LeftToRight, SlideDirection::TopToBottom] {
+            assert_eq!(
+                direction.degrees(),
+                TransitionDirection::from(direction).degrees()
+            );
+        }
+    }
+    #[test]
+    fn written_to_page() -> anyhow::Result<()> {
+        let mut document = PdfDocument::new("Transitions");
+        document.add_page(
+            PdfPage::new_from_page_size(SLIDE_16_9)
+                .with_transition(PageTransition::dissolve().with_display_duration(5.0)),
+        );
+        document.add_page(PdfPage::new_from_page_size(SLIDE_16_9));
+        let writer = document.write_into_pdf_document_writer()?;
+
+        let pages: Vec<_> = writer
+            .objects()
+            .filter_map(|(_, object)| object.as_dictionary())
+            .filter(|dictionary| dictionary.get("Type") == Some(&Object::name("Page")))
+            .collect();
+        assert_eq!(pages.len(), 2);
+        let transition = pages[0]
+            .get("Trans")
+            .and_then(Object::as_dictionary)
+            .expect("The first page has a transition");
+        assert_eq!(transition.get("S"), Some(&Object::name("Dissolve")));
+        assert_eq!(pages[0].get("Dur"), Some(&Object::from(5.0f32)));
+        assert_eq!(pages[1].get("Trans"), None);
+        Ok(())
+    }
+}
@@ -0,0 +1,240 @@
+/*!
+ * # Test Support
+ *
+ * Turns the contents of a page into plain text so layouts can be checked in tests without comparing PDF files.
+ *
+ * Requires the `test-support` feature.
+ *
+ * The `render-tests` feature adds the `render` module for comparing rasterized pages.
+ *
+ * ```rust
+ * use tux_pdf::{
+ *     document::PdfDocument,
+ *     graphics::{LayerType, shapes::PaintedRect},
+ *     page::{PdfPage, page_sizes::A4},
+ *     test_support::PageSnapshot,
+ *     units::UnitType,
+ * };
+ * let document = PdfDocument::new("snapshot");
+ * let mut page = PdfPage::new_from_page_size(A4);
+ * page.add_to_layer(PaintedRect::new(10f32.pt(), 10f32.pt(), 20f32.pt(), 20f32.pt())).unwrap();
+ * let snapshot = PageSnapshot::new(&document, &page).unwrap();
+ * assert_eq!(snapshot.to_string(), "10 10 20 20 re\nf\nn\n");
+ * ```
+*/
+use std::{
+    fmt::{Display, Write},
+    path::Path,
+};
+#[cfg(feature = "render-tests")]
+pub mod render;
+
+use tux_pdf_low::types::{Object, PdfString};
+
+use crate::{
+    TuxPdfError,
+    document::PdfDocument,
+    graphics::{OperationWriter, PdfObjectType},
+    page::PdfPage,
+};
+
+/// Environment variable that makes [assert_page_ops_snapshot](crate::assert_page_ops_snapshot) rewrite the snapshot files
+pub const UPDATE_SNAPSHOTS_ENV: &str = "TUX_PDF_UPDATE_SNAPSHOTS";
+
+/// A single operation with its operands formatted as text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperationSnapshot {
+    pub operator: String,
+    pub operands: Vec<String>,
+}
+impl Display for OperationSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for operand in &self.operands {
+            write!(f, "{operand} ")?;
+        }
+        f.write_str(&self.operator)
+    }
+}
+/// Every operation of a page in order
+///
+/// Numbers are rounded to 3 decimal places so small floating point differences do not change the snapshot
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PageSnapshot(pub Vec<OperationSnapshot>);
+impl PageSnapshot {
+    /// Writes the contents of the page the same way the document does when it is saved
+    pub fn new(document: &PdfDocument, page: &PdfPage) -> Result<Self, TuxPdfError> {
+        let mut writer = OperationWriter::with_capacity(page.contents.len());
+        for object in page.contents.iter().cloned() {
+            object.write(&document.resources, &mut writer)?;
+        }
+        let operations = writer
+            .operations()
+            .into_iter()
+            .map(|operation| OperationSnapshot {
+                operator: operation.operation.to_string(),
+                operands: operation.arguments.iter().map(format_operand).collect(),
+            })
+            .collect();
+        Ok(Self(operations))
+    }
+    /// Operators in order without the operands
+    pub fn operators(&self) -> Vec<&str> {
+        self.0
+            .iter()
+            .map(|operation| operation.operator.as_str())
+            .collect()
+    }
+    /// Compares the snapshot with the file at `path`
+    ///
+    /// The file is written if it does not exist or [UPDATE_SNAPSHOTS_ENV] is set.
+    /// Returns a description of the first difference if the snapshot does not match
+    pub fn compare_with_file(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        let actual = self.to_string();
+        if std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some() || !path.exists() {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+            }
+            return std::fs::write(path, actual).map_err(|err| err.to_string());
+        }
+        let expected = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+        first_difference(&expected, &actual).map_or(Ok(()), |difference| {
+            Err(format!(
+                "Page operations do not match {}\n{difference}\nSet {UPDATE_SNAPSHOTS_ENV}=1 to update the snapshot",
+                path.display()
+            ))
+        })
+    }
+}
+impl Display for PageSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for operation in &self.0 {
+            writeln!(f, "{operation}")?;
+        }
+        Ok(())
+    }
+}
+fn first_difference(expected: &str, actual: &str) -> Option<String> {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (None, None) => return None,
+            (Some(expected), Some(actual)) if expected == actual => {}
+            (expected, actual) => {
+                return Some(format!(
+                    "line {line}\n  expected: {}\n  actual:   {}",
+                    expected.unwrap_or("<end of snapshot>"),
+                    actual.unwrap_or("<end of page>")
+                ));
+            }
+        }
+        line += 1;
+    }
+}
+fn format_number(number: f32) -> String {
+    let rounded = (number * 1000f32).round() / 1000f32;
+    // Avoids -0 showing up in the snapshot
+    if rounded == 0f32 {
+        return "0".to_string();
+    }
+    rounded.to_string()
+}
+fn format_operand(operand: &Object) -> String {
+    match operand {
+        Object::Null => "null".to_string(),
+        Object::Boolean(value) => value.to_string(),
+        Object::Integer(value) => value.to_string(),
+        Object::Real(value) => format_number(*value),
+        Object::String(PdfString::Literal(value)) => {
+            format!("({})", String::from_utf8_lossy(value))
+        }
+        Object::String(PdfString::Hexadecimal(value)) => {
+            let mut hex = String::with_capacity(value.len() * 2 + 2);
+            hex.push('<');
+            for byte in value {
+                let _ = write!(hex, "{byte:02X}");
+            }
+            hex.push('>');
+            hex
+        }
+        Object::Name(name) => format!("/{}", String::from_utf8_lossy(&name.0)),
+        Object::Array(items) => {
+            let items: Vec<String> = items.iter().map(format_operand).collect();
+            format!("[{}]", items.join(" "))
+        }
+        other => format!("{other:?}"),
+    }
+}
+/// Compares the operations of a page with a snapshot file in `tests/snapshots/{name}.ops`
+///
+/// The file is created the first time the test runs. Set the `TUX_PDF_UPDATE_SNAPSHOTS` environment variable to update it
+///
+/// ```rust,no_run
+/// use tux_pdf::{assert_page_ops_snapshot, document::PdfDocument, page::{PdfPage, page_sizes::A4}};
+/// let document = PdfDocument::new("snapshot");
+/// let page = PdfPage::new_from_page_size(A4);
+/// assert_page_ops_snapshot!(&document, &page, "empty_page");
+/// ```
+#[macro_export]
+macro_rules! assert_page_ops_snapshot {
+    ($document:expr, $page:expr, $name:expr) => {{
+        let snapshot = $crate::test_support::PageSnapshot::new($document, $page)
+            .expect("Unable to write the page operations");
+        let path = ::std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("snapshots")
+            .join(format!("{}.ops", $name));
+        if let Err(message) = snapshot.compare_with_file(&path) {
+            panic!("{}", message);
+        }
+    }};
+}
+#[cfg(test)]
+mod tests {
+    use super::{PageSnapshot, first_difference};
+    use crate::{
+        document::PdfDocument,
+        graphics::{LayerType, PdfPosition, shapes::PaintedRect},
+        page::{PdfPage, page_sizes::A4},
+        units::UnitType,
+    };
+
+    #[test]
+    fn rect_snapshot() -> anyhow::Result<()> {
+        let document = PdfDocument::new("rect_snapshot");
+        let mut page = PdfPage::new_from_page_size(A4);
+        page.add_to_layer(PaintedRect::new(
+            10.5f32.pt(),
+            10f32.pt(),
+            20f32.pt(),
+            (1f32 / 3f32).pt(),
+        ))?;
+        let snapshot = PageSnapshot::new(&document, &page)?;
+        assert_eq!(snapshot.operators(), vec!["re", "f", "n"]);
+        assert_eq!(snapshot.to_string(), "10.5 10 20 0.333 re\nf\nn\n");
+        Ok(())
+    }
+
+    #[test]
+    fn moved_rect_is_a_difference() -> anyhow::Result<()> {
+        let document = PdfDocument::new("moved_rect");
+        let mut page = PdfPage::new_from_page_size(A4);
+        page.add_to_layer(PaintedRect::from((
+            PdfPosition::new(0f32.pt(), 0f32.pt()),
+            A4,
+        )))?;
+        let expected = PageSnapshot::new(&document, &page)?.to_string();
+        page.contents.clear();
+        page.add_to_layer(PaintedRect::from((
+            PdfPosition::new(1f32.pt(), 0f32.pt()),
+            A4,
+        )))?;
+        let actual = PageSnapshot::new(&document, &page)?.to_string();
+        let difference = first_difference(&expected, &actual).unwrap();
+        assert!(difference.starts_with("line 1"));
+        assert_eq!(first_difference(&expected, &expected), None);
+        Ok(())
+    }
+}
@@ -0,0 +1,294 @@
+//! Rasterizes pages to images for visual regression tests
+//!
+//! Rendering is done by an external program behind the [PageRasterizer] trait.
+//! [GhostscriptRasterizer] uses the `gs` command. Implement the trait to use another renderer such as pdfium.
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use image::{Rgba, RgbaImage};
+use thiserror::Error;
+
+use crate::{TuxPdfError, document::PdfDocument};
+
+use super::UPDATE_SNAPSHOTS_ENV;
+
+#[derive(Debug, Error)]
+pub enum RenderTestError {
+    #[error(transparent)]
+    Pdf(#[from] TuxPdfError),
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    Image(#[from] image::ImageError),
+    #[error("Renderer {renderer} failed: {message}")]
+    RendererFailed {
+        renderer: &'static str,
+        message: String,
+    },
+    #[error("Page {0} does not exist")]
+    PageOutOfRange(usize),
+}
+/// Turns a page of a PDF file into an image
+pub trait PageRasterizer {
+    /// Renders the page at `page_index` (starting at 0) of the PDF file
+    fn rasterize(
+        &self,
+        pdf: &[u8],
+        page_index: usize,
+        dpi: f32,
+    ) -> Result<RgbaImage, RenderTestError>;
+    /// Saves the document and renders one of its pages
+    fn rasterize_document(
+        &self,
+        document: PdfDocument,
+        page_index: usize,
+        dpi: f32,
+    ) -> Result<RgbaImage, RenderTestError> {
+        let mut pdf = Vec::new();
+        document.save_to(&mut pdf)?;
+        self.rasterize(&pdf, page_index, dpi)
+    }
+}
+/// Renders pages with the Ghostscript `gs` command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GhostscriptRasterizer {
+    /// Path or name of the Ghostscript executable
+    pub executable: PathBuf,
+}
+impl Default for GhostscriptRasterizer {
+    fn default() -> Self {
+        Self {
+            executable: PathBuf::from("gs"),
+        }
+    }
+}
+impl GhostscriptRasterizer {
+    /// Checks if the executable can be run
+    pub fn is_available(&self) -> bool {
+        Command::new(&self.executable)
+            .arg("--version")
+            .output()
+            .is_ok_and(|output| output.status.success())
+    }
+}
+impl PageRasterizer for GhostscriptRasterizer {
+    fn rasterize(
+        &self,
+        pdf: &[u8],
+        page_index: usize,
+        dpi: f32,
+    ) -> Result<RgbaImage, RenderTestError> {
+        // Ghostscript needs to seek within the file so it is written to a temporary file
+        static NEXT_FILE: AtomicUsize = AtomicUsize::new(0);
+        let input = std::env::temp_dir().join(format!(
+            "tux-pdf-render-{}-{}.pdf",
+            std::process::id(),
+            NEXT_FILE.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::write(&input, pdf)?;
+        let page_number = page_index + 1;
+        let output = Command::new(&self.executable)
+            .args(["-q", "-dNOPAUSE", "-dBATCH", "-dSAFER", "-sDEVICE=pngalpha"])
+            .arg(format!("-r{dpi}"))
+            .arg(format!("-dFirstPage={page_number}"))
+            .arg(format!("-dLastPage={page_number}"))
+            .arg("-sOutputFile=%stdout")
+            .arg(&input)
+            .output();
+        let _ = std::fs::remove_file(&input);
+        let output = output?;
+        if !output.status.success() {
+            return Err(RenderTestError::RendererFailed {
+                renderer: "ghostscript",
+                message: String::from_utf8_lossy(&output.stderr).into_owned(),
+            });
+        }
+        if output.stdout.is_empty() {
+            return Err(RenderTestError::PageOutOfRange(page_index));
+        }
+        Ok(image::load_from_memory(&output.stdout)?.to_rgba8())
+    }
+}
+/// Settings for [ImageDiff::compare]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DiffOptions {
+    /// How different two pixels can be before they count as different. From 0 to 1
+    ///
+    /// The difference is weighted by how sensitive eyes are to each color
+    pub pixel_threshold: f32,
+    /// The fraction of pixels that can be different for the images to match
+    pub max_different_ratio: f32,
+}
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            pixel_threshold: 0.1,
+            max_different_ratio: 0.001,
+        }
+    }
+}
+/// The result of comparing two images
+#[derive(Debug, Clone)]
+pub struct ImageDiff {
+    pub different_pixels: u64,
+    pub total_pixels: u64,
+    /// The actual image faded with the different pixels in red
+    pub diff_image: RgbaImage,
+    /// Images of different sizes never match
+    pub same_size: bool,
+}
+impl ImageDiff {
+    /// Compares the images pixel by pixel
+    pub fn compare(expected: &RgbaImage, actual: &RgbaImage, options: &DiffOptions) -> Self {
+        let same_size = expected.dimensions() == actual.dimensions();
+        let (width, height) = actual.dimensions();
+        let mut diff_image = RgbaImage::new(width, height);
+        let mut different_pixels = 0;
+        for (x, y, actual_pixel) in actual.enumerate_pixels() {
+            let different = expected
+                .get_pixel_checked(x, y)
+                .is_none_or(|expected_pixel| {
+                    pixel_difference(expected_pixel, actual_pixel) > options.pixel_threshold
+                });
+            let diff_pixel = if different {
+                different_pixels += 1;
+                Rgba([255, 0, 0, 255])
+            } else {
+                let faded = (luminance(actual_pixel) * 0.3 * 255f32) as u8 + 178;
+                Rgba([faded, faded, faded, 255])
+            };
+            diff_image.put_pixel(x, y, diff_pixel);
+        }
+        Self {
+            different_pixels,
+            total_pixels: u64::from(width) * u64::from(height),
+            diff_image,
+            same_size,
+        }
+    }
+    pub fn different_ratio(&self) -> f32 {
+        if self.total_pixels == 0 {
+            return 0f32;
+        }
+        self.different_pixels as f32 / self.total_pixels as f32
+    }
+    pub fn is_match(&self, options: &DiffOptions) -> bool {
+        self.same_size && self.different_ratio() <= options.max_different_ratio
+    }
+}
+/// Luminance of the pixel blended onto white. From 0 to 1
+fn luminance(pixel: &Rgba<u8>) -> f32 {
+    let [r, g, b] = blend_on_white(pixel);
+    0.299 * r + 0.587 * g + 0.114 * b
+}
+fn blend_on_white(pixel: &Rgba<u8>) -> [f32; 3] {
+    let alpha = f32::from(pixel[3]) / 255f32;
+    let blend = |channel: u8| (f32::from(channel) / 255f32) * alpha + (1f32 - alpha);
+    [blend(pixel[0]), blend(pixel[1]), blend(pixel[2])]
+}
+/// Difference of two pixels in the YIQ color space. From 0 to 1
+fn pixel_difference(expected: &Rgba<u8>, actual: &Rgba<u8>) -> f32 {
+    let yiq = |pixel: &Rgba<u8>| {
+        let [r, g, b] = blend_on_white(pixel);
+        (
+            0.299 * r + 0.587 * g + 0.114 * b,
+            0.596 * r - 0.274 * g - 0.322 * b,
+            0.211 * r - 0.523 * g + 0.312 * b,
+        )
+    };
+    let (y1, i1, q1) = yiq(expected);
+    let (y2, i2, q2) = yiq(actual);
+    let delta = 0.5053 * (y1 - y2).powi(2) + 0.299 * (i1 - i2).powi(2) + 0.1957 * (q1 - q2).powi(2);
+    // 0.5053 is the largest possible delta. Between black and white
+    (delta / 0.5053).sqrt()
+}
+/// Compares a rendered page with the reference image at `reference`
+///
+/// The reference is only written if [UPDATE_SNAPSHOTS_ENV] is set. A missing reference is an error so commit the references.
+/// When the images do not match the diff image is saved next to the reference with the extension `diff.png`
+pub fn compare_with_reference(
+    actual: &RgbaImage,
+    reference: impl AsRef<Path>,
+    options: &DiffOptions,
+) -> Result<(), String> {
+    let reference = reference.as_ref();
+    if std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some() {
+        if let Some(parent) = reference.parent() {
+            std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        }
+        return actual.save(reference).map_err(|err| err.to_string());
+    }
+    if !reference.exists() {
+        return Err(format!(
+            "No reference image at {}\nSet {UPDATE_SNAPSHOTS_ENV}=1 to create it",
+            reference.display()
+        ));
+    }
+    let expected = image::open(reference)
+        .map_err(|err| err.to_string())?
+        .to_rgba8();
+    let diff = ImageDiff::compare(&expected, actual, options);
+    if diff.is_match(options) {
+        return Ok(());
+    }
+    let diff_path = reference.with_extension("diff.png");
+    diff.diff_image
+        .save(&diff_path)
+        .map_err(|err| err.to_string())?;
+    Err(format!(
+        "Rendered page does not match {}. {} of {} pixels are different{}. See {}\nSet {UPDATE_SNAPSHOTS_ENV}=1 to update the reference",
+        reference.display(),
+        diff.different_pixels,
+        diff.total_pixels,
+        if diff.same_size {
+            ""
+        } else {
+            " and the sizes do not match"
+        },
+        diff_path.display()
+    ))
+}
+#[cfg(test)]
+mod tests {
+    use image::{Rgba, RgbaImage};
+
+    use super::{DiffOptions, ImageDiff, compare_with_reference, pixel_difference};
+
+    #[test]
+    fn pixel_difference_range() {
+        let black = Rgba([0, 0, 0, 255]);
+        let white = Rgba([255, 255, 255, 255]);
+        assert!((pixel_difference(&black, &white) - 1f32).abs() < 0.001);
+        assert_eq!(pixel_difference(&black, &black), 0f32);
+        // Transparent pixels are treated as white
+        assert_eq!(pixel_difference(&white, &Rgba([0, 0, 0, 0])), 0f32);
+    }
+
+    #[test]
+    fn compare_images() {
+        let options = DiffOptions::default();
+        let expected = RgbaImage::from_pixel(10, 10, Rgba([255, 255, 255, 255]));
+        let mut actual = expected.clone();
+        assert!(ImageDiff::compare(&expected, &actual, &options).is_match(&options));
+
+        actual.put_pixel(5, 5, Rgba([0, 0, 0, 255]));
+        let diff = ImageDiff::compare(&expected, &actual, &options);
+        assert_eq!(diff.different_pixels, 1);
+        assert!(!diff.is_match(&options));
+
+        let smaller = RgbaImage::from_pixel(5, 5, Rgba([255, 255, 255, 255]));
+        assert!(!ImageDiff::compare(&expected, &smaller, &options).is_match(&options));
+    }
+    #[test]
+    fn missing_reference_fails() {
+        let image = RgbaImage::from_pixel(1, 1, Rgba([255, 255, 255, 255]));
+        let reference = std::env::temp_dir().join("tux-pdf-missing-reference.png");
+        let error = compare_with_reference(&image, &reference, &DiffOptions::default())
+            .expect_err("A missing reference must fail");
+        assert!(error.starts_with("No reference image"));
+        assert!(!reference.exists());
+    }
+}
@@ -0,0 +1,77 @@
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, TimeZone, Timelike};
+
+use super::{DateOutOfRange, PdfDate, PdfDateTime, PdfDateTimeType, Weekday};
+
+impl From<NaiveDate> for PdfDate {
+    fn from(date: NaiveDate) -> Self {
+        PdfDate::new(date.year(), date.month(), date.day())
+            .expect("chrono::NaiveDate is always a valid date")
+    }
+}
+impl TryFrom<PdfDate> for NaiveDate {
+    type Error = DateOutOfRange;
+    /// Fails if the year is outside of the range chrono supports
+    fn try_from(date: PdfDate) -> Result<Self, Self::Error> {
+        NaiveDate::from_ymd_opt(date.year(), date.month(), date.day()).ok_or(DateOutOfRange(date))
+    }
+}
+impl From<NaiveDateTime> for PdfDateTime {
+    fn from(date_time: NaiveDateTime) -> Self {
+        PdfDateTime {
+            date: date_time.date().into(),
+            hour: date_time.hour() as u8,
+            minute: date_time.minute() as u8,
+            // Leap seconds are reported as 59
+            second: date_time.second().min(59) as u8,
+            offset_minutes: 0,
+        }
+    }
+}
+impl<Tz: TimeZone> From<DateTime<Tz>> for PdfDateTime {
+    fn from(date_time: DateTime<Tz>) -> Self {
+        let offset_seconds = date_time.fixed_offset().offset().local_minus_utc();
+        PdfDateTime::from(date_time.naive_local()).with_offset_minutes((offset_seconds / 60) as i16)
+    }
+}
+impl<Tz: TimeZone> PdfDateTimeType for DateTime<Tz> {
+    fn format_pdf_date_time(&self) -> String {
+        PdfDateTime::from(self.clone()).format_pdf_date_time()
+    }
+}
+impl From<chrono::Weekday> for Weekday {
+    fn from(weekday: chrono::Weekday) -> Self {
+        match weekday {
+            chrono::Weekday::Mon => Weekday::Monday,
+            chrono::Weekday::Tue => Weekday::Tuesday,
+            chrono::Weekday::Wed => Weekday::Wednesday,
+            chrono::Weekday::Thu => Weekday::Thursday,
+            chrono::Weekday::Fri => Weekday::Friday,
+            chrono::Weekday::Sat => Weekday::Saturday,
+            chrono::Weekday::Sun => Weekday::Sunday,
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, NaiveDate, TimeZone};
+
+    use crate::time_impl::{DateOutOfRange, PdfDate, PdfDateTime, PdfDateTimeType};
+
+    #[test]
+    fn chrono_date_time() {
+        let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+        let date_time = offset.with_ymd_and_hms(2024, 12, 16, 17, 29, 49).unwrap();
+        assert_eq!(date_time.format_pdf_date_time(), "D:20241216172949+02'00");
+        assert_eq!(PdfDateTime::from(date_time).offset_minutes, 120);
+    }
+    #[test]
+    fn date_out_of_chrono_range() {
+        let date = PdfDate::new(2024, 2, 29).unwrap();
+        assert_eq!(
+            NaiveDate::try_from(date),
+            Ok(NaiveDate::from_ymd_opt(2024, 2, 29).unwrap())
+        );
+        let date = PdfDate::new(i32::MAX, 1, 1).unwrap();
+        assert_eq!(NaiveDate::try_from(date), Err(DateOutOfRange(date)));
+    }
+}
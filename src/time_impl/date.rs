@@ -0,0 +1,182 @@
+use std::fmt::Display;
+
+use thiserror::Error;
+
+/// A day of the week
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+impl Weekday {
+    const ALL: [Weekday; 7] = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+        Weekday::Sunday,
+    ];
+    /// The next day of the week
+    pub fn succ(&self) -> Self {
+        Self::ALL[(self.num_days_from_monday() as usize + 1) % 7]
+    }
+    pub fn num_days_from_monday(&self) -> u32 {
+        *self as u32
+    }
+    pub fn num_days_from_sunday(&self) -> u32 {
+        (self.num_days_from_monday() + 1) % 7
+    }
+}
+
+/// A calendar date that does not depend on a date time crate.
+///
+/// Conversions exist for the `time` and `chrono` crates when their features are enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PdfDate {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+impl PdfDate {
+    /// Returns `None` if the date does not exist
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month as u8) as u32 {
+            return None;
+        }
+        Some(Self {
+            year,
+            month: month as u8,
+            day: day as u8,
+        })
+    }
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+    pub fn month(&self) -> u32 {
+        self.month as u32
+    }
+    pub fn day(&self) -> u32 {
+        self.day as u32
+    }
+    pub fn weekday(&self) -> Weekday {
+        // 1970-01-01 was a Thursday
+        let days = self.days_since_unix_epoch() + 3;
+        Weekday::ALL[days.rem_euclid(7) as usize]
+    }
+    pub fn days_in_month(&self) -> u32 {
+        days_in_month(self.year, self.month) as u32
+    }
+    pub fn first_day_of_month(&self) -> Self {
+        Self { day: 1, ..*self }
+    }
+    pub fn last_day_of_month(&self) -> Self {
+        Self {
+            day: days_in_month(self.year, self.month),
+            ..*self
+        }
+    }
+    /// Adds (or subtracts if negative) a number of days
+    pub fn add_days(&self, days: i64) -> Self {
+        Self::from_days_since_unix_epoch(self.days_since_unix_epoch() + days)
+    }
+    /// Iterates over every day starting with this date
+    pub fn iter_days(&self) -> impl Iterator<Item = PdfDate> {
+        let start = self.days_since_unix_epoch();
+        (start..).map(Self::from_days_since_unix_epoch)
+    }
+    /// Based on `days_from_civil` from <https://howardhinnant.github.io/date_algorithms.html>
+    pub fn days_since_unix_epoch(&self) -> i64 {
+        let month = self.month as i64;
+        let year = self.year as i64 - i64::from(month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let day_of_year =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146097 + day_of_era - 719468
+    }
+    /// Based on `civil_from_days` from <https://howardhinnant.github.io/date_algorithms.html>
+    pub fn from_days_since_unix_epoch(days: i64) -> Self {
+        let days = days + 719468;
+        let era = days.div_euclid(146097);
+        let day_of_era = days - era * 146097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+        let month = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        Self {
+            year: year as i32,
+            month: month as u8,
+            day: day as u8,
+        }
+    }
+}
+impl Display for PdfDate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+/// The date can not be represented by the date time crate it is converted into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("{0} is outside of the range of supported dates")]
+pub struct DateOutOfRange(pub PdfDate);
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::{PdfDate, Weekday};
+
+    #[test]
+    fn epoch_round_trip() {
+        let epoch = PdfDate::new(1970, 1, 1).unwrap();
+        assert_eq!(epoch.days_since_unix_epoch(), 0);
+        assert_eq!(epoch.weekday(), Weekday::Thursday);
+        for days in [-1_000_000, -1, 0, 59, 60, 19_000, 1_000_000] {
+            let date = PdfDate::from_days_since_unix_epoch(days);
+            assert_eq!(date.days_since_unix_epoch(), days);
+        }
+    }
+    #[test]
+    fn month_lengths() {
+        assert_eq!(PdfDate::new(2024, 2, 10).unwrap().days_in_month(), 29);
+        assert_eq!(PdfDate::new(2023, 2, 10).unwrap().days_in_month(), 28);
+        assert_eq!(PdfDate::new(2023, 2, 29), None);
+        assert_eq!(
+            PdfDate::new(2024, 12, 31).unwrap().add_days(1),
+            PdfDate::new(2025, 1, 1).unwrap()
+        );
+    }
+    #[test]
+    fn weekdays() {
+        assert_eq!(PdfDate::new(2024, 9, 1).unwrap().weekday(), Weekday::Sunday);
+        assert_eq!(
+            PdfDate::new(2024, 12, 16).unwrap().weekday(),
+            Weekday::Monday
+        );
+        assert_eq!(Weekday::Sunday.succ(), Weekday::Monday);
+        assert_eq!(Weekday::Sunday.num_days_from_sunday(), 0);
+    }
+}
@@ -0,0 +1,74 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::{PdfDate, PdfDateTimeType};
+
+/// A date and time with a UTC offset that does not depend on a date time crate.
+///
+/// Used by the document metadata and the calendar layouts.
+/// Conversions exist for the `time` and `chrono` crates when their features are enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PdfDateTime {
+    pub date: PdfDate,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    /// The offset from UTC in minutes
+    pub offset_minutes: i16,
+}
+impl PdfDateTime {
+    /// Returns `None` if the time is invalid
+    pub fn new(date: PdfDate, hour: u8, minute: u8, second: u8) -> Option<Self> {
+        if hour > 23 || minute > 59 || second > 59 {
+            return None;
+        }
+        Some(Self {
+            date,
+            hour,
+            minute,
+            second,
+            offset_minutes: 0,
+        })
+    }
+    pub fn with_offset_minutes(mut self, offset_minutes: i16) -> Self {
+        self.offset_minutes = offset_minutes;
+        self
+    }
+    /// The date time in UTC from the number of seconds since the unix epoch
+    pub fn from_unix_timestamp(timestamp: i64) -> Self {
+        let seconds_of_day = timestamp.rem_euclid(86_400);
+        Self {
+            date: PdfDate::from_days_since_unix_epoch(timestamp.div_euclid(86_400)),
+            hour: (seconds_of_day / 3600) as u8,
+            minute: (seconds_of_day % 3600 / 60) as u8,
+            second: (seconds_of_day % 60) as u8,
+            offset_minutes: 0,
+        }
+    }
+}
+impl From<SystemTime> for PdfDateTime {
+    fn from(time: SystemTime) -> Self {
+        let timestamp = match time.duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration.as_secs() as i64,
+            Err(before_epoch) => -(before_epoch.duration().as_secs() as i64),
+        };
+        Self::from_unix_timestamp(timestamp)
+    }
+}
+impl PdfDateTimeType for PdfDateTime {
+    fn format_pdf_date_time(&self) -> String {
+        let sign = if self.offset_minutes < 0 { '-' } else { '+' };
+        let offset = self.offset_minutes.unsigned_abs();
+        format!(
+            "D:{:04}{:02}{:02}{:02}{:02}{:02}{}{:02}'{:02}",
+            self.date.year(),
+            self.date.month(),
+            self.date.day(),
+            self.hour,
+            self.minute,
+            self.second,
+            sign,
+            offset / 60,
+            offset % 60
+        )
+    }
+}
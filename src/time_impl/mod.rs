@@ -1,11 +1,19 @@
-use time::OffsetDateTime;
-use time::format_description;
+//! Date and time types used by the document metadata and the calendar layouts.
+//!
+//! [PdfDateTime] and [PdfDate] do not depend on a date time crate.
+//! Enable the `time` or `chrono` feature to convert from that crate's types.
 use tux_pdf_low::types::Object;
-pub fn format_time_offset_date_time(offset_date_time: OffsetDateTime) -> String {
-    let format = "D:[year][month padding:zero][day padding:zero][hour padding:zero][minute padding:zero][second padding:zero][offset_hour sign:mandatory]'[offset_minute]";
-    let format = format_description::parse(format).unwrap();
-    offset_date_time.format(&format).unwrap()
-}
+#[cfg(feature = "chrono")]
+mod chrono_impl;
+mod date;
+mod date_time;
+#[cfg(feature = "time")]
+mod time_crate_impl;
+pub use date::*;
+pub use date_time::*;
+#[cfg(feature = "time")]
+pub use time_crate_impl::format_time_offset_date_time;
+
 pub trait PdfDateTimeType {
     fn format_pdf_date_time(&self) -> String;
 
@@ -13,29 +21,18 @@ pub trait PdfDateTimeType {
         Object::string_literal_owned(self.format_pdf_date_time())
     }
 }
-impl PdfDateTimeType for OffsetDateTime {
-    fn format_pdf_date_time(&self) -> String {
-        format_time_offset_date_time(*self)
-    }
-}
 #[cfg(test)]
 mod tests {
+    use super::{PdfDate, PdfDateTime, PdfDateTimeType};
 
-    use time::OffsetDateTime;
     #[test]
-    fn print_tests() {
-        let now = OffsetDateTime::now_utc();
-        let result = super::format_time_offset_date_time(now);
-        println!("Result: {:?}", result);
+    fn format_without_backend() {
+        let date_time = PdfDateTime::from_unix_timestamp(1734362989);
+        assert_eq!(date_time.format_pdf_date_time(), "D:20241216152949+00'00");
 
-        let now = OffsetDateTime::now_local().unwrap();
-        let result = super::format_time_offset_date_time(now);
-        println!("Result: {:?}", result);
-    }
-    #[test]
-    fn test() {
-        let now = OffsetDateTime::from_unix_timestamp(1734362989).unwrap();
-        let result = super::format_time_offset_date_time(now);
-        assert_eq!(result, "D:20241216152949+00'00");
+        let date_time = PdfDateTime::new(PdfDate::new(2024, 1, 2).unwrap(), 3, 4, 5)
+            .unwrap()
+            .with_offset_minutes(-330);
+        assert_eq!(date_time.format_pdf_date_time(), "D:20240102030405-05'30");
     }
 }
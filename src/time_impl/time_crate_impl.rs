@@ -0,0 +1,61 @@
+use time::{Date, OffsetDateTime, format_description};
+
+use super::{PdfDate, PdfDateTime, PdfDateTimeType};
+
+pub fn format_time_offset_date_time(offset_date_time: OffsetDateTime) -> String {
+    let format = "D:[year][month padding:zero][day padding:zero][hour padding:zero][minute padding:zero][second padding:zero][offset_hour sign:mandatory]'[offset_minute]";
+    let format = format_description::parse_borrowed::<1>(format).unwrap();
+    offset_date_time.format(&format).unwrap()
+}
+impl PdfDateTimeType for OffsetDateTime {
+    fn format_pdf_date_time(&self) -> String {
+        format_time_offset_date_time(*self)
+    }
+}
+impl From<Date> for PdfDate {
+    fn from(date: Date) -> Self {
+        PdfDate::new(date.year(), date.month() as u32, date.day() as u32)
+            .expect("time::Date is always a valid date")
+    }
+}
+impl From<OffsetDateTime> for PdfDateTime {
+    fn from(date_time: OffsetDateTime) -> Self {
+        PdfDateTime {
+            date: date_time.date().into(),
+            hour: date_time.hour(),
+            minute: date_time.minute(),
+            second: date_time.second(),
+            offset_minutes: date_time.offset().whole_minutes(),
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use time::OffsetDateTime;
+
+    use crate::time_impl::{PdfDateTime, PdfDateTimeType};
+    #[test]
+    fn print_tests() {
+        let now = OffsetDateTime::now_utc();
+        let result = super::format_time_offset_date_time(now);
+        println!("Result: {:?}", result);
+
+        let now = OffsetDateTime::now_local().unwrap();
+        let result = super::format_time_offset_date_time(now);
+        println!("Result: {:?}", result);
+    }
+    #[test]
+    fn test() {
+        let now = OffsetDateTime::from_unix_timestamp(1734362989).unwrap();
+        let result = super::format_time_offset_date_time(now);
+        assert_eq!(result, "D:20241216152949+00'00");
+    }
+    #[test]
+    fn matches_pdf_date_time() {
+        let now = OffsetDateTime::from_unix_timestamp(1734362989).unwrap();
+        assert_eq!(
+            PdfDateTime::from(now).format_pdf_date_time(),
+            now.format_pdf_date_time()
+        );
+    }
+}
@@ -4,7 +4,11 @@
 |-----------|------------|-----------------------------|
 | [Pt]        | Point      | Standard PDF Unit           |
 | [Mm]        | millimeter |                             |
+| [In]        | inch       |                             |
 | [Px]        | pixels     | Requires DPI for conversion |
+| [Percentage] | percentage | Relative to a reference length such as the page size |
+
+[Length] can hold any of the units above and is resolved into [Pt] once the reference length is known.
 
 */
 
@@ -226,11 +230,48 @@ serde_transparent!(Pt(f32));
 into_lo_object!(Pt);
 basic_trait_impl!(Pt(f32));
 negation!(Pt);
+impl Pt {
+    /// The value as it is written to a PDF with `precision` digits after the decimal point
+    ///
+    /// Pass the precision of the document. See [PdfDocument::set_real_precision](crate::document::PdfDocument::set_real_precision)
+    pub fn as_written(self, precision: Option<u8>) -> Pt {
+        Pt(tux_pdf_low::types::round_real(self.0, precision))
+    }
+}
 impl From<Pt> for Mm {
     fn from(pt: Pt) -> Self {
         Mm(pt.0 * 0.352_778_f32)
     }
 }
+/// Scale in inches
+#[derive(Debug, Default, Copy, Clone, PartialOrd)]
+pub struct In(pub f32);
+serde_transparent!(In(f32));
+impl_partialeq!(In);
+self_math!(In(f32));
+into_lo_object!(In);
+basic_trait_impl!(In(f32));
+negation!(In);
+impl From<In> for Pt {
+    fn from(inch: In) -> Self {
+        Pt(inch.0 * 72f32)
+    }
+}
+impl From<Pt> for In {
+    fn from(pt: Pt) -> Self {
+        In(pt.0 / 72f32)
+    }
+}
+impl From<In> for Mm {
+    fn from(inch: In) -> Self {
+        Mm(inch.0 * 25.4f32)
+    }
+}
+impl From<Mm> for In {
+    fn from(mm: Mm) -> Self {
+        In(mm.0 / 25.4f32)
+    }
+}
 impl From<usize> for Pt {
     fn from(val: usize) -> Self {
         Pt(val as f32)
@@ -255,6 +296,7 @@ pub trait UnitType {
     fn mm(&self) -> Mm;
     fn pt(&self) -> Pt;
     fn px(&self) -> Px;
+    fn inch(&self) -> In;
 }
 impl UnitType for Pt {
     fn mm(&self) -> Mm {
@@ -266,6 +308,9 @@ impl UnitType for Pt {
     fn px(&self) -> Px {
         Px((self.0 * 1.333_333_4) as i64)
     }
+    fn inch(&self) -> In {
+        In::from(*self)
+    }
 }
 
 impl UnitType for Mm {
@@ -273,11 +318,29 @@ impl UnitType for Mm {
         *self
     }
     fn pt(&self) -> Pt {
-        Pt(self.0 / 2.834_646_f32)
+        Pt::from(*self)
     }
     fn px(&self) -> Px {
         Px((self.0) as i64)
     }
+    fn inch(&self) -> In {
+        In::from(*self)
+    }
+}
+
+impl UnitType for In {
+    fn mm(&self) -> Mm {
+        Mm::from(*self)
+    }
+    fn pt(&self) -> Pt {
+        Pt::from(*self)
+    }
+    fn px(&self) -> Px {
+        self.pt().px()
+    }
+    fn inch(&self) -> In {
+        *self
+    }
 }
 
 impl UnitType for f32 {
@@ -290,6 +353,9 @@ impl UnitType for f32 {
     fn px(&self) -> Px {
         Px(*self as i64)
     }
+    fn inch(&self) -> In {
+        In(*self)
+    }
 }
 macro_rules! unit_type_core_types {
     (
@@ -308,6 +374,9 @@ macro_rules! unit_type_core_types {
                 fn px(&self) -> Px {
                     Px(*self as i64)
                 }
+                fn inch(&self) -> In {
+                    In(*self as f32)
+                }
             }
         )*
     };
@@ -315,7 +384,98 @@ macro_rules! unit_type_core_types {
 // Following implementations just use as conversion and assumes what is passed doesn't need scaling
 // If it needs scaling convert it to the appropriate type first
 unit_type_core_types!(i64, i32, i16, i8, u64, u32, u16, u8);
+/// A percentage where `1.0` is 100%
 #[derive(Debug, Default, Copy, Clone, PartialEq, PartialOrd)]
 pub struct Percentage(pub f32);
 
 basic_trait_impl!(Percentage(f32));
+impl Percentage {
+    /// The percentage of the reference length
+    pub fn resolve(self, reference: Pt) -> Pt {
+        reference * self.0
+    }
+}
+/// A length in any unit.
+///
+/// Percentages are relative to a reference length, for positions and sizes on a page that is the page width or height.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Length {
+    Pt(Pt),
+    Mm(Mm),
+    In(In),
+    Percentage(Percentage),
+}
+impl Default for Length {
+    fn default() -> Self {
+        Length::Pt(Pt::default())
+    }
+}
+impl Length {
+    /// Converts the length into [Pt]
+    ///
+    /// `reference` is only used for [Length::Percentage]
+    pub fn resolve(self, reference: Pt) -> Pt {
+        match self {
+            Length::Pt(pt) => pt,
+            Length::Mm(mm) => mm.into(),
+            Length::In(inch) => inch.into(),
+            Length::Percentage(percentage) => percentage.resolve(reference),
+        }
+    }
+}
+impl From<Pt> for Length {
+    fn from(pt: Pt) -> Self {
+        Length::Pt(pt)
+    }
+}
+impl From<Mm> for Length {
+    fn from(mm: Mm) -> Self {
+        Length::Mm(mm)
+    }
+}
+impl From<In> for Length {
+    fn from(inch: In) -> Self {
+        Length::In(inch)
+    }
+}
+impl From<Percentage> for Length {
+    fn from(percentage: Percentage) -> Self {
+        Length::Percentage(percentage)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::{In, Length, Mm, Percentage, Pt, UnitType};
+
+    #[test]
+    fn unit_conversions() {
+        assert_eq!(1f32.inch().pt(), Pt(72f32));
+        assert_eq!(25.4f32.mm().pt(), Pt(72f32));
+        assert_eq!(Mm::from(In(1f32)), Mm(25.4f32));
+        assert_eq!(72f32.pt().inch(), In(1f32));
+    }
+    #[test]
+    fn written_points_read_back() -> anyhow::Result<()> {
+        use tux_pdf_low::types::{DEFAULT_REAL_PRECISION, Object, PdfObjectType};
+        for value in [
+            Pt::from(Mm(10f32)),
+            Pt(12.3f32),
+            Pt(-0.125f32),
+            Pt(595.276f32),
+        ] {
+            let mut buffer = Vec::new();
+            Object::from(value).encode(&mut buffer)?;
+            let written: f32 = String::from_utf8(buffer)?.parse()?;
+            assert_eq!(Pt(written), value);
+            assert_eq!(Pt(written), value.as_written(Some(DEFAULT_REAL_PRECISION)));
+        }
+        Ok(())
+    }
+    #[test]
+    fn resolve_lengths() {
+        let reference = Pt(200f32);
+        assert_eq!(Length::from(Pt(10f32)).resolve(reference), Pt(10f32));
+        assert_eq!(Length::from(In(1f32)).resolve(reference), Pt(72f32));
+        assert_eq!(Length::from(Percentage(0.25)).resolve(reference), Pt(50f32));
+    }
+}
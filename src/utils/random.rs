@@ -1,10 +1,31 @@
-use rand::{RngExt, SeedableRng, distr::Alphanumeric};
+use std::{
+    hash::BuildHasher,
+    sync::{
+        LazyLock,
+        atomic::{AtomicU64, Ordering},
+    },
+};
 
-/// Returns a string with 32 random characters
+const ALPHANUMERIC: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+static SEED: LazyLock<std::hash::RandomState> = LazyLock::new(std::hash::RandomState::new);
+static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Returns a string of random alphanumeric characters
+///
+/// These are only used for resource names so they do not need to be cryptographically secure.
+/// Not relying on the OS random number generator allows this to work on targets such as `wasm32-unknown-unknown`
 pub(crate) fn random_character_string(length: usize) -> String {
-    rand::rngs::StdRng::from_rng(&mut rand::rng())
-        .sample_iter(&Alphanumeric)
-        .take(length)
-        .map(char::from)
+    let mut state = SEED.hash_one(COUNTER.fetch_add(1, Ordering::Relaxed));
+    (0..length)
+        .map(|_| {
+            // SplitMix64
+            state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut value = state;
+            value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            value ^= value >> 31;
+            ALPHANUMERIC[(value % ALPHANUMERIC.len() as u64) as usize] as char
+        })
         .collect()
 }
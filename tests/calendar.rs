@@ -0,0 +1,148 @@
+use test_utils::{create_test_document, fonts_dir, save_pdf_doc};
+use tux_pdf::{
+    document::{BuiltinFont, owned_ttf_parser::OwnedPdfTtfFont},
+    graphics::{PdfPosition, TextStyle, color::GRAY_RGB, size::Size},
+    layouts::{
+        LayoutItemType,
+        calendar::{
+            CalendarStyles, MonthCalendar, MonthCalendarLayout, WeekCalendarLayout, WeekStartDay,
+            YearCalendarLayout,
+        },
+    },
+    page::{PdfPage, page_sizes::A4},
+    time_impl::{PdfDate, PdfDateTime},
+    units::UnitType,
+};
+mod test_utils;
+
+#[test]
+pub fn month_calendar_with_events() -> anyhow::Result<()> {
+    test_utils::init_logger();
+    let mut doc = create_test_document("month_calendar_with_events");
+    let roboto_font_reader =
+        std::fs::File::open(fonts_dir().join("Roboto").join("Roboto-Regular.ttf"))?;
+    let roboto_font = OwnedPdfTtfFont::new_from_reader(roboto_font_reader, 0)?;
+    let roboto = doc.resources.fonts.register_external_font(roboto_font)?;
+
+    let page_size = A4.landscape();
+    let calendar = MonthCalendar::new(2024, 9, WeekStartDay::Monday).expect("Valid month");
+    let mut layout = MonthCalendarLayout::new(
+        calendar,
+        PdfPosition {
+            x: 20f32.pt(),
+            y: page_size.height - 20f32.pt(),
+        },
+        Size::new(page_size.width - 40f32.pt(), page_size.height - 40f32.pt()),
+    )
+    .with_styles(CalendarStyles {
+        text_styles: TextStyle {
+            font_ref: roboto,
+            font_size: 10f32.pt(),
+            ..Default::default()
+        },
+        outside_month_color: Some(GRAY_RGB),
+        ..Default::default()
+    });
+    let day = |day| PdfDate::new(2024, 9, day).unwrap();
+    layout.add_event(day(2), "Labor Day");
+    layout.add_event(day(10), "Team meeting with a long title that needs to wrap");
+    for hour in 8..20 {
+        layout.add_event(day(18), format!("{hour}:00 Appointment"));
+    }
+    // Outside of the month. Should be ignored
+    layout.add_event(PdfDate::new(2024, 10, 1).unwrap(), "October");
+
+    let mut page = PdfPage::new_from_page_size(page_size);
+    layout.render(&doc, &mut page)?;
+    doc.add_page(page);
+
+    save_pdf_doc(doc, "month_calendar_with_events")?;
+    Ok(())
+}
+
+#[test]
+pub fn year_calendar() -> anyhow::Result<()> {
+    test_utils::init_logger();
+    let mut doc = create_test_document("year_calendar");
+    let helvetica = doc
+        .resources
+        .fonts
+        .register_builtin_font(BuiltinFont::Helvetica);
+    let page_size = A4;
+    let layout = YearCalendarLayout::new(
+        2024,
+        PdfPosition {
+            x: 20f32.pt(),
+            y: page_size.height - 20f32.pt(),
+        },
+        Size::new(page_size.width - 40f32.pt(), page_size.height - 40f32.pt()),
+    )
+    .with_week_start(WeekStartDay::Sunday)
+    .with_styles(CalendarStyles {
+        text_styles: TextStyle {
+            font_ref: helvetica,
+            font_size: 6f32.pt(),
+            ..Default::default()
+        },
+        border_width: 0.5f32.pt(),
+        ..Default::default()
+    });
+
+    let mut page = PdfPage::new_from_page_size(page_size);
+    layout.render(&doc, &mut page)?;
+    doc.add_page(page);
+
+    save_pdf_doc(doc, "year_calendar")?;
+    Ok(())
+}
+
+#[test]
+pub fn week_calendar_schedule() -> anyhow::Result<()> {
+    test_utils::init_logger();
+    let mut doc = create_test_document("week_calendar_schedule");
+    let helvetica = doc
+        .resources
+        .fonts
+        .register_builtin_font(BuiltinFont::Helvetica);
+    let page_size = A4.landscape();
+    let date = PdfDate::new(2024, 9, 18).unwrap();
+    let mut layout = WeekCalendarLayout::new(
+        date,
+        WeekStartDay::Monday,
+        PdfPosition {
+            x: 20f32.pt(),
+            y: page_size.height - 20f32.pt(),
+        },
+        Size::new(page_size.width - 40f32.pt(), page_size.height - 40f32.pt()),
+    )
+    .with_hours(7..19)
+    .with_styles(CalendarStyles {
+        text_styles: TextStyle {
+            font_ref: helvetica,
+            font_size: 9f32.pt(),
+            ..Default::default()
+        },
+        event_background_color: Some(GRAY_RGB),
+        ..Default::default()
+    });
+    let at = |day, hour, minute| {
+        PdfDateTime::new(PdfDate::new(2024, 9, day).unwrap(), hour, minute, 0).unwrap()
+    };
+    layout.add_event(at(16, 9, 0), at(16, 10, 30), "Planning");
+    layout.add_event(
+        at(18, 13, 0),
+        at(18, 14, 0),
+        "Lunch with a long name that wraps",
+    );
+    // Starts before the visible hours
+    layout.add_event(at(20, 6, 0), at(20, 8, 0), "Early run");
+    // Outside of the week
+    layout.add_event(at(25, 9, 0), at(25, 10, 0), "Next week");
+
+    let mut page = PdfPage::new_from_page_size(page_size);
+    layout.render(&doc, &mut page)?;
+    doc.add_page(page);
+
+    save_pdf_doc(doc, "week_calendar_schedule")?;
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use test_utils::{create_test_document, save_pdf_doc};
+use tux_pdf::{
+    document::BuiltinFont,
+    graphics::{PdfPosition, TextStyle},
+    layouts::{
+        LayoutItemType,
+        list::{ListItem, ListLayout, ListMarker, ListStyles},
+    },
+    page::{PdfPage, page_sizes::A4},
+    units::UnitType,
+};
+mod test_utils;
+
+#[test]
+pub fn nested_lists() -> anyhow::Result<()> {
+    test_utils::init_logger();
+    let mut doc = create_test_document("nested_lists");
+    let helvetica = doc
+        .resources
+        .fonts
+        .register_builtin_font(BuiltinFont::Helvetica);
+    let mut page = PdfPage::new_from_page_size(A4);
+
+    let styles = ListStyles {
+        text_styles: TextStyle {
+            font_ref: helvetica,
+            font_size: 12f32.pt(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let bullets = ListLayout::new(
+        PdfPosition::new(40f32.pt(), A4.height - 40f32.pt()),
+        250f32.pt(),
+    )
+    .with_styles(styles.clone())
+    .add_item("A short item")
+    .add_item(
+        ListItem::from("An item with enough text that it needs to wrap onto a second line")
+            .add_child("Nested item")
+            .add_child(ListItem::from("Another nested item").add_child("Even deeper")),
+    )
+    .add_item("The last item");
+    let mut bullets_for_size = bullets.clone();
+    let bullet_size = bullets_for_size.calculate_size(&doc)?;
+    assert!(bullet_size.height > 0f32.pt());
+    bullets.render(&doc, &mut page)?;
+
+    let numbered = ListLayout::new(
+        PdfPosition::new(40f32.pt(), A4.height - 80f32.pt() - bullet_size.height),
+        250f32.pt(),
+    )
+    .with_styles(styles)
+    .with_markers(vec![ListMarker::Decimal, ListMarker::LowerRoman])
+    .add_item("Decimal one")
+    .add_item(
+        ListItem::from("Decimal two")
+            .add_child("Roman one")
+            .add_child("Roman two"),
+    )
+    .add_item("Decimal three");
+    numbered.render(&doc, &mut page)?;
+
+    doc.add_page(page);
+    save_pdf_doc(doc, "nested_lists")
+}
@@ -0,0 +1,55 @@
+//! Visual regression tests. Requires the `render-tests` feature and Ghostscript
+#![cfg(feature = "render-tests")]
+use tux_pdf::{
+    document::PdfDocument,
+    graphics::{
+        GraphicStyles, GraphicsGroup, LayerType,
+        color::{BLUE_RGB, RED_RGB},
+        shapes::PaintedRect,
+    },
+    page::{PdfPage, page_sizes::A4},
+    test_support::render::{
+        DiffOptions, GhostscriptRasterizer, PageRasterizer, compare_with_reference,
+    },
+    units::UnitType,
+};
+
+fn shapes_document() -> anyhow::Result<PdfDocument> {
+    let mut doc = PdfDocument::new("render_shapes");
+    let mut page = PdfPage::new_from_page_size(A4);
+    page.add_to_layer(GraphicsGroup {
+        styles: Some(GraphicStyles {
+            fill_color: Some(RED_RGB),
+            ..Default::default()
+        }),
+        items: vec![PaintedRect::new(20f32.pt(), 20f32.pt(), 100f32.pt(), 50f32.pt()).into()],
+        ..Default::default()
+    })?;
+    page.add_to_layer(GraphicsGroup {
+        styles: Some(GraphicStyles {
+            fill_color: Some(BLUE_RGB),
+            ..Default::default()
+        }),
+        items: vec![PaintedRect::new(60f32.pt(), 100f32.pt(), 80f32.pt(), 80f32.pt()).into()],
+        ..Default::default()
+    })?;
+    doc.add_page(page);
+    Ok(doc)
+}
+
+#[test]
+fn render_shapes() -> anyhow::Result<()> {
+    let rasterizer = GhostscriptRasterizer::default();
+    if !rasterizer.is_available() {
+        eprintln!("Ghostscript is not installed. Skipping render test");
+        return Ok(());
+    }
+    let image = rasterizer.rasterize_document(shapes_document()?, 0, 72f32)?;
+    let reference = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+        .join("render_shapes.png");
+    compare_with_reference(&image, reference, &DiffOptions::default())
+        .map_err(anyhow::Error::msg)?;
+    Ok(())
+}
@@ -7,7 +7,12 @@ use tux_pdf::{
         ExternalLoadedFont, PdfDocument, PdfXObjectImage, owned_ttf_parser::OwnedPdfTtfFont,
     },
     graphics::{
-        LayerType, PdfPosition, TextBlock, TextBlockContent, TextItem, TextLine, image::PdfImage,
+        ClipRegion, ClippedGroup, GraphicStyles, GraphicsGroup, LayerType, PdfPosition, TextBlock,
+        TextBlockContent, TextItem, TextLine,
+        image::PdfImage,
+        primitives::{PaintMode, PathBuilder},
+        shapes::PaintedRect,
+        size::Size,
         text::TextStyle,
     },
     layouts::LayoutItemType,
@@ -355,3 +360,75 @@ fn all_noto_color_emojis() -> anyhow::Result<()> {
 
     Ok(())
 }
+/// Crops an image with a rounded rectangle and clips a group of shapes to a triangle
+#[test]
+pub fn clipping_regions() -> anyhow::Result<()> {
+    init_logger();
+    let mut doc = test_utils::create_test_document("Clipping Test");
+    let code_image_reader = BufReader::new(File::open(images_dir().join("code_image.png"))?);
+    let image = PngDecoder::new(code_image_reader)?;
+    let code_image_ref = doc.add_xobject(PdfXObjectImage::load_from_decoder(image)?);
+
+    let mut page = PdfPage::new_from_page_size(A4);
+
+    let mut cropped_image = ClippedGroup::new(ClipRegion::rounded_rect(
+        PdfPosition::new(10.0.pt(), 600.0.pt()),
+        Size::new(200.0.pt(), 150.0.pt()),
+        20.0.pt(),
+    ));
+    cropped_image.add_to_layer(
+        PdfImage::new(code_image_ref)
+            .with_position(PdfPosition::new(10.0.pt(), 600.0.pt()))
+            .with_scale(2f32, 2f32)
+            .with_dpi(300.0),
+    )?;
+    page.add_to_layer(cropped_image)?;
+
+    let mut triangle = GraphicsGroup::new()
+        .with_styles(GraphicStyles {
+            fill_color: Some(test_utils::random_color()),
+            ..Default::default()
+        })
+        .with_clip(ClipRegion::polygon([
+            (300.0.pt(), 400.0.pt()),
+            (500.0.pt(), 400.0.pt()),
+            (400.0.pt(), 550.0.pt()),
+        ]));
+    triangle.add_item(PaintedRect::new(
+        250.0.pt(),
+        350.0.pt(),
+        300.0.pt(),
+        300.0.pt(),
+    ));
+    page.add_to_layer(triangle)?;
+    doc.add_page(page);
+
+    test_utils::save_pdf_doc(doc, "clipping_regions")
+}
+/// Draws a rounded tab shape using the path builder
+#[test]
+pub fn path_builder() -> anyhow::Result<()> {
+    init_logger();
+    let mut doc = test_utils::create_test_document("Path Builder Test");
+    let mut page = PdfPage::new_from_page_size(A4);
+
+    let path = PathBuilder::new()
+        .move_to((100.0.pt(), 500.0.pt()))
+        .line_to((300.0.pt(), 500.0.pt()))
+        .arc_to((300.0.pt(), 550.0.pt()), 50.0.pt(), -90.0, 90.0)
+        .line_to((100.0.pt(), 600.0.pt()))
+        .close()
+        .with_mode(PaintMode::FillStroke)
+        .build();
+    let mut group = GraphicsGroup::new().with_styles(GraphicStyles {
+        fill_color: Some(test_utils::random_color()),
+        outline_color: Some(test_utils::random_color()),
+        line_width: Some(2f32.pt()),
+        ..Default::default()
+    });
+    group.add_item(path);
+    page.add_to_layer(group)?;
+    doc.add_page(page);
+
+    test_utils::save_pdf_doc(doc, "path_builder")
+}
@@ -1,16 +1,22 @@
 mod test_utils;
+use std::sync::{Arc, Mutex};
+
 use chrono::Local;
 use rand::{RngExt, SeedableRng, rngs::StdRng};
 use test_utils::{destination_dir, fonts_dir};
 use tux_pdf::{
-    document::{PdfDocument, owned_ttf_parser::OwnedPdfTtfFont},
+    document::{BuiltinFont, PdfDocument, owned_ttf_parser::OwnedPdfTtfFont},
     graphics::{
-        TextStyle,
+        PartialTextStyle, PdfObject, PdfPosition, TextStyle,
         color::{BLACK_RGB, GRAY_RGB, WHITE_RGB},
+        shapes::{OutlineRect, PaintedRect},
+        size::Size,
         styles::{Margin, Padding},
     },
     layouts::table::{
-        Column, Row, RowStyles, Table, TablePageRules, TableStyles, TableValue,
+        CellRenderArea, CellRenderer, Column, ColumnGroup, ColumnSizing, HeaderGroupRow,
+        HorizontalOverflow, NewPageFn, Row, RowStyles, Table, TableError, TablePageRules,
+        TableStyles, TableValue,
         builder::{GridStyleGroup, TableColumnMaxWidth, TableColumnMinWidth},
     },
     page::{PdfPage, page_sizes::A4},
@@ -72,6 +78,7 @@ fn table_test() -> anyhow::Result<()> {
 
     let table = Table {
         columns,
+        header_groups: Vec::new(),
         rows: actual_rows,
         styles: TableStyles {
             text_styles: TextStyle {
@@ -87,7 +94,7 @@ fn table_test() -> anyhow::Result<()> {
             }),
             ..Default::default()
         },
-        new_page: |_| {
+        new_page: NewPageFn::new(|_| {
             let page = PdfPage::new_from_page_size(A4.landscape());
             let table_start = A4.landscape().height - 10f32.pt();
             let table_end = 10f32.pt();
@@ -98,7 +105,7 @@ fn table_test() -> anyhow::Result<()> {
                 margin: Some(Margin::left_and_right(10f32.pt(), 10f32.pt())),
             };
             Ok((page_rules, page))
-        },
+        }),
     };
 
     let table_start = A4.landscape().height - 10f32.pt();
@@ -182,6 +189,7 @@ fn table_test_large_column_but_limited_space() -> anyhow::Result<()> {
 
     let table = Table {
         columns,
+        header_groups: Vec::new(),
         rows: actual_rows,
         styles: TableStyles {
             text_styles: TextStyle {
@@ -197,7 +205,7 @@ fn table_test_large_column_but_limited_space() -> anyhow::Result<()> {
             }),
             ..Default::default()
         },
-        new_page: |_| {
+        new_page: NewPageFn::new(|_| {
             let page = PdfPage::new_from_page_size(A4.landscape());
             let table_start = A4.landscape().height - 10f32.pt();
             let table_end = 10f32.pt();
@@ -208,7 +216,7 @@ fn table_test_large_column_but_limited_space() -> anyhow::Result<()> {
                 margin: Some(Margin::left_and_right(10f32.pt(), 10f32.pt())),
             };
             Ok((page_rules, page))
-        },
+        }),
     };
 
     let table_start = A4.landscape().height - 10f32.pt();
@@ -231,3 +239,525 @@ fn table_test_large_column_but_limited_space() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn table_with_header_groups() -> anyhow::Result<()> {
+    test_utils::init_logger();
+    let columns: Vec<_> = vec![
+        Column::from("Order Number"),
+        Column::from("Customer Name"),
+        Column::from("Order Type"),
+        Column::from("Order Size"),
+        Column::from("Notes").with_min_width(TableColumnMinWidth::AutoFill),
+    ];
+    let header_groups = vec![
+        HeaderGroupRow::from(vec![
+            ColumnGroup::new("Order", 2),
+            ColumnGroup::new("Details", 3).with_styles(RowStyles {
+                background_color: Some(WHITE_RGB),
+                ..Default::default()
+            }),
+        ]),
+        HeaderGroupRow::from(vec![ColumnGroup::new("All Orders", 5)]),
+    ];
+    let mut actual_rows = Vec::new();
+    for number in 0..75 {
+        let row = Row::from(vec![
+            format!("12345{}", number).into(),
+            format!("Customer {}", number).into(),
+            format!("Type {}", number).into(),
+            format!("Size {}", number).into(),
+            TableValue::BlankSpace,
+        ]);
+        actual_rows.push(row);
+    }
+    let mut doc = PdfDocument::new("Table Header Groups Test");
+    test_utils::set_metadata_for_test(&mut doc);
+
+    let roboto_font_reader =
+        std::fs::File::open(fonts_dir().join("Roboto").join("Roboto-Regular.ttf"))?;
+    let roboto_font = OwnedPdfTtfFont::new_from_reader(roboto_font_reader, 0)?;
+
+    let roboto = doc.font_map().register_external_font(roboto_font)?;
+
+    let table = Table {
+        columns,
+        header_groups,
+        rows: actual_rows,
+        styles: TableStyles {
+            text_styles: TextStyle {
+                font_ref: roboto,
+                font_size: 15.0.pt(),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        new_page: NewPageFn::new(|_| {
+            let page = PdfPage::new_from_page_size(A4);
+            let page_rules = TablePageRules {
+                page_size: A4,
+                table_start_y: Some(A4.height - 10f32.pt()),
+                table_stop_y: Some(10f32.pt()),
+                margin: Some(Margin::left_and_right(10f32.pt(), 10f32.pt())),
+            };
+            Ok((page_rules, page))
+        }),
+    };
+    table.validate()?;
+
+    let page = PdfPage::new_from_page_size(A4);
+    let page_rules = TablePageRules {
+        page_size: A4,
+        table_start_y: Some(A4.height - 10f32.pt()),
+        table_stop_y: Some(10f32.pt()),
+        margin: Some(Margin::left_and_right(10f32.pt(), 10f32.pt())),
+    };
+    let report = table.render(&mut doc, (page_rules, page))?;
+    assert!(report.pages.len() > 1);
+    for page_report in &report.pages {
+        let page = &doc.pages()[page_report.page_index];
+        // The header groups are repeated on every page
+        let group_position = |header: &str| {
+            let positions: Vec<PdfPosition> = page
+                .contents
+                .iter()
+                .filter_map(|object| match object {
+                    PdfObject::TextBlock(text) if text.content.text() == header => {
+                        Some(text.position)
+                    }
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(positions.len(), 1, "{header}");
+            positions[0]
+        };
+        let order = group_position("Order");
+        let details = group_position("Details");
+        let all_orders = group_position("All Orders");
+        // One row for each header group row
+        assert_eq!(order.y, details.y);
+        assert_ne!(order.y, all_orders.y);
+
+        // Each group starts at the first column it spans
+        let header_cells = &page_report.header_cells;
+        assert_eq!(order.x, header_cells[0].content_position.x);
+        assert_eq!(details.x, header_cells[2].content_position.x);
+        assert_eq!(all_orders.x, header_cells[0].content_position.x);
+
+        // Both rows are above the column headers and within the table
+        let header_top = header_cells[0].cell_position.y + header_cells[0].cell_size.height;
+        let table_top = page_report.bounds.upper_right().y;
+        for group in [order, all_orders] {
+            assert!(group.y > header_top && group.y < table_top);
+        }
+    }
+    let pdf = doc.write_into_pdf_document_writer()?;
+    let mut file = std::fs::File::create(destination_dir().join("table_header_groups.pdf"))?;
+    pdf.save(&mut file)?;
+
+    Ok(())
+}
+
+#[test]
+fn table_header_groups_must_fit_columns() {
+    let table = Table {
+        columns: vec![Column::from("One"), Column::from("Two")],
+        header_groups: vec![HeaderGroupRow::from(vec![ColumnGroup::new("Group", 3)])],
+        ..Default::default()
+    };
+    assert!(matches!(
+        table.validate(),
+        Err(TableError::HeaderGroupSpanMismatch {
+            columns: 2,
+            spans: 3,
+            in_row: 0
+        })
+    ));
+}
+
+#[test]
+fn wide_table_horizontal_pagination() -> anyhow::Result<()> {
+    test_utils::init_logger();
+    let columns: Vec<_> = (0..16)
+        .map(|index| Column::from(format!("Column Number {}", index)))
+        .collect();
+    let header_groups = vec![HeaderGroupRow::from(vec![
+        ColumnGroup::new("Key", 1),
+        ColumnGroup::new("Values", 15),
+    ])];
+    let rows: Vec<_> = (0..60)
+        .map(|row| {
+            Row::from(
+                (0..16)
+                    .map(|column| format!("Value {row}-{column}"))
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect();
+    let mut doc = PdfDocument::new("Wide Table Test");
+    test_utils::set_metadata_for_test(&mut doc);
+
+    let roboto_font_reader =
+        std::fs::File::open(fonts_dir().join("Roboto").join("Roboto-Regular.ttf"))?;
+    let roboto_font = OwnedPdfTtfFont::new_from_reader(roboto_font_reader, 0)?;
+    let roboto = doc.font_map().register_external_font(roboto_font)?;
+
+    let table = Table {
+        columns,
+        header_groups,
+        rows,
+        styles: TableStyles {
+            text_styles: TextStyle {
+                font_ref: roboto,
+                font_size: 12.0.pt(),
+                ..Default::default()
+            },
+            horizontal_overflow: HorizontalOverflow::Paginate {
+                repeated_columns: 1,
+            },
+            ..Default::default()
+        },
+        new_page: NewPageFn::new(|_| {
+            let page = PdfPage::new_from_page_size(A4.landscape());
+            let page_rules = TablePageRules {
+                page_size: A4.landscape(),
+                table_start_y: Some(A4.landscape().height - 10f32.pt()),
+                table_stop_y: Some(10f32.pt()),
+                margin: Some(Margin::left_and_right(10f32.pt(), 10f32.pt())),
+            };
+            Ok((page_rules, page))
+        }),
+    };
+    let first_page = table.new_page.create(&mut doc)?;
+    let report = table.render(&mut doc, first_page)?;
+    assert_eq!(report.pages.len(), doc.number_of_pages());
+    for (index, page) in report.pages.iter().enumerate() {
+        assert_eq!(page.page_index, index);
+        // The key column is repeated on every set of pages
+        assert_eq!(page.columns[0], 0);
+        assert_eq!(page.column_widths.len(), page.columns.len());
+        assert_eq!(page.cells.len(), page.rows.len());
+    }
+
+    let pdf = doc.write_into_pdf_document_writer()?;
+    let mut file = std::fs::File::create(destination_dir().join("table_wide_horizontal.pdf"))?;
+    pdf.save(&mut file)?;
+    Ok(())
+}
+
+#[test]
+fn table_measure_all_columns() -> anyhow::Result<()> {
+    test_utils::init_logger();
+    let columns = vec![
+        Column::from("ID"),
+        Column::from("Name"),
+        Column::from("Notes"),
+    ];
+    let mut rows: Vec<_> = (0..150)
+        .map(|row| {
+            Row::from(vec![
+                format!("{row}"),
+                format!("Name {row}"),
+                "Short".to_owned(),
+            ])
+        })
+        .collect();
+    // Only the last page contains a wide value. All pages should still use its width
+    let wide_row = Row::from(vec![
+        "150".to_owned(),
+        "A much longer name than every other row".to_owned(),
+        "A note that is wider than the rest".to_owned(),
+    ]);
+    rows.push(wide_row.clone());
+    // The first row uses a larger font. Its ID is measured with its own style
+    let large_text = RowStyles {
+        text_style: Some(
+            PartialTextStyle {
+                font_size: Some(30.0.pt()),
+                ..Default::default()
+            }
+            .into(),
+        ),
+        ..Default::default()
+    };
+    rows[0] = Row::from(vec![
+        "9999".to_owned(),
+        "Name 0".to_owned(),
+        "Short".to_owned(),
+    ])
+    .with_styles(large_text);
+    let mut doc = PdfDocument::new("Measured Column Table Test");
+    test_utils::set_metadata_for_test(&mut doc);
+
+    let roboto_font_reader =
+        std::fs::File::open(fonts_dir().join("Roboto").join("Roboto-Regular.ttf"))?;
+    let roboto_font = OwnedPdfTtfFont::new_from_reader(roboto_font_reader, 0)?;
+    let roboto = doc.font_map().register_external_font(roboto_font)?;
+
+    let table = Table {
+        columns,
+        header_groups: Vec::new(),
+        rows,
+        styles: TableStyles {
+            text_styles: TextStyle {
+                font_ref: roboto,
+                font_size: 12.0.pt(),
+                ..Default::default()
+            },
+            column_sizing: ColumnSizing::MeasureAll,
+            ..Default::default()
+        },
+        new_page: NewPageFn::new(|_| {
+            let page = PdfPage::new_from_page_size(A4);
+            let page_rules = TablePageRules {
+                page_size: A4,
+                table_start_y: Some(A4.height - 10f32.pt()),
+                table_stop_y: Some(10f32.pt()),
+                margin: Some(Margin::left_and_right(10f32.pt(), 10f32.pt())),
+            };
+            Ok((page_rules, page))
+        }),
+    };
+    let text_style = table.styles.text_styles.clone();
+    let padding = table
+        .styles
+        .cell_content_padding
+        .horizontal_value()
+        .unwrap_or_default();
+    let first_page = table.new_page.create(&mut doc)?;
+    let report = table.render(&mut doc, first_page)?;
+
+    assert!(report.pages.len() > 1);
+    let widths = &report.pages[0].column_widths;
+    assert!(
+        report
+            .pages
+            .iter()
+            .all(|page| &page.column_widths == widths)
+    );
+    let large_id = Row::from(vec!["9999"]).calculate_sizes(
+        &doc,
+        &TextStyle {
+            font_size: 30.0.pt(),
+            ..text_style.clone()
+        },
+    )?;
+    let wide_values = wide_row.calculate_sizes(&doc, &text_style)?;
+    assert_eq!(widths[0], large_id[0].width + padding);
+    assert_eq!(widths[1], wide_values[1].width + padding);
+    assert_eq!(widths[2], wide_values[2].width + padding);
+
+    let pdf = doc.write_into_pdf_document_writer()?;
+    let mut file = std::fs::File::create(destination_dir().join("table_measure_all.pdf"))?;
+    pdf.save(&mut file)?;
+    Ok(())
+}
+
+/// The progress and area of every cell a [ProgressBar] was rendered in
+type RenderedCells = Arc<Mutex<Vec<(f32, CellRenderArea)>>>;
+#[derive(Debug)]
+struct ProgressBar {
+    progress: f32,
+    rendered: RenderedCells,
+}
+impl CellRenderer for ProgressBar {
+    fn content_size(
+        &self,
+        _: &PdfDocument,
+        text_style: &TextStyle,
+    ) -> Result<Size, tux_pdf::TuxPdfError> {
+        Ok(Size::new(100f32.pt(), text_style.font_size))
+    }
+    fn render(
+        &self,
+        _: &PdfDocument,
+        area: CellRenderArea,
+        _: &TextStyle,
+    ) -> Result<Vec<PdfObject>, tux_pdf::TuxPdfError> {
+        self.rendered.lock().unwrap().push((self.progress, area));
+        let outline = OutlineRect {
+            position: PdfPosition {
+                x: area.content_position.x,
+                y: area.content_position.y + area.content_size.height,
+            },
+            size: area.content_size,
+        };
+        let bar = PaintedRect {
+            position: area.content_position,
+            size: Size::new(
+                area.content_size.width * self.progress,
+                area.content_size.height,
+            ),
+            ..Default::default()
+        };
+        Ok(vec![outline.into(), bar.into()])
+    }
+}
+#[test]
+fn table_custom_cell_renderer() -> anyhow::Result<()> {
+    test_utils::init_logger();
+    let columns = vec![Column::from("Task"), Column::from("Progress")];
+    let rendered = RenderedCells::default();
+    let mut rows: Vec<_> = (0..=10)
+        .map(|task| Row {
+            values: vec![
+                TableValue::from(format!("Task {task}")).into(),
+                TableValue::custom(ProgressBar {
+                    progress: task as f32 / 10f32,
+                    rendered: rendered.clone(),
+                })
+                .into(),
+            ],
+            styles: None,
+        })
+        .collect();
+    // The last row uses a larger font. Its height is measured with its own style
+    rows[10].styles = Some(RowStyles {
+        text_style: Some(
+            PartialTextStyle {
+                font_size: Some(30.0.pt()),
+                ..Default::default()
+            }
+            .into(),
+        ),
+        ..Default::default()
+    });
+    let mut doc = PdfDocument::new("Custom Cell Table Test");
+    test_utils::set_metadata_for_test(&mut doc);
+
+    let roboto_font_reader =
+        std::fs::File::open(fonts_dir().join("Roboto").join("Roboto-Regular.ttf"))?;
+    let roboto_font = OwnedPdfTtfFont::new_from_reader(roboto_font_reader, 0)?;
+    let roboto = doc.font_map().register_external_font(roboto_font)?;
+
+    let table = Table {
+        columns,
+        header_groups: Vec::new(),
+        rows,
+        styles: TableStyles {
+            text_styles: TextStyle {
+                font_ref: roboto,
+                font_size: 12.0.pt(),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        new_page: NewPageFn::new(|_| {
+            let page = PdfPage::new_from_page_size(A4);
+            let page_rules = TablePageRules {
+                page_size: A4,
+                table_start_y: Some(A4.height - 10f32.pt()),
+                table_stop_y: Some(10f32.pt()),
+                margin: Some(Margin::left_and_right(10f32.pt(), 10f32.pt())),
+            };
+            Ok((page_rules, page))
+        }),
+    };
+    let first_page = table.new_page.create(&mut doc)?;
+    table.render(&mut doc, first_page)?;
+
+    let rendered = rendered.lock().unwrap();
+    // Once for every row, in row order, and only for the progress column
+    let progress: Vec<_> = rendered.iter().map(|(progress, _)| *progress).collect();
+    let expected: Vec<_> = (0..=10).map(|task| task as f32 / 10f32).collect();
+    assert_eq!(progress, expected);
+    let (_, first) = rendered[0];
+    assert!(first.content_position.x > 10f32.pt());
+    for (_, area) in rendered.iter() {
+        assert_eq!(area.content_position.x, first.content_position.x);
+        assert_eq!(area.content_size.width, first.content_size.width);
+        assert!(area.content_size.width >= 100f32.pt());
+        assert!(area.content_size.height >= 12f32.pt());
+    }
+    for (_, area) in &rendered[..10] {
+        assert!(area.content_size.height < 30f32.pt());
+    }
+    assert!(rendered[10].1.content_size.height >= 30f32.pt());
+    for pair in rendered.windows(2) {
+        assert!(pair[1].1.content_position.y < pair[0].1.content_position.y);
+    }
+
+    let pdf = doc.write_into_pdf_document_writer()?;
+    let mut file = std::fs::File::create(destination_dir().join("table_custom_cells.pdf"))?;
+    pdf.save(&mut file)?;
+    Ok(())
+}
+#[test]
+fn table_pages_call_page_created_hooks() -> anyhow::Result<()> {
+    test_utils::init_logger();
+    let mut doc = PdfDocument::new("Page Created Hooks");
+    let roboto_font_reader =
+        std::fs::File::open(fonts_dir().join("Roboto").join("Roboto-Regular.ttf"))?;
+    let roboto_font = OwnedPdfTtfFont::new_from_reader(roboto_font_reader, 0)?;
+    let roboto = doc.font_map().register_external_font(roboto_font)?;
+
+    let created = Arc::new(Mutex::new(Vec::new()));
+    let hook_created = created.clone();
+    doc.on_page_created(move |page, number| {
+        assert!(page.contents.is_empty());
+        hook_created.lock().unwrap().push(number.0);
+    });
+
+    let table = Table {
+        columns: vec![Column::from("Number"), Column::from("Name")],
+        rows: (0..150)
+            .map(|row| Row::from(vec![format!("{row}"), format!("Name {row}")]))
+            .collect(),
+        styles: TableStyles {
+            text_styles: TextStyle {
+                font_ref: roboto,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        new_page: NewPageFn::new(|_| {
+            let page_rules = TablePageRules {
+                page_size: A4,
+                table_start_y: Some(A4.height - 10f32.pt()),
+                table_stop_y: Some(10f32.pt()),
+                margin: Some(Margin::left_and_right(10f32.pt(), 10f32.pt())),
+            };
+            Ok((page_rules, PdfPage::new_from_page_size(A4)))
+        }),
+        ..Default::default()
+    };
+    // The first page is not created by the table
+    doc.add_page(PdfPage::new_from_page_size(A4));
+    let first_page = table.new_page.create(&mut doc)?;
+    table.render(&mut doc, first_page)?;
+
+    let pages = doc.number_of_pages();
+    assert!(pages > 2);
+    assert_eq!(*created.lock().unwrap(), (3..=pages).collect::<Vec<_>>());
+    Ok(())
+}
+#[test]
+fn new_page_closure_keeps_state() -> anyhow::Result<()> {
+    let mut doc = PdfDocument::new("Stateful New Page");
+    doc.font_map().register_builtin_font(BuiltinFont::Helvetica);
+
+    let numbers = Arc::new(Mutex::new(Vec::new()));
+    let created = numbers.clone();
+    // Owned by the closure and kept between calls
+    let mut page_number = 0;
+    let table = Table {
+        columns: vec![Column::from("Number"), Column::from("Name")],
+        rows: (0..150)
+            .map(|row| Row::from(vec![format!("{row}"), format!("Name {row}")]))
+            .collect(),
+        new_page: NewPageFn::new(move |_| {
+            page_number += 1;
+            created.lock().unwrap().push(page_number);
+            let page = PdfPage::new_from_page_size(A4);
+            Ok((TablePageRules::for_page(&page), page))
+        }),
+        ..Default::default()
+    };
+    let first_page = table.new_page.create(&mut doc)?;
+    table.render(&mut doc, first_page)?;
+
+    let pages = doc.number_of_pages();
+    assert!(pages > 2);
+    assert_eq!(*numbers.lock().unwrap(), (1..=pages).collect::<Vec<_>>());
+    Ok(())
+}
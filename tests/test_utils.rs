@@ -2,6 +2,7 @@ use rand::{RngExt, rngs::ThreadRng};
 use tux_pdf::{
     document::PdfDocument,
     graphics::color::{Color, Rgb},
+    time_impl::PdfDateTime,
 };
 include!("test_utils_external.rs");
 
@@ -20,8 +21,7 @@ pub fn random_color() -> Color {
 pub fn set_metadata_for_test(document: &mut PdfDocument) {
     document.metadata.info.author = Some("tux-pdf tests".to_string());
     document.metadata.info.creator = Some("tux-pdf tests".to_string());
-    document.metadata.info.creation_date =
-        Some(time::OffsetDateTime::now_local().unwrap_or(time::OffsetDateTime::now_utc()));
+    document.metadata.info.creation_date = Some(PdfDateTime::from(std::time::SystemTime::now()));
 }
 #[allow(dead_code)]
 pub fn create_test_document(name: &str) -> PdfDocument {
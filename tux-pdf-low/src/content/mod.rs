@@ -1,23 +1,83 @@
+use std::fmt::Display;
+
 use crate::types::{Object, PdfObjectType, PdfType};
+/// The operator of an [Operation]
+///
+/// Operators are almost always known at compile time. So we avoid allocating a string for each operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operator {
+    Static(&'static str),
+    Owned(String),
+}
+impl Operator {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Operator::Static(operator) => operator,
+            Operator::Owned(operator) => operator,
+        }
+    }
+}
+impl AsRef<[u8]> for Operator {
+    fn as_ref(&self) -> &[u8] {
+        self.as_str().as_bytes()
+    }
+}
+impl AsRef<str> for Operator {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+impl Display for Operator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+impl From<&'static str> for Operator {
+    fn from(value: &'static str) -> Self {
+        Self::Static(value)
+    }
+}
+impl From<String> for Operator {
+    fn from(value: String) -> Self {
+        Self::Owned(value)
+    }
+}
+impl PartialEq<str> for Operator {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+impl PartialEq<&str> for Operator {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
 #[derive(Debug, Clone, PartialEq)]
-pub struct Operation<Obj: PdfObjectType = Object, Op: AsRef<[u8]> = String> {
+pub struct Operation<Obj: PdfObjectType = Object, Op: AsRef<[u8]> = Operator> {
     pub operation: Op,
     pub arguments: Vec<Obj>,
 }
 impl Operation {
-    pub fn new(operation: impl Into<String>, arguments: Vec<Object>) -> Self {
+    pub fn new(operation: impl Into<Operator>, arguments: Vec<Object>) -> Self {
         Self {
             operation: operation.into(),
             arguments,
         }
     }
 
-    pub fn new_empty(operation: impl Into<String>) -> Self {
+    pub fn new_empty(operation: impl Into<Operator>) -> Self {
         Self {
             operation: operation.into(),
             arguments: Vec::new(),
         }
     }
+    /// A rough estimate of the number of bytes this operation will take up once written.
+    ///
+    /// Used to pre-size buffers. Not an exact size so do not use it for the stream length
+    pub fn estimated_size(&self) -> usize {
+        // Most operands are numbers or names that fit in 8 bytes plus the separator
+        self.operation.as_str().len() + (self.arguments.len() * 9)
+    }
 }
 impl PdfType for Operation {
     fn write<W>(self, writer: &mut W) -> Result<(), crate::LowTuxPdfError>
@@ -52,6 +112,18 @@ impl PdfType for Operation {
     }
 }
 
+/// Writes all the operations into a buffer that was pre-sized using [Operation::estimated_size]
+pub fn write_operations_to_vec(
+    operations: Vec<Operation>,
+) -> Result<Vec<u8>, crate::LowTuxPdfError> {
+    let estimated_size = operations
+        .iter()
+        .map(|operation| operation.estimated_size() + 1)
+        .sum();
+    let mut buffer = Vec::with_capacity(estimated_size);
+    operations.write(&mut buffer)?;
+    Ok(buffer)
+}
 impl PdfType for Vec<Operation> {
     fn write<W>(self, writer: &mut W) -> Result<(), crate::LowTuxPdfError>
     where
@@ -1,32 +1,47 @@
 use std::{collections::BTreeMap, io::Write};
 pub mod xref;
 
-use tracing::trace;
-use xref::{Xref, XrefEntry, XrefType};
+use tracing::{debug_span, field, trace};
+use xref::{Xref, XrefType};
 
 use crate::{
     types::{
-        Dictionary, DictionaryIoWriter, DictionaryType, Object, ObjectId, PdfType, PdfVersion,
-        WritableDictionary,
-        trailer::{PdfTrailer, StandardTrailer},
+        ChunkedStream, DEFAULT_REAL_PRECISION, Dictionary, DictionaryIoWriter, DictionaryType,
+        Object, ObjectId, PdfType, PdfVersion, WritableDictionary,
+        trailer::{IdHasher, PdfTrailer, StandardTrailer},
+        with_real_precision,
     },
-    utils::write::write_object,
+    utils::write::{end_object, start_object, write_object},
 };
 #[derive(Debug)]
 pub struct PdfDocumentWriter {
     pub version: PdfVersion,
     pub trailer: PdfTrailer,
     objects: BTreeMap<ObjectId, Object>,
+    chunked_streams: Vec<PendingChunkedStream>,
     pub cross_reference_type: XrefType,
+    /// Digits written after the decimal point of real numbers. `None` for full precision
+    ///
+    /// Defaults to [DEFAULT_REAL_PRECISION]. See [with_real_precision]
+    pub real_precision: Option<u8>,
     max_id: u32,
 }
+/// A chunked stream and the id reserved for its `/Length` object
+#[derive(Debug)]
+struct PendingChunkedStream {
+    id: ObjectId,
+    length_id: ObjectId,
+    stream: ChunkedStream,
+}
 impl Default for PdfDocumentWriter {
     fn default() -> Self {
         Self {
             version: PdfVersion::default(),
             trailer: PdfTrailer::default(),
             objects: BTreeMap::new(),
-            cross_reference_type: XrefType::CrossReferenceTable,
+            chunked_streams: Vec::new(),
+            cross_reference_type: XrefType::CrossReferenceStream,
+            real_precision: Some(DEFAULT_REAL_PRECISION),
             max_id: 0,
         }
     }
@@ -39,53 +54,146 @@ impl PdfDocumentWriter {
             ..Default::default()
         }
     }
+    /// Saves the document into a new buffer
+    ///
+    /// Useful for targets without a filesystem such as `wasm32-unknown-unknown`
+    pub fn save_to_vec(self) -> Result<Vec<u8>, crate::LowTuxPdfError> {
+        let mut buffer = Vec::new();
+        self.save(&mut buffer)?;
+        Ok(buffer)
+    }
+    /// Writes the document
+    ///
+    /// Runs inside of a `save_document` span with a `write_xref` child span. Both record the bytes written
     pub fn save<W: Write>(self, writer: &mut W) -> Result<(), crate::LowTuxPdfError> {
-        let mut xref = Xref::new(self.max_id + 1, self.cross_reference_type);
-
+        with_real_precision(self.real_precision, || self.write_document(writer))
+    }
+    fn write_document<W: Write>(self, writer: &mut W) -> Result<(), crate::LowTuxPdfError> {
+        let span = debug_span!(
+            "save_document",
+            objects = self.objects.len(),
+            version = %self.version,
+            bytes = field::Empty
+        )
+        .entered();
+        let bytes = self.start_save(writer)?.finish(self)?;
+        span.record("bytes", bytes);
+        Ok(())
+    }
+    /// Writes the header of the document. The objects are written with [IncrementalSave::flush] and [IncrementalSave::finish]
+    ///
+    /// Objects can be flushed while more are being added. So the entire document never has to be in memory
+    pub fn start_save<W: Write>(
+        &self,
+        writer: W,
+    ) -> Result<IncrementalSave<W>, crate::LowTuxPdfError> {
         let mut writer = crate::utils::CountingWriter::new(writer);
+        if self.trailer.needs_hash() {
+            writer.hasher = Some(IdHasher::default());
+        }
         self.version.write(&mut writer)?;
-        for (object_id, object) in self.objects {
-            if object
-                .as_dictionary_or_stream_dictionary()
-                .map(should_skip_dictionary)
-                .unwrap_or_default()
-            {
-                trace!(?object_id, ?object, "Skipping item");
-                continue;
+        Ok(IncrementalSave {
+            writer,
+            xref: Xref::new(0, self.cross_reference_type),
+        })
+    }
+    /// Saves the document into an [AsyncWrite](tokio::io::AsyncWrite)
+    ///
+    /// Each object is encoded into a reused buffer and then written. So the entire document is never held in memory twice.
+    #[cfg(feature = "tokio")]
+    pub async fn save_async<W>(self, writer: &mut W) -> Result<(), crate::LowTuxPdfError>
+    where
+        W: tokio::io::AsyncWrite + Unpin,
+    {
+        use tokio::io::AsyncWriteExt;
+        use tracing::Instrument;
+        let Self {
+            version,
+            mut trailer,
+            objects,
+            chunked_streams,
+            cross_reference_type,
+            real_precision,
+            max_id,
+        } = self;
+        let span = debug_span!(
+            "save_document",
+            objects = objects.len(),
+            %version,
+            bytes = field::Empty
+        );
+        async move {
+            let mut xref = Xref::new(max_id + 1, cross_reference_type);
+            let mut hasher = trailer.needs_hash().then(IdHasher::default);
+            let mut buffer = Vec::new();
+            version.write(&mut buffer)?;
+            if let Some(hasher) = &mut hasher {
+                hasher.update(&buffer);
             }
-            trace!(?object_id, ?object, "Writing object");
-            let offset = writer.count() as u32;
-            xref.insert(
-                object_id.object_number(),
-                XrefEntry::Normal {
-                    offset,
-                    generation: object_id.generation_number,
-                },
-            );
-            write_object(object, object_id, &mut writer)?;
-        }
-        let xref_start = writer.count();
-
-        match self.cross_reference_type {
-            xref::XrefType::CrossReferenceStream => {
-                xref::write_xref_section(&mut writer, &xref)?;
-                let trailer = StandardTrailer {
-                    trailer: self.trailer,
-                    size: self.max_id + 1,
-                };
-                writer.write_all(b"trailer\n")?;
-                let mut dictionary_writer = DictionaryIoWriter::from(&mut writer);
-                dictionary_writer.start_dictionary()?;
-                trailer.write_to_dictionary(&mut dictionary_writer)?;
-                dictionary_writer.end_dictionary()?;
+            writer.write_all(&buffer).await?;
+            let mut count = buffer.len();
+            for (object_id, object) in writable_objects(objects) {
+                xref.insert_offset(object_id, count);
+                buffer.clear();
+                with_real_precision(real_precision, || {
+                    write_object(object, object_id, &mut buffer)
+                })?;
+                if let Some(hasher) = &mut hasher {
+                    hasher.update(&buffer);
+                }
+                writer.write_all(&buffer).await?;
+                count += buffer.len();
             }
-            xref::XrefType::CrossReferenceTable => {
-                xref.write_as_stream(self.trailer, xref_start, self.max_id, &mut writer)?;
+            for PendingChunkedStream {
+                id,
+                length_id,
+                stream,
+            } in chunked_streams
+            {
+                xref.insert_offset(id, count);
+                buffer.clear();
+                start_object(id, false, &mut buffer)?;
+                let mut source = with_real_precision(real_precision, || {
+                    stream.write_start(length_id, &mut buffer)
+                })?;
+                let mut length = 0;
+                loop {
+                    if let Some(hasher) = &mut hasher {
+                        hasher.update(&buffer);
+                    }
+                    writer.write_all(&buffer).await?;
+                    count += buffer.len();
+                    let Some(chunk) = source.next_chunk()? else {
+                        break;
+                    };
+                    length += chunk.len();
+                    buffer = chunk;
+                }
+                buffer.clear();
+                buffer.extend_from_slice(b"\nendstream");
+                end_object(true, &mut buffer)?;
+                xref.insert_offset(length_id, count + buffer.len());
+                write_object(Object::from(length as i64), length_id, &mut buffer)?;
+                if let Some(hasher) = &mut hasher {
+                    hasher.update(&buffer);
+                }
+                writer.write_all(&buffer).await?;
+                count += buffer.len();
+            }
+            buffer.clear();
+            trailer.resolve_id(hasher.as_ref())?;
+            {
+                let xref_span = debug_span!("write_xref", bytes = field::Empty).entered();
+                write_document_end(xref, trailer, max_id, count, &mut buffer)?;
+                xref_span.record("bytes", buffer.len());
             }
+            writer.write_all(&buffer).await?;
+            writer.flush().await?;
+            tracing::Span::current().record("bytes", count + buffer.len());
+            Ok(())
         }
-        write!(writer, "\nstartxref\n{}\n%%EOF", xref_start)?;
-
-        Ok(())
+        .instrument(span)
+        .await
     }
     pub fn next_object_id(&mut self) -> ObjectId {
         self.max_id += 1;
@@ -102,13 +210,42 @@ impl PdfDocumentWriter {
         self.objects.insert(id, object.into());
     }
 
+    /// Adds a stream whose content is written in chunks while saving. Returning its object id.
+    ///
+    /// Another object id is reserved for the `/Length` of the stream
+    pub fn add_chunked_stream(&mut self, stream: ChunkedStream) -> ObjectId {
+        let id = self.next_object_id();
+        let length_id = self.next_object_id();
+        self.chunked_streams.push(PendingChunkedStream {
+            id,
+            length_id,
+            stream,
+        });
+        id
+    }
+
     pub fn get_object(&self, id: &ObjectId) -> Option<&Object> {
         self.objects.get(id)
     }
 
+    pub fn get_object_mut(&mut self, id: &ObjectId) -> Option<&mut Object> {
+        self.objects.get_mut(id)
+    }
+
     pub fn remove_object(&mut self, id: &ObjectId) -> Option<Object> {
         self.objects.remove(id)
     }
+    /// All objects in the document ordered by their object id
+    pub fn objects(&self) -> impl Iterator<Item = (&ObjectId, &Object)> {
+        self.objects.iter()
+    }
+    pub fn objects_mut(&mut self) -> impl Iterator<Item = (&ObjectId, &mut Object)> {
+        self.objects.iter_mut()
+    }
+    /// The highest object number handed out by [Self::next_object_id]
+    pub fn max_object_number(&self) -> u32 {
+        self.max_id
+    }
     /// Find all objects with the given object number.
     pub fn find_object_by_object_number(&self, object_number: u32) -> Vec<&Object> {
         self.objects
@@ -124,6 +261,123 @@ impl PdfDocumentWriter {
     }
 }
 
+/// A document being written. Created with [PdfDocumentWriter::start_save]
+///
+/// The objects of the [PdfDocumentWriter] are written and removed on every [Self::flush].
+/// Only flush once the added objects will not change anymore. Objects can reference ids that are written later
+pub struct IncrementalSave<W> {
+    writer: crate::utils::CountingWriter<W>,
+    xref: Xref,
+}
+impl<W: Write> IncrementalSave<W> {
+    /// Writes the objects and chunked streams added since the last flush. They are removed from the document
+    pub fn flush(&mut self, document: &mut PdfDocumentWriter) -> Result<(), crate::LowTuxPdfError> {
+        let objects = std::mem::take(&mut document.objects);
+        let chunked_streams = std::mem::take(&mut document.chunked_streams);
+        with_real_precision(document.real_precision, || {
+            self.write_objects(objects, chunked_streams)
+        })
+    }
+    fn write_objects(
+        &mut self,
+        objects: BTreeMap<ObjectId, Object>,
+        chunked_streams: Vec<PendingChunkedStream>,
+    ) -> Result<(), crate::LowTuxPdfError> {
+        let Self { writer, xref } = self;
+        for (object_id, object) in writable_objects(objects) {
+            xref.insert_offset(object_id, writer.count());
+            write_object(object, object_id, writer)?;
+        }
+        for PendingChunkedStream {
+            id,
+            length_id,
+            stream,
+        } in chunked_streams
+        {
+            xref.insert_offset(id, writer.count());
+            start_object(id, false, writer)?;
+            let length = stream.write(length_id, writer)?;
+            end_object(true, writer)?;
+
+            xref.insert_offset(length_id, writer.count());
+            write_object(Object::from(length as i64), length_id, writer)?;
+        }
+        Ok(())
+    }
+    /// Writes the remaining objects of the document, the cross reference section and the trailer
+    ///
+    /// Returns the number of bytes written
+    pub fn finish(
+        mut self,
+        mut document: PdfDocumentWriter,
+    ) -> Result<usize, crate::LowTuxPdfError> {
+        self.flush(&mut document)?;
+        let Self {
+            mut writer,
+            mut xref,
+        } = self;
+        let PdfDocumentWriter {
+            mut trailer,
+            max_id,
+            ..
+        } = document;
+        xref.size = max_id + 1;
+        let xref_start = writer.count();
+        trailer.resolve_id(writer.hasher.take().as_ref())?;
+        {
+            let xref_span = debug_span!("write_xref", bytes = field::Empty).entered();
+            write_document_end(xref, trailer, max_id, xref_start, &mut writer)?;
+            xref_span.record("bytes", writer.count() - xref_start);
+        }
+        writer.flush()?;
+        Ok(writer.count())
+    }
+}
+/// Filters out the objects that get regenerated when saving
+fn writable_objects(
+    objects: BTreeMap<ObjectId, Object>,
+) -> impl Iterator<Item = (ObjectId, Object)> {
+    objects.into_iter().filter(|(object_id, object)| {
+        let skip = object
+            .as_dictionary_or_stream_dictionary()
+            .map(should_skip_dictionary)
+            .unwrap_or_default();
+        if skip {
+            trace!(?object_id, ?object, "Skipping item");
+        } else {
+            trace!(?object_id, ?object, "Writing object");
+        }
+        !skip
+    })
+}
+/// Writes the cross reference section, trailer and `startxref`
+fn write_document_end<W: Write>(
+    xref: Xref,
+    trailer: PdfTrailer,
+    max_id: u32,
+    xref_start: usize,
+    writer: &mut W,
+) -> Result<(), crate::LowTuxPdfError> {
+    match xref.cross_reference_type {
+        xref::XrefType::CrossReferenceTable => {
+            xref::write_xref_section(writer, &xref)?;
+            let trailer = StandardTrailer {
+                trailer,
+                size: max_id + 1,
+            };
+            writer.write_all(b"trailer\n")?;
+            let mut dictionary_writer = DictionaryIoWriter::from(&mut *writer);
+            dictionary_writer.start_dictionary()?;
+            trailer.write_to_dictionary(&mut dictionary_writer)?;
+            dictionary_writer.end_dictionary()?;
+        }
+        xref::XrefType::CrossReferenceStream => {
+            xref.write_as_stream(trailer, xref_start, max_id, writer)?;
+        }
+    }
+    write!(writer, "\nstartxref\n{}\n%%EOF", xref_start)?;
+    Ok(())
+}
 fn should_skip_dictionary(dict: &Dictionary) -> bool {
     match dict.dictionary_type() {
         Ok(Some(name)) => matches!(name.as_slice(), b"ObjStm" | b"XRef" | b"Linearized"),
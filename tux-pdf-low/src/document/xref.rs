@@ -88,6 +88,16 @@ impl Xref {
     pub fn insert(&mut self, id: u32, entry: XrefEntry) {
         self.entries.insert(id, entry);
     }
+    /// Inserts a [XrefEntry::Normal] for an object written at `offset`
+    pub fn insert_offset(&mut self, id: ObjectId, offset: usize) {
+        self.insert(
+            id.object_number(),
+            XrefEntry::Normal {
+                offset: offset as u32,
+                generation: id.generation_number,
+            },
+        );
+    }
 
     /// Combine Xref entries. Only add them if they do not exists already.
     /// Do not replace existing entries.
@@ -19,6 +19,8 @@ pub enum LowTuxPdfError {
         actual: &'static str,
         expected: &'static str,
     },
+    #[error("Random number generator failed: {0}")]
+    RandomNumberGenerator(getrandom::Error),
 }
 #[test]
 pub fn test() {}
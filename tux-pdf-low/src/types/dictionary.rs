@@ -40,6 +40,19 @@ impl Dictionary {
     pub fn get(&self, key: impl AsRef<NameRef>) -> Option<&Object> {
         self.0.get(key.as_ref())
     }
+    pub fn get_mut(&mut self, key: impl AsRef<NameRef>) -> Option<&mut Object> {
+        self.0.get_mut(key.as_ref())
+    }
+    /// Removes the entry. Keeps the order of the other entries
+    pub fn remove(&mut self, key: impl AsRef<NameRef>) -> Option<Object> {
+        self.0.shift_remove(key.as_ref())
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (&Name, &Object)> {
+        self.0.iter()
+    }
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&Name, &mut Object)> {
+        self.0.iter_mut()
+    }
     pub fn get_or_err(&self, key: &str) -> Result<&Object, LowTuxPdfError> {
         self.get(key)
             .ok_or_else(|| LowTuxPdfError::MissingDictionaryKey(key.to_string()))
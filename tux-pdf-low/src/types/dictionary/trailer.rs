@@ -1,10 +1,105 @@
-use crate::types::{Name, NameRef, ObjectId};
+use md5::{Digest, Md5};
+
+use crate::{
+    LowTuxPdfError,
+    types::{Name, NameRef, Object, ObjectId, PdfString},
+};
 
 use super::{Dictionary, DictionaryType, WritableDictionary};
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct PdfTrailer {
     pub root: Option<ObjectId>,
     pub info: Option<ObjectId>,
+    /// The `/ID` array. If not set one is created as picked by [Self::id_generation]
+    pub id: Option<DocumentId>,
+    pub id_generation: DocumentIdGeneration,
+}
+/// The two identifiers of the `/ID` array in the trailer
+///
+/// The first identifier is set when the file is created and never changes.
+/// The second one changes every time the file is updated. For a new file both are the same.
+///
+/// Required by PDF/A and PDF 2.0
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DocumentId {
+    pub original: Vec<u8>,
+    pub current: Vec<u8>,
+}
+impl DocumentId {
+    pub fn new(original: impl Into<Vec<u8>>, current: impl Into<Vec<u8>>) -> Self {
+        Self {
+            original: original.into(),
+            current: current.into(),
+        }
+    }
+    /// An id for a new file. Both identifiers are the same
+    pub fn from_bytes(id: impl Into<Vec<u8>>) -> Self {
+        let id = id.into();
+        Self {
+            original: id.clone(),
+            current: id,
+        }
+    }
+    /// An id derived from `data`. The same data always gives the same id
+    pub fn hash_of(data: &[u8]) -> Self {
+        let mut hasher = IdHasher::default();
+        hasher.update(data);
+        hasher.finish()
+    }
+    /// A random id from the random number generator of the OS. Different every time
+    pub fn random() -> Result<Self, LowTuxPdfError> {
+        let mut id = [0u8; 16];
+        getrandom::fill(&mut id).map_err(LowTuxPdfError::RandomNumberGenerator)?;
+        Ok(Self::from_bytes(id))
+    }
+}
+impl From<DocumentId> for Object {
+    fn from(id: DocumentId) -> Self {
+        Object::Array(vec![
+            PdfString::Hexadecimal(id.original).into(),
+            PdfString::Hexadecimal(id.current).into(),
+        ])
+    }
+}
+/// How the `/ID` of the trailer is created if [PdfTrailer::id] is not set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DocumentIdGeneration {
+    /// Hash of the bytes written before the trailer. Writing the same document again gives the same id
+    #[default]
+    Deterministic,
+    /// See [DocumentId::random]
+    Random,
+    /// No `/ID` is written
+    None,
+}
+/// MD5 of the written bytes. The specification suggests MD5 for the `/ID`. Only used to identify files
+#[derive(Debug, Clone, Default)]
+pub(crate) struct IdHasher(Md5);
+impl IdHasher {
+    pub(crate) fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+    pub(crate) fn finish(&self) -> DocumentId {
+        DocumentId::from_bytes(self.0.clone().finalize().to_vec())
+    }
+}
+impl PdfTrailer {
+    /// Whether the bytes of the document need to be hashed to create the id
+    pub(crate) fn needs_hash(&self) -> bool {
+        self.id.is_none() && self.id_generation == DocumentIdGeneration::Deterministic
+    }
+    /// Creates the id if it is not set. `hash` is used for [DocumentIdGeneration::Deterministic]
+    pub(crate) fn resolve_id(&mut self, hash: Option<&IdHasher>) -> Result<(), LowTuxPdfError> {
+        if self.id.is_some() {
+            return Ok(());
+        }
+        self.id = match (self.id_generation, hash) {
+            (DocumentIdGeneration::Deterministic, Some(hash)) => Some(hash.finish()),
+            (DocumentIdGeneration::Random, _) => Some(DocumentId::random()?),
+            _ => None,
+        };
+        Ok(())
+    }
 }
 #[derive(Debug, Clone, PartialEq)]
 pub struct StandardTrailer {
@@ -33,6 +128,9 @@ impl DictionaryType for StandardTrailer {
         if let Some(info) = self.trailer.info {
             dict.write_value(Name::from("Info"), info)?;
         }
+        if let Some(id) = self.trailer.id {
+            dict.write_value(Name::from("ID"), id)?;
+        }
         dict.write_value(Name::from("Size"), self.size)
     }
     fn write_to_dictionary_borrowed<WD>(&self, dict: &mut WD) -> Result<(), crate::LowTuxPdfError>
@@ -81,6 +179,9 @@ impl DictionaryType for XRefPdfTrailer {
         if let Some(info) = self.trailer.info {
             dict.write_value("Info", info)?;
         }
+        if let Some(id) = self.trailer.id {
+            dict.write_value("ID", id)?;
+        }
         dict.write_value("Size", self.size as i64)?;
 
         dict.write_value("W", self.w)?;
@@ -0,0 +1,393 @@
+//! Function objects. See 7.10 of the PDF specification
+//!
+//! Functions map input values to output values. Shadings use them to pick the color at each point
+use crate::dictionary;
+
+use super::{Dictionary, Object, ObjectId, Stream};
+
+/// A function that can be used as the `/Function` of a shading or within a [StitchingFunction]
+///
+/// [SampledFunction]s are streams. Streams can not be written inside of another object.
+/// So add them to the document with [PdfDocumentWriter::add_object](crate::document::PdfDocumentWriter::add_object)
+/// and use the returned id
+#[derive(Debug, Clone, PartialEq)]
+pub enum PdfFunction {
+    Exponential(ExponentialFunction),
+    Stitching(StitchingFunction),
+    /// A function that was already added to the document
+    Reference(ObjectId),
+}
+impl From<ExponentialFunction> for PdfFunction {
+    fn from(function: ExponentialFunction) -> Self {
+        Self::Exponential(function)
+    }
+}
+impl From<StitchingFunction> for PdfFunction {
+    fn from(function: StitchingFunction) -> Self {
+        Self::Stitching(function)
+    }
+}
+impl From<ObjectId> for PdfFunction {
+    fn from(id: ObjectId) -> Self {
+        Self::Reference(id)
+    }
+}
+impl From<PdfFunction> for Object {
+    fn from(function: PdfFunction) -> Self {
+        match function {
+            PdfFunction::Exponential(function) => Object::Dictionary(function.into()),
+            PdfFunction::Stitching(function) => Object::Dictionary(function.into()),
+            PdfFunction::Reference(id) => Object::Reference(id),
+        }
+    }
+}
+fn numbers(values: Vec<f32>) -> Object {
+    Object::Array(values.into_iter().map(Object::Real).collect())
+}
+/// Type 0 function. Output values are looked up in a table of samples
+///
+/// Samples are stored in the order of the first input changing the fastest.
+/// Each sample has one value per output. Every value is `bits_per_sample` bits wide
+///
+/// ```rust
+/// use tux_pdf_low::types::SampledFunction;
+/// // Black to red to white. 3 samples of 3 RGB values
+/// let function = SampledFunction::new(vec![3], vec![0.0, 1.0, 0.0, 1.0, 0.0, 1.0])
+///     .with_samples(vec![0, 0, 0, 255, 0, 0, 255, 255, 255]);
+/// assert_eq!(function.number_of_outputs(), 3);
+/// ```
+///
+/// Sampled functions are streams so they are added to the document and used by their id
+///
+/// ```rust
+/// use tux_pdf_low::{
+///     document::PdfDocumentWriter,
+///     types::{SampledFunction, Shading},
+/// };
+/// let mut writer = PdfDocumentWriter::default();
+/// let function = SampledFunction::new(vec![2], vec![0.0, 1.0, 0.0, 1.0, 0.0, 1.0])
+///     .with_samples(vec![255, 0, 0, 0, 0, 255]);
+/// let function_id = writer.add_object(function);
+/// let shading = Shading::axial([0.0, 0.0], [100.0, 0.0], function_id);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampledFunction {
+    /// Pairs of minimum and maximum for each input. Defaults to 0 to 1 for each input
+    pub domain: Vec<f32>,
+    /// Pairs of minimum and maximum for each output
+    pub range: Vec<f32>,
+    /// The number of samples for each input
+    pub size: Vec<u32>,
+    /// 1, 2, 4, 8, 12, 16, 24 or 32
+    pub bits_per_sample: u8,
+    /// Cubic spline interpolation is used if true. Otherwise linear
+    pub cubic: bool,
+    /// Maps the inputs to the sample table. Defaults to 0 to size - 1 for each input
+    pub encode: Option<Vec<f32>>,
+    /// Maps the samples to the outputs. Defaults to the range
+    pub decode: Option<Vec<f32>>,
+    pub samples: Vec<u8>,
+}
+impl SampledFunction {
+    /// A function with 8 bits per sample and no samples yet
+    pub fn new(size: Vec<u32>, range: Vec<f32>) -> Self {
+        Self {
+            domain: size.iter().flat_map(|_| [0f32, 1f32]).collect(),
+            range,
+            size,
+            bits_per_sample: 8,
+            cubic: false,
+            encode: None,
+            decode: None,
+            samples: Vec::new(),
+        }
+    }
+    pub fn with_domain(mut self, domain: Vec<f32>) -> Self {
+        self.domain = domain;
+        self
+    }
+    pub fn with_bits_per_sample(mut self, bits_per_sample: u8) -> Self {
+        self.bits_per_sample = bits_per_sample;
+        self
+    }
+    pub fn with_cubic_interpolation(mut self) -> Self {
+        self.cubic = true;
+        self
+    }
+    pub fn with_encode(mut self, encode: Vec<f32>) -> Self {
+        self.encode = Some(encode);
+        self
+    }
+    pub fn with_decode(mut self, decode: Vec<f32>) -> Self {
+        self.decode = Some(decode);
+        self
+    }
+    /// The packed sample table
+    pub fn with_samples(mut self, samples: Vec<u8>) -> Self {
+        self.samples = samples;
+        self
+    }
+    pub fn number_of_outputs(&self) -> usize {
+        self.range.len() / 2
+    }
+    /// The number of bytes the samples should take up
+    pub fn expected_sample_bytes(&self) -> usize {
+        let samples: usize = self.size.iter().map(|size| *size as usize).product();
+        let bits = samples * self.number_of_outputs() * self.bits_per_sample as usize;
+        bits.div_ceil(8)
+    }
+}
+impl From<SampledFunction> for Stream {
+    fn from(function: SampledFunction) -> Self {
+        let SampledFunction {
+            domain,
+            range,
+            size,
+            bits_per_sample,
+            cubic,
+            encode,
+            decode,
+            samples,
+        } = function;
+        let mut dictionary = dictionary! {
+            "FunctionType" => 0,
+            "Domain" => numbers(domain),
+            "Range" => numbers(range),
+            "Size" => size,
+            "BitsPerSample" => bits_per_sample
+        };
+        if cubic {
+            dictionary.set("Order", 3);
+        }
+        if let Some(encode) = encode {
+            dictionary.set("Encode", numbers(encode));
+        }
+        if let Some(decode) = decode {
+            dictionary.set("Decode", numbers(decode));
+        }
+        Stream::new(dictionary, samples)
+    }
+}
+impl From<SampledFunction> for Object {
+    fn from(function: SampledFunction) -> Self {
+        Object::Stream(function.into())
+    }
+}
+/// Type 2 function. Interpolates from `c0` to `c1` with `y = c0 + x^exponent * (c1 - c0)`
+///
+/// ```rust
+/// use tux_pdf_low::types::ExponentialFunction;
+/// // Red to blue
+/// let function = ExponentialFunction::linear(vec![1.0, 0.0, 0.0], vec![0.0, 0.0, 1.0]);
+/// assert_eq!(function.exponent, 1.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExponentialFunction {
+    pub domain: [f32; 2],
+    /// The output at 0. Defaults to `[0.0]`
+    pub c0: Vec<f32>,
+    /// The output at 1. Defaults to `[1.0]`
+    pub c1: Vec<f32>,
+    /// The exponent `N`
+    pub exponent: f32,
+    /// Pairs of minimum and maximum for each output. Optional
+    pub range: Option<Vec<f32>>,
+}
+impl Default for ExponentialFunction {
+    fn default() -> Self {
+        Self {
+            domain: [0f32, 1f32],
+            c0: vec![0f32],
+            c1: vec![1f32],
+            exponent: 1f32,
+            range: None,
+        }
+    }
+}
+impl ExponentialFunction {
+    pub fn new(c0: Vec<f32>, c1: Vec<f32>, exponent: f32) -> Self {
+        Self {
+            c0,
+            c1,
+            exponent,
+            ..Default::default()
+        }
+    }
+    /// Evenly blends from `c0` to `c1`
+    pub fn linear(c0: Vec<f32>, c1: Vec<f32>) -> Self {
+        Self::new(c0, c1, 1f32)
+    }
+    pub fn with_domain(mut self, start: f32, end: f32) -> Self {
+        self.domain = [start, end];
+        self
+    }
+    pub fn with_range(mut self, range: Vec<f32>) -> Self {
+        self.range = Some(range);
+        self
+    }
+}
+impl From<ExponentialFunction> for Dictionary {
+    fn from(function: ExponentialFunction) -> Self {
+        let ExponentialFunction {
+            domain,
+            c0,
+            c1,
+            exponent,
+            range,
+        } = function;
+        let mut dictionary = dictionary! {
+            "FunctionType" => 2,
+            "Domain" => domain,
+            "C0" => numbers(c0),
+            "C1" => numbers(c1),
+            "N" => exponent
+        };
+        if let Some(range) = range {
+            dictionary.set("Range", numbers(range));
+        }
+        dictionary
+    }
+}
+/// Type 3 function. Joins functions of one input into one function
+///
+/// The domain is split at the bounds. Each part is handled by one of the functions
+///
+/// ```rust
+/// use tux_pdf_low::types::{ExponentialFunction, StitchingFunction};
+/// // Red to green from 0 to 0.5 then green to blue
+/// let function = StitchingFunction::new()
+///     .with_function(ExponentialFunction::linear(vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0]))
+///     .with_bound(0.5)
+///     .with_function(ExponentialFunction::linear(vec![0.0, 1.0, 0.0], vec![0.0, 0.0, 1.0]));
+/// assert_eq!(function.functions.len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct StitchingFunction {
+    pub domain: [f32; 2],
+    pub functions: Vec<PdfFunction>,
+    /// Where the domain is split. One less than the number of functions. In increasing order
+    pub bounds: Vec<f32>,
+    /// Maps each part of the domain to the domain of its function.
+    /// Defaults to 0 to 1 for every function
+    pub encode: Option<Vec<f32>>,
+    /// Pairs of minimum and maximum for each output. Optional
+    pub range: Option<Vec<f32>>,
+}
+impl Default for StitchingFunction {
+    fn default() -> Self {
+        Self {
+            domain: [0f32, 1f32],
+            functions: Vec::new(),
+            bounds: Vec::new(),
+            encode: None,
+            range: None,
+        }
+    }
+}
+impl StitchingFunction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn with_domain(mut self, start: f32, end: f32) -> Self {
+        self.domain = [start, end];
+        self
+    }
+    pub fn with_function(mut self, function: impl Into<PdfFunction>) -> Self {
+        self.functions.push(function.into());
+        self
+    }
+    pub fn with_bound(mut self, bound: f32) -> Self {
+        self.bounds.push(bound);
+        self
+    }
+    pub fn with_encode(mut self, encode: Vec<f32>) -> Self {
+        self.encode = Some(encode);
+        self
+    }
+    pub fn with_range(mut self, range: Vec<f32>) -> Self {
+        self.range = Some(range);
+        self
+    }
+}
+impl From<StitchingFunction> for Dictionary {
+    fn from(function: StitchingFunction) -> Self {
+        let StitchingFunction {
+            domain,
+            functions,
+            bounds,
+            encode,
+            range,
+        } = function;
+        let encode = encode.unwrap_or_else(|| {
+            functions
+                .iter()
+                .flat_map(|_| [0f32, 1f32])
+                .collect::<Vec<_>>()
+        });
+        let mut dictionary = dictionary! {
+            "FunctionType" => 3,
+            "Domain" => domain,
+            "Functions" => functions,
+            "Bounds" => numbers(bounds),
+            "Encode" => numbers(encode)
+        };
+        if let Some(range) = range {
+            dictionary.set("Range", numbers(range));
+        }
+        dictionary
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PdfObjectType;
+
+    fn encoded(object: impl Into<Object>) -> anyhow::Result<String> {
+        let mut buffer = Vec::new();
+        object.into().encode(&mut buffer)?;
+        Ok(String::from_utf8(buffer)?)
+    }
+    #[test]
+    fn exponential() -> anyhow::Result<()> {
+        let function = ExponentialFunction::new(vec![0.0, 0.0, 0.0], vec![1.0, 0.5, 0.0], 2.0);
+        assert_eq!(
+            encoded(Dictionary::from(function))?,
+            "<</FunctionType 2/Domain[0 1]/C0[0 0 0]/C1[1 0.5 0]/N 2>>"
+        );
+        Ok(())
+    }
+    #[test]
+    fn stitching_defaults_encode() -> anyhow::Result<()> {
+        let function = StitchingFunction::new()
+            .with_function(ExponentialFunction::default())
+            .with_bound(0.25)
+            .with_function(ObjectId::from(4));
+        let dictionary = Dictionary::from(function);
+        assert_eq!(
+            dictionary.get("Encode"),
+            Some(&Object::from(vec![0f32, 1f32, 0f32, 1f32]))
+        );
+        let Some(Object::Array(functions)) = dictionary.get("Functions") else {
+            panic!("Functions is an array");
+        };
+        assert!(matches!(functions[0], Object::Dictionary(_)));
+        assert_eq!(functions[1], Object::Reference(ObjectId::from(4)));
+        Ok(())
+    }
+    #[test]
+    fn sampled_stream() -> anyhow::Result<()> {
+        let function = SampledFunction::new(vec![2], vec![0.0, 1.0])
+            .with_bits_per_sample(4)
+            .with_samples(vec![0x0F]);
+        assert_eq!(function.expected_sample_bytes(), 1);
+        let stream = Stream::from(function);
+        assert_eq!(stream.dictionary.get("Order"), None);
+        assert_eq!(
+            stream.dictionary.get("BitsPerSample"),
+            Some(&Object::from(4))
+        );
+        assert_eq!(stream.content, vec![0x0F]);
+        assert!(encoded(stream)?.starts_with("<</FunctionType 0/Domain[0 1]/Range[0 1]/Size[2]"));
+        Ok(())
+    }
+}
@@ -1,12 +1,18 @@
 use crate::LowTuxPdfError;
 mod dictionary;
+mod function;
 mod generic_object;
 mod object_id;
+mod real;
+mod shading;
 mod stream;
 mod string;
 pub use dictionary::*;
+pub use function::*;
 pub use generic_object::*;
 pub use object_id::*;
+pub use real::*;
+pub use shading::*;
 pub use stream::*;
 pub use string::*;
 
@@ -56,8 +62,29 @@ impl PdfType for Vec<u8> {
         Ok(())
     }
 }
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The version in the header of the file. (major, minor)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct PdfVersion(pub u8, pub u8);
+impl PdfVersion {
+    pub const PDF_1_4: PdfVersion = PdfVersion(1, 4);
+    pub const PDF_1_5: PdfVersion = PdfVersion(1, 5);
+    pub const PDF_1_6: PdfVersion = PdfVersion(1, 6);
+    pub const PDF_1_7: PdfVersion = PdfVersion(1, 7);
+    pub const PDF_2_0: PdfVersion = PdfVersion(2, 0);
+    /// If the version has been published. 1.0 to 1.7 and 2.0
+    pub fn is_published(&self) -> bool {
+        matches!(self, PdfVersion(1, 0..=7) | PdfVersion(2, 0))
+    }
+    /// Cross reference streams were added in PDF 1.5
+    pub fn supports_cross_reference_streams(&self) -> bool {
+        *self >= Self::PDF_1_5
+    }
+}
+impl std::fmt::Display for PdfVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.0, self.1)
+    }
+}
 impl From<(u8, u8)> for PdfVersion {
     fn from((major, minor): (u8, u8)) -> Self {
         Self(major, minor)
@@ -200,11 +227,6 @@ num_object_type! {
         u8, u16, u32, u64
     ]
 }
-num_object_type! {
-    ryu::Buffer => "Real" => [
-        f64, f32
-    ]
-}
 impl<T> PdfObjectType for Vec<T>
 where
     T: PdfObjectType,
@@ -0,0 +1,183 @@
+//! Writing of real numbers
+//!
+//! Reals are written with at most [DEFAULT_REAL_PRECISION] digits after the decimal point.
+//! `f32` values widened to `f64` otherwise show digits such as `12.300000190734863` that only make content streams larger
+//!
+//! The precision is an option of the writer. See [PdfDocumentWriter::real_precision](crate::document::PdfDocumentWriter::real_precision)
+use std::cell::Cell;
+
+use crate::LowTuxPdfError;
+
+use super::{PdfObjectType, copy_encode};
+
+/// Digits written after the decimal point unless the writer is configured otherwise
+pub const DEFAULT_REAL_PRECISION: u8 = 5;
+/// The highest precision. Past it `f32` values only gain noise
+pub const MAX_REAL_PRECISION: u8 = 9;
+/// Stored in [REAL_PRECISION] for full precision
+const FULL_PRECISION: u8 = u8::MAX;
+
+thread_local! {
+    /// The precision of the encoding running on this thread. Only changed by [with_real_precision]
+    static REAL_PRECISION: Cell<u8> = const { Cell::new(DEFAULT_REAL_PRECISION) };
+}
+fn stored_precision(precision: Option<u8>) -> u8 {
+    precision
+        .map(|precision| precision.min(MAX_REAL_PRECISION))
+        .unwrap_or(FULL_PRECISION)
+}
+/// Restores the previous precision. Even if encoding panics
+struct PrecisionGuard(u8);
+impl Drop for PrecisionGuard {
+    fn drop(&mut self) {
+        REAL_PRECISION.set(self.0);
+    }
+}
+/// Encodes every real number inside of `encode` with `precision` digits after the decimal point. Capped at [MAX_REAL_PRECISION]
+///
+/// `None` writes the shortest representation that reads back as the same value.
+/// Writers call this with their own precision. So documents written at the same time do not affect each other
+///
+/// ```rust
+/// use tux_pdf_low::types::{Object, PdfObjectType, with_real_precision};
+/// let mut buffer = Vec::new();
+/// with_real_precision(Some(2), || Object::Real(12.3456).encode(&mut buffer)).unwrap();
+/// assert_eq!(buffer, b"12.35");
+/// ```
+pub fn with_real_precision<R>(precision: Option<u8>, encode: impl FnOnce() -> R) -> R {
+    let _guard = PrecisionGuard(REAL_PRECISION.replace(stored_precision(precision)));
+    encode()
+}
+/// Digits written after the decimal point by the current encoding. `None` for full precision
+pub fn real_precision() -> Option<u8> {
+    match REAL_PRECISION.get() {
+        FULL_PRECISION => None,
+        precision => Some(precision),
+    }
+}
+/// Rounds the value to the number that is written for it with `precision`
+///
+/// Values compared with numbers read back from a written file should be rounded first
+pub fn round_real(value: f32, precision: Option<u8>) -> f32 {
+    match precision {
+        Some(precision) => {
+            let scale = 10f64.powi(precision.min(MAX_REAL_PRECISION) as i32);
+            ((value as f64 * scale).round() / scale) as f32
+        }
+        None => value,
+    }
+}
+/// Writes the value without an exponent and without trailing zeros
+fn write_real<W>(value: f64, precision: u8, writer: &mut W) -> Result<bool, LowTuxPdfError>
+where
+    W: std::io::Write,
+{
+    let scale = 10u64.pow(precision as u32);
+    let scaled = (value * scale as f64).round();
+    // Too large to be scaled into an integer
+    if !scaled.is_finite() || scaled.abs() >= u64::MAX as f64 {
+        return Ok(false);
+    }
+    let scaled_abs = scaled.abs() as u64;
+    if scaled < 0f64 && scaled_abs != 0 {
+        writer.write_all(b"-")?;
+    }
+    let mut buffer = itoa::Buffer::new();
+    writer.write_all(buffer.format(scaled_abs / scale).as_bytes())?;
+    let mut fraction = scaled_abs % scale;
+    if fraction != 0 {
+        let mut digits = precision as usize;
+        while fraction.is_multiple_of(10) {
+            fraction /= 10;
+            digits -= 1;
+        }
+        let fraction = buffer.format(fraction);
+        writer.write_all(b".")?;
+        for _ in fraction.len()..digits {
+            writer.write_all(b"0")?;
+        }
+        writer.write_all(fraction.as_bytes())?;
+    }
+    Ok(true)
+}
+macro_rules! real_object_type {
+    ($($i:ty),*) => {
+        $(
+            impl PdfObjectType for $i {
+                fn encode<W>(self, writer: &mut W) -> Result<(), LowTuxPdfError>
+                where
+                    W: std::io::Write,
+                    Self: Sized,
+                {
+                    if let Some(precision) = real_precision()
+                        && write_real(self as f64, precision, writer)?
+                    {
+                        return Ok(());
+                    }
+                    let mut buffer = ryu::Buffer::new();
+                    writer.write_all(buffer.format(self).as_bytes())?;
+                    Ok(())
+                }
+                copy_encode!();
+
+                fn requires_end_separator(&self) -> bool {
+                    true
+                }
+                fn requires_separator(&self) -> bool {
+                    true
+                }
+
+                fn type_name(&self) -> &'static str {
+                    concat!("Real<", stringify!($i), ">")
+                }
+            }
+        )*
+    };
+}
+real_object_type!(f64, f32);
+
+#[cfg(test)]
+mod tests {
+    use super::{real_precision, round_real, with_real_precision, write_real};
+
+    fn written(value: f64, precision: u8) -> anyhow::Result<String> {
+        let mut buffer = Vec::new();
+        assert!(write_real(value, precision, &mut buffer)?);
+        Ok(String::from_utf8(buffer)?)
+    }
+    #[test]
+    fn precision() -> anyhow::Result<()> {
+        assert_eq!(written(12.3f32 as f64, 5)?, "12.3");
+        assert_eq!(written(10.0, 5)?, "10");
+        assert_eq!(written(0.000_04, 4)?, "0");
+        assert_eq!(written(-0.000_04, 4)?, "0");
+        assert_eq!(written(-1.005_01, 2)?, "-1.01");
+        assert_eq!(written(0.0625, 5)?, "0.0625");
+        assert_eq!(written(1e-5, 5)?, "0.00001");
+        assert_eq!(written(2.5, 0)?, "3");
+        Ok(())
+    }
+    #[test]
+    fn scoped_precision() -> anyhow::Result<()> {
+        use crate::types::{Object, PdfObjectType};
+        let encode = |value: f32| -> anyhow::Result<String> {
+            let mut buffer = Vec::new();
+            Object::Real(value).encode(&mut buffer)?;
+            Ok(String::from_utf8(buffer)?)
+        };
+        assert_eq!(with_real_precision(Some(1), || encode(1.25))?, "1.3");
+        assert_eq!(with_real_precision(None, || encode(0.1))?, "0.1");
+        // Restored once the encoding is done
+        assert_eq!(real_precision(), Some(super::DEFAULT_REAL_PRECISION));
+        assert_eq!(encode(1.25)?, "1.25");
+        assert_eq!(round_real(1.25, Some(1)), 1.3);
+        Ok(())
+    }
+    #[test]
+    fn too_large() -> anyhow::Result<()> {
+        let mut buffer = Vec::new();
+        assert!(!write_real(f64::INFINITY, 5, &mut buffer)?);
+        assert!(buffer.is_empty());
+        Ok(())
+    }
+}
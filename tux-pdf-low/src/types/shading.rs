@@ -0,0 +1,194 @@
+//! Shading dictionaries. See 8.7.4.5 of the PDF specification
+use crate::{content::Operation, dictionary};
+
+use super::{Dictionary, Name, Object, PdfFunction};
+
+/// The geometry of a [Shading]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShadingGeometry {
+    /// Type 2. Blends along the line from `start` to `end`
+    Axial { start: [f32; 2], end: [f32; 2] },
+    /// Type 3. Blends between two circles. Each circle is `[x, y, radius]`
+    Radial { start: [f32; 3], end: [f32; 3] },
+}
+impl ShadingGeometry {
+    pub fn shading_type(&self) -> i64 {
+        match self {
+            ShadingGeometry::Axial { .. } => 2,
+            ShadingGeometry::Radial { .. } => 3,
+        }
+    }
+    fn coords(&self) -> Vec<f32> {
+        match self {
+            ShadingGeometry::Axial { start, end } => vec![start[0], start[1], end[0], end[1]],
+            ShadingGeometry::Radial { start, end } => start.iter().chain(end).copied().collect(),
+        }
+    }
+}
+/// An axial or radial shading
+///
+/// Add it to the `/Shading` entry of the resources then paint it with [Operation::paint_shading]
+///
+/// ```rust
+/// use tux_pdf_low::{
+///     content::Operation,
+///     dictionary,
+///     document::PdfDocumentWriter,
+///     types::{ExponentialFunction, Shading},
+/// };
+/// let mut writer = PdfDocumentWriter::default();
+/// let shading = Shading::axial(
+///     [0.0, 0.0],
+///     [200.0, 0.0],
+///     ExponentialFunction::linear(vec![1.0, 0.0, 0.0], vec![0.0, 0.0, 1.0]),
+/// )
+/// .with_extend(true, true);
+/// let shading_id = writer.add_object(shading);
+/// let resources = dictionary! {
+///     "Shading" => dictionary! { "Sh1" => shading_id }
+/// };
+/// let content = vec![Operation::paint_shading("Sh1")];
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shading {
+    pub geometry: ShadingGeometry,
+    /// Defaults to `DeviceRGB`
+    pub color_space: Object,
+    /// Called with the position along the shading. Returns the color components
+    pub function: PdfFunction,
+    /// The inputs of the function at the start and the end. Defaults to 0 to 1
+    pub domain: Option<[f32; 2]>,
+    /// Whether the shading continues past the start and the end
+    pub extend: [bool; 2],
+    /// Color of the area outside of the shading. Ignored by the `sh` operator
+    pub background: Option<Vec<f32>>,
+    /// `[left, bottom, right, top]` the shading is clipped to
+    pub bbox: Option<[f32; 4]>,
+    pub anti_alias: bool,
+}
+impl Shading {
+    pub fn new(geometry: ShadingGeometry, function: impl Into<PdfFunction>) -> Self {
+        Self {
+            geometry,
+            color_space: Object::name("DeviceRGB"),
+            function: function.into(),
+            domain: None,
+            extend: [false, false],
+            background: None,
+            bbox: None,
+            anti_alias: false,
+        }
+    }
+    pub fn axial(start: [f32; 2], end: [f32; 2], function: impl Into<PdfFunction>) -> Self {
+        Self::new(ShadingGeometry::Axial { start, end }, function)
+    }
+    pub fn radial(start: [f32; 3], end: [f32; 3], function: impl Into<PdfFunction>) -> Self {
+        Self::new(ShadingGeometry::Radial { start, end }, function)
+    }
+    /// A name such as `DeviceGray` or a reference to a color space
+    pub fn with_color_space(mut self, color_space: impl Into<Object>) -> Self {
+        self.color_space = color_space.into();
+        self
+    }
+    pub fn with_domain(mut self, start: f32, end: f32) -> Self {
+        self.domain = Some([start, end]);
+        self
+    }
+    pub fn with_extend(mut self, start: bool, end: bool) -> Self {
+        self.extend = [start, end];
+        self
+    }
+    pub fn with_background(mut self, background: Vec<f32>) -> Self {
+        self.background = Some(background);
+        self
+    }
+    pub fn with_bbox(mut self, bbox: [f32; 4]) -> Self {
+        self.bbox = Some(bbox);
+        self
+    }
+    pub fn with_anti_alias(mut self, anti_alias: bool) -> Self {
+        self.anti_alias = anti_alias;
+        self
+    }
+}
+impl From<Shading> for Dictionary {
+    fn from(shading: Shading) -> Self {
+        let Shading {
+            geometry,
+            color_space,
+            function,
+            domain,
+            extend,
+            background,
+            bbox,
+            anti_alias,
+        } = shading;
+        let mut dictionary = dictionary! {
+            "ShadingType" => geometry.shading_type(),
+            "ColorSpace" => color_space,
+            "Coords" => geometry.coords(),
+            "Function" => function
+        };
+        if let Some(domain) = domain {
+            dictionary.set("Domain", domain);
+        }
+        if extend != [false, false] {
+            dictionary.set("Extend", extend);
+        }
+        if let Some(background) = background {
+            dictionary.set("Background", background);
+        }
+        if let Some(bbox) = bbox {
+            dictionary.set("BBox", bbox);
+        }
+        if anti_alias {
+            dictionary.set("AntiAlias", true);
+        }
+        dictionary
+    }
+}
+impl From<Shading> for Object {
+    fn from(shading: Shading) -> Self {
+        Object::Dictionary(shading.into())
+    }
+}
+impl Operation {
+    /// `sh` Paints the shading named `name` in the `/Shading` resources over the current clipping area
+    pub fn paint_shading(name: impl Into<Name>) -> Self {
+        Operation::new("sh", vec![Object::Name(name.into())])
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ExponentialFunction, PdfType};
+
+    #[test]
+    fn radial_dictionary() {
+        let shading = Shading::radial(
+            [50.0, 50.0, 0.0],
+            [50.0, 50.0, 25.0],
+            ExponentialFunction::default(),
+        )
+        .with_color_space(Object::name("DeviceGray"))
+        .with_extend(false, true);
+        let dictionary = Dictionary::from(shading);
+        assert_eq!(dictionary.get("ShadingType"), Some(&Object::Integer(3)));
+        assert_eq!(
+            dictionary.get("Coords"),
+            Some(&Object::from(vec![50f32, 50f32, 0f32, 50f32, 50f32, 25f32]))
+        );
+        assert_eq!(dictionary.get("Extend"), Some(&Object::from([false, true])));
+        assert!(matches!(
+            dictionary.get("Function"),
+            Some(Object::Dictionary(_))
+        ));
+        assert_eq!(dictionary.get("Domain"), None);
+    }
+    #[test]
+    fn sh_operator() -> anyhow::Result<()> {
+        let operation = Operation::paint_shading("Sh1");
+        assert_eq!(operation.write_to_vec()?, b"/Sh1 sh");
+        Ok(())
+    }
+}
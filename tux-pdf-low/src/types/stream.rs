@@ -1,8 +1,10 @@
-use crate::LowTuxPdfError;
+use std::{borrow::Cow, fmt::Debug, io::Write};
+
+use crate::{LowTuxPdfError, utils::CountingWriter};
 
 use super::{
-    DictionaryIoWriter, DictionaryType, PdfObjectType, PdfType, WritableDictionary,
-    dictionary::Dictionary,
+    DictionaryIoWriter, DictionaryType, Name, Object, ObjectId, PdfObjectType, PdfType,
+    WritableDictionary, dictionary::Dictionary,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -33,6 +35,47 @@ impl<D, Content: PdfType> Stream<D, Content> {
         }
     }
 }
+impl Stream {
+    /// The content with its filters removed. `None` if a filter can not be removed
+    ///
+    /// Only `FlateDecode` without decode parameters is removed. It requires the flate2 feature
+    pub fn decoded_content(&self) -> Option<Cow<'_, [u8]>> {
+        let filters = match self.dictionary.get("Filter") {
+            None => return Some(Cow::Borrowed(&self.content)),
+            Some(Object::Name(name)) => vec![name],
+            Some(Object::Array(names)) => names
+                .iter()
+                .map(Object::as_name)
+                .collect::<Option<Vec<_>>>()?,
+            Some(_) => return None,
+        };
+        // Predictors are not supported
+        if self.dictionary.get("DecodeParms").is_some() {
+            return None;
+        }
+        let mut content = Cow::Borrowed(self.content.as_slice());
+        for filter in filters {
+            content = Cow::Owned(decode_filter(filter, &content)?);
+        }
+        Some(content)
+    }
+}
+#[cfg(feature = "flate2")]
+fn decode_filter(filter: &Name, content: &[u8]) -> Option<Vec<u8>> {
+    use std::io::Read;
+    if filter.as_slice() != b"FlateDecode" {
+        return None;
+    }
+    let mut decoded = Vec::new();
+    flate2::read::ZlibDecoder::new(content)
+        .read_to_end(&mut decoded)
+        .ok()?;
+    Some(decoded)
+}
+#[cfg(not(feature = "flate2"))]
+fn decode_filter(_: &Name, _: &[u8]) -> Option<Vec<u8>> {
+    None
+}
 impl<D: DictionaryType, Content: PdfType> From<Stream<D, Content>> for Stream<Dictionary, Content> {
     fn from(stream: Stream<D, Content>) -> Self {
         Stream {
@@ -156,3 +199,72 @@ where
         false
     }
 }
+/// Content of a [ChunkedStream]. Produced one chunk at a time while the document is saved
+///
+/// Implemented for any iterator of byte chunks
+pub trait StreamSource: Send {
+    /// The next chunk of the content. None once all the content has been produced
+    fn next_chunk(&mut self) -> Result<Option<Vec<u8>>, LowTuxPdfError>;
+}
+impl<I> StreamSource for I
+where
+    I: Iterator<Item = Vec<u8>> + Send,
+{
+    fn next_chunk(&mut self) -> Result<Option<Vec<u8>>, LowTuxPdfError> {
+        Ok(self.next())
+    }
+}
+/// A stream whose content is never held in memory at once
+///
+/// The chunks are written directly to the output. The `/Length` is an indirect object written after the stream.
+/// Add it with [PdfDocumentWriter::add_chunked_stream](crate::document::PdfDocumentWriter::add_chunked_stream)
+pub struct ChunkedStream {
+    /// The dictionary of the stream. `Length` is set when written
+    pub dictionary: Dictionary,
+    source: Box<dyn StreamSource>,
+}
+impl ChunkedStream {
+    pub fn new(dictionary: Dictionary, source: impl StreamSource + 'static) -> Self {
+        Self {
+            dictionary,
+            source: Box::new(source),
+        }
+    }
+    /// Writes the dictionary with `/Length` referencing `length_id`. Returns the source to write the content with
+    pub(crate) fn write_start<W: Write>(
+        self,
+        length_id: ObjectId,
+        writer: &mut W,
+    ) -> Result<Box<dyn StreamSource>, LowTuxPdfError> {
+        let Self {
+            mut dictionary,
+            source,
+        } = self;
+        dictionary.set("Length", length_id);
+        dictionary.encode(writer)?;
+        writer.write_all(b"\nstream\n")?;
+        Ok(source)
+    }
+    /// Writes the stream. Returns the length of the content
+    pub fn write<W: Write>(
+        self,
+        length_id: ObjectId,
+        writer: &mut W,
+    ) -> Result<usize, LowTuxPdfError> {
+        let mut source = self.write_start(length_id, writer)?;
+        let mut content_writer = CountingWriter::new(&mut *writer);
+        while let Some(chunk) = source.next_chunk()? {
+            content_writer.write_all(&chunk)?;
+        }
+        let length = content_writer.count();
+        writer.write_all(b"\nendstream")?;
+        Ok(length)
+    }
+}
+impl Debug for ChunkedStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChunkedStream")
+            .field("dictionary", &self.dictionary)
+            .finish_non_exhaustive()
+    }
+}
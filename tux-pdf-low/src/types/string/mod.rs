@@ -68,6 +68,11 @@ impl Deref for Name {
         NameRef::new(&self.0)
     }
 }
+impl AsRef<NameRef> for Name {
+    fn as_ref(&self) -> &NameRef {
+        NameRef::new(&self.0)
+    }
+}
 impl Borrow<NameRef> for Name {
     fn borrow(&self) -> &NameRef {
         NameRef::new(&self.0)
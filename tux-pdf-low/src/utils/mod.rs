@@ -1,13 +1,21 @@
 use std::io::Write;
+
+use crate::types::trailer::IdHasher;
 pub mod write;
 pub struct CountingWriter<W> {
     writer: W,
     count: usize,
+    /// Hashes the written bytes if set. Used for the `/ID` of the trailer
+    pub(crate) hasher: Option<IdHasher>,
 }
 
 impl<W> CountingWriter<W> {
     pub fn new(writer: W) -> Self {
-        CountingWriter { writer, count: 0 }
+        CountingWriter {
+            writer,
+            count: 0,
+            hasher: None,
+        }
     }
 
     pub fn into_inner(self) -> W {
@@ -26,6 +34,9 @@ where
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
         let count = self.writer.write(buf)?;
         self.count += count;
+        if let Some(hasher) = &mut self.hasher {
+            hasher.update(&buf[..count]);
+        }
         Ok(count)
     }
 
@@ -4,12 +4,13 @@ use test_utils::{destination_dir, init_logger};
 use tux_pdf_low::{
     content::Operation,
     document::PdfDocumentWriter,
-    types::{Dictionary, Name, PdfString, PdfType, Stream},
+    types::{
+        ChunkedStream, Dictionary, Name, PdfString, PdfType, Stream,
+        trailer::{DocumentId, DocumentIdGeneration},
+    },
 };
 mod test_utils;
-#[test]
-pub fn hello_world() -> anyhow::Result<()> {
-    init_logger();
+fn hello_world_document() -> anyhow::Result<PdfDocumentWriter> {
     let mut doc = PdfDocumentWriter::default();
 
     let pages_id = doc.next_object_id();
@@ -55,9 +56,114 @@ pub fn hello_world() -> anyhow::Result<()> {
     catalog.set("Pages", pages_id);
     let catalog_id = doc.add_object(catalog);
     doc.trailer.root = Some(catalog_id);
+    Ok(doc)
+}
+#[test]
+pub fn hello_world() -> anyhow::Result<()> {
+    init_logger();
+    let doc = hello_world_document()?;
 
     let mut target = File::create(destination_dir().join("hello_world.pdf"))?;
 
     doc.save(&mut target)?;
     Ok(())
 }
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+pub async fn save_async_matches_save() -> anyhow::Result<()> {
+    init_logger();
+    let expected = hello_world_document()?.save_to_vec()?;
+
+    let mut actual = Vec::new();
+    hello_world_document()?.save_async(&mut actual).await?;
+
+    assert_eq!(expected, actual);
+    Ok(())
+}
+fn add_chunked_stream(doc: &mut PdfDocumentWriter) {
+    let chunks = (0..100).map(|index| format!("{index} 0 0 RG\n").into_bytes());
+    doc.add_chunked_stream(ChunkedStream::new(Dictionary::new(), chunks));
+}
+fn chunked_document() -> anyhow::Result<PdfDocumentWriter> {
+    let mut doc = hello_world_document()?;
+    add_chunked_stream(&mut doc);
+    Ok(doc)
+}
+#[test]
+pub fn chunked_stream() -> anyhow::Result<()> {
+    init_logger();
+    let doc = chunked_document()?;
+    let length_id = doc.max_object_number();
+    let pdf = doc.save_to_vec()?;
+    let expected_length: usize = (0..100)
+        .map(|index| format!("{index} 0 0 RG\n").len())
+        .sum();
+
+    let contains = |needle: &[u8]| pdf.windows(needle.len()).any(|w| w == needle);
+    assert!(contains(format!("/Length {length_id} 0 R").as_bytes()));
+    assert!(contains(
+        format!("{length_id} 0 obj\n {expected_length} \nendobj").as_bytes()
+    ));
+    assert!(contains(b"stream\n0 0 0 RG\n1 0 0 RG\n"));
+    assert!(contains(b"99 0 0 RG\n\nendstream"));
+    Ok(())
+}
+#[test]
+pub fn incremental_save_matches_save() -> anyhow::Result<()> {
+    init_logger();
+    let expected = chunked_document()?.save_to_vec()?;
+
+    let mut doc = hello_world_document()?;
+    let mut actual = Vec::new();
+    let mut save = doc.start_save(&mut actual)?;
+    save.flush(&mut doc)?;
+    // Flushed objects are released
+    assert_eq!(doc.objects().count(), 0);
+    add_chunked_stream(&mut doc);
+    let bytes = save.finish(doc)?;
+
+    assert_eq!(bytes, actual.len());
+    assert_eq!(expected, actual);
+    Ok(())
+}
+#[cfg(feature = "tokio")]
+#[tokio::test]
+pub async fn chunked_stream_async_matches_save() -> anyhow::Result<()> {
+    let expected = chunked_document()?.save_to_vec()?;
+
+    let mut actual = Vec::new();
+    chunked_document()?.save_async(&mut actual).await?;
+
+    assert_eq!(expected, actual);
+    Ok(())
+}
+#[test]
+pub fn trailer_id() -> anyhow::Result<()> {
+    let contains = |pdf: &[u8], needle: &[u8]| pdf.windows(needle.len()).any(|w| w == needle);
+
+    // The same document gets the same id
+    let first = hello_world_document()?.save_to_vec()?;
+    let second = hello_world_document()?.save_to_vec()?;
+    assert!(contains(&first, b"/ID"));
+    assert_eq!(first, second);
+
+    let mut doc = hello_world_document()?;
+    doc.trailer.id = Some(DocumentId::from_bytes(*b"tux"));
+    let pdf = doc.save_to_vec()?;
+    assert!(contains(&pdf, b"/ID[<747578><747578>]"));
+
+    let mut doc = hello_world_document()?;
+    doc.trailer.id_generation = DocumentIdGeneration::None;
+    assert!(!contains(&doc.save_to_vec()?, b"/ID"));
+
+    // MD5 of the empty string
+    assert_eq!(
+        DocumentId::hash_of(b""),
+        DocumentId::from_bytes(
+            *b"\xd4\x1d\x8c\xd9\x8f\x00\xb2\x04\xe9\x80\x09\x98\xec\xf8\x42\x7e"
+        )
+    );
+    assert_ne!(DocumentId::random()?, DocumentId::random()?);
+    Ok(())
+}